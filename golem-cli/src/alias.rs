@@ -0,0 +1,211 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::{GolemCliCommand, GolemCliFallbackCommand};
+use crate::config::{Config, ConfigMigrationMode};
+use anyhow::{anyhow, bail};
+use clap::CommandFactory;
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsString;
+use tracing::debug;
+
+/// Expands the user defined alias used as the subcommand, before parsing the command line.
+///
+/// Built-in subcommands always take precedence, and aliases are not expanded recursively.
+/// Config problems are ignored here, as they are reported when initializing the context.
+pub fn expand_aliases(args: Vec<OsString>) -> Vec<OsString> {
+    let config_dir = GolemCliFallbackCommand::try_parse_from(&args, true)
+        .global_flags
+        .config_dir();
+
+    let aliases = match Config::load(&config_dir, ConfigMigrationMode::InMemory) {
+        Ok((config, _)) => config.aliases,
+        Err(error) => {
+            debug!(error = ?error, "Failed to load config for expanding aliases");
+            return args;
+        }
+    };
+
+    match expand_alias(&args, &aliases) {
+        Ok(Some(expanded)) => {
+            debug!(args = ?expanded, "Expanded alias");
+            expanded
+        }
+        Ok(None) => args,
+        Err(error) => {
+            debug!(error = ?error, "Failed to expand alias");
+            args
+        }
+    }
+}
+
+/// Returns the expanded args if the subcommand is a user defined alias
+pub fn expand_alias(
+    args: &[OsString],
+    aliases: &BTreeMap<String, String>,
+) -> anyhow::Result<Option<Vec<OsString>>> {
+    if aliases.is_empty() {
+        return Ok(None);
+    }
+
+    let Some(index) = subcommand_index(args) else {
+        return Ok(None);
+    };
+    let Some(command) = args[index].to_str().and_then(|name| aliases.get(name)) else {
+        return Ok(None);
+    };
+    if is_builtin_subcommand(&args[index].to_string_lossy()) {
+        return Ok(None);
+    }
+
+    let alias_args = shlex::split(command)
+        .ok_or_else(|| anyhow!("Invalid alias command: {}", command))?
+        .into_iter()
+        .map(OsString::from);
+
+    Ok(Some(
+        args[..index]
+            .iter()
+            .cloned()
+            .chain(alias_args)
+            .chain(args[index + 1..].iter().cloned())
+            .collect(),
+    ))
+}
+
+pub fn validate_alias_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty()
+        || name.starts_with('-')
+        || name.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        bail!(
+            "Invalid alias name: {name}, alias names cannot start with '-' or contain whitespace"
+        );
+    }
+    if is_builtin_subcommand(name) {
+        bail!("Invalid alias name: {name}, it is a built-in command");
+    }
+    Ok(())
+}
+
+fn is_builtin_subcommand(name: &str) -> bool {
+    GolemCliCommand::command()
+        .get_subcommands()
+        .any(|subcommand| {
+            subcommand.get_name() == name || subcommand.get_all_aliases().any(|a| a == name)
+        })
+        || name == "help"
+}
+
+/// Index of the first positional argument, skipping the global flags and their values
+fn subcommand_index(args: &[OsString]) -> Option<usize> {
+    let command = GolemCliCommand::command();
+    let value_flags = command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && arg.get_action().takes_values())
+        .flat_map(|arg| {
+            arg.get_long()
+                .map(|long| format!("--{}", long))
+                .into_iter()
+                .chain(arg.get_short().map(|short| format!("-{}", short)))
+        })
+        .collect::<HashSet<_>>();
+
+    let mut index = 1;
+    while index < args.len() {
+        let arg = args[index].to_string_lossy();
+        if arg == "--" {
+            return None;
+        } else if arg.starts_with('-') && arg.len() > 1 {
+            if value_flags.contains(arg.as_ref()) {
+                index += 1;
+            }
+        } else {
+            return Some(index);
+        }
+        index += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::alias::{expand_alias, validate_alias_name};
+    use assert2::{check, let_assert};
+    use std::collections::BTreeMap;
+    use std::ffi::OsString;
+    use test_r::test;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn aliases() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            (
+                "deploy-prod".to_string(),
+                "--profile prod app deploy".to_string(),
+            ),
+            ("app".to_string(), "component list".to_string()),
+        ])
+    }
+
+    #[test]
+    fn expands_alias() {
+        let_assert!(
+            Ok(Some(expanded)) = expand_alias(&args(&["golem", "deploy-prod", "-y"]), &aliases())
+        );
+        check!(expanded == args(&["golem", "--profile", "prod", "app", "deploy", "-y"]));
+    }
+
+    #[test]
+    fn expands_alias_after_global_flags() {
+        let_assert!(
+            Ok(Some(expanded)) = expand_alias(
+                &args(&["golem", "--format", "json", "-v", "deploy-prod"]),
+                &aliases()
+            )
+        );
+        check!(
+            expanded
+                == args(&[
+                    "golem",
+                    "--format",
+                    "json",
+                    "-v",
+                    "--profile",
+                    "prod",
+                    "app",
+                    "deploy"
+                ])
+        );
+    }
+
+    #[test]
+    fn builtin_commands_are_not_expanded() {
+        let_assert!(Ok(None) = expand_alias(&args(&["golem", "app", "build"]), &aliases()));
+        let_assert!(
+            Ok(None) = expand_alias(&args(&["golem", "--profile", "deploy-prod"]), &aliases())
+        );
+    }
+
+    #[test]
+    fn alias_names() {
+        check!(validate_alias_name("deploy-prod").is_ok());
+        check!(validate_alias_name("worker").is_err());
+        check!(validate_alias_name("--prod").is_err());
+        check!(validate_alias_name("deploy prod").is_err());
+    }
+}
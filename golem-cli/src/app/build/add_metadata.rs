@@ -25,6 +25,8 @@ pub async fn add_metadata_to_selected_components(
     let _indent = LogIndent::new();
 
     for component_name in ctx.selected_component_names() {
+        ctx.config.cancellation_token.check()?;
+
         let linked_wasm = ctx.application.component_linked_wasm(component_name);
         let final_linked_wasm = ctx
             .application
@@ -52,6 +54,11 @@ pub async fn add_metadata_to_selected_components(
             continue;
         }
 
+        let final_linked_wasm_in_progress = ctx
+            .config
+            .cancellation_token
+            .track_in_progress_path(&final_linked_wasm);
+
         task_result_marker.result(
             async {
                 log_action(
@@ -65,6 +72,8 @@ pub async fn add_metadata_to_selected_components(
             }
             .await,
         )?;
+
+        final_linked_wasm_in_progress.done();
     }
 
     Ok(())
@@ -14,56 +14,194 @@
 
 use crate::app::build::external_command::execute_external_command;
 use crate::app::context::ApplicationContext;
-use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::log::{
+    log_action, log_thread_prefix, log_warn_action, LogColorize, LogIndent, LogThreadPrefix,
+};
 use crate::model::app::{AppComponentName, DependencyType};
 use crate::wasm_rpc_stubgen::wit_resolve::ExportedFunction;
 use anyhow::{anyhow, Context};
 use heck::ToLowerCamelCase;
+use itertools::Itertools;
+use std::any::Any;
 use std::collections::{BTreeSet, HashMap};
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc;
 
 pub fn componentize(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
     log_action("Building", "components");
     let _indent = LogIndent::new();
 
     let components_to_build = components_to_build(ctx);
+
+    let jobs = ctx.config.build_jobs;
+    if jobs > 1 && components_to_build.len() > 1 {
+        return build_components_concurrently(ctx, components_to_build, jobs);
+    }
+
     for component_name in components_to_build {
-        let component_properties = ctx
-            .application
-            .component_properties(&component_name, ctx.profile());
-
-        if component_properties.build.is_empty() {
-            log_warn_action(
-                "Skipping",
-                format!(
-                    "building {}, no build steps",
-                    component_name.as_str().log_color_highlight(),
-                ),
-            );
-            continue;
-        }
+        ctx.config.cancellation_token.check()?;
+        build_component(ctx, &component_name)?;
+    }
 
-        log_action(
-            "Building",
-            format!("{}", component_name.as_str().log_color_highlight()),
+    Ok(())
+}
+
+fn build_component(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+) -> anyhow::Result<()> {
+    let component_properties = ctx
+        .application
+        .component_properties(component_name, ctx.profile());
+
+    if component_properties.build.is_empty() {
+        log_warn_action(
+            "Skipping",
+            format!(
+                "building {}, no build steps",
+                component_name.as_str().log_color_highlight(),
+            ),
         );
-        let _indent = LogIndent::new();
-
-        let env_vars = build_step_env_vars(ctx, &component_name)
-            .context("Failed to get env vars for build step")?;
-
-        for build_step in &component_properties.build {
-            execute_external_command(
-                ctx,
-                ctx.application.component_source_dir(&component_name),
-                build_step,
-                env_vars.clone(),
-            )?;
+        return Ok(());
+    }
+
+    log_action(
+        "Building",
+        format!("{}", component_name.as_str().log_color_highlight()),
+    );
+    // Concurrent builds are told apart by their log prefix, indents are shared between threads
+    let _indent = log_thread_prefix().is_none().then(LogIndent::new);
+
+    let env_vars = build_step_env_vars(ctx, component_name)
+        .context("Failed to get env vars for build step")?;
+
+    for build_step in &component_properties.build {
+        ctx.config.cancellation_token.check()?;
+        execute_external_command(
+            ctx,
+            ctx.application.component_source_dir(component_name),
+            build_step,
+            env_vars.clone(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Builds up to `jobs` components at the same time, a component is only started once all of
+/// its WASM dependencies are built. After a failure no new builds are started.
+fn build_components_concurrently(
+    ctx: &ApplicationContext,
+    components: BTreeSet<AppComponentName>,
+    jobs: usize,
+) -> anyhow::Result<()> {
+    let wasm_dependencies = |component_name: &AppComponentName| {
+        ctx.application
+            .component_dependencies(component_name)
+            .iter()
+            .filter(|dep| dep.dep_type == DependencyType::Wasm)
+            .map(|dep| dep.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut pending = components;
+    let mut built = BTreeSet::new();
+    let mut running = 0;
+    let mut first_error = None;
+    let (result_tx, result_rx) = mpsc::channel();
+
+    std::thread::scope(|scope| loop {
+        while first_error.is_none()
+            && running < jobs
+            && !ctx.config.cancellation_token.is_cancelled()
+        {
+            let Some(component_name) =
+                next_buildable_component(&pending, &built, &wasm_dependencies)
+            else {
+                break;
+            };
+            pending.remove(&component_name);
+            running += 1;
+
+            let result_tx = result_tx.clone();
+            scope.spawn(move || {
+                let _prefix = LogThreadPrefix::new(format!("[{}] ", component_name.as_str()));
+                // Panics are also reported as a result, otherwise receiving would block forever
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    build_component(ctx, &component_name)
+                }))
+                .unwrap_or_else(|panic| {
+                    Err(anyhow!("Build panicked: {}", panic_message(panic.as_ref())))
+                });
+                let _ = result_tx.send((component_name, result));
+            });
         }
+
+        if running == 0 {
+            break;
+        }
+
+        let (component_name, result) = result_rx
+            .recv()
+            .expect("Component build result channel closed");
+        running -= 1;
+        match result {
+            Ok(()) => {
+                built.insert(component_name);
+            }
+            Err(err) => {
+                if first_error.is_none() {
+                    first_error =
+                        Some(err.context(format!("Failed to build component {}", component_name)));
+                }
+            }
+        }
+    });
+
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    ctx.config.cancellation_token.check()?;
+    if !pending.is_empty() {
+        return Err(anyhow!(
+            "Cannot build components with circular WASM dependencies: {}",
+            pending.iter().map(|c| c.as_str()).join(", ")
+        ));
     }
 
     Ok(())
 }
 
+pub(super) fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| {
+            panic
+                .downcast_ref::<String>()
+                .map(|message| message.as_str())
+        })
+        .unwrap_or("unknown panic")
+}
+
+fn next_buildable_component<F>(
+    pending: &BTreeSet<AppComponentName>,
+    built: &BTreeSet<AppComponentName>,
+    dependencies: F,
+) -> Option<AppComponentName>
+where
+    F: Fn(&AppComponentName) -> Vec<AppComponentName>,
+{
+    pending
+        .iter()
+        .find(|component_name| {
+            dependencies(component_name)
+                .iter()
+                .all(|dep| built.contains(dep))
+        })
+        .cloned()
+}
+
 fn components_to_build(ctx: &ApplicationContext) -> BTreeSet<AppComponentName> {
     let mut components_to_build = BTreeSet::new();
     let mut remaining: Vec<_> = ctx.selected_component_names().iter().cloned().collect();
@@ -139,3 +277,48 @@ fn jco_async_export_args(
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::componentize::{next_buildable_component, panic_message};
+    use crate::model::app::AppComponentName;
+    use assert2::check;
+    use std::collections::BTreeSet;
+    use test_r::test;
+
+    fn names(names: &[&str]) -> BTreeSet<AppComponentName> {
+        names
+            .iter()
+            .map(|name| AppComponentName::from(*name))
+            .collect()
+    }
+
+    #[test]
+    fn components_are_built_after_their_dependencies() {
+        let dependencies = |component_name: &AppComponentName| match component_name.as_str() {
+            "a" => vec!["b".into(), "c".into()],
+            "b" => vec!["c".into()],
+            _ => vec![],
+        };
+
+        let pending = names(&["a", "b", "c"]);
+        check!(next_buildable_component(&pending, &names(&[]), dependencies) == Some("c".into()));
+
+        // b must wait for the running build of c
+        let pending = names(&["a", "b"]);
+        check!(next_buildable_component(&pending, &names(&[]), dependencies).is_none());
+
+        check!(
+            next_buildable_component(&pending, &names(&["c"]), dependencies) == Some("b".into())
+        );
+    }
+
+    #[test]
+    fn panic_messages() {
+        let panic = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        check!(panic_message(panic.as_ref()) == "static message");
+
+        let panic = std::panic::catch_unwind(|| panic!("formatted {}", 1)).unwrap_err();
+        check!(panic_message(panic.as_ref()) == "formatted 1");
+    }
+}
@@ -13,35 +13,81 @@
 // limitations under the License.
 
 use crate::app::build::task_result_marker::{ResolvedExternalCommandMarkerHash, TaskResultMarker};
+use crate::app::build::toolchain::path_with_tool_bin_dirs;
 use crate::app::build::{delete_path_logged, is_up_to_date, valid_env_vars};
 use crate::app::context::ApplicationContext;
 use crate::app::error::CustomCommandError;
 use crate::fs::compile_and_collect_globs;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{
+    log_action, log_skipping_up_to_date, log_thread_prefix, logln, output_sink, LogColorize,
+    LogIndent, LogThreadPrefix,
+};
 use crate::model::app_raw;
+use crate::model::app_variables::substitute_command;
 use anyhow::{anyhow, Context};
-use std::collections::HashMap;
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, ExitStatus, Stdio};
 use tracing::debug;
 
 pub fn execute_custom_command(
     ctx: &ApplicationContext,
     command_name: &str,
+    args: &[String],
 ) -> Result<(), CustomCommandError> {
     let all_custom_commands = ctx.application.all_custom_commands(ctx.profile());
     if !all_custom_commands.contains(command_name) {
         return Err(CustomCommandError::CommandNotFound);
     }
 
+    let args = parse_custom_command_args(args)
+        .map_err(|errors| CustomCommandError::InvalidArgs { errors })?;
+
+    // Arguments are validated against all the matching definitions before executing any steps
+    let mut errors = Vec::<String>::new();
+
+    let common_command = ctx
+        .application
+        .common_custom_commands()
+        .get(command_name)
+        .and_then(
+            |command| match resolve_custom_command_env(&command.value, &args) {
+                Ok(env) => Some((command, env)),
+                Err(command_errors) => {
+                    errors.extend(command_errors);
+                    None
+                }
+            },
+        );
+
+    let mut component_commands = Vec::new();
+    for component_name in ctx.application.component_names() {
+        let properties = &ctx
+            .application
+            .component_properties(component_name, ctx.profile());
+        if let Some(custom_command) = properties.custom_commands.get(command_name) {
+            match resolve_custom_command_env(custom_command, &args) {
+                Ok(env) => component_commands.push((component_name, custom_command.clone(), env)),
+                Err(command_errors) => errors.extend(command_errors),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(CustomCommandError::InvalidArgs {
+            errors: errors.into_iter().unique().collect(),
+        });
+    }
+
     log_action(
         "Executing",
         format!("custom command {}", command_name.log_color_highlight()),
     );
     let _indent = LogIndent::new();
 
-    let common_custom_commands = ctx.application.common_custom_commands();
-    if let Some(command) = common_custom_commands.get(command_name) {
+    if let Some((command, env)) = common_command {
         log_action(
             "Executing",
             format!(
@@ -51,38 +97,32 @@ pub fn execute_custom_command(
         );
         let _indent = LogIndent::new();
 
-        for step in &command.value {
-            if let Err(error) = execute_external_command(ctx, &command.source, step, HashMap::new())
-            {
+        for step in command.value.steps() {
+            if let Err(error) = execute_external_command(ctx, &command.source, step, env.clone()) {
                 return Err(CustomCommandError::CommandError { error });
             }
         }
     }
 
-    for component_name in ctx.application.component_names() {
-        let properties = &ctx
-            .application
-            .component_properties(component_name, ctx.profile());
-        if let Some(custom_command) = properties.custom_commands.get(command_name) {
-            log_action(
-                "Executing",
-                format!(
-                    "custom command {} for component {}",
-                    command_name.log_color_highlight(),
-                    component_name.as_str().log_color_highlight()
-                ),
-            );
-            let _indent = LogIndent::new();
-
-            for step in custom_command {
-                if let Err(error) = execute_external_command(
-                    ctx,
-                    ctx.application.component_source_dir(component_name),
-                    step,
-                    HashMap::new(),
-                ) {
-                    return Err(CustomCommandError::CommandError { error });
-                }
+    for (component_name, custom_command, env) in component_commands {
+        log_action(
+            "Executing",
+            format!(
+                "custom command {} for component {}",
+                command_name.log_color_highlight(),
+                component_name.as_str().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        for step in custom_command.steps() {
+            if let Err(error) = execute_external_command(
+                ctx,
+                ctx.application.component_source_dir(component_name),
+                step,
+                env.clone(),
+            ) {
+                return Err(CustomCommandError::CommandError { error });
             }
         }
     }
@@ -90,6 +130,126 @@ pub fn execute_custom_command(
     Ok(())
 }
 
+/// Parses custom command arguments in `--name value`, `--name=value` or `--flag` form
+fn parse_custom_command_args(
+    args: &[String],
+) -> Result<BTreeMap<String, Option<String>>, Vec<String>> {
+    let mut result = BTreeMap::<String, Option<String>>::new();
+    let mut errors = Vec::<String>::new();
+
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            errors.push(format!("Unexpected argument: {}", arg));
+            continue;
+        };
+
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (name, args.next_if(|next| !next.starts_with("--")).cloned()),
+        };
+
+        if result.insert(name.to_string(), value).is_some() {
+            errors.push(format!("Parameter --{} is specified multiple times", name));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(result)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates the arguments against the declared parameters, and returns the env vars to be used
+/// for the steps: the parameter values and the declared env vars, which can reference the
+/// parameters
+fn resolve_custom_command_env(
+    command: &app_raw::CustomCommand,
+    args: &BTreeMap<String, Option<String>>,
+) -> Result<HashMap<String, String>, Vec<String>> {
+    let no_params = BTreeMap::new();
+    let params = command.params().unwrap_or(&no_params);
+
+    let mut errors = Vec::<String>::new();
+    let mut values = HashMap::<String, String>::new();
+
+    for (name, value) in args {
+        match params.get(name) {
+            Some(param) => match validate_param_value(param.type_, value.as_deref()) {
+                Ok(value) => {
+                    values.insert(name.clone(), value);
+                }
+                Err(error) => errors.push(format!("Invalid value for --{}: {}", name, error)),
+            },
+            None => errors.push(format!("Unknown parameter: --{}", name)),
+        }
+    }
+
+    for (name, param) in params {
+        if args.contains_key(name) {
+            continue;
+        }
+
+        match &param.default {
+            Some(default) => {
+                let default = match default {
+                    serde_json::Value::String(default) => default.clone(),
+                    other => other.to_string(),
+                };
+                match validate_param_value(param.type_, Some(&default)) {
+                    Ok(value) => {
+                        values.insert(name.clone(), value);
+                    }
+                    Err(error) => errors.push(format!(
+                        "Invalid default value for parameter {}: {}",
+                        name, error
+                    )),
+                }
+            }
+            None if param.type_ == app_raw::CustomCommandParamType::Bool => {
+                values.insert(name.clone(), "false".to_string());
+            }
+            None => errors.push(format!("Missing required parameter: --{}", name)),
+        }
+    }
+
+    let mut env = values.clone();
+    for (key, value) in command.env().into_iter().flatten() {
+        match envsubst::substitute(value, &values) {
+            Ok(value) => {
+                env.insert(key.clone(), value);
+            }
+            Err(error) => errors.push(format!("Failed to substitute env var {}: {}", key, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(env)
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_param_value(
+    param_type: app_raw::CustomCommandParamType,
+    value: Option<&str>,
+) -> Result<String, String> {
+    match (param_type, value) {
+        (app_raw::CustomCommandParamType::Bool, None) => Ok("true".to_string()),
+        (_, None) => Err("missing value".to_string()),
+        (app_raw::CustomCommandParamType::String, Some(value)) => Ok(value.to_string()),
+        (app_raw::CustomCommandParamType::Int, Some(value)) => value
+            .parse::<i64>()
+            .map(|value| value.to_string())
+            .map_err(|_| format!("expected integer, got: {}", value)),
+        (app_raw::CustomCommandParamType::Bool, Some(value)) => value
+            .parse::<bool>()
+            .map(|value| value.to_string())
+            .map_err(|_| format!("expected true or false, got: {}", value)),
+    }
+}
+
 pub fn execute_external_command(
     ctx: &ApplicationContext,
     base_build_dir: &Path,
@@ -118,15 +278,22 @@ pub fn execute_external_command(
         "execute external command"
     );
 
+    // Only exported to the build steps, the --build-dir input is read from GOLEM_CLI_BUILD_DIR,
+    // so nested CLI calls do not inherit the temp dir as their build dir
+    let golem_build_dir = base_build_dir.join(ctx.application.temp_dir());
+
     let env_vars = {
         let mut map = HashMap::new();
         map.extend(valid_env_vars());
-        map.extend(additional_env_vars);
+        map.insert(
+            "GOLEM_BUILD_DIR".to_string(),
+            golem_build_dir.to_string_lossy().to_string(),
+        );
+        map.extend(additional_env_vars.clone());
         map
     };
 
-    let command_string = envsubst::substitute(&command.command, &env_vars)
-        .context("Failed to substitute env vars in command")?;
+    let command_string = substitute_command(&command.command, &env_vars);
 
     if !command.sources.is_empty() && !command.targets.is_empty() {
         let sources = compile_and_collect_globs(&build_dir, &command.sources)?;
@@ -181,11 +348,25 @@ pub fn execute_external_command(
             return Err(anyhow!("Empty command!"));
         }
 
-        let result = Command::new(command_tokens[0].clone())
+        let mut process = Command::new(command_tokens[0].clone());
+        process
             .args(command_tokens.iter().skip(1))
             .current_dir(build_dir)
-            .status()
-            .with_context(|| "Failed to execute command".to_string())?;
+            .envs(&additional_env_vars)
+            .env("GOLEM_BUILD_DIR", &golem_build_dir);
+        if !ctx.tool_bin_dirs.is_empty() {
+            process.env("PATH", path_with_tool_bin_dirs(&ctx.tool_bin_dirs)?);
+        }
+        // When serving requests stdout is used for the protocol messages
+        if output_sink().is_some() {
+            process.stdout(std::io::stderr());
+        }
+        // Output of concurrently built components is forwarded with the component prefix
+        let result = match log_thread_prefix() {
+            Some(_) => status_with_logged_output(&mut process),
+            None => process.status(),
+        }
+        .with_context(|| "Failed to execute command".to_string())?;
 
         if result.success() {
             Ok(())
@@ -200,3 +381,141 @@ pub fn execute_external_command(
         }
     })())
 }
+
+/// Runs the process with its output forwarded line by line through the log module, so the
+/// lines get the log prefix of the current thread
+fn status_with_logged_output(process: &mut Command) -> std::io::Result<ExitStatus> {
+    let mut child = process
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let prefix = log_thread_prefix().unwrap_or_default();
+
+    std::thread::scope(|scope| {
+        if let Some(stderr) = stderr {
+            let prefix = &prefix;
+            scope.spawn(move || {
+                let _prefix = LogThreadPrefix::new(prefix);
+                log_lines(stderr)
+            });
+        }
+        if let Some(stdout) = stdout {
+            log_lines(stdout);
+        }
+    });
+
+    child.wait()
+}
+
+fn log_lines<R: Read>(reader: R) {
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        logln(line);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::external_command::{
+        parse_custom_command_args, resolve_custom_command_env,
+    };
+    use crate::model::app_raw::CustomCommand;
+    use assert2::{check, let_assert};
+    use test_r::test;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn command(yaml: &str) -> CustomCommand {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn parse_args() {
+        let_assert!(
+            Ok(parsed) = parse_custom_command_args(&args(&[
+                "--env",
+                "staging",
+                "--times=3",
+                "--dry-run",
+                "--offset",
+                "-1"
+            ]))
+        );
+        check!(parsed.get("env") == Some(&Some("staging".to_string())));
+        check!(parsed.get("times") == Some(&Some("3".to_string())));
+        check!(parsed.get("dry-run") == Some(&None));
+        check!(parsed.get("offset") == Some(&Some("-1".to_string())));
+
+        let_assert!(Err(errors) = parse_custom_command_args(&args(&["staging", "--a", "--a"])));
+        check!(errors.len() == 2);
+    }
+
+    #[test]
+    fn resolve_env_with_params() {
+        let command = command(
+            r#"
+params:
+  env:
+    default: dev
+  times:
+    type: int
+    default: 1
+  verbose:
+    type: bool
+env:
+  TARGET: "deploy-${env}"
+steps:
+  - command: ./script.sh ${env} ${times}
+"#,
+        );
+
+        let_assert!(
+            Ok(env) = resolve_custom_command_env(
+                &command,
+                &parse_custom_command_args(&args(&["--env", "staging", "--times", "3"])).unwrap()
+            )
+        );
+        check!(env.get("env") == Some(&"staging".to_string()));
+        check!(env.get("times") == Some(&"3".to_string()));
+        check!(env.get("verbose") == Some(&"false".to_string()));
+        check!(env.get("TARGET") == Some(&"deploy-staging".to_string()));
+
+        let_assert!(Ok(env) = resolve_custom_command_env(&command, &Default::default()));
+        check!(env.get("env") == Some(&"dev".to_string()));
+        check!(env.get("times") == Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn resolve_env_with_invalid_args() {
+        let command = command(
+            r#"
+params:
+  target: {}
+  times:
+    type: int
+steps:
+  - command: ./script.sh
+"#,
+        );
+
+        let_assert!(
+            Err(errors) = resolve_custom_command_env(
+                &command,
+                &parse_custom_command_args(&args(&["--times", "many", "--unknown", "x"])).unwrap()
+            )
+        );
+        check!(errors.len() == 3);
+
+        let steps_only = self::command("[{ command: ./script.sh }]");
+        let_assert!(Ok(_) = resolve_custom_command_env(&steps_only, &Default::default()));
+        let_assert!(
+            Err(_) = resolve_custom_command_env(
+                &steps_only,
+                &parse_custom_command_args(&args(&["--env", "staging"])).unwrap()
+            )
+        );
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::delete_path_logged;
+use crate::fs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::{AppComponentName, Application};
+use crate::model::component::AppComponentType;
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::wit_generate::extract_component_wasm_wit;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Describes the fetched version of an external component, stored next to its extracted WIT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalComponentMetadata {
+    pub component_id: Uuid,
+    pub version: u64,
+    pub component_type: AppComponentType,
+}
+
+impl ExternalComponentMetadata {
+    pub fn load(
+        application: &Application,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<Option<Self>> {
+        let path = application.external_component_metadata(component_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(&path)?)?))
+    }
+
+    fn store(
+        &self,
+        application: &Application,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<()> {
+        fs::write_str(
+            application.external_component_metadata(component_name),
+            serde_json::to_string_pretty(self)?,
+        )
+    }
+}
+
+pub fn is_external_component_up_to_date(
+    application: &Application,
+    component_name: &AppComponentName,
+    metadata: &ExternalComponentMetadata,
+) -> anyhow::Result<bool> {
+    Ok(application.external_component_wit(component_name).exists()
+        && ExternalComponentMetadata::load(application, component_name)?.as_ref() == Some(metadata))
+}
+
+/// Extracts the WIT of the fetched external component, to be used for generating its client
+pub fn update_external_component(
+    application: &Application,
+    component_name: &AppComponentName,
+    metadata: &ExternalComponentMetadata,
+    component_wasm: &[u8],
+) -> anyhow::Result<()> {
+    let wit_dir = application.external_component_wit(component_name);
+    let main_package_name = naming::wit::external_component_parser_package_name(
+        component_name.as_str(),
+    )
+    .ok_or_else(|| {
+        anyhow!(
+            "Invalid external component name: {}",
+            component_name.as_str().log_color_error_highlight()
+        )
+    })?;
+
+    log_action(
+        "Extracting",
+        format!(
+            "WIT of external component {} to {}",
+            component_name.as_str().log_color_highlight(),
+            wit_dir.log_color_highlight()
+        ),
+    );
+    let _indent = LogIndent::new();
+
+    delete_path_logged("external component wit", &wit_dir)?;
+    extract_component_wasm_wit(component_wasm, &main_package_name, &wit_dir)?;
+    metadata.store(application, component_name)
+}
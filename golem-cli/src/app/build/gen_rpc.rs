@@ -23,7 +23,7 @@ use crate::wasm_rpc_stubgen::cargo::regenerate_cargo_package_component;
 use crate::wasm_rpc_stubgen::commands;
 use crate::wasm_rpc_stubgen::wit_generate::{
     add_client_as_dependency_to_wit_dir, extract_exports_as_wit_dep, AddClientAsDepConfig,
-    UpdateCargoToml,
+    ClientPackageCollision, UpdateCargoToml,
 };
 use anyhow::{anyhow, Context, Error};
 use itertools::Itertools;
@@ -283,12 +283,8 @@ async fn build_client(
     ctx: &mut ApplicationContext,
     component: &DependentComponent,
 ) -> anyhow::Result<bool> {
-    let stub_def = ctx.component_stub_def(
-        &component.name,
-        ctx.application
-            .component_properties(&component.name, ctx.profile())
-            .is_ephemeral(),
-    )?;
+    let is_ephemeral = ctx.is_ephemeral_component(&component.name)?;
+    let stub_def = ctx.component_stub_def(&component.name, is_ephemeral)?;
     let client_wit_root = stub_def.client_wit_root();
 
     let client_dep_package_ids = stub_def.stub_dep_package_ids();
@@ -358,20 +354,35 @@ async fn build_client(
                         fs::create_dir_all(&client_wit_root)?;
 
                         let offline = ctx.config.offline;
+                        let reproducible = ctx.config.reproducible_build;
+                        // The temp build dir is kept on failure, for inspecting the generated crate
                         commands::generate::build(
-                            ctx.component_stub_def(
-                                &component.name,
-                                ctx.application
-                                    .component_properties(&component.name, ctx.profile())
-                                    .is_ephemeral(),
-                            )?,
+                            ctx.component_stub_def(&component.name, is_ephemeral)?,
                             &client_wasm,
                             &client_wit,
                             offline,
+                            reproducible,
                         )
-                        .await?;
+                        .await
+                        .with_context(|| {
+                            anyhow!(
+                                "Failed to build WASM RPC client for {}, kept the client temp build dir at {}",
+                                component.name.as_str().log_color_highlight(),
+                                client_wit_root.log_color_highlight()
+                            )
+                        })?;
 
-                        if !env_var_flag("WASM_RPC_KEEP_CLIENT_DIR") {
+                        if ctx.config.keep_temp_build_dirs
+                            || env_var_flag("WASM_RPC_KEEP_CLIENT_DIR")
+                        {
+                            log_action(
+                                "Keeping",
+                                format!(
+                                    "client temp build dir {}",
+                                    client_wit_root.log_color_highlight()
+                                ),
+                            );
+                        } else {
                             delete_path_logged("client temp build dir", &client_wit_root)?;
                         }
 
@@ -398,12 +409,7 @@ async fn build_client(
                         );
                         fs::create_dir_all(&client_wit_root)?;
 
-                        let stub_def = ctx.component_stub_def(
-                            &component.name,
-                            ctx.application
-                                .component_properties(&component.name, ctx.profile())
-                                .is_ephemeral(),
-                        )?;
+                        let stub_def = ctx.component_stub_def(&component.name, is_ephemeral)?;
                         commands::generate::generate_and_copy_client_wit(stub_def, &client_wit)
                     }
                     DependencyType::Wasm => {
@@ -455,6 +461,7 @@ fn add_client_deps(
                         .application
                         .component_generated_wit(component_name, ctx.profile()),
                     update_cargo_toml: UpdateCargoToml::NoUpdate,
+                    package_collision: ClientPackageCollision::Fail,
                 })?
             }
         }
@@ -12,21 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::componentize::panic_message;
 use crate::app::build::is_up_to_date;
 use crate::app::build::task_result_marker::{LinkRpcMarkerHash, TaskResultMarker};
 use crate::app::context::ApplicationContext;
 use crate::fs;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
-use crate::model::app::DependencyType;
+use crate::log::{
+    log_action, log_skipping_up_to_date, log_thread_prefix, LogColorize, LogIndent, LogThreadPrefix,
+};
+use crate::model::app::{AppComponentName, DependencyType};
 use crate::wasm_rpc_stubgen::commands;
+use anyhow::anyhow;
 use itertools::Itertools;
 use std::collections::BTreeSet;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
     log_action("Linking", "dependencies");
     let _indent = LogIndent::new();
 
+    let jobs = ctx.config.build_jobs;
+    let mut concurrent_link_tasks = Vec::new();
+
     for component_name in ctx.selected_component_names() {
+        ctx.config.cancellation_token.check()?;
+
         let static_dependencies = ctx
             .application
             .component_dependencies(component_name)
@@ -125,49 +137,133 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             continue;
         }
 
-        task_result_marker.result(
-            async {
-                if wasms_to_compose_with.is_empty() {
-                    log_action(
-                        "Copying",
-                        format!(
-                            "{} without linking, no static dependencies were found",
-                            component_name.as_str().log_color_highlight(),
-                        ),
-                    );
-                    fs::copy(&component_wasm, &linked_wasm).map(|_| ())
-                } else {
-                    log_action(
-                        "Linking",
-                        format!(
-                            "static dependencies ({}) into {}",
-                            static_dependencies
-                                .iter()
-                                .map(|s| s.name.as_str().log_color_highlight())
-                                .chain(
-                                    library_dependencies
-                                        .iter()
-                                        .map(|s| s.name.as_str().log_color_highlight()),
-                                )
-                                .join(", "),
-                            component_name.as_str().log_color_highlight(),
-                        ),
-                    );
-                    let _indent = LogIndent::new();
-
-                    commands::composition::compose(
-                        ctx.application
-                            .component_wasm(component_name, ctx.profile())
-                            .as_path(),
-                        &wasms_to_compose_with,
-                        linked_wasm.as_path(),
-                    )
-                    .await
+        let link_task = LinkTask {
+            component_name,
+            dependency_names: static_dependencies
+                .iter()
+                .chain(library_dependencies.iter())
+                .map(|dep| dep.name.as_str())
+                .collect(),
+            component_wasm,
+            wasms_to_compose_with,
+            linked_wasm,
+            task_result_marker,
+        };
+
+        if jobs > 1 {
+            concurrent_link_tasks.push(link_task);
+        } else {
+            link_task.run(ctx)?;
+        }
+    }
+
+    if !concurrent_link_tasks.is_empty() {
+        link_concurrently(ctx, concurrent_link_tasks, jobs)?;
+    }
+
+    Ok(())
+}
+
+struct LinkTask<'a> {
+    component_name: &'a AppComponentName,
+    dependency_names: Vec<&'a str>,
+    component_wasm: PathBuf,
+    wasms_to_compose_with: Vec<PathBuf>,
+    linked_wasm: PathBuf,
+    task_result_marker: TaskResultMarker,
+}
+
+impl LinkTask<'_> {
+    fn run(self, ctx: &ApplicationContext) -> anyhow::Result<()> {
+        let linked_wasm_in_progress = ctx
+            .config
+            .cancellation_token
+            .track_in_progress_path(&self.linked_wasm);
+
+        let result = if self.wasms_to_compose_with.is_empty() {
+            log_action(
+                "Copying",
+                format!(
+                    "{} without linking, no static dependencies were found",
+                    self.component_name.as_str().log_color_highlight(),
+                ),
+            );
+            fs::copy(&self.component_wasm, &self.linked_wasm).map(|_| ())
+        } else {
+            log_action(
+                "Linking",
+                format!(
+                    "static dependencies ({}) into {}",
+                    self.dependency_names
+                        .iter()
+                        .map(|name| name.log_color_highlight())
+                        .join(", "),
+                    self.component_name.as_str().log_color_highlight(),
+                ),
+            );
+            // Concurrent linking is told apart by the log prefix, indents are shared between threads
+            let _indent = log_thread_prefix().is_none().then(LogIndent::new);
+
+            commands::composition::compose(
+                &self.component_wasm,
+                &self.wasms_to_compose_with,
+                &self.linked_wasm,
+            )
+        };
+        self.task_result_marker.result(result)?;
+
+        linked_wasm_in_progress.done();
+
+        Ok(())
+    }
+}
+
+/// Links up to `jobs` components at the same time. Linking only reads already built WASMs, so
+/// the components do not depend on each other. After a failure no new linking is started.
+fn link_concurrently(
+    ctx: &ApplicationContext,
+    link_tasks: Vec<LinkTask<'_>>,
+    jobs: usize,
+) -> anyhow::Result<()> {
+    let workers = jobs.min(link_tasks.len());
+    let link_tasks = Mutex::new(link_tasks.into_iter());
+    let first_error = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                if first_error.lock().unwrap().is_some()
+                    || ctx.config.cancellation_token.is_cancelled()
+                {
+                    break;
                 }
-            }
-            .await,
-        )?;
+                let Some(link_task) = link_tasks.lock().unwrap().next() else {
+                    break;
+                };
+
+                let component_name = link_task.component_name;
+                let _prefix = LogThreadPrefix::new(format!("[{}] ", component_name.as_str()));
+                // Panics are also reported as an error, instead of tearing down the whole scope
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| link_task.run(ctx)))
+                    .unwrap_or_else(|panic| {
+                        Err(anyhow!(
+                            "Linking panicked: {}",
+                            panic_message(panic.as_ref())
+                        ))
+                    });
+                if let Err(err) = result {
+                    first_error.lock().unwrap().get_or_insert_with(|| {
+                        err.context(format!("Failed to link component {}", component_name))
+                    });
+                }
+            });
+        }
+    });
+
+    if let Some(err) = first_error.into_inner().unwrap() {
+        return Err(err);
     }
+    ctx.config.cancellation_token.check()?;
 
     Ok(())
 }
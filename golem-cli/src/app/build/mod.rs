@@ -16,6 +16,7 @@ use crate::app::build::add_metadata::add_metadata_to_selected_components;
 use crate::app::build::componentize::componentize;
 use crate::app::build::gen_rpc::gen_rpc;
 use crate::app::build::link::link;
+use crate::app::build::toolchain::ensure_pinned_tools;
 use crate::app::context::ApplicationContext;
 use crate::fs;
 use crate::log::{log_warn_action, LogColorize};
@@ -34,21 +35,33 @@ pub mod add_metadata;
 pub mod clean;
 pub mod componentize;
 pub mod external_command;
+pub mod external_component;
 pub mod gen_rpc;
 pub mod link;
 pub mod task_result_marker;
+pub mod toolchain;
 
 pub async fn build_app(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
+    let cancellation_token = ctx.config.cancellation_token.clone();
+    let _cancellable_scope = cancellation_token.cancellable_scope();
+
+    if ctx.config.should_run_step(AppBuildStep::Componentize) {
+        ensure_pinned_tools(ctx)?;
+    }
     if ctx.config.should_run_step(AppBuildStep::GenRpc) {
+        cancellation_token.check()?;
         gen_rpc(ctx).await?;
     }
     if ctx.config.should_run_step(AppBuildStep::Componentize) {
+        cancellation_token.check()?;
         componentize(ctx)?;
     }
     if ctx.config.should_run_step(AppBuildStep::Link) {
+        cancellation_token.check()?;
         link(ctx).await?;
     }
     if ctx.config.should_run_step(AppBuildStep::AddMetadata) {
+        cancellation_token.check()?;
         add_metadata_to_selected_components(ctx).await?;
     }
 
@@ -0,0 +1,279 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tool versions pinned in the application manifest, e.g.:
+//!
+//! ```yaml
+//! tools:
+//!   cargo-component: 0.20.0
+//!   wasm-tools: 1.223.0
+//! ```
+//!
+//! Pinned versions are verified before building the components. Tools distributed as crates can
+//! also be installed with `--install-tools` into the managed toolchain dir, where they are kept per
+//! version, and used by the build commands of all applications pinning the same version.
+
+use crate::app::context::ApplicationContext;
+use crate::diagnose::npm_package_version;
+use crate::log::{log_action, output_sink, LogColorize, LogIndent};
+use crate::model::app::PinnedTool;
+use anyhow::{anyhow, bail, Context};
+use regex::Regex;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use version_compare::Cmp;
+
+const TOOLCHAIN_DIR_ENV_VAR: &str = "GOLEM_TOOLCHAIN_DIR";
+
+/// Managed toolchain dir, can be overridden with the GOLEM_TOOLCHAIN_DIR env var
+pub fn toolchain_dir() -> Option<PathBuf> {
+    match std::env::var_os(TOOLCHAIN_DIR_ENV_VAR) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => dirs::data_local_dir().map(|dir| dir.join("golem").join("toolchain")),
+    }
+}
+
+/// Verifies the pinned tool versions, and installs the mismatching ones into the managed toolchain
+/// dir if requested. Already installed managed versions take precedence over the ones on the PATH.
+pub fn ensure_pinned_tools(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
+    if ctx.application.tools().is_empty() {
+        return Ok(());
+    }
+
+    log_action("Checking", "pinned tool versions");
+    let _indent = LogIndent::new();
+
+    let toolchain_dir = toolchain_dir();
+    let mut tool_bin_dirs = Vec::new();
+    let mut problems = Vec::new();
+
+    for (tool, version) in ctx.application.tools() {
+        let managed_root = match (tool_crate(*tool), &toolchain_dir) {
+            (Some(_), Some(toolchain_dir)) => {
+                Some(toolchain_dir.join(tool.as_str()).join(&version.value))
+            }
+            _ => None,
+        };
+
+        if let Some(managed_root) = &managed_root {
+            let bin_dir = managed_root.join("bin");
+            if bin_dir.join(binary_file_name(*tool)).is_file() {
+                log_action(
+                    "Using",
+                    format!(
+                        "{} {} from {}",
+                        tool.as_str().log_color_highlight(),
+                        version.value.log_color_highlight(),
+                        bin_dir.log_color_highlight()
+                    ),
+                );
+                tool_bin_dirs.push(bin_dir);
+                continue;
+            }
+        }
+
+        let detected_version = detect_version(*tool, &version.source);
+        if let Ok(detected_version) = &detected_version {
+            if version_matches(&version.value, detected_version) {
+                log_action(
+                    "Found",
+                    format!(
+                        "{} {}",
+                        tool.as_str().log_color_highlight(),
+                        detected_version.log_color_highlight()
+                    ),
+                );
+                continue;
+            }
+        }
+
+        match (ctx.config.install_tools, tool_crate(*tool), managed_root) {
+            (true, Some(crate_name), Some(managed_root)) => {
+                install_with_cargo(*tool, crate_name, &version.value, &managed_root)?;
+                tool_bin_dirs.push(managed_root.join("bin"));
+            }
+            _ => problems.push(format!(
+                "{}: required {}, {}, {}",
+                tool.as_str().log_color_highlight(),
+                version.value.log_color_highlight(),
+                match detected_version {
+                    Ok(detected_version) =>
+                        format!("found {}", detected_version.log_color_error_highlight()),
+                    Err(error) => format!("not found ({})", error),
+                },
+                install_hint(*tool, &version.value, ctx.config.install_tools)
+            )),
+        }
+    }
+
+    if !problems.is_empty() {
+        bail!(
+            "Pinned tool versions are not available:\n{}",
+            problems
+                .iter()
+                .map(|problem| format!("  - {}", problem))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    ctx.tool_bin_dirs = tool_bin_dirs;
+
+    Ok(())
+}
+
+/// PATH with the tool bin dirs prepended
+pub fn path_with_tool_bin_dirs(tool_bin_dirs: &[PathBuf]) -> anyhow::Result<OsString> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::join_paths(
+        tool_bin_dirs
+            .iter()
+            .cloned()
+            .chain(std::env::split_paths(&path)),
+    )
+    .context("Failed to add the managed toolchain dirs to PATH")
+}
+
+/// Crate of the tool, for tools which can be installed with cargo
+fn tool_crate(tool: PinnedTool) -> Option<&'static str> {
+    match tool {
+        PinnedTool::CargoComponent => Some("cargo-component"),
+        PinnedTool::WasmTools => Some("wasm-tools"),
+        PinnedTool::WitBindgen => Some("wit-bindgen-cli"),
+        PinnedTool::Jco | PinnedTool::TinyGo => None,
+    }
+}
+
+fn binary_file_name(tool: PinnedTool) -> String {
+    format!("{}{}", tool.as_str(), std::env::consts::EXE_SUFFIX)
+}
+
+fn install_hint(tool: PinnedTool, version: &str, install_tools: bool) -> String {
+    match tool_crate(tool) {
+        Some(_) if !install_tools => format!(
+            "use {} to install it into the managed toolchain dir",
+            "--install-tools".log_color_highlight()
+        ),
+        Some(crate_name) => format!(
+            "install it with: cargo install --force --locked {}@{}",
+            crate_name, version
+        ),
+        None => match tool {
+            PinnedTool::Jco => format!(
+                "install it with: npm install --save-dev @golemcloud/jco@{}",
+                version
+            ),
+            _ => "see https://tinygo.org/getting-started/install/".to_string(),
+        },
+    }
+}
+
+fn detect_version(tool: PinnedTool, dir: &Path) -> Result<String, String> {
+    match tool {
+        PinnedTool::Jco => npm_package_version(dir, "@golemcloud/jco"),
+        PinnedTool::TinyGo => cmd_version(dir, "tinygo", &["version"]),
+        PinnedTool::CargoComponent | PinnedTool::WasmTools | PinnedTool::WitBindgen => {
+            cmd_version(dir, tool.as_str(), &["--version"])
+        }
+    }
+}
+
+fn cmd_version(dir: &Path, cmd: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(cmd)
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .map_err(|err| err.to_string())?;
+    parse_version_output(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| "failed to extract version from output".to_string())
+}
+
+fn parse_version_output(output: &str) -> Option<String> {
+    let version_regex = Regex::new(r"([0-9]+\.[0-9]+(\.[0-9]+)?(-[0-9A-Za-z.]+)?)")
+        .expect("Failed to compile version regex");
+    version_regex
+        .captures(output)
+        .and_then(|captures| captures.get(1))
+        .map(|version| version.as_str().to_string())
+}
+
+fn version_matches(required: &str, detected: &str) -> bool {
+    required == detected || version_compare::compare(detected, required) == Ok(Cmp::Eq)
+}
+
+fn install_with_cargo(
+    tool: PinnedTool,
+    crate_name: &str,
+    version: &str,
+    root: &Path,
+) -> anyhow::Result<()> {
+    log_action(
+        "Installing",
+        format!(
+            "{} {} into {}",
+            tool.as_str().log_color_highlight(),
+            version.log_color_highlight(),
+            root.log_color_highlight()
+        ),
+    );
+
+    let mut process = Command::new("cargo");
+    process
+        .arg("install")
+        .arg("--locked")
+        .arg("--root")
+        .arg(root)
+        .arg(format!("{}@{}", crate_name, version));
+    // When serving requests stdout is used for the protocol messages
+    if output_sink().is_some() {
+        process.stdout(std::io::stderr());
+    }
+
+    let status = process
+        .status()
+        .with_context(|| anyhow!("Failed to execute cargo install for {}", tool))?;
+    if !status.success() {
+        bail!("Failed to install {} {}, {}", tool, version, status);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::app::build::toolchain::{parse_version_output, version_matches};
+    use assert2::check;
+
+    #[test]
+    fn tool_versions() {
+        check!(
+            parse_version_output("cargo-component-component 0.20.0 (wasi:040ec92)")
+                == Some("0.20.0".to_string())
+        );
+        check!(
+            parse_version_output("tinygo version 0.35.0 linux/amd64 (using go version go1.23.4)")
+                == Some("0.35.0".to_string())
+        );
+        check!(parse_version_output("1.4.4-golem.1 (jco)") == Some("1.4.4-golem.1".to_string()));
+        check!(parse_version_output("command not found").is_none());
+
+        check!(version_matches("1.223.0", "1.223.0"));
+        check!(version_matches("0.35", "0.35.0"));
+        check!(!version_matches("0.20.0", "0.19.0"));
+        check!(!version_matches("1.4.4-golem.1", "1.4.4"));
+    }
+}
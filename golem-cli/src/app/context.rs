@@ -15,6 +15,7 @@
 use crate::app::build::build_app;
 use crate::app::build::clean::clean_app;
 use crate::app::build::external_command::execute_custom_command;
+use crate::app::build::external_component::ExternalComponentMetadata;
 use crate::app::error::{format_warns, AppValidationError, CustomCommandError};
 use crate::fs::{compile_and_collect_globs, PathExtra};
 use crate::log::{log_action, log_warn_action, logln, LogColorize, LogIndent};
@@ -24,9 +25,11 @@ use crate::model::app::{
     DynamicHelpSections, DEFAULT_CONFIG_FILE_NAME,
 };
 use crate::model::app_raw;
+use crate::model::app_variables::ManifestVariableDefinitions;
+use crate::model::component::AppComponentType;
 use crate::validation::{ValidatedResult, ValidationBuilder};
 use crate::wasm_rpc_stubgen::naming;
-use crate::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
+use crate::wasm_rpc_stubgen::stub::{ClientBorrowMode, StubConfig, StubDefinition};
 use crate::wasm_rpc_stubgen::wit_resolve::{ResolvedWitApplication, WitDepsResolver};
 use anyhow::{anyhow, bail, Context};
 use colored::control::SHOULD_COLORIZE;
@@ -42,10 +45,13 @@ pub struct ApplicationContext {
     pub application: Application,
     pub wit: ResolvedWitApplication,
     pub calling_working_dir: PathBuf,
+    /// Managed toolchain bin dirs of the pinned tools, prepended to the PATH of external commands
+    pub tool_bin_dirs: Vec<PathBuf>,
     component_stub_defs: HashMap<AppComponentName, StubDefinition>,
     common_wit_deps: OnceLock<anyhow::Result<WitDepsResolver>>,
     component_generated_base_wit_deps: HashMap<AppComponentName, WitDepsResolver>,
     selected_component_names: BTreeSet<AppComponentName>,
+    validation_warns: Vec<String>,
 }
 
 impl ApplicationContext {
@@ -54,7 +60,8 @@ impl ApplicationContext {
             return Ok(None);
         };
 
-        let ctx = to_anyhow(
+        let mut validation_warns = Vec::new();
+        let mut ctx = to_anyhow(
             "Failed to create application context, see problems above",
             app_and_calling_working_dir.and_then(|(application, calling_working_dir)| {
                 ResolvedWitApplication::new(&application, config.profile.as_ref()).map(|wit| {
@@ -63,14 +70,18 @@ impl ApplicationContext {
                         application,
                         wit,
                         calling_working_dir,
+                        tool_bin_dirs: Vec::new(),
                         component_stub_defs: HashMap::new(),
                         common_wit_deps: OnceLock::new(),
                         component_generated_base_wit_deps: HashMap::new(),
                         selected_component_names: BTreeSet::new(),
+                        validation_warns: Vec::new(),
                     }
                 })
             }),
+            &mut validation_warns,
         )?;
+        ctx.validation_warns = validation_warns;
 
         ctx.select_and_validate_profiles()?;
 
@@ -250,6 +261,15 @@ impl ApplicationContext {
         Ok(())
     }
 
+    /// Unique validation warnings reported since the application context was created
+    pub fn validation_warns(&self) -> Vec<&str> {
+        self.validation_warns
+            .iter()
+            .map(|warn| warn.as_str())
+            .unique()
+            .collect()
+    }
+
     pub fn profile(&self) -> Option<&BuildProfileName> {
         self.config.profile.as_ref()
     }
@@ -260,6 +280,7 @@ impl ApplicationContext {
             ResolvedWitApplication::new(&self.application, self.profile()).map(|wit| {
                 self.wit = wit;
             }),
+            &mut self.validation_warns,
         )
     }
 
@@ -269,12 +290,16 @@ impl ApplicationContext {
         is_ephemeral: bool,
     ) -> anyhow::Result<&StubDefinition> {
         if !self.component_stub_defs.contains_key(component_name) {
+            let is_external = self.application.is_external_component(component_name);
             self.component_stub_defs.insert(
                 component_name.clone(),
                 StubDefinition::new(StubConfig {
-                    source_wit_root: self
-                        .application
-                        .component_generated_base_wit(component_name),
+                    source_wit_root: if is_external {
+                        self.application.external_component_wit(component_name)
+                    } else {
+                        self.application
+                            .component_generated_base_wit(component_name)
+                    },
                     client_root: self.application.client_temp_build_dir(component_name),
                     selected_world: None,
                     stub_crate_version: WASM_RPC_VERSION.to_string(),
@@ -283,6 +308,21 @@ impl ApplicationContext {
                     seal_cargo_workspace: true,
                     component_name: component_name.clone(),
                     is_ephemeral,
+                    borrow_mode: if is_external {
+                        ClientBorrowMode::default()
+                    } else {
+                        self.application
+                            .component_properties(component_name, self.profile())
+                            .client_borrow_mode
+                    },
+                    client_derives: if is_external {
+                        vec![]
+                    } else {
+                        self.application
+                            .component_properties(component_name, self.profile())
+                            .client_derives
+                            .clone()
+                    },
                 })
                 .context("Failed to gather information for the stub generator")?,
             );
@@ -290,14 +330,34 @@ impl ApplicationContext {
         Ok(self.component_stub_defs.get(component_name).unwrap())
     }
 
+    /// Checks if a WASM RPC dependency target is ephemeral, for external components this
+    /// is based on the fetched component metadata
+    pub fn is_ephemeral_component(
+        &self,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<bool> {
+        if self.application.is_external_component(component_name) {
+            let metadata = ExternalComponentMetadata::load(&self.application, component_name)?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "External component {} is not fetched yet",
+                        component_name.as_str().log_color_error_highlight()
+                    )
+                })?;
+            Ok(metadata.component_type == AppComponentType::Ephemeral)
+        } else {
+            Ok(self
+                .application
+                .component_properties(component_name, self.profile())
+                .is_ephemeral())
+        }
+    }
+
     pub fn component_stub_interfaces(
         &mut self,
         component_name: &AppComponentName,
     ) -> anyhow::Result<ComponentStubInterfaces> {
-        let is_ephemeral = self
-            .application
-            .component_properties(component_name, self.profile())
-            .is_ephemeral();
+        let is_ephemeral = self.is_ephemeral_component(component_name)?;
         let stub_def = self.component_stub_def(component_name, is_ephemeral)?;
         let client_package_name = stub_def.client_parser_package_name();
         let result = ComponentStubInterfaces {
@@ -440,6 +500,7 @@ impl ApplicationContext {
         let selected_component_names = to_anyhow(
             "Failed to select requested components",
             selected_component_names,
+            &mut self.validation_warns,
         )?;
 
         if self.application.component_names().next().is_none() {
@@ -486,8 +547,12 @@ impl ApplicationContext {
         build_app(self).await
     }
 
-    pub fn custom_command(&self, command_name: &str) -> Result<(), CustomCommandError> {
-        execute_custom_command(self, command_name)
+    pub fn custom_command(
+        &self,
+        command_name: &str,
+        args: &[String],
+    ) -> Result<(), CustomCommandError> {
+        execute_custom_command(self, command_name, args)
     }
 
     pub fn clean(&self) -> anyhow::Result<()> {
@@ -656,21 +721,48 @@ impl ApplicationContext {
 fn load_app(config: &ApplicationConfig) -> Option<ValidatedResult<(Application, PathBuf)>> {
     let result =
         collect_sources(&config.app_source_mode)?.and_then(|(sources, calling_working_dir)| {
-            sources
-                .into_iter()
-                .map(|source| {
-                    ValidatedResult::from_result(app_raw::ApplicationWithSource::from_yaml_file(
-                        source,
-                    ))
+            collect_manifest_variables(&sources, config)
+                .and_then(|variables| {
+                    sources
+                        .into_iter()
+                        .map(|source| {
+                            ValidatedResult::from_result(
+                                app_raw::ApplicationWithSource::from_yaml_file(source, &variables),
+                            )
+                        })
+                        .collect::<ValidatedResult<Vec<_>>>()
                 })
-                .collect::<ValidatedResult<Vec<_>>>()
                 .and_then(Application::from_raw_apps)
-                .map(|app| (app, calling_working_dir))
+                .map(|mut app| {
+                    if let Some(build_dir) = &config.build_dir {
+                        app.override_temp_dir(calling_working_dir.join(build_dir));
+                    }
+                    (app, calling_working_dir)
+                })
         });
 
     Some(result)
 }
 
+fn collect_manifest_variables(
+    sources: &BTreeSet<PathBuf>,
+    config: &ApplicationConfig,
+) -> ValidatedResult<HashMap<String, String>> {
+    let mut definitions = ManifestVariableDefinitions::default();
+    let errors = sources
+        .iter()
+        .flat_map(|source| definitions.add_from_yaml_file(source))
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        ValidatedResult::Ok(
+            definitions.resolve(config.profile.as_ref(), &config.manifest_variables),
+        )
+    } else {
+        ValidatedResult::WarnsAndErrors(vec![], errors)
+    }
+}
+
 fn collect_sources(
     mode: &ApplicationSourceMode,
 ) -> Option<ValidatedResult<(BTreeSet<PathBuf>, PathBuf)>> {
@@ -760,11 +852,17 @@ fn find_main_source() -> Option<PathBuf> {
     last_source
 }
 
-fn to_anyhow<T>(message: &str, result: ValidatedResult<T>) -> anyhow::Result<T> {
+/// Converts the validation result, logging and collecting the warnings into `collected_warns`
+fn to_anyhow<T>(
+    message: &str,
+    result: ValidatedResult<T>,
+    collected_warns: &mut Vec<String>,
+) -> anyhow::Result<T> {
     match result {
         ValidatedResult::Ok(value) => Ok(value),
         ValidatedResult::OkWithWarns(components, warns) => {
             log_warn_action("App validation warnings:\n", format_warns(&warns));
+            collected_warns.extend(warns);
             Ok(components)
         }
         ValidatedResult::WarnsAndErrors(warns, errors) => Err(anyhow!(AppValidationError {
@@ -59,5 +59,6 @@ pub fn format_errors(errors: &[String]) -> String {
 
 pub enum CustomCommandError {
     CommandNotFound,
+    InvalidArgs { errors: Vec<String> },
     CommandError { error: anyhow::Error },
 }
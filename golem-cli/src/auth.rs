@@ -17,7 +17,7 @@ use crate::cloud::{
 };
 use crate::config::{Config, Profile, ProfileName};
 use crate::error::service::AnyhowMapServiceError;
-use crate::log::LogColorize;
+use crate::log::{ascii_only, LogColorize};
 use anyhow::{anyhow, bail, Context};
 use colored::Colorize;
 use golem_cloud_client::api::{LoginClient, LoginClientLive, LoginOauth2WebFlowPollError};
@@ -189,17 +189,32 @@ impl Auth {
 fn inform_user(data: &WebFlowAuthorizeUrlResponse) {
     let url = &data.url.underline();
 
-    printdoc! {
-        "
-        ┌────────────────────────────────────────┐
-        │       Authenticate with GitHub         │
-        │                                        │
-        │  Visit the following URL in a browser  │
-        │                                        │
-        └────────────────────────────────────────┘
-        {url}
-        ──────────────────────────────────────────
-        "
+    if ascii_only() {
+        printdoc! {
+            "
+            +----------------------------------------+
+            |       Authenticate with GitHub         |
+            |                                        |
+            |  Visit the following URL in a browser  |
+            |                                        |
+            +----------------------------------------+
+            {url}
+            ------------------------------------------
+            "
+        }
+    } else {
+        printdoc! {
+            "
+            ┌────────────────────────────────────────┐
+            │       Authenticate with GitHub         │
+            │                                        │
+            │  Visit the following URL in a browser  │
+            │                                        │
+            └────────────────────────────────────────┘
+            {url}
+            ──────────────────────────────────────────
+            "
+        }
     }
 
     println!("Waiting for authentication...");
@@ -0,0 +1,266 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::CancelledError;
+use crate::log::{log_warn_action, logln, set_log_output, LogColorize, Output};
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tracing::debug;
+
+pub const CANCELLED_EXIT_CODE: u8 = 130;
+
+/// Cooperative cancellation token, shared between the signal handler and the running command.
+///
+/// Operations which can be safely stopped between their steps open a cancellable scope, and
+/// check the token at their safe points. Files which are written by an operation are tracked
+/// while in progress, so they can be deleted on cancellation instead of leaving half-written
+/// artifacts behind (which could be later considered up-to-date).
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationTokenInner>,
+}
+
+#[derive(Default)]
+struct CancellationTokenInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+    active_scopes: AtomicUsize,
+    in_progress_paths: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes when the token gets cancelled
+    pub async fn cancelled(&self) {
+        let notified = self.inner.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await
+    }
+
+    /// Returns CancelledError if the token was cancelled, to be used at safe points of operations
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            Err(CancelledError.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Marks that the current operation handles cancellation cooperatively until the
+    /// returned guard is dropped. Outside of cancellable scopes signals terminate the process.
+    pub fn cancellable_scope(&self) -> CancellableScope {
+        self.inner.active_scopes.fetch_add(1, Ordering::SeqCst);
+        CancellableScope {
+            token: self.clone(),
+        }
+    }
+
+    fn has_active_scope(&self) -> bool {
+        self.inner.active_scopes.load(Ordering::SeqCst) > 0
+    }
+
+    /// Tracks a file or directory which is being written. Unless the returned guard is marked as
+    /// done, the path is deleted on cancellation.
+    pub fn track_in_progress_path(&self, path: &Path) -> InProgressPath {
+        self.inner
+            .in_progress_paths
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf());
+        InProgressPath {
+            token: self.clone(),
+            path: path.to_path_buf(),
+            done: false,
+        }
+    }
+
+    fn untrack_in_progress_path(&self, path: &Path) {
+        self.inner.in_progress_paths.lock().unwrap().remove(path);
+    }
+
+    /// Deletes all paths which are still in progress
+    pub fn cleanup(&self) {
+        let paths = std::mem::take(&mut *self.inner.in_progress_paths.lock().unwrap());
+        for path in paths {
+            delete_in_progress_path(&path);
+        }
+    }
+}
+
+impl Debug for CancellationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+pub struct CancellableScope {
+    token: CancellationToken,
+}
+
+impl Drop for CancellableScope {
+    fn drop(&mut self) {
+        self.token
+            .inner
+            .active_scopes
+            .fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct InProgressPath {
+    token: CancellationToken,
+    path: PathBuf,
+    done: bool,
+}
+
+impl InProgressPath {
+    pub fn done(mut self) {
+        self.done = true;
+    }
+}
+
+impl Drop for InProgressPath {
+    fn drop(&mut self) {
+        self.token.untrack_in_progress_path(&self.path);
+        if !self.done && self.token.is_cancelled() {
+            delete_in_progress_path(&self.path);
+        }
+    }
+}
+
+fn delete_in_progress_path(path: &Path) {
+    let result = if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        std::fs::remove_file(path)
+    } else {
+        return;
+    };
+
+    match result {
+        Ok(()) => {
+            log_warn_action(
+                "Deleted",
+                format!("incomplete artifact {}", path.log_color_highlight()),
+            );
+        }
+        Err(err) => {
+            debug!(path = %path.display(), err = %err, "Failed to delete incomplete artifact");
+        }
+    }
+}
+
+/// Handles SIGINT and SIGTERM for the lifetime of the command.
+///
+/// The first signal cancels the token if there is an active cancellable scope, otherwise (and on
+/// a second signal) in-progress artifacts are deleted and the process exits immediately.
+pub async fn cancel_on_signals(token: CancellationToken) {
+    let mut forced = false;
+    loop {
+        if !wait_for_signal().await {
+            return;
+        }
+
+        set_log_output(Output::Stderr);
+        logln("");
+
+        if forced || !token.has_active_scope() {
+            token.cleanup();
+            std::process::exit(CANCELLED_EXIT_CODE as i32);
+        }
+
+        log_warn_action(
+            "Cancelling",
+            "after the current step, press Ctrl-C again to exit immediately",
+        );
+        token.cancel();
+        forced = true;
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> bool {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+        return tokio::signal::ctrl_c().await.is_ok();
+    };
+
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result.is_ok(),
+        result = terminate.recv() => result.is_some(),
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> bool {
+    tokio::signal::ctrl_c().await.is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cancellation::CancellationToken;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn in_progress_paths_are_deleted_on_cancellation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let done_path = temp_dir.path().join("done.wasm");
+        let incomplete_path = temp_dir.path().join("incomplete.wasm");
+        std::fs::write(&done_path, "done").unwrap();
+        std::fs::write(&incomplete_path, "incomplete").unwrap();
+
+        let token = CancellationToken::default();
+        let done = token.track_in_progress_path(&done_path);
+        let incomplete = token.track_in_progress_path(&incomplete_path);
+
+        check!(token.check().is_ok());
+        token.cancel();
+        check!(token.check().is_err());
+
+        done.done();
+        drop(incomplete);
+
+        check!(done_path.exists());
+        check!(!incomplete_path.exists());
+    }
+
+    #[test]
+    fn in_progress_paths_are_kept_without_cancellation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("failed.wasm");
+        std::fs::write(&path, "failed").unwrap();
+
+        let token = CancellationToken::default();
+        drop(token.track_in_progress_path(&path));
+
+        check!(path.exists());
+    }
+}
@@ -12,24 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::command::alias::AliasSubcommand;
 use crate::command::api::ApiSubcommand;
 use crate::command::app::AppSubcommand;
+use crate::command::cache::CacheSubcommand;
 use crate::command::cloud::CloudSubcommand;
 use crate::command::component::ComponentSubcommand;
+use crate::command::history::HistorySubcommand;
 use crate::command::plugin::PluginSubcommand;
 use crate::command::profile::ProfileSubcommand;
 use crate::command::worker::WorkerSubcommand;
 use crate::config::{BuildProfileName, ProfileName};
 use crate::log::LogColorize;
-use crate::model::{Format, WorkerName};
+use crate::model::app::{InitialComponentFile, InitialComponentFileSource};
+use crate::model::{ColorMode, Format, UpdateChannel, WorkerName};
 use crate::{command_name, version};
 use anyhow::{anyhow, bail, Context as AnyhowContext};
 use chrono::{DateTime, Utc};
 use clap::error::{ContextKind, ContextValue, ErrorKind};
-use clap::{self, CommandFactory, Subcommand};
+use clap::{self, ArgMatches, CommandFactory, FromArgMatches, Subcommand};
 use clap::{Args, Parser};
 use clap_verbosity_flag::{ErrorLevel, LogLevel};
 use golem_client::model::ScanCursor;
+use golem_common::model::{
+    ComponentFilePath, ComponentFilePathWithPermissions, ComponentFilePermissions,
+};
 use lenient_bool::LenientBool;
 use std::collections::{BTreeSet, HashMap};
 use std::ffi::OsString;
@@ -90,7 +97,8 @@ impl Verbosity {
 
 #[derive(Debug, Default, Args)]
 pub struct GolemCliGlobalFlags {
-    /// Output format, defaults to text, unless specified by the selected profile
+    /// Output format, unless specified by the GOLEM_FORMAT env var or by the selected profile, defaults
+    /// to text when stdout is a terminal, and to JSON otherwise
     #[arg(long, short, global = true, display_order = 101)]
     pub format: Option<Format>,
 
@@ -126,6 +134,61 @@ pub struct GolemCliGlobalFlags {
     #[arg(long, short, global = true, display_order = 109)]
     pub yes: bool,
 
+    /// Use the given auth token for this invocation, overriding the token of the selected profile.
+    /// Can also be set with the GOLEM_AUTH_TOKEN env var
+    #[arg(long, global = true, display_order = 110)]
+    pub auth_token: Option<Uuid>,
+
+    /// Directory for the temporary application build artifacts (generated WIT, RPC clients and
+    /// other golem-temp content), overriding the manifest defined tempDir. Component WASM paths
+    /// and compiler output directories (e.g. cargo's target dir) are not affected.
+    /// Can also be set with the GOLEM_CLI_BUILD_DIR env var
+    #[arg(long, global = true, display_order = 111)]
+    pub build_dir: Option<PathBuf>,
+
+    /// Reject all commands which could change server side state. Can also be enabled for a
+    /// profile, or with the GOLEM_READ_ONLY env var
+    #[arg(long, global = true, display_order = 112)]
+    pub read_only: bool,
+
+    /// Set a variable for ${VAR} references in application manifests, overriding manifest
+    /// defined variables and environment variables. Can be repeated
+    #[arg(
+        long = "set",
+        global = true,
+        value_parser = parse_key_val,
+        value_name = "KEY=VAL",
+        display_order = 113
+    )]
+    pub manifest_variables: Vec<(String, String)>,
+
+    /// Script mode: print the result, or the error of a failed command, as a single JSON
+    /// document to stdout, and all other output to stderr. Can also be enabled with the
+    /// GOLEM_SCRIPT env var
+    #[arg(long, global = true, display_order = 114)]
+    pub script: bool,
+
+    /// Colored output: auto, always or never. Auto disables colors when the NO_COLOR env var
+    /// is set or the output is not a terminal. Can also be set with the GOLEM_COLOR env var
+    #[arg(long, global = true, display_order = 115)]
+    pub color: Option<ColorMode>,
+
+    /// Only use ASCII characters in logs and text views, for terminals and CI systems which
+    /// mangle Unicode box-drawing characters. Can also be enabled with the GOLEM_ASCII env var
+    #[arg(long, global = true, display_order = 116)]
+    pub ascii: bool,
+
+    /// Git repository to use for the golem-rust dependency of generated RPC clients, instead of
+    /// the released version. Ignored when GOLEM_RUST_PATH is set. Can also be set with the
+    /// GOLEM_RUST_GIT env var
+    #[arg(long = "wasm-rpc-git-override", global = true, display_order = 117)]
+    pub golem_rust_git: Option<String>,
+
+    /// Git branch to use together with --wasm-rpc-git-override. Can also be set with the
+    /// GOLEM_RUST_GIT_BRANCH env var
+    #[arg(long = "wasm-rpc-git-branch", global = true, display_order = 118)]
+    pub golem_rust_git_branch: Option<String>,
+
     #[command(flatten)]
     pub verbosity: Verbosity,
 
@@ -144,11 +207,20 @@ pub struct GolemCliGlobalFlags {
     pub http_batch_size: Option<u64>,
 
     #[arg(skip)]
-    pub auth_token: Option<Uuid>,
+    pub name_cache_ttl: Option<u64>,
+
+    // Subcommand path of the parsed command, used for resolving the per-command profile defaults
+    #[arg(skip)]
+    pub command_path: Vec<String>,
 }
 
 impl GolemCliGlobalFlags {
     pub fn with_env_overrides(mut self) -> GolemCliGlobalFlags {
+        if self.format.is_none() {
+            // Invalid values are reported by GolemCliCommand::try_parse_from_lenient
+            self.format = env_format().ok().flatten();
+        }
+
         if self.profile.is_none() {
             if let Ok(profile) = std::env::var("GOLEM_PROFILE") {
                 self.profile = Some(profile.into());
@@ -176,6 +248,51 @@ impl GolemCliGlobalFlags {
             }
         }
 
+        if self.build_dir.is_none() {
+            if let Ok(build_dir) = std::env::var("GOLEM_CLI_BUILD_DIR") {
+                self.build_dir = Some(PathBuf::from(build_dir));
+            }
+        }
+
+        if !self.read_only {
+            if let Ok(read_only) = std::env::var("GOLEM_READ_ONLY") {
+                self.read_only = read_only
+                    .parse::<LenientBool>()
+                    .map(|b| b.into())
+                    .unwrap_or_default()
+            }
+        }
+
+        if !self.script {
+            if let Ok(script) = std::env::var("GOLEM_SCRIPT") {
+                self.script = script
+                    .parse::<LenientBool>()
+                    .map(|b| b.into())
+                    .unwrap_or_default()
+            }
+        }
+
+        if self.color.is_none() {
+            if let Ok(color) = std::env::var("GOLEM_COLOR") {
+                self.color = Some(
+                    color
+                        .parse::<ColorMode>()
+                        .map_err(|err| anyhow!(err))
+                        .context("Failed to parse GOLEM_COLOR")
+                        .unwrap(),
+                );
+            }
+        }
+
+        if !self.ascii {
+            if let Ok(ascii) = std::env::var("GOLEM_ASCII") {
+                self.ascii = ascii
+                    .parse::<LenientBool>()
+                    .map(|b| b.into())
+                    .unwrap_or_default()
+            }
+        }
+
         if let Ok(offline) = std::env::var("GOLEM_WASM_RPC_OFFLINE") {
             self.wasm_rpc_offline = offline
                 .parse::<LenientBool>()
@@ -195,6 +312,18 @@ impl GolemCliGlobalFlags {
             }
         }
 
+        if self.golem_rust_git.is_none() {
+            if let Ok(git) = std::env::var("GOLEM_RUST_GIT") {
+                self.golem_rust_git = Some(git);
+            }
+        }
+
+        if self.golem_rust_git_branch.is_none() {
+            if let Ok(branch) = std::env::var("GOLEM_RUST_GIT_BRANCH") {
+                self.golem_rust_git_branch = Some(branch);
+            }
+        }
+
         if let Ok(batch_size) = std::env::var("GOLEM_HTTP_BATCH_SIZE") {
             self.http_batch_size = Some(
                 batch_size
@@ -206,13 +335,23 @@ impl GolemCliGlobalFlags {
             )
         }
 
-        if let Ok(auth_token) = std::env::var("GOLEM_AUTH_TOKEN") {
-            self.auth_token = Some(
-                auth_token
-                    .parse()
-                    .context("Failed to parse GOLEM_AUTH_TOKEN, expected uuid")
+        if let Ok(ttl) = std::env::var("GOLEM_NAME_CACHE_TTL") {
+            self.name_cache_ttl = Some(
+                ttl.parse()
+                    .with_context(|| format!("Failed to parse GOLEM_NAME_CACHE_TTL: {}", ttl))
                     .unwrap(),
-            );
+            )
+        }
+
+        if self.auth_token.is_none() {
+            if let Ok(auth_token) = std::env::var("GOLEM_AUTH_TOKEN") {
+                self.auth_token = Some(
+                    auth_token
+                        .parse()
+                        .context("Failed to parse GOLEM_AUTH_TOKEN, expected uuid")
+                        .unwrap(),
+                );
+            }
         }
 
         self
@@ -242,7 +381,7 @@ pub struct GolemCliFallbackCommand {
 }
 
 impl GolemCliFallbackCommand {
-    fn try_parse_from<I, T>(args: I, with_env_overrides: bool) -> Self
+    pub fn try_parse_from<I, T>(args: I, with_env_overrides: bool) -> Self
     where
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
@@ -269,6 +408,18 @@ impl GolemCliFallbackCommand {
 }
 
 impl GolemCliCommand {
+    /// Same as try_parse_from, but also returns the matches of the parsed command line
+    pub fn try_parse_with_matches<I, T>(args: I) -> Result<(Self, ArgMatches), clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = Self::command().try_get_matches_from(args)?;
+        let command =
+            Self::from_arg_matches(&matches).map_err(|error| error.format(&mut Self::command()))?;
+        Ok((command, matches))
+    }
+
     pub fn try_parse_from_lenient<I, T>(
         iterator: I,
         with_env_overrides: bool,
@@ -282,12 +433,23 @@ impl GolemCliCommand {
             .map(|arg| arg.into())
             .collect::<Vec<OsString>>();
 
-        match GolemCliCommand::try_parse_from(&args) {
-            Ok(mut command) => {
+        match GolemCliCommand::try_parse_with_matches(&args) {
+            Ok((mut command, matches)) => {
                 if with_env_overrides {
+                    if command.global_flags.format.is_none() {
+                        if let Err(error) = env_format() {
+                            return GolemCliCommandParseResult::Error {
+                                error,
+                                fallback_command: GolemCliFallbackCommand::try_parse_from(
+                                    &args,
+                                    with_env_overrides,
+                                ),
+                            };
+                        }
+                    }
                     command.global_flags = command.global_flags.with_env_overrides()
                 }
-                GolemCliCommandParseResult::FullMatch(command)
+                GolemCliCommandParseResult::FullMatch { command, matches }
             }
             Err(error) => {
                 let fallback_command =
@@ -455,7 +617,11 @@ struct InvalidArgMatcher {
 
 #[allow(clippy::large_enum_variant)]
 pub enum GolemCliCommandParseResult {
-    FullMatch(GolemCliCommand),
+    FullMatch {
+        command: GolemCliCommand,
+        /// Matches of the parsed command line, for inspecting the used subcommands and flags
+        matches: ArgMatches,
+    },
     ErrorWithPartialMatch {
         error: clap::Error,
         fallback_command: GolemCliFallbackCommand,
@@ -524,6 +690,39 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: CloudSubcommand,
     },
+    /// Manage the local journal of mutating commands
+    History {
+        #[clap(subcommand)]
+        subcommand: HistorySubcommand,
+    },
+    /// Manage the local cache of resolved project names
+    Cache {
+        #[clap(subcommand)]
+        subcommand: CacheSubcommand,
+    },
+    /// Manage user defined command aliases
+    Alias {
+        #[clap(subcommand)]
+        subcommand: AliasSubcommand,
+    },
+    /// Update the CLI binary to the latest or to a specific release
+    SelfUpdate {
+        /// Release channel to update from
+        #[arg(long, value_enum, default_value_t = UpdateChannel::Stable, conflicts_with = "version")]
+        channel: UpdateChannel,
+        /// Install a specific version instead of the latest one from the channel
+        #[arg(long)]
+        version: Option<String>,
+        /// Only report whether an update is available, without installing it
+        #[arg(long)]
+        check: bool,
+    },
+    /// Serve CLI operations over JSON-RPC for editor integrations and other tools
+    Serve {
+        /// Exchange JSON-RPC 2.0 messages on stdin and stdout, one message per line
+        #[arg(long, required = true)]
+        stdio: bool,
+    },
     /// Generate shell completion
     Completion {
         /// Selects shell
@@ -534,7 +733,9 @@ pub enum GolemCliSubcommand {
 pub mod shared_args {
     use crate::cloud::AccountId;
     use crate::model::app::AppBuildStep;
-    use crate::model::{ComponentName, ProjectName, WorkerName, WorkerUpdateMode};
+    use crate::model::{
+        ComponentName, ProjectName, VersionCheckMode, WorkerName, WorkerUpdateMode,
+    };
     use clap::Args;
     use golem_templates::model::GuestLanguage;
 
@@ -603,6 +804,14 @@ pub mod shared_args {
         pub force_build: bool,
     }
 
+    #[derive(Debug, Args, Default)]
+    pub struct AwaitProcessingArg {
+        /// Wait until the server finished processing the deployed component versions (e.g. running
+        /// transformer plugins), and fail if they do not become available
+        #[clap(long)]
+        pub await_processing: bool,
+    }
+
     #[derive(Debug, Args)]
     pub struct BuildArgs {
         /// Select specific build step(s)
@@ -610,6 +819,29 @@ pub mod shared_args {
         pub step: Vec<AppBuildStep>,
         #[command(flatten)]
         pub force_build: ForceBuildArg,
+        /// Build WASM RPC clients reproducibly: pins build settings, remaps build paths, strips
+        /// build environment dependent custom sections, and verifies the result with a second build
+        #[clap(long, default_value = "false")]
+        pub reproducible: bool,
+        /// Keep the temporary build directories of WASM RPC clients after successful builds,
+        /// for inspecting the generated crates. Failed builds always keep them.
+        #[clap(long, default_value = "false")]
+        pub keep_temp: bool,
+        /// Check the WASM RPC version used for clients against the server of the selected
+        /// profile before building them, and warn (default) or fail when they are incompatible
+        #[clap(long, value_name = "MODE", num_args = 0..=1, default_missing_value = "warn")]
+        pub version_check: Option<VersionCheckMode>,
+        /// Treat application validation warnings as errors, and fail the build after listing them
+        #[clap(long, default_value = "false")]
+        pub strict: bool,
+        /// Number of components built and linked at the same time, a component is only built after
+        /// its WASM dependencies. The output of concurrent builds is prefixed with the component name
+        #[clap(long, default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        pub jobs: usize,
+        /// Install the tools pinned in the application manifest into the managed toolchain dir
+        /// when the available version does not match, instead of failing the build
+        #[clap(long, default_value = "false")]
+        pub install_tools: bool,
     }
 
     #[derive(Debug, Args)]
@@ -620,6 +852,7 @@ pub mod shared_args {
         ///   - <COMPONENT>/<WORKER>
         ///   - <PROJECT>/<COMPONENT>/<WORKER>
         ///   - <ACCOUNT>/<PROJECT>/<COMPONENT>/<WORKER>
+        ///   - urn:worker:<COMPONENT_ID>/<WORKER>
         #[arg(verbatim_doc_comment)]
         pub worker_name: WorkerName,
     }
@@ -645,6 +878,28 @@ pub mod shared_args {
         pub redeploy_workers: bool,
     }
 
+    #[derive(Debug, Args)]
+    pub struct OutputFieldArgs {
+        /// Only print the ID of the result (e.g. the worker name or the component ID), for using it in scripts
+        #[clap(long, conflicts_with = "output_field")]
+        pub output_id_only: bool,
+        /// Only print the given field of the result, using the field names of the JSON output,
+        /// nested fields are separated by dots, e.g. `component_version` or `env.SHARD`
+        #[clap(long, value_name = "PATH")]
+        pub output_field: Option<String>,
+    }
+
+    impl OutputFieldArgs {
+        /// Path of the printed field, the id field is used for --output-id-only
+        pub fn field<'a>(&'a self, id_field: &'a str) -> Option<&'a str> {
+            if self.output_id_only {
+                Some(id_field)
+            } else {
+                self.output_field.as_deref()
+            }
+        }
+    }
+
     #[derive(Debug, Args)]
     pub struct ProjectNameOptionalArg {
         /// Project Name
@@ -685,9 +940,12 @@ pub mod shared_args {
 
 pub mod app {
     use crate::command::shared_args::{
-        AppOptionalComponentNames, BuildArgs, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+        AppOptionalComponentNames, AwaitProcessingArg, BuildArgs, ForceBuildArg, StreamArgs,
+        WorkerFunctionArgument, WorkerFunctionName, WorkerUpdateOrRedeployArgs,
     };
-    use crate::model::WorkerUpdateMode;
+    use crate::model::app_graph::AppGraphFormat;
+    use crate::model::trace::TraceGraphFormat;
+    use crate::model::{ComponentName, IdempotencyKey, WorkerName, WorkerUpdateMode};
     use clap::Subcommand;
     use golem_templates::model::GuestLanguage;
 
@@ -715,7 +973,28 @@ pub mod app {
             #[command(flatten)]
             force_build: ForceBuildArg,
             #[command(flatten)]
+            await_processing: AwaitProcessingArg,
+            #[command(flatten)]
             update_or_redeploy: WorkerUpdateOrRedeployArgs,
+            /// Reproduce exactly the component versions recorded in golem.lock, fails on drift
+            #[arg(long)]
+            locked: bool,
+        },
+        /// Build and deploy the application to the local Golem server, then stream the combined
+        /// output of all its workers, including the ones created while running, until stopped
+        ///
+        /// Components calling each other through RPC stubs are wired to the workers of the same
+        /// local server.
+        Run {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            #[command(flatten)]
+            force_build: ForceBuildArg,
+            #[command(flatten)]
+            stream_args: StreamArgs,
+            /// Interval in seconds for discovering newly created workers
+            #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+            worker_poll_interval: u64,
         },
         /// Clean all components in the application or by selection
         Clean {
@@ -740,6 +1019,58 @@ pub mod app {
             #[command(flatten)]
             component_name: AppOptionalComponentNames,
         },
+        /// Show the component and client (stub) dependency graph of all or selected components
+        ///
+        /// Shows which clients are generated for WASM RPC targets, and which clients and components
+        /// are composed into the dependant components.
+        #[command(alias = "graph")]
+        Deps {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            /// Output format of the dependency graph
+            #[arg(long, value_enum, default_value_t = AppGraphFormat::Text)]
+            graph_format: AppGraphFormat,
+        },
+        /// Benchmark a worker function by repeatedly invoking it and reporting throughput and latency
+        /// percentiles, e.g. for measuring the overhead of worker-to-worker calls through RPC stubs
+        Bench {
+            /// Component name of the benchmarked worker
+            #[arg(long)]
+            component: ComponentName,
+            /// Worker function name to benchmark
+            #[arg(long)]
+            function: WorkerFunctionName,
+            /// Worker function arguments in WAVE format
+            arguments: Vec<WorkerFunctionArgument>,
+            /// Function to benchmark for comparison with the same arguments and worker, e.g. one doing
+            /// the same work without RPC calls
+            #[arg(long)]
+            baseline_function: Option<WorkerFunctionName>,
+            /// Worker name to use, defaults to a generated name, the generated worker is deleted
+            /// after the benchmark
+            #[arg(long)]
+            worker: Option<WorkerName>,
+            /// Number of measured invocations
+            #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+            iterations: u32,
+            /// Number of invocations before measuring, e.g. for creating and warming up the workers
+            #[arg(long, default_value_t = 5)]
+            warmup: u32,
+        },
+        /// Reconstruct the worker-to-worker RPC call graph of a request from the oplogs of the
+        /// involved workers, and print it as a diagram
+        Trace {
+            /// Worker which received the request, the call graph starts from this worker
+            #[arg(long)]
+            entry: WorkerName,
+            /// Idempotency key of the traced invocation of the entry worker, defaults to its last
+            /// invocation
+            #[arg(long)]
+            idempotency_key: Option<IdempotencyKey>,
+            /// Diagram format of the call graph
+            #[arg(long, value_enum, default_value_t = TraceGraphFormat::Mermaid)]
+            graph_format: TraceGraphFormat,
+        },
         /// Run custom command
         #[clap(external_subcommand)]
         CustomCommand(Vec<String>),
@@ -749,12 +1080,17 @@ pub mod app {
 pub mod component {
     use crate::command::component::plugin::ComponentPluginSubcommand;
     use crate::command::shared_args::{
-        BuildArgs, ComponentOptionalComponentName, ComponentOptionalComponentNames,
-        ComponentTemplatePositionalArg, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+        AwaitProcessingArg, BuildArgs, ComponentOptionalComponentName,
+        ComponentOptionalComponentNames, ComponentTemplatePositionalArg, ForceBuildArg,
+        OutputFieldArgs, ProjectNameOptionalArg, WorkerFunctionName, WorkerUpdateOrRedeployArgs,
     };
-    use crate::model::WorkerUpdateMode;
+    use crate::command::{parse_initial_component_file, parse_key_val, parse_sha256};
+    use crate::model::app::InitialComponentFile;
+    use crate::model::{ClientDirection, ComponentName, ImportCategory, WorkerUpdateMode};
     use clap::Subcommand;
-    use golem_templates::model::PackageName;
+    use golem_templates::model::{GuestLanguage, GuestLanguageTier, PackageName};
+    use std::path::PathBuf;
+    use url::Url;
 
     #[derive(Debug, Subcommand)]
     pub enum ComponentSubcommand {
@@ -765,10 +1101,79 @@ pub mod component {
             /// Name of the new component package in 'package:name' form
             component_package_name: PackageName,
         },
+        /// Add a new component from a WASM file, without using an application manifest
+        Add {
+            #[command(flatten)]
+            project: ProjectNameOptionalArg,
+            /// Name of the new component
+            component_name: ComponentName,
+            /// Path to the component WASM file
+            #[arg(value_hint = clap::ValueHint::FilePath, required_unless_present = "url")]
+            component_wasm: Option<PathBuf>,
+            /// Stream the component WASM from an http(s) URL instead of a local file,
+            /// without storing it on the local disk
+            #[arg(long, conflicts_with = "component_wasm")]
+            url: Option<Url>,
+            /// Expected SHA-256 checksum of the component WASM streamed from --url, in hex form,
+            /// the component is not created if the checksum does not match
+            #[arg(long, requires = "url", value_parser = parse_sha256, value_name = "HEX")]
+            sha256: Option<String>,
+            /// Create an ephemeral component, defaults to durable
+            #[arg(long)]
+            ephemeral: bool,
+            /// Initial file or directory to add to the worker filesystem in SOURCE:PERMISSIONS:TARGET form,
+            /// where PERMISSIONS is "ro" or "rw", e.g. ./assets:ro:/assets
+            ///
+            /// Can be used multiple times. SOURCE can be a local path relative to the current directory
+            /// or an http(s) URL.
+            #[arg(long = "files", value_parser = parse_initial_component_file, value_name = "SOURCE:PERMISSIONS:TARGET")]
+            files: Vec<InitialComponentFile>,
+            /// Adapt a WASI preview1 core module into a component before uploading it
+            #[arg(long, conflicts_with = "url")]
+            auto_adapt: bool,
+            #[command(flatten)]
+            output: OutputFieldArgs,
+        },
         /// List or search component templates
         Templates {
             /// Optional filter for language or template name
             filter: Option<String>,
+            /// Only list templates of the given language
+            #[arg(long)]
+            language: Option<GuestLanguage>,
+            /// Only list templates of languages with the given support tier (1, 2 or 3)
+            #[arg(long)]
+            tier: Option<GuestLanguageTier>,
+            /// Search in the language, name and description of the templates
+            #[arg(long)]
+            search: Option<String>,
+            /// Print the file tree of a template instead of listing templates, in LANGUAGE[/TEMPLATE] form
+            #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["filter", "language", "tier", "search"])]
+            tree: Option<String>,
+        },
+        /// Preview the WASM RPC client WIT generated for a WIT root, without generating and
+        /// building the client crate
+        ///
+        /// With `--direction import` a mock WIT is generated instead, exporting the interfaces
+        /// imported by the component. A component implementing it can be composed into the
+        /// component as a `wasm` dependency, for testing the component in isolation.
+        ClientWit {
+            /// WIT root of the component to generate the client for
+            #[arg(long, value_hint = clap::ValueHint::DirPath)]
+            source_wit_root: PathBuf,
+            /// World to generate the client for, optional if the root package has a single world
+            #[arg(long)]
+            world: Option<String>,
+            /// Generate a client for the exported interfaces, or a mock of the imported interfaces
+            #[arg(long, value_enum, default_value_t = ClientDirection::Export)]
+            direction: ClientDirection,
+            /// Generate the client for an ephemeral component
+            #[arg(long, conflicts_with = "direction")]
+            ephemeral: bool,
+            /// Write the client WIT directory, including its dependencies, to the given directory
+            /// instead of printing the client WIT
+            #[arg(long, value_hint = clap::ValueHint::DirPath)]
+            output_dir: Option<PathBuf>,
         },
         /// Build component(s) based on the current directory or by selection
         Build {
@@ -784,7 +1189,22 @@ pub mod component {
             #[command(flatten)]
             force_build: ForceBuildArg,
             #[command(flatten)]
+            await_processing: AwaitProcessingArg,
+            #[command(flatten)]
             update_or_redeploy: WorkerUpdateOrRedeployArgs,
+            /// Only show the differences between the deployed components and the manifest,
+            /// without deploying them
+            #[arg(long, conflicts_with_all = ["update_workers", "redeploy_workers"])]
+            plan: bool,
+            /// Reproduce exactly the component versions recorded in golem.lock, fails on drift
+            #[arg(long, conflicts_with = "plan")]
+            locked: bool,
+            /// Human-readable note attached to the new component version(s), shown by 'component list'
+            #[arg(long, conflicts_with_all = ["plan", "locked"])]
+            note: Option<String>,
+            /// Metadata attached to the new component version(s), can be specified multiple times
+            #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL", conflicts_with_all = ["plan", "locked"])]
+            set_meta: Vec<(String, String)>,
         },
         /// Clean component(s) based on the current directory or by selection
         Clean {
@@ -802,6 +1222,13 @@ pub mod component {
             component_name: ComponentOptionalComponentName,
             /// Optional component version to get
             version: Option<u64>,
+            #[command(flatten)]
+            output: OutputFieldArgs,
+        },
+        /// Compare the latest deployed version of component(s) with the application manifest
+        Diff {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentNames,
         },
         /// Try to automatically update all existing workers of the selected component to the latest version
         UpdateWorkers {
@@ -826,6 +1253,29 @@ pub mod component {
             #[command(flatten)]
             component_name: ComponentOptionalComponentNames,
         },
+        /// Print the JSON Schema of a deployed component function's parameters and result,
+        /// derived from its WIT types
+        FunctionSchema {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Function name, fuzzy matched against the exported functions of the component
+            #[arg(long)]
+            function: WorkerFunctionName,
+            /// Optional component version, defaults to the latest version
+            #[arg(long)]
+            version: Option<u64>,
+        },
+        /// Inspect a local component WASM, listing its imports and exports, and auditing its
+        /// sensitive imports (sockets, filesystem, environment, clocks, HTTP, non-WASI)
+        Inspect {
+            /// Path to the component WASM file
+            #[arg(value_hint = clap::ValueHint::FilePath)]
+            component_wasm: PathBuf,
+            /// Fail if the component imports any interface of the category, can be used multiple
+            /// times, e.g. for gating third-party components in CI before uploading them
+            #[arg(long, value_enum)]
+            deny: Vec<ImportCategory>,
+        },
     }
 
     pub mod plugin {
@@ -852,13 +1302,17 @@ pub mod component {
                 /// List of parameters (key-value pairs) passed to the plugin
                 #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL")]
                 param: Vec<(String, String)>,
+                /// Expected latest version of the component, installing creates a new version based on it
+                #[arg(long)]
+                component_version: Option<u64>,
             },
             /// Get the installed plugins of the component
             Get {
                 #[command(flatten)]
                 component_name: ComponentOptionalComponentName,
-                /// The version of the component
-                version: Option<u64>,
+                /// The version of the component, defaults to the latest version
+                #[arg(long)]
+                component_version: Option<u64>,
             },
             /// Update component plugin
             Update {
@@ -874,6 +1328,9 @@ pub mod component {
                 /// Updated list of parameters (key-value pairs) passed to the plugin
                 #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL")]
                 param: Vec<(String, String)>,
+                /// Expected latest version of the component, updating creates a new version based on it
+                #[arg(long)]
+                component_version: Option<u64>,
             },
             /// Uninstall a plugin for selected component
             Uninstall {
@@ -883,6 +1340,9 @@ pub mod component {
                 /// Installation id of the plugin to uninstall
                 #[arg(long)]
                 installation_id: PluginInstallationId,
+                /// Expected latest version of the component, uninstalling creates a new version based on it
+                #[arg(long)]
+                component_version: Option<u64>,
             },
         }
     }
@@ -890,14 +1350,21 @@ pub mod component {
 
 pub mod worker {
     use crate::command::parse_cursor;
+    use crate::command::parse_instant;
     use crate::command::parse_key_val;
     use crate::command::shared_args::{
-        ComponentOptionalComponentName, NewWorkerArgument, StreamArgs, WorkerFunctionArgument,
-        WorkerFunctionName, WorkerNameArg,
+        ComponentOptionalComponentName, NewWorkerArgument, OutputFieldArgs, ProjectNameOptionalArg,
+        StreamArgs, WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
+    };
+    use crate::model::{
+        ApiDefinitionId, ApiDefinitionVersion, IdempotencyKey, InvokeTimeoutAction, ProjectName,
+        WorkerFunctionFileArgument, WorkerLabel, WorkerListColumn, WorkerLogLevel, WorkerLogStream,
+        WorkerUpdateMode,
     };
-    use crate::model::{IdempotencyKey, WorkerUpdateMode};
+    use chrono::{DateTime, Utc};
     use clap::Subcommand;
     use golem_client::model::ScanCursor;
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum WorkerSubcommand {
@@ -910,49 +1377,173 @@ pub mod worker {
             /// Worker environment variables
             #[arg(short, long, value_parser = parse_key_val, value_name = "ENV=VAL")]
             env: Vec<(String, String)>,
+            /// Worker label, can be used multiple times. Labels are stored as GOLEM_LABEL_<KEY>
+            /// environment variables, and can be used for selecting workers with --selector
+            #[arg(long, value_name = "KEY=VALUE")]
+            label: Vec<WorkerLabel>,
+            /// Wait until the worker is instantiated and becomes idle or running,
+            /// and fail with the instantiation error if it does not start
+            #[arg(long)]
+            wait_until_ready: bool,
+            /// Maximum time to wait for the worker to become ready, in seconds
+            #[arg(
+                long,
+                requires = "wait_until_ready",
+                value_name = "SECONDS",
+                default_value_t = 60
+            )]
+            ready_timeout: u64,
+            #[command(flatten)]
+            output: OutputFieldArgs,
         },
         // TODO: json args
         /// Invoke (or enqueue invocation for) worker
         Invoke {
             #[command(flatten)]
             worker_name: WorkerNameArg,
-            /// Worker function name to invoke
-            function_name: WorkerFunctionName,
+            /// Worker function name to invoke.
+            ///
+            /// Resource methods can be called with inline resource construction:
+            /// `[INTERFACE.]{RESOURCE(ARGS).METHOD}`, e.g. `api.{cart(user-1).add-item}`, where
+            /// ARGS are the comma separated WAVE constructor arguments (quotes can be omitted for strings)
+            ///
+            /// Can be omitted with --interactive, to select the function from the exported ones.
+            #[clap(required_unless_present = "interactive")]
+            function_name: Option<WorkerFunctionName>,
             /// Worker function arguments in WAVE format
             arguments: Vec<WorkerFunctionArgument>,
+            /// Worker function argument in WAVE format, as an alternative to the positional arguments,
+            /// e.g. for values starting with '-'
+            ///
+            /// Can be used multiple times, in the order of the function parameters.
+            #[clap(long = "arg", value_name = "WAVE", conflicts_with = "arguments")]
+            named_arguments: Vec<WorkerFunctionArgument>,
+            /// Read a list<u8> argument from a file, in POSITION=PATH form, where POSITION is the
+            /// 1-based position of the function parameter, e.g. 2=./image.png
+            ///
+            /// Can be used multiple times. The file arguments are inserted between the WAVE arguments.
+            #[clap(long = "file-arg", value_name = "POSITION=PATH")]
+            file_args: Vec<WorkerFunctionFileArgument>,
+            /// Select the function with a fuzzy selector (unless the function name is given),
+            /// then prompt for the arguments field by field, based on the parameter types
+            #[clap(long, conflicts_with_all = ["arguments", "named_arguments", "file_args"])]
+            interactive: bool,
             /// Enqueue invocation, and do not wait for it
             #[clap(long, short)]
             enqueue: bool,
             /// Set idempotency key for the call, use "-" for auto generated key
             #[clap(long, short)]
             idempotency_key: Option<IdempotencyKey>,
-            #[clap(long, short)]
-            /// Connect to the worker before invoke (the worker must already exist)
-            /// and live stream its standard output, error and log channels
+            /// Live stream the standard output, error and log channels of the worker while waiting
+            /// for the result, the streamed lines are interleaved with the result
+            ///
+            /// If the worker does not exist yet, the stream is connected once the invocation created it.
+            #[clap(long, short, conflicts_with = "enqueue")]
             stream: bool,
             #[command(flatten)]
             stream_args: StreamArgs,
+            /// Stop waiting for the invocation result after the given number of seconds,
+            /// and exit with code 124
+            #[clap(long, conflicts_with = "enqueue", value_name = "SECONDS")]
+            timeout: Option<u64>,
+            /// Action to take on the worker when the invocation times out: cancel or interrupt.
+            /// Cancel only succeeds if the invocation has not started yet.
+            #[clap(long, requires = "timeout")]
+            on_timeout: Option<InvokeTimeoutAction>,
+            /// Print the invocation result as the raw protocol JSON, without decoding it
+            /// into WAVE using the function's result types
+            #[clap(long, conflicts_with = "enqueue")]
+            raw: bool,
+        },
+        /// Print the equivalent raw HTTP request of an invocation as a curl command
+        Curl {
+            #[command(flatten)]
+            worker_name: WorkerNameArg,
+            /// Worker function name to invoke.
+            ///
+            /// Resource methods can be called with inline resource construction:
+            /// `[INTERFACE.]{RESOURCE(ARGS).METHOD}`, e.g. `api.{cart(user-1).add-item}`, where
+            /// ARGS are the comma separated WAVE constructor arguments (quotes can be omitted for strings)
+            function_name: WorkerFunctionName,
+            /// Worker function arguments in WAVE format
+            arguments: Vec<WorkerFunctionArgument>,
+            /// Worker function argument in WAVE format, as an alternative to the positional arguments,
+            /// e.g. for values starting with '-'
+            ///
+            /// Can be used multiple times, in the order of the function parameters.
+            #[clap(long = "arg", value_name = "WAVE", conflicts_with = "arguments")]
+            named_arguments: Vec<WorkerFunctionArgument>,
+            /// Read a list<u8> argument from a file, in POSITION=PATH form, where POSITION is the
+            /// 1-based position of the function parameter, e.g. 2=./image.png
+            ///
+            /// Can be used multiple times. The file arguments are inserted between the WAVE arguments.
+            #[clap(long = "file-arg", value_name = "POSITION=PATH")]
+            file_args: Vec<WorkerFunctionFileArgument>,
+            /// Use the enqueue endpoint instead of invoke-and-await
+            #[clap(long, short)]
+            enqueue: bool,
+            /// Set idempotency key for the call, use "-" for auto generated key
+            #[clap(long, short)]
+            idempotency_key: Option<IdempotencyKey>,
+            /// Show the authorization token instead of redacting it
+            #[clap(long)]
+            show_token: bool,
+            /// Execute the request using curl after printing it
+            #[clap(long)]
+            execute: bool,
         },
         /// Get worker metadata
         Get {
             #[command(flatten)]
             worker_name: WorkerNameArg,
+            #[command(flatten)]
+            output: OutputFieldArgs,
         },
         /// Deletes a worker
         Delete {
             #[command(flatten)]
             worker_name: WorkerNameArg,
         },
+        /// Deletes all workers of the selected components matching the filters, after confirmation
+        DeleteMany {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Filter for selecting the deleted workers in form of `property op value`, same as for "worker list".
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long, required_unless_present_any = ["selector", "all"])]
+            filter: Vec<String>,
+            /// Select workers by label, in KEY=VALUE form, same as `--filter "env.GOLEM_LABEL_<KEY> = VALUE"`.
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long, value_name = "KEY=VALUE")]
+            selector: Vec<WorkerLabel>,
+            /// Delete all workers of the selected components, required when no filter or selector is used
+            #[arg(long, conflicts_with_all = ["filter", "selector"])]
+            all: bool,
+            /// Only list the matching workers, without deleting them
+            #[arg(long)]
+            dry_run: bool,
+        },
         /// List worker metadata
         List {
             #[command(flatten)]
             component_name: ComponentOptionalComponentName,
+            /// Search workers in all components of the selected project concurrently,
+            /// instead of the selected component
+            #[arg(long, conflicts_with_all = ["component_name", "scan_cursor"])]
+            all_components: bool,
+            /// Project to search in when using --all-components, defaults to the default project
+            #[arg(long, requires = "all_components")]
+            project: Option<ProjectName>,
             /// Filter for worker metadata in form of `property op value`.
             ///
             /// Filter examples: `name = worker-name`, `version >= 0`, `status = Running`, `env.var1 = value`.
             /// Can be used multiple times (AND condition is applied between them)
             #[arg(long)]
             filter: Vec<String>,
+            /// Select workers by label, in KEY=VALUE form, same as `--filter "env.GOLEM_LABEL_<KEY> = VALUE"`.
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long, value_name = "KEY=VALUE")]
+            selector: Vec<WorkerLabel>,
             /// Cursor position, if not provided, starts from the beginning.
             ///
             /// Cursor can be used to get the next page of results, use the cursor returned
@@ -967,14 +1558,85 @@ pub mod worker {
             /// When set to true it queries for most up-to-date status for each worker, default is false
             #[arg(long, default_value_t = false)]
             precise: bool,
+            /// Custom column to show instead of the default ones, can be used multiple times.
+            ///
+            /// Column examples: `env.SHARD`, `worker_name`, `status`, `component_version`, `created_at`.
+            /// Only affects text and CSV output, values are evaluated client side from the worker metadata.
+            /// JSON and YAML output always contain the whole worker metadata.
+            #[arg(long)]
+            column: Vec<WorkerListColumn>,
+        },
+        /// Rank workers by the number of pending (queued) invocations, for finding overloaded workers
+        Backlog {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Filter for worker metadata in form of `property op value`, same as for "worker list".
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long)]
+            filter: Vec<String>,
+            /// Maximum number of shown workers
+            #[arg(long, default_value_t = 20)]
+            limit: usize,
+            /// Also show workers without pending invocations
+            #[arg(long)]
+            all: bool,
         },
         /// Connect to a worker and live stream its standard output, error and log channels
         Stream {
             #[command(flatten)]
             worker_name: WorkerNameArg,
+            /// Only show events emitted at or after the given timestamp, e.g. 2025-01-01T00:00:00Z,
+            /// for skipping the already seen events replayed on connection
+            #[arg(long, value_parser = parse_instant)]
+            since: Option<DateTime<Utc>>,
+            #[command(flatten)]
+            stream_args: StreamArgs,
+        },
+        /// Print the output and logs of a worker, reconstructed from its oplog, and optionally
+        /// follow its live output
+        Logs {
+            #[command(flatten)]
+            worker_name: WorkerNameArg,
+            /// Keep streaming the live output after printing the recorded one
+            #[arg(long)]
+            follow: bool,
+            /// Only show output emitted at or after the given timestamp, e.g. 2025-01-01T00:00:00Z
+            #[arg(long, value_parser = parse_instant)]
+            since: Option<DateTime<Utc>>,
+            /// Minimum level of the shown log entries, stdout and stderr are not filtered by level
+            #[arg(long, value_enum)]
+            level: Option<WorkerLogLevel>,
+            /// Only show the given output streams, can be used multiple times, defaults to all streams
+            #[arg(long, value_enum)]
+            stream: Vec<WorkerLogStream>,
             #[command(flatten)]
             stream_args: StreamArgs,
         },
+        /// Continuously watch the workers matching the filters, and run a hook (or exit with --once)
+        /// when any of them transitions to Failed or Exited
+        Monitor {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Filter for selecting the watched workers in form of `property op value`, same as for "worker list".
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long)]
+            filter: Vec<String>,
+            /// Select workers by label, in KEY=VALUE form, same as `--filter "env.GOLEM_LABEL_<KEY> = VALUE"`.
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long, value_name = "KEY=VALUE")]
+            selector: Vec<WorkerLabel>,
+            /// Polling interval in seconds
+            #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+            interval: u64,
+            /// Command to execute for every worker transitioning to Failed or Exited. The
+            /// GOLEM_COMPONENT_NAME, GOLEM_WORKER_NAME, GOLEM_WORKER_STATUS and GOLEM_WORKER_ERROR
+            /// environment variables are set for the command.
+            #[arg(long)]
+            on_failure: Option<String>,
+            /// Exit with a non-zero exit code after the first detected failure
+            #[arg(long)]
+            once: bool,
+        },
         /// Updates a worker
         Update {
             #[command(flatten)]
@@ -984,6 +1646,60 @@ pub mod worker {
             /// The new version of the updated worker
             target_version: u64,
         },
+        /// Updates many workers of the selected components in batches, optionally awaiting each batch
+        /// and stopping or rolling back the rollout on failures
+        UpdateMany {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// Filter for selecting the updated workers in form of `property op value`, same as for "worker list".
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long)]
+            filter: Vec<String>,
+            /// Select workers by label, in KEY=VALUE form, same as `--filter "env.GOLEM_LABEL_<KEY> = VALUE"`.
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long, value_name = "KEY=VALUE")]
+            selector: Vec<WorkerLabel>,
+            /// Update mode - auto or manual, defaults to "auto"
+            #[arg(long, short, default_value_t = WorkerUpdateMode::Automatic)]
+            update_mode: WorkerUpdateMode,
+            /// The new version of the updated workers, defaults to the latest component version
+            #[arg(long, short)]
+            target_version: Option<u64>,
+            /// Number of workers updated in one batch
+            #[arg(long, default_value_t = 10, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+            batch_size: usize,
+            /// Maximum number of workers being updated at the same time, limits the batch size
+            #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+            max_unavailable: Option<usize>,
+            /// Wait for the workers of each batch to reach the target version before continuing with the next batch
+            #[arg(long = "await")]
+            await_update: bool,
+            /// Maximum time in seconds to wait for the workers of one batch when using --await
+            #[arg(long, default_value_t = 300)]
+            await_timeout: u64,
+            /// Maximum ratio (between 0.0 and 1.0) of failed worker updates tolerated before the rollout is stopped,
+            /// requires --await, as without it only the failures of triggering the updates are known
+            #[arg(long, default_value_t = 0.0, requires = "await_update")]
+            max_failure_ratio: f64,
+            /// Revert the already updated workers to their original version when the rollout is stopped,
+            /// requires --await
+            #[arg(long, requires = "await_update")]
+            rollback_on_failure: bool,
+        },
+        /// Sets environment variables of an existing worker
+        SetEnv {
+            #[command(flatten)]
+            worker_name: WorkerNameArg,
+            /// Environment variables to set, merged with the current environment of the worker
+            #[arg(required = true, value_parser = parse_key_val, value_name = "ENV=VAL")]
+            env: Vec<(String, String)>,
+            /// Delete and recreate the worker with the merged environment, the state of the worker
+            /// is lost. The worker is created with the latest component version, then updated back
+            /// to its original version. Required until the server supports updating the environment
+            /// of existing workers.
+            #[arg(long)]
+            restart: bool,
+        },
         /// Interrupts a running worker
         Interrupt {
             #[command(flatten)]
@@ -1001,27 +1717,35 @@ pub mod worker {
             #[command(flatten)]
             worker_name: WorkerNameArg,
         },
-        /// Queries and dumps a worker's full oplog
+        /// Queries and dumps a worker's oplog
         Oplog {
             #[command(flatten)]
             worker_name: WorkerNameArg,
             /// Index of the first oplog entry to get. If missing, the whole oplog is returned
-            #[arg(long, conflicts_with = "query")]
-            from: Option<u64>,
+            #[arg(long, alias = "from", conflicts_with = "query")]
+            from_index: Option<u64>,
+            /// Maximum number of oplog entries to get
+            #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+            count: Option<u64>,
             /// Lucene query to look for oplog entries. If missing, the whole oplog is returned
-            #[arg(long, conflicts_with = "from")]
+            #[arg(long, conflicts_with = "from_index")]
             query: Option<String>,
         },
-        /// Reverts a worker by undoing its last recorded operations
+        /// Reverts a worker by undoing its last recorded operations, then shows the resulting
+        /// worker state
         Revert {
             #[command(flatten)]
             worker_name: WorkerNameArg,
-            /// Revert by oplog index
-            #[arg(long, conflicts_with = "number_of_invocations")]
-            last_oplog_index: Option<u64>,
-            /// Revert by number of invocations
-            #[arg(long, conflicts_with = "last_oplog_index")]
-            number_of_invocations: Option<u64>,
+            /// Revert to the given oplog index, dropping all later oplog entries
+            #[arg(long, alias = "last-oplog-index", conflicts_with = "undo_invocations")]
+            to_oplog_index: Option<u64>,
+            /// Revert by undoing the given number of last invocations
+            #[arg(
+                long,
+                alias = "number-of-invocations",
+                conflicts_with = "to_oplog_index"
+            )]
+            undo_invocations: Option<u64>,
         },
         /// Cancels an enqueued invocation if it has not started yet
         CancelInvocation {
@@ -1030,6 +1754,33 @@ pub mod worker {
             /// Idempotency key of the invocation to be cancelled
             idempotency_key: IdempotencyKey,
         },
+        /// Convert HTTP requests saved by HTTP testing tools into direct worker invocations
+        ///
+        /// The requests are resolved against the routes of the API definition they were sent to,
+        /// then the worker names and function arguments are evaluated from the route bindings.
+        /// Text output prints the equivalent `worker invoke` commands, use `--format json` or
+        /// `--format yaml` for a batch file.
+        ImportInvocations {
+            #[command(flatten)]
+            project: ProjectNameOptionalArg,
+            /// Postman collection (v2.0 or v2.1) file
+            #[arg(
+                long,
+                value_hint = clap::ValueHint::FilePath,
+                required_unless_present = "har",
+                conflicts_with = "har"
+            )]
+            postman: Option<PathBuf>,
+            /// HAR (HTTP Archive) file
+            #[arg(long, value_hint = clap::ValueHint::FilePath)]
+            har: Option<PathBuf>,
+            /// Id of the API definition the requests were sent to
+            #[arg(long)]
+            definition_id: ApiDefinitionId,
+            /// Version of the api definition, optional if the definition has a single version
+            #[arg(long)]
+            definition_version: Option<ApiDefinitionVersion>,
+        },
     }
 }
 
@@ -1066,6 +1817,7 @@ pub mod api {
 
     pub mod definition {
         use crate::command::shared_args::ProjectNameOptionalArg;
+        use crate::model::api_definition::ApiRouteRequest;
         use crate::model::{ApiDefinitionId, ApiDefinitionVersion, PathBufOrStdin};
         use clap::Subcommand;
 
@@ -1116,6 +1868,84 @@ pub mod api {
                 #[arg(short, long)]
                 id: Option<ApiDefinitionId>,
             },
+            /// Checks an API definition file or a stored API definition for common problems
+            ///
+            /// Reports unbound routes, duplicate routes, invalid Rib scripts and mutating routes
+            /// without security. Use `--format json` or `--format yaml` for machine-readable findings.
+            Lint {
+                #[command(flatten)]
+                project: ProjectNameOptionalArg,
+                /// The Golem API definition file, required unless a stored definition is selected with --id
+                #[arg(
+                    value_hint = clap::ValueHint::FilePath,
+                    required_unless_present = "id",
+                    conflicts_with_all = ["id", "version"]
+                )]
+                definition: Option<PathBufOrStdin>,
+                /// Id of a stored api definition to lint
+                #[arg(short, long, requires = "version")]
+                id: Option<ApiDefinitionId>,
+                /// Version of the stored api definition to lint
+                #[arg(long, requires = "id")]
+                version: Option<ApiDefinitionVersion>,
+                /// Fail on warnings too, not only on errors
+                #[arg(long)]
+                deny_warnings: bool,
+            },
+            /// Resolves a request against the routes of a stored API definition locally
+            ///
+            /// Shows the matched route, the bound component, the Rib scripts with the functions
+            /// they call, and the mapped request parameters, for debugging bindings.
+            Try {
+                #[command(flatten)]
+                project: ProjectNameOptionalArg,
+                /// Api definition id
+                #[arg(short, long)]
+                id: ApiDefinitionId,
+                /// Version of the api definition, optional if the definition has a single version
+                #[arg(long)]
+                version: Option<ApiDefinitionVersion>,
+                /// Request to resolve, in 'METHOD /path?query' form, e.g. 'GET /users/1'
+                #[arg(long)]
+                route: ApiRouteRequest,
+                /// Request body as JSON, or @FILE for reading it from a file
+                #[arg(long, value_name = "JSON|@FILE")]
+                body: Option<String>,
+                /// Request header in NAME:VALUE form, can be specified multiple times
+                #[arg(long = "header", value_name = "NAME:VALUE")]
+                headers: Vec<String>,
+            },
+            /// Clones an existing API definition version into a new version for editing
+            Bump {
+                #[command(flatten)]
+                project: ProjectNameOptionalArg,
+                /// Api definition id
+                #[arg(short, long)]
+                id: ApiDefinitionId,
+                /// Version of the api definition to clone
+                #[arg(long)]
+                from: ApiDefinitionVersion,
+                /// New version of the api definition
+                #[arg(long)]
+                to: ApiDefinitionVersion,
+                /// Create the new version as a draft
+                #[arg(long)]
+                draft: bool,
+            },
+            /// Shows the route level changes between two versions of an API definition
+            Diff {
+                #[command(flatten)]
+                project: ProjectNameOptionalArg,
+                /// Api definition id
+                #[arg(short, long)]
+                id: ApiDefinitionId,
+                /// Base version of the api definition
+                #[arg(long)]
+                from: ApiDefinitionVersion,
+                /// Version of the api definition to compare with the base version
+                #[arg(long)]
+                to: ApiDefinitionVersion,
+            },
             /// Deletes an existing API definition
             Delete {
                 #[command(flatten)]
@@ -1305,9 +2135,21 @@ pub mod plugin {
     use crate::command::shared_args::PluginScopeArgs;
     use crate::model::PathBufOrStdin;
     use clap::Subcommand;
+    use golem_templates::model::{GuestLanguage, PackageName, PluginType};
 
     #[derive(Debug, Subcommand)]
     pub enum PluginSubcommand {
+        /// Create a new plugin project from a template
+        New {
+            /// Name of the new plugin package in 'package:name' form
+            plugin_package_name: PackageName,
+            /// Plugin type
+            #[arg(long = "type", short = 't')]
+            plugin_type: PluginType,
+            /// Language of the plugin project
+            #[arg(long)]
+            language: GuestLanguage,
+        },
         /// List component for the select scope
         List {
             /// The scope to list components from
@@ -1338,6 +2180,72 @@ pub mod plugin {
     }
 }
 
+pub mod alias {
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum AliasSubcommand {
+        /// Add or replace an alias, e.g. "alias add deploy-prod -- --profile prod app deploy"
+        Add {
+            /// Name of the alias, used in place of a command
+            name: String,
+            /// Command line the alias expands to, use "--" before it if it starts with a flag
+            #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+            command: Vec<String>,
+        },
+        /// List the defined aliases
+        List,
+        /// Remove an alias
+        Remove {
+            /// Name of the alias
+            name: String,
+        },
+    }
+}
+
+pub mod cache {
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum CacheSubcommand {
+        /// Clear the local caches, all of them unless selected with the flags
+        #[command(alias = "clean")]
+        Clear {
+            /// Remove the cached name resolutions ($HOME/.golem/name-cache.json)
+            #[arg(long)]
+            names: bool,
+            /// Remove the cached WASM RPC client binaries ($HOME/.cache/golem-stubgen, or GOLEM_STUBGEN_CACHE_DIR)
+            #[arg(long)]
+            stubs: bool,
+        },
+        /// Show the number of entries and the size of the local caches
+        Stats,
+    }
+}
+
+pub mod history {
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum HistorySubcommand {
+        /// Enable recording mutating commands into the local history journal ($HOME/.golem/history.jsonl)
+        Enable,
+        /// Disable recording mutating commands, already recorded entries are kept
+        Disable,
+        /// List recorded commands
+        List {
+            /// Maximum number of listed entries, counting from the most recent one
+            #[arg(long)]
+            limit: Option<usize>,
+        },
+        /// Show a recorded command
+        Show {
+            /// Index of the history entry, as shown by "history list"
+            index: usize,
+        },
+    }
+}
+
 pub mod profile {
     use crate::command::profile::config::ProfileConfigSubcommand;
     use crate::config::{ProfileKind, ProfileName};
@@ -1394,6 +2302,9 @@ pub mod profile {
         Delete {
             /// Profile name to delete
             profile_name: ProfileName,
+            /// Revoke the authentication token of the profile on the server before deleting it
+            #[arg(long)]
+            purge_tokens: bool,
         },
         /// Profile config
         Config {
@@ -1402,18 +2313,59 @@ pub mod profile {
             #[command(subcommand)]
             subcommand: ProfileConfigSubcommand,
         },
+        /// Migrate the config file to the latest config version, a backup of the original config is created
+        Migrate {
+            /// Only show the migrations that would be applied, without changing the config file
+            #[arg(long)]
+            dry_run: bool,
+        },
+        /// Repair an invalid config file, profiles which can still be parsed are kept, the others are dropped and reported
+        Repair {
+            /// Only show the profiles that would be kept and dropped, without changing the config file
+            #[arg(long)]
+            dry_run: bool,
+        },
     }
 
     pub mod config {
+        use crate::config::ProfileConfigKey;
         use crate::model::Format;
         use clap::Subcommand;
 
         #[derive(Debug, Subcommand)]
         pub enum ProfileConfigSubcommand {
-            /// Set default output format for the requested profile
+            /// Show config values of the requested profile, lists all available keys if <KEY> is not specified
+            Get {
+                /// Config key, e.g. url, worker-url, format, default-project, timeout
+                key: Option<ProfileConfigKey>,
+            },
+            /// Validate and set a config value for the requested profile
+            Set {
+                /// Config key, e.g. url, worker-url, format, default-project, timeout
+                key: ProfileConfigKey,
+                /// New value for the key
+                value: String,
+                /// Only set the output format of the given command, e.g. worker-list, the subcommand
+                /// names can be separated by '-' or spaces
+                #[arg(long = "for", value_name = "COMMAND")]
+                for_command: Option<String>,
+            },
+            /// Remove a config value of the requested profile, restoring its default
+            Unset {
+                /// Config key, e.g. url, worker-url, format, default-project, timeout
+                key: ProfileConfigKey,
+                /// Only remove the output format of the given command, e.g. worker-list
+                #[arg(long = "for", value_name = "COMMAND")]
+                for_command: Option<String>,
+            },
+            /// Deprecated, use 'set format <FORMAT>' instead
+            #[command(hide = true)]
             SetFormat {
                 /// CLI output format
                 format: Format,
+                /// Only set the output format of the given command, e.g. worker-list
+                #[arg(long = "for", value_name = "COMMAND")]
+                for_command: Option<String>,
             },
         }
     }
@@ -1421,6 +2373,7 @@ pub mod profile {
 
 pub mod cloud {
     use crate::command::cloud::account::AccountSubcommand;
+    use crate::command::cloud::admin::AdminSubcommand;
     use crate::command::cloud::project::ProjectSubcommand;
     use crate::command::cloud::token::TokenSubcommand;
     use clap::Subcommand;
@@ -1442,11 +2395,41 @@ pub mod cloud {
             #[clap(subcommand)]
             subcommand: TokenSubcommand,
         },
+        /// Administrative commands, requires the Admin role
+        Admin {
+            #[clap(subcommand)]
+            subcommand: AdminSubcommand,
+        },
+    }
+
+    pub mod admin {
+        use crate::cloud::AccountId;
+        use clap::Subcommand;
+
+        #[derive(Debug, Subcommand)]
+        pub enum AdminSubcommand {
+            /// Run a single command as another account, using a short-lived token
+            ///
+            /// The impersonation is always recorded in the history journal, and the token is
+            /// revoked after the command finished.
+            Impersonate {
+                /// The account to impersonate
+                #[arg(long)]
+                account_id: AccountId,
+                /// Lifetime of the impersonation token in minutes
+                #[arg(long, default_value_t = 15, value_parser = clap::value_parser!(u32).range(1..))]
+                expires_in_minutes: u32,
+                /// The command to run as the impersonated account, without the leading command name,
+                /// e.g. `-- worker list`
+                #[arg(last = true, required = true)]
+                command: Vec<String>,
+            },
+        }
     }
 
     pub mod token {
         use crate::command::parse_instant;
-        use crate::model::TokenId;
+        use crate::model::{ProjectName, TokenId, TokenScope};
         use chrono::{DateTime, Utc};
         use clap::Subcommand;
 
@@ -1459,6 +2442,19 @@ pub mod cloud {
                 /// Expiration date of the generated token
                 #[arg(long, value_parser = parse_instant, default_value = "2100-01-01T00:00:00Z")]
                 expires_at: DateTime<Utc>,
+                /// Limit the token to the given permission scope, can be used multiple times
+                ///
+                /// Only supported if the server supports scoped tokens, otherwise the project
+                /// actions needed for an equivalent dedicated CI account are listed.
+                #[arg(long, value_enum)]
+                scope: Vec<TokenScope>,
+                /// Limit the scoped token to the given project
+                #[arg(long, requires = "scope")]
+                project: Option<ProjectName>,
+                /// Print the commands for storing the token as a GitHub Actions secret, and the
+                /// workflow snippet for using it
+                #[arg(long)]
+                github_actions: bool,
             },
             /// Delete an existing token
             Delete {
@@ -1526,6 +2522,9 @@ pub mod cloud {
                     account_id: AccountIdOptionalArg,
                     /// The role to be granted
                     role: Role,
+                    /// Show the capabilities enabled by the role and ask for confirmation before granting it
+                    #[arg(long)]
+                    explain: bool,
                 },
                 /// Remove a role from the account
                 Delete {
@@ -1534,6 +2533,8 @@ pub mod cloud {
                     /// The role to be deleted
                     role: Role,
                 },
+                /// List all available roles together with an informational summary of the capabilities they enable
+                Roles,
             }
         }
     }
@@ -1542,8 +2543,9 @@ pub mod cloud {
         use crate::cloud::AccountId;
         use crate::command::cloud::project::plugin::ProjectPluginSubcommand;
         use crate::command::cloud::project::policy::PolicySubcommand;
-        use crate::model::{ProjectAction, ProjectName, ProjectPolicyId};
+        use crate::model::{ProjectAction, ProjectName, ProjectPolicyGrant, ProjectPolicyId};
         use clap::Subcommand;
+        use std::path::PathBuf;
 
         #[derive(clap::Args, Debug)]
         #[group(required = true, multiple = false)]
@@ -1565,6 +2567,12 @@ pub mod cloud {
                 /// The new project's description
                 #[arg(short, long)]
                 description: Option<String>,
+                /// Share the new project with an account using a project policy, in the form of
+                /// `<ACCOUNT_ID>:<POLICY_ID>`. Can be used multiple times.
+                ///
+                /// If any of the grants fails, the new project is deleted.
+                #[arg(long)]
+                grant: Vec<ProjectPolicyGrant>,
             },
             /// Lists existing projects
             List {
@@ -1582,6 +2590,20 @@ pub mod cloud {
                 #[command(flatten)]
                 project_actions_or_policy_id: ProjectActionsOrPolicyId,
             },
+            /// Share a project with a list of accounts defined in a team file
+            ///
+            /// The team file lists the members with their account email or ID and policy, and
+            /// optionally custom policies by name. Policies are referenced by ID, by a custom
+            /// policy name, or by a preset name (viewer, operator, developer, admin). Custom and
+            /// preset policies are created before granting.
+            GrantMany {
+                /// The project to be shared
+                #[arg(long)]
+                project: ProjectName,
+                /// Path of the YAML team file
+                #[arg(long)]
+                file: PathBuf,
+            },
             /// Manage project policies
             Policy {
                 #[command(subcommand)]
@@ -1613,6 +2635,27 @@ pub mod cloud {
                     /// Project policy ID
                     policy_id: ProjectPolicyId,
                 },
+                /// Updates the actions of a project sharing policy
+                ///
+                /// Policies cannot be modified once created, so a new policy is created with the
+                /// resulting actions, existing grants keep using the original policy.
+                Update {
+                    /// Project policy ID
+                    policy_id: ProjectPolicyId,
+                    /// Name of the new policy, defaults to the name of the original policy
+                    #[arg(long)]
+                    policy_name: Option<String>,
+                    /// Action to add to the policy, can be used multiple times
+                    #[arg(
+                        long = "add-action",
+                        value_name = "ACTION",
+                        required_unless_present = "remove_actions"
+                    )]
+                    add_actions: Vec<ProjectAction>,
+                    /// Action to remove from the policy, can be used multiple times
+                    #[arg(long = "remove-action", value_name = "ACTION")]
+                    remove_actions: Vec<ProjectAction>,
+                },
             }
         }
 
@@ -1730,6 +2773,80 @@ fn parse_key_val(key_and_val: &str) -> anyhow::Result<(String, String)> {
     ))
 }
 
+fn parse_sha256(sha256: &str) -> anyhow::Result<String> {
+    if sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(sha256.to_lowercase())
+    } else {
+        Err(anyhow!(
+            "invalid SHA-256 checksum `{}`, expected 64 hex digits",
+            sha256.log_color_error_highlight()
+        ))
+    }
+}
+
+/// Output format set by the GOLEM_FORMAT env var
+fn env_format() -> Result<Option<Format>, clap::Error> {
+    match std::env::var("GOLEM_FORMAT") {
+        Ok(format) => format.parse::<Format>().map(Some).map_err(|err| {
+            GolemCliCommand::command().error(
+                ErrorKind::InvalidValue,
+                format!("invalid GOLEM_FORMAT env var: {err}"),
+            )
+        }),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_initial_component_file(file: &str) -> anyhow::Result<InitialComponentFile> {
+    let invalid_format = || {
+        anyhow!(
+            "invalid SOURCE:PERMISSIONS:TARGET in `{}`, example: ./assets:ro:/assets",
+            file.log_color_error_highlight()
+        )
+    };
+
+    // Splitting from the right, as the source can be an URL containing colons
+    let mut parts = file.rsplitn(3, ':');
+    let (Some(target), Some(permissions), Some(source)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid_format());
+    };
+    if source.is_empty() || target.is_empty() {
+        return Err(invalid_format());
+    }
+
+    let permissions = match permissions {
+        "ro" => ComponentFilePermissions::ReadOnly,
+        "rw" => ComponentFilePermissions::ReadWrite,
+        _ => {
+            bail!(
+                "invalid permissions `{}`, expected \"ro\" or \"rw\"",
+                permissions.log_color_error_highlight()
+            )
+        }
+    };
+
+    let target = ComponentFilePath::from_abs_str(target).map_err(|err| {
+        anyhow!(
+            "invalid target path `{}`: {}",
+            target.log_color_error_highlight(),
+            err
+        )
+    })?;
+
+    let source = InitialComponentFileSource::new_relative_to_dir(source, &std::env::current_dir()?)
+        .map_err(|err| anyhow!(err))?;
+
+    Ok(InitialComponentFile {
+        source,
+        target: ComponentFilePathWithPermissions {
+            path: target,
+            permissions,
+        },
+    })
+}
+
 // TODO: better error context and messages
 fn parse_cursor(cursor: &str) -> anyhow::Result<ScanCursor> {
     let parts = cursor.split('/').collect::<Vec<_>>();
@@ -1755,10 +2872,11 @@ fn parse_instant(
 
 #[cfg(test)]
 mod test {
-    use crate::command::{builtin_app_subcommands, GolemCliCommand};
-    use assert2::assert;
+    use crate::command::{builtin_app_subcommands, parse_initial_component_file, GolemCliCommand};
+    use assert2::{assert, let_assert};
     use clap::builder::StyledStr;
     use clap::{Command, CommandFactory};
+    use golem_common::model::ComponentFilePermissions;
     use itertools::Itertools;
     use std::collections::{BTreeMap, BTreeSet};
     use test_r::test;
@@ -1976,4 +3094,43 @@ mod test {
     fn builtin_app_subcommands_no_panic() {
         println!("{:?}", builtin_app_subcommands())
     }
+
+    #[test]
+    fn initial_component_file_valid() {
+        let_assert!(
+            Ok(file) =
+                parse_initial_component_file("https://example.com/data.txt:ro:/files/data.txt")
+        );
+        assert!(file.source.as_url().as_str() == "https://example.com/data.txt");
+        assert!(file.target.path.as_path().as_str() == "/files/data.txt");
+        assert!(matches!(
+            file.target.permissions,
+            ComponentFilePermissions::ReadOnly
+        ));
+
+        let_assert!(Ok(file) = parse_initial_component_file("./assets:rw:/assets"));
+        assert!(file.source.as_url().scheme() == "file");
+        assert!(matches!(
+            file.target.permissions,
+            ComponentFilePermissions::ReadWrite
+        ));
+    }
+
+    #[test]
+    fn initial_component_file_missing_separator() {
+        assert!(parse_initial_component_file("./assets:/assets").is_err());
+        assert!(parse_initial_component_file("./assets").is_err());
+    }
+
+    #[test]
+    fn initial_component_file_empty_path() {
+        assert!(parse_initial_component_file(":ro:/assets").is_err());
+        assert!(parse_initial_component_file("./assets:ro:").is_err());
+    }
+
+    #[test]
+    fn initial_component_file_bad_permissions() {
+        let_assert!(Err(err) = parse_initial_component_file("./assets:rx:/assets"));
+        assert!(err.to_string().contains("invalid permissions"));
+    }
 }
@@ -0,0 +1,100 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alias::validate_alias_name;
+use crate::command::alias::AliasSubcommand;
+use crate::config::Config;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::text::alias::{AliasListView, AliasView};
+use crate::model::text::fmt::log_error;
+use anyhow::bail;
+use std::sync::Arc;
+
+pub struct AliasCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl AliasCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub fn handle_command(&self, subcommand: AliasSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            AliasSubcommand::Add { name, command } => self.cmd_add(name, command),
+            AliasSubcommand::List => self.cmd_list(),
+            AliasSubcommand::Remove { name } => self.cmd_remove(name),
+        }
+    }
+
+    fn cmd_add(&self, name: String, command: Vec<String>) -> anyhow::Result<()> {
+        if let Err(error) = validate_alias_name(&name) {
+            log_error(error.to_string());
+            bail!(NonSuccessfulExit);
+        }
+
+        // A single argument is stored as is, so quoted command lines are kept unchanged
+        let command = match command.as_slice() {
+            [command] => command.clone(),
+            command => shlex::try_join(command.iter().map(|arg| arg.as_str()))?,
+        };
+        if !shlex::split(&command).is_some_and(|args| !args.is_empty()) {
+            log_error(format!(
+                "Invalid alias command: {}",
+                command.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        Config::set_alias(name.clone(), command.clone(), self.ctx.config_dir())?;
+
+        log_action(
+            "Added",
+            format!(
+                "alias {} for {}",
+                name.log_color_highlight(),
+                command.log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn cmd_list(&self) -> anyhow::Result<()> {
+        let config = Config::from_dir(self.ctx.config_dir())?;
+
+        self.ctx.log_handler().log_view(&AliasListView(
+            config
+                .aliases
+                .into_iter()
+                .map(|(name, command)| AliasView { name, command })
+                .collect(),
+        ));
+
+        Ok(())
+    }
+
+    fn cmd_remove(&self, name: String) -> anyhow::Result<()> {
+        if !Config::remove_alias(&name, self.ctx.config_dir())? {
+            log_error(format!("Alias {} not found", name.log_color_highlight()));
+            bail!(NonSuccessfulExit);
+        }
+
+        log_warn_action("Removed", format!("alias {}", name.log_color_highlight()));
+
+        Ok(())
+    }
+}
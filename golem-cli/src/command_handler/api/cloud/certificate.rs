@@ -24,6 +24,7 @@ use crate::model::{PathBufOrStdin, ProjectName};
 use anyhow::bail;
 use golem_cloud_client::api::ApiCertificateClient;
 use golem_cloud_client::model::CertificateRequest;
+use itertools::Itertools;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -86,6 +87,10 @@ impl ApiCloudCertificateCommandHandler {
             .await
             .map_service_error()?;
 
+        let certificates = certificates
+            .into_iter()
+            .sorted_by(|a, b| (&a.domain_name, a.id).cmp(&(&b.domain_name, b.id)))
+            .collect();
         self.ctx
             .log_handler()
             .log_view(&CertificateListView(certificates));
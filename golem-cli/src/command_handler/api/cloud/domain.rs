@@ -22,6 +22,7 @@ use golem_cloud_client::api::ApiDomainClient;
 use golem_cloud_client::model::DomainRequest;
 
 use crate::log::log_warn_action;
+use itertools::Itertools;
 use std::sync::Arc;
 
 pub struct ApiCloudDomainCommandHandler {
@@ -65,6 +66,10 @@ impl ApiCloudDomainCommandHandler {
             .await
             .map_service_error()?;
 
+        let domains = domains
+            .into_iter()
+            .sorted_by(|a, b| a.domain_name.cmp(&b.domain_name))
+            .collect();
         self.ctx.log_handler().log_view(&ApiDomainListView(domains));
 
         Ok(())
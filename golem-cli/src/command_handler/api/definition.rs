@@ -17,17 +17,26 @@ use crate::command::shared_args::ProjectNameOptionalArg;
 use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
 use crate::log::{log_warn_action, LogColorize};
+use crate::model::api_definition::{
+    bump_api_definition, diff_api_definitions, lint_api_definition, rib_script_calls_function,
+    try_api_route, ApiDefinitionDiff, ApiRouteRequest, ApiRouteTryResult,
+};
+use crate::model::component::{exported_functions, format_function_name, Component};
 use crate::model::text::api_definition::{
     ApiDefinitionGetView, ApiDefinitionNewView, ApiDefinitionUpdateView,
 };
-use crate::model::{ApiDefinitionId, ApiDefinitionVersion, PathBufOrStdin};
-use anyhow::Context as AnyhowContext;
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::{ApiDefinitionId, ApiDefinitionVersion, ComponentName, PathBufOrStdin};
+use anyhow::{bail, Context as AnyhowContext};
 use golem_client::api::ApiDefinitionClient as ApiDefinitionClientOss;
 use golem_client::model::HttpApiDefinitionRequest as HttpApiDefinitionRequestOss;
 use golem_cloud_client::api::ApiDefinitionClient as ApiDefinitionClientCloud;
 use golem_cloud_client::model::HttpApiDefinitionRequest as HttpApiDefinitionRequestCloud;
+use itertools::Itertools;
 use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub struct ApiDefinitionCommandHandler {
@@ -64,6 +73,40 @@ impl ApiDefinitionCommandHandler {
                 version,
             } => self.cmd_delete(project, id, version).await,
             ApiDefinitionSubcommand::List { project, id } => self.list(project, id).await,
+            ApiDefinitionSubcommand::Try {
+                project,
+                id,
+                version,
+                route,
+                body,
+                headers,
+            } => {
+                self.cmd_try(project, id, version, route, body, headers)
+                    .await
+            }
+            ApiDefinitionSubcommand::Bump {
+                project,
+                id,
+                from,
+                to,
+                draft,
+            } => self.cmd_bump(project, id, from, to, draft).await,
+            ApiDefinitionSubcommand::Diff {
+                project,
+                id,
+                from,
+                to,
+            } => self.cmd_diff(project, id, from, to).await,
+            ApiDefinitionSubcommand::Lint {
+                project,
+                definition,
+                id,
+                version,
+                deny_warnings,
+            } => {
+                self.cmd_lint(project, definition, id, version, deny_warnings)
+                    .await
+            }
         }
     }
 
@@ -248,7 +291,7 @@ impl ApiDefinitionCommandHandler {
             .opt_select_project(None /* TODO: account id */, project.project.as_ref())
             .await?;
 
-        let definitions = match self.ctx.golem_clients().await? {
+        let mut definitions = match self.ctx.golem_clients().await? {
             GolemClients::Oss(clients) => clients
                 .api_definition
                 .list_definitions(api_definition_id.as_ref().map(|id| id.0.as_str()))
@@ -271,11 +314,296 @@ impl ApiDefinitionCommandHandler {
             }
         };
 
+        definitions.sort_by(|a, b| (&a.id, &a.version).cmp(&(&b.id, &b.version)));
         self.ctx.log_handler().log_view(&definitions);
 
         Ok(())
     }
 
+    async fn cmd_lint(
+        &self,
+        project: ProjectNameOptionalArg,
+        definition: Option<PathBufOrStdin>,
+        api_def_id: Option<ApiDefinitionId>,
+        version: Option<ApiDefinitionVersion>,
+        deny_warnings: bool,
+    ) -> anyhow::Result<()> {
+        let definition = match (definition, api_def_id, version) {
+            (Some(definition), _, _) => read_and_parse_api_definition(definition)?,
+            (None, Some(api_def_id), Some(version)) => {
+                self.get_definition_as_json(&project, &api_def_id, &version)
+                    .await?
+            }
+            _ => bail!("Either an API definition file or --id and --version are required"),
+        };
+
+        let result = lint_api_definition(&definition);
+
+        self.ctx.log_handler().log_view(&result);
+
+        if result.error_count() > 0 || (deny_warnings && result.warning_count() > 0) {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_try(
+        &self,
+        project: ProjectNameOptionalArg,
+        api_def_id: ApiDefinitionId,
+        version: Option<ApiDefinitionVersion>,
+        route: ApiRouteRequest,
+        body: Option<String>,
+        headers: Vec<String>,
+    ) -> anyhow::Result<()> {
+        let headers = headers
+            .iter()
+            .map(|header| match header.split_once(':') {
+                Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+                None => bail!("Invalid header: {header}, expected NAME:VALUE"),
+            })
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+        let body = body.map(read_request_body).transpose()?;
+
+        let version = match version {
+            Some(version) => version,
+            None => {
+                self.single_definition_version(&project, &api_def_id)
+                    .await?
+            }
+        };
+        let definition = self
+            .get_definition_as_json(&project, &api_def_id, &version)
+            .await?;
+
+        let Some(mut result) = try_api_route(&definition, &route, headers, body) else {
+            log_error(format!(
+                "No route of API definition {}/{} matches {} {}",
+                api_def_id.0.log_color_highlight(),
+                version.0.log_color_highlight(),
+                route.method.log_color_error_highlight(),
+                route.path.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        if let Some(component_name) = &result.component_name {
+            let project = self
+                .ctx
+                .cloud_project_handler()
+                .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+                .await?;
+            let component = self
+                .ctx
+                .component_handler()
+                .component_by_name_and_version(
+                    project.as_ref(),
+                    &ComponentName::from(component_name.as_str()),
+                    result.component_version,
+                )
+                .await?;
+            match component {
+                Some(component) => {
+                    result.functions = called_functions(&component, &result);
+                }
+                None => log_warn(format!(
+                    "Component {} of the binding was not found, cannot resolve the called functions",
+                    component_name.log_color_highlight()
+                )),
+            }
+        }
+
+        self.ctx.log_handler().log_view(&result);
+
+        Ok(())
+    }
+
+    async fn cmd_bump(
+        &self,
+        project: ProjectNameOptionalArg,
+        api_def_id: ApiDefinitionId,
+        from: ApiDefinitionVersion,
+        to: ApiDefinitionVersion,
+        draft: bool,
+    ) -> anyhow::Result<()> {
+        if from.0 == to.0 {
+            log_error(format!(
+                "The new version must differ from the cloned version {}",
+                from.0.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let definition = bump_api_definition(
+            &self
+                .get_definition_as_json(&project, &api_def_id, &from)
+                .await?,
+            &to.0,
+            draft,
+        );
+
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+            .await?;
+
+        let result = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .api_definition
+                .create_definition_json(&serde_json::from_value::<HttpApiDefinitionRequestOss>(
+                    definition,
+                )?)
+                .await
+                .map_service_error()?,
+            GolemClients::Cloud(clients) => {
+                let project = self
+                    .ctx
+                    .cloud_project_handler()
+                    .selected_project_or_default(project)
+                    .await?;
+                clients
+                    .api_definition
+                    .create_definition_json(
+                        &project.project_id.0,
+                        &serde_json::from_value::<HttpApiDefinitionRequestCloud>(definition)?,
+                    )
+                    .await
+                    .map_service_error()?
+            }
+        };
+
+        self.ctx
+            .log_handler()
+            .log_view(&ApiDefinitionNewView(result));
+
+        Ok(())
+    }
+
+    async fn cmd_diff(
+        &self,
+        project: ProjectNameOptionalArg,
+        api_def_id: ApiDefinitionId,
+        from: ApiDefinitionVersion,
+        to: ApiDefinitionVersion,
+    ) -> anyhow::Result<()> {
+        let from_definition = self
+            .get_definition_as_json(&project, &api_def_id, &from)
+            .await?;
+        let to_definition = self
+            .get_definition_as_json(&project, &api_def_id, &to)
+            .await?;
+
+        self.ctx.log_handler().log_view(&ApiDefinitionDiff {
+            id: api_def_id.0,
+            from_version: from.0,
+            to_version: to.0,
+            routes: diff_api_definitions(&from_definition, &to_definition),
+        });
+
+        Ok(())
+    }
+
+    pub async fn single_definition_version(
+        &self,
+        project: &ProjectNameOptionalArg,
+        api_def_id: &ApiDefinitionId,
+    ) -> anyhow::Result<ApiDefinitionVersion> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+            .await?;
+
+        let versions = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .api_definition
+                .list_definitions(Some(&api_def_id.0))
+                .await
+                .map_service_error()?
+                .into_iter()
+                .map(|definition| definition.version)
+                .collect::<Vec<_>>(),
+            GolemClients::Cloud(clients) => {
+                let project = self
+                    .ctx
+                    .cloud_project_handler()
+                    .selected_project_or_default(project)
+                    .await?;
+                clients
+                    .api_definition
+                    .list_definitions(&project.project_id.0, Some(&api_def_id.0))
+                    .await
+                    .map_service_error()?
+                    .into_iter()
+                    .map(|definition| definition.version)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        match versions.as_slice() {
+            [version] => Ok(ApiDefinitionVersion(version.clone())),
+            [] => {
+                log_error(format!(
+                    "API definition {} not found",
+                    api_def_id.0.log_color_error_highlight()
+                ));
+                bail!(NonSuccessfulExit)
+            }
+            versions => {
+                log_error(format!(
+                    "API definition {} has multiple versions: {}, please specify one with --version",
+                    api_def_id.0.log_color_error_highlight(),
+                    versions
+                        .iter()
+                        .map(|version| version.log_color_highlight())
+                        .join(", ")
+                ));
+                bail!(NonSuccessfulExit)
+            }
+        }
+    }
+
+    pub async fn get_definition_as_json(
+        &self,
+        project: &ProjectNameOptionalArg,
+        api_def_id: &ApiDefinitionId,
+        version: &ApiDefinitionVersion,
+    ) -> anyhow::Result<serde_json::Value> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+            .await?;
+
+        let definition = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => serde_json::to_value(
+                clients
+                    .api_definition
+                    .get_definition(&api_def_id.0, &version.0)
+                    .await
+                    .map_service_error()?,
+            )?,
+            GolemClients::Cloud(clients) => {
+                let project = self
+                    .ctx
+                    .cloud_project_handler()
+                    .selected_project_or_default(project)
+                    .await?;
+                serde_json::to_value(
+                    clients
+                        .api_definition
+                        .get_definition(&project.project_id.0, &api_def_id.0, &version.0)
+                        .await
+                        .map_service_error()?,
+                )?
+            }
+        };
+
+        Ok(definition)
+    }
+
     async fn cmd_delete(
         &self,
         project: ProjectNameOptionalArg,
@@ -321,6 +649,29 @@ impl ApiDefinitionCommandHandler {
     }
 }
 
+fn read_request_body(body: String) -> anyhow::Result<serde_json::Value> {
+    let body = match body.strip_prefix('@') {
+        Some(path) => {
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read body: {path}"))?
+        }
+        None => body,
+    };
+    // Non-JSON bodies are passed as strings, same as by the API gateway
+    Ok(serde_json::from_str(&body).unwrap_or(serde_json::Value::String(body)))
+}
+
+fn called_functions(component: &Component, result: &ApiRouteTryResult) -> Vec<String> {
+    exported_functions(&component.metadata.exports)
+        .into_iter()
+        .filter(|(_, function)| {
+            result
+                .rib_scripts()
+                .any(|script| rib_script_calls_function(script, &function.name))
+        })
+        .map(|(prefix, function)| format_function_name(prefix, &function.name))
+        .collect()
+}
+
 fn parse_api_definition<T: DeserializeOwned>(input: &str) -> anyhow::Result<T> {
     serde_yaml::from_str(input).context("Failed to parse API definition")
 }
@@ -12,34 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::external_component::{
+    is_external_component_up_to_date, update_external_component, ExternalComponentMetadata,
+};
 use crate::app::error::CustomCommandError;
 use crate::command::app::AppSubcommand;
 use crate::command::builtin_app_subcommands;
 use crate::command::shared_args::{
-    AppOptionalComponentNames, BuildArgs, ForceBuildArg, WorkerUpdateOrRedeployArgs,
+    AppOptionalComponentNames, AwaitProcessingArg, BuildArgs, ForceBuildArg, StreamArgs,
+    WorkerFunctionArgument, WorkerFunctionName, WorkerUpdateOrRedeployArgs,
 };
 use crate::command_handler::Handlers;
-use crate::context::Context;
+use crate::config::ProfileKind;
+use crate::context::{Context, GolemClients};
 use crate::diagnose::diagnose;
+use crate::error::service::AnyhowMapServiceError;
 use crate::error::{HintError, NonSuccessfulExit};
 use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
-use crate::log::{log_action, logln, LogColorize, LogIndent, LogOutput, Output};
-use crate::model::app::{ApplicationComponentSelectMode, DynamicHelpSections};
-use crate::model::component::Component;
+use crate::log::{
+    log_action, log_skipping_up_to_date, logln, LogColorize, LogIndent, LogOutput, Output,
+};
+use crate::model::app::{
+    AppBuildStep, AppComponentName, ApplicationComponentSelectMode, DynamicHelpSections,
+    ExternalComponent,
+};
+use crate::model::app_graph::{AppDependencyGraph, AppGraphFormat};
+use crate::model::bench::InvocationBenchmark;
+use crate::model::component::{AppComponentType, Component};
+use crate::model::text::app_graph::AppDependencyGraphView;
+use crate::model::text::bench::InvocationBenchmarkView;
 use crate::model::text::fmt::{log_error, log_fuzzy_matches, log_text_view, log_warn};
 use crate::model::text::help::AvailableComponentNamesHelp;
-use crate::model::{ComponentName, WorkerUpdateMode};
+use crate::model::text::trace::CallTraceView;
+use crate::model::trace::{find_invocation, CallTrace, TraceCall, TraceGraphFormat, TraceWorker};
+use crate::model::{
+    is_wasm_rpc_runtime_compatible, ComponentName, IdempotencyKey, ProjectName, VersionCheckMode,
+    WorkerName, WorkerNameMatch, WorkerUpdateMode,
+};
 use anyhow::{anyhow, bail};
 use colored::Colorize;
+use golem_client::api::ComponentClient as ComponentClientOss;
+use golem_cloud_client::api::ComponentClient as ComponentClientCloud;
 use golem_templates::add_component_by_template;
 use golem_templates::model::{
     ComposableAppGroupName, GuestLanguage, PackageName, Template, TemplateName,
 };
+use golem_wasm_rpc::WASM_RPC_VERSION;
 use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use strum::IntoEnumIterator;
+use uuid::Uuid;
 
 pub struct AppCommandHandler {
     ctx: Arc<Context>,
@@ -63,10 +89,32 @@ impl AppCommandHandler {
             AppSubcommand::Deploy {
                 component_name,
                 force_build,
+                await_processing,
                 update_or_redeploy,
+                locked,
             } => {
-                self.cmd_deploy(component_name, force_build, update_or_redeploy)
-                    .await
+                self.cmd_deploy(
+                    component_name,
+                    force_build,
+                    await_processing,
+                    update_or_redeploy,
+                    locked,
+                )
+                .await
+            }
+            AppSubcommand::Run {
+                component_name,
+                force_build,
+                stream_args,
+                worker_poll_interval,
+            } => {
+                self.cmd_run(
+                    component_name,
+                    force_build,
+                    stream_args,
+                    Duration::from_secs(worker_poll_interval),
+                )
+                .await
             }
             AppSubcommand::Clean { component_name } => self.cmd_clean(component_name).await,
             AppSubcommand::UpdateWorkers {
@@ -81,6 +129,35 @@ impl AppCommandHandler {
                     .await
             }
             AppSubcommand::Diagnose { component_name } => self.cmd_diagnose(component_name).await,
+            AppSubcommand::Deps {
+                component_name,
+                graph_format,
+            } => self.cmd_deps(component_name, graph_format).await,
+            AppSubcommand::Bench {
+                component,
+                function,
+                arguments,
+                baseline_function,
+                worker,
+                iterations,
+                warmup,
+            } => {
+                self.cmd_bench(
+                    component,
+                    function,
+                    arguments,
+                    baseline_function,
+                    worker,
+                    iterations,
+                    warmup,
+                )
+                .await
+            }
+            AppSubcommand::Trace {
+                entry,
+                idempotency_key,
+                graph_format,
+            } => self.cmd_trace(entry, idempotency_key, graph_format).await,
             AppSubcommand::CustomCommand(command) => self.cmd_custom_command(command).await,
         }
     }
@@ -208,7 +285,9 @@ impl AppCommandHandler {
         &mut self,
         component_name: AppOptionalComponentNames,
         force_build: ForceBuildArg,
+        await_processing: AwaitProcessingArg,
         update_or_redeploy: WorkerUpdateOrRedeployArgs,
+        locked: bool,
     ) -> anyhow::Result<()> {
         self.ctx
             .component_handler()
@@ -221,24 +300,96 @@ impl AppCommandHandler {
                 component_name.component_name,
                 Some(force_build),
                 &ApplicationComponentSelectMode::All,
+                await_processing,
                 update_or_redeploy,
+                locked,
+                None,
             )
             .await
     }
 
-    async fn cmd_custom_command(&mut self, command: Vec<String>) -> anyhow::Result<()> {
-        if command.len() != 1 {
-            bail!(
-                "Expected exactly one custom subcommand, got: {}",
-                command.join(" ").log_color_error_highlight()
-            );
+    async fn cmd_run(
+        &mut self,
+        component_name: AppOptionalComponentNames,
+        force_build: ForceBuildArg,
+        stream_args: StreamArgs,
+        worker_poll_interval: Duration,
+    ) -> anyhow::Result<()> {
+        if self.ctx.profile_kind() != ProfileKind::Oss {
+            log_error(format!(
+                "Running the application requires a local profile, the selected profile {} is a cloud profile",
+                self.ctx.profile_name().0.log_color_highlight()
+            ));
+            logln("");
+            logln("Select the builtin local profile with the '--local' flag, or switch to an OSS profile with 'profile switch'");
+            bail!(NonSuccessfulExit);
         }
 
-        let command = command[0].strip_prefix(":").unwrap_or(&command[0]);
+        if let Err(error) = self.ctx.server_version().await {
+            log_error("The local Golem server is not reachable");
+            logln(format!("{:#}", error));
+            logln("");
+            logln("Start the server in another terminal with 'server run', then try again");
+            bail!(NonSuccessfulExit);
+        }
+
+        self.ctx
+            .component_handler()
+            .deploy(
+                None,
+                component_name.component_name,
+                Some(force_build),
+                &ApplicationComponentSelectMode::All,
+                AwaitProcessingArg::default(),
+                WorkerUpdateOrRedeployArgs::default(),
+                false,
+                None,
+            )
+            .await?;
+
+        let components = self
+            .components_for_update_or_redeploy()
+            .await?
+            .into_iter()
+            .map(|component| {
+                (
+                    component.component_name,
+                    component.versioned_component_id.component_id,
+                )
+            })
+            .collect::<Vec<_>>();
+        if components.is_empty() {
+            log_error("No deployed components found to run");
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Streaming",
+            format!(
+                "output of the workers of {}, press Ctrl-C to stop",
+                components
+                    .iter()
+                    .map(|(component_name, _)| component_name.0.log_color_highlight())
+                    .join(", ")
+            ),
+        );
+
+        self.ctx
+            .worker_handler()
+            .stream_component_workers(&components, stream_args.into(), worker_poll_interval)
+            .await
+    }
+
+    async fn cmd_custom_command(&mut self, command: Vec<String>) -> anyhow::Result<()> {
+        let Some((command, args)) = command.split_first() else {
+            bail!("Expected a custom subcommand");
+        };
+
+        let command = command.strip_prefix(":").unwrap_or(command);
 
         let app_ctx = self.ctx.app_context_lock().await;
         let app_ctx = app_ctx.some_or_err()?;
-        if let Err(error) = app_ctx.custom_command(command) {
+        if let Err(error) = app_ctx.custom_command(command, args) {
             match error {
                 CustomCommandError::CommandNotFound => {
                     logln("");
@@ -275,6 +426,19 @@ impl AppCommandHandler {
 
                     bail!(NonSuccessfulExit)
                 }
+                CustomCommandError::InvalidArgs { errors } => {
+                    logln("");
+                    log_error(format!(
+                        "Invalid arguments for custom command {}:",
+                        command.log_color_error_highlight()
+                    ));
+                    for error in errors {
+                        logln(format!("  - {}", error));
+                    }
+                    logln("");
+
+                    bail!(NonSuccessfulExit)
+                }
                 CustomCommandError::CommandError { error } => {
                     bail!(
                         "Command {} failed: {error}",
@@ -331,24 +495,591 @@ impl AppCommandHandler {
         .await
     }
 
+    async fn cmd_deps(
+        &mut self,
+        component_names: AppOptionalComponentNames,
+        graph_format: AppGraphFormat,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        self.must_select_components(
+            component_names.component_name,
+            &ApplicationComponentSelectMode::All,
+        )
+        .await?;
+
+        let graph = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            AppDependencyGraph::new(&app_ctx.application, app_ctx.selected_component_names())
+        };
+
+        if graph.components.is_empty() {
+            log_warn("The application has no components.");
+        }
+
+        self.ctx.log_handler().log_view(&AppDependencyGraphView {
+            diagram: graph.render(graph_format),
+            graph,
+        });
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn cmd_trace(
+        &mut self,
+        entry: WorkerName,
+        idempotency_key: Option<IdempotencyKey>,
+        graph_format: TraceGraphFormat,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+
+        let mut worker_handler = self.ctx.worker_handler();
+        let worker_name_match = worker_handler.match_worker_name(entry).await?;
+        let (component, worker_name) = worker_handler
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let entry = TraceWorker {
+            component_id: component.versioned_component_id.component_id,
+            component_name: Some(component.component_name.0.clone()),
+            worker_name: worker_name.0,
+        };
+
+        log_action(
+            "Tracing",
+            format!(
+                "RPC calls starting from worker {}",
+                entry.to_string().log_color_highlight()
+            ),
+        );
+
+        let entries = worker_handler
+            .get_oplog(
+                &entry.component_id,
+                &entry.worker_name.as_str().into(),
+                None,
+                None,
+                None,
+            )
+            .await?;
+        let Some(invocation) =
+            find_invocation(&entries, idempotency_key.as_ref().map(|key| key.0.as_str()))
+        else {
+            match &idempotency_key {
+                Some(idempotency_key) => log_error(format!(
+                    "No invocation found for worker {} with idempotency key {}",
+                    entry.to_string().log_color_highlight(),
+                    idempotency_key.0.log_color_highlight()
+                )),
+                None => log_error(format!(
+                    "No invocation found for worker {}",
+                    entry.to_string().log_color_highlight()
+                )),
+            }
+            bail!(NonSuccessfulExit);
+        };
+
+        let mut trace = CallTrace {
+            entry: entry.clone(),
+            entry_function: invocation.function_name.clone(),
+            entry_idempotency_key: invocation.idempotency_key.clone(),
+            calls: vec![],
+        };
+
+        let mut component_names = HashMap::<Uuid, Option<String>>::from([(
+            entry.component_id,
+            entry.component_name.clone(),
+        )]);
+        let mut visited = HashSet::<(Uuid, String, String)>::from([(
+            entry.component_id,
+            entry.worker_name.clone(),
+            invocation.idempotency_key.clone(),
+        )]);
+        let rpc_calls = invocation.rpc_calls();
+        let mut pending = VecDeque::from([(entry, invocation.function_name, rpc_calls)]);
+
+        while let Some((caller, caller_function, rpc_calls)) = pending.pop_front() {
+            for rpc_call in rpc_calls {
+                self.ctx.cancellation_token().check()?;
+
+                let component_name = match component_names.get(&rpc_call.callee_component_id) {
+                    Some(component_name) => component_name.clone(),
+                    None => {
+                        let component_name = self
+                            .ctx
+                            .component_handler()
+                            .component_by_id(&rpc_call.callee_component_id)
+                            .await?
+                            .map(|component| component.component_name.0);
+                        component_names
+                            .insert(rpc_call.callee_component_id, component_name.clone());
+                        component_name
+                    }
+                };
+
+                let callee = TraceWorker {
+                    component_id: rpc_call.callee_component_id,
+                    component_name,
+                    worker_name: rpc_call.callee_worker_name,
+                };
+
+                trace.calls.push(TraceCall {
+                    caller: caller.clone(),
+                    caller_function: caller_function.clone(),
+                    callee: callee.clone(),
+                    function_name: rpc_call.function_name.clone(),
+                    kind: rpc_call.kind,
+                    idempotency_key: rpc_call.idempotency_key.clone(),
+                });
+
+                // Without an idempotency key the remote invocation cannot be identified
+                let Some(idempotency_key) = rpc_call.idempotency_key else {
+                    continue;
+                };
+                if !visited.insert((
+                    callee.component_id,
+                    callee.worker_name.clone(),
+                    idempotency_key.clone(),
+                )) {
+                    continue;
+                }
+
+                let callee_entries = worker_handler
+                    .get_oplog(
+                        &callee.component_id,
+                        &callee.worker_name.as_str().into(),
+                        None,
+                        None,
+                        None,
+                    )
+                    .await?;
+                match find_invocation(&callee_entries, Some(idempotency_key.as_str())) {
+                    Some(invocation) => {
+                        pending.push_back((callee, rpc_call.function_name, invocation.rpc_calls()))
+                    }
+                    None => log_warn(format!(
+                        "No invocation found for worker {} with idempotency key {}, skipping its calls",
+                        callee.to_string().log_color_highlight(),
+                        idempotency_key.log_color_highlight()
+                    )),
+                }
+            }
+        }
+
+        let diagram = trace.render(graph_format);
+        self.ctx
+            .log_handler()
+            .log_view(&CallTraceView { trace, diagram });
+
+        Ok(())
+    }
+
+    async fn cmd_bench(
+        &mut self,
+        component_name: ComponentName,
+        function_name: WorkerFunctionName,
+        arguments: Vec<WorkerFunctionArgument>,
+        baseline_function_name: Option<WorkerFunctionName>,
+        worker_name: Option<WorkerName>,
+        iterations: u32,
+        warmup: u32,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+
+        let mut worker_name_match = self
+            .ctx
+            .worker_handler()
+            .match_worker_name(
+                format!(
+                    "{}/{}",
+                    component_name.0,
+                    worker_name
+                        .as_ref()
+                        .map(|name| name.0.as_str())
+                        .unwrap_or("-")
+                )
+                .into(),
+            )
+            .await?;
+
+        let component = self
+            .ctx
+            .component_handler()
+            .component_by_name_with_auto_deploy(
+                worker_name_match.project.as_ref(),
+                worker_name_match.component_name_match_kind,
+                &worker_name_match.component_name,
+                worker_name_match.worker_name.as_ref(),
+            )
+            .await?;
+
+        if component.component_type == AppComponentType::Ephemeral
+            && worker_name_match.worker_name.is_some()
+        {
+            log_error("Cannot use explicit worker name for ephemeral components!");
+            logln("");
+            bail!(NonSuccessfulExit);
+        }
+
+        // Durable workers are created by the warmup invocations, generated ones are deleted
+        // after the benchmark
+        let generated_worker_name = (component.component_type == AppComponentType::Durable
+            && worker_name_match.worker_name.is_none())
+        .then(|| WorkerName::from(format!("bench-{}", Uuid::new_v4())));
+        if let Some(worker_name) = &generated_worker_name {
+            worker_name_match.worker_name = Some(worker_name.clone());
+        }
+
+        let result = self
+            .bench_functions(
+                &worker_name_match,
+                &component,
+                function_name,
+                arguments,
+                baseline_function_name,
+                iterations,
+                warmup,
+            )
+            .await;
+
+        if let Some(worker_name) = &generated_worker_name {
+            log_action(
+                "Deleting",
+                format!(
+                    "generated benchmark worker {}",
+                    worker_name.0.log_color_highlight()
+                ),
+            );
+            if let Err(err) = self
+                .ctx
+                .worker_handler()
+                .delete(
+                    component.versioned_component_id.component_id,
+                    &worker_name.0,
+                )
+                .await
+            {
+                log_warn(format!("Failed to delete benchmark worker: {err:#}"));
+            }
+        }
+
+        let (benchmark, baseline) = result?;
+
+        self.ctx.log_handler().log_view(&InvocationBenchmarkView {
+            component_name: worker_name_match.component_name,
+            worker_name: worker_name_match.worker_name,
+            benchmark,
+            baseline,
+        });
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn bench_functions(
+        &self,
+        worker_name_match: &WorkerNameMatch,
+        component: &Component,
+        function_name: WorkerFunctionName,
+        arguments: Vec<WorkerFunctionArgument>,
+        baseline_function_name: Option<WorkerFunctionName>,
+        iterations: u32,
+        warmup: u32,
+    ) -> anyhow::Result<(InvocationBenchmark, Option<InvocationBenchmark>)> {
+        let benchmark = self
+            .ctx
+            .worker_handler()
+            .bench_invocations(
+                worker_name_match,
+                component,
+                &function_name,
+                arguments.clone(),
+                warmup,
+                iterations,
+            )
+            .await?;
+
+        let baseline = match baseline_function_name {
+            Some(baseline_function_name) => Some(
+                self.ctx
+                    .worker_handler()
+                    .bench_invocations(
+                        worker_name_match,
+                        component,
+                        &baseline_function_name,
+                        arguments,
+                        warmup,
+                        iterations,
+                    )
+                    .await?,
+            ),
+            None => None,
+        };
+
+        Ok((benchmark, baseline))
+    }
+
     pub async fn build(
         &mut self,
         component_names: Vec<ComponentName>,
         build: Option<BuildArgs>,
         default_component_select_mode: &ApplicationComponentSelectMode,
     ) -> anyhow::Result<()> {
+        let mut version_check = None;
+        let mut strict = false;
         if let Some(build) = build {
+            version_check = build.version_check;
+            strict = build.strict;
             self.ctx
                 .set_steps_filter(build.step.into_iter().collect())
                 .await;
             self.ctx
                 .set_skip_up_to_date_checks(build.force_build.force_build)
                 .await;
+            self.ctx.set_reproducible_build(build.reproducible).await;
+            self.ctx.set_keep_temp_build_dirs(build.keep_temp).await;
+            self.ctx.set_build_jobs(build.jobs).await;
+            self.ctx.set_install_tools(build.install_tools).await;
         }
         self.must_select_components(component_names, default_component_select_mode)
             .await?;
+        if let Some(version_check) = version_check {
+            self.check_wasm_rpc_runtime_version(version_check).await?;
+        }
+        self.fetch_external_components().await?;
         let mut app_ctx = self.ctx.app_context_lock_mut().await;
-        app_ctx.some_or_err_mut()?.build().await
+        let app_ctx = app_ctx.some_or_err_mut()?;
+        app_ctx.build().await?;
+
+        if strict {
+            let warns = app_ctx.validation_warns();
+            if !warns.is_empty() {
+                log_error(format!(
+                    "Build failed in strict mode, found {} validation warning(s):",
+                    warns.len().to_string().log_color_highlight()
+                ));
+                let _indent = LogIndent::new();
+                for warn in warns {
+                    logln(format!("- {}", warn));
+                }
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the WASM RPC version used for generating clients against the server version
+    /// of the selected profile, so protocol drift is reported before building and deploying
+    async fn check_wasm_rpc_runtime_version(
+        &self,
+        version_check: VersionCheckMode,
+    ) -> anyhow::Result<()> {
+        {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            let uses_wasm_rpc = app_ctx
+                .application
+                .all_dependencies()
+                .iter()
+                .any(|dependency| dependency.dep_type.is_wasm_rpc());
+            if !uses_wasm_rpc || !app_ctx.config.should_run_step(AppBuildStep::GenRpc) {
+                return Ok(());
+            }
+        }
+
+        log_action("Checking", "WASM RPC version compatibility with the server");
+        let _indent = LogIndent::new();
+
+        let report = |message: String| -> anyhow::Result<()> {
+            match version_check {
+                VersionCheckMode::Warn => {
+                    log_warn(message);
+                    Ok(())
+                }
+                VersionCheckMode::Fail => {
+                    log_error(message);
+                    bail!(NonSuccessfulExit)
+                }
+            }
+        };
+
+        let server_version = match self.ctx.server_version().await {
+            Ok(server_version) => server_version,
+            Err(error) => return report(format!("{error:#}")),
+        };
+
+        match is_wasm_rpc_runtime_compatible(WASM_RPC_VERSION, &server_version) {
+            Ok(true) => {
+                log_action(
+                    "Compatible",
+                    format!(
+                        "WASM RPC version {} with server version {}",
+                        WASM_RPC_VERSION.log_color_highlight(),
+                        server_version.log_color_highlight()
+                    ),
+                );
+                Ok(())
+            }
+            Ok(false) => report(format!(
+                "WASM RPC version {} is incompatible with server version {}, \
+                 clients built with it may fail during composition or invocation",
+                WASM_RPC_VERSION.log_color_highlight(),
+                server_version.log_color_highlight()
+            )),
+            Err(error) => report(format!(
+                "Cannot check WASM RPC version compatibility: {error:#}"
+            )),
+        }
+    }
+
+    /// Fetches the deployed external components which are used as WASM RPC dependencies,
+    /// and extracts their WIT for generating clients
+    async fn fetch_external_components(&self) -> anyhow::Result<()> {
+        let external_components = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            if !app_ctx.config.should_run_step(AppBuildStep::GenRpc) {
+                return Ok(());
+            }
+            app_ctx
+                .application
+                .external_components()
+                .map(|(component_name, component)| (component_name.clone(), component.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        if external_components.is_empty() {
+            return Ok(());
+        }
+
+        log_action("Fetching", "external components");
+        let _indent = LogIndent::new();
+
+        for (component_name, external_component) in external_components {
+            self.ctx.cancellation_token().check()?;
+            self.fetch_external_component(&component_name, external_component)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_external_component(
+        &self,
+        component_name: &AppComponentName,
+        external_component: ExternalComponent,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(
+                None,
+                external_component.project.map(ProjectName::from).as_ref(),
+            )
+            .await?;
+
+        let Some(latest_component) = self
+            .ctx
+            .component_handler()
+            .component_by_name(project.as_ref(), &component_name.as_str().into(), None)
+            .await?
+        else {
+            log_error(format!(
+                "External component {} not found",
+                component_name.as_str().log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let component_id = latest_component.versioned_component_id.component_id;
+        let version = external_component
+            .version
+            .unwrap_or(latest_component.versioned_component_id.version);
+
+        let component = if version == latest_component.versioned_component_id.version {
+            Some(latest_component)
+        } else {
+            match self.ctx.golem_clients().await? {
+                GolemClients::Oss(clients) => clients
+                    .component
+                    .get_component_metadata(&component_id, &version.to_string())
+                    .await
+                    .map_service_error_not_found_as_opt()?
+                    .map(Component::from),
+                GolemClients::Cloud(clients) => clients
+                    .component
+                    .get_component_metadata(&component_id, &version.to_string())
+                    .await
+                    .map_service_error_not_found_as_opt()?
+                    .map(Component::from),
+            }
+        };
+        let Some(component) = component else {
+            log_error(format!(
+                "External component {} version {} not found",
+                component_name.as_str().log_color_error_highlight(),
+                version.to_string().log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let metadata = ExternalComponentMetadata {
+            component_id,
+            version,
+            component_type: component.component_type,
+        };
+
+        {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            if !app_ctx.config.skip_up_to_date_checks
+                && is_external_component_up_to_date(
+                    &app_ctx.application,
+                    component_name,
+                    &metadata,
+                )?
+            {
+                log_skipping_up_to_date(format!(
+                    "fetching external component {} version {}",
+                    component_name.as_str().log_color_highlight(),
+                    version.to_string().log_color_highlight()
+                ));
+                return Ok(());
+            }
+        }
+
+        log_action(
+            "Fetching",
+            format!(
+                "external component {} version {}",
+                component_name.as_str().log_color_highlight(),
+                version.to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let component_wasm = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .download_component(&component_id, Some(version))
+                .await
+                .map_service_error()?,
+            GolemClients::Cloud(clients) => clients
+                .component
+                .download_component(&component_id, Some(version))
+                .await
+                .map_service_error()?,
+        };
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        update_external_component(
+            &app_ctx.some_or_err()?.application,
+            component_name,
+            &metadata,
+            &component_wasm,
+        )
     }
 
     pub async fn clean(
@@ -400,7 +1131,7 @@ impl AppCommandHandler {
         Ok(components)
     }
 
-    async fn must_select_components(
+    pub async fn must_select_components(
         &mut self,
         component_names: Vec<ComponentName>,
         default: &ApplicationComponentSelectMode,
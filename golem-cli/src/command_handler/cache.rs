@@ -0,0 +1,107 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::cache::CacheSubcommand;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::log::{log_action, LogColorize};
+use crate::model::text::cache::CacheStatsView;
+use crate::model::text::fmt::format_binary_size;
+use crate::name_cache;
+use crate::name_cache::NameCache;
+use crate::wasm_rpc_stubgen::stub_cache;
+use anyhow::anyhow;
+use std::sync::Arc;
+
+pub struct CacheCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl CacheCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub fn handle_command(&self, subcommand: CacheSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            CacheSubcommand::Clear { names, stubs } => self.cmd_clear(names, stubs),
+            CacheSubcommand::Stats => self.cmd_stats(),
+        }
+    }
+
+    fn cmd_clear(&self, names: bool, stubs: bool) -> anyhow::Result<()> {
+        let all = !names && !stubs;
+        if all || names {
+            self.clear_names()?;
+        }
+        if all || stubs {
+            self.clear_stubs()?;
+        }
+
+        Ok(())
+    }
+
+    fn clear_names(&self) -> anyhow::Result<()> {
+        let path = name_cache::name_cache_path(self.ctx.config_dir());
+        if name_cache::clear(self.ctx.config_dir())? {
+            log_action(
+                "Cleared",
+                format!("name cache {}", path.log_color_highlight()),
+            );
+        } else {
+            log_action("Skipping", "clearing name cache, it is already empty");
+        }
+
+        Ok(())
+    }
+
+    fn clear_stubs(&self) -> anyhow::Result<()> {
+        let cache_dir = stub_cache::cache_dir()
+            .ok_or_else(|| anyhow!("Failed to determine the stub cache directory"))?;
+        let removed = stub_cache::clean(&cache_dir)?;
+        if removed.entries == 0 {
+            log_action("Skipping", "cleaning stub cache, it is already empty");
+        } else {
+            log_action(
+                "Removed",
+                format!(
+                    "{} cached client binaries ({}) from {}",
+                    removed.entries.to_string().log_color_highlight(),
+                    format_binary_size(&removed.size),
+                    cache_dir.log_color_highlight()
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn cmd_stats(&self) -> anyhow::Result<()> {
+        let stub_cache_dir = stub_cache::cache_dir();
+        let stub_cache_stats = match &stub_cache_dir {
+            Some(cache_dir) => stub_cache::stats(cache_dir)?,
+            None => Default::default(),
+        };
+
+        self.ctx.log_handler().log_view(&CacheStatsView {
+            name_cache_path: name_cache::name_cache_path(self.ctx.config_dir()),
+            name_cache_entries: NameCache::load(self.ctx.config_dir()).entries.len(),
+            stub_cache_dir,
+            stub_cache_entries: stub_cache_stats.entries,
+            stub_cache_size: stub_cache_stats.size,
+        });
+
+        Ok(())
+    }
+}
@@ -17,12 +17,15 @@ use crate::command::cloud::account::grant::GrantSubcommand;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
-use crate::model::text::account::GrantGetView;
+use crate::error::NonSuccessfulExit;
+use crate::model::text::account::{GrantGetView, RoleCapabilitiesView};
 use crate::model::Role;
 
 use crate::log::log_action;
+use anyhow::bail;
 use golem_cloud_client::api::GrantClient;
 use std::sync::Arc;
+use strum::IntoEnumIterator;
 
 pub struct CloudAccountGrantCommandHandler {
     ctx: Arc<Context>,
@@ -36,12 +39,15 @@ impl CloudAccountGrantCommandHandler {
     pub async fn handle_command(&self, subcommand: GrantSubcommand) -> anyhow::Result<()> {
         match subcommand {
             GrantSubcommand::Get { account_id } => self.cmd_get(account_id.account_id).await,
-            GrantSubcommand::New { account_id, role } => {
-                self.cmd_new(account_id.account_id, role).await
-            }
+            GrantSubcommand::New {
+                account_id,
+                role,
+                explain,
+            } => self.cmd_new(account_id.account_id, role, explain).await,
             GrantSubcommand::Delete { account_id, role } => {
                 self.cmd_delete(account_id.account_id, role).await
             }
+            GrantSubcommand::Roles => self.cmd_roles(),
         }
     }
 
@@ -67,20 +73,37 @@ impl CloudAccountGrantCommandHandler {
         Ok(())
     }
 
-    async fn cmd_new(&self, account_id: Option<AccountId>, role: Role) -> anyhow::Result<()> {
+    async fn cmd_new(
+        &self,
+        account_id: Option<AccountId>,
+        role: Role,
+        explain: bool,
+    ) -> anyhow::Result<()> {
+        let account_id = self
+            .ctx
+            .cloud_account_handler()
+            .select_account_id_or_err(account_id)
+            .await?;
+
+        if explain {
+            self.ctx
+                .log_handler()
+                .log_view(&RoleCapabilitiesView::new(vec![role]));
+
+            if !self
+                .ctx
+                .interactive_handler()
+                .confirm_grant_role(&account_id, role)?
+            {
+                bail!(NonSuccessfulExit)
+            }
+        }
+
         self.ctx
             .golem_clients_cloud()
             .await?
             .grant
-            .create_account_grant(
-                &self
-                    .ctx
-                    .cloud_account_handler()
-                    .select_account_id_or_err(account_id)
-                    .await?
-                    .0,
-                &role.into(),
-            )
+            .create_account_grant(&account_id.0, &role.into())
             .await
             .map_service_error()?;
 
@@ -89,6 +112,14 @@ impl CloudAccountGrantCommandHandler {
         Ok(())
     }
 
+    fn cmd_roles(&self) -> anyhow::Result<()> {
+        self.ctx
+            .log_handler()
+            .log_view(&RoleCapabilitiesView::new(Role::iter().collect()));
+
+        Ok(())
+    }
+
     async fn cmd_delete(&self, account_id: Option<AccountId>, role: Role) -> anyhow::Result<()> {
         self.ctx
             .golem_clients_cloud()
@@ -0,0 +1,193 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cloud::AccountId;
+use crate::command::cloud::admin::AdminSubcommand;
+use crate::context::Context;
+use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::history;
+use crate::log::{log_action, log_error_action, log_warn_action, logln, LogColorize};
+use crate::model::text::fmt::log_error;
+use crate::model::Role;
+use anyhow::{bail, Context as AnyhowContext};
+use chrono::{Duration, Utc};
+use golem_cloud_client::api::{GrantClient, TokenClient};
+use golem_cloud_client::model::CreateTokenDto;
+use std::ffi::OsString;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct CloudAdminCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl CloudAdminCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: AdminSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            AdminSubcommand::Impersonate {
+                account_id,
+                expires_in_minutes,
+                command,
+            } => {
+                self.cmd_impersonate(account_id, expires_in_minutes, command)
+                    .await
+            }
+        }
+    }
+
+    async fn cmd_impersonate(
+        &self,
+        account_id: AccountId,
+        expires_in_minutes: u32,
+        command: Vec<String>,
+    ) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients_cloud().await?;
+
+        let roles = clients
+            .grant
+            .get_account_grants(&clients.account_id().0)
+            .await
+            .map_service_error()?;
+
+        if !roles
+            .into_iter()
+            .any(|role| Role::from(role) == Role::Admin)
+        {
+            log_error(format!(
+                "Impersonating other accounts requires the {} role",
+                Role::Admin.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let args = std::env::args_os().collect::<Vec<_>>();
+        history::record_impersonation_started(
+            self.ctx.config_dir(),
+            self.ctx.profile_name(),
+            &args,
+            &account_id,
+        )
+        .context("Failed to record the impersonation audit entry, not impersonating")?;
+
+        let token = match clients
+            .token
+            .create_token(
+                &account_id.0,
+                &CreateTokenDto {
+                    expires_at: Utc::now() + Duration::minutes(expires_in_minutes as i64),
+                },
+            )
+            .await
+            .map_service_error()
+        {
+            Ok(token) => token,
+            Err(error) => {
+                self.record_impersonation_finished(&args, &account_id, false)?;
+                return Err(error);
+            }
+        };
+
+        log_warn_action(
+            "Impersonating",
+            format!(
+                "account {}, token {} expires at {}",
+                account_id.0.log_color_highlight(),
+                token.data.id.to_string().log_color_highlight(),
+                token.data.expires_at.to_string().log_color_highlight()
+            ),
+        );
+        logln("");
+
+        let result = self.run_impersonated(token.secret.value, &command);
+
+        let recorded = self.record_impersonation_finished(
+            &args,
+            &account_id,
+            result.as_ref().is_ok_and(|success| *success),
+        );
+
+        logln("");
+        match clients
+            .token
+            .delete_token(&account_id.0, &token.data.id)
+            .await
+            .map_service_error()
+        {
+            Ok(_) => log_action(
+                "Revoked",
+                format!(
+                    "impersonation token {}",
+                    token.data.id.to_string().log_color_highlight()
+                ),
+            ),
+            Err(error) => log_error_action(
+                "Failed",
+                format!(
+                    "to revoke impersonation token {}, it expires at {}: {:#}",
+                    token.data.id.to_string().log_color_highlight(),
+                    token.data.expires_at.to_string().log_color_highlight(),
+                    error
+                ),
+            ),
+        }
+
+        recorded?;
+        if !result? {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    fn record_impersonation_finished(
+        &self,
+        args: &[OsString],
+        account_id: &AccountId,
+        success: bool,
+    ) -> anyhow::Result<()> {
+        history::record_impersonation_finished(
+            self.ctx.config_dir(),
+            self.ctx.profile_name(),
+            args,
+            account_id,
+            success,
+        )
+        .context("Failed to record the outcome of the impersonation into the audit entries")
+    }
+
+    /// Runs the command with the current executable, using the same profile, but authenticated
+    /// with the impersonation token. The token is passed using the environment, so it does not
+    /// show up in the process list or in the recorded history.
+    fn run_impersonated(&self, token_secret: Uuid, command: &[String]) -> anyhow::Result<bool> {
+        let executable = std::env::current_exe().context("Failed to get the current executable")?;
+
+        let status = std::process::Command::new(executable)
+            .args(command)
+            .env("GOLEM_PROFILE", self.ctx.profile_name().0.as_str())
+            .env("GOLEM_AUTH_TOKEN", token_secret.to_string())
+            .status()
+            .context("Failed to execute impersonated command")?;
+
+        if !status.success() {
+            log_error_action("Failed", format!("impersonated command, {}", status));
+        }
+
+        Ok(status.success())
+    }
+}
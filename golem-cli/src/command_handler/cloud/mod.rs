@@ -18,6 +18,7 @@ use crate::context::Context;
 use std::sync::Arc;
 
 pub mod account;
+pub mod admin;
 pub mod project;
 pub mod token;
 
@@ -50,6 +51,12 @@ impl CloudCommandHandler {
                     .handle_command(subcommand)
                     .await
             }
+            CloudSubcommand::Admin { subcommand } => {
+                self.ctx
+                    .cloud_admin_handler()
+                    .handle_command(subcommand)
+                    .await
+            }
         }
     }
 }
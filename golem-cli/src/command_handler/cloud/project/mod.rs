@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cloud::AccountId;
+use crate::cloud::{AccountId, ProjectId};
 use crate::command::cloud::project::{ProjectActionsOrPolicyId, ProjectSubcommand};
 use crate::command_handler::Handlers;
 use crate::config::ProfileKind;
@@ -20,18 +20,34 @@ use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::HintError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{logln, LogColorize};
-use crate::model::project::ProjectView;
+use crate::fs;
+use crate::log::{log_action, log_warn_action, logln, LogColorize};
+use crate::model::project::{
+    ProjectTeam, ProjectTeamAccount, ProjectTeamMember, ProjectTeamPolicy, ProjectView,
+};
 use crate::model::text::fmt::{log_error, log_text_view};
 use crate::model::text::help::ComponentNameHelp;
 use crate::model::text::project::{
-    ProjectCreatedView, ProjectGetView, ProjectGrantView, ProjectListView,
+    ProjectCreatedView, ProjectGetView, ProjectGrantManyView, ProjectGrantOutcome,
+    ProjectGrantView, ProjectListView,
+};
+use crate::model::{ProjectName, ProjectNameAndId, ProjectPolicyGrant};
+use crate::name_cache;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use futures_util::StreamExt;
+use golem_cloud_client::api::{
+    AccountClient, ProjectClient, ProjectGrantClient, ProjectPolicyClient,
+};
+use golem_cloud_client::model::{
+    Project, ProjectActions, ProjectDataRequest, ProjectGrantDataRequest, ProjectPolicyData,
 };
-use crate::model::{ProjectName, ProjectNameAndId};
-use anyhow::{anyhow, bail};
-use golem_cloud_client::api::{ProjectClient, ProjectGrantClient};
-use golem_cloud_client::model::{Project, ProjectDataRequest, ProjectGrantDataRequest};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use uuid::Uuid;
+
+// Maximum number of grants created concurrently by project grant-many
+const GRANT_MANY_CONCURRENCY: usize = 8;
 
 pub mod plugin;
 pub mod policy;
@@ -50,7 +66,8 @@ impl CloudProjectCommandHandler {
             ProjectSubcommand::New {
                 project_name,
                 description,
-            } => self.cmd_new(project_name, description).await,
+                grant,
+            } => self.cmd_new(project_name, description, grant).await,
             ProjectSubcommand::List { project_name } => self.cmd_list(project_name).await,
             ProjectSubcommand::GetDefault => self.cmd_get_default().await,
             ProjectSubcommand::Grant {
@@ -65,6 +82,9 @@ impl CloudProjectCommandHandler {
                 )
                 .await
             }
+            ProjectSubcommand::GrantMany { project, file } => {
+                self.cmd_grant_many(project, file).await
+            }
             ProjectSubcommand::Policy { subcommand } => {
                 self.ctx
                     .cloud_project_policy_handler()
@@ -84,6 +104,7 @@ impl CloudProjectCommandHandler {
         &mut self,
         project_name: ProjectName,
         description: Option<String>,
+        grants: Vec<ProjectPolicyGrant>,
     ) -> anyhow::Result<()> {
         let clients = self.ctx.golem_clients_cloud().await?;
         let project = clients
@@ -95,6 +116,67 @@ impl CloudProjectCommandHandler {
             })
             .await
             .map_service_error()?;
+
+        for grant in grants {
+            let result = clients
+                .project_grant
+                .create_project_grant(
+                    &project.project_id,
+                    &ProjectGrantDataRequest {
+                        grantee_account_id: grant.account_id.0.clone(),
+                        project_policy_id: Some(grant.policy_id.0),
+                        project_actions: vec![],
+                        project_policy_name: None,
+                    },
+                )
+                .await
+                .map_service_error();
+
+            match result {
+                Ok(_) => {
+                    log_action(
+                        "Granted",
+                        format!(
+                            "project policy {} to account {}",
+                            grant.policy_id.0.to_string().log_color_highlight(),
+                            grant.account_id.0.log_color_highlight()
+                        ),
+                    );
+                }
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to grant project policy {} to account {}, deleting the new project",
+                        grant.policy_id.0.to_string().log_color_highlight(),
+                        grant.account_id.0.log_color_highlight()
+                    ));
+                    if let Err(delete_err) = clients
+                        .project
+                        .delete_project(&project.project_id)
+                        .await
+                        .map_service_error()
+                    {
+                        log_error(format!(
+                            "Failed to delete project {}, the project still exists without the requested grants",
+                            project.project_data.name.log_color_highlight()
+                        ));
+                        return Err(err.context(format!(
+                            "Failed to delete project {} after failed grant, the project still exists: {:#}",
+                            project.project_data.name.log_color_highlight(),
+                            delete_err
+                        )));
+                    }
+                    log_warn_action(
+                        "Deleted",
+                        format!(
+                            "project {}",
+                            project.project_data.name.log_color_highlight()
+                        ),
+                    );
+                    return Err(err);
+                }
+            }
+        }
+
         self.ctx
             .log_handler()
             .log_view(&ProjectCreatedView(ProjectView::from(project)));
@@ -198,14 +280,47 @@ impl CloudProjectCommandHandler {
                 bail!(HintError::ExpectedCloudProfile);
             }
             (ProfileKind::Oss, None) => Ok(None),
-            (ProfileKind::Cloud, Some(project_name)) => {
-                let project = self.project_by_name(account_id, project_name).await?;
-                Ok(Some(ProjectNameAndId {
-                    project_name: project.project_data.name.into(),
-                    project_id: project.project_id.into(),
-                }))
+            (ProfileKind::Cloud, project_name) => {
+                // Falling back to the default project of the profile, if configured
+                let project_name =
+                    project_name.or(self.ctx.profile_config().default_project.as_ref());
+                match project_name {
+                    Some(project_name) => {
+                        let cache_key = self
+                            .ctx
+                            .name_cache_scope()
+                            .map(|scope| name_cache::project_key(&scope, account_id, project_name));
+                        if let Some((name, id)) = cache_key.as_ref().and_then(|cache_key| {
+                            name_cache::cached(
+                                self.ctx.config_dir(),
+                                cache_key,
+                                self.ctx.name_cache_ttl(),
+                            )
+                        }) {
+                            return Ok(Some(ProjectNameAndId {
+                                project_name: name.into(),
+                                project_id: id.into(),
+                            }));
+                        }
+
+                        let project = self.project_by_name(account_id, project_name).await?;
+                        if let Some(cache_key) = cache_key {
+                            name_cache::cache(
+                                self.ctx.config_dir(),
+                                cache_key,
+                                project.project_data.name.clone(),
+                                project.project_id,
+                                self.ctx.name_cache_ttl(),
+                            );
+                        }
+                        Ok(Some(ProjectNameAndId {
+                            project_name: project.project_data.name.into(),
+                            project_id: project.project_id.into(),
+                        }))
+                    }
+                    None => Ok(None),
+                }
             }
-            (ProfileKind::Cloud, None) => Ok(None),
         }
     }
 
@@ -227,21 +342,58 @@ impl CloudProjectCommandHandler {
         &self,
         project: Option<ProjectNameAndId>,
     ) -> anyhow::Result<ProjectNameAndId> {
-        match project {
-            Some(project_name) => Ok(project_name),
-            None => self
-                .ctx
-                .golem_clients_cloud()
-                .await?
-                .project
-                .get_default_project()
-                .await
-                .map_service_error()
-                .map(|project| ProjectNameAndId {
-                    project_name: project.project_data.name.into(),
-                    project_id: project.project_id.into(),
-                }),
+        if let Some(project) = project {
+            return Ok(project);
         }
+
+        let cache_key = self
+            .ctx
+            .name_cache_scope()
+            .map(|scope| name_cache::default_project_key(&scope));
+        if let Some((name, id)) = cache_key.as_ref().and_then(|cache_key| {
+            name_cache::cached(self.ctx.config_dir(), cache_key, self.ctx.name_cache_ttl())
+        }) {
+            return Ok(ProjectNameAndId {
+                project_name: name.into(),
+                project_id: id.into(),
+            });
+        }
+
+        let project = self
+            .ctx
+            .golem_clients_cloud()
+            .await?
+            .project
+            .get_default_project()
+            .await
+            .map_service_error()?;
+        if let Some(cache_key) = cache_key {
+            name_cache::cache(
+                self.ctx.config_dir(),
+                cache_key,
+                project.project_data.name.clone(),
+                project.project_id,
+                self.ctx.name_cache_ttl(),
+            );
+        }
+        Ok(ProjectNameAndId {
+            project_name: project.project_data.name.into(),
+            project_id: project.project_id.into(),
+        })
+    }
+
+    pub async fn project_by_id(&self, project_id: &ProjectId) -> anyhow::Result<ProjectNameAndId> {
+        self.ctx
+            .golem_clients_cloud()
+            .await?
+            .project
+            .get_project(&project_id.0)
+            .await
+            .map_service_error()
+            .map(|project| ProjectNameAndId {
+                project_name: project.project_data.name.into(),
+                project_id: project.project_id.into(),
+            })
     }
 
     async fn cmd_grant(
@@ -276,6 +428,135 @@ impl CloudProjectCommandHandler {
 
         Ok(())
     }
+
+    async fn cmd_grant_many(&self, project_name: ProjectName, file: PathBuf) -> anyhow::Result<()> {
+        let team: ProjectTeam = serde_yaml::from_str(&fs::read_to_string(&file)?)
+            .with_context(|| anyhow!("Failed to parse team file {}", file.log_color_highlight()))?;
+        let policies = team.policies_to_grant()?;
+
+        let project = self.select_project(None, &project_name).await?;
+        let clients = self.ctx.golem_clients_cloud().await?;
+
+        let mut policy_ids = HashMap::<String, Uuid>::new();
+        for (policy_ref, policy) in policies {
+            let policy_id = match policy {
+                ProjectTeamPolicy::Existing(policy_id) => policy_id.0,
+                ProjectTeamPolicy::New { name, actions } => {
+                    let policy = clients
+                        .project_policy
+                        .create_project_policy(&ProjectPolicyData {
+                            name: format!("{}-{}", project.project_name.0, name),
+                            project_actions: ProjectActions {
+                                actions: actions.into_iter().map(|a| a.into()).collect(),
+                            },
+                        })
+                        .await
+                        .map_service_error()
+                        .with_context(|| {
+                            anyhow!(
+                                "Failed to create project policy {}",
+                                name.log_color_highlight()
+                            )
+                        })?;
+                    log_action(
+                        "Created",
+                        format!(
+                            "project policy {} ({})",
+                            policy.name.log_color_highlight(),
+                            policy.id.to_string().log_color_highlight()
+                        ),
+                    );
+                    policy.id
+                }
+            };
+            policy_ids.insert(policy_ref, policy_id);
+        }
+
+        log_action(
+            "Granting",
+            format!(
+                "project {} to {} accounts",
+                project.project_name.0.log_color_highlight(),
+                team.members.len().to_string().log_color_highlight()
+            ),
+        );
+
+        let policy_ids = &policy_ids;
+        let project_id = &project.project_id;
+        let outcomes = futures_util::stream::iter(team.members.iter().map(|member| async move {
+            let policy_id = policy_ids[&member.policy];
+            let account_id = self.resolve_team_account(member).await;
+            let grant = match &account_id {
+                Ok(account_id) => clients
+                    .project_grant
+                    .create_project_grant(
+                        &project_id.0,
+                        &ProjectGrantDataRequest {
+                            grantee_account_id: account_id.0.clone(),
+                            project_policy_id: Some(policy_id),
+                            project_actions: vec![],
+                            project_policy_name: None,
+                        },
+                    )
+                    .await
+                    .map_service_error(),
+                Err(err) => Err(anyhow!("{err:#}")),
+            };
+
+            ProjectGrantOutcome {
+                account: member.account.clone(),
+                account_id: account_id.ok().map(|account_id| account_id.0),
+                policy: member.policy.clone(),
+                policy_id: Some(policy_id),
+                grant_id: grant.as_ref().ok().map(|grant| grant.id),
+                error: grant.err().map(|err| format!("{err:#}")),
+            }
+        }))
+        .buffered(GRANT_MANY_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+        let failed = outcomes
+            .iter()
+            .filter(|outcome| outcome.error.is_some())
+            .count();
+
+        self.ctx
+            .log_handler()
+            .log_view(&ProjectGrantManyView(outcomes));
+
+        if failed > 0 {
+            logln("");
+            log_error(format!(
+                "Failed to grant project to {} accounts",
+                failed.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_team_account(&self, member: &ProjectTeamMember) -> anyhow::Result<AccountId> {
+        match member.account() {
+            ProjectTeamAccount::Id(account_id) => Ok(account_id),
+            ProjectTeamAccount::Email(email) => {
+                let mut accounts = self
+                    .ctx
+                    .golem_clients_cloud()
+                    .await?
+                    .account
+                    .find_accounts(Some(&email))
+                    .await
+                    .map_service_error()?;
+                match accounts.len() {
+                    0 => bail!("No account found with email {email}"),
+                    1 => Ok(accounts.pop().unwrap().id.into()),
+                    _ => bail!("Multiple accounts found with email {email}, use the account ID"),
+                }
+            }
+        }
+    }
 }
 
 fn project_not_found(account_id: Option<&AccountId>, project_name: &ProjectName) -> anyhow::Error {
@@ -16,10 +16,16 @@ use crate::command::cloud::project::policy::PolicySubcommand;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::log::{logln, LogColorize};
+use crate::model::text::fmt::log_warn;
 use crate::model::text::project::{ProjectPolicyGetView, ProjectPolicyNewView};
 use crate::model::{ProjectAction, ProjectPolicyId};
+use anyhow::bail;
+use colored::Colorize;
 use golem_cloud_client::api::ProjectPolicyClient;
 use golem_cloud_client::model::{ProjectActions, ProjectPolicyData};
+use std::fmt::Display;
 use std::sync::Arc;
 
 pub struct CloudProjectPolicyCommandHandler {
@@ -38,6 +44,15 @@ impl CloudProjectPolicyCommandHandler {
                 actions,
             } => self.cmd_new(policy_name, actions).await,
             PolicySubcommand::Get { policy_id } => self.cmd_get(policy_id).await,
+            PolicySubcommand::Update {
+                policy_id,
+                policy_name,
+                add_actions,
+                remove_actions,
+            } => {
+                self.cmd_update(policy_id, policy_name, add_actions, remove_actions)
+                    .await
+            }
         }
     }
 
@@ -83,4 +98,171 @@ impl CloudProjectPolicyCommandHandler {
 
         Ok(())
     }
+
+    async fn cmd_update(
+        &self,
+        policy_id: ProjectPolicyId,
+        policy_name: Option<String>,
+        add_actions: Vec<ProjectAction>,
+        remove_actions: Vec<ProjectAction>,
+    ) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients_cloud().await?;
+
+        let policy = clients
+            .project_policy
+            .get_project_policies(&policy_id.0)
+            .await
+            .map_service_error()?;
+
+        let current_actions = policy
+            .project_actions
+            .actions
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let diff = ProjectActionsDiff::new(
+            &current_actions,
+            add_actions.into_iter().map(|a| a.into()).collect(),
+            remove_actions.into_iter().map(|a| a.into()).collect(),
+        );
+
+        if !diff.has_changes() {
+            log_warn(format!(
+                "The actions of project policy {} are already up-to-date, no new policy is created",
+                policy.name.log_color_highlight()
+            ));
+            return Ok(());
+        }
+
+        let policy_name = policy_name.unwrap_or(policy.name);
+
+        logln(format!(
+            "Actions of the updated project policy {}:",
+            policy_name.log_color_highlight()
+        ));
+        diff.log();
+        logln("");
+
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_update_project_policy(&policy_name)?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        let new_policy = clients
+            .project_policy
+            .create_project_policy(&ProjectPolicyData {
+                name: policy_name,
+                project_actions: ProjectActions {
+                    actions: diff.into_actions().into_iter().collect(),
+                },
+            })
+            .await
+            .map_service_error()?;
+
+        let new_policy_id = new_policy.id;
+        self.ctx
+            .log_handler()
+            .log_view(&ProjectPolicyNewView(new_policy));
+
+        logln("");
+        log_warn(format!(
+            "Existing grants still use the original policy {}, grant the new policy with {}",
+            policy_id.0.to_string().log_color_highlight(),
+            format!("--policy-id {}", new_policy_id).log_color_highlight()
+        ));
+
+        Ok(())
+    }
+}
+
+/// Actions of an updated policy, relative to the current actions of the policy
+struct ProjectActionsDiff<T> {
+    kept: Vec<T>,
+    added: Vec<T>,
+    removed: Vec<T>,
+}
+
+impl<T: Clone + PartialEq + Display> ProjectActionsDiff<T> {
+    fn new(current: &[T], add: Vec<T>, remove: Vec<T>) -> Self {
+        let mut kept = Vec::new();
+        let mut removed = Vec::new();
+        for action in current {
+            if remove.contains(action) && !add.contains(action) {
+                removed.push(action.clone());
+            } else {
+                kept.push(action.clone());
+            }
+        }
+
+        let mut added = Vec::<T>::new();
+        for action in add {
+            if !current.contains(&action) && !added.contains(&action) {
+                added.push(action);
+            }
+        }
+
+        Self {
+            kept,
+            added,
+            removed,
+        }
+    }
+
+    fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+
+    fn log(&self) {
+        for action in &self.kept {
+            logln(format!("  {}", action));
+        }
+        for action in &self.removed {
+            logln(format!("- {}", action).red().to_string());
+        }
+        for action in &self.added {
+            logln(format!("+ {}", action).green().to_string());
+        }
+    }
+
+    fn into_actions(self) -> Vec<T> {
+        self.kept.into_iter().chain(self.added).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::cloud::project::policy::ProjectActionsDiff;
+    use crate::model::ProjectAction;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn project_actions_diff() {
+        let diff = ProjectActionsDiff::new(
+            &[ProjectAction::ViewComponent, ProjectAction::ViewWorker],
+            vec![
+                ProjectAction::CreateWorker,
+                ProjectAction::ViewComponent,
+                ProjectAction::CreateWorker,
+            ],
+            vec![ProjectAction::ViewWorker, ProjectAction::DeleteWorker],
+        );
+
+        check!(diff.has_changes());
+        check!(diff.removed == vec![ProjectAction::ViewWorker]);
+        check!(diff.added == vec![ProjectAction::CreateWorker]);
+        check!(
+            diff.into_actions() == vec![ProjectAction::ViewComponent, ProjectAction::CreateWorker]
+        );
+
+        let diff = ProjectActionsDiff::new(
+            &[ProjectAction::ViewComponent],
+            vec![ProjectAction::ViewComponent],
+            vec![ProjectAction::DeleteWorker],
+        );
+        check!(!diff.has_changes());
+    }
 }
@@ -14,16 +14,23 @@
 
 use crate::command::cloud::token::TokenSubcommand;
 use crate::command_handler::Handlers;
+use crate::config::ProfileName;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
-use crate::log::{log_warn_action, LogColorize};
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_warn_action, logln, LogColorize};
+use crate::model::text::fmt::log_error;
 use crate::model::text::token::{TokenListView, TokenNewView};
-use crate::model::TokenId;
+use crate::model::{ProjectName, TokenId, TokenScope};
+use anyhow::bail;
 use chrono::{DateTime, Utc};
 use golem_cloud_client::api::TokenClient;
 use golem_cloud_client::model::CreateTokenDto;
+use itertools::Itertools;
 use std::sync::Arc;
 
+const GITHUB_ACTIONS_SECRET_NAME: &str = "GOLEM_AUTH_TOKEN";
+
 pub struct CloudTokenCommandHandler {
     ctx: Arc<Context>,
 }
@@ -36,7 +43,15 @@ impl CloudTokenCommandHandler {
     pub async fn handle_command(&self, subcommand: TokenSubcommand) -> anyhow::Result<()> {
         match subcommand {
             TokenSubcommand::List => self.cmd_list().await,
-            TokenSubcommand::New { expires_at } => self.cmd_new(expires_at).await,
+            TokenSubcommand::New {
+                expires_at,
+                scope,
+                project,
+                github_actions,
+            } => {
+                self.cmd_new(expires_at, scope, project, github_actions)
+                    .await
+            }
             TokenSubcommand::Delete { token_id } => self.cmd_delete(token_id).await,
         }
     }
@@ -50,12 +65,50 @@ impl CloudTokenCommandHandler {
             .await
             .map_service_error()?;
 
+        let tokens = tokens
+            .into_iter()
+            .sorted_by(|a, b| (a.created_at, a.id).cmp(&(b.created_at, b.id)))
+            .collect();
         self.ctx.log_handler().log_view(&TokenListView(tokens));
 
         Ok(())
     }
 
-    async fn cmd_new(&self, expires_at: DateTime<Utc>) -> anyhow::Result<()> {
+    async fn cmd_new(
+        &self,
+        expires_at: DateTime<Utc>,
+        scopes: Vec<TokenScope>,
+        project: Option<ProjectName>,
+        github_actions: bool,
+    ) -> anyhow::Result<()> {
+        // Tokens are not scoped by the server, so an account wide token is never created
+        // when a limited one was requested
+        if !scopes.is_empty() {
+            log_error("Scoped tokens are not supported by the server");
+            logln("");
+            logln("For least-privilege access, create the token for a dedicated account, and share the project with it using a policy with the following actions:");
+            let actions = scopes
+                .iter()
+                .flat_map(|scope| scope.project_actions())
+                .unique()
+                .collect::<Vec<_>>();
+            for action in &actions {
+                logln(format!("  - {}", action));
+            }
+            logln("");
+            logln(format!(
+                "e.g. 'cloud project grant {} <CI_ACCOUNT_ID> {}'",
+                project
+                    .map(|project| project.0)
+                    .unwrap_or_else(|| "<PROJECT>".to_string()),
+                actions
+                    .iter()
+                    .map(|action| format!("--action {}", action))
+                    .join(" ")
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
         let clients = self.ctx.golem_clients_cloud().await?;
 
         let token = clients
@@ -66,6 +119,26 @@ impl CloudTokenCommandHandler {
 
         self.ctx.log_handler().log_view(&TokenNewView(token));
 
+        if github_actions {
+            logln("");
+            logln(
+                "Store the token as a GitHub Actions secret, pasting the secret above when prompted:"
+                    .log_color_help_group()
+                    .to_string(),
+            );
+            logln(format!("  gh secret set {}", GITHUB_ACTIONS_SECRET_NAME));
+            logln("");
+            logln("Use it in the workflow:".log_color_help_group().to_string());
+            logln("  env:");
+            logln(format!(
+                "    GOLEM_AUTH_TOKEN: ${{{{ secrets.{} }}}}",
+                GITHUB_ACTIONS_SECRET_NAME
+            ));
+            if *self.ctx.profile_name() == ProfileName::cloud() {
+                logln("    GOLEM_PROFILE: cloud");
+            }
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,108 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::ImportCategory;
+use anyhow::{bail, Context};
+use wit_parser::decoding::DecodedWasm;
+use wit_parser::{Resolve, WorldItem, WorldKey};
+
+/// Imported and exported interfaces and functions of a component, decoded from its WASM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInterfaces {
+    pub imports: Vec<String>,
+    pub exports: Vec<String>,
+}
+
+impl ComponentInterfaces {
+    pub fn decode(component_wasm: &[u8]) -> anyhow::Result<Self> {
+        let decoded = wit_parser::decoding::decode(component_wasm)
+            .context("Failed to decode WIT from component WASM")?;
+        let DecodedWasm::Component(resolve, world_id) = decoded else {
+            bail!("Expected a WASM component, got a WIT package");
+        };
+        let world = &resolve.worlds[world_id];
+
+        Ok(Self {
+            imports: item_names(&resolve, world.imports.iter()),
+            exports: item_names(&resolve, world.exports.iter()),
+        })
+    }
+}
+
+fn item_names<'a>(
+    resolve: &Resolve,
+    items: impl Iterator<Item = (&'a WorldKey, &'a WorldItem)>,
+) -> Vec<String> {
+    let mut names = items
+        .filter_map(|(key, item)| match item {
+            WorldItem::Interface { .. } | WorldItem::Function(_) => {
+                Some(resolve.name_world_key(key))
+            }
+            WorldItem::Type(_) => None,
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Sensitive category of an imported interface, None for imports considered safe
+/// (e.g. wasi:io, wasi:random and Golem host interfaces)
+pub fn import_category(import: &str) -> Option<ImportCategory> {
+    let Some((namespace, path)) = import.split_once(':') else {
+        // Inline interfaces and functions imported by plain name
+        return Some(ImportCategory::NonWasi);
+    };
+    let path = path.split_once('@').map(|(path, _)| path).unwrap_or(path);
+
+    match namespace {
+        "wasi" => {
+            let (package, interface) = path.split_once('/').unwrap_or((path, ""));
+            match (package, interface) {
+                ("sockets", _) => Some(ImportCategory::Sockets),
+                ("filesystem", _) => Some(ImportCategory::Filesystem),
+                ("cli", "environment") => Some(ImportCategory::Environment),
+                ("clocks", _) => Some(ImportCategory::Clocks),
+                ("http", "outgoing-handler") => Some(ImportCategory::Http),
+                _ => None,
+            }
+        }
+        "golem" => None,
+        _ => Some(ImportCategory::NonWasi),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::component::import_audit::import_category;
+    use crate::model::ImportCategory;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn import_categories() {
+        check!(import_category("wasi:sockets/tcp@0.2.0") == Some(ImportCategory::Sockets));
+        check!(
+            import_category("wasi:filesystem/preopens@0.2.0") == Some(ImportCategory::Filesystem)
+        );
+        check!(import_category("wasi:cli/environment@0.2.0") == Some(ImportCategory::Environment));
+        check!(import_category("wasi:cli/stdout@0.2.0").is_none());
+        check!(import_category("wasi:clocks/wall-clock@0.2.0") == Some(ImportCategory::Clocks));
+        check!(import_category("wasi:http/outgoing-handler@0.2.0") == Some(ImportCategory::Http));
+        check!(import_category("wasi:http/types@0.2.0").is_none());
+        check!(import_category("wasi:io/streams@0.2.0").is_none());
+        check!(import_category("golem:api/host@1.1.7").is_none());
+        check!(import_category("acme:billing-client/api") == Some(ImportCategory::NonWasi));
+        check!(import_category("log") == Some(ImportCategory::NonWasi));
+    }
+}
@@ -17,30 +17,56 @@ use crate::cloud::AccountId;
 use crate::command::builtin_app_subcommands;
 use crate::command::component::ComponentSubcommand;
 use crate::command::shared_args::{
-    BuildArgs, ComponentOptionalComponentNames, ComponentTemplatePositionalArg, ForceBuildArg,
-    WorkerUpdateOrRedeployArgs,
+    AwaitProcessingArg, BuildArgs, ComponentOptionalComponentNames, ComponentTemplatePositionalArg,
+    ForceBuildArg, ProjectNameOptionalArg, WorkerFunctionName, WorkerUpdateOrRedeployArgs,
 };
-use crate::command_handler::component::ifs::IfsArchiveBuilder;
+use crate::command_handler::component::ifs::{ComponentFilesArchive, IfsArchiveBuilder};
+use crate::command_handler::component::import_audit::{import_category, ComponentInterfaces};
+use crate::command_handler::component::processing::{
+    await_with_processing_status, processing_failure_message, TransformerPlugin, UploadProgress,
+};
+use crate::command_handler::component::remote_wasm::RemoteComponentWasm;
+use crate::command_handler::component::wasm_binary::{adapt_preview1_module, WasmBinaryKind};
 use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
-use crate::error::NonSuccessfulExit;
-use crate::log::{log_action, logln, LogColorize, LogIndent};
+use crate::error::{CancelledError, NonSuccessfulExit};
+use crate::log::{log_action, log_warn_action, logln, LogColorize, LogIndent};
 use crate::model::app::{
     AppComponentName, ApplicationComponentSelectMode, BuildProfileName, DynamicHelpSections,
 };
 use crate::model::app::{DependencyType, InitialComponentFile};
-use crate::model::component::{Component, ComponentView};
-use crate::model::deploy::TryUpdateAllWorkersResult;
-use crate::model::text::component::{ComponentCreateView, ComponentGetView, ComponentUpdateView};
+use crate::model::component::{function_params, function_result_types, Component, ComponentView};
+use crate::model::deploy::{
+    linked_wasm_hash, locked_deploy_action, ComponentDrift, DeclaredComponentProperties,
+    DeployLock, LockedComponent, LockedDeployAction, TryUpdateAllWorkersResult,
+};
+use crate::model::json_schema::function_json_schema;
+use crate::model::payload::PayloadKind;
+use crate::model::text::component::{
+    ComponentCreateView, ComponentDriftView, ComponentGetView, ComponentInspectView,
+    ComponentUpdateView, FunctionSchemaView, InspectedImport,
+};
 use crate::model::text::fmt::{log_error, log_text_view, log_warn};
 use crate::model::text::help::ComponentNameHelp;
 use crate::model::to_cloud::ToCloud;
 use crate::model::{
-    ComponentName, ComponentNameMatchKind, ProjectNameAndId, SelectedComponents, WorkerName,
+    ClientDirection, ComponentName, ComponentNameMatchKind, ImportCategory, ProjectNameAndId,
+    SelectedComponents, TemplateDescription, TemplateFiles, TemplateFilter, WorkerName,
     WorkerUpdateMode,
 };
+use crate::version_notes::{VersionNote, VersionNotes};
+use crate::wasm_rpc_stubgen::commands::generate::generate_and_copy_client_wit;
+use crate::wasm_rpc_stubgen::stub::{
+    ClientBorrowMode, RustDependencyOverride, StubConfig, StubDefinition,
+};
+use crate::wasm_rpc_stubgen::wit_generate::{
+    generate_and_copy_import_mock_wit, generate_client_wit_from_stub_def,
+    generate_import_mock_package,
+};
+use crate::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
 use anyhow::{anyhow, bail, Context as AnyhowContext};
+use chrono::Utc;
 use golem_client::api::ComponentClient as ComponentClientOss;
 use golem_client::model::DynamicLinkedInstance as DynamicLinkedInstanceOss;
 use golem_client::model::DynamicLinkedWasmRpc as DynamicLinkedWasmRpcOss;
@@ -49,16 +75,29 @@ use golem_cloud_client::api::ComponentClient as ComponentClientCloud;
 use golem_cloud_client::model::ComponentQuery;
 use golem_common::model::component_metadata::WasmRpcTarget;
 use golem_common::model::{ComponentId, ComponentType};
-use golem_templates::add_component_by_template;
-use golem_templates::model::{GuestLanguage, PackageName};
+use golem_templates::model::{ComposableAppGroupName, GuestLanguage, PackageName};
+use golem_templates::{add_component_by_template, template_files};
+use golem_wasm_rpc::WASM_RPC_VERSION;
 use itertools::Itertools;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
 use tokio::fs::File;
+use url::Url;
+use uuid::Uuid;
 
 pub mod ifs;
+pub mod import_audit;
 pub mod plugin;
+pub mod processing;
+pub mod remote_wasm;
+pub mod wasm_binary;
+
+const AWAIT_PROCESSING_TIMEOUT: Duration = Duration::from_secs(300);
+const AWAIT_PROCESSING_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct ComponentCommandHandler {
     ctx: Arc<Context>,
@@ -75,10 +114,65 @@ impl ComponentCommandHandler {
                 template,
                 component_package_name,
             } => self.cmd_new(template, component_package_name).await,
-            ComponentSubcommand::Templates { filter } => {
-                self.cmd_templates(filter);
-                Ok(())
+            ComponentSubcommand::Add {
+                project,
+                component_name,
+                component_wasm,
+                url,
+                sha256,
+                ephemeral,
+                files,
+                auto_adapt,
+                output,
+            } => {
+                self.cmd_add(
+                    project,
+                    component_name,
+                    component_wasm,
+                    url,
+                    sha256,
+                    ephemeral,
+                    files,
+                    auto_adapt,
+                    output.field("component_id"),
+                )
+                .await
             }
+            ComponentSubcommand::Templates {
+                filter,
+                language,
+                tier,
+                search,
+                tree,
+            } => {
+                if let Some(template) = tree {
+                    self.cmd_template_tree(&template)
+                } else if language.is_some() || tier.is_some() || search.is_some() {
+                    self.cmd_list_templates(TemplateFilter {
+                        language,
+                        tier,
+                        search,
+                    });
+                    Ok(())
+                } else {
+                    self.cmd_templates(filter);
+                    Ok(())
+                }
+            }
+            ComponentSubcommand::ClientWit {
+                source_wit_root,
+                world,
+                direction,
+                ephemeral,
+                output_dir,
+            } => match direction {
+                ClientDirection::Export => {
+                    self.cmd_client_wit(source_wit_root, world, ephemeral, output_dir)
+                }
+                ClientDirection::Import => {
+                    self.cmd_import_mock_wit(source_wit_root, world, output_dir)
+                }
+            },
             ComponentSubcommand::Build {
                 component_name,
                 build: build_args,
@@ -86,10 +180,23 @@ impl ComponentCommandHandler {
             ComponentSubcommand::Deploy {
                 component_name,
                 force_build,
+                await_processing,
                 update_or_redeploy,
+                plan,
+                locked,
+                note,
+                set_meta,
             } => {
-                self.cmd_deploy(component_name, force_build, update_or_redeploy)
-                    .await
+                self.cmd_deploy(
+                    component_name,
+                    force_build,
+                    await_processing,
+                    update_or_redeploy,
+                    plan,
+                    locked,
+                    VersionNote::new(note, set_meta),
+                )
+                .await
             }
             ComponentSubcommand::Clean { component_name } => self.cmd_clean(component_name).await,
             ComponentSubcommand::List { component_name } => {
@@ -98,7 +205,16 @@ impl ComponentCommandHandler {
             ComponentSubcommand::Get {
                 component_name,
                 version,
-            } => self.cmd_get(component_name.component_name, version).await,
+                output,
+            } => {
+                self.cmd_get(
+                    component_name.component_name,
+                    version,
+                    output.field("component_id"),
+                )
+                .await
+            }
+            ComponentSubcommand::Diff { component_name } => self.cmd_diff(component_name).await,
 
             ComponentSubcommand::UpdateWorkers {
                 component_name,
@@ -120,6 +236,18 @@ impl ComponentCommandHandler {
             ComponentSubcommand::Diagnose { component_name } => {
                 self.cmd_diagnose(component_name).await
             }
+            ComponentSubcommand::FunctionSchema {
+                component_name,
+                function,
+                version,
+            } => {
+                self.cmd_function_schema(component_name.component_name, function, version)
+                    .await
+            }
+            ComponentSubcommand::Inspect {
+                component_wasm,
+                deny,
+            } => self.cmd_inspect(component_wasm, deny),
         }
     }
 
@@ -229,22 +357,128 @@ impl ComponentCommandHandler {
         &mut self,
         component_name: ComponentOptionalComponentNames,
         force_build: ForceBuildArg,
+        await_processing: AwaitProcessingArg,
         update_or_redeploy: WorkerUpdateOrRedeployArgs,
+        plan: bool,
+        locked: bool,
+        version_note: VersionNote,
     ) -> anyhow::Result<()> {
-        self.deploy(
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None, None)
+            .await?;
+
+        if plan {
+            // Building first, so the plan is based on the up-to-date linked WASMs
             self.ctx
-                .cloud_project_handler()
-                .opt_select_project(None, None)
-                .await?
-                .as_ref(),
+                .app_handler()
+                .build(
+                    component_name.component_name,
+                    Some(BuildArgs {
+                        step: vec![],
+                        force_build,
+                        reproducible: false,
+                        keep_temp: false,
+                        version_check: None,
+                        strict: false,
+                        jobs: 1,
+                        install_tools: false,
+                    }),
+                    &ApplicationComponentSelectMode::CurrentDir,
+                )
+                .await?;
+            return self.log_drift(project.as_ref()).await;
+        }
+
+        self.deploy(
+            project.as_ref(),
             component_name.component_name,
             Some(force_build),
             &ApplicationComponentSelectMode::CurrentDir,
+            await_processing,
             update_or_redeploy,
+            locked,
+            (!version_note.is_empty()).then_some(&version_note),
         )
         .await
     }
 
+    async fn cmd_diff(
+        &mut self,
+        component_names: ComponentOptionalComponentNames,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None, None)
+            .await?;
+
+        self.ctx
+            .app_handler()
+            .must_select_components(
+                component_names.component_name,
+                &ApplicationComponentSelectMode::CurrentDir,
+            )
+            .await?;
+
+        self.log_drift(project.as_ref()).await
+    }
+
+    /// Logs the drift report of the selected application components
+    async fn log_drift(&mut self, project: Option<&ProjectNameAndId>) -> anyhow::Result<()> {
+        let drift = self.drift(project).await?;
+        self.ctx.log_handler().log_view(&ComponentDriftView(drift));
+        Ok(())
+    }
+
+    /// Compares the latest deployed versions of the selected application components with their
+    /// manifest properties
+    pub async fn drift(
+        &mut self,
+        project: Option<&ProjectNameAndId>,
+    ) -> anyhow::Result<Vec<ComponentDrift>> {
+        let build_profile = self.ctx.build_profile().cloned();
+        let selected_component_names = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            app_ctx
+                .some_or_err()?
+                .selected_component_names()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        let mut drift = Vec::with_capacity(selected_component_names.len());
+        for component_name in &selected_component_names {
+            let declared = {
+                let mut app_ctx = self.ctx.app_context_lock_mut().await;
+                let app_ctx = app_ctx.some_or_err_mut()?;
+                if !app_ctx
+                    .application
+                    .component_properties(component_name, build_profile.as_ref())
+                    .is_deployable()
+                {
+                    continue;
+                }
+                component_declared_properties(app_ctx, component_name, build_profile.as_ref())
+                    .await?
+            };
+
+            let deployed = self
+                .component_by_name(project, &component_name.as_str().into(), None)
+                .await?;
+
+            drift.push(ComponentDrift::new(
+                component_name.as_str().into(),
+                deployed.map(ComponentView::from).as_ref(),
+                &declared,
+            ));
+        }
+
+        Ok(drift)
+    }
+
     fn cmd_templates(&self, filter: Option<String>) {
         match filter {
             Some(filter) => {
@@ -262,6 +496,120 @@ impl ComponentCommandHandler {
         }
     }
 
+    fn cmd_list_templates(&self, filter: TemplateFilter) {
+        let templates = self
+            .ctx
+            .templates()
+            .values()
+            .filter_map(|groups| groups.get(&ComposableAppGroupName::default()))
+            .flat_map(|templates| templates.components.values())
+            .filter(|template| {
+                filter.matches(template.language, &template.name, &template.description)
+            })
+            .map(TemplateDescription::from_template)
+            .collect::<Vec<_>>();
+
+        self.ctx.log_handler().log_view(&templates);
+    }
+
+    fn cmd_template_tree(&self, template_name: &str) -> anyhow::Result<()> {
+        let app_handler = self.ctx.app_handler();
+        let (common_template, component_template) = app_handler.get_template(template_name)?;
+
+        let files = common_template
+            .into_iter()
+            .chain(std::iter::once(component_template))
+            .flat_map(template_files)
+            .sorted()
+            .dedup()
+            .collect();
+
+        self.ctx.log_handler().log_view(&TemplateFiles {
+            template: template_name.to_string(),
+            files,
+        });
+
+        Ok(())
+    }
+
+    fn cmd_client_wit(
+        &self,
+        source_wit_root: PathBuf,
+        world: Option<String>,
+        ephemeral: bool,
+        output_dir: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        // Extracting the exports package modifies the WIT root, so it is done on a copy
+        let source_copy = tempfile::tempdir()?;
+        let client_root = tempfile::tempdir()?;
+        fs_extra::dir::copy(
+            &source_wit_root,
+            source_copy.path(),
+            &fs_extra::dir::CopyOptions::new().content_only(true),
+        )
+        .with_context(|| {
+            anyhow!(
+                "Failed to copy source WIT root {}",
+                source_wit_root.log_color_error_highlight()
+            )
+        })?;
+
+        let stub_def = StubDefinition::new(StubConfig {
+            source_wit_root: source_copy.path().to_path_buf(),
+            client_root: client_root.path().to_path_buf(),
+            selected_world: world,
+            stub_crate_version: WASM_RPC_VERSION.to_string(),
+            golem_rust_override: RustDependencyOverride::default(),
+            extract_source_exports_package: true,
+            seal_cargo_workspace: false,
+            // Only used for the generated Rust sources
+            component_name: AppComponentName::from("preview"),
+            is_ephemeral: ephemeral,
+            borrow_mode: ClientBorrowMode::default(),
+            client_derives: vec![],
+        })
+        .context("Failed to resolve the source WIT root")?;
+
+        match output_dir {
+            Some(output_dir) => {
+                generate_and_copy_client_wit(&stub_def, &output_dir)?;
+                log_action(
+                    "Generated",
+                    format!(
+                        "client WIT directory to {}",
+                        output_dir.log_color_highlight()
+                    ),
+                );
+            }
+            None => logln(generate_client_wit_from_stub_def(&stub_def)?),
+        }
+
+        Ok(())
+    }
+
+    fn cmd_import_mock_wit(
+        &self,
+        source_wit_root: PathBuf,
+        world: Option<String>,
+        output_dir: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let source = ResolvedWitDir::new(&source_wit_root)
+            .context("Failed to resolve the source WIT root")?;
+
+        match output_dir {
+            Some(output_dir) => {
+                generate_and_copy_import_mock_wit(&source, world.as_deref(), &output_dir)?;
+                log_action(
+                    "Generated",
+                    format!("mock WIT directory to {}", output_dir.log_color_highlight()),
+                );
+            }
+            None => logln(generate_import_mock_package(&source, world.as_deref())?.to_string()),
+        }
+
+        Ok(())
+    }
+
     async fn cmd_list(&self, component_name: Option<ComponentName>) -> anyhow::Result<()> {
         let selected_component_names = self
             .opt_select_components_by_app_or_name(component_name.as_ref())
@@ -354,6 +702,11 @@ impl ComponentCommandHandler {
         if component_views.is_empty() {
             bail!(NonSuccessfulExit)
         } else {
+            let mut component_views = self.with_version_notes(component_views)?;
+            component_views.sort_by(|a, b| {
+                (&a.component_name.0, a.component_version)
+                    .cmp(&(&b.component_name.0, b.component_version))
+            });
             self.ctx.log_handler().log_view(&component_views);
         }
 
@@ -364,7 +717,10 @@ impl ComponentCommandHandler {
         &self,
         component_name: Option<ComponentName>,
         version: Option<u64>,
+        output_field: Option<&str>,
     ) -> anyhow::Result<()> {
+        self.ctx.log_handler().prepare_output_field(output_field);
+
         let selected_components = self
             .must_select_components_by_app_or_name(component_name.as_ref())
             .await?;
@@ -461,11 +817,13 @@ impl ComponentCommandHandler {
         }
 
         let no_matches = component_views.is_empty();
-        for component_view in component_views {
+        for component_view in self.with_version_notes(component_views)? {
             self.ctx
                 .log_handler()
-                .log_view(&ComponentGetView(component_view));
-            logln("");
+                .log_view_field(&ComponentGetView(component_view), output_field)?;
+            if output_field.is_none() {
+                logln("");
+            }
         }
 
         if no_matches {
@@ -563,14 +921,173 @@ impl ComponentCommandHandler {
             .await
     }
 
+    fn cmd_inspect(
+        &self,
+        component_wasm: PathBuf,
+        deny: Vec<ImportCategory>,
+    ) -> anyhow::Result<()> {
+        let bytes = std::fs::read(&component_wasm).with_context(|| {
+            anyhow!(
+                "Failed to read component WASM at {}",
+                component_wasm
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight()
+            )
+        })?;
+
+        if let Some(problem) = WasmBinaryKind::detect(&bytes).problem() {
+            log_error(format!(
+                "{} {}",
+                component_wasm
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight(),
+                problem
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let interfaces = ComponentInterfaces::decode(&bytes)?;
+        let imports = interfaces
+            .imports
+            .into_iter()
+            .map(|name| InspectedImport {
+                category: import_category(&name),
+                name,
+            })
+            .collect::<Vec<_>>();
+        let denied = imports
+            .iter()
+            .filter_map(|import| import.category)
+            .filter(|category| deny.contains(category))
+            .sorted()
+            .dedup()
+            .collect::<Vec<_>>();
+
+        self.ctx.log_handler().log_view(&ComponentInspectView {
+            component_wasm: component_wasm.display().to_string(),
+            imports,
+            exports: interfaces.exports,
+            denied: denied.clone(),
+        });
+
+        if !denied.is_empty() {
+            logln("");
+            log_error(format!(
+                "Component imports denied interfaces: {}",
+                denied
+                    .iter()
+                    .map(|category| category.to_string().log_color_error_highlight())
+                    .join(", ")
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_function_schema(
+        &self,
+        component_name: Option<ComponentName>,
+        function_name: WorkerFunctionName,
+        version: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let selected_components = self
+            .must_select_components_by_app_or_name(component_name.as_ref())
+            .await?;
+
+        if selected_components.component_names.len() != 1 {
+            log_error(format!(
+                "Multiple components were selected: {}",
+                selected_components
+                    .component_names
+                    .iter()
+                    .map(|component_name| component_name.0.log_color_highlight())
+                    .join(", ")
+            ));
+            logln("");
+            logln("Please specify a single component name!");
+            logln("");
+            log_text_view(&ComponentNameHelp);
+            bail!(NonSuccessfulExit);
+        }
+        let component_name = &selected_components.component_names[0];
+
+        let Some(component) = self
+            .component_by_name(selected_components.project.as_ref(), component_name, None)
+            .await?
+        else {
+            log_error(format!(
+                "Component {} not found",
+                component_name.0.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let component = match version {
+            Some(version) if version != component.versioned_component_id.version => {
+                let component_id = &component.versioned_component_id.component_id;
+                let component = match self.ctx.golem_clients().await? {
+                    GolemClients::Oss(clients) => clients
+                        .component
+                        .get_component_metadata(component_id, &version.to_string())
+                        .await
+                        .map_service_error_not_found_as_opt()?
+                        .map(Component::from),
+                    GolemClients::Cloud(clients) => clients
+                        .component
+                        .get_component_metadata(component_id, &version.to_string())
+                        .await
+                        .map_service_error_not_found_as_opt()?
+                        .map(Component::from),
+                };
+                match component {
+                    Some(component) => component,
+                    None => {
+                        log_error(format!(
+                            "Component {} version {} not found",
+                            component_name.0.log_color_error_highlight(),
+                            version.to_string().log_color_error_highlight()
+                        ));
+                        bail!(NonSuccessfulExit);
+                    }
+                }
+            }
+            _ => component,
+        };
+
+        let function_name = self.ctx.worker_handler().match_function_name(
+            component_name,
+            &component,
+            &function_name,
+        )?;
+
+        let schema = function_json_schema(
+            &function_name,
+            &function_params(&component, &function_name)?,
+            &function_result_types(&component, &function_name)?,
+        );
+
+        self.ctx.log_handler().log_view(&FunctionSchemaView(schema));
+
+        Ok(())
+    }
+
     pub async fn deploy(
         &mut self,
         project: Option<&ProjectNameAndId>,
         component_names: Vec<ComponentName>,
         force_build: Option<ForceBuildArg>,
         default_component_select_mode: &ApplicationComponentSelectMode,
+        await_processing: AwaitProcessingArg,
         update_or_redeploy: WorkerUpdateOrRedeployArgs,
+        locked: bool,
+        version_note: Option<&VersionNote>,
     ) -> anyhow::Result<()> {
+        let cancellation_token = self.ctx.cancellation_token().clone();
+        let _cancellable_scope = cancellation_token.cancellable_scope();
+
         self.ctx
             .app_handler()
             .build(
@@ -578,6 +1095,12 @@ impl ComponentCommandHandler {
                 force_build.map(|force_build| BuildArgs {
                     step: vec![],
                     force_build,
+                    reproducible: false,
+                    keep_temp: false,
+                    version_check: None,
+                    strict: false,
+                    jobs: 1,
+                    install_tools: false,
                 }),
                 default_component_select_mode,
             )
@@ -596,12 +1119,49 @@ impl ComponentCommandHandler {
         };
         let build_profile = self.ctx.build_profile().cloned();
 
+        let lock_file = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            app_ctx.some_or_err()?.application.deploy_lock_file()
+        };
+        let mut lock = match DeployLock::load(&lock_file)? {
+            Some(lock) => lock,
+            None if locked => {
+                log_error(format!(
+                    "Lock file {} not found, deploy without {} first to create it",
+                    lock_file.log_color_highlight(),
+                    "--locked".log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+            None => DeployLock::default(),
+        };
+
         let components = {
             log_action("Deploying", "components");
             let _indent = LogIndent::new();
 
             let mut components = Vec::with_capacity(selected_component_names.len());
             for component_name in &selected_component_names {
+                if cancellation_token.is_cancelled() {
+                    log_warn_action(
+                        "Stopping",
+                        format!(
+                            "deployment, deployed components before cancellation: {}",
+                            if components.is_empty() {
+                                "-".to_string()
+                            } else {
+                                components
+                                    .iter()
+                                    .map(|component: &Component| {
+                                        component.component_name.0.log_color_highlight()
+                                    })
+                                    .join(", ")
+                            }
+                        ),
+                    );
+                    bail!(CancelledError);
+                }
+
                 let app_ctx = self.ctx.app_context_lock().await;
                 if app_ctx
                     .some_or_err()?
@@ -610,16 +1170,53 @@ impl ComponentCommandHandler {
                     .is_deployable()
                 {
                     drop(app_ctx);
-                    components.push(
-                        self.deploy_component(build_profile.as_ref(), project, component_name)
-                            .await?,
+                    let locked_component = if locked {
+                        match lock.component(&component_name.as_str().into()) {
+                            Some(locked_component) => Some(locked_component.clone()),
+                            None => {
+                                log_error(format!(
+                                    "Component {} is not recorded in {}",
+                                    component_name.as_str().log_color_highlight(),
+                                    lock_file.log_color_highlight()
+                                ));
+                                bail!(NonSuccessfulExit);
+                            }
+                        }
+                    } else {
+                        None
+                    };
+                    let (component, linked_wasm_hash) = self
+                        .deploy_component(
+                            build_profile.as_ref(),
+                            project,
+                            component_name,
+                            await_processing.await_processing,
+                            locked_component.as_ref(),
+                        )
+                        .await?;
+                    lock.insert(
+                        &component.component_name,
+                        LockedComponent {
+                            component_id: component.versioned_component_id.component_id,
+                            version: component.versioned_component_id.version,
+                            linked_wasm_hash,
+                        },
                     );
+                    components.push(component);
                 }
             }
 
             components
         };
 
+        if !locked {
+            lock.save(&lock_file)?;
+        }
+
+        if let Some(version_note) = version_note {
+            self.store_version_notes(&components, version_note)?;
+        }
+
         if let Some(update) = update_or_redeploy.update_workers {
             self.update_workers_by_components(components, update)
                 .await?;
@@ -630,14 +1227,51 @@ impl ComponentCommandHandler {
         Ok(())
     }
 
+    /// Attaches the note and metadata to the deployed component versions
+    fn store_version_notes(
+        &self,
+        components: &[Component],
+        version_note: &VersionNote,
+    ) -> anyhow::Result<()> {
+        let config_dir = self.ctx.config_dir();
+        let mut notes = VersionNotes::load(config_dir)?;
+        let now = Utc::now();
+        for component in components {
+            notes.insert(
+                component.versioned_component_id.component_id,
+                component.versioned_component_id.version,
+                version_note.clone(),
+                now,
+            );
+        }
+        notes.store(config_dir)
+    }
+
+    /// Attaches the locally stored version notes to the component views
+    fn with_version_notes(
+        &self,
+        component_views: Vec<ComponentView>,
+    ) -> anyhow::Result<Vec<ComponentView>> {
+        let notes = VersionNotes::load(self.ctx.config_dir())?;
+        Ok(component_views
+            .into_iter()
+            .map(|view| {
+                let note = notes.get(&view.component_id, view.component_version);
+                view.with_version_note(note)
+            })
+            .collect())
+    }
+
     async fn deploy_component(
         &mut self,
         build_profile: Option<&BuildProfileName>,
         project: Option<&ProjectNameAndId>,
         component_name: &AppComponentName,
-    ) -> anyhow::Result<Component> {
-        let component_id = self
-            .component_id_by_name(project, &component_name.as_str().into())
+        await_processing: bool,
+        locked: Option<&LockedComponent>,
+    ) -> anyhow::Result<(Component, String)> {
+        let existing_component = self
+            .component_by_name(project, &component_name.as_str().into(), None)
             .await?;
         let deploy_properties = {
             let mut app_ctx = self.ctx.app_context_lock_mut().await;
@@ -645,30 +1279,57 @@ impl ComponentCommandHandler {
             component_deploy_properties(app_ctx, component_name, build_profile)?
         };
 
-        let ifs_files = {
-            if !deploy_properties.files.is_empty() {
-                Some(
-                    IfsArchiveBuilder::new(self.ctx.file_download_client().await?)
-                        .build_files_archive(deploy_properties.files)
-                        .await?,
-                )
-            } else {
-                None
-            }
-        };
-        let ifs_properties = ifs_files.as_ref().map(|f| &f.properties);
-        let ifs_archive = {
-            if let Some(files) = ifs_files.as_ref() {
-                Some(File::open(&files.archive_path).await.with_context(|| {
+        let linked_wasm_hash = linked_wasm_hash(
+            &tokio::fs::read(&deploy_properties.linked_wasm_path)
+                .await
+                .with_context(|| {
                     anyhow!(
-                        "Failed to open IFS archive: {}",
-                        files.archive_path.display()
+                        "Failed to read component linked WASM at {}",
+                        deploy_properties.linked_wasm_path.display()
                     )
-                })?)
-            } else {
-                None
+                })?,
+        );
+
+        if let Some(locked) = locked {
+            let deployed = existing_component.as_ref().map(|component| {
+                (
+                    component.versioned_component_id.component_id,
+                    component.versioned_component_id.version,
+                )
+            });
+            match locked_deploy_action(locked, &linked_wasm_hash, deployed) {
+                Ok(LockedDeployAction::UpToDate) => {
+                    log_action(
+                        "Skipping",
+                        format!(
+                            "deploying component {}, locked version {} is already deployed",
+                            component_name.as_str().log_color_highlight(),
+                            locked.version.to_string().log_color_highlight()
+                        ),
+                    );
+                    if let Some(component) = existing_component {
+                        return Ok((component, linked_wasm_hash));
+                    }
+                }
+                Ok(LockedDeployAction::Deploy) => {}
+                Err(drift) => {
+                    log_error(format!(
+                        "Component {} drifted from the lock file: {}",
+                        component_name.as_str().log_color_highlight(),
+                        drift
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
             }
-        };
+        }
+
+        let ifs_files = self.build_ifs_archive(deploy_properties.files).await?;
+        let ifs_properties = ifs_files.as_ref().map(|f| &f.properties);
+
+        self.confirm_upload_size(&deploy_properties.linked_wasm_path, ifs_files.as_ref())
+            .await?;
+
+        let ifs_archive = open_ifs_archive(ifs_files.as_ref()).await?;
 
         let linked_wasm = File::open(&deploy_properties.linked_wasm_path)
             .await
@@ -682,9 +1343,14 @@ impl ComponentCommandHandler {
                         .log_color_error_highlight()
                 )
             })?;
+        let upload_progress = UploadProgress::default();
+        let linked_wasm = upload_progress.body(linked_wasm);
+
+        let component = match &existing_component {
+            Some(existing_component) => {
+                let component_id = &existing_component.versioned_component_id.component_id;
+                let transformers = self.transformer_plugins(existing_component).await?;
 
-        let component = match &component_id {
-            Some(component_id) => {
                 // TODO: use hashes for checking if component files has to be updated?
                 log_action(
                     "Updating",
@@ -694,40 +1360,53 @@ impl ComponentCommandHandler {
                     ),
                 );
                 let _indent = LogIndent::new();
-                let component = match self.ctx.golem_clients().await? {
-                    GolemClients::Oss(clients) => {
-                        let component = clients
-                            .component
-                            .update_component(
-                                &component_id.0,
-                                Some(&deploy_properties.component_type),
-                                linked_wasm,
-                                ifs_properties,
-                                ifs_archive,
-                                deploy_properties.dynamic_linking.as_ref(),
-                            )
-                            .await
-                            .map_service_error()?;
-                        Component::from(component)
-                    }
-                    GolemClients::Cloud(clients) => {
-                        let component = clients
-                            .component
-                            .update_component(
-                                &component_id.0,
-                                Some(&deploy_properties.component_type),
-                                linked_wasm,
-                                ifs_properties,
-                                ifs_archive,
-                                deploy_properties
-                                    .dynamic_linking
-                                    .map(|dl| dl.to_cloud())
-                                    .as_ref(),
-                            )
-                            .await
-                            .map_service_error()?;
-                        Component::from(component)
-                    }
+                let result = await_with_processing_status(
+                    async {
+                        match self.ctx.golem_clients().await? {
+                            GolemClients::Oss(clients) => clients
+                                .component
+                                .update_component(
+                                    component_id,
+                                    Some(&deploy_properties.component_type),
+                                    linked_wasm,
+                                    ifs_properties,
+                                    ifs_archive,
+                                    deploy_properties.dynamic_linking.as_ref(),
+                                )
+                                .await
+                                .map_service_error()
+                                .map(Component::from),
+                            GolemClients::Cloud(clients) => clients
+                                .component
+                                .update_component(
+                                    component_id,
+                                    Some(&deploy_properties.component_type),
+                                    linked_wasm,
+                                    ifs_properties,
+                                    ifs_archive,
+                                    deploy_properties
+                                        .dynamic_linking
+                                        .map(|dl| dl.to_cloud())
+                                        .as_ref(),
+                                )
+                                .await
+                                .map_service_error()
+                                .map(Component::from),
+                        }
+                    },
+                    &upload_progress,
+                    &transformers,
+                )
+                .await;
+                let component = match result {
+                    Ok(component) => component,
+                    Err(error) => match processing_failure_message(&error, &transformers) {
+                        Some(message) => {
+                            log_error(message);
+                            bail!(NonSuccessfulExit);
+                        }
+                        None => return Err(error),
+                    },
                 };
                 self.ctx
                     .log_handler()
@@ -743,51 +1422,410 @@ impl ComponentCommandHandler {
                     ),
                 );
                 let _indent = self.ctx.log_handler().nested_text_view_indent();
-                let component = match self.ctx.golem_clients().await? {
-                    GolemClients::Oss(clients) => {
-                        let component = clients
-                            .component
-                            .create_component(
-                                component_name.as_str(),
-                                Some(&deploy_properties.component_type),
-                                linked_wasm,
-                                ifs_properties,
-                                ifs_archive,
-                                deploy_properties.dynamic_linking.as_ref(),
-                            )
-                            .await
-                            .map_service_error()?;
-                        Component::from(component)
-                    }
-                    GolemClients::Cloud(clients) => {
-                        let component = clients
-                            .component
-                            .create_component(
-                                &ComponentQuery {
-                                    project_id: project.map(|p| p.project_id.0),
-                                    component_name: component_name.to_string(),
-                                },
-                                linked_wasm,
-                                Some(&deploy_properties.component_type),
-                                ifs_properties,
-                                ifs_archive,
-                                deploy_properties
-                                    .dynamic_linking
-                                    .map(|dl| dl.to_cloud())
-                                    .as_ref(),
-                            )
-                            .await
-                            .map_service_error()?;
-                        Component::from(component)
-                    }
-                };
+                let component = await_with_processing_status(
+                    async {
+                        match self.ctx.golem_clients().await? {
+                            GolemClients::Oss(clients) => clients
+                                .component
+                                .create_component(
+                                    component_name.as_str(),
+                                    Some(&deploy_properties.component_type),
+                                    linked_wasm,
+                                    ifs_properties,
+                                    ifs_archive,
+                                    deploy_properties.dynamic_linking.as_ref(),
+                                )
+                                .await
+                                .map_service_error()
+                                .map(Component::from),
+                            GolemClients::Cloud(clients) => clients
+                                .component
+                                .create_component(
+                                    &ComponentQuery {
+                                        project_id: project.map(|p| p.project_id.0),
+                                        component_name: component_name.to_string(),
+                                    },
+                                    linked_wasm,
+                                    Some(&deploy_properties.component_type),
+                                    ifs_properties,
+                                    ifs_archive,
+                                    deploy_properties
+                                        .dynamic_linking
+                                        .map(|dl| dl.to_cloud())
+                                        .as_ref(),
+                                )
+                                .await
+                                .map_service_error()
+                                .map(Component::from),
+                        }
+                    },
+                    &upload_progress,
+                    &[],
+                )
+                .await?;
                 self.ctx
                     .log_handler()
                     .log_view(&ComponentCreateView(ComponentView::from(component.clone())));
                 component
             }
         };
-        Ok(component)
+
+        if let Some(locked) = locked {
+            if component.versioned_component_id.version != locked.version {
+                log_error(format!(
+                    "Deployed component {} version {} differs from the locked version {}",
+                    component_name.as_str().log_color_highlight(),
+                    component.versioned_component_id.version,
+                    locked.version
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        if await_processing {
+            self.await_component_version(&component).await?;
+        }
+
+        Ok((component, linked_wasm_hash))
+    }
+
+    /// Component transformer plugins installed on the component, in their execution order
+    async fn transformer_plugins(
+        &self,
+        component: &Component,
+    ) -> anyhow::Result<Vec<TransformerPlugin>> {
+        let component_id = &component.versioned_component_id.component_id;
+        let version = component.versioned_component_id.version.to_string();
+        let installations = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .get_installed_plugins(component_id, &version)
+                .await
+                .map_service_error()?,
+            GolemClients::Cloud(clients) => clients
+                .component
+                .get_installed_plugins(component_id, &version)
+                .await
+                .map_service_error()?,
+        };
+
+        let mut transformers = Vec::new();
+        for installation in installations
+            .into_iter()
+            .sorted_by_key(|installation| installation.priority)
+        {
+            let plugin = self
+                .ctx
+                .plugin_handler()
+                .get(&installation.name, &installation.version)
+                .await?;
+            if plugin.component_transformer_transform_url.is_some() {
+                transformers.push(TransformerPlugin {
+                    name: installation.name,
+                    version: installation.version,
+                });
+            }
+        }
+
+        Ok(transformers)
+    }
+
+    /// Waits until the uploaded component version is available
+    async fn await_component_version(&self, component: &Component) -> anyhow::Result<()> {
+        let component_id = &component.versioned_component_id.component_id;
+        let version = component.versioned_component_id.version;
+        let started_at = Instant::now();
+        let mut queued_logged = false;
+
+        loop {
+            let available = match self.ctx.golem_clients().await? {
+                GolemClients::Oss(clients) => clients
+                    .component
+                    .get_component_metadata(component_id, &version.to_string())
+                    .await
+                    .map_service_error_not_found_as_opt()?
+                    .is_some(),
+                GolemClients::Cloud(clients) => clients
+                    .component
+                    .get_component_metadata(component_id, &version.to_string())
+                    .await
+                    .map_service_error_not_found_as_opt()?
+                    .is_some(),
+            };
+
+            if available {
+                log_action(
+                    "Processed",
+                    format!(
+                        "component {} version {}",
+                        component.component_name.0.log_color_highlight(),
+                        version.to_string().log_color_highlight()
+                    ),
+                );
+                return Ok(());
+            }
+
+            if started_at.elapsed() >= AWAIT_PROCESSING_TIMEOUT {
+                log_error(format!(
+                    "Component {} version {} did not become available in {}s",
+                    component.component_name.0.log_color_highlight(),
+                    version.to_string().log_color_highlight(),
+                    AWAIT_PROCESSING_TIMEOUT.as_secs()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+
+            if !queued_logged {
+                log_action(
+                    "Queued",
+                    format!(
+                        "component {} version {}, waiting for processing",
+                        component.component_name.0.log_color_highlight(),
+                        version.to_string().log_color_highlight()
+                    ),
+                );
+                queued_logged = true;
+            }
+
+            self.ctx.cancellation_token().check()?;
+            tokio::time::sleep(AWAIT_PROCESSING_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn cmd_add(
+        &self,
+        project: ProjectNameOptionalArg,
+        component_name: ComponentName,
+        component_wasm: Option<PathBuf>,
+        url: Option<Url>,
+        sha256: Option<String>,
+        ephemeral: bool,
+        files: Vec<InitialComponentFile>,
+        auto_adapt: bool,
+        output_field: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.ctx.log_handler().prepare_output_field(output_field);
+
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+            .await?;
+
+        if self
+            .component_id_by_name(project.as_ref(), &component_name)
+            .await?
+            .is_some()
+        {
+            log_error(format!(
+                "Component {} already exists",
+                component_name.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let component_type = if ephemeral {
+            ComponentType::Ephemeral
+        } else {
+            ComponentType::Durable
+        };
+
+        let ifs_files = self.build_ifs_archive(files).await?;
+        let ifs_properties = ifs_files.as_ref().map(|f| &f.properties);
+        let ifs_archive = open_ifs_archive(ifs_files.as_ref()).await?;
+
+        // Keeps the adapted component on disk until it is uploaded
+        let adapted_wasm: Option<NamedTempFile>;
+        let (component_wasm, checksum) = match (component_wasm, url) {
+            (_, Some(url)) => {
+                let remote_wasm = RemoteComponentWasm::open(
+                    &self.ctx.file_download_client().await?,
+                    &url,
+                    sha256,
+                )
+                .await?;
+                (remote_wasm.body, remote_wasm.checksum)
+            }
+            (Some(component_wasm), None) => {
+                adapted_wasm = self.ensure_component_binary(&component_wasm, auto_adapt)?;
+                let component_wasm = adapted_wasm
+                    .as_ref()
+                    .map(|file| file.path().to_path_buf())
+                    .unwrap_or(component_wasm);
+
+                self.confirm_upload_size(&component_wasm, ifs_files.as_ref())
+                    .await?;
+
+                let component_wasm_file = File::open(&component_wasm).await.with_context(|| {
+                    anyhow!(
+                        "Failed to open component WASM at {}",
+                        component_wasm
+                            .display()
+                            .to_string()
+                            .log_color_error_highlight()
+                    )
+                })?;
+                (reqwest::Body::from(component_wasm_file), None)
+            }
+            (None, None) => {
+                bail!("Either the component WASM path or --url is required")
+            }
+        };
+
+        log_action(
+            "Creating",
+            format!("component {}", component_name.0.log_color_highlight()),
+        );
+        let _indent = self.ctx.log_handler().nested_text_view_indent();
+        let result = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .create_component(
+                    &component_name.0,
+                    Some(&component_type),
+                    component_wasm,
+                    ifs_properties,
+                    ifs_archive,
+                    None,
+                )
+                .await
+                .map_service_error()
+                .map(Component::from),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .create_component(
+                    &ComponentQuery {
+                        project_id: project.as_ref().map(|p| p.project_id.0),
+                        component_name: component_name.0.clone(),
+                    },
+                    component_wasm,
+                    Some(&component_type),
+                    ifs_properties,
+                    ifs_archive,
+                    None,
+                )
+                .await
+                .map_service_error()
+                .map(Component::from),
+        };
+
+        // A checksum mismatch aborts the upload, report it instead of the resulting upload error
+        if let Some(checksum) = &checksum {
+            if let Some(actual) = checksum.mismatch() {
+                log_error(format!(
+                    "SHA-256 checksum mismatch for the component WASM, expected {}, got {}",
+                    checksum.expected().log_color_highlight(),
+                    actual.log_color_error_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        self.ctx.log_handler().log_view_field(
+            &ComponentCreateView(ComponentView::from(result?)),
+            output_field,
+        )
+    }
+
+    async fn confirm_upload_size(
+        &self,
+        component_wasm: &Path,
+        ifs_files: Option<&ComponentFilesArchive>,
+    ) -> anyhow::Result<()> {
+        let mut size = file_size(component_wasm).await?;
+        if let Some(ifs_files) = ifs_files {
+            size += file_size(&ifs_files.archive_path).await?;
+        }
+
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_payload_size(PayloadKind::ComponentUpload, size)?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the WASM file is a component, and adapts WASI preview1 modules into a
+    /// temporary component file if requested
+    fn ensure_component_binary(
+        &self,
+        component_wasm: &Path,
+        auto_adapt: bool,
+    ) -> anyhow::Result<Option<NamedTempFile>> {
+        let bytes = std::fs::read(component_wasm).with_context(|| {
+            anyhow!(
+                "Failed to read component WASM at {}",
+                component_wasm
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight()
+            )
+        })?;
+
+        let kind = WasmBinaryKind::detect(&bytes);
+        let Some(problem) = kind.problem() else {
+            return Ok(None);
+        };
+
+        if kind != WasmBinaryKind::Preview1Module || !auto_adapt {
+            log_error(format!(
+                "{} {}",
+                component_wasm
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight(),
+                problem
+            ));
+            match kind {
+                WasmBinaryKind::Preview1Module => {
+                    logln("");
+                    logln(format!(
+                        "Use {} to adapt it automatically, or adapt it with {}",
+                        "--auto-adapt".log_color_highlight(),
+                        "wasm-tools component new --adapt".log_color_highlight()
+                    ));
+                }
+                WasmBinaryKind::CoreModule => {
+                    logln("");
+                    logln(format!(
+                        "Build it as a component, or create one with {}",
+                        "wasm-tools component new".log_color_highlight()
+                    ));
+                }
+                WasmBinaryKind::Component | WasmBinaryKind::Invalid => {}
+            }
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Adapting",
+            format!(
+                "WASI preview1 module {} into a component",
+                component_wasm.display().to_string().log_color_highlight()
+            ),
+        );
+        let component = adapt_preview1_module(&bytes)?;
+        let mut file = NamedTempFile::new()?;
+        file.write_all(&component)?;
+        Ok(Some(file))
+    }
+
+    async fn build_ifs_archive(
+        &self,
+        files: Vec<InitialComponentFile>,
+    ) -> anyhow::Result<Option<ComponentFilesArchive>> {
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            IfsArchiveBuilder::new(self.ctx.file_download_client().await?)
+                .build_files_archive(files)
+                .await?,
+        ))
     }
 
     async fn components_for_update_or_redeploy(
@@ -839,6 +1877,10 @@ impl ComponentCommandHandler {
 
         let mut update_results = TryUpdateAllWorkersResult::default();
         for component in &components {
+            if self.ctx.cancellation_token().is_cancelled() {
+                break;
+            }
+
             let result = self
                 .ctx
                 .worker_handler()
@@ -853,7 +1895,7 @@ impl ComponentCommandHandler {
         }
 
         self.ctx.log_handler().log_view(&update_results);
-        Ok(())
+        self.ctx.cancellation_token().check()
     }
 
     pub async fn redeploy_workers_by_components(
@@ -1095,7 +2137,10 @@ impl ComponentCommandHandler {
                             vec![component_name.clone()],
                             None,
                             &ApplicationComponentSelectMode::CurrentDir,
+                            AwaitProcessingArg::default(),
                             WorkerUpdateOrRedeployArgs::default(),
+                            false,
+                            None,
                         )
                         .await?;
                     self.ctx
@@ -1113,6 +2158,39 @@ impl ComponentCommandHandler {
     }
 
     // TODO: server: we might want to have a filter for batch name lookups on the server side
+    /// Returns the latest version of every component, optionally limited to the given project
+    pub async fn latest_components(
+        &self,
+        project: Option<&ProjectNameAndId>,
+    ) -> anyhow::Result<Vec<Component>> {
+        let components = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .get_components(None)
+                .await
+                .map_service_error()?
+                .into_iter()
+                .map(Component::from)
+                .collect::<Vec<_>>(),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .get_components(project.map(|p| &p.project_id.0), None)
+                .await
+                .map_service_error()?
+                .into_iter()
+                .map(Component::from)
+                .collect::<Vec<_>>(),
+        };
+
+        Ok(components
+            .into_iter()
+            .into_grouping_map_by(|component| component.versioned_component_id.component_id)
+            .max_by_key(|_, component| component.versioned_component_id.version)
+            .into_values()
+            .sorted_by(|a, b| a.component_name.0.cmp(&b.component_name.0))
+            .collect())
+    }
+
     // TODO: server: also the search returns all versions
     // TODO: maybe add transient or persistent cache for all the meta
     pub async fn component_by_name(
@@ -1197,6 +2275,59 @@ impl ComponentCommandHandler {
         }
     }
 
+    /// Returns the specified version of the component, or the latest one if no version is given
+    pub async fn component_by_name_and_version(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        component_name: &ComponentName,
+        version: Option<u64>,
+    ) -> anyhow::Result<Option<Component>> {
+        let Some(component) = self
+            .component_by_name(project, component_name, None)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        match version {
+            Some(version) if version != component.versioned_component_id.version => {
+                let component_id = &component.versioned_component_id.component_id;
+                Ok(match self.ctx.golem_clients().await? {
+                    GolemClients::Oss(clients) => clients
+                        .component
+                        .get_component_metadata(component_id, &version.to_string())
+                        .await
+                        .map_service_error_not_found_as_opt()?
+                        .map(Component::from),
+                    GolemClients::Cloud(clients) => clients
+                        .component
+                        .get_component_metadata(component_id, &version.to_string())
+                        .await
+                        .map_service_error_not_found_as_opt()?
+                        .map(Component::from),
+                })
+            }
+            _ => Ok(Some(component)),
+        }
+    }
+
+    pub async fn component_by_id(&self, component_id: &Uuid) -> anyhow::Result<Option<Component>> {
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .component
+                .get_latest_component_metadata(component_id)
+                .await
+                .map_service_error_not_found_as_opt()
+                .map(|component| component.map(Component::from)),
+            GolemClients::Cloud(clients) => clients
+                .component
+                .get_latest_component_metadata(component_id)
+                .await
+                .map_service_error_not_found_as_opt()
+                .map(|component| component.map(Component::from)),
+        }
+    }
+
     pub async fn component_id_by_name(
         &self,
         project: Option<&ProjectNameAndId>,
@@ -1209,6 +2340,27 @@ impl ComponentCommandHandler {
     }
 }
 
+async fn file_size(path: &Path) -> anyhow::Result<u64> {
+    Ok(tokio::fs::metadata(path)
+        .await
+        .with_context(|| anyhow!("Failed to get the size of {}", path.display()))?
+        .len())
+}
+
+async fn open_ifs_archive(files: Option<&ComponentFilesArchive>) -> anyhow::Result<Option<File>> {
+    match files {
+        Some(files) => Ok(Some(File::open(&files.archive_path).await.with_context(
+            || {
+                anyhow!(
+                    "Failed to open IFS archive: {}",
+                    files.archive_path.display()
+                )
+            },
+        )?)),
+        None => Ok(None),
+    }
+}
+
 struct ComponentDeployProperties {
     component_type: ComponentType,
     linked_wasm_path: PathBuf,
@@ -1242,6 +2394,54 @@ fn component_deploy_properties(
     })
 }
 
+async fn component_declared_properties(
+    app_ctx: &mut ApplicationContext,
+    component_name: &AppComponentName,
+    build_profile: Option<&BuildProfileName>,
+) -> anyhow::Result<DeclaredComponentProperties> {
+    let deploy_properties = component_deploy_properties(app_ctx, component_name, build_profile)?;
+
+    let linked_wasm_size = tokio::fs::metadata(&deploy_properties.linked_wasm_path)
+        .await
+        .ok()
+        .map(|metadata| metadata.len());
+
+    let files = deploy_properties
+        .files
+        .iter()
+        .map(|file| (file.target.path.to_string(), file.target.permissions))
+        .collect();
+
+    let dynamic_linking = deploy_properties
+        .dynamic_linking
+        .map(|dynamic_linking| {
+            dynamic_linking
+                .dynamic_linking
+                .into_iter()
+                .map(|(name, link)| {
+                    (
+                        name,
+                        match link {
+                            DynamicLinkedInstanceOss::WasmRpc(links) => links
+                                .targets
+                                .into_iter()
+                                .map(|(resource, target)| (resource, target.interface_name))
+                                .collect::<BTreeMap<_, _>>(),
+                        },
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(DeclaredComponentProperties {
+        component_type: deploy_properties.component_type.into(),
+        linked_wasm_size,
+        files,
+        dynamic_linking,
+    })
+}
+
 fn app_component_dynamic_linking(
     app_ctx: &mut ApplicationContext,
     component_name: &AppComponentName,
@@ -17,9 +17,10 @@ use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{log_action, log_error_action, log_warn_action, LogIndent};
-use crate::model::text::fmt::log_warn;
-use crate::model::ComponentName;
+use crate::log::{log_action, log_error_action, log_warn_action, LogColorize, LogIndent};
+use crate::model::component::Component;
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::{ComponentName, ProjectNameAndId};
 use anyhow::bail;
 use golem_client::api::ComponentClient as ComponentClientOss;
 use golem_client::model::{
@@ -49,6 +50,7 @@ impl ComponentPluginCommandHandler {
                 plugin_version,
                 priority,
                 param,
+                component_version,
             } => {
                 self.cmd_install(
                     component_name.component_name,
@@ -56,33 +58,44 @@ impl ComponentPluginCommandHandler {
                     plugin_version,
                     priority,
                     param,
+                    component_version,
                 )
                 .await
             }
             ComponentPluginSubcommand::Get {
                 component_name,
-                version,
-            } => self.cmd_get(component_name.component_name, version).await,
+                component_version,
+            } => {
+                self.cmd_get(component_name.component_name, component_version)
+                    .await
+            }
             ComponentPluginSubcommand::Update {
                 component_name,
                 installation_id,
                 priority,
                 param,
+                component_version,
             } => {
                 self.cmd_update(
                     component_name.component_name,
                     installation_id,
                     priority,
                     param,
+                    component_version,
                 )
                 .await
             }
             ComponentPluginSubcommand::Uninstall {
                 component_name,
                 installation_id,
+                component_version,
             } => {
-                self.cmd_uninstall(component_name.component_name, installation_id)
-                    .await
+                self.cmd_uninstall(
+                    component_name.component_name,
+                    installation_id,
+                    component_version,
+                )
+                .await
             }
         }
     }
@@ -94,6 +107,7 @@ impl ComponentPluginCommandHandler {
         plugin_version: String,
         priority: i32,
         parameters: Vec<(String, String)>,
+        component_version: Option<u64>,
     ) -> anyhow::Result<()> {
         let selected_components = self
             .ctx
@@ -111,12 +125,14 @@ impl ComponentPluginCommandHandler {
             let _indent = LogIndent::new();
 
             let component = self
-                .ctx
-                .component_handler()
-                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .component_for_change(
+                    selected_components.project.as_ref(),
+                    component_name,
+                    component_version,
+                )
                 .await?;
 
-            let result = match component {
+            let result = match &component {
                 Some(component) => match self.ctx.golem_clients().await? {
                     GolemClients::Oss(clients) => Some(
                         clients
@@ -155,8 +171,13 @@ impl ComponentPluginCommandHandler {
                     None
                 }
             };
-            if let Some(result) = result {
-                log_action("Installed", "plugin");
+            if let (Some(component), Some(result)) = (&component, result) {
+                self.log_plugin_change(
+                    selected_components.project.as_ref(),
+                    component,
+                    "Installed",
+                )
+                .await?;
                 installations.push(result);
             }
         }
@@ -173,7 +194,7 @@ impl ComponentPluginCommandHandler {
     async fn cmd_get(
         &self,
         component_name: Option<ComponentName>,
-        version: Option<u64>,
+        component_version: Option<u64>,
     ) -> anyhow::Result<()> {
         let selected_components = self
             .ctx
@@ -195,7 +216,7 @@ impl ComponentPluginCommandHandler {
                         .component
                         .get_installed_plugins(
                             &component.versioned_component_id.component_id,
-                            &version
+                            &component_version
                                 .unwrap_or(component.versioned_component_id.version)
                                 .to_string(),
                         )
@@ -205,7 +226,7 @@ impl ComponentPluginCommandHandler {
                         .component
                         .get_installed_plugins(
                             &component.versioned_component_id.component_id,
-                            &version
+                            &component_version
                                 .unwrap_or(component.versioned_component_id.version)
                                 .to_string(),
                         )
@@ -231,6 +252,7 @@ impl ComponentPluginCommandHandler {
         plugin_installation_id: PluginInstallationId,
         priority: i32,
         parameters: Vec<(String, String)>,
+        component_version: Option<u64>,
     ) -> anyhow::Result<()> {
         let selected_components = self
             .ctx
@@ -250,9 +272,11 @@ impl ComponentPluginCommandHandler {
             let _indent = LogIndent::new();
 
             let component = self
-                .ctx
-                .component_handler()
-                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .component_for_change(
+                    selected_components.project.as_ref(),
+                    component_name,
+                    component_version,
+                )
                 .await?;
 
             match component {
@@ -285,7 +309,12 @@ impl ComponentPluginCommandHandler {
                             .map(|_| ())
                             .map_service_error()?,
                     }
-                    log_action("Updated", "plugin");
+                    self.log_plugin_change(
+                        selected_components.project.as_ref(),
+                        &component,
+                        "Updated",
+                    )
+                    .await?;
                 }
                 None => {
                     log_warn(format!("Component {} not found", component_name));
@@ -305,6 +334,7 @@ impl ComponentPluginCommandHandler {
         &self,
         component_name: Option<ComponentName>,
         plugin_installation_id: PluginInstallationId,
+        component_version: Option<u64>,
     ) -> anyhow::Result<()> {
         let selected_components = self
             .ctx
@@ -324,12 +354,14 @@ impl ComponentPluginCommandHandler {
             let _ident = LogIndent::new();
 
             let component = self
-                .ctx
-                .component_handler()
-                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .component_for_change(
+                    selected_components.project.as_ref(),
+                    component_name,
+                    component_version,
+                )
                 .await?;
 
-            let result = match component {
+            let result = match &component {
                 Some(component) => match self.ctx.golem_clients().await? {
                     GolemClients::Oss(clients) => clients
                         .component
@@ -357,11 +389,17 @@ impl ComponentPluginCommandHandler {
                 }
             };
 
-            match result {
-                Ok(()) => {
-                    log_action("Uninstalled", "plugin");
+            match (result, &component) {
+                (Ok(()), Some(component)) => {
+                    self.log_plugin_change(
+                        selected_components.project.as_ref(),
+                        component,
+                        "Uninstalled",
+                    )
+                    .await?;
                 }
-                Err(error) => {
+                (Ok(()), None) => {}
+                (Err(error), _) => {
                     log_error_action("Uninstall", format!("failed: {}", error));
                     any_error = true;
                 }
@@ -374,4 +412,70 @@ impl ComponentPluginCommandHandler {
 
         Ok(())
     }
+
+    /// Plugin changes are applied on the latest component version by the server, creating a new
+    /// version, so an explicitly requested version must be the latest one
+    async fn component_for_change(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        component_name: &ComponentName,
+        component_version: Option<u64>,
+    ) -> anyhow::Result<Option<Component>> {
+        let component = self
+            .ctx
+            .component_handler()
+            .component_by_name(project, component_name, None)
+            .await?;
+
+        if let (Some(component), Some(component_version)) = (&component, component_version) {
+            let latest_version = component.versioned_component_id.version;
+            if latest_version != component_version {
+                log_error(format!(
+                    "Requested version {} of component {} is not the latest version, plugin changes create a new version based on the latest version {}",
+                    component_version.to_string().log_color_error_highlight(),
+                    component_name.0.log_color_highlight(),
+                    latest_version.to_string().log_color_highlight(),
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        Ok(component)
+    }
+
+    async fn log_plugin_change(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        component: &Component,
+        action: &str,
+    ) -> anyhow::Result<()> {
+        let base_version = component.versioned_component_id.version;
+        let new_version = self
+            .ctx
+            .component_handler()
+            .component_by_name(project, &component.component_name, None)
+            .await?
+            .map(|component| component.versioned_component_id.version)
+            .filter(|version| *version != base_version);
+
+        match new_version {
+            Some(new_version) => log_action(
+                action,
+                format!(
+                    "plugin on component version {}, created new component version {}",
+                    base_version.to_string().log_color_highlight(),
+                    new_version.to_string().log_color_highlight()
+                ),
+            ),
+            None => log_action(
+                action,
+                format!(
+                    "plugin on component version {}",
+                    base_version.to_string().log_color_highlight()
+                ),
+            ),
+        }
+
+        Ok(())
+    }
 }
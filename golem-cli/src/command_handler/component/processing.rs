@@ -0,0 +1,241 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{log_action, LogColorize};
+use bytes::Bytes;
+use futures_util::stream;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+use tokio::time;
+
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+const STATUS_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+const STATUS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Component transformer plugin installed on a component, these are executed by the server
+/// when a new component version is uploaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformerPlugin {
+    pub name: String,
+    pub version: String,
+}
+
+impl Display for TransformerPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// Tracks whether the component WASM was fully sent, after that the upload request is only
+/// waiting for the server side processing
+#[derive(Clone, Default)]
+pub struct UploadProgress {
+    uploaded: Arc<AtomicBool>,
+}
+
+impl UploadProgress {
+    pub fn body(&self, file: File) -> reqwest::Body {
+        let uploaded = self.uploaded.clone();
+        let chunks = stream::unfold(Some(file), move |file| {
+            let uploaded = uploaded.clone();
+            async move {
+                let mut file = file?;
+                let mut buffer = vec![0u8; UPLOAD_CHUNK_SIZE];
+                match file.read(&mut buffer).await {
+                    Ok(0) => {
+                        uploaded.store(true, Ordering::SeqCst);
+                        None
+                    }
+                    Ok(size) => {
+                        buffer.truncate(size);
+                        Some((Ok(Bytes::from(buffer)), Some(file)))
+                    }
+                    Err(err) => Some((Err::<Bytes, std::io::Error>(err), None)),
+                }
+            }
+        });
+        reqwest::Body::wrap_stream(chunks)
+    }
+
+    fn is_uploaded(&self) -> bool {
+        self.uploaded.load(Ordering::SeqCst)
+    }
+}
+
+/// Awaits a component create or update request, while rendering the server side processing
+/// status: the transformation by the installed transformer plugins, or the processing
+/// if it takes longer than usual
+pub async fn await_with_processing_status<F: Future>(
+    request: F,
+    progress: &UploadProgress,
+    transformers: &[TransformerPlugin],
+) -> F::Output {
+    let started_at = Instant::now();
+    let mut processing_since = None::<Instant>;
+    let mut last_report = None::<Instant>;
+    let mut interval = time::interval(STATUS_CHECK_INTERVAL);
+
+    tokio::pin!(request);
+
+    loop {
+        tokio::select! {
+            result = &mut request => return result,
+            _ = interval.tick() => {
+                let now = Instant::now();
+                match processing_since {
+                    None => {
+                        if progress.is_uploaded() {
+                            processing_since = Some(now);
+                            if !transformers.is_empty() {
+                                log_transforming(transformers);
+                                last_report = Some(now);
+                            }
+                        }
+                    }
+                    Some(processing_since) => {
+                        let report_due = last_report
+                            .map(|last_report| now - last_report >= STATUS_REPORT_INTERVAL)
+                            .unwrap_or_else(|| now - processing_since >= STATUS_REPORT_INTERVAL);
+                        if report_due {
+                            log_still_processing(transformers, now - started_at);
+                            last_report = Some(now);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn log_transforming(transformers: &[TransformerPlugin]) {
+    log_action(
+        "Transforming",
+        format!(
+            "component with {} {}",
+            if transformers.len() == 1 {
+                "plugin"
+            } else {
+                "plugins"
+            },
+            format_transformers(transformers)
+        ),
+    );
+}
+
+fn log_still_processing(transformers: &[TransformerPlugin], elapsed: Duration) {
+    log_action(
+        if transformers.is_empty() {
+            "Processing"
+        } else {
+            "Transforming"
+        },
+        format!(
+            "component, waiting for the server for {}s",
+            elapsed.as_secs().to_string().log_color_highlight()
+        ),
+    );
+}
+
+fn format_transformers(transformers: &[TransformerPlugin]) -> String {
+    transformers
+        .iter()
+        .map(|transformer| transformer.to_string().log_color_highlight())
+        .join(", ")
+}
+
+/// Describes a failed create or update request, pointing out the transformer plugin
+/// if the error is related to one of them
+pub fn processing_failure_message(
+    error: &anyhow::Error,
+    transformers: &[TransformerPlugin],
+) -> Option<String> {
+    if transformers.is_empty() {
+        return None;
+    }
+
+    let error = format!("{:#}", error);
+    let failed_transformer = transformers
+        .iter()
+        .find(|transformer| error.contains(&transformer.name));
+
+    Some(match failed_transformer {
+        Some(transformer) => format!(
+            "Component processing failed in transformer plugin {}:\n{}",
+            transformer.to_string().log_color_error_highlight(),
+            error
+        ),
+        None => format!(
+            "Component processing failed, installed transformer plugins: {}\n{}",
+            format_transformers(transformers),
+            error
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::component::processing::{
+        processing_failure_message, TransformerPlugin,
+    };
+    use anyhow::anyhow;
+    use assert2::{check, let_assert};
+    use test_r::test;
+
+    fn transformers() -> Vec<TransformerPlugin> {
+        vec![
+            TransformerPlugin {
+                name: "otel".to_string(),
+                version: "1.0.0".to_string(),
+            },
+            TransformerPlugin {
+                name: "wasi-virt".to_string(),
+                version: "0.2.0".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn failure_message_without_transformers() {
+        check!(processing_failure_message(&anyhow!("error"), &[]) == None);
+    }
+
+    #[test]
+    fn failure_message_with_failed_transformer() {
+        let_assert!(
+            Some(message) = processing_failure_message(
+                &anyhow!("Component transformation failed: wasi-virt: invalid import"),
+                &transformers()
+            )
+        );
+        check!(message.contains("failed in transformer plugin"));
+        check!(message.contains("wasi-virt@0.2.0"));
+        check!(message.contains("invalid import"));
+    }
+
+    #[test]
+    fn failure_message_with_unknown_transformer() {
+        let_assert!(
+            Some(message) = processing_failure_message(&anyhow!("Internal error"), &transformers())
+        );
+        check!(message.contains("otel@1.0.0"));
+        check!(message.contains("wasi-virt@0.2.0"));
+        check!(message.contains("Internal error"));
+    }
+}
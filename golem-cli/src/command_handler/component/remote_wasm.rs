@@ -0,0 +1,124 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{log_action, LogColorize};
+use anyhow::{anyhow, Context};
+use futures_util::{future, stream, StreamExt};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Component WASM streamed from a remote URL, without storing it on the local disk
+pub struct RemoteComponentWasm {
+    pub body: reqwest::Body,
+    pub checksum: Option<StreamedChecksum>,
+}
+
+impl RemoteComponentWasm {
+    pub async fn open(
+        client: &reqwest::Client,
+        url: &Url,
+        expected_sha256: Option<String>,
+    ) -> anyhow::Result<Self> {
+        log_action(
+            "Streaming",
+            format!("component WASM from {}", url.as_str().log_color_highlight()),
+        );
+
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| anyhow!("Failed to download component WASM: {}", url))?;
+
+        let Some(expected_sha256) = expected_sha256 else {
+            return Ok(Self {
+                body: reqwest::Body::wrap_stream(response.bytes_stream()),
+                checksum: None,
+            });
+        };
+
+        let checksum = StreamedChecksum::new(expected_sha256);
+
+        let chunks = response.bytes_stream().map({
+            let checksum = checksum.clone();
+            move |chunk| {
+                if let Ok(chunk) = &chunk {
+                    checksum.update(chunk);
+                }
+                chunk.map_err(anyhow::Error::from)
+            }
+        });
+
+        // Failing the stream at its end aborts the upload, so a component with unexpected
+        // content is never created
+        let verification = stream::once({
+            let checksum = checksum.clone();
+            async move { checksum.finish() }
+        })
+        .filter_map(|error| future::ready(error.map(Err)));
+
+        Ok(Self {
+            body: reqwest::Body::wrap_stream(chunks.chain(verification)),
+            checksum: Some(checksum),
+        })
+    }
+}
+
+/// SHA-256 checksum calculated while a component WASM is streamed
+#[derive(Clone)]
+pub struct StreamedChecksum {
+    expected: String,
+    hasher: Arc<Mutex<Sha256>>,
+    mismatch: Arc<Mutex<Option<String>>>,
+}
+
+impl StreamedChecksum {
+    fn new(expected: String) -> Self {
+        Self {
+            expected: expected.to_lowercase(),
+            hasher: Arc::new(Mutex::new(Sha256::new())),
+            mismatch: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn update(&self, chunk: &[u8]) {
+        self.hasher.lock().unwrap().update(chunk);
+    }
+
+    fn finish(&self) -> Option<anyhow::Error> {
+        let actual = hex::encode(self.hasher.lock().unwrap().clone().finalize());
+        if actual == self.expected {
+            None
+        } else {
+            *self.mismatch.lock().unwrap() = Some(actual.clone());
+            Some(anyhow!(
+                "SHA-256 checksum mismatch for component WASM, expected {}, got {}",
+                self.expected,
+                actual
+            ))
+        }
+    }
+
+    /// Returns the actual checksum if the whole content was streamed, and it did not match
+    /// the expected one
+    pub fn mismatch(&self) -> Option<String> {
+        self.mismatch.lock().unwrap().clone()
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Context};
+use golem_templates::model::GuestLanguageTier;
+use wasmparser::{Parser, Payload};
+use wit_component::ComponentEncoder;
+
+const WASI_PREVIEW1_MODULE: &str = "wasi_snapshot_preview1";
+
+/// Kind of WASM binary, as detected before uploading it as a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmBinaryKind {
+    Component,
+    /// Core module importing WASI preview1, can be adapted into a component
+    Preview1Module,
+    /// Core module without WASI preview1 imports
+    CoreModule,
+    Invalid,
+}
+
+impl WasmBinaryKind {
+    pub fn detect(bytes: &[u8]) -> Self {
+        if Parser::is_component(bytes) {
+            Self::Component
+        } else if Parser::is_core_wasm(bytes) {
+            match imports_wasi_preview1(bytes) {
+                Ok(true) => Self::Preview1Module,
+                Ok(false) => Self::CoreModule,
+                Err(_) => Self::Invalid,
+            }
+        } else {
+            Self::Invalid
+        }
+    }
+
+    /// Explains why the binary cannot be uploaded as is, None for components
+    pub fn problem(&self) -> Option<&'static str> {
+        match self {
+            Self::Component => None,
+            Self::Preview1Module => Some(
+                "is a core WASM module targeting WASI preview1, not a component, \
+                 it has to be adapted to the component model before uploading",
+            ),
+            Self::CoreModule => Some(
+                "is a core WASM module without component model types, \
+                 it has to be turned into a component before uploading",
+            ),
+            Self::Invalid => Some("is not a valid WASM binary"),
+        }
+    }
+}
+
+fn imports_wasi_preview1(bytes: &[u8]) -> anyhow::Result<bool> {
+    for payload in Parser::new(0).parse_all(bytes) {
+        if let Payload::ImportSection(reader) = payload? {
+            for import in reader {
+                if import?.module == WASI_PREVIEW1_MODULE {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Turns a WASI preview1 core module into a component, using the preview1 adapter
+/// embedded in the CLI
+pub fn adapt_preview1_module(module: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let adapter = golem_templates::wasi_preview1_adapter(&GuestLanguageTier::Tier1)
+        .ok_or_else(|| anyhow!("WASI preview1 adapter is not available"))?;
+
+    ComponentEncoder::default()
+        .validate(true)
+        .module(module)
+        .context("Failed to read the core WASM module")?
+        .adapter(WASI_PREVIEW1_MODULE, adapter)
+        .context("Failed to apply the WASI preview1 adapter")?
+        .encode()
+        .context("Failed to encode the adapted component")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::component::wasm_binary::WasmBinaryKind;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn detect_wasm_binary_kind() {
+        check!(WasmBinaryKind::detect(b"\0asm\x01\0\0\0") == WasmBinaryKind::CoreModule);
+        check!(WasmBinaryKind::detect(b"\0asm\x0d\0\x01\0") == WasmBinaryKind::Component);
+        check!(WasmBinaryKind::detect(b"not a wasm binary") == WasmBinaryKind::Invalid);
+        check!(WasmBinaryKind::Component.problem().is_none());
+        check!(WasmBinaryKind::Preview1Module.problem().is_some());
+    }
+}
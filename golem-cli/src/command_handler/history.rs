@@ -0,0 +1,108 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::history::HistorySubcommand;
+use crate::command_handler::Handlers;
+use crate::config::Config;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::history;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::text::fmt::log_error;
+use crate::model::text::history::{HistoryEntryView, HistoryListView};
+use anyhow::bail;
+use std::sync::Arc;
+
+pub struct HistoryCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl HistoryCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub fn handle_command(&self, subcommand: HistorySubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            HistorySubcommand::Enable => self.cmd_enable(),
+            HistorySubcommand::Disable => self.cmd_disable(),
+            HistorySubcommand::List { limit } => self.cmd_list(limit),
+            HistorySubcommand::Show { index } => self.cmd_show(index),
+        }
+    }
+
+    fn cmd_enable(&self) -> anyhow::Result<()> {
+        Config::set_history_enabled(true, self.ctx.config_dir())?;
+
+        log_action(
+            "Enabled",
+            format!(
+                "command history, mutating commands are recorded into {}",
+                history::history_path(self.ctx.config_dir()).log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn cmd_disable(&self) -> anyhow::Result<()> {
+        Config::set_history_enabled(false, self.ctx.config_dir())?;
+
+        log_warn_action("Disabled", "command history");
+
+        Ok(())
+    }
+
+    fn cmd_list(&self, limit: Option<usize>) -> anyhow::Result<()> {
+        let entries = self.entries()?;
+        let skip = limit.map_or(0, |limit| entries.len().saturating_sub(limit));
+
+        self.ctx
+            .log_handler()
+            .log_view(&HistoryListView(entries.into_iter().skip(skip).collect()));
+
+        Ok(())
+    }
+
+    fn cmd_show(&self, index: usize) -> anyhow::Result<()> {
+        match self
+            .entries()?
+            .into_iter()
+            .find(|entry| entry.index == index)
+        {
+            Some(entry) => {
+                self.ctx.log_handler().log_view(&entry);
+                Ok(())
+            }
+            None => {
+                log_error(format!(
+                    "History entry {} not found",
+                    index.to_string().log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn entries(&self) -> anyhow::Result<Vec<HistoryEntryView>> {
+        Ok(history::read_all(self.ctx.config_dir())?
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| HistoryEntryView {
+                index: index + 1,
+                entry,
+            })
+            .collect())
+    }
+}
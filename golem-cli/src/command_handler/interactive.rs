@@ -12,21 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::cloud::AccountId;
 use crate::config::{
     CloudProfile, OssProfile, Profile, ProfileConfig, ProfileKind, ProfileName, CLOUD_URL,
     DEFAULT_OSS_URL,
 };
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
-use crate::log::{log_warn_action, LogColorize};
-use crate::model::text::fmt::log_warn;
-use crate::model::{ComponentName, Format};
+use crate::log::{log_warn_action, output_sink, LogColorize};
+use crate::model::component::{
+    exported_functions, format_function_name, render_exported_function, render_type,
+};
+use crate::model::payload::PayloadKind;
+use crate::model::text::fmt::{format_binary_size, log_warn};
+use crate::model::wave::{type_wave_compatible, wave_char, wave_string};
+use crate::model::{ComponentName, Format, Role};
 use anyhow::{anyhow, bail};
 use colored::Colorize;
 use golem_cloud_client::model::Account;
+use golem_wasm_ast::analysis::{AnalysedExport, AnalysedFunctionParameter, AnalysedType};
+use golem_wasm_rpc::parse_type_annotated_value;
 use inquire::validator::{ErrorMessage, Validation};
-use inquire::{Confirm, CustomType, InquireError, Select, Text};
+use inquire::{Confirm, CustomType, InquireError, MultiSelect, Select, Text};
 use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
 use std::str::FromStr;
 use std::sync::Arc;
 use strum::IntoEnumIterator;
@@ -65,6 +74,33 @@ impl InteractiveHandler {
         )
     }
 
+    pub fn confirm_delete_workers(&self, number_of_workers: usize) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "Are you sure you want to {} {} worker(s)?",
+                "delete".log_color_warn(),
+                number_of_workers.to_string().log_color_highlight()
+            ),
+        )
+    }
+
+    pub fn confirm_restart_worker_with_env(
+        &self,
+        worker_name: &str,
+        component_version: u64,
+    ) -> anyhow::Result<bool> {
+        self.confirm(
+            true,
+            format!(
+                "Setting the environment will {} then recreate worker {} at component version {}, losing its state, do you want to continue?",
+                "delete".log_color_warn(),
+                worker_name.log_color_highlight(),
+                component_version.to_string().log_color_highlight()
+            ),
+        )
+    }
+
     pub fn confirm_delete_account(&self, account: &Account) -> anyhow::Result<bool> {
         self.confirm(
             false,
@@ -76,6 +112,51 @@ impl InteractiveHandler {
         )
     }
 
+    /// Checks the payload size against the limit configured for the profile, payloads exceeding
+    /// it are only sent after confirmation
+    pub fn confirm_payload_size(&self, kind: PayloadKind, size: u64) -> anyhow::Result<bool> {
+        let Some(max_size) = kind.max_size(self.ctx.profile_config()) else {
+            return Ok(true);
+        };
+        if size <= max_size {
+            return Ok(true);
+        }
+
+        log_warn(format!(
+            "The {} size is {}, which exceeds the configured maximum of {} for profile {}. \
+            The limit can be changed with 'profile config {} set {} <SIZE>'.",
+            kind,
+            format_binary_size(&size).log_color_highlight(),
+            format_binary_size(&max_size).log_color_highlight(),
+            self.ctx.profile_name().0.log_color_highlight(),
+            self.ctx.profile_name().0,
+            kind.config_key()
+        ));
+
+        self.confirm(false, format!("Do you want to send the {} anyway?", kind))
+    }
+
+    pub fn confirm_update_project_policy(&self, policy_name: &str) -> anyhow::Result<bool> {
+        self.confirm(
+            true,
+            format!(
+                "Do you want to create the updated project policy {} with the above actions?",
+                policy_name.log_color_highlight()
+            ),
+        )
+    }
+
+    pub fn confirm_grant_role(&self, account_id: &AccountId, role: Role) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "Are you sure you want to grant role {} to account {}?",
+                role.to_string().log_color_highlight(),
+                account_id.0.log_color_highlight()
+            ),
+        )
+    }
+
     pub fn create_profile(&self) -> anyhow::Result<(ProfileName, Profile, bool)> {
         if !self.confirm(
             true,
@@ -135,14 +216,20 @@ impl InteractiveHandler {
                 url: component_service_url,
                 worker_url: worker_service_url,
                 allow_insecure: false,
-                config: ProfileConfig { default_format },
+                config: ProfileConfig {
+                    default_format,
+                    ..ProfileConfig::default()
+                },
             }),
             ProfileKind::Cloud => Profile::GolemCloud(CloudProfile {
                 custom_url: Some(component_service_url),
                 custom_cloud_url: cloud_service_url,
                 custom_worker_url: worker_service_url,
                 allow_insecure: false,
-                config: ProfileConfig { default_format },
+                config: ProfileConfig {
+                    default_format,
+                    ..ProfileConfig::default()
+                },
                 auth: None,
             }),
         };
@@ -154,6 +241,57 @@ impl InteractiveHandler {
         Ok((profile_name.into(), profile, set_as_active))
     }
 
+    /// Selects an exported function of the component with a fuzzy filtered selector. Functions
+    /// with resource handle parameters are not offered, as handles cannot be entered.
+    pub fn select_function(&self, exports: &[AnalysedExport]) -> anyhow::Result<String> {
+        self.ensure_prompt_available("selecting the function")?;
+
+        let options = exported_functions(exports)
+            .into_iter()
+            .filter(|(_, function)| {
+                function
+                    .parameters
+                    .iter()
+                    .all(|param| type_wave_compatible(&param.typ))
+            })
+            .map(|(prefix, function)| FunctionOption {
+                name: format_function_name(prefix, &function.name),
+                signature: render_exported_function(prefix, function),
+            })
+            .collect::<Vec<_>>();
+
+        if options.is_empty() {
+            bail!("The component has no exported functions which can be invoked interactively");
+        }
+
+        Ok(Select::new("Function:", options)
+            .with_help_message("Type to filter, ↑↓ to move, enter to select")
+            .prompt()?
+            .name)
+    }
+
+    /// Prompts for the value of each parameter field by field based on its type, returns the
+    /// arguments in WAVE format
+    pub fn prompt_function_arguments(
+        &self,
+        parameters: &[&AnalysedFunctionParameter],
+    ) -> anyhow::Result<Vec<String>> {
+        self.ensure_prompt_available("entering the function arguments")?;
+
+        parameters
+            .iter()
+            .map(|param| prompt_wave_value(&param.name, &param.typ))
+            .collect()
+    }
+
+    fn ensure_prompt_available(&self, purpose: &str) -> anyhow::Result<()> {
+        // When serving requests stdin is used for the protocol messages
+        if output_sink().is_some() || !std::io::stdin().is_terminal() {
+            bail!("Interactive input is required for {purpose}, but the current input device is not a teletype");
+        }
+        Ok(())
+    }
+
     fn confirm<M: AsRef<str>>(&self, default: bool, message: M) -> anyhow::Result<bool> {
         const YES_FLAG_HINT: &str = "To automatically confirm such questions use the '--yes' flag.";
 
@@ -165,6 +303,14 @@ impl InteractiveHandler {
             return Ok(true);
         }
 
+        // When serving requests stdin is used for the protocol messages
+        if output_sink().is_some() {
+            bail!(
+                "Confirmation required for question: \"{}\", use the '--yes' flag to automatically confirm",
+                message.as_ref()
+            );
+        }
+
         match Confirm::new(message.as_ref())
             .with_help_message(YES_FLAG_HINT)
             .with_default(default)
@@ -182,6 +328,142 @@ impl InteractiveHandler {
     }
 }
 
+fn prompt_wave_value(path: &str, typ: &AnalysedType) -> anyhow::Result<String> {
+    let label = format!("{} ({}):", path, render_type(typ));
+
+    let value = match typ {
+        AnalysedType::Str(_) => wave_string(&Text::new(&label).prompt()?),
+        AnalysedType::Chr(_) => wave_char(CustomType::<char>::new(&label).prompt()?),
+        AnalysedType::Bool(_) => Confirm::new(&label)
+            .with_default(false)
+            .prompt()?
+            .to_string(),
+        AnalysedType::Enum(typ) => Select::new(&label, typ.cases.clone()).prompt()?,
+        AnalysedType::Flags(typ) => format!(
+            "{{{}}}",
+            MultiSelect::new(&label, typ.names.clone())
+                .prompt()?
+                .join(", ")
+        ),
+        AnalysedType::Option(typ) => {
+            if Confirm::new(&format!("Set optional {}?", path))
+                .with_default(false)
+                .prompt()?
+            {
+                format!("some({})", prompt_wave_value(path, &typ.inner)?)
+            } else {
+                "none".to_string()
+            }
+        }
+        AnalysedType::Record(typ) => format!(
+            "{{{}}}",
+            typ.fields
+                .iter()
+                .map(|field| {
+                    prompt_wave_value(&format!("{}.{}", path, field.name), &field.typ)
+                        .map(|value| format!("{}: {}", field.name, value))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        AnalysedType::Tuple(typ) => format!(
+            "({})",
+            typ.items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| prompt_wave_value(&format!("{}.{}", path, idx), item))
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        AnalysedType::List(typ) => {
+            let mut items = Vec::new();
+            while Confirm::new(&format!("Add item #{} to {}?", items.len() + 1, path))
+                .with_default(false)
+                .prompt()?
+            {
+                items.push(prompt_wave_value(
+                    &format!("{}[{}]", path, items.len()),
+                    &typ.inner,
+                )?);
+            }
+            format!("[{}]", items.join(", "))
+        }
+        AnalysedType::Variant(typ) => {
+            let case_name = Select::new(
+                &label,
+                typ.cases.iter().map(|case| case.name.clone()).collect(),
+            )
+            .prompt()?;
+            let case = typ
+                .cases
+                .iter()
+                .find(|case| case.name == case_name)
+                .ok_or_else(|| anyhow!("Unknown variant case: {}", case_name))?;
+            match &case.typ {
+                Some(case_typ) => format!(
+                    "{}({})",
+                    case.name,
+                    prompt_wave_value(&format!("{}.{}", path, case.name), case_typ)?
+                ),
+                None => case.name.clone(),
+            }
+        }
+        AnalysedType::Result(typ) => {
+            let case = Select::new(&label, vec!["ok", "err"]).prompt()?;
+            let case_typ = if case == "ok" { &typ.ok } else { &typ.err };
+            match case_typ {
+                Some(case_typ) => format!(
+                    "{}({})",
+                    case,
+                    prompt_wave_value(&format!("{}.{}", path, case), case_typ)?
+                ),
+                None => case.to_string(),
+            }
+        }
+        AnalysedType::Handle(_) => {
+            bail!("Resource handle {} cannot be entered interactively", path)
+        }
+        AnalysedType::U8(_)
+        | AnalysedType::U16(_)
+        | AnalysedType::U32(_)
+        | AnalysedType::U64(_)
+        | AnalysedType::S8(_)
+        | AnalysedType::S16(_)
+        | AnalysedType::S32(_)
+        | AnalysedType::S64(_)
+        | AnalysedType::F32(_)
+        | AnalysedType::F64(_) => {
+            let typ = typ.clone();
+            Text::new(&label)
+                .with_validator(move |value: &str| {
+                    match parse_type_annotated_value(&typ, value.trim()) {
+                        Ok(_) => Ok(Validation::Valid),
+                        Err(_) => Ok(Validation::Invalid(ErrorMessage::from(format!(
+                            "Expected a {} value",
+                            render_type(&typ)
+                        )))),
+                    }
+                })
+                .prompt()?
+                .trim()
+                .to_string()
+        }
+    };
+
+    Ok(value)
+}
+
+struct FunctionOption {
+    name: String,
+    signature: String,
+}
+
+impl Display for FunctionOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.signature)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct OptionalUrl(Option<Url>);
 
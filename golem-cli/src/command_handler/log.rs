@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use crate::context::Context;
-use crate::model::text::fmt::{NestedTextViewIndent, TextView};
+use crate::log::{capture_output, log_warn_action, set_log_output, CapturedOutput, Output};
+use crate::model::text::fmt::{format_csv, NestedTextViewIndent, TextView};
 use crate::model::Format;
+use anyhow::bail;
+use itertools::Itertools;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 use std::sync::Arc;
 
 pub struct LogHandler {
@@ -29,21 +33,123 @@ impl LogHandler {
     }
 
     pub fn log_view<View: TextView + Serialize + DeserializeOwned>(&self, view: &View) {
+        // Serializing through serde_json::Value, as its maps are ordered by key, so the key order
+        // of structured outputs is stable between runs (including the keys of any hash maps)
+        let value = || serde_json::to_value(view).unwrap();
+
+        if capture_output(|| CapturedOutput::View(value())) {
+            return;
+        }
+
         match self.ctx.format() {
             Format::Json => {
-                println!("{}", serde_json::to_string(view).unwrap());
+                println!("{}", serde_json::to_string(&value()).unwrap());
             }
             Format::Yaml => {
                 // TODO: handle "streaming" optionally
-                println!("---\n{}", serde_yaml::to_string(view).unwrap());
+                println!("---\n{}", serde_yaml::to_string(&value()).unwrap());
             }
             Format::Text => {
                 view.log();
             }
+            Format::Csv => match view.csv() {
+                Some(table) => {
+                    print!("{}", format_csv(&table));
+                }
+                None => {
+                    log_warn_action(
+                        "Using",
+                        "JSON output, as CSV is only supported for tabular results",
+                    );
+                    println!("{}", serde_json::to_string(&value()).unwrap());
+                }
+            },
+        }
+    }
+
+    /// Redirects the logs to stderr when only a field of the result will be printed,
+    /// so the field value is the only output on stdout
+    pub fn prepare_output_field(&self, field: Option<&str>) {
+        if field.is_some() {
+            set_log_output(Output::Stderr);
+        }
+    }
+
+    /// Logs the view, or if a field path is given, only the value of that field from its
+    /// structured form: strings are printed without quotes, other values as JSON
+    pub fn log_view_field<View: TextView + Serialize + DeserializeOwned>(
+        &self,
+        view: &View,
+        field: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let Some(field) = field else {
+            self.log_view(view);
+            return Ok(());
+        };
+
+        let value = serde_json::to_value(view)?;
+        let Some(field_value) = select_field(&value, field) else {
+            bail!(
+                "Field {} not found in the output, available fields: {}",
+                field,
+                match &value {
+                    Value::Object(fields) => fields.keys().join(", "),
+                    _ => "-".to_string(),
+                }
+            );
+        };
+
+        if capture_output(|| CapturedOutput::View(field_value.clone())) {
+            return Ok(());
         }
+
+        match field_value {
+            Value::String(value) => println!("{}", value),
+            Value::Null => println!(),
+            value => println!("{}", serde_json::to_string(value)?),
+        }
+
+        Ok(())
     }
 
     pub fn nested_text_view_indent(&self) -> NestedTextViewIndent {
         NestedTextViewIndent::new(self.ctx.format())
     }
 }
+
+/// Selects a nested field by a dot separated path, array elements are selected by their index
+fn select_field<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |value, segment| match value {
+            Value::Object(fields) => fields.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::command_handler::log::select_field;
+    use assert2::check;
+    use serde_json::json;
+
+    #[test]
+    fn select_nested_fields() {
+        let value = json!({
+            "worker_name": "w1",
+            "component_version": 3,
+            "env": {"SHARD": "a"},
+            "args": ["x", "y"]
+        });
+
+        check!(select_field(&value, "worker_name") == Some(&json!("w1")));
+        check!(select_field(&value, "component_version") == Some(&json!(3)));
+        check!(select_field(&value, "env.SHARD") == Some(&json!("a")));
+        check!(select_field(&value, "args.1") == Some(&json!("y")));
+        check!(select_field(&value, "args.2").is_none());
+        check!(select_field(&value, "env.SHARD.x").is_none());
+        check!(select_field(&value, "missing").is_none());
+    }
+}
@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::alias::expand_aliases;
 use crate::app::error::AppValidationError;
+use crate::cancellation::{cancel_on_signals, CANCELLED_EXIT_CODE};
+use crate::command::profile::ProfileSubcommand;
 #[cfg(feature = "server-commands")]
 use crate::command::server::ServerSubcommand;
 use crate::command::{
     GolemCliCommand, GolemCliCommandParseResult, GolemCliFallbackCommand, GolemCliGlobalFlags,
     GolemCliSubcommand,
 };
+use crate::command_handler::alias::AliasCommandHandler;
 use crate::command_handler::api::cloud::certificate::ApiCloudCertificateCommandHandler;
 use crate::command_handler::api::cloud::domain::ApiCloudDomainCommandHandler;
 use crate::command_handler::api::cloud::ApiCloudCommandHandler;
@@ -27,8 +31,10 @@ use crate::command_handler::api::deployment::ApiDeploymentCommandHandler;
 use crate::command_handler::api::security_scheme::ApiSecuritySchemeCommandHandler;
 use crate::command_handler::api::ApiCommandHandler;
 use crate::command_handler::app::AppCommandHandler;
+use crate::command_handler::cache::CacheCommandHandler;
 use crate::command_handler::cloud::account::grant::CloudAccountGrantCommandHandler;
 use crate::command_handler::cloud::account::CloudAccountCommandHandler;
+use crate::command_handler::cloud::admin::CloudAdminCommandHandler;
 use crate::command_handler::cloud::project::plugin::CloudProjectPluginCommandHandler;
 use crate::command_handler::cloud::project::policy::CloudProjectPolicyCommandHandler;
 use crate::command_handler::cloud::project::CloudProjectCommandHandler;
@@ -36,21 +42,28 @@ use crate::command_handler::cloud::token::CloudTokenCommandHandler;
 use crate::command_handler::cloud::CloudCommandHandler;
 use crate::command_handler::component::plugin::ComponentPluginCommandHandler;
 use crate::command_handler::component::ComponentCommandHandler;
+use crate::command_handler::history::HistoryCommandHandler;
 use crate::command_handler::interactive::InteractiveHandler;
 use crate::command_handler::log::LogHandler;
 use crate::command_handler::partial_match::ErrorHandler;
 use crate::command_handler::plugin::PluginCommandHandler;
 use crate::command_handler::profile::config::ProfileConfigCommandHandler;
 use crate::command_handler::profile::ProfileCommandHandler;
+use crate::command_handler::script::{init_script_mode, print_script_error, run_in_script_mode};
+use crate::command_handler::self_update::SelfUpdateCommandHandler;
+use crate::command_handler::serve::ServeCommandHandler;
 use crate::command_handler::worker::WorkerCommandHandler;
-use crate::config::{Config, ProfileName};
+use crate::config::{Config, ConfigMigrationMode, ProfileName};
 use crate::context::Context;
-use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit};
-use crate::log::{logln, set_log_output, Output};
+use crate::error::{
+    CancelledError, ContextInitHintError, HintError, NonSuccessfulExit, TimeoutError,
+    TIMEOUT_EXIT_CODE,
+};
+use crate::log::{init_output_style, log_warn_action, logln, set_log_output, LogColorize, Output};
 use crate::model::text::fmt::log_error;
-use crate::{command_name, init_tracing};
-use anyhow::anyhow;
-use clap::CommandFactory;
+use crate::{command_name, history, init_tracing};
+use anyhow::{anyhow, bail};
+use clap::{ArgMatches, CommandFactory};
 use clap_complete::Shell;
 #[cfg(feature = "server-commands")]
 use clap_verbosity_flag::Verbosity;
@@ -59,15 +72,21 @@ use std::process::ExitCode;
 use std::sync::Arc;
 use tracing::{debug, Level};
 
+mod alias;
 mod api;
 mod app;
+mod cache;
 mod cloud;
 mod component;
+mod history;
 mod interactive;
 mod log;
 mod partial_match;
 mod plugin;
 mod profile;
+mod script;
+mod self_update;
+mod serve;
 mod worker;
 
 // NOTE: We are explicitly not using #[async_trait] here to be able to NOT have a Send bound
@@ -94,7 +113,11 @@ pub struct CommandHandler<Hooks: CommandHandlerHooks> {
 }
 
 impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
-    fn new(global_flags: &GolemCliGlobalFlags, hooks: Arc<Hooks>) -> anyhow::Result<Self> {
+    fn new(
+        global_flags: &GolemCliGlobalFlags,
+        config_migration_mode: ConfigMigrationMode,
+        hooks: Arc<Hooks>,
+    ) -> anyhow::Result<Self> {
         let profile_name = {
             if global_flags.local {
                 Some(ProfileName::local())
@@ -107,7 +130,11 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
 
         let ctx = Arc::new(Context::new(
             global_flags,
-            Config::get_active_profile(&global_flags.config_dir(), profile_name)?,
+            Config::get_active_profile(
+                &global_flags.config_dir(),
+                profile_name,
+                config_migration_mode,
+            )?,
         ));
         Ok(Self {
             ctx: ctx.clone(),
@@ -117,9 +144,10 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
 
     fn new_with_init_hint_error_handler(
         global_flags: &GolemCliGlobalFlags,
+        config_migration_mode: ConfigMigrationMode,
         hooks: Arc<Hooks>,
     ) -> anyhow::Result<Self> {
-        match Self::new(global_flags, hooks) {
+        match Self::new(global_flags, config_migration_mode, hooks) {
             Ok(ok) => Ok(ok),
             Err(error) => {
                 set_log_output(Output::Stderr);
@@ -138,8 +166,20 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        let result = match GolemCliCommand::try_parse_from_lenient(args_iterator, true) {
-            GolemCliCommandParseResult::FullMatch(command) => {
+        let args = expand_aliases(
+            args_iterator
+                .into_iter()
+                .map(|arg| arg.into())
+                .collect::<Vec<OsString>>(),
+        );
+
+        let result = match GolemCliCommand::try_parse_from_lenient(&args, true) {
+            GolemCliCommandParseResult::FullMatch {
+                mut command,
+                matches,
+            } => {
+                command.global_flags.command_path = history::command_path(&matches);
+
                 #[cfg(feature = "server-commands")]
                 let verbosity = if matches!(command.subcommand, GolemCliSubcommand::Server { .. }) {
                     Hooks::override_verbosity(command.global_flags.verbosity())
@@ -148,20 +188,68 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
                 };
                 #[cfg(not(feature = "server-commands"))]
                 let verbosity = command.global_flags.verbosity();
+                init_output_style(
+                    command.global_flags.color.unwrap_or_default(),
+                    command.global_flags.ascii,
+                );
                 init_tracing(verbosity);
 
-                match Self::new_with_init_hint_error_handler(&command.global_flags, hooks) {
+                let script = command.global_flags.script;
+                if script {
+                    init_script_mode();
+                }
+
+                // Explicit config migrations and repairs are handled (and reported) by the commands themselves
+                let config_migration_mode = match &command.subcommand {
+                    GolemCliSubcommand::Profile {
+                        subcommand: ProfileSubcommand::Migrate { .. },
+                    } => ConfigMigrationMode::InMemory,
+                    GolemCliSubcommand::Profile {
+                        subcommand: ProfileSubcommand::Repair { .. },
+                    } => ConfigMigrationMode::Repair,
+                    _ => ConfigMigrationMode::Persist,
+                };
+
+                match Self::new_with_init_hint_error_handler(
+                    &command.global_flags,
+                    config_migration_mode,
+                    hooks,
+                ) {
                     Ok(mut handler) => {
-                        let result = handler
-                            .handle_command(command)
+                        let cancellation_token = handler.ctx.cancellation_token().clone();
+                        let signal_handler =
+                            tokio::spawn(cancel_on_signals(cancellation_token.clone()));
+
+                        let result = if script {
+                            run_in_script_mode(async {
+                                handler.check_read_only(&matches)?;
+                                handler.handle_command(command).await
+                            })
                             .await
-                            .map(|()| ExitCode::SUCCESS);
+                        } else {
+                            match handler.check_read_only(&matches) {
+                                Ok(()) => handler.handle_command(command).await,
+                                Err(error) => Err(error),
+                            }
+                        }
+                        .map(|()| ExitCode::SUCCESS);
+
+                        signal_handler.abort();
+                        cancellation_token.cleanup();
+
+                        handler.record_history(&args, &matches, result.is_ok());
 
                         match result {
                             Ok(result) => Ok(result),
                             Err(error) => {
                                 set_log_output(Output::Stderr);
-                                if let Some(hint_error) = error.downcast_ref::<HintError>() {
+                                if error.downcast_ref::<CancelledError>().is_some() {
+                                    logln("");
+                                    log_warn_action("Cancelled", "by user request");
+                                    Ok(ExitCode::from(CANCELLED_EXIT_CODE))
+                                } else if error.downcast_ref::<TimeoutError>().is_some() {
+                                    Ok(ExitCode::from(TIMEOUT_EXIT_CODE))
+                                } else if let Some(hint_error) = error.downcast_ref::<HintError>() {
                                     handler
                                         .ctx
                                         .error_handler()
@@ -173,7 +261,17 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
                             }
                         }
                     }
-                    Err(error) => Err(error),
+                    Err(error) => {
+                        if script {
+                            let message = if error.downcast_ref::<NonSuccessfulExit>().is_some() {
+                                "Failed to initialize the CLI context".to_string()
+                            } else {
+                                format!("{:#}", error)
+                            };
+                            print_script_error("failed", message);
+                        }
+                        Err(error)
+                    }
                 }
             }
             GolemCliCommandParseResult::ErrorWithPartialMatch {
@@ -181,6 +279,10 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
                 fallback_command,
                 partial_match,
             } => {
+                init_output_style(
+                    fallback_command.global_flags.color.unwrap_or_default(),
+                    fallback_command.global_flags.ascii,
+                );
                 init_tracing(
                     fallback_command
                         .global_flags
@@ -191,9 +293,15 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
                 debug!(partial_match = ?partial_match, "Partial match");
                 debug_log_parse_error(&error, &fallback_command);
                 error.print().unwrap();
+                if fallback_command.global_flags.script {
+                    print_script_error("invalidArguments", error.render().to_string());
+                }
 
-                match Self::new_with_init_hint_error_handler(&fallback_command.global_flags, hooks)
-                {
+                match Self::new_with_init_hint_error_handler(
+                    &fallback_command.global_flags,
+                    ConfigMigrationMode::Persist,
+                    hooks,
+                ) {
                     Ok(handler) => {
                         set_log_output(Output::Stderr);
                         let exit_code = clamp_exit_code(error.exit_code());
@@ -211,9 +319,16 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
                 error,
                 fallback_command,
             } => {
+                init_output_style(
+                    fallback_command.global_flags.color.unwrap_or_default(),
+                    fallback_command.global_flags.ascii,
+                );
                 init_tracing(fallback_command.global_flags.verbosity());
                 debug_log_parse_error(&error, &fallback_command);
                 error.print().unwrap();
+                if fallback_command.global_flags.script && error.exit_code() != 0 {
+                    print_script_error("invalidArguments", error.render().to_string());
+                }
 
                 Ok(clamp_exit_code(error.exit_code()))
             }
@@ -271,10 +386,61 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
             GolemCliSubcommand::Cloud { subcommand } => {
                 self.ctx.cloud_handler().handle_command(subcommand).await
             }
+            GolemCliSubcommand::History { subcommand } => {
+                self.ctx.history_handler().handle_command(subcommand)
+            }
+            GolemCliSubcommand::Cache { subcommand } => {
+                self.ctx.cache_handler().handle_command(subcommand)
+            }
+            GolemCliSubcommand::Alias { subcommand } => {
+                self.ctx.alias_handler().handle_command(subcommand)
+            }
+            GolemCliSubcommand::SelfUpdate {
+                channel,
+                version,
+                check,
+            } => {
+                self.ctx
+                    .self_update_handler()
+                    .handle_command(channel, version, check)
+                    .await
+            }
+            GolemCliSubcommand::Serve { stdio } => {
+                self.ctx.serve_handler().handle_command(stdio).await
+            }
             GolemCliSubcommand::Completion { shell } => self.cmd_completion(shell),
         }
     }
 
+    fn check_read_only(&self, matches: &ArgMatches) -> anyhow::Result<()> {
+        if !self.ctx.read_only() {
+            return Ok(());
+        }
+
+        if let Some(command_path) = history::mutating_command_path(matches) {
+            log_error(format!(
+                "The {} command is not allowed in read-only mode, as it could change server side state",
+                command_path.join(" ").log_color_highlight()
+            ));
+            logln("Read-only mode is enabled by the --read-only flag, the GOLEM_READ_ONLY env var, or by the selected profile");
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    fn record_history(&self, args: &[OsString], matches: &ArgMatches, success: bool) {
+        if let Err(error) = history::record(
+            self.ctx.config_dir(),
+            self.ctx.profile_name(),
+            args,
+            matches,
+            success,
+        ) {
+            debug!("Failed to record command history: {:#}", error);
+        }
+    }
+
     fn cmd_completion(&self, shell: Shell) -> anyhow::Result<()> {
         let mut command = GolemCliCommand::command();
         let command_name = command_name();
@@ -288,6 +454,7 @@ impl<Hooks: CommandHandlerHooks> CommandHandler<Hooks> {
 //       by moving these simple factory methods into the specific handlers on demand,
 //       if the need ever arises
 trait Handlers {
+    fn alias_handler(&self) -> AliasCommandHandler;
     fn api_cloud_certificate_handler(&self) -> ApiCloudCertificateCommandHandler;
     fn api_cloud_domain_handler(&self) -> ApiCloudDomainCommandHandler;
     fn api_cloud_handler(&self) -> ApiCloudCommandHandler;
@@ -296,8 +463,10 @@ trait Handlers {
     fn api_handler(&self) -> ApiCommandHandler;
     fn api_security_scheme_handler(&self) -> ApiSecuritySchemeCommandHandler;
     fn app_handler(&self) -> AppCommandHandler;
+    fn cache_handler(&self) -> CacheCommandHandler;
     fn cloud_account_grant_handler(&self) -> CloudAccountGrantCommandHandler;
     fn cloud_account_handler(&self) -> CloudAccountCommandHandler;
+    fn cloud_admin_handler(&self) -> CloudAdminCommandHandler;
     fn cloud_handler(&self) -> CloudCommandHandler;
     fn cloud_project_handler(&self) -> CloudProjectCommandHandler;
     fn cloud_project_plugin_handler(&self) -> CloudProjectPluginCommandHandler;
@@ -306,15 +475,22 @@ trait Handlers {
     fn component_handler(&self) -> ComponentCommandHandler;
     fn component_plugin_handler(&self) -> ComponentPluginCommandHandler;
     fn error_handler(&self) -> ErrorHandler;
+    fn history_handler(&self) -> HistoryCommandHandler;
     fn interactive_handler(&self) -> InteractiveHandler;
     fn log_handler(&self) -> LogHandler;
     fn plugin_handler(&self) -> PluginCommandHandler;
     fn profile_config_handler(&self) -> ProfileConfigCommandHandler;
     fn profile_handler(&self) -> ProfileCommandHandler;
+    fn self_update_handler(&self) -> SelfUpdateCommandHandler;
+    fn serve_handler(&self) -> ServeCommandHandler;
     fn worker_handler(&self) -> WorkerCommandHandler;
 }
 
 impl Handlers for Arc<Context> {
+    fn alias_handler(&self) -> AliasCommandHandler {
+        AliasCommandHandler::new(self.clone())
+    }
+
     fn api_cloud_certificate_handler(&self) -> ApiCloudCertificateCommandHandler {
         ApiCloudCertificateCommandHandler::new(self.clone())
     }
@@ -347,6 +523,10 @@ impl Handlers for Arc<Context> {
         AppCommandHandler::new(self.clone())
     }
 
+    fn cache_handler(&self) -> CacheCommandHandler {
+        CacheCommandHandler::new(self.clone())
+    }
+
     fn cloud_account_grant_handler(&self) -> CloudAccountGrantCommandHandler {
         CloudAccountGrantCommandHandler::new(self.clone())
     }
@@ -355,6 +535,10 @@ impl Handlers for Arc<Context> {
         CloudAccountCommandHandler::new(self.clone())
     }
 
+    fn cloud_admin_handler(&self) -> CloudAdminCommandHandler {
+        CloudAdminCommandHandler::new(self.clone())
+    }
+
     fn cloud_handler(&self) -> CloudCommandHandler {
         CloudCommandHandler::new(self.clone())
     }
@@ -387,6 +571,10 @@ impl Handlers for Arc<Context> {
         ErrorHandler::new(self.clone())
     }
 
+    fn history_handler(&self) -> HistoryCommandHandler {
+        HistoryCommandHandler::new(self.clone())
+    }
+
     fn interactive_handler(&self) -> InteractiveHandler {
         InteractiveHandler::new(self.clone())
     }
@@ -407,6 +595,14 @@ impl Handlers for Arc<Context> {
         ProfileCommandHandler::new(self.clone())
     }
 
+    fn self_update_handler(&self) -> SelfUpdateCommandHandler {
+        SelfUpdateCommandHandler::new(self.clone())
+    }
+
+    fn serve_handler(&self) -> ServeCommandHandler {
+        ServeCommandHandler::new(self.clone())
+    }
+
     fn worker_handler(&self) -> WorkerCommandHandler {
         WorkerCommandHandler::new(self.clone())
     }
@@ -267,6 +267,19 @@ impl ErrorHandler {
                     }
                 }
 
+                Ok(())
+            }
+            ContextInitHintError::InvalidConfig { config_path, error } => {
+                log_error(format!(
+                    "Invalid config file {}!",
+                    config_path.log_color_highlight()
+                ));
+                logln("");
+                logln(error);
+                logln("");
+                logln("No last good copy of the config is available, use 'profile repair' to keep the valid profiles and drop the invalid ones");
+                logln("");
+
                 Ok(())
             }
         }
@@ -17,11 +17,13 @@ use crate::command::shared_args::PluginScopeArgs;
 use crate::command_handler::Handlers;
 use crate::context::{Context, GolemClients};
 use crate::error::service::AnyhowMapServiceError;
-use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_warn_action, logln, LogColorize, LogIndent};
 use crate::model::component::Component;
 use crate::model::plugin_manifest::{PluginManifest, PluginTypeSpecificManifest};
+use crate::model::text::fmt::log_error;
 use crate::model::{ComponentName, PathBufOrStdin, PluginDefinition, ProjectNameAndId};
-use anyhow::{anyhow, Context as AnyhowContext};
+use anyhow::{anyhow, bail, Context as AnyhowContext};
 use golem_client::api::{ComponentClient as ComponentClientOss, PluginClient as PluginClientOss};
 use golem_client::model::{
     ComponentTransformerDefinition, ComponentType, DefaultPluginScope, OplogProcessorDefinition,
@@ -34,6 +36,11 @@ use golem_cloud_client::model::{ComponentQuery, PluginDefinitionCreationCloudPlu
 use golem_cloud_client::{CloudPluginScope, ProjectPluginScope};
 use golem_common::model::plugin::ComponentPluginScope;
 use golem_common::model::{ComponentId, Empty};
+use golem_templates::instantiate_template;
+use golem_templates::model::{
+    GuestLanguage, PackageName, PluginType, TargetExistsResolveMode, TemplateKind,
+    TemplateParameters,
+};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs::File;
@@ -49,6 +56,11 @@ impl PluginCommandHandler {
 
     pub async fn handle_command(&self, subcommand: PluginSubcommand) -> anyhow::Result<()> {
         match subcommand {
+            PluginSubcommand::New {
+                plugin_package_name,
+                plugin_type,
+                language,
+            } => self.cmd_new(plugin_package_name, plugin_type, language),
             PluginSubcommand::List { scope } => self.cmd_list(scope).await,
             PluginSubcommand::Get {
                 plugin_name,
@@ -64,10 +76,79 @@ impl PluginCommandHandler {
         }
     }
 
+    fn cmd_new(
+        &self,
+        plugin_package_name: PackageName,
+        plugin_type: PluginType,
+        language: GuestLanguage,
+    ) -> anyhow::Result<()> {
+        let templates = golem_templates::all_plugin_templates();
+
+        let Some(template) = templates.iter().find(|template| {
+            template.language == language
+                && matches!(
+                    template.kind,
+                    TemplateKind::Plugin { plugin_type: template_plugin_type }
+                        if template_plugin_type == plugin_type
+                )
+        }) else {
+            log_error(format!(
+                "No {} plugin template found for language {}",
+                plugin_type.to_string().log_color_highlight(),
+                language.name().log_color_highlight()
+            ));
+            logln("");
+            logln("Available plugin templates:");
+            for template in &templates {
+                if let TemplateKind::Plugin { plugin_type } = template.kind {
+                    logln(format!(
+                        "  - {} {}",
+                        template.language.id().log_color_highlight(),
+                        plugin_type
+                    ));
+                }
+            }
+            logln("");
+            bail!(NonSuccessfulExit);
+        };
+
+        let plugin_dir = PathBuf::from(plugin_package_name.to_kebab_case());
+        if plugin_dir.exists() {
+            bail!(
+                "Plugin directory already exists: {}",
+                plugin_dir.log_color_error_highlight()
+            );
+        }
+
+        let instructions = instantiate_template(
+            template,
+            &TemplateParameters {
+                component_name: plugin_package_name.to_string_with_colon().as_str().into(),
+                package_name: plugin_package_name,
+                target_path: plugin_dir.clone(),
+            },
+            TargetExistsResolveMode::Fail,
+        )
+        .with_context(|| anyhow!("Failed to instantiate plugin template {}", template.name))?;
+
+        log_action(
+            "Created",
+            format!(
+                "{} plugin project in {}",
+                plugin_type,
+                plugin_dir.log_color_highlight()
+            ),
+        );
+        logln("");
+        logln(instructions);
+
+        Ok(())
+    }
+
     async fn cmd_list(&self, scope: PluginScopeArgs) -> anyhow::Result<()> {
         let (scope_project, scope_component_id) = self.resolve_scope(&scope).await?;
 
-        let plugin_definitions = match self.ctx.golem_clients().await? {
+        let mut plugin_definitions = match self.ctx.golem_clients().await? {
             GolemClients::Oss(clients) => clients
                 .plugin
                 .list_plugins(Some(&default_plugin_scope(scope_component_id.as_ref())))
@@ -95,6 +176,7 @@ impl PluginCommandHandler {
                 .map_service_error()?,
         };
 
+        plugin_definitions.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
         self.ctx.log_handler().log_view(&plugin_definitions);
 
         Ok(())
@@ -388,7 +470,7 @@ impl PluginCommandHandler {
         Ok(())
     }
 
-    async fn get(&self, name: &str, version: &str) -> anyhow::Result<PluginDefinition> {
+    pub async fn get(&self, name: &str, version: &str) -> anyhow::Result<PluginDefinition> {
         match self.ctx.golem_clients().await? {
             GolemClients::Oss(client) => client
                 .plugin
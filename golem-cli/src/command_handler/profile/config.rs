@@ -13,14 +13,18 @@
 // limitations under the License.
 
 use crate::command::profile::config::ProfileConfigSubcommand;
-use crate::config::{Config, ProfileName};
+use crate::command::GolemCliCommand;
+use crate::config::{Config, Profile, ProfileConfigKey, ProfileName};
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
-use crate::log::log_action;
-use crate::model::text::fmt::log_error;
-use crate::model::Format;
+use crate::log::{log_action, LogColorize};
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::{Format, ProfileConfigValueView};
 use anyhow::bail;
+use clap::CommandFactory;
+use itertools::Itertools;
 use std::sync::Arc;
+use strum::IntoEnumIterator;
 
 pub struct ProfileConfigCommandHandler {
     ctx: Arc<Context>,
@@ -37,34 +41,295 @@ impl ProfileConfigCommandHandler {
         subcommand: ProfileConfigSubcommand,
     ) -> anyhow::Result<()> {
         match subcommand {
-            ProfileConfigSubcommand::SetFormat { format } => {
-                self.cmd_set_format(profile_name, format)
+            ProfileConfigSubcommand::Get { key } => self.cmd_get(profile_name, key),
+            ProfileConfigSubcommand::Set {
+                key,
+                value,
+                for_command: None,
+            } => self.cmd_set(profile_name, key, value),
+            ProfileConfigSubcommand::Set {
+                key,
+                value,
+                for_command: Some(command),
+            } => self.cmd_set_command_format(profile_name, key, &command, value),
+            ProfileConfigSubcommand::Unset {
+                key,
+                for_command: None,
+            } => self.cmd_unset(profile_name, key),
+            ProfileConfigSubcommand::Unset {
+                key,
+                for_command: Some(command),
+            } => self.cmd_unset_command_format(profile_name, key, &command),
+            ProfileConfigSubcommand::SetFormat {
+                format,
+                for_command,
+            } => {
+                log_warn(format!(
+                    "'set-format' is deprecated, use 'profile config {} set {} {}{}' instead",
+                    profile_name.0,
+                    ProfileConfigKey::Format,
+                    format,
+                    for_command
+                        .as_ref()
+                        .map(|command| format!(" --for {}", command))
+                        .unwrap_or_default()
+                ));
+                match for_command {
+                    Some(command) => self.cmd_set_command_format(
+                        profile_name,
+                        ProfileConfigKey::Format,
+                        &command,
+                        format.to_string(),
+                    ),
+                    None => {
+                        self.cmd_set(profile_name, ProfileConfigKey::Format, format.to_string())
+                    }
+                }
             }
         }
     }
 
-    fn cmd_set_format(&mut self, profile_name: ProfileName, format: Format) -> anyhow::Result<()> {
-        match Config::get_profile(&profile_name, self.ctx.config_dir())? {
-            Some(mut profile) => {
-                profile.get_config_mut().default_format = format;
+    fn cmd_get(
+        &mut self,
+        profile_name: ProfileName,
+        key: Option<ProfileConfigKey>,
+    ) -> anyhow::Result<()> {
+        let profile = self.existing_profile(&profile_name)?;
+
+        let mut values = match key {
+            Some(key) => vec![ProfileConfigValueView::new(&profile, key)],
+            None => ProfileConfigKey::iter()
+                .map(|key| ProfileConfigValueView::new(&profile, key))
+                .collect(),
+        };
+        if matches!(key, None | Some(ProfileConfigKey::Format)) {
+            values.extend(
+                profile
+                    .get_config()
+                    .command_formats
+                    .iter()
+                    .map(|(command, format)| {
+                        ProfileConfigValueView::command_format(command, *format)
+                    }),
+            );
+        }
+        self.ctx.log_handler().log_view(&values);
 
-                log_action(
-                    "Updating",
-                    format!(
-                        "profile's default format for {} to {}",
-                        &profile_name, format
-                    ),
-                );
-                Config::set_profile(profile_name, profile, self.ctx.config_dir())?;
-                log_action("Updated", "");
+        Ok(())
+    }
 
-                Ok(())
+    fn cmd_set(
+        &mut self,
+        profile_name: ProfileName,
+        key: ProfileConfigKey,
+        value: String,
+    ) -> anyhow::Result<()> {
+        let mut profile = self.existing_profile(&profile_name)?;
+
+        if let Err(error) = profile.set_config_value(key, &value) {
+            log_error(format!(
+                "Invalid value for {}: {}",
+                key.to_string().log_color_highlight(),
+                error
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Updating",
+            format!(
+                "profile config {} for {} to {}",
+                key.to_string().log_color_highlight(),
+                &profile_name,
+                value.log_color_highlight()
+            ),
+        );
+        Config::set_profile(profile_name, profile, self.ctx.config_dir())?;
+        log_action("Updated", "");
+
+        Ok(())
+    }
+
+    fn cmd_unset(
+        &mut self,
+        profile_name: ProfileName,
+        key: ProfileConfigKey,
+    ) -> anyhow::Result<()> {
+        let mut profile = self.existing_profile(&profile_name)?;
+
+        if let Err(error) = profile.unset_config_value(key) {
+            log_error(format!(
+                "Cannot unset {}: {}",
+                key.to_string().log_color_highlight(),
+                error
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Unsetting",
+            format!(
+                "profile config {} for {}",
+                key.to_string().log_color_highlight(),
+                &profile_name
+            ),
+        );
+        Config::set_profile(profile_name, profile, self.ctx.config_dir())?;
+        log_action("Updated", "");
+
+        Ok(())
+    }
+
+    fn cmd_set_command_format(
+        &mut self,
+        profile_name: ProfileName,
+        key: ProfileConfigKey,
+        command: &str,
+        value: String,
+    ) -> anyhow::Result<()> {
+        let command = self.validated_command_format_key(key, command)?;
+        let format = match value.parse::<Format>() {
+            Ok(format) => format,
+            Err(error) => {
+                log_error(format!(
+                    "Invalid value for {}: {}",
+                    key.to_string().log_color_highlight(),
+                    error
+                ));
+                bail!(NonSuccessfulExit);
             }
+        };
+
+        let mut profile = self.existing_profile(&profile_name)?;
+
+        log_action(
+            "Updating",
+            format!(
+                "profile config {} of command {} for {} to {}",
+                key.to_string().log_color_highlight(),
+                command.log_color_highlight(),
+                &profile_name,
+                format.to_string().log_color_highlight()
+            ),
+        );
+        profile
+            .get_config_mut()
+            .command_formats
+            .insert(command, format);
+        Config::set_profile(profile_name, profile, self.ctx.config_dir())?;
+        log_action("Updated", "");
+
+        Ok(())
+    }
+
+    fn cmd_unset_command_format(
+        &mut self,
+        profile_name: ProfileName,
+        key: ProfileConfigKey,
+        command: &str,
+    ) -> anyhow::Result<()> {
+        let command = self.validated_command_format_key(key, command)?;
+        let mut profile = self.existing_profile(&profile_name)?;
+
+        if profile
+            .get_config_mut()
+            .command_formats
+            .remove(&command)
+            .is_none()
+        {
+            log_warn(format!(
+                "No {} is set for command {} in profile {}",
+                key.to_string().log_color_highlight(),
+                command.log_color_highlight(),
+                &profile_name
+            ));
+            return Ok(());
+        }
+
+        log_action(
+            "Unsetting",
+            format!(
+                "profile config {} of command {} for {}",
+                key.to_string().log_color_highlight(),
+                command.log_color_highlight(),
+                &profile_name
+            ),
+        );
+        Config::set_profile(profile_name, profile, self.ctx.config_dir())?;
+        log_action("Updated", "");
+
+        Ok(())
+    }
+
+    /// Validates the --for command, only the output format can be set per command
+    fn validated_command_format_key(
+        &self,
+        key: ProfileConfigKey,
+        command: &str,
+    ) -> anyhow::Result<String> {
+        if key != ProfileConfigKey::Format {
+            log_error(format!(
+                "Only {} can be set for a specific command, {} applies to every command",
+                ProfileConfigKey::Format.to_string().log_color_highlight(),
+                key.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        match command_format_key(command) {
+            Some(command) => Ok(command),
+            None => {
+                log_error(format!(
+                    "Unknown command: {}, expected a subcommand path, e.g. worker-list",
+                    command.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn existing_profile(&self, profile_name: &ProfileName) -> anyhow::Result<Profile> {
+        match Config::get_profile(profile_name, self.ctx.config_dir())? {
+            Some(profile) => Ok(profile),
             None => {
                 log_error(format!("Profile {} not found", profile_name));
-                // TODO: show available profiles
                 bail!(NonSuccessfulExit);
             }
         }
     }
 }
+
+/// Normalizes a '-' or space separated subcommand path, returns None if no such command exists
+fn command_format_key(command: &str) -> Option<String> {
+    let key = command.split_whitespace().join("-");
+    command_paths(&GolemCliCommand::command(), &mut Vec::new())
+        .into_iter()
+        .find(|path| *path == key)
+}
+
+fn command_paths(command: &clap::Command, prefix: &mut Vec<String>) -> Vec<String> {
+    let mut paths = Vec::new();
+    for subcommand in command.get_subcommands() {
+        prefix.push(subcommand.get_name().to_string());
+        paths.push(prefix.join("-"));
+        paths.extend(command_paths(subcommand, prefix));
+        prefix.pop();
+    }
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::profile::config::command_format_key;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn command_format_keys() {
+        check!(command_format_key("worker-list") == Some("worker-list".to_string()));
+        check!(command_format_key("worker list") == Some("worker-list".to_string()));
+        check!(command_format_key("component") == Some("component".to_string()));
+        check!(command_format_key("worker-lists").is_none());
+        check!(command_format_key("").is_none());
+    }
+}
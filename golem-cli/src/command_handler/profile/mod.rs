@@ -17,15 +17,19 @@ pub mod config;
 use crate::command::profile::ProfileSubcommand;
 use crate::command_handler::Handlers;
 use crate::config::{
-    CloudProfile, Config, NamedProfile, OssProfile, Profile, ProfileConfig, ProfileKind,
-    ProfileName, DEFAULT_OSS_URL,
+    ClientConfig, CloudProfile, Config, ConfigMigrationMode, NamedProfile, OssProfile, Profile,
+    ProfileConfig, ProfileKind, ProfileName, DEFAULT_OSS_URL,
 };
-use crate::context::Context;
+use crate::context::{Clients, Context, GolemClients};
+use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
 use crate::log::{log_action, log_warn_action, LogColorize};
-use crate::model::text::fmt::log_error;
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::text::profile::{ProfileMigrateView, ProfileRepairView};
 use crate::model::{Format, ProfileView};
 use anyhow::bail;
+use golem_cloud_client::api::TokenClient;
+use itertools::Itertools;
 use std::sync::Arc;
 use url::Url;
 
@@ -62,7 +66,10 @@ impl ProfileCommandHandler {
             ProfileSubcommand::List => self.cmd_list(),
             ProfileSubcommand::Switch { profile_name } => self.cmd_switch(profile_name),
             ProfileSubcommand::Get { profile_name } => self.cmd_get(profile_name),
-            ProfileSubcommand::Delete { profile_name } => self.cmd_delete(profile_name),
+            ProfileSubcommand::Delete {
+                profile_name,
+                purge_tokens,
+            } => self.cmd_delete(profile_name, purge_tokens).await,
             ProfileSubcommand::Config {
                 profile_name,
                 subcommand,
@@ -72,6 +79,8 @@ impl ProfileCommandHandler {
                     .handler_subcommand(profile_name, subcommand)
                     .await
             }
+            ProfileSubcommand::Migrate { dry_run } => self.cmd_migrate(dry_run),
+            ProfileSubcommand::Repair { dry_run } => self.cmd_repair(dry_run),
         }
     }
 
@@ -103,7 +112,10 @@ impl ProfileCommandHandler {
                         url: component_url.unwrap_or(Url::parse(DEFAULT_OSS_URL)?),
                         worker_url,
                         allow_insecure,
-                        config: ProfileConfig { default_format },
+                        config: ProfileConfig {
+                            default_format,
+                            ..ProfileConfig::default()
+                        },
                     }),
                     ProfileKind::Cloud => Profile::GolemCloud(CloudProfile {
                         custom_url: component_url,
@@ -147,6 +159,7 @@ impl ProfileCommandHandler {
         let profiles = config
             .profiles
             .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.0.cmp(&b.0))
             .map(|(name, profile)| {
                 ProfileView::from_profile(&default_profile_name, NamedProfile { name, profile })
             })
@@ -163,7 +176,8 @@ impl ProfileCommandHandler {
     }
 
     fn cmd_get(&self, profile_name: Option<ProfileName>) -> anyhow::Result<()> {
-        let active_profile = Config::get_active_profile(self.ctx.config_dir(), None)?;
+        let active_profile =
+            Config::get_active_profile(self.ctx.config_dir(), None, ConfigMigrationMode::Persist)?;
         let active_profile_name = active_profile.name.clone();
 
         let profile = match profile_name {
@@ -192,7 +206,11 @@ impl ProfileCommandHandler {
         Ok(())
     }
 
-    fn cmd_delete(&self, profile_name: ProfileName) -> anyhow::Result<()> {
+    async fn cmd_delete(
+        &self,
+        profile_name: ProfileName,
+        purge_tokens: bool,
+    ) -> anyhow::Result<()> {
         if profile_name.is_builtin() {
             log_error(format!(
                 "Cannot delete builtin profile: {}",
@@ -201,6 +219,21 @@ impl ProfileCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
+        let config = Config::from_dir(self.ctx.config_dir())?;
+        if config.default_profile_name() == profile_name {
+            log_warn(format!(
+                "Deleting the active profile {}, use {} to switch to another profile first",
+                profile_name.0.log_color_highlight(),
+                "golem profile switch <PROFILE_NAME>".log_color_highlight()
+            ));
+        }
+
+        if purge_tokens {
+            if let Some(profile) = config.profiles.get(&profile_name) {
+                self.purge_profile_token(&profile_name, profile).await?;
+            }
+        }
+
         Config::delete_profile(&profile_name, self.ctx.config_dir())?;
 
         log_warn_action(
@@ -210,4 +243,99 @@ impl ProfileCommandHandler {
 
         Ok(())
     }
+
+    async fn purge_profile_token(
+        &self,
+        profile_name: &ProfileName,
+        profile: &Profile,
+    ) -> anyhow::Result<()> {
+        let auth = match profile {
+            Profile::Golem(_) => {
+                log_warn(format!(
+                    "Profile {} is an OSS profile, there are no tokens to revoke",
+                    profile_name.0.log_color_highlight()
+                ));
+                return Ok(());
+            }
+            Profile::GolemCloud(CloudProfile { auth: None, .. }) => {
+                log_warn(format!(
+                    "Profile {} is not authenticated, there are no tokens to revoke",
+                    profile_name.0.log_color_highlight()
+                ));
+                return Ok(());
+            }
+            Profile::GolemCloud(CloudProfile {
+                auth: Some(auth), ..
+            }) => auth,
+        };
+
+        let clients = Clients::new(
+            ClientConfig::from(profile),
+            None,
+            profile_name,
+            Some(auth),
+            self.ctx.config_dir(),
+        )
+        .await?;
+        let GolemClients::Cloud(clients) = clients.golem else {
+            bail!("Expected cloud clients for profile {}", profile_name.0);
+        };
+
+        let token_id = auth.data.id;
+        let result = clients
+            .token
+            .delete_token(&clients.account_id().0, &token_id)
+            .await
+            .map_service_error();
+
+        match result {
+            Ok(_) => {
+                log_warn_action(
+                    "Revoked",
+                    format!(
+                        "token {} of profile {}",
+                        token_id.to_string().log_color_highlight(),
+                        profile_name.0.log_color_highlight()
+                    ),
+                );
+                Ok(())
+            }
+            Err(error) => {
+                log_error(format!(
+                    "Failed to revoke token {} of profile {}, the profile was not deleted: {:#}",
+                    token_id.to_string().log_color_error_highlight(),
+                    profile_name.0.log_color_highlight(),
+                    error
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn cmd_migrate(&self, dry_run: bool) -> anyhow::Result<()> {
+        let result = Config::migrate(
+            self.ctx.config_dir(),
+            if dry_run {
+                ConfigMigrationMode::InMemory
+            } else {
+                ConfigMigrationMode::Persist
+            },
+        )?;
+
+        self.ctx
+            .log_handler()
+            .log_view(&ProfileMigrateView::new(result, dry_run));
+
+        Ok(())
+    }
+
+    fn cmd_repair(&self, dry_run: bool) -> anyhow::Result<()> {
+        let result = Config::repair(self.ctx.config_dir(), dry_run)?;
+
+        self.ctx
+            .log_handler()
+            .log_view(&ProfileRepairView::new(result, dry_run));
+
+        Ok(())
+    }
 }
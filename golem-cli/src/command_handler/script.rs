@@ -0,0 +1,114 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{CancelledError, NonSuccessfulExit, TimeoutError};
+use crate::log::{set_log_output, with_output_sink, CapturedOutput, Output};
+use serde_json::{json, Value};
+use std::future::Future;
+use tokio::sync::mpsc;
+
+/// Prepares the process for script mode: stdout is reserved for the result document
+pub fn init_script_mode() {
+    set_log_output(Output::Stderr);
+    colored::control::set_override(false);
+}
+
+/// Runs the command with its views and errors captured, then prints the result or the error
+/// as a single JSON document to stdout. The original result is returned, so exit codes are kept.
+pub async fn run_in_script_mode<F>(command: F) -> anyhow::Result<()>
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let (sink_tx, mut sink_rx) = mpsc::unbounded_channel::<CapturedOutput>();
+
+    let mut views = vec![];
+    let mut events = vec![];
+    let mut errors = vec![];
+    let mut handle_output = |output: CapturedOutput| match output {
+        CapturedOutput::View(view) => views.push(view),
+        CapturedOutput::StreamEvent(event) => events.push(event),
+        CapturedOutput::Error(error) => errors.push(error),
+    };
+
+    let command = with_output_sink(sink_tx, command);
+    tokio::pin!(command);
+
+    let result = loop {
+        tokio::select! {
+            result = &mut command => break result,
+            Some(output) = sink_rx.recv() => handle_output(output),
+        }
+    };
+    while let Ok(output) = sink_rx.try_recv() {
+        handle_output(output);
+    }
+
+    let document = match &result {
+        Ok(()) => {
+            let mut document = json!({
+                "success": true,
+                "result": match views.len() {
+                    0 => Value::Null,
+                    1 => views.pop().unwrap(),
+                    _ => Value::Array(views),
+                },
+            });
+            if !events.is_empty() {
+                document["events"] = Value::Array(events);
+            }
+            document
+        }
+        Err(error) => {
+            let message = if error.downcast_ref::<NonSuccessfulExit>().is_some() {
+                if errors.is_empty() {
+                    "Command failed".to_string()
+                } else {
+                    errors.join("\n")
+                }
+            } else {
+                format!("{:#}", error)
+            };
+            script_error_document(error_kind(error), message)
+        }
+    };
+    println!("{}", document);
+
+    result
+}
+
+/// Prints the error document for errors which happen before running the command,
+/// e.g. invalid arguments
+pub fn print_script_error(kind: &str, message: String) {
+    println!("{}", script_error_document(kind, message));
+}
+
+fn error_kind(error: &anyhow::Error) -> &'static str {
+    if error.downcast_ref::<CancelledError>().is_some() {
+        "cancelled"
+    } else if error.downcast_ref::<TimeoutError>().is_some() {
+        "timeout"
+    } else {
+        "failed"
+    }
+}
+
+fn script_error_document(kind: &str, message: String) -> Value {
+    json!({
+        "success": false,
+        "error": {
+            "kind": kind,
+            "message": message.trim(),
+        },
+    })
+}
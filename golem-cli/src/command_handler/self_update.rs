@@ -0,0 +1,119 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::context::Context;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::UpdateChannel;
+use crate::self_update::{
+    asset_name, binary_name, download_verified, find_release, is_newer, parse_version,
+    replace_executable,
+};
+use crate::{build, version};
+use anyhow::{anyhow, Context as AnyhowContext};
+use std::sync::Arc;
+
+pub struct SelfUpdateCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl SelfUpdateCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(
+        &self,
+        channel: UpdateChannel,
+        version: Option<String>,
+        check: bool,
+    ) -> anyhow::Result<()> {
+        let requested_version = version
+            .as_deref()
+            .map(parse_version)
+            .transpose()
+            .context("Invalid --version")?;
+
+        let client = self.ctx.external_download_client()?;
+
+        let release = find_release(&client, channel, requested_version.as_ref()).await?;
+        let release_version = release.version()?;
+        let current_version = version();
+        let newer = is_newer(current_version, &release_version);
+
+        log_action(
+            "Found",
+            format!(
+                "{} release {}, current version is {}",
+                channel,
+                release.tag_name.log_color_highlight(),
+                current_version.log_color_highlight()
+            ),
+        );
+
+        if check {
+            if newer {
+                log_action(
+                    "Available",
+                    format!(
+                        "update to {}, run {} to install it",
+                        release.tag_name.log_color_ok_highlight(),
+                        "self-update".log_color_highlight()
+                    ),
+                );
+            } else {
+                log_action("Skipping", "update, already up to date");
+            }
+            return Ok(());
+        }
+
+        if requested_version.is_none() && !newer {
+            log_action("Skipping", "update, already up to date");
+            return Ok(());
+        }
+
+        let asset_name = asset_name(binary_name(), build::BUILD_TARGET);
+        let exe = std::env::current_exe()
+            .and_then(|exe| exe.canonicalize())
+            .map_err(|err| anyhow!("Failed to locate the current executable: {err}"))?;
+
+        log_action(
+            "Downloading",
+            format!(
+                "{} from {}",
+                asset_name.log_color_highlight(),
+                release.tag_name
+            ),
+        );
+        let content = {
+            let _indent = LogIndent::new();
+            let content = download_verified(&client, &release, &asset_name).await?;
+            log_action("Verified", "checksum");
+            content
+        };
+
+        replace_executable(&exe, &content)?;
+
+        log_action(
+            "Updated",
+            format!(
+                "{} from {} to {}",
+                exe.display().to_string().log_color_highlight(),
+                current_version,
+                release.tag_name.log_color_ok_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+}
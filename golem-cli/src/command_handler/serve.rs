@@ -0,0 +1,443 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::{GolemCliCommand, GolemCliSubcommand};
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::history;
+use crate::log::{
+    log_action, set_log_output, with_output_sink, CapturedOutput, LogColorize, Output,
+};
+use crate::model::json_rpc::{
+    json_rpc_notification, JsonRpcRequest, JsonRpcResponse, COMMAND_FAILED, INVALID_PARAMS,
+    METHOD_NOT_FOUND, REQUEST_CANCELLED,
+};
+use anyhow::bail;
+use clap::ArgMatches;
+use futures_util::future::{abortable, AbortHandle, Aborted, LocalBoxFuture};
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+const METHOD_COMPONENT_LIST: &str = "component/list";
+const METHOD_WORKER_INVOKE: &str = "worker/invoke";
+const METHOD_WORKER_STREAM_LOGS: &str = "worker/streamLogs";
+const METHOD_APP_DEPLOY: &str = "app/deploy";
+const METHOD_CANCEL_REQUEST: &str = "$/cancelRequest";
+const METHOD_EXIT: &str = "exit";
+
+const NOTIFICATION_WORKER_LOG: &str = "worker/log";
+
+pub struct ServeCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl ServeCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, stdio: bool) -> anyhow::Result<()> {
+        if !stdio {
+            bail!("Only the --stdio transport is supported");
+        }
+        self.cmd_serve_stdio().await
+    }
+
+    async fn cmd_serve_stdio(&self) -> anyhow::Result<()> {
+        // stdout is reserved for the protocol messages, logs are written to stderr
+        set_log_output(Output::Stderr);
+        log_action("Serving", "JSON-RPC requests on stdin");
+
+        let mut stdin = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+
+        let (notifications_tx, mut notifications_rx) = mpsc::unbounded_channel::<Value>();
+        let mut requests = FuturesUnordered::<LocalBoxFuture<'static, (String, Value)>>::new();
+        let mut running_requests = HashMap::<String, AbortHandle>::new();
+        let mut stdin_closed = false;
+
+        loop {
+            if stdin_closed && requests.is_empty() {
+                break;
+            }
+
+            let message = tokio::select! {
+                line = stdin.next_line(), if !stdin_closed => {
+                    match line? {
+                        Some(line) if line.trim().is_empty() => None,
+                        Some(line) => {
+                            match self.handle_message(&line, &notifications_tx) {
+                                Message::Request { key, abort_handle, response } => {
+                                    running_requests.insert(key, abort_handle);
+                                    requests.push(response);
+                                    None
+                                }
+                                Message::Cancel { key } => {
+                                    if let Some(abort_handle) = running_requests.remove(&key) {
+                                        abort_handle.abort();
+                                    }
+                                    None
+                                }
+                                Message::Response(response) => Some(response),
+                                Message::Exit => {
+                                    stdin_closed = true;
+                                    abort_all(&mut running_requests);
+                                    None
+                                }
+                                Message::None => None,
+                            }
+                        }
+                        None => {
+                            stdin_closed = true;
+                            abort_all(&mut running_requests);
+                            None
+                        }
+                    }
+                }
+                Some((key, response)) = requests.next(), if !requests.is_empty() => {
+                    running_requests.remove(&key);
+                    Some(response)
+                }
+                Some(notification) = notifications_rx.recv() => Some(notification),
+            };
+
+            if let Some(message) = message {
+                let mut line = serde_json::to_string(&message)?;
+                line.push('\n');
+                stdout.write_all(line.as_bytes()).await?;
+                stdout.flush().await?;
+            }
+        }
+
+        // Sending the remaining notifications of the finished requests
+        while let Ok(notification) = notifications_rx.try_recv() {
+            let mut line = serde_json::to_string(&notification)?;
+            line.push('\n');
+            stdout.write_all(line.as_bytes()).await?;
+        }
+        stdout.flush().await?;
+
+        Ok(())
+    }
+
+    fn handle_message(&self, line: &str, notifications: &mpsc::UnboundedSender<Value>) -> Message {
+        let request = match JsonRpcRequest::parse(line) {
+            Ok(request) => request,
+            Err(response) => return Message::Response(response),
+        };
+
+        debug!(method = request.method, id = ?request.id, "Serve request");
+
+        match request.method.as_str() {
+            METHOD_EXIT => return Message::Exit,
+            METHOD_CANCEL_REQUEST => {
+                return match request.params::<CancelRequestParams>() {
+                    Ok(params) => Message::Cancel {
+                        key: request_key(&params.id),
+                    },
+                    Err(_) => Message::None,
+                }
+            }
+            _ => {}
+        }
+
+        // Requests without id are notifications, and only the above notifications are supported
+        let Some(id) = request.id.clone() else {
+            return Message::None;
+        };
+
+        let args = match request_args(&request) {
+            Ok(args) => args,
+            Err(error) => return Message::Response(error.into_response(id)),
+        };
+
+        let (subcommand, matches) = match parse_subcommand(args) {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                return Message::Response(JsonRpcResponse::error(id, INVALID_PARAMS, message))
+            }
+        };
+
+        if self.ctx.read_only() && history::mutating_command_path(&matches).is_some() {
+            return Message::Response(JsonRpcResponse::error(
+                id,
+                COMMAND_FAILED,
+                format!("{} is not allowed in read-only mode", request.method),
+            ));
+        }
+
+        let key = request_key(&id);
+        let (response, abort_handle) = abortable(run_request(
+            self.ctx.clone(),
+            id.clone(),
+            subcommand,
+            notifications.clone(),
+        ));
+
+        Message::Request {
+            key: key.clone(),
+            abort_handle,
+            response: async move {
+                let response = match response.await {
+                    Ok(response) => response,
+                    Err(Aborted) => JsonRpcResponse::error(
+                        id,
+                        REQUEST_CANCELLED,
+                        "Request cancelled".to_string(),
+                    ),
+                };
+                (key, response)
+            }
+            .boxed_local(),
+        }
+    }
+}
+
+enum Message {
+    Request {
+        key: String,
+        abort_handle: AbortHandle,
+        response: LocalBoxFuture<'static, (String, Value)>,
+    },
+    Cancel {
+        key: String,
+    },
+    Response(Value),
+    Exit,
+    None,
+}
+
+enum RequestError {
+    MethodNotFound(String),
+    InvalidParams(String),
+}
+
+impl RequestError {
+    fn into_response(self, id: Value) -> Value {
+        match self {
+            RequestError::MethodNotFound(method) => JsonRpcResponse::error(
+                id,
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            ),
+            RequestError::InvalidParams(message) => {
+                JsonRpcResponse::error(id, INVALID_PARAMS, message)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelRequestParams {
+    id: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ComponentListParams {
+    component_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkerInvokeParams {
+    worker_name: String,
+    function: String,
+    #[serde(default)]
+    arguments: Vec<String>,
+    idempotency_key: Option<String>,
+    #[serde(default)]
+    enqueue: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkerStreamLogsParams {
+    worker_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AppDeployParams {
+    #[serde(default)]
+    component_names: Vec<String>,
+    #[serde(default)]
+    force_build: bool,
+    #[serde(default)]
+    await_processing: bool,
+    update_workers: Option<String>,
+    #[serde(default)]
+    redeploy_workers: bool,
+}
+
+fn abort_all(running_requests: &mut HashMap<String, AbortHandle>) {
+    for (_, abort_handle) in running_requests.drain() {
+        abort_handle.abort();
+    }
+}
+
+fn request_key(id: &Value) -> String {
+    id.to_string()
+}
+
+// Requests are mapped to CLI arguments, so they are validated and handled exactly
+// the same way as the matching commands
+fn request_args(request: &JsonRpcRequest) -> Result<Vec<String>, RequestError> {
+    let params_error = RequestError::InvalidParams;
+    match request.method.as_str() {
+        METHOD_COMPONENT_LIST => {
+            let params: ComponentListParams = request.params().map_err(params_error)?;
+            let mut args = vec!["component".to_string(), "list".to_string()];
+            args.extend(params.component_name);
+            Ok(args)
+        }
+        METHOD_WORKER_INVOKE => {
+            let params: WorkerInvokeParams = request.params().map_err(params_error)?;
+            let mut args = vec!["worker".to_string(), "invoke".to_string()];
+            if params.enqueue {
+                args.push("--enqueue".to_string());
+            }
+            if let Some(idempotency_key) = params.idempotency_key {
+                args.push("--idempotency-key".to_string());
+                args.push(idempotency_key);
+            }
+            args.push(params.worker_name);
+            args.push(params.function);
+            // Arguments can start with '-', e.g. negative numbers
+            args.push("--".to_string());
+            args.extend(params.arguments);
+            Ok(args)
+        }
+        METHOD_WORKER_STREAM_LOGS => {
+            let params: WorkerStreamLogsParams = request.params().map_err(params_error)?;
+            Ok(vec![
+                "worker".to_string(),
+                "stream".to_string(),
+                params.worker_name,
+            ])
+        }
+        METHOD_APP_DEPLOY => {
+            let params: AppDeployParams = request.params().map_err(params_error)?;
+            let mut args = vec!["app".to_string(), "deploy".to_string()];
+            if params.force_build {
+                args.push("--force-build".to_string());
+            }
+            if params.await_processing {
+                args.push("--await-processing".to_string());
+            }
+            if let Some(update_mode) = params.update_workers {
+                args.push(format!("--update-workers={}", update_mode));
+            }
+            if params.redeploy_workers {
+                args.push("--redeploy-workers".to_string());
+            }
+            args.extend(params.component_names);
+            Ok(args)
+        }
+        other => Err(RequestError::MethodNotFound(other.to_string())),
+    }
+}
+
+fn parse_subcommand(args: Vec<String>) -> Result<(GolemCliSubcommand, ArgMatches), String> {
+    GolemCliCommand::try_parse_with_matches(std::iter::once("golem".to_string()).chain(args))
+        .map(|(command, matches)| (command.subcommand, matches))
+        .map_err(|error| format!("Invalid params: {}", error.render()))
+}
+
+async fn run_request(
+    ctx: Arc<Context>,
+    id: Value,
+    subcommand: GolemCliSubcommand,
+    notifications: mpsc::UnboundedSender<Value>,
+) -> Value {
+    let (sink_tx, mut sink_rx) = mpsc::unbounded_channel::<CapturedOutput>();
+
+    let mut views = vec![];
+    let mut errors = vec![];
+    let mut handle_output = |output: CapturedOutput| match output {
+        CapturedOutput::View(view) => views.push(view),
+        CapturedOutput::StreamEvent(event) => {
+            let _ = notifications.send(json_rpc_notification(
+                NOTIFICATION_WORKER_LOG,
+                json!({
+                    "requestId": id,
+                    "event": event,
+                }),
+            ));
+        }
+        CapturedOutput::Error(error) => errors.push(error),
+    };
+
+    let command = with_output_sink(sink_tx, run_subcommand(ctx, subcommand));
+    tokio::pin!(command);
+
+    let result = loop {
+        tokio::select! {
+            result = &mut command => break result,
+            Some(output) = sink_rx.recv() => handle_output(output),
+        }
+    };
+    while let Ok(output) = sink_rx.try_recv() {
+        handle_output(output);
+    }
+
+    match result {
+        Ok(()) => JsonRpcResponse::result(
+            id,
+            match views.len() {
+                0 => Value::Null,
+                1 => views.pop().unwrap(),
+                _ => Value::Array(views),
+            },
+        ),
+        Err(error) => {
+            let message = if error.downcast_ref::<NonSuccessfulExit>().is_some() {
+                if errors.is_empty() {
+                    "Command failed".to_string()
+                } else {
+                    errors.join("\n")
+                }
+            } else {
+                format!("{:#}", error)
+            };
+            JsonRpcResponse::error(id, COMMAND_FAILED, message)
+        }
+    }
+}
+
+async fn run_subcommand(ctx: Arc<Context>, subcommand: GolemCliSubcommand) -> anyhow::Result<()> {
+    match subcommand {
+        GolemCliSubcommand::App { subcommand } => {
+            ctx.app_handler().handle_command(subcommand).await
+        }
+        GolemCliSubcommand::Component { subcommand } => {
+            ctx.component_handler().handle_command(subcommand).await
+        }
+        GolemCliSubcommand::Worker { subcommand } => {
+            ctx.worker_handler().handle_command(subcommand).await
+        }
+        _ => bail!(
+            "Command is not supported in {} mode",
+            "serve".log_color_highlight()
+        ),
+    }
+}
@@ -14,47 +14,65 @@
 
 use crate::cloud::AccountId;
 use crate::command::shared_args::{
-    NewWorkerArgument, StreamArgs, WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
+    NewWorkerArgument, ProjectNameOptionalArg, StreamArgs, WorkerFunctionArgument,
+    WorkerFunctionName, WorkerNameArg,
 };
 use crate::command::worker::WorkerSubcommand;
 use crate::command_handler::Handlers;
+use crate::command_name;
 use crate::connect_output::ConnectOutput;
 use crate::context::{Context, GolemClients};
 use crate::error::service::{AnyhowMapServiceError, ServiceError};
-use crate::error::NonSuccessfulExit;
+use crate::error::{CancelledError, NonSuccessfulExit, TimeoutError};
+use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
 use crate::log::{log_action, log_error_action, log_warn_action, logln, LogColorize, LogIndent};
-use crate::model::app::ApplicationComponentSelectMode;
+use crate::model::api_definition::{rib_script_calls_function, try_api_route};
+use crate::model::app::{AppComponentName, ApplicationComponentSelectMode};
+use crate::model::app_secrets::resolve_env_secrets;
+use crate::model::bench::InvocationBenchmark;
 use crate::model::component::{
-    function_params_types, show_exported_functions, AppComponentType, Component,
+    exported_functions, format_function_name, function_params, function_params_types,
+    show_exported_functions, AppComponentType, Component, InlineResourceCall,
+};
+use crate::model::deploy::{
+    TryUpdateAllWorkersResult, WorkerUpdateAttempt, WorkerUpdateRolloutResult,
+};
+use crate::model::invocation_import::{
+    json_to_wave, parse_har, parse_postman_collection, rib_function_arguments, rib_worker_name,
+    ImportedInvocation, RecordedHttpRequest,
 };
-use crate::model::deploy::{TryUpdateAllWorkersResult, WorkerUpdateAttempt};
 use crate::model::invoke_result_view::InvokeResultView;
+use crate::model::payload::PayloadKind;
 use crate::model::text::fmt::{
-    format_export, format_worker_name_match, log_error, log_fuzzy_match, log_text_view, log_warn,
+    format_export, format_status, format_worker_name_match, log_error, log_fuzzy_match,
+    log_text_view, log_warn,
 };
 use crate::model::text::help::{
     ArgumentError, AvailableComponentNamesHelp, AvailableFunctionNamesHelp, ComponentNameHelp,
     ParameterErrorTableView, WorkerNameHelp,
 };
-use crate::model::text::worker::{WorkerCreateView, WorkerGetView};
+use crate::model::text::worker::{WorkerCreateView, WorkerGetView, WorkerInvokeCurlView};
 use crate::model::to_oss::ToOss;
 use crate::model::{
-    ComponentName, ComponentNameMatchKind, Format, IdempotencyKey, ProjectName,
-    WorkerConnectOptions, WorkerMetadata, WorkerMetadataView, WorkerName, WorkerNameMatch,
-    WorkerUpdateMode, WorkersMetadataResponseView,
+    ApiDefinitionId, ApiDefinitionVersion, ComponentName, ComponentNameMatchKind, IdempotencyKey,
+    InvokeTimeoutAction, ProjectName, ProjectNameAndId, WorkerConnectOptions,
+    WorkerFunctionFileArgument, WorkerLabel, WorkerListColumn, WorkerLogFilter, WorkerMetadata,
+    WorkerMetadataView, WorkerName, WorkerNameMatch, WorkerUpdateMode, WorkerUrn,
+    WorkersMetadataResponseView,
 };
 use anyhow::{anyhow, bail, Context as AnyhowContext};
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use colored::Colorize;
 use futures_util::{future, pin_mut, SinkExt, StreamExt};
 use golem_client::api::WorkerClient as WorkerClientOss;
 use golem_client::model::{
     InvokeParameters as InvokeParametersOss, PublicOplogEntry,
     RevertLastInvocations as RevertLastInvocationsOss, RevertToOplogIndex as RevertToOplogIndexOss,
-    RevertWorkerTarget as RevertWorkerTargetOss, ScanCursor,
+    RevertWorkerTarget as RevertWorkerTargetOss, ScanCursor, UpdateRecord,
     UpdateWorkerRequest as UpdateWorkerRequestOss,
-    WorkerCreationRequest as WorkerCreationRequestOss,
+    WorkerCreationRequest as WorkerCreationRequestOss, WorkerStatus,
 };
 use golem_cloud_client::api::WorkerClient as WorkerClientCloud;
 use golem_cloud_client::model::{
@@ -63,16 +81,22 @@ use golem_cloud_client::model::{
     UpdateWorkerRequest as UpdateWorkerRequestCloud,
     WorkerCreationRequest as WorkerCreationRequestCloud,
 };
+use golem_common::model::oplog::LogLevel as OplogLogLevel;
 use golem_common::model::public_oplog::OplogCursor;
-use golem_common::model::WorkerEvent;
+use golem_common::model::{LogLevel, WorkerEvent};
+use golem_wasm_ast::analysis::{AnalysedType, TypeList};
 use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
 use golem_wasm_rpc::parse_type_annotated_value;
 use itertools::{EitherOrBoth, Itertools};
 use native_tls::TlsConnector;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::task::JoinHandle;
+use tokio::sync::Notify;
+use tokio::task::{AbortHandle, JoinHandle};
 use tokio::{task, time};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::Message;
@@ -81,6 +105,12 @@ use tracing::{debug, error, info, trace};
 use url::Url;
 use uuid::Uuid;
 
+// Maximum number of components searched concurrently by worker list --all-components
+const LIST_ALL_COMPONENTS_CONCURRENCY: usize = 8;
+const WORKER_READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const INVOKE_STREAM_CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+const INVOKE_STREAM_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct WorkerCommandHandler {
     ctx: Arc<Context>,
 }
@@ -95,71 +125,244 @@ impl WorkerCommandHandler {
             WorkerSubcommand::New {
                 worker_name,
                 arguments,
-                env,
-            } => self.cmd_new(worker_name, arguments, env).await,
+                mut env,
+                label,
+                wait_until_ready,
+                ready_timeout,
+                output,
+            } => {
+                env.extend(label.iter().map(WorkerLabel::env_var));
+                self.cmd_new(
+                    worker_name,
+                    arguments,
+                    env,
+                    wait_until_ready.then(|| Duration::from_secs(ready_timeout)),
+                    output.field("worker_name"),
+                )
+                .await
+            }
             WorkerSubcommand::Invoke {
                 worker_name,
                 function_name,
                 arguments,
+                named_arguments,
+                file_args,
+                interactive,
                 enqueue,
                 idempotency_key,
                 stream,
                 stream_args,
+                timeout,
+                on_timeout,
+                raw,
             } => {
                 self.cmd_invoke(
                     worker_name,
-                    &function_name,
-                    arguments,
+                    function_name,
+                    merge_arguments(arguments, named_arguments),
+                    file_args,
+                    interactive,
                     enqueue,
                     idempotency_key,
                     stream,
                     stream_args,
+                    timeout.map(Duration::from_secs),
+                    on_timeout,
+                    raw,
+                )
+                .await
+            }
+            WorkerSubcommand::Curl {
+                worker_name,
+                function_name,
+                arguments,
+                named_arguments,
+                file_args,
+                enqueue,
+                idempotency_key,
+                show_token,
+                execute,
+            } => {
+                self.cmd_curl(
+                    worker_name,
+                    &function_name,
+                    merge_arguments(arguments, named_arguments),
+                    file_args,
+                    enqueue,
+                    idempotency_key,
+                    show_token,
+                    execute,
                 )
                 .await
             }
-            WorkerSubcommand::Get { worker_name } => self.cmd_get(worker_name).await,
+            WorkerSubcommand::Get {
+                worker_name,
+                output,
+            } => self.cmd_get(worker_name, output.field("worker_name")).await,
             WorkerSubcommand::Delete { worker_name } => self.cmd_delete(worker_name).await,
+            WorkerSubcommand::DeleteMany {
+                component_name,
+                filter: filters,
+                selector,
+                all: _,
+                dry_run,
+            } => {
+                self.cmd_delete_many(
+                    component_name.component_name,
+                    filters_with_selectors(filters, &selector),
+                    dry_run,
+                )
+                .await
+            }
+            WorkerSubcommand::List {
+                all_components: true,
+                project,
+                filter: filters,
+                selector,
+                max_count,
+                precise,
+                column: columns,
+                ..
+            } => {
+                self.cmd_list_all_components(
+                    project,
+                    filters_with_selectors(filters, &selector),
+                    max_count,
+                    precise,
+                    columns,
+                )
+                .await
+            }
             WorkerSubcommand::List {
                 component_name,
+                all_components: false,
+                project: _,
                 filter: filters,
+                selector,
                 scan_cursor,
                 max_count,
                 precise,
+                column: columns,
             } => {
                 self.cmd_list(
                     component_name.component_name,
-                    filters,
+                    filters_with_selectors(filters, &selector),
                     scan_cursor,
                     max_count,
                     precise,
+                    columns,
                 )
                 .await
             }
+            WorkerSubcommand::Backlog {
+                component_name,
+                filter: filters,
+                limit,
+                all,
+            } => {
+                self.cmd_backlog(component_name.component_name, filters, limit, all)
+                    .await
+            }
             WorkerSubcommand::Stream {
                 worker_name,
+                since,
                 stream_args,
-            } => self.cmd_stream(worker_name, stream_args).await,
+            } => self.cmd_stream(worker_name, since, stream_args).await,
+            WorkerSubcommand::Logs {
+                worker_name,
+                follow,
+                since,
+                level,
+                stream,
+                stream_args,
+            } => {
+                self.cmd_logs(
+                    worker_name,
+                    follow,
+                    WorkerLogFilter {
+                        since,
+                        min_level: level,
+                        streams: stream,
+                    },
+                    stream_args,
+                )
+                .await
+            }
+            WorkerSubcommand::Monitor {
+                component_name,
+                filter: filters,
+                selector,
+                interval,
+                on_failure,
+                once,
+            } => {
+                self.cmd_monitor(
+                    component_name.component_name,
+                    filters_with_selectors(filters, &selector),
+                    Duration::from_secs(interval),
+                    on_failure,
+                    once,
+                )
+                .await
+            }
+            WorkerSubcommand::SetEnv {
+                worker_name,
+                env,
+                restart,
+            } => self.cmd_set_env(worker_name, env, restart).await,
             WorkerSubcommand::Interrupt { worker_name } => self.cmd_interrupt(worker_name).await,
             WorkerSubcommand::Update {
                 worker_name,
                 mode,
                 target_version,
             } => self.cmd_update(worker_name, mode, target_version).await,
+            WorkerSubcommand::UpdateMany {
+                component_name,
+                filter: filters,
+                selector,
+                update_mode,
+                target_version,
+                batch_size,
+                max_unavailable,
+                await_update,
+                await_timeout,
+                max_failure_ratio,
+                rollback_on_failure,
+            } => {
+                self.cmd_update_many(
+                    component_name.component_name,
+                    filters_with_selectors(filters, &selector),
+                    update_mode,
+                    target_version,
+                    WorkerUpdateRolloutOptions {
+                        batch_size: max_unavailable
+                            .map_or(batch_size, |max_unavailable| {
+                                batch_size.min(max_unavailable)
+                            })
+                            .max(1),
+                        await_timeout: await_update.then(|| Duration::from_secs(await_timeout)),
+                        max_failure_ratio,
+                        rollback_on_failure,
+                    },
+                )
+                .await
+            }
             WorkerSubcommand::Resume { worker_name } => self.cmd_resume(worker_name).await,
             WorkerSubcommand::SimulateCrash { worker_name } => {
                 self.cmd_simulate_crash(worker_name).await
             }
             WorkerSubcommand::Oplog {
                 worker_name,
-                from,
+                from_index,
+                count,
                 query,
-            } => self.cmd_oplog(worker_name, from, query).await,
+            } => self.cmd_oplog(worker_name, from_index, count, query).await,
             WorkerSubcommand::Revert {
                 worker_name,
-                last_oplog_index,
-                number_of_invocations,
+                to_oplog_index,
+                undo_invocations,
             } => {
-                self.cmd_revert(worker_name, last_oplog_index, number_of_invocations)
+                self.cmd_revert(worker_name, to_oplog_index, undo_invocations)
                     .await
             }
             WorkerSubcommand::CancelInvocation {
@@ -169,6 +372,22 @@ impl WorkerCommandHandler {
                 self.cmd_cancel_invocation(worker_name, idempotency_key)
                     .await
             }
+            WorkerSubcommand::ImportInvocations {
+                project,
+                postman,
+                har,
+                definition_id,
+                definition_version,
+            } => {
+                self.cmd_import_invocations(
+                    project,
+                    postman,
+                    har,
+                    definition_id,
+                    definition_version,
+                )
+                .await
+            }
         }
     }
 
@@ -177,8 +396,11 @@ impl WorkerCommandHandler {
         worker_name: WorkerNameArg,
         arguments: Vec<NewWorkerArgument>,
         env: Vec<(String, String)>,
+        ready_timeout: Option<Duration>,
+        output_field: Option<&str>,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
+        self.ctx.log_handler().prepare_output_field(output_field);
 
         let worker_name = worker_name.worker_name;
         let mut worker_name_match = self.match_worker_name(worker_name).await?;
@@ -216,58 +438,60 @@ impl WorkerCommandHandler {
             ),
         );
 
+        let worker_env = with_app_env(
+            self.app_component_env(&worker_name_match.component_name)
+                .await?,
+            env,
+        );
+
         self.new_worker(
             component.versioned_component_id.component_id,
             worker_name.clone(),
             arguments,
-            env.into_iter().collect(),
+            worker_env,
         )
         .await?;
 
-        logln("");
-        self.ctx.log_handler().log_view(&WorkerCreateView {
-            component_name: worker_name_match.component_name,
-            worker_name: Some(worker_name.into()),
-        });
+        if let Some(ready_timeout) = ready_timeout {
+            self.await_worker_ready(
+                component.versioned_component_id.component_id,
+                &worker_name_match.component_name,
+                &worker_name.clone().into(),
+                ready_timeout,
+            )
+            .await?;
+        }
 
-        Ok(())
+        if output_field.is_none() {
+            logln("");
+        }
+        self.ctx.log_handler().log_view_field(
+            &WorkerCreateView {
+                component_name: worker_name_match.component_name,
+                worker_name: Some(worker_name.into()),
+            },
+            output_field,
+        )
     }
 
     async fn cmd_invoke(
         &mut self,
         worker_name: WorkerNameArg,
-        function_name: &WorkerFunctionName,
+        function_name: Option<WorkerFunctionName>,
         arguments: Vec<WorkerFunctionArgument>,
+        file_args: Vec<WorkerFunctionFileArgument>,
+        interactive: bool,
         enqueue: bool,
         idempotency_key: Option<IdempotencyKey>,
         stream: bool,
         stream_args: StreamArgs,
+        timeout: Option<Duration>,
+        on_timeout: Option<InvokeTimeoutAction>,
+        raw: bool,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
-        fn new_idempotency_key() -> IdempotencyKey {
-            let key = IdempotencyKey::new();
-            log_action(
-                "Using",
-                format!("generated idempotency key: {}", key.0.log_color_highlight()),
-            );
-            key
-        }
-
-        let idempotency_key = match idempotency_key {
-            Some(idempotency_key) if idempotency_key.0 == "-" => new_idempotency_key(),
-            Some(idempotency_key) => {
-                log_action(
-                    "Using",
-                    format!(
-                        "requested idempotency key: {}",
-                        idempotency_key.0.log_color_highlight()
-                    ),
-                );
-                idempotency_key
-            }
-            None => new_idempotency_key(),
-        };
+        let idempotency_key = resolve_idempotency_key(idempotency_key);
 
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
 
@@ -282,52 +506,40 @@ impl WorkerCommandHandler {
             )
             .await?;
 
-        let component_functions = show_exported_functions(&component.metadata.exports);
-        let fuzzy_search = FuzzySearch::new(component_functions.iter().map(|s| s.as_str()));
-        let function_name = match fuzzy_search.find(function_name) {
-            Ok(match_) => {
-                log_fuzzy_match(&match_);
-                match_.option
-            }
-            Err(error) => match error {
-                Error::Ambiguous {
-                    highlighted_options,
-                    ..
-                } => {
-                    logln("");
-                    log_error(format!(
-                        "The requested function name ({}) is ambiguous.",
-                        function_name.log_color_error_highlight()
-                    ));
-                    logln("");
-                    logln("Did you mean one of");
-                    for option in highlighted_options {
-                        logln(format!(" - {}", option.bold()));
-                    }
-                    logln("?");
-                    logln("");
-                    log_text_view(&AvailableFunctionNamesHelp {
-                        component_name: worker_name_match.component_name.0,
-                        function_names: component_functions,
-                    });
-
-                    bail!(NonSuccessfulExit);
-                }
-                Error::NotFound { .. } => {
-                    logln("");
-                    log_error(format!(
-                        "The requested function name ({}) was not found.",
-                        function_name.log_color_error_highlight()
-                    ));
-                    logln("");
-                    log_text_view(&AvailableFunctionNamesHelp {
-                        component_name: worker_name_match.component_name.0,
-                        function_names: component_functions,
-                    });
+        let function_name = match function_name {
+            Some(function_name) => self.match_function_name(
+                &worker_name_match.component_name,
+                &component,
+                &function_name,
+            )?,
+            None => self
+                .ctx
+                .interactive_handler()
+                .select_function(&component.metadata.exports)?,
+        };
 
-                    bail!(NonSuccessfulExit);
-                }
-            },
+        let arguments = if interactive {
+            let arguments = self
+                .ctx
+                .interactive_handler()
+                .prompt_function_arguments(&function_params(&component, &function_name)?)?;
+            log_action(
+                "Collected",
+                format!(
+                    "arguments, the equivalent invoke arguments are: {}",
+                    shlex::try_join(
+                        std::iter::once(function_name.as_str()).chain(
+                            arguments
+                                .iter()
+                                .flat_map(|argument| ["--arg", argument.as_str()])
+                        )
+                    )?
+                    .log_color_highlight()
+                ),
+            );
+            arguments
+        } else {
+            arguments
         };
 
         if enqueue {
@@ -350,52 +562,50 @@ impl WorkerCommandHandler {
             );
         }
 
+        let arguments = insert_file_args(&component, &function_name, arguments, file_args).await?;
         let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
 
-        let connect_handle = match worker_name_match.worker_name.clone() {
-            Some(worker_name) => {
-                if stream {
-                    let connection = connect_to_worker(
-                        self.ctx.worker_service_url().clone(),
-                        self.ctx.auth_token().await?,
-                        component.versioned_component_id.component_id,
-                        worker_name.0,
-                        stream_args.into(),
-                        self.ctx.allow_insecure(),
-                        self.ctx.format(),
-                    )
-                    .await?;
-                    Some(tokio::task::spawn(async move {
-                        connection.read_messages().await
-                    }))
-                } else {
-                    None
-                }
+        if !self.ctx.interactive_handler().confirm_payload_size(
+            PayloadKind::InvokeParameters,
+            serde_json::to_vec(&arguments)?.len() as u64,
+        )? {
+            bail!(NonSuccessfulExit);
+        }
+
+        let invocation_finished = Arc::new(Notify::new());
+        let stream_output = stream.then(|| {
+            ConnectOutput::new(stream_args.into(), self.ctx.format())
+                .with_awaited_invocation(idempotency_key.0.clone(), invocation_finished.clone())
+        });
+        let connect_handle = match (&worker_name_match.worker_name, &stream_output) {
+            (Some(worker_name), Some(output)) => {
+                connect_to_invoked_worker(
+                    self.ctx.worker_service_url().clone(),
+                    self.ctx.auth_token().await?,
+                    component.versioned_component_id.component_id,
+                    worker_name.0.clone(),
+                    output.clone(),
+                    self.ctx.allow_insecure(),
+                )
+                .await
             }
-            None => None,
+            (None, Some(_)) => {
+                log_warn(
+                    "Streaming is not available for workers without a name, invoking without it",
+                );
+                None
+            }
+            (_, None) => None,
         };
 
-        let result = match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => match &worker_name_match.worker_name {
-                Some(worker_name) => {
-                    if enqueue {
-                        clients
-                            .worker
-                            .invoke_function(
-                                &component.versioned_component_id.component_id,
-                                &worker_name.0,
-                                Some(&idempotency_key.0),
-                                function_name.as_str(),
-                                &InvokeParametersOss { params: arguments },
-                            )
-                            .await
-                            .map_service_error()?;
-                        None
-                    } else {
-                        Some(
+        let invoke = async {
+            let result = match self.ctx.golem_clients().await? {
+                GolemClients::Oss(clients) => match &worker_name_match.worker_name {
+                    Some(worker_name) => {
+                        if enqueue {
                             clients
-                                .worker_invoke
-                                .invoke_and_await_function(
+                                .worker
+                                .invoke_function(
                                     &component.versioned_component_id.component_id,
                                     &worker_name.0,
                                     Some(&idempotency_key.0),
@@ -403,59 +613,59 @@ impl WorkerCommandHandler {
                                     &InvokeParametersOss { params: arguments },
                                 )
                                 .await
-                                .map_service_error()?,
-                        )
-                    }
-                }
-                None => {
-                    if enqueue {
-                        clients
-                            .worker
-                            .invoke_function_without_name(
-                                &component.versioned_component_id.component_id,
-                                Some(&idempotency_key.0),
-                                function_name.as_str(),
-                                &InvokeParametersOss { params: arguments },
+                                .map_service_error()?;
+                            None
+                        } else {
+                            Some(
+                                clients
+                                    .worker_invoke
+                                    .invoke_and_await_function(
+                                        &component.versioned_component_id.component_id,
+                                        &worker_name.0,
+                                        Some(&idempotency_key.0),
+                                        function_name.as_str(),
+                                        &InvokeParametersOss { params: arguments },
+                                    )
+                                    .await
+                                    .map_service_error()?,
                             )
-                            .await
-                            .map_service_error()?;
-                        None
-                    } else {
-                        Some(
+                        }
+                    }
+                    None => {
+                        if enqueue {
                             clients
-                                .worker_invoke
-                                .invoke_and_await_function_without_name(
+                                .worker
+                                .invoke_function_without_name(
                                     &component.versioned_component_id.component_id,
                                     Some(&idempotency_key.0),
                                     function_name.as_str(),
                                     &InvokeParametersOss { params: arguments },
                                 )
                                 .await
-                                .map_service_error()?,
-                        )
-                    }
-                }
-            },
-            GolemClients::Cloud(clients) => match worker_name_match.worker_name {
-                Some(worker_name) => {
-                    if enqueue {
-                        clients
-                            .worker
-                            .invoke_function(
-                                &component.versioned_component_id.component_id,
-                                &worker_name.0,
-                                Some(&idempotency_key.0),
-                                function_name.as_str(),
-                                &InvokeParametersCloud { params: arguments },
+                                .map_service_error()?;
+                            None
+                        } else {
+                            Some(
+                                clients
+                                    .worker_invoke
+                                    .invoke_and_await_function_without_name(
+                                        &component.versioned_component_id.component_id,
+                                        Some(&idempotency_key.0),
+                                        function_name.as_str(),
+                                        &InvokeParametersOss { params: arguments },
+                                    )
+                                    .await
+                                    .map_service_error()?,
                             )
-                            .await
-                            .map_service_error()?;
-                        None
-                    } else {
-                        Some(
+                        }
+                    }
+                },
+                GolemClients::Cloud(clients) => match &worker_name_match.worker_name {
+                    Some(worker_name) => {
+                        if enqueue {
                             clients
-                                .worker_invoke
-                                .invoke_and_await_function(
+                                .worker
+                                .invoke_function(
                                     &component.versioned_component_id.component_id,
                                     &worker_name.0,
                                     Some(&idempotency_key.0),
@@ -463,55 +673,109 @@ impl WorkerCommandHandler {
                                     &InvokeParametersCloud { params: arguments },
                                 )
                                 .await
-                                .map_service_error()?,
-                        )
-                    }
-                }
-                None => {
-                    if enqueue {
-                        clients
-                            .worker
-                            .invoke_function_without_name(
-                                &component.versioned_component_id.component_id,
-                                Some(&idempotency_key.0),
-                                function_name.as_str(),
-                                &InvokeParametersCloud { params: arguments },
+                                .map_service_error()?;
+                            None
+                        } else {
+                            Some(
+                                clients
+                                    .worker_invoke
+                                    .invoke_and_await_function(
+                                        &component.versioned_component_id.component_id,
+                                        &worker_name.0,
+                                        Some(&idempotency_key.0),
+                                        function_name.as_str(),
+                                        &InvokeParametersCloud { params: arguments },
+                                    )
+                                    .await
+                                    .map_service_error()?,
                             )
-                            .await
-                            .map_service_error()?;
-                        None
-                    } else {
-                        Some(
+                        }
+                    }
+                    None => {
+                        if enqueue {
                             clients
-                                .worker_invoke
-                                .invoke_and_await_function_without_name(
+                                .worker
+                                .invoke_function_without_name(
                                     &component.versioned_component_id.component_id,
                                     Some(&idempotency_key.0),
                                     function_name.as_str(),
                                     &InvokeParametersCloud { params: arguments },
                                 )
                                 .await
-                                .map_service_error()?,
-                        )
+                                .map_service_error()?;
+                            None
+                        } else {
+                            Some(
+                                clients
+                                    .worker_invoke
+                                    .invoke_and_await_function_without_name(
+                                        &component.versioned_component_id.component_id,
+                                        Some(&idempotency_key.0),
+                                        function_name.as_str(),
+                                        &InvokeParametersCloud { params: arguments },
+                                    )
+                                    .await
+                                    .map_service_error()?,
+                            )
+                        }
                     }
                 }
-            }
-            .to_oss(),
+                .to_oss(),
+            };
+            Ok::<_, anyhow::Error>(result)
+        };
+
+        let result = match timeout {
+            Some(timeout) => match time::timeout(timeout, invoke).await {
+                Ok(result) => result?,
+                Err(_) => {
+                    connect_handle.iter().for_each(|handle| handle.abort());
+                    self.handle_invoke_timeout(
+                        &component,
+                        &worker_name_match,
+                        &idempotency_key,
+                        timeout,
+                        on_timeout,
+                    )
+                    .await?;
+                    bail!(TimeoutError);
+                }
+            },
+            None => invoke.await?,
         };
 
-        connect_handle.iter().for_each(|handle| handle.abort());
+        if let Some(mut handle) = connect_handle {
+            // Events emitted right before the result can still be in flight, so the stream is
+            // drained until the end of the invocation is received or the connection is closed
+            let drained = time::timeout(INVOKE_STREAM_DRAIN_TIMEOUT, async {
+                tokio::select! {
+                    _ = invocation_finished.notified() => {}
+                    _ = &mut handle => {}
+                }
+            })
+            .await;
+            if drained.is_err() {
+                log_warn("Stopped streaming before receiving the end of the invocation, the output may be incomplete");
+            }
+            handle.abort();
+        }
+        if let Some(output) = &stream_output {
+            output.flush().await;
+        }
 
         match result {
             Some(result) => {
                 logln("");
-                self.ctx
-                    .log_handler()
-                    .log_view(&InvokeResultView::new_invoke(
+                self.ctx.log_handler().log_view(&if raw {
+                    InvokeResultView::new_invoke_raw(idempotency_key, result)
+                } else {
+                    InvokeResultView::new_invoke(
                         idempotency_key,
                         result,
                         &component,
                         function_name.as_str(),
-                    ));
+                    )
+                });
             }
             None => {
                 log_action("Enqueued", "invocation");
@@ -524,32 +788,433 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
-    async fn cmd_stream(
+    async fn handle_invoke_timeout(
         &mut self,
-        worker_name: WorkerNameArg,
-        stream_args: StreamArgs,
+        component: &Component,
+        worker_name_match: &WorkerNameMatch,
+        idempotency_key: &IdempotencyKey,
+        timeout: Duration,
+        on_timeout: Option<InvokeTimeoutAction>,
     ) -> anyhow::Result<()> {
-        self.ctx.silence_app_context_init().await;
-        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
-        let (component, worker_name) = self
-            .component_by_worker_name_match(&worker_name_match)
-            .await?;
+        logln("");
+        log_error(format!(
+            "Invocation timed out after {} seconds, idempotency key: {}",
+            timeout.as_secs().to_string().log_color_highlight(),
+            idempotency_key.0.log_color_highlight()
+        ));
 
-        log_action(
-            "Connecting",
-            format!("to worker {}", format_worker_name_match(&worker_name_match)),
-        );
+        let Some(on_timeout) = on_timeout else {
+            return Ok(());
+        };
 
-        let connection = connect_to_worker(
-            self.ctx.worker_service_url().clone(),
-            self.ctx.auth_token().await?,
-            component.versioned_component_id.component_id,
-            worker_name.0.clone(),
-            stream_args.into(),
-            self.ctx.allow_insecure(),
-            self.ctx.format(),
-        )
-        .await?;
+        let Some(worker_name) = &worker_name_match.worker_name else {
+            log_warn_action(
+                "Skipping",
+                format!("{} on timeout, the worker has no name", on_timeout),
+            );
+            return Ok(());
+        };
+
+        match on_timeout {
+            InvokeTimeoutAction::Cancel => {
+                log_warn_action(
+                    "Canceling",
+                    format!(
+                        "invocation for worker {}",
+                        format_worker_name_match(worker_name_match)
+                    ),
+                );
+                if self
+                    .cancel_invocation(component, worker_name, idempotency_key)
+                    .await?
+                {
+                    log_action("Canceled", "invocation");
+                } else {
+                    log_warn_action("Failed", "to cancel, invocation already started");
+                }
+            }
+            InvokeTimeoutAction::Interrupt => {
+                log_warn_action(
+                    "Interrupting",
+                    format!("worker {}", format_worker_name_match(worker_name_match)),
+                );
+                self.interrupt_worker(component, worker_name, false).await?;
+                log_action(
+                    "Interrupted",
+                    format!("worker {}", format_worker_name_match(worker_name_match)),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_curl(
+        &mut self,
+        worker_name: WorkerNameArg,
+        function_name: &WorkerFunctionName,
+        arguments: Vec<WorkerFunctionArgument>,
+        file_args: Vec<WorkerFunctionFileArgument>,
+        enqueue: bool,
+        idempotency_key: Option<IdempotencyKey>,
+        show_token: bool,
+        execute: bool,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+
+        let idempotency_key = resolve_idempotency_key(idempotency_key);
+
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+
+        let component = self
+            .ctx
+            .component_handler()
+            .component_by_name(
+                worker_name_match.project.as_ref(),
+                &worker_name_match.component_name,
+                worker_name_match.worker_name.as_ref(),
+            )
+            .await?;
+
+        let Some(component) = component else {
+            log_error(format!(
+                "Component {} not found",
+                worker_name_match
+                    .component_name
+                    .0
+                    .log_color_error_highlight()
+            ));
+            logln("");
+            bail!(NonSuccessfulExit);
+        };
+
+        let function_name =
+            self.match_function_name(&worker_name_match.component_name, &component, function_name)?;
+
+        let arguments = insert_file_args(&component, &function_name, arguments, file_args).await?;
+        let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
+
+        let mut url = self.ctx.worker_service_url().clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|()| anyhow!("Failed to get url path for invoke url".to_string()))?;
+            segments
+                .push("v1")
+                .push("components")
+                .push(&component.versioned_component_id.component_id.to_string());
+            if let Some(worker_name) = &worker_name_match.worker_name {
+                segments.push("workers").push(&worker_name.0);
+            }
+            segments.push(if enqueue {
+                "invoke"
+            } else {
+                "invoke-and-await"
+            });
+        }
+        url.query_pairs_mut()
+            .append_pair("function", function_name.as_str());
+
+        let auth_token = self.ctx.auth_token().await?;
+
+        let body = serde_json::to_string(&InvokeParametersOss { params: arguments })?;
+
+        let curl_args = |auth_token: Option<&str>| {
+            let mut args = vec![
+                "-X".to_string(),
+                "POST".to_string(),
+                url.to_string(),
+                "-H".to_string(),
+                "Content-Type: application/json".to_string(),
+                "-H".to_string(),
+                format!("Idempotency-Key: {}", idempotency_key.0),
+            ];
+            if let Some(auth_token) = auth_token {
+                args.push("-H".to_string());
+                args.push(format!("Authorization: Bearer {}", auth_token));
+            }
+            if self.ctx.allow_insecure() {
+                args.push("--insecure".to_string());
+            }
+            args.push("--data".to_string());
+            args.push(body.clone());
+            args
+        };
+
+        let printed_args = curl_args(auth_token.as_ref().map(|token| {
+            if show_token {
+                token.as_str()
+            } else {
+                "<REDACTED>"
+            }
+        }));
+
+        self.ctx.log_handler().log_view(&WorkerInvokeCurlView {
+            method: "POST".to_string(),
+            url: url.to_string(),
+            idempotency_key: idempotency_key.0.clone(),
+            body: serde_json::from_str(&body)?,
+            command: format!(
+                "curl {}",
+                shlex::try_join(printed_args.iter().map(|arg| arg.as_str()))?
+            ),
+        });
+
+        if execute {
+            logln("");
+            log_action(
+                "Executing",
+                format!(
+                    "request for worker {}/{}",
+                    format_worker_name_match(&worker_name_match),
+                    format_export(&function_name)
+                ),
+            );
+
+            // The token is passed as a config read from stdin, so it is not visible in the
+            // process list
+            let mut command = std::process::Command::new("curl");
+            command.args(curl_args(None));
+            if auth_token.is_some() {
+                command.args(["--config", "-"]).stdin(Stdio::piped());
+            }
+            let mut child = command.spawn().context("Failed to execute curl")?;
+            if let Some(auth_token) = &auth_token {
+                let mut stdin = child
+                    .stdin
+                    .take()
+                    .ok_or_else(|| anyhow!("Failed to open curl stdin"))?;
+                stdin
+                    .write_all(
+                        curl_config_header(&format!("Authorization: Bearer {}", auth_token))
+                            .as_bytes(),
+                    )
+                    .context("Failed to pass the authorization header to curl")?;
+            }
+            let status = child.wait().context("Failed to execute curl")?;
+
+            logln("");
+            if !status.success() {
+                log_error_action("Failed", format!("to execute curl, {}", status));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn match_function_name(
+        &self,
+        component_name: &ComponentName,
+        component: &Component,
+        function_name: &str,
+    ) -> anyhow::Result<String> {
+        if let Some(call) = InlineResourceCall::parse(function_name) {
+            return match call.resolve(&component.metadata.exports) {
+                Ok(resolved) => {
+                    log_action(
+                        "Resolved",
+                        format!(
+                            "resource method call {} to {}",
+                            function_name.log_color_highlight(),
+                            resolved.log_color_highlight()
+                        ),
+                    );
+                    Ok(resolved)
+                }
+                Err(error) => {
+                    logln("");
+                    log_error(format!(
+                        "Failed to resolve resource method call {}: {}",
+                        function_name.log_color_error_highlight(),
+                        error
+                    ));
+                    logln("");
+                    log_text_view(&AvailableFunctionNamesHelp {
+                        component_name: component_name.0.clone(),
+                        function_names: show_exported_functions(&component.metadata.exports),
+                    });
+                    bail!(NonSuccessfulExit);
+                }
+            };
+        }
+
+        let component_functions = show_exported_functions(&component.metadata.exports);
+        let fuzzy_search = FuzzySearch::new(component_functions.iter().map(|s| s.as_str()));
+        match fuzzy_search.find(function_name) {
+            Ok(match_) => {
+                log_fuzzy_match(&match_);
+                Ok(match_.option)
+            }
+            Err(error) => match error {
+                Error::Ambiguous {
+                    highlighted_options,
+                    ..
+                } => {
+                    logln("");
+                    log_error(format!(
+                        "The requested function name ({}) is ambiguous.",
+                        function_name.log_color_error_highlight()
+                    ));
+                    logln("");
+                    logln("Did you mean one of");
+                    for option in highlighted_options {
+                        logln(format!(" - {}", option.bold()));
+                    }
+                    logln("?");
+                    logln("");
+                    log_text_view(&AvailableFunctionNamesHelp {
+                        component_name: component_name.0.clone(),
+                        function_names: component_functions,
+                    });
+
+                    bail!(NonSuccessfulExit);
+                }
+                Error::NotFound { .. } => {
+                    logln("");
+                    log_error(format!(
+                        "The requested function name ({}) was not found.",
+                        function_name.log_color_error_highlight()
+                    ));
+                    logln("");
+                    log_text_view(&AvailableFunctionNamesHelp {
+                        component_name: component_name.0.clone(),
+                        function_names: component_functions,
+                    });
+
+                    bail!(NonSuccessfulExit);
+                }
+            },
+        }
+    }
+
+    async fn cmd_stream(
+        &mut self,
+        worker_name: WorkerNameArg,
+        since: Option<DateTime<Utc>>,
+        stream_args: StreamArgs,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        log_action(
+            "Connecting",
+            format!("to worker {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        let connection = connect_to_worker(
+            self.ctx.worker_service_url().clone(),
+            self.ctx.auth_token().await?,
+            component.versioned_component_id.component_id,
+            worker_name.0.clone(),
+            ConnectOutput::new(stream_args.into(), self.ctx.format()).with_since(since),
+            self.ctx.allow_insecure(),
+        )
+        .await?;
+
+        connection.read_messages().await;
+
+        Ok(())
+    }
+
+    async fn cmd_logs(
+        &mut self,
+        worker_name: WorkerNameArg,
+        follow: bool,
+        filter: WorkerLogFilter,
+        stream_args: StreamArgs,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+        let component_id = component.versioned_component_id.component_id;
+
+        let output = ConnectOutput::new(stream_args.into(), self.ctx.format()).with_filter(filter);
+
+        // Recorded output is reconstructed from the log entries of the oplog
+        let entries = self
+            .get_oplog(&component_id, &worker_name, None, None, None)
+            .await?;
+        let mut last_recorded_timestamp = None;
+        for (_, entry) in entries {
+            let PublicOplogEntry::Log(params) = entry else {
+                continue;
+            };
+            last_recorded_timestamp = Some(params.timestamp);
+            match params.level {
+                OplogLogLevel::Stdout => output.emit_stdout(params.timestamp, params.message).await,
+                OplogLogLevel::Stderr => output.emit_stderr(params.timestamp, params.message).await,
+                OplogLogLevel::Trace => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Trace,
+                    params.context,
+                    params.message,
+                ),
+                OplogLogLevel::Debug => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Debug,
+                    params.context,
+                    params.message,
+                ),
+                OplogLogLevel::Info => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Info,
+                    params.context,
+                    params.message,
+                ),
+                OplogLogLevel::Warn => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Warn,
+                    params.context,
+                    params.message,
+                ),
+                OplogLogLevel::Error => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Error,
+                    params.context,
+                    params.message,
+                ),
+                OplogLogLevel::Critical => output.emit_log(
+                    params.timestamp,
+                    LogLevel::Critical,
+                    params.context,
+                    params.message,
+                ),
+            }
+        }
+        output.flush().await;
+
+        if !follow {
+            return Ok(());
+        }
+
+        log_action(
+            "Following",
+            format!(
+                "worker {}, press Ctrl-C to stop",
+                format_worker_name_match(&worker_name_match)
+            ),
+        );
+
+        // The connection replays the recent events, skipping the ones already printed from the oplog
+        let output = output.with_since(last_recorded_timestamp.and_then(|timestamp| {
+            DateTime::<Utc>::from_timestamp_millis(timestamp.to_millis() as i64 + 1)
+        }));
+
+        let connection = connect_to_worker(
+            self.ctx.worker_service_url().clone(),
+            self.ctx.auth_token().await?,
+            component_id,
+            worker_name.0.clone(),
+            output,
+            self.ctx.allow_insecure(),
+        )
+        .await?;
 
         connection.read_messages().await;
 
@@ -588,7 +1253,8 @@ impl WorkerCommandHandler {
     async fn cmd_oplog(
         &mut self,
         worker_name: WorkerNameArg,
-        from: Option<u64>,
+        from_index: Option<u64>,
+        count: Option<u64>,
         query: Option<String>,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
@@ -597,21 +1263,54 @@ impl WorkerCommandHandler {
             .component_by_worker_name_match(&worker_name_match)
             .await?;
 
-        let batch_size = self.ctx.http_batch_size();
+        let entries = self
+            .get_oplog(
+                &component.versioned_component_id.component_id,
+                &worker_name,
+                from_index,
+                query.as_deref(),
+                count,
+            )
+            .await?;
+
+        if entries.is_empty() {
+            log_warn("No results.")
+        }
+
+        self.ctx.log_handler().log_view(&entries);
+
+        Ok(())
+    }
+
+    pub async fn get_oplog(
+        &self,
+        component_id: &Uuid,
+        worker_name: &WorkerName,
+        from: Option<u64>,
+        query: Option<&str>,
+        max_count: Option<u64>,
+    ) -> anyhow::Result<Vec<(u64, PublicOplogEntry)>> {
         let mut entries = Vec::<(u64, PublicOplogEntry)>::new();
         let mut cursor = Option::<OplogCursor>::None;
         loop {
+            let batch_size = match max_count {
+                Some(max_count) => self
+                    .ctx
+                    .http_batch_size()
+                    .min(max_count - entries.len() as u64),
+                None => self.ctx.http_batch_size(),
+            };
             cursor = match self.ctx.golem_clients().await? {
                 GolemClients::Oss(clients) => {
                     let result = clients
                         .worker
                         .get_oplog(
-                            &component.versioned_component_id.component_id,
+                            component_id,
                             &worker_name.0,
                             from,
                             batch_size,
                             cursor.as_ref(),
-                            query.as_deref(),
+                            query,
                         )
                         .await
                         .map_service_error()?;
@@ -627,12 +1326,12 @@ impl WorkerCommandHandler {
                     let result = clients
                         .worker
                         .get_oplog(
-                            &component.versioned_component_id.component_id,
+                            component_id,
                             &worker_name.0,
                             from,
                             batch_size,
                             cursor.as_ref(),
-                            query.as_deref(),
+                            query,
                         )
                         .await
                         .map_service_error()?;
@@ -645,31 +1344,31 @@ impl WorkerCommandHandler {
                     result.next
                 }
             };
+            if let Some(max_count) = max_count {
+                entries.truncate(max_count as usize);
+                if entries.len() as u64 >= max_count {
+                    break;
+                }
+            }
             if cursor.is_none() {
                 break;
             }
         }
 
-        if entries.is_empty() {
-            log_warn("No results.")
-        }
-
-        self.ctx.log_handler().log_view(&entries);
-
-        Ok(())
+        Ok(entries)
     }
 
     async fn cmd_revert(
         &mut self,
         worker_name: WorkerNameArg,
-        last_oplog_index: Option<u64>,
-        number_of_invocations: Option<u64>,
+        to_oplog_index: Option<u64>,
+        undo_invocations: Option<u64>,
     ) -> anyhow::Result<()> {
-        if last_oplog_index.is_none() && number_of_invocations.is_none() {
+        if to_oplog_index.is_none() && undo_invocations.is_none() {
             log_error(format!(
                 "One of [{}, {}] must be specified",
-                "last-oplog-index".log_color_highlight(),
-                "number of invocations".log_color_highlight()
+                "--to-oplog-index".log_color_highlight(),
+                "--undo-invocations".log_color_highlight()
             ));
             bail!(NonSuccessfulExit)
         }
@@ -688,16 +1387,16 @@ impl WorkerCommandHandler {
         match self.ctx.golem_clients().await? {
             GolemClients::Oss(clients) => {
                 let target = {
-                    if let Some(last_oplog_index) = last_oplog_index {
+                    if let Some(last_oplog_index) = to_oplog_index {
                         RevertWorkerTargetOss::RevertToOplogIndex(RevertToOplogIndexOss {
                             last_oplog_index,
                         })
-                    } else if let Some(number_of_invocations) = number_of_invocations {
+                    } else if let Some(number_of_invocations) = undo_invocations {
                         RevertWorkerTargetOss::RevertLastInvocations(RevertLastInvocationsOss {
                             number_of_invocations,
                         })
                     } else {
-                        bail!("Expected either last_oplog_index or number_of_invocations")
+                        bail!("Expected either to_oplog_index or undo_invocations")
                     }
                 };
 
@@ -714,16 +1413,16 @@ impl WorkerCommandHandler {
             }
             GolemClients::Cloud(clients) => {
                 let target = {
-                    if let Some(last_oplog_index) = last_oplog_index {
+                    if let Some(last_oplog_index) = to_oplog_index {
                         RevertWorkerTargetCloud::RevertToOplogIndex(RevertToOplogIndexCloud {
                             last_oplog_index,
                         })
-                    } else if let Some(number_of_invocations) = number_of_invocations {
+                    } else if let Some(number_of_invocations) = undo_invocations {
                         RevertWorkerTargetCloud::RevertLastInvocations(RevertLastInvocationsCloud {
                             number_of_invocations,
                         })
                     } else {
-                        bail!("Expected either last_oplog_index or number_of_invocations")
+                        bail!("Expected either to_oplog_index or undo_invocations")
                     }
                 };
 
@@ -745,6 +1444,19 @@ impl WorkerCommandHandler {
             format!("worker {}", format_worker_name_match(&worker_name_match)),
         );
 
+        let worker_metadata = self
+            .worker_metadata(
+                component.versioned_component_id.component_id,
+                &worker_name_match.component_name,
+                &worker_name,
+            )
+            .await?;
+
+        logln("");
+        self.ctx
+            .log_handler()
+            .log_view(&WorkerGetView::from(worker_metadata));
+
         Ok(())
     }
 
@@ -768,28 +1480,9 @@ impl WorkerCommandHandler {
             ),
         );
 
-        let canceled = match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => clients
-                .worker
-                .cancel_invocation(
-                    &component.versioned_component_id.component_id,
-                    &worker_name.0,
-                    &idempotency_key.0,
-                )
-                .await
-                .map(|result| (result.canceled))
-                .map_service_error()?,
-            GolemClients::Cloud(clients) => clients
-                .worker
-                .cancel_invocation(
-                    &component.versioned_component_id.component_id,
-                    &worker_name.0,
-                    &idempotency_key.0,
-                )
-                .await
-                .map(|result| (result.canceled))
-                .map_service_error()?,
-        };
+        let canceled = self
+            .cancel_invocation(&component, &worker_name, &idempotency_key)
+            .await?;
 
         // TODO: json / yaml response?
         if canceled {
@@ -801,6 +1494,163 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    async fn cmd_import_invocations(
+        &self,
+        project: ProjectNameOptionalArg,
+        postman: Option<PathBuf>,
+        har: Option<PathBuf>,
+        definition_id: ApiDefinitionId,
+        definition_version: Option<ApiDefinitionVersion>,
+    ) -> anyhow::Result<()> {
+        let read_json = |path: &PathBuf| -> anyhow::Result<serde_json::Value> {
+            serde_json::from_str(&fs::read_to_string(path)?)
+                .with_context(|| format!("Failed to parse {}", path.display()))
+        };
+        let requests = match (postman, har) {
+            (Some(postman), _) => parse_postman_collection(&read_json(&postman)?)?,
+            (None, Some(har)) => parse_har(&read_json(&har)?)?,
+            (None, None) => bail!("Either a Postman collection or a HAR file is required"),
+        };
+
+        let definition_version = match definition_version {
+            Some(definition_version) => definition_version,
+            None => {
+                self.ctx
+                    .api_definition_handler()
+                    .single_definition_version(&project, &definition_id)
+                    .await?
+            }
+        };
+        let definition = self
+            .ctx
+            .api_definition_handler()
+            .get_definition_as_json(&project, &definition_id, &definition_version)
+            .await?;
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None /* TODO: account id */, project.project.as_ref())
+            .await?;
+
+        let mut components = HashMap::new();
+        let mut invocations = vec![];
+        for request in requests {
+            match self
+                .import_invocation(project.as_ref(), &definition, &request, &mut components)
+                .await?
+            {
+                Ok(invocation) => invocations.push(invocation),
+                Err(reason) => log_warn(format!(
+                    "Skipping request {}: {}",
+                    request.name.log_color_highlight(),
+                    reason
+                )),
+            }
+        }
+
+        self.ctx.log_handler().log_view(&invocations);
+
+        Ok(())
+    }
+
+    /// Converts the request to an invocation, or returns the reason why it cannot be converted
+    async fn import_invocation(
+        &self,
+        project: Option<&ProjectNameAndId>,
+        definition: &serde_json::Value,
+        request: &RecordedHttpRequest,
+        components: &mut HashMap<(String, Option<u64>), Option<Component>>,
+    ) -> anyhow::Result<Result<ImportedInvocation, String>> {
+        let route_request = match request.route_request() {
+            Ok(route_request) => route_request,
+            Err(error) => return Ok(Err(error)),
+        };
+        let Some(route) = try_api_route(
+            definition,
+            &route_request,
+            request.headers.clone(),
+            request.body_json(),
+        ) else {
+            return Ok(Err("no matching route".to_string()));
+        };
+        let Some(component_name) = route.component_name.clone() else {
+            return Ok(Err(format!(
+                "route {} {} is not bound to a component",
+                route.method, route.path
+            )));
+        };
+
+        let component_key = (component_name.clone(), route.component_version);
+        if !components.contains_key(&component_key) {
+            let component = self
+                .ctx
+                .component_handler()
+                .component_by_name_and_version(
+                    project,
+                    &ComponentName::from(component_name.as_str()),
+                    route.component_version,
+                )
+                .await?;
+            components.insert(component_key.clone(), component);
+        }
+        let Some(component) = &components[&component_key] else {
+            return Ok(Err(format!("component {} not found", component_name)));
+        };
+
+        let Some(worker_name) = route
+            .worker_name
+            .as_deref()
+            .and_then(|script| rib_worker_name(script, &route))
+        else {
+            return Ok(Err(
+                "cannot evaluate the worker name of the binding".to_string()
+            ));
+        };
+        let Some(response) = route.response.as_deref() else {
+            return Ok(Err("the binding has no response mapping".to_string()));
+        };
+
+        let called_functions = exported_functions(&component.metadata.exports)
+            .into_iter()
+            .filter(|(_, function)| rib_script_calls_function(response, &function.name))
+            .collect::<Vec<_>>();
+        let [(prefix, function)] = called_functions.as_slice() else {
+            return Ok(Err(format!(
+                "the response mapping calls {} exported functions, expected exactly one",
+                called_functions.len()
+            )));
+        };
+
+        let Some(arguments) = rib_function_arguments(response, &function.name, &route) else {
+            return Ok(Err("cannot evaluate the function arguments".to_string()));
+        };
+        if arguments.len() != function.parameters.len() {
+            return Ok(Err(format!(
+                "expected {} function arguments, found {}",
+                function.parameters.len(),
+                arguments.len()
+            )));
+        }
+        let Some(arguments) = arguments
+            .iter()
+            .zip(&function.parameters)
+            .map(|(argument, parameter)| json_to_wave(argument, &parameter.typ))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(Err(
+                "cannot convert the function arguments to the parameter types".to_string(),
+            ));
+        };
+
+        Ok(Ok(ImportedInvocation {
+            name: request.name.clone(),
+            component_name,
+            worker_name,
+            function_name: format_function_name(*prefix, &function.name),
+            arguments,
+        }))
+    }
+
     async fn cmd_list(
         &self,
         component_name: Option<ComponentName>,
@@ -808,6 +1658,7 @@ impl WorkerCommandHandler {
         scan_cursor: Option<ScanCursor>,
         max_count: Option<u64>,
         precise: bool,
+        columns: Vec<WorkerListColumn>,
     ) -> anyhow::Result<()> {
         let selected_components = self
             .ctx
@@ -830,7 +1681,10 @@ impl WorkerCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
-        let mut view = WorkersMetadataResponseView::default();
+        let mut view = WorkersMetadataResponseView {
+            columns,
+            ..WorkersMetadataResponseView::default()
+        };
 
         for component_name in &selected_components.component_names {
             match self
@@ -869,11 +1723,381 @@ impl WorkerCommandHandler {
             }
         }
 
+        view.workers.sort_by(|a, b| {
+            (&a.component_name.0, &a.worker_name.0).cmp(&(&b.component_name.0, &b.worker_name.0))
+        });
+        self.ctx.log_handler().log_view(&view);
+
+        Ok(())
+    }
+
+    async fn cmd_backlog(
+        &self,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+        limit: usize,
+        all: bool,
+    ) -> anyhow::Result<()> {
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_or_name(component_name.as_ref())
+            .await?;
+
+        let mut workers = Vec::new();
+        for component_name in &selected_components.component_names {
+            match self
+                .ctx
+                .component_handler()
+                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .await?
+            {
+                Some(component) => {
+                    // Precise query, as the pending invocation counts are only up-to-date
+                    // when queried from the executors
+                    let (component_workers, _) = self
+                        .list_component_workers(
+                            component_name,
+                            component.versioned_component_id.component_id,
+                            Some(filters.as_slice()),
+                            None,
+                            None,
+                            true,
+                        )
+                        .await?;
+                    workers.extend(component_workers.into_iter().map(WorkerMetadataView::from));
+                }
+                None => {
+                    log_warn(format!(
+                        "Component not found: {}",
+                        component_name.0.log_color_error_highlight()
+                    ));
+                }
+            }
+        }
+
+        if !all {
+            workers.retain(|worker| worker.pending_invocation_count > 0);
+        }
+        workers.sort_by(|a, b| {
+            b.pending_invocation_count
+                .cmp(&a.pending_invocation_count)
+                .then_with(|| {
+                    (&a.component_name.0, &a.worker_name.0)
+                        .cmp(&(&b.component_name.0, &b.worker_name.0))
+                })
+        });
+        workers.truncate(limit);
+
+        if workers.is_empty() {
+            log_action("Found", "no workers with pending invocations");
+            return Ok(());
+        }
+
+        self.ctx
+            .log_handler()
+            .log_view(&WorkersMetadataResponseView {
+                workers,
+                cursors: BTreeMap::new(),
+                columns: vec![
+                    WorkerListColumn::ComponentName,
+                    WorkerListColumn::WorkerName,
+                    WorkerListColumn::Status,
+                    WorkerListColumn::PendingInvocationCount,
+                ],
+            });
+
+        Ok(())
+    }
+
+    async fn cmd_list_all_components(
+        &self,
+        project: Option<ProjectName>,
+        filters: Vec<String>,
+        max_count: Option<u64>,
+        precise: bool,
+        columns: Vec<WorkerListColumn>,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None, project.as_ref())
+            .await?;
+
+        let components = self
+            .ctx
+            .component_handler()
+            .latest_components(project.as_ref())
+            .await?;
+
+        log_action(
+            "Searching",
+            format!(
+                "workers in {} component(s)",
+                components.len().to_string().log_color_highlight()
+            ),
+        );
+
+        let filters = filters.as_slice();
+        let results = futures_util::stream::iter(components.iter().map(|component| async move {
+            let result = self
+                .list_component_workers(
+                    &component.component_name,
+                    component.versioned_component_id.component_id,
+                    Some(filters),
+                    None,
+                    max_count,
+                    precise,
+                )
+                .await;
+            (&component.component_name, result)
+        }))
+        .buffer_unordered(LIST_ALL_COMPONENTS_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+        let mut view = WorkersMetadataResponseView {
+            columns,
+            ..WorkersMetadataResponseView::default()
+        };
+
+        for (component_name, result) in results {
+            let (workers, scan_cursor) = result?;
+            scan_cursor.into_iter().for_each(|scan_cursor| {
+                view.cursors.insert(
+                    component_name.to_string(),
+                    scan_cursor_to_string(&scan_cursor),
+                );
+            });
+            view.workers
+                .extend(workers.into_iter().map(WorkerMetadataView::from));
+        }
+
+        view.workers.sort_by(|a, b| {
+            (&a.component_name.0, &a.worker_name.0).cmp(&(&b.component_name.0, &b.worker_name.0))
+        });
+
         self.ctx.log_handler().log_view(&view);
 
         Ok(())
     }
 
+    async fn cmd_monitor(
+        &self,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+        interval: Duration,
+        on_failure: Option<String>,
+        once: bool,
+    ) -> anyhow::Result<()> {
+        let on_failure = match on_failure {
+            Some(on_failure) => match shlex::split(&on_failure) {
+                Some(tokens) if !tokens.is_empty() => Some(tokens),
+                _ => {
+                    log_error(format!(
+                        "Invalid --on-failure command: {}",
+                        on_failure.log_color_highlight()
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
+            },
+            None => None,
+        };
+
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_or_name(component_name.as_ref())
+            .await?;
+
+        let mut components = Vec::<(ComponentName, Uuid)>::new();
+        for component_name in &selected_components.component_names {
+            match self
+                .ctx
+                .component_handler()
+                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .await?
+            {
+                Some(component) => components.push((
+                    component_name.clone(),
+                    component.versioned_component_id.component_id,
+                )),
+                None => log_warn(format!(
+                    "Component not found: {}",
+                    component_name.0.log_color_error_highlight()
+                )),
+            }
+        }
+        if components.is_empty() {
+            log_error("No components found to monitor");
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Monitoring",
+            format!(
+                "workers of {} every {}s, press Ctrl-C to stop",
+                components
+                    .iter()
+                    .map(|(component_name, _)| component_name.0.log_color_highlight())
+                    .join(", "),
+                interval.as_secs().to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let cancellation_token = self.ctx.cancellation_token().clone();
+        let _cancellable_scope = cancellation_token.cancellable_scope();
+
+        // Last known status of the workers matching the filters. Workers dropping out of the
+        // filters are checked once more, so transitions to statuses excluded by the filters
+        // are detected too, but they are not watched any further.
+        let mut watched = BTreeMap::<(String, String), (ComponentName, Uuid, WorkerStatus)>::new();
+        // Workers which are already failed at startup are not reported
+        let mut is_first_poll = true;
+        let mut wait_before_poll = false;
+        loop {
+            if wait_before_poll {
+                tokio::select! {
+                    _ = time::sleep(interval) => {}
+                    _ = cancellation_token.cancelled() => {}
+                }
+                if cancellation_token.is_cancelled() {
+                    log_warn_action("Stopping", "monitoring, cancelled");
+                    return cancellation_token.check();
+                }
+            }
+            wait_before_poll = true;
+
+            // Listing errors are expected to be transient, so monitoring goes on with the next poll
+            let mut current = match self.list_monitored_workers(&components, &filters).await {
+                Ok(current) => current,
+                Err(error) => {
+                    log_warn(format!(
+                        "Failed to list workers, retrying in {}s: {:#}",
+                        interval.as_secs(),
+                        error
+                    ));
+                    continue;
+                }
+            };
+            let listed = current.keys().cloned().collect::<BTreeSet<_>>();
+
+            for (key, (component_name, component_id, _)) in &watched {
+                if listed.contains(key) {
+                    continue;
+                }
+                let worker_name = &key.1;
+                match self
+                    .worker_metadata(*component_id, component_name, &worker_name.as_str().into())
+                    .await
+                {
+                    Ok(worker) => {
+                        current.insert(key.clone(), worker);
+                    }
+                    Err(_) => {
+                        log_warn_action(
+                            "Stopped watching",
+                            format!(
+                                "worker {}/{}, it is no longer available",
+                                component_name.0.bold().blue(),
+                                worker_name.bold().green(),
+                            ),
+                        );
+                    }
+                }
+            }
+
+            let mut failed = Vec::<&WorkerMetadata>::new();
+            for (key, worker) in &current {
+                let is_failure =
+                    matches!(worker.status, WorkerStatus::Failed | WorkerStatus::Exited);
+                let was_failure = watched
+                    .get(key)
+                    .map(|(_, _, status)| {
+                        matches!(status, WorkerStatus::Failed | WorkerStatus::Exited)
+                    })
+                    .unwrap_or(false);
+                if is_failure && !was_failure && !is_first_poll {
+                    failed.push(worker);
+                }
+            }
+
+            for worker in &failed {
+                log_error_action(
+                    "Detected",
+                    format!(
+                        "worker {}/{} transitioned to {}{}",
+                        worker.component_name.0.bold().blue(),
+                        worker.worker_id.worker_name.bold().green(),
+                        format_status(&worker.status),
+                        worker
+                            .last_error
+                            .as_ref()
+                            .map(|error| format!(", last error: {}", error.log_color_warn()))
+                            .unwrap_or_default()
+                    ),
+                );
+                if let Some(on_failure) = &on_failure {
+                    let _indent = LogIndent::new();
+                    if let Err(error) = run_monitor_hook(on_failure, worker) {
+                        log_warn(format!("Failed to execute --on-failure command: {}", error));
+                    }
+                }
+            }
+
+            if once && !failed.is_empty() {
+                bail!(NonSuccessfulExit);
+            }
+
+            watched = current
+                .into_iter()
+                .filter(|(key, _)| listed.contains(key))
+                .map(|(key, worker)| {
+                    (
+                        key,
+                        (
+                            worker.component_name,
+                            worker.worker_id.component_id.0,
+                            worker.status,
+                        ),
+                    )
+                })
+                .collect();
+            is_first_poll = false;
+        }
+    }
+
+    async fn list_monitored_workers(
+        &self,
+        components: &[(ComponentName, Uuid)],
+        filters: &[String],
+    ) -> anyhow::Result<BTreeMap<(String, String), WorkerMetadata>> {
+        let mut workers_by_key = BTreeMap::new();
+        for (component_name, component_id) in components {
+            let (workers, _) = self
+                .list_component_workers(
+                    component_name,
+                    *component_id,
+                    Some(filters),
+                    None,
+                    None,
+                    true,
+                )
+                .await?;
+            for worker in workers {
+                workers_by_key.insert(
+                    (
+                        component_name.0.clone(),
+                        worker.worker_id.worker_name.clone(),
+                    ),
+                    worker,
+                );
+            }
+        }
+        Ok(workers_by_key)
+    }
+
     async fn cmd_interrupt(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
@@ -909,21 +2133,637 @@ impl WorkerCommandHandler {
             format!("worker {}", format_worker_name_match(&worker_name_match)),
         );
 
-        self.resume_worker(&component, &worker_name).await?;
+        self.resume_worker(&component, &worker_name).await?;
+
+        log_action(
+            "Resumed",
+            format!("worker {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_update(
+        &mut self,
+        worker_name: WorkerNameArg,
+        mode: WorkerUpdateMode,
+        target_version: u64,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        self.update_worker(
+            &component.component_name,
+            component.versioned_component_id.component_id,
+            &worker_name.0,
+            mode,
+            target_version,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cmd_update_many(
+        &mut self,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+        update_mode: WorkerUpdateMode,
+        target_version: Option<u64>,
+        options: WorkerUpdateRolloutOptions,
+    ) -> anyhow::Result<()> {
+        if !(0.0..=1.0).contains(&options.max_failure_ratio) {
+            log_error(format!(
+                "The max failure ratio must be between 0.0 and 1.0, got {}",
+                options.max_failure_ratio.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_or_name(component_name.as_ref())
+            .await?;
+
+        let mut targets = Vec::<WorkerUpdateTarget>::new();
+        for component_name in &selected_components.component_names {
+            match self
+                .ctx
+                .component_handler()
+                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .await?
+            {
+                Some(component) => {
+                    let target_version =
+                        target_version.unwrap_or(component.versioned_component_id.version);
+                    let (workers, _) = self
+                        .list_component_workers(
+                            component_name,
+                            component.versioned_component_id.component_id,
+                            Some(filters.as_slice()),
+                            None,
+                            None,
+                            true,
+                        )
+                        .await?;
+
+                    targets.extend(
+                        workers
+                            .into_iter()
+                            .filter(|worker| worker.component_version != target_version)
+                            .map(|worker| WorkerUpdateTarget {
+                                component_name: component_name.clone(),
+                                component_id: component.versioned_component_id.component_id,
+                                worker_name: worker.worker_id.worker_name.as_str().into(),
+                                original_version: worker.component_version,
+                                target_version,
+                                known_update_count: worker.updates.len(),
+                            }),
+                    );
+                }
+                None => {
+                    log_warn(format!(
+                        "Component {} not found",
+                        component_name.0.log_color_highlight()
+                    ));
+                }
+            }
+        }
+
+        if targets.is_empty() {
+            log_warn_action(
+                "Skipping",
+                "updating workers, no workers found requiring an update",
+            );
+            return Ok(());
+        }
+
+        let batch_count = targets.len().div_ceil(options.batch_size);
+
+        log_action(
+            "Updating",
+            format!(
+                "{} workers in {} batch(es) using {} update mode",
+                targets.len().to_string().log_color_highlight(),
+                batch_count.to_string().log_color_highlight(),
+                update_mode.to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let cancellation_token = self.ctx.cancellation_token().clone();
+        let _cancellable_scope = cancellation_token.cancellable_scope();
+
+        let mut result = WorkerUpdateRolloutResult::default();
+        let mut updated_targets = Vec::<&WorkerUpdateTarget>::new();
+
+        for (batch_idx, batch) in targets.chunks(options.batch_size).enumerate() {
+            if cancellation_token.is_cancelled() {
+                log_warn_action("Stopping", "rollout, cancelled");
+                result.stopped = true;
+                result.skipped = targets
+                    .iter()
+                    .skip(batch_idx * options.batch_size)
+                    .map(|target| target.attempt(target.target_version, None))
+                    .collect();
+                break;
+            }
+
+            log_action(
+                "Updating",
+                format!(
+                    "batch {}/{}",
+                    (batch_idx + 1).to_string().log_color_highlight(),
+                    batch_count.to_string().log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            let mut triggered_targets = Vec::<&WorkerUpdateTarget>::new();
+            for target in batch {
+                match self
+                    .update_worker(
+                        &target.component_name,
+                        target.component_id,
+                        &target.worker_name.0,
+                        update_mode,
+                        target.target_version,
+                    )
+                    .await
+                {
+                    Ok(()) => triggered_targets.push(target),
+                    Err(error) => result
+                        .failed
+                        .push(target.attempt(target.target_version, Some(error.to_string()))),
+                }
+            }
+
+            // The timeout applies to the whole batch, not to each worker separately
+            let deadline = options
+                .await_timeout
+                .map(|timeout| time::Instant::now() + timeout);
+            // Errors of checking the update status (e.g. connection errors) stop the rollout
+            // after the current batch, as the state of the remaining workers is unknown
+            let mut status_check_failed = false;
+            for target in triggered_targets {
+                // Without awaiting, the outcome of the update is unknown, so these are neither
+                // counted as updated, nor rolled back
+                let Some(deadline) = deadline else {
+                    result
+                        .triggered
+                        .push(target.attempt(target.target_version, None));
+                    continue;
+                };
+
+                let error = match self
+                    .await_worker_update(
+                        target,
+                        target.target_version,
+                        target.known_update_count,
+                        deadline,
+                    )
+                    .await
+                {
+                    Ok(error) => error,
+                    Err(error) => {
+                        status_check_failed = true;
+                        Some(format!("failed to check the update status: {error:#}"))
+                    }
+                };
+
+                match error {
+                    Some(error) => result
+                        .failed
+                        .push(target.attempt(target.target_version, Some(error))),
+                    None => {
+                        result
+                            .updated
+                            .push(target.attempt(target.target_version, None));
+                        updated_targets.push(target);
+                    }
+                }
+            }
+
+            let attempted_count =
+                result.updated.len() + result.triggered.len() + result.failed.len();
+            let failure_ratio = result.failed.len() as f64 / attempted_count as f64;
+            if status_check_failed || failure_ratio > options.max_failure_ratio {
+                if status_check_failed {
+                    log_error_action(
+                        "Stopping",
+                        "rollout, failed to check the update status of some workers",
+                    );
+                } else {
+                    log_error_action(
+                        "Stopping",
+                        format!(
+                            "rollout, failure ratio {} exceeds the allowed maximum {}",
+                            format!("{:.2}", failure_ratio).log_color_highlight(),
+                            format!("{:.2}", options.max_failure_ratio).log_color_highlight()
+                        ),
+                    );
+                }
+                result.stopped = true;
+                result.skipped = targets
+                    .iter()
+                    .skip((batch_idx + 1) * options.batch_size)
+                    .map(|target| target.attempt(target.target_version, None))
+                    .collect();
+                break;
+            }
+        }
+
+        if result.stopped
+            && !cancellation_token.is_cancelled()
+            && options.rollback_on_failure
+            && !updated_targets.is_empty()
+        {
+            log_warn_action(
+                "Rolling back",
+                format!(
+                    "{} updated workers to their original versions",
+                    updated_targets.len().to_string().log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            for target in updated_targets {
+                let error = match self
+                    .rollback_worker_update(target, update_mode, options.await_timeout)
+                    .await
+                {
+                    Ok(error) => error,
+                    Err(error) => Some(error.to_string()),
+                };
+                result
+                    .rolled_back
+                    .push(target.attempt(target.original_version, error));
+            }
+        }
+
+        let stopped = result.stopped;
+        self.ctx.log_handler().log_view(&result);
+
+        if stopped {
+            cancellation_token.check()?;
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_worker_update(
+        &self,
+        target: &WorkerUpdateTarget,
+        update_mode: WorkerUpdateMode,
+        await_timeout: Option<Duration>,
+    ) -> anyhow::Result<Option<String>> {
+        let known_update_count = self
+            .worker_metadata(
+                target.component_id,
+                &target.component_name,
+                &target.worker_name,
+            )
+            .await?
+            .updates
+            .len();
+
+        self.update_worker(
+            &target.component_name,
+            target.component_id,
+            &target.worker_name.0,
+            update_mode,
+            target.original_version,
+        )
+        .await?;
+
+        match await_timeout {
+            Some(timeout) => {
+                self.await_worker_update(
+                    target,
+                    target.original_version,
+                    known_update_count,
+                    time::Instant::now() + timeout,
+                )
+                .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Polls the worker metadata until the worker reaches the target version, or the update
+    /// fails or the deadline passes, in which case the error details are returned.
+    async fn await_worker_update(
+        &self,
+        target: &WorkerUpdateTarget,
+        target_version: u64,
+        known_update_count: usize,
+        deadline: time::Instant,
+    ) -> anyhow::Result<Option<String>> {
+        loop {
+            let metadata = self
+                .worker_metadata(
+                    target.component_id,
+                    &target.component_name,
+                    &target.worker_name,
+                )
+                .await?;
+
+            let failed_update =
+                metadata
+                    .updates
+                    .iter()
+                    .skip(known_update_count)
+                    .find_map(|update| match update {
+                        UpdateRecord::FailedUpdate(update)
+                            if update.target_version == target_version =>
+                        {
+                            Some(
+                                update
+                                    .details
+                                    .clone()
+                                    .unwrap_or_else(|| "update failed".to_string()),
+                            )
+                        }
+                        _ => None,
+                    });
+
+            if let Some(details) = failed_update {
+                log_error_action(
+                    "Failed",
+                    format!(
+                        "to update worker {}/{} to version {}",
+                        target.component_name.0.bold().blue(),
+                        target.worker_name.0.bold().green(),
+                        target_version.to_string().log_color_highlight()
+                    ),
+                );
+                return Ok(Some(details));
+            }
+
+            if metadata.component_version == target_version {
+                log_action(
+                    "Updated",
+                    format!(
+                        "worker {}/{} to version {}",
+                        target.component_name.0.bold().blue(),
+                        target.worker_name.0.bold().green(),
+                        target_version.to_string().log_color_highlight()
+                    ),
+                );
+                return Ok(None);
+            }
+
+            if time::Instant::now() >= deadline {
+                log_error_action(
+                    "Timed out",
+                    format!(
+                        "waiting for worker {}/{} to reach version {}",
+                        target.component_name.0.bold().blue(),
+                        target.worker_name.0.bold().green(),
+                        target_version.to_string().log_color_highlight()
+                    ),
+                );
+                return Ok(Some(format!(
+                    "timed out waiting for the worker to reach version {}",
+                    target_version
+                )));
+            }
+
+            time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    async fn cmd_get(
+        &mut self,
+        worker_name: WorkerNameArg,
+        output_field: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        self.ctx.log_handler().prepare_output_field(output_field);
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let result = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => {
+                let result = clients
+                    .worker
+                    .get_worker_metadata(
+                        &component.versioned_component_id.component_id,
+                        &worker_name.0,
+                    )
+                    .await
+                    .map_service_error()?;
+
+                WorkerMetadata::from_oss(worker_name_match.component_name, result)
+            }
+            GolemClients::Cloud(clients) => {
+                let result = clients
+                    .worker
+                    .get_worker_metadata(
+                        &component.versioned_component_id.component_id,
+                        &worker_name.0,
+                    )
+                    .await
+                    .map_service_error()?;
+
+                WorkerMetadata::from_cloud(worker_name_match.component_name, result)
+            }
+        };
+
+        self.ctx
+            .log_handler()
+            .log_view_field(&WorkerGetView::from(result), output_field)
+    }
+
+    async fn cmd_delete(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        log_warn_action(
+            "Deleting",
+            format!("worker {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        self.delete(
+            component.versioned_component_id.component_id,
+            &worker_name.0,
+        )
+        .await?;
+
+        log_action(
+            "Deleted",
+            format!("worker {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_delete_many(
+        &mut self,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+        dry_run: bool,
+    ) -> anyhow::Result<()> {
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_or_name(component_name.as_ref())
+            .await?;
+
+        let mut targets = Vec::<(ComponentName, Uuid, String)>::new();
+        for component_name in &selected_components.component_names {
+            match self
+                .ctx
+                .component_handler()
+                .component_by_name(selected_components.project.as_ref(), component_name, None)
+                .await?
+            {
+                Some(component) => {
+                    let component_id = component.versioned_component_id.component_id;
+                    let (workers, _) = self
+                        .list_component_workers(
+                            component_name,
+                            component_id,
+                            Some(filters.as_slice()),
+                            None,
+                            None,
+                            false,
+                        )
+                        .await?;
+                    targets.extend(workers.into_iter().map(|worker| {
+                        (
+                            component_name.clone(),
+                            component_id,
+                            worker.worker_id.worker_name,
+                        )
+                    }));
+                }
+                None => {
+                    log_warn(format!(
+                        "Component {} not found",
+                        component_name.0.log_color_highlight()
+                    ));
+                }
+            }
+        }
+
+        if targets.is_empty() {
+            log_warn_action("Skipping", "deleting workers, no matching workers found");
+            return Ok(());
+        }
+
+        if dry_run {
+            log_action(
+                "Found",
+                format!(
+                    "{} matching worker(s), not deleting them",
+                    targets.len().to_string().log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+            for (component_name, _, worker_name) in &targets {
+                logln(format!(
+                    "{}/{}",
+                    component_name.0.bold().blue(),
+                    worker_name.bold().green()
+                ));
+            }
+            return Ok(());
+        }
+
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_delete_workers(targets.len())?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        let cancellation_token = self.ctx.cancellation_token().clone();
+        let _cancellable_scope = cancellation_token.cancellable_scope();
+
+        log_warn_action(
+            "Deleting",
+            format!(
+                "{} worker(s)",
+                targets.len().to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let mut deleted_count = 0;
+        let mut failed_count = 0;
+        for (component_name, component_id, worker_name) in &targets {
+            if cancellation_token.is_cancelled() {
+                log_warn_action(
+                    "Stopping",
+                    format!(
+                        "deleting workers, cancelled after deleting {} worker(s)",
+                        deleted_count.to_string().log_color_highlight()
+                    ),
+                );
+                bail!(CancelledError);
+            }
+
+            match self.delete(*component_id, worker_name).await {
+                Ok(()) => {
+                    deleted_count += 1;
+                    log_action(
+                        "Deleted",
+                        format!(
+                            "worker {}/{}",
+                            component_name.0.bold().blue(),
+                            worker_name.bold().green()
+                        ),
+                    );
+                }
+                Err(error) => {
+                    failed_count += 1;
+                    log_error(format!(
+                        "Failed to delete worker {}/{}: {}",
+                        component_name.0.bold().blue(),
+                        worker_name.bold().green(),
+                        error
+                    ));
+                }
+            }
+        }
+
+        if failed_count > 0 {
+            log_error(format!(
+                "Deleted {} worker(s), failed to delete {} worker(s)",
+                deleted_count.to_string().log_color_highlight(),
+                failed_count.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
 
         log_action(
-            "Resumed",
-            format!("worker {}", format_worker_name_match(&worker_name_match)),
+            "Deleted",
+            format!(
+                "{} worker(s)",
+                deleted_count.to_string().log_color_highlight()
+            ),
         );
 
         Ok(())
     }
 
-    async fn cmd_update(
+    async fn cmd_set_env(
         &mut self,
         worker_name: WorkerNameArg,
-        mode: WorkerUpdateMode,
-        target_version: u64,
+        env: Vec<(String, String)>,
+        restart: bool,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
@@ -931,71 +2771,62 @@ impl WorkerCommandHandler {
             .component_by_worker_name_match(&worker_name_match)
             .await?;
 
-        self.update_worker(
-            &component.component_name,
-            component.versioned_component_id.component_id,
-            &worker_name.0,
-            mode,
-            target_version,
-        )
-        .await?;
-
-        Ok(())
-    }
+        if !restart {
+            log_error(format!(
+                "Updating the environment of worker {} is not supported by the server, workers \
+                get their environment when they are created",
+                format_worker_name_match(&worker_name_match)
+            ));
+            logln("");
+            logln(format!(
+                "Use {} to delete and recreate the worker with the merged environment, \
+                this loses the state of the worker",
+                "--restart".log_color_highlight()
+            ));
+            logln("");
+            bail!(NonSuccessfulExit);
+        }
 
-    async fn cmd_get(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
-        self.ctx.silence_app_context_init().await;
-        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
-        let (component, worker_name) = self
-            .component_by_worker_name_match(&worker_name_match)
+        let worker_metadata = self
+            .worker_metadata(
+                component.versioned_component_id.component_id,
+                &worker_name_match.component_name,
+                &worker_name,
+            )
             .await?;
 
-        let result = match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => {
-                let result = clients
-                    .worker
-                    .get_worker_metadata(
-                        &component.versioned_component_id.component_id,
-                        &worker_name.0,
-                    )
-                    .await
-                    .map_service_error()?;
-
-                WorkerMetadata::from_oss(worker_name_match.component_name, result)
-            }
-            GolemClients::Cloud(clients) => {
-                let result = clients
-                    .worker
-                    .get_worker_metadata(
-                        &component.versioned_component_id.component_id,
-                        &worker_name.0,
-                    )
-                    .await
-                    .map_service_error()?;
-
-                WorkerMetadata::from_cloud(worker_name_match.component_name, result)
-            }
-        };
-
-        self.ctx
-            .log_handler()
-            .log_view(&WorkerGetView::from(result));
-
-        Ok(())
-    }
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_restart_worker_with_env(
+                &format_worker_name_match(&worker_name_match),
+                worker_metadata.component_version,
+            )?
+        {
+            bail!(NonSuccessfulExit);
+        }
 
-    async fn cmd_delete(&mut self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
-        self.ctx.silence_app_context_init().await;
-        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
-        let (component, worker_name) = self
-            .component_by_worker_name_match(&worker_name_match)
-            .await?;
+        let original_version = worker_metadata.component_version;
+        let mut merged_env = worker_metadata.env;
+        merged_env.extend(env);
 
+        let recovery_hint = worker_recreate_hint(
+            &worker_name_match,
+            &worker_metadata.args,
+            &merged_env,
+            original_version,
+        )?;
         log_warn_action(
             "Deleting",
             format!("worker {}", format_worker_name_match(&worker_name_match)),
         );
-
+        {
+            let _indent = LogIndent::new();
+            logln(format!(
+                "If recreating the worker fails, it can be recreated manually with:\n{}\n{}",
+                recovery_hint, WORKER_RECREATE_HINT_ENV_NOTE
+            ));
+        }
         self.delete(
             component.versioned_component_id.component_id,
             &worker_name.0,
@@ -1003,9 +2834,59 @@ impl WorkerCommandHandler {
         .await?;
 
         log_action(
-            "Deleted",
-            format!("worker {}", format_worker_name_match(&worker_name_match)),
+            "Recreating",
+            format!(
+                "worker {} with updated environment",
+                format_worker_name_match(&worker_name_match)
+            ),
         );
+        if let Err(error) = self
+            .new_worker(
+                component.versioned_component_id.component_id,
+                worker_name.0.clone(),
+                worker_metadata.args,
+                merged_env,
+            )
+            .await
+        {
+            log_error(format!(
+                "Failed to recreate worker {}, the worker was already deleted",
+                format_worker_name_match(&worker_name_match)
+            ));
+            logln("");
+            logln("Recreate it manually with:");
+            logln(recovery_hint);
+            logln(WORKER_RECREATE_HINT_ENV_NOTE);
+            logln("");
+            return Err(error);
+        }
+
+        // New workers are always created with the latest component version, so the recreated
+        // worker is updated back to the original one, the update is only replaying the creation
+        let recreated_version = self
+            .worker_metadata(
+                component.versioned_component_id.component_id,
+                &worker_name_match.component_name,
+                &worker_name,
+            )
+            .await?
+            .component_version;
+        if recreated_version != original_version {
+            self.update_worker(
+                &worker_name_match.component_name,
+                component.versioned_component_id.component_id,
+                &worker_name.0,
+                WorkerUpdateMode::Automatic,
+                original_version,
+            )
+            .await?;
+        }
+
+        logln("");
+        self.ctx.log_handler().log_view(&WorkerCreateView {
+            component_name: worker_name_match.component_name,
+            worker_name: Some(worker_name),
+        });
 
         Ok(())
     }
@@ -1017,33 +2898,93 @@ impl WorkerCommandHandler {
         args: Vec<String>,
         env: HashMap<String, String>,
     ) -> anyhow::Result<()> {
-        match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => clients
-                .worker
-                .launch_new_worker(
-                    &component_id,
-                    &WorkerCreationRequestOss {
-                        name: worker_name,
-                        args,
-                        env,
-                    },
-                )
-                .await
-                .map(|_| ())
-                .map_service_error(),
-            GolemClients::Cloud(clients) => clients
-                .worker
-                .launch_new_worker(
-                    &component_id,
-                    &WorkerCreationRequestCloud {
-                        name: worker_name,
-                        args,
-                        env,
-                    },
-                )
-                .await
-                .map(|_| ())
-                .map_service_error(),
+        self.ctx
+            .throttle()
+            .call(|| async {
+                match self.ctx.golem_clients().await? {
+                    GolemClients::Oss(clients) => clients
+                        .worker
+                        .launch_new_worker(
+                            &component_id,
+                            &WorkerCreationRequestOss {
+                                name: worker_name.clone(),
+                                args: args.clone(),
+                                env: env.clone(),
+                            },
+                        )
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                    GolemClients::Cloud(clients) => clients
+                        .worker
+                        .launch_new_worker(
+                            &component_id,
+                            &WorkerCreationRequestCloud {
+                                name: worker_name.clone(),
+                                args: args.clone(),
+                                env: env.clone(),
+                            },
+                        )
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                }
+            })
+            .await
+    }
+
+    /// Waits until a newly created worker becomes idle or running, failing on instantiation errors
+    async fn await_worker_ready(
+        &self,
+        component_id: Uuid,
+        component_name: &ComponentName,
+        worker_name: &WorkerName,
+        timeout: Duration,
+    ) -> anyhow::Result<()> {
+        let started_at = time::Instant::now();
+
+        loop {
+            let metadata = self
+                .worker_metadata(component_id, component_name, worker_name)
+                .await?;
+
+            match metadata.status {
+                WorkerStatus::Idle | WorkerStatus::Running => {
+                    log_action(
+                        "Ready",
+                        format!(
+                            "worker {}, status: {}",
+                            worker_name.0.log_color_highlight(),
+                            format_status(&metadata.status)
+                        ),
+                    );
+                    return Ok(());
+                }
+                WorkerStatus::Failed | WorkerStatus::Exited | WorkerStatus::Interrupted => {
+                    log_error(format!(
+                        "Worker {} failed to start, status: {}",
+                        worker_name.0.log_color_highlight(),
+                        format_status(&metadata.status)
+                    ));
+                    log_worker_last_error(&metadata);
+                    bail!(NonSuccessfulExit);
+                }
+                WorkerStatus::Suspended | WorkerStatus::Retrying => {}
+            }
+
+            if started_at.elapsed() >= timeout {
+                log_error(format!(
+                    "Worker {} did not become ready in {}s, status: {}",
+                    worker_name.0.log_color_highlight(),
+                    timeout.as_secs(),
+                    format_status(&metadata.status)
+                ));
+                log_worker_last_error(&metadata);
+                bail!(TimeoutError);
+            }
+
+            self.ctx.cancellation_token().check()?;
+            time::sleep(WORKER_READY_POLL_INTERVAL).await;
         }
     }
 
@@ -1077,21 +3018,26 @@ impl WorkerCommandHandler {
         Ok(result)
     }
 
-    async fn delete(&self, component_id: Uuid, worker_name: &str) -> anyhow::Result<()> {
-        match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => clients
-                .worker
-                .delete_worker(&component_id, worker_name)
-                .await
-                .map(|_| ())
-                .map_service_error(),
-            GolemClients::Cloud(clients) => clients
-                .worker
-                .delete_worker(&component_id, worker_name)
-                .await
-                .map(|_| ())
-                .map_service_error(),
-        }
+    pub async fn delete(&self, component_id: Uuid, worker_name: &str) -> anyhow::Result<()> {
+        self.ctx
+            .throttle()
+            .call(|| async {
+                match self.ctx.golem_clients().await? {
+                    GolemClients::Oss(clients) => clients
+                        .worker
+                        .delete_worker(&component_id, worker_name)
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                    GolemClients::Cloud(clients) => clients
+                        .worker
+                        .delete_worker(&component_id, worker_name)
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                }
+            })
+            .await
     }
 
     pub async fn update_component_workers(
@@ -1127,8 +3073,22 @@ impl WorkerCommandHandler {
         );
         let _indent = LogIndent::new();
 
+        let worker_count = workers.len();
         let mut update_results = TryUpdateAllWorkersResult::default();
-        for worker in workers {
+        for (worker_idx, worker) in workers.into_iter().enumerate() {
+            if self.ctx.cancellation_token().is_cancelled() {
+                log_warn_action(
+                    "Skipping",
+                    format!(
+                        "updating the remaining {} worker(s), cancelled",
+                        (worker_count - worker_idx)
+                            .to_string()
+                            .log_color_highlight()
+                    ),
+                );
+                break;
+            }
+
             let result = self
                 .update_worker(
                     component_name,
@@ -1149,7 +3109,7 @@ impl WorkerCommandHandler {
                     });
                 }
                 Err(error) => {
-                    update_results.triggered.push(WorkerUpdateAttempt {
+                    update_results.failed.push(WorkerUpdateAttempt {
                         component_name: component_name.clone(),
                         target_version,
                         worker_name: worker.worker_id.worker_name.as_str().into(),
@@ -1181,48 +3141,54 @@ impl WorkerCommandHandler {
             ),
         );
 
-        let result = match self.ctx.golem_clients().await? {
-            GolemClients::Oss(clients) => clients
-                .worker
-                .update_worker(
-                    &component_id,
-                    worker_name,
-                    &UpdateWorkerRequestOss {
-                        mode: match update_mode {
-                            WorkerUpdateMode::Automatic => {
-                                golem_client::model::WorkerUpdateMode::Automatic
-                            }
-                            WorkerUpdateMode::Manual => {
-                                golem_client::model::WorkerUpdateMode::Manual
-                            }
-                        },
-                        target_version,
-                    },
-                )
-                .await
-                .map(|_| ())
-                .map_service_error(),
-            GolemClients::Cloud(clients) => clients
-                .worker
-                .update_worker(
-                    &component_id,
-                    worker_name,
-                    &UpdateWorkerRequestCloud {
-                        mode: match update_mode {
-                            WorkerUpdateMode::Automatic => {
-                                golem_cloud_client::model::WorkerUpdateMode::Automatic
-                            }
-                            WorkerUpdateMode::Manual => {
-                                golem_cloud_client::model::WorkerUpdateMode::Manual
-                            }
-                        },
-                        target_version,
-                    },
-                )
-                .await
-                .map(|_| ())
-                .map_service_error(),
-        };
+        let result = self
+            .ctx
+            .throttle()
+            .call(|| async {
+                match self.ctx.golem_clients().await? {
+                    GolemClients::Oss(clients) => clients
+                        .worker
+                        .update_worker(
+                            &component_id,
+                            worker_name,
+                            &UpdateWorkerRequestOss {
+                                mode: match update_mode {
+                                    WorkerUpdateMode::Automatic => {
+                                        golem_client::model::WorkerUpdateMode::Automatic
+                                    }
+                                    WorkerUpdateMode::Manual => {
+                                        golem_client::model::WorkerUpdateMode::Manual
+                                    }
+                                },
+                                target_version,
+                            },
+                        )
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                    GolemClients::Cloud(clients) => clients
+                        .worker
+                        .update_worker(
+                            &component_id,
+                            worker_name,
+                            &UpdateWorkerRequestCloud {
+                                mode: match update_mode {
+                                    WorkerUpdateMode::Automatic => {
+                                        golem_cloud_client::model::WorkerUpdateMode::Automatic
+                                    }
+                                    WorkerUpdateMode::Manual => {
+                                        golem_cloud_client::model::WorkerUpdateMode::Manual
+                                    }
+                                },
+                                target_version,
+                            },
+                        )
+                        .await
+                        .map(|_| ())
+                        .map_service_error(),
+                }
+            })
+            .await;
 
         match result {
             Ok(_) => {
@@ -1276,8 +3242,10 @@ impl WorkerCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
+        let app_env = self.app_component_env(component_name).await?;
         for worker in workers {
-            self.redeploy_worker(component_name, worker).await?;
+            self.redeploy_worker(component_name, worker, &app_env)
+                .await?;
         }
 
         Ok(())
@@ -1287,6 +3255,7 @@ impl WorkerCommandHandler {
         &self,
         component_name: &ComponentName,
         worker_metadata: WorkerMetadata,
+        app_env: &HashMap<String, String>,
     ) -> anyhow::Result<()> {
         log_warn_action(
             "Redeploying",
@@ -1325,7 +3294,7 @@ impl WorkerCommandHandler {
             worker_metadata.worker_id.component_id.0,
             worker_metadata.worker_id.worker_name,
             worker_metadata.args,
-            worker_metadata.env,
+            with_app_env(app_env.clone(), worker_metadata.env),
         )
         .await?;
         log_action("Recreated", "worker");
@@ -1333,6 +3302,44 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    /// Worker env defined for the component in the application manifest, with secret references
+    /// resolved. Returns an empty env for components which are not part of the application.
+    async fn app_component_env(
+        &self,
+        component_name: &ComponentName,
+    ) -> anyhow::Result<HashMap<String, String>> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let Some(app_ctx) = app_ctx.opt()? else {
+            return Ok(HashMap::new());
+        };
+
+        let app_component_name = AppComponentName::from(component_name.0.clone());
+        if !app_ctx.application.contains_component(&app_component_name) {
+            return Ok(HashMap::new());
+        }
+
+        let env = &app_ctx
+            .application
+            .component_properties(&app_component_name, self.ctx.build_profile())
+            .env;
+        if env.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        resolve_env_secrets(
+            env,
+            app_ctx
+                .application
+                .component_source_dir(&app_component_name),
+        )
+        .with_context(|| {
+            anyhow!(
+                "Failed to resolve the env of component {}",
+                component_name.0.log_color_highlight()
+            )
+        })
+    }
+
     pub async fn list_component_workers(
         &self,
         component_name: &ComponentName,
@@ -1413,7 +3420,7 @@ impl WorkerCommandHandler {
         Ok((workers, final_result_cursor))
     }
 
-    async fn component_by_worker_name_match(
+    pub async fn component_by_worker_name_match(
         &mut self,
         worker_name_match: &WorkerNameMatch,
     ) -> anyhow::Result<(Component, WorkerName)> {
@@ -1469,15 +3476,46 @@ impl WorkerCommandHandler {
             }
             GolemClients::Cloud(clients) => clients
                 .worker
-                .resume_worker(
+                .resume_worker(
+                    &component.versioned_component_id.component_id,
+                    &worker_name.0,
+                )
+                .await
+                .map(|_| ())
+                .map_service_error()?,
+        }
+        Ok(())
+    }
+
+    async fn cancel_invocation(
+        &self,
+        component: &Component,
+        worker_name: &WorkerName,
+        idempotency_key: &IdempotencyKey,
+    ) -> anyhow::Result<bool> {
+        let canceled = match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => clients
+                .worker
+                .cancel_invocation(
+                    &component.versioned_component_id.component_id,
+                    &worker_name.0,
+                    &idempotency_key.0,
+                )
+                .await
+                .map(|result| (result.canceled))
+                .map_service_error()?,
+            GolemClients::Cloud(clients) => clients
+                .worker
+                .cancel_invocation(
                     &component.versioned_component_id.component_id,
                     &worker_name.0,
+                    &idempotency_key.0,
                 )
                 .await
-                .map(|_| ())
+                .map(|result| (result.canceled))
                 .map_service_error()?,
-        }
-        Ok(())
+        };
+        Ok(canceled)
     }
 
     async fn interrupt_worker(
@@ -1513,6 +3551,260 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    /// Streams the combined output of all workers of the given components until cancelled,
+    /// workers created or recreated while streaming are connected on the next poll
+    pub async fn stream_component_workers(
+        &self,
+        components: &[(ComponentName, Uuid)],
+        connect_options: WorkerConnectOptions,
+        poll_interval: Duration,
+    ) -> anyhow::Result<()> {
+        let cancellation_token = self.ctx.cancellation_token().clone();
+        let _cancellable_scope = cancellation_token.cancellable_scope();
+
+        let auth_token = self.ctx.auth_token().await?;
+        // A connection ends when its worker is deleted, so finished connections are dropped on
+        // every poll, and workers which are listed again (e.g. recreated) are reconnected
+        let mut connections = BTreeMap::<(String, String), JoinHandle<()>>::new();
+        let mut failed = BTreeSet::<(String, String)>::new();
+
+        loop {
+            connections.retain(|(component_name, worker_name), connection| {
+                if connection.is_finished() {
+                    let source = format!("{}/{}", component_name, worker_name);
+                    log_warn_action(
+                        "Disconnected",
+                        format!(
+                            "from worker {}, streaming ended, it is reconnected if the worker is listed again",
+                            source.log_color_highlight()
+                        ),
+                    );
+                    false
+                } else {
+                    true
+                }
+            });
+
+            for (component_name, component_id) in components {
+                let (workers, _) = self
+                    .list_component_workers(component_name, *component_id, None, None, None, true)
+                    .await?;
+                for worker in workers {
+                    let worker_name = worker.worker_id.worker_name;
+                    let key = (component_name.0.clone(), worker_name.clone());
+                    if connections.contains_key(&key) {
+                        continue;
+                    }
+
+                    let source = format!("{}/{}", component_name.0, worker_name);
+                    match connect_to_worker(
+                        self.ctx.worker_service_url().clone(),
+                        auth_token.clone(),
+                        *component_id,
+                        worker_name,
+                        ConnectOutput::new(connect_options.clone(), self.ctx.format())
+                            .with_source(Some(source.clone())),
+                        self.ctx.allow_insecure(),
+                    )
+                    .await
+                    {
+                        Ok(connection) => {
+                            log_action(
+                                "Connected",
+                                format!("to worker {}", source.log_color_highlight()),
+                            );
+                            failed.remove(&key);
+                            connections.insert(key, task::spawn(connection.read_messages()));
+                        }
+                        Err(error) => {
+                            // Retried on every poll, but only reported once
+                            if failed.insert(key) {
+                                log_warn(format!(
+                                    "Failed to connect to worker {}: {}",
+                                    source.log_color_highlight(),
+                                    error
+                                ))
+                            }
+                        }
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = time::sleep(poll_interval) => {}
+                _ = cancellation_token.cancelled() => {}
+            }
+            if cancellation_token.is_cancelled() {
+                for connection in connections.values() {
+                    connection.abort();
+                }
+                log_warn_action("Stopping", "streaming, cancelled");
+                return cancellation_token.check();
+            }
+        }
+    }
+
+    pub async fn bench_invocations(
+        &mut self,
+        worker_name_match: &WorkerNameMatch,
+        component: &Component,
+        function_name: &str,
+        arguments: Vec<WorkerFunctionArgument>,
+        warmup: u32,
+        iterations: u32,
+    ) -> anyhow::Result<InvocationBenchmark> {
+        let function_name =
+            self.match_function_name(&worker_name_match.component_name, component, function_name)?;
+        let arguments = wave_args_to_invoke_args(component, &function_name, arguments)?;
+
+        log_action(
+            "Benchmarking",
+            format!(
+                "worker {}/{} with {} warmup and {} measured invocations",
+                format_worker_name_match(worker_name_match),
+                format_export(&function_name),
+                warmup.to_string().log_color_highlight(),
+                iterations.to_string().log_color_highlight()
+            ),
+        );
+
+        let worker_name = worker_name_match.worker_name.as_ref();
+
+        for _ in 0..warmup {
+            self.ctx.cancellation_token().check()?;
+            self.bench_invoke_and_await(component, worker_name, &function_name, arguments.clone())
+                .await?;
+        }
+
+        let mut latencies = Vec::with_capacity(iterations as usize);
+        let started_at = time::Instant::now();
+        for _ in 0..iterations {
+            self.ctx.cancellation_token().check()?;
+            let invocation_started_at = time::Instant::now();
+            self.bench_invoke_and_await(component, worker_name, &function_name, arguments.clone())
+                .await?;
+            latencies.push(invocation_started_at.elapsed());
+        }
+
+        Ok(InvocationBenchmark::new(
+            function_name,
+            latencies,
+            started_at.elapsed(),
+        ))
+    }
+
+    async fn bench_invoke_and_await(
+        &self,
+        component: &Component,
+        worker_name: Option<&WorkerName>,
+        function_name: &str,
+        arguments: Vec<OptionallyTypeAnnotatedValueJson>,
+    ) -> anyhow::Result<()> {
+        let component_id = &component.versioned_component_id.component_id;
+        let idempotency_key = IdempotencyKey::new();
+
+        match self.ctx.golem_clients().await? {
+            GolemClients::Oss(clients) => match worker_name {
+                Some(worker_name) => clients
+                    .worker_invoke
+                    .invoke_and_await_function(
+                        component_id,
+                        &worker_name.0,
+                        Some(&idempotency_key.0),
+                        function_name,
+                        &InvokeParametersOss { params: arguments },
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_service_error(),
+                None => clients
+                    .worker_invoke
+                    .invoke_and_await_function_without_name(
+                        component_id,
+                        Some(&idempotency_key.0),
+                        function_name,
+                        &InvokeParametersOss { params: arguments },
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_service_error(),
+            },
+            GolemClients::Cloud(clients) => match worker_name {
+                Some(worker_name) => clients
+                    .worker_invoke
+                    .invoke_and_await_function(
+                        component_id,
+                        &worker_name.0,
+                        Some(&idempotency_key.0),
+                        function_name,
+                        &InvokeParametersCloud { params: arguments },
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_service_error(),
+                None => clients
+                    .worker_invoke
+                    .invoke_and_await_function_without_name(
+                        component_id,
+                        Some(&idempotency_key.0),
+                        function_name,
+                        &InvokeParametersCloud { params: arguments },
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_service_error(),
+            },
+        }
+    }
+
+    async fn match_worker_urn(&self, worker_urn: &str) -> anyhow::Result<WorkerNameMatch> {
+        let worker_urn = match worker_urn.parse::<WorkerUrn>() {
+            Ok(worker_urn) => worker_urn,
+            Err(err) => {
+                logln("");
+                log_error(err);
+                logln("");
+                log_text_view(&WorkerNameHelp);
+                bail!(NonSuccessfulExit);
+            }
+        };
+
+        let Some(component) = self
+            .ctx
+            .component_handler()
+            .component_by_id(&worker_urn.component_id)
+            .await?
+        else {
+            log_error(format!(
+                "Component {} not found",
+                worker_urn
+                    .component_id
+                    .to_string()
+                    .log_color_error_highlight()
+            ));
+            logln("");
+            bail!(NonSuccessfulExit);
+        };
+
+        let project = match &component.project_id {
+            Some(project_id) => Some(
+                self.ctx
+                    .cloud_project_handler()
+                    .project_by_id(project_id)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        Ok(WorkerNameMatch {
+            account_id: None,
+            project,
+            component_name_match_kind: ComponentNameMatchKind::Unknown,
+            component_name: component.component_name,
+            worker_name: Some(worker_urn.worker_name),
+        })
+    }
+
     pub async fn match_worker_name(
         &mut self,
         worker_name: WorkerName,
@@ -1521,6 +3813,10 @@ impl WorkerCommandHandler {
             (worker_name != "-").then(|| worker_name.into())
         }
 
+        if WorkerUrn::is_urn(&worker_name.0) {
+            return self.match_worker_urn(&worker_name.0).await;
+        }
+
         let segments = worker_name.0.split("/").collect::<Vec<&str>>();
         match segments.len() {
             // <WORKER>
@@ -1735,6 +4031,109 @@ impl WorkerCommandHandler {
     }
 }
 
+fn resolve_idempotency_key(idempotency_key: Option<IdempotencyKey>) -> IdempotencyKey {
+    fn new_idempotency_key() -> IdempotencyKey {
+        let key = IdempotencyKey::new();
+        log_action(
+            "Using",
+            format!("generated idempotency key: {}", key.0.log_color_highlight()),
+        );
+        key
+    }
+
+    match idempotency_key {
+        Some(idempotency_key) if idempotency_key.0 == "-" => new_idempotency_key(),
+        Some(idempotency_key) => {
+            log_action(
+                "Using",
+                format!(
+                    "requested idempotency key: {}",
+                    idempotency_key.0.log_color_highlight()
+                ),
+            );
+            idempotency_key
+        }
+        None => new_idempotency_key(),
+    }
+}
+
+/// Inserts the file arguments between the WAVE arguments, encoded as WAVE list<u8> values
+async fn insert_file_args(
+    component: &Component,
+    function_name: &str,
+    mut wave_args: Vec<String>,
+    mut file_args: Vec<WorkerFunctionFileArgument>,
+) -> anyhow::Result<Vec<String>> {
+    if file_args.is_empty() {
+        return Ok(wave_args);
+    }
+
+    let types = function_params_types(component, function_name)?;
+
+    file_args.sort_by_key(|file_arg| file_arg.position);
+    if let Some((_, file_arg)) = file_args
+        .iter()
+        .tuple_windows()
+        .find(|(left, right)| left.position == right.position)
+    {
+        log_error(format!(
+            "Multiple file arguments for parameter {}",
+            file_arg.position.to_string().log_color_highlight()
+        ));
+        bail!(NonSuccessfulExit);
+    }
+
+    for file_arg in file_args {
+        let index = file_arg.position - 1;
+
+        let is_byte_list = matches!(
+            types.get(index),
+            Some(AnalysedType::List(TypeList { inner })) if matches!(inner.as_ref(), AnalysedType::U8(_))
+        );
+        if !is_byte_list {
+            log_error(format!(
+                "Parameter {} of {} is not a list<u8>, it cannot be used as a file argument",
+                file_arg.position.to_string().log_color_highlight(),
+                format_export(function_name)
+            ));
+            bail!(NonSuccessfulExit);
+        }
+        if index > wave_args.len() {
+            log_error(format!(
+                "Missing WAVE arguments before the file argument for parameter {}",
+                file_arg.position.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let content = tokio::fs::read(&file_arg.path).await.with_context(|| {
+            anyhow!(
+                "Failed to read file argument {}",
+                file_arg
+                    .path
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight()
+            )
+        })?;
+        wave_args.insert(index, format!("[{}]", content.iter().join(", ")));
+    }
+
+    Ok(wave_args)
+}
+
+/// Positional and --arg arguments are mutually exclusive, so only one of them can be non-empty
+fn merge_arguments(
+    arguments: Vec<WorkerFunctionArgument>,
+    named_arguments: Vec<WorkerFunctionArgument>,
+) -> Vec<WorkerFunctionArgument> {
+    if named_arguments.is_empty() {
+        arguments
+    } else {
+        named_arguments
+    }
+}
+
 fn wave_args_to_invoke_args(
     component: &Component,
     function_name: &str,
@@ -1819,6 +4218,21 @@ fn wave_args_to_invoke_args(
         .map_err(|err| anyhow!("Failed to convert type annotated value: {err}"))
 }
 
+fn log_worker_last_error(worker: &WorkerMetadata) {
+    if let Some(last_error) = &worker.last_error {
+        logln("");
+        logln("Last error:".log_color_help_group().to_string());
+        let _indent = LogIndent::new();
+        logln(last_error);
+    }
+}
+
+/// Appends the label selectors to the worker metadata filters
+fn filters_with_selectors(mut filters: Vec<String>, selectors: &[WorkerLabel]) -> Vec<String> {
+    filters.extend(selectors.iter().map(WorkerLabel::filter));
+    filters
+}
+
 fn scan_cursor_to_string(cursor: &ScanCursor) -> String {
     format!("{}/{}", cursor.layer, cursor.cursor)
 }
@@ -1870,6 +4284,151 @@ fn parse_worker_error(status: u16, body: Vec<u8>) -> ServiceError {
     }
 }
 
+/// Connects to the worker of an invocation for streaming its output, if the worker does not
+/// exist yet, connecting is retried in the background until the invocation creates it.
+/// Other connection errors are reported as warnings, and the invocation continues without
+/// streaming.
+async fn connect_to_invoked_worker(
+    worker_service_url: Url,
+    auth_token: Option<String>,
+    component_id: Uuid,
+    worker_name: String,
+    output: ConnectOutput,
+    allow_insecure: bool,
+) -> Option<JoinHandle<()>> {
+    let connect = move || {
+        connect_to_worker(
+            worker_service_url.clone(),
+            auth_token.clone(),
+            component_id,
+            worker_name.clone(),
+            output.clone(),
+            allow_insecure,
+        )
+    };
+
+    match connect().await {
+        Ok(connection) => Some(task::spawn(connection.read_messages())),
+        Err(error) if is_worker_not_found(&error) => {
+            debug!("Worker not found, connecting after the invocation created it");
+            Some(task::spawn(async move {
+                // Events emitted before connecting are replayed by the connection
+                loop {
+                    time::sleep(INVOKE_STREAM_CONNECT_RETRY_INTERVAL).await;
+                    match connect().await {
+                        Ok(connection) => break connection.read_messages().await,
+                        Err(error) if is_worker_not_found(&error) => {
+                            trace!("Worker not found yet, retrying connect");
+                        }
+                        Err(error) => break log_stream_connect_error(&error),
+                    }
+                }
+            }))
+        }
+        Err(error) => {
+            log_stream_connect_error(&error);
+            None
+        }
+    }
+}
+
+fn is_worker_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<ServiceError>()
+        .and_then(ServiceError::status_code)
+        == Some(404)
+}
+
+fn log_stream_connect_error(error: &anyhow::Error) {
+    log_warn(format!(
+        "Failed to connect to the worker, invoking without streaming: {error:#}"
+    ));
+}
+
+/// Worker env based on the env defined in the application manifest, explicitly set worker env
+/// takes precedence over it
+fn with_app_env(
+    app_env: HashMap<String, String>,
+    worker_env: impl IntoIterator<Item = (String, String)>,
+) -> HashMap<String, String> {
+    let mut env = app_env;
+    env.extend(worker_env);
+    env
+}
+
+const WORKER_RECREATE_HINT_ENV_NOTE: &str =
+    "The environment values are redacted as they can contain secrets, they have to be supplied again";
+
+/// Commands for recreating a worker deleted by `worker set-env --restart`, new workers are
+/// created with the latest component version, so the second command updates it back.
+/// Env values are redacted, as they can contain resolved secrets.
+fn worker_recreate_hint(
+    worker_name_match: &WorkerNameMatch,
+    args: &[String],
+    env: &HashMap<String, String>,
+    component_version: u64,
+) -> anyhow::Result<String> {
+    let worker_name = worker_name_match
+        .account_id
+        .iter()
+        .map(|account_id| account_id.0.as_str())
+        .chain(
+            worker_name_match
+                .project
+                .iter()
+                .map(|project| project.project_name.0.as_str()),
+        )
+        .chain([
+            worker_name_match.component_name.0.as_str(),
+            worker_name_match
+                .worker_name
+                .as_ref()
+                .map(|worker_name| worker_name.0.as_str())
+                .unwrap_or("-"),
+        ])
+        .join("/");
+
+    let env = env
+        .keys()
+        .map(|key| format!("{key}=<redacted>"))
+        .sorted()
+        .collect::<Vec<_>>();
+
+    let mut new_args = vec![command_name(), "worker".to_string(), "new".to_string()];
+    new_args.push(worker_name.clone());
+    for env in &env {
+        new_args.push("--env".to_string());
+        new_args.push(env.clone());
+    }
+    if !args.is_empty() {
+        new_args.push("--".to_string());
+        new_args.extend(args.iter().cloned());
+    }
+
+    let update_args = [
+        command_name(),
+        "worker".to_string(),
+        "update".to_string(),
+        worker_name,
+        WorkerUpdateMode::Automatic.to_string(),
+        component_version.to_string(),
+    ];
+
+    Ok(format!(
+        "{}\n{}",
+        shlex::try_join(new_args.iter().map(|arg| arg.as_str()))?,
+        shlex::try_join(update_args.iter().map(|arg| arg.as_str()))?
+    ))
+}
+
+/// Header line in curl config file syntax
+fn curl_config_header(header: &str) -> String {
+    format!(
+        "header = \"{}\"\n",
+        header.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
 struct WorkerConnection {
     pings: JoinHandle<anyhow::Error>,
     read_messages: JoinHandle<()>,
@@ -1879,19 +4438,57 @@ impl WorkerConnection {
     async fn read_messages(self) {
         let pings = self.pings;
         let read_res = self.read_messages;
+        // Stops the connection tasks if reading gets dropped before completion,
+        // e.g. when a served stream request is cancelled
+        let _abort_on_drop = AbortOnDrop(vec![pings.abort_handle(), read_res.abort_handle()]);
         pin_mut!(pings, read_res);
         future::select(pings, read_res).await;
     }
 }
 
+struct AbortOnDrop(Vec<AbortHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for handle in &self.0 {
+            handle.abort();
+        }
+    }
+}
+
+fn run_monitor_hook(command_tokens: &[String], worker: &WorkerMetadata) -> anyhow::Result<()> {
+    let status = std::process::Command::new(&command_tokens[0])
+        .args(&command_tokens[1..])
+        .env("GOLEM_COMPONENT_NAME", &worker.component_name.0)
+        .env("GOLEM_WORKER_NAME", &worker.worker_id.worker_name)
+        .env("GOLEM_WORKER_STATUS", worker.status.to_string())
+        .env(
+            "GOLEM_WORKER_ERROR",
+            worker.last_error.clone().unwrap_or_default(),
+        )
+        .status()
+        .context("Failed to execute command")?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "command failed with exit code: {}",
+            status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        ))
+    }
+}
+
 async fn connect_to_worker(
     worker_service_url: Url,
     auth_token: Option<String>,
     component_id: Uuid,
     worker_name: String,
-    connect_options: WorkerConnectOptions,
+    output: ConnectOutput,
     allow_insecure: bool,
-    format: Format,
 ) -> anyhow::Result<WorkerConnection> {
     let mut url = worker_service_url;
 
@@ -1938,7 +4535,7 @@ async fn connect_to_worker(
                 let status = http_error_response.status().as_u16();
                 match http_error_response.body().clone() {
                     Some(body) => anyhow!(parse_worker_error(status, body)),
-                    None => anyhow!("Websocket connect failed, HTTP error: {}", status),
+                    None => anyhow!(parse_worker_error(status, vec![])),
                 }
             }
             _ => anyhow!("Websocket connect failed, error: {}", e),
@@ -1967,8 +4564,6 @@ async fn connect_to_worker(
         }
     });
 
-    let output = ConnectOutput::new(connect_options, format);
-
     let read_messages = task::spawn(async move {
         read.for_each(move |message_or_error| {
             let output = output.clone();
@@ -2056,7 +4651,11 @@ async fn connect_to_worker(
                                 }
                                 WorkerEvent::Close => {} // TODO:
                                 WorkerEvent::InvocationStart { .. } => {} // TODO:
-                                WorkerEvent::InvocationFinished { .. } => {} // TODO:
+                                WorkerEvent::InvocationFinished {
+                                    idempotency_key, ..
+                                } => {
+                                    output.invocation_finished(&idempotency_key.value);
+                                }
                             },
                         }
                     }
@@ -2071,3 +4670,103 @@ async fn connect_to_worker(
         read_messages,
     })
 }
+
+struct WorkerUpdateRolloutOptions {
+    batch_size: usize,
+    await_timeout: Option<Duration>,
+    max_failure_ratio: f64,
+    rollback_on_failure: bool,
+}
+
+struct WorkerUpdateTarget {
+    component_name: ComponentName,
+    component_id: Uuid,
+    worker_name: WorkerName,
+    original_version: u64,
+    target_version: u64,
+    known_update_count: usize,
+}
+
+impl WorkerUpdateTarget {
+    fn attempt(&self, target_version: u64, error: Option<String>) -> WorkerUpdateAttempt {
+        WorkerUpdateAttempt {
+            component_name: self.component_name.clone(),
+            target_version,
+            worker_name: self.worker_name.clone(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::command_handler::worker::{curl_config_header, with_app_env, worker_recreate_hint};
+    use crate::command_name;
+    use crate::model::{ComponentName, ComponentNameMatchKind, WorkerName, WorkerNameMatch};
+    use assert2::{check, let_assert};
+    use std::collections::HashMap;
+
+    #[test]
+    fn curl_config_headers_are_quoted() {
+        check!(
+            curl_config_header("Authorization: Bearer abc")
+                == "header = \"Authorization: Bearer abc\"\n"
+        );
+        check!(curl_config_header(r#"X: a"b\c"#) == "header = \"X: a\\\"b\\\\c\"\n");
+    }
+
+    #[test]
+    fn worker_env_takes_precedence_over_app_env() {
+        let env = with_app_env(
+            HashMap::from([
+                ("KEY".to_string(), "manifest".to_string()),
+                ("APP_ONLY".to_string(), "app".to_string()),
+            ]),
+            HashMap::from([
+                ("KEY".to_string(), "x".to_string()),
+                ("WORKER_ONLY".to_string(), "worker".to_string()),
+            ]),
+        );
+
+        check!(
+            env == HashMap::from([
+                ("KEY".to_string(), "x".to_string()),
+                ("APP_ONLY".to_string(), "app".to_string()),
+                ("WORKER_ONLY".to_string(), "worker".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn worker_recreate_hint_restores_args_and_redacts_env() {
+        let worker_name_match = WorkerNameMatch {
+            account_id: None,
+            project: None,
+            component_name_match_kind: ComponentNameMatchKind::App,
+            component_name: ComponentName("app:component".to_string()),
+            worker_name: Some(WorkerName("worker-1".to_string())),
+        };
+        let env = HashMap::from([
+            ("B".to_string(), "two words".to_string()),
+            ("A".to_string(), "1".to_string()),
+        ]);
+
+        let_assert!(
+            Ok(hint) = worker_recreate_hint(
+                &worker_name_match,
+                &["--flag".to_string(), "arg".to_string()],
+                &env,
+                3
+            )
+        );
+        let command_name = command_name();
+        check!(
+            hint == format!(
+                "{command_name} worker new app:component/worker-1 --env 'A=<redacted>' --env 'B=<redacted>' -- --flag arg\n\
+                {command_name} worker update app:component/worker-1 auto 3"
+            )
+        );
+    }
+}
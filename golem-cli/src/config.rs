@@ -14,17 +14,20 @@
 
 use crate::cloud::CloudAuthenticationConfig;
 use crate::error::ContextInitHintError;
-use crate::model::{Format, HasFormatConfig};
+use crate::log::{log_warn_action, LogColorize};
+use crate::model::payload::parse_byte_size;
+use crate::model::{Format, HasFormatConfig, ProjectName};
 use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter};
 use std::fs::{create_dir_all, File, OpenOptions};
-use std::io::{BufReader, BufWriter};
+use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use url::Url;
 
@@ -32,13 +35,68 @@ pub const CLOUD_URL: &str = "https://release.api.golem.cloud";
 pub const DEFAULT_OSS_URL: &str = "http://localhost:9881";
 
 // TODO: review and separate model, config and serialization parts
-// TODO: when doing the serialization we can do a legacy migration
+
+/// Migrations are applied on the raw JSON representation of the config, so they do not depend
+/// on the current config model. The migration at index N upgrades the config from version N to N + 1.
+const CONFIG_MIGRATIONS: &[ConfigMigration] = &[ConfigMigration {
+    description: "Replace the legacy default profiles with the builtin local and cloud profiles",
+    migrate: migrate_legacy_default_profiles,
+}];
+
+pub const CONFIG_VERSION: u32 = CONFIG_MIGRATIONS.len() as u32;
+
+struct ConfigMigration {
+    description: &'static str,
+    migrate: fn(&mut serde_json::Value) -> anyhow::Result<()>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigMigrationMode {
+    /// Migrated configs are saved, after creating a backup of the original config file
+    Persist,
+    /// Migrations are only applied on the loaded config, the config file is not changed
+    InMemory,
+    /// Like InMemory, but an invalid config file is salvaged in memory instead of failing,
+    /// used by the repair command
+    Repair,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigMigrationResult {
+    pub config_path: PathBuf,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub applied_migrations: Vec<String>,
+    pub backup_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigRepairResult {
+    pub config_path: PathBuf,
+    pub valid: bool,
+    pub salvaged_profiles: Vec<ProfileName>,
+    pub dropped_profiles: Vec<DroppedProfile>,
+    pub backup_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DroppedProfile {
+    pub name: ProfileName,
+    pub reason: String,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
+    #[serde(default)]
+    pub config_version: u32,
     pub profiles: HashMap<ProfileName, Profile>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub default_profile: Option<ProfileName>,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub history_enabled: bool,
+    /// User defined command aliases, expanded before parsing the command line
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub aliases: BTreeMap<String, String>,
     // TODO: these are deprecated now, remove them properly
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub active_profile: Option<ProfileName>,
@@ -177,6 +235,137 @@ impl Profile {
             Profile::GolemCloud(_) => ProfileKind::Cloud,
         }
     }
+
+    pub fn config_value(&self, key: ProfileConfigKey) -> Option<String> {
+        let config = self.get_config();
+        match key {
+            ProfileConfigKey::Url => match self {
+                Profile::Golem(profile) => Some(profile.url.to_string()),
+                Profile::GolemCloud(profile) => profile.custom_url.as_ref().map(Url::to_string),
+            },
+            ProfileConfigKey::WorkerUrl => match self {
+                Profile::Golem(profile) => profile.worker_url.as_ref().map(Url::to_string),
+                Profile::GolemCloud(profile) => {
+                    profile.custom_worker_url.as_ref().map(Url::to_string)
+                }
+            },
+            ProfileConfigKey::CloudUrl => match self {
+                Profile::Golem(_) => None,
+                Profile::GolemCloud(profile) => {
+                    profile.custom_cloud_url.as_ref().map(Url::to_string)
+                }
+            },
+            ProfileConfigKey::Format => Some(config.default_format.to_string()),
+            ProfileConfigKey::DefaultProject => config
+                .default_project
+                .as_ref()
+                .map(|project| project.0.clone()),
+            ProfileConfigKey::Timeout => config.timeout_seconds.map(|t| t.to_string()),
+            ProfileConfigKey::MaxUploadSize => config.max_upload_size.map(|s| s.to_string()),
+            ProfileConfigKey::MaxInvokePayloadSize => {
+                config.max_invoke_payload_size.map(|s| s.to_string())
+            }
+            ProfileConfigKey::ReadOnly => Some(config.read_only.to_string()),
+        }
+    }
+
+    /// Validates and sets a config value, returns the validation error message on failure
+    pub fn set_config_value(&mut self, key: ProfileConfigKey, value: &str) -> Result<(), String> {
+        fn parse_url(value: &str) -> Result<Url, String> {
+            Url::parse(value).map_err(|err| format!("invalid URL: {}", err))
+        }
+
+        match key {
+            ProfileConfigKey::Url => {
+                let url = parse_url(value)?;
+                match self {
+                    Profile::Golem(profile) => profile.url = url,
+                    Profile::GolemCloud(profile) => profile.custom_url = Some(url),
+                }
+            }
+            ProfileConfigKey::WorkerUrl => {
+                let url = parse_url(value)?;
+                match self {
+                    Profile::Golem(profile) => profile.worker_url = Some(url),
+                    Profile::GolemCloud(profile) => profile.custom_worker_url = Some(url),
+                }
+            }
+            ProfileConfigKey::CloudUrl => {
+                let url = parse_url(value)?;
+                match self {
+                    Profile::Golem(_) => return Err(cloud_only_key(key)),
+                    Profile::GolemCloud(profile) => profile.custom_cloud_url = Some(url),
+                }
+            }
+            ProfileConfigKey::Format => {
+                self.get_config_mut().default_format = Format::from_str(value)?;
+            }
+            ProfileConfigKey::DefaultProject => {
+                if self.kind() == ProfileKind::Oss {
+                    return Err(cloud_only_key(key));
+                }
+                if value.is_empty() {
+                    return Err("the project name cannot be empty".to_string());
+                }
+                self.get_config_mut().default_project = Some(value.into());
+            }
+            ProfileConfigKey::Timeout => {
+                let seconds = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|seconds| *seconds > 0)
+                    .ok_or_else(|| {
+                        format!("invalid timeout: {}, expected positive seconds", value)
+                    })?;
+                self.get_config_mut().timeout_seconds = Some(seconds);
+            }
+            ProfileConfigKey::MaxUploadSize => {
+                self.get_config_mut().max_upload_size = Some(parse_byte_size(value)?);
+            }
+            ProfileConfigKey::MaxInvokePayloadSize => {
+                self.get_config_mut().max_invoke_payload_size = Some(parse_byte_size(value)?);
+            }
+            ProfileConfigKey::ReadOnly => {
+                self.get_config_mut().read_only = value
+                    .parse::<bool>()
+                    .map_err(|_| format!("invalid boolean: {}, expected true or false", value))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores the default of a config value, returns the error message for required values
+    pub fn unset_config_value(&mut self, key: ProfileConfigKey) -> Result<(), String> {
+        match key {
+            ProfileConfigKey::Url => match self {
+                Profile::Golem(_) => {
+                    return Err(format!("{} is required for OSS profiles", key));
+                }
+                Profile::GolemCloud(profile) => profile.custom_url = None,
+            },
+            ProfileConfigKey::WorkerUrl => match self {
+                Profile::Golem(profile) => profile.worker_url = None,
+                Profile::GolemCloud(profile) => profile.custom_worker_url = None,
+            },
+            ProfileConfigKey::CloudUrl => match self {
+                Profile::Golem(_) => return Err(cloud_only_key(key)),
+                Profile::GolemCloud(profile) => profile.custom_cloud_url = None,
+            },
+            ProfileConfigKey::Format => self.get_config_mut().default_format = Format::default(),
+            ProfileConfigKey::DefaultProject => self.get_config_mut().default_project = None,
+            ProfileConfigKey::Timeout => self.get_config_mut().timeout_seconds = None,
+            ProfileConfigKey::MaxUploadSize => self.get_config_mut().max_upload_size = None,
+            ProfileConfigKey::MaxInvokePayloadSize => {
+                self.get_config_mut().max_invoke_payload_size = None
+            }
+            ProfileConfigKey::ReadOnly => self.get_config_mut().read_only = false,
+        }
+        Ok(())
+    }
+}
+
+fn cloud_only_key(key: ProfileConfigKey) -> String {
+    format!("{} is only available for cloud profiles", key)
 }
 
 impl HasFormatConfig for Profile {
@@ -231,6 +420,107 @@ impl HasFormatConfig for OssProfile {
 pub struct ProfileConfig {
     #[serde(default)]
     pub default_format: Format,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_upload_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_invoke_payload_size: Option<u64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub read_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_project: Option<ProjectName>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timeout_seconds: Option<u64>,
+    /// Output formats overriding the default format for specific commands, keyed by the
+    /// '-' separated subcommand path, e.g. worker-list
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub command_formats: BTreeMap<String, Format>,
+}
+
+/// Keys of the profile settings which can be managed with `profile config get / set / unset`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, EnumIter)]
+pub enum ProfileConfigKey {
+    Url,
+    WorkerUrl,
+    CloudUrl,
+    Format,
+    DefaultProject,
+    Timeout,
+    MaxUploadSize,
+    MaxInvokePayloadSize,
+    ReadOnly,
+}
+
+impl ProfileConfigKey {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ProfileConfigKey::Url => "URL of the Golem Component service",
+            ProfileConfigKey::WorkerUrl => {
+                "URL of the Golem Worker service, defaults to the component service URL"
+            }
+            ProfileConfigKey::CloudUrl => {
+                "URL of the Golem Cloud service, defaults to the component service URL (cloud only)"
+            }
+            ProfileConfigKey::Format => "Default output format: text, json or yaml",
+            ProfileConfigKey::DefaultProject => {
+                "Project used when no project is specified (cloud only)"
+            }
+            ProfileConfigKey::Timeout => "Timeout of service calls in seconds",
+            ProfileConfigKey::MaxUploadSize => {
+                "Maximum size of component uploads without confirmation, e.g. 50MiB"
+            }
+            ProfileConfigKey::MaxInvokePayloadSize => {
+                "Maximum size of invocation parameters without confirmation, e.g. 1MiB"
+            }
+            ProfileConfigKey::ReadOnly => "Reject commands which could change server side state",
+        }
+    }
+}
+
+impl Display for ProfileConfigKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let key = match self {
+            ProfileConfigKey::Url => "url",
+            ProfileConfigKey::WorkerUrl => "worker-url",
+            ProfileConfigKey::CloudUrl => "cloud-url",
+            ProfileConfigKey::Format => "format",
+            ProfileConfigKey::DefaultProject => "default-project",
+            ProfileConfigKey::Timeout => "timeout",
+            ProfileConfigKey::MaxUploadSize => "max-upload-size",
+            ProfileConfigKey::MaxInvokePayloadSize => "max-invoke-payload-size",
+            ProfileConfigKey::ReadOnly => "read-only",
+        };
+        write!(f, "{}", key)
+    }
+}
+
+impl FromStr for ProfileConfigKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProfileConfigKey::iter()
+            .find(|key| key.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown profile config key: {}, available keys: {}",
+                    s,
+                    ProfileConfigKey::iter().join(", ")
+                )
+            })
+    }
+}
+
+impl ProfileConfig {
+    pub fn service_timeout(&self) -> Option<Duration> {
+        self.timeout_seconds.map(Duration::from_secs)
+    }
+
+    /// Output format configured for the command, falls back to the default format
+    pub fn command_format(&self, command_path: &[String]) -> Format {
+        self.command_formats
+            .get(&command_path.join("-"))
+            .copied()
+            .unwrap_or(self.default_format)
+    }
 }
 
 impl Config {
@@ -238,6 +528,15 @@ impl Config {
         config_dir.join("config.json")
     }
 
+    /// Copy of the last successfully stored config, used for recovering from invalid config files
+    fn last_good_config_path(config_dir: &Path) -> PathBuf {
+        config_dir.join("config.last-good.json")
+    }
+
+    fn corrupted_config_backup_path(config_dir: &Path) -> PathBuf {
+        config_dir.join("config.invalid.backup.json")
+    }
+
     pub fn default_profile_name(&self) -> ProfileName {
         self.default_profile
             .clone()
@@ -245,57 +544,297 @@ impl Config {
     }
 
     pub fn from_dir(config_dir: &Path) -> anyhow::Result<Config> {
+        Self::load(config_dir, ConfigMigrationMode::Persist).map(|(config, _)| config)
+    }
+
+    pub fn load(
+        config_dir: &Path,
+        migration_mode: ConfigMigrationMode,
+    ) -> anyhow::Result<(Config, Option<ConfigMigrationResult>)> {
         let config_path = Self::config_path(config_dir);
 
         if !config_path
             .try_exists()
             .with_context(|| anyhow!("Failed to check config file: {}", config_path.display()))?
         {
-            return Ok(Config::default().with_local_and_cloud_profiles());
+            let config = Config {
+                config_version: CONFIG_VERSION,
+                ..Config::default()
+            };
+            return Ok((config.with_local_and_cloud_profiles(), None));
         }
 
-        let file = File::open(&config_path)
+        let (config, migration_result) = match Self::read_file(&config_path) {
+            Ok(result) => result,
+            Err(error) if error.downcast_ref::<InvalidConfigError>().is_some() => {
+                return Self::recover(config_dir, migration_mode, error);
+            }
+            Err(error) => return Err(error),
+        };
+
+        let migration_result = match migration_result {
+            Some(mut migration_result) if migration_mode == ConfigMigrationMode::Persist => {
+                let backup_path = config_dir.join(format!(
+                    "config.v{}.backup.json",
+                    migration_result.from_version
+                ));
+                crate::fs::copy(&config_path, &backup_path).with_context(|| {
+                    anyhow!(
+                        "Failed to backup config before migration: {}",
+                        config_path.display()
+                    )
+                })?;
+                config.store_file(config_dir).with_context(|| {
+                    anyhow!(
+                        "Failed to save config after migration: {}",
+                        config_path.display()
+                    )
+                })?;
+                migration_result.backup_path = Some(backup_path);
+                Some(migration_result)
+            }
+            migration_result => migration_result,
+        };
+
+        Ok((config.with_local_and_cloud_profiles(), migration_result))
+    }
+
+    fn read_file(config_path: &Path) -> anyhow::Result<(Config, Option<ConfigMigrationResult>)> {
+        let file = File::open(config_path)
             .with_context(|| anyhow!("Failed to open config file: {}", config_path.display()))?;
 
         let reader = BufReader::new(file);
-        let mut config: Config = serde_json::from_reader(reader).with_context(|| {
-            anyhow!(
-                "Failed to deserialize config file {}",
-                config_path.display(),
-            )
-        })?;
-
-        // Detect if it was not yet migrated
-        if config.default_profile.is_none() {
-            // Drop old default profiles
-            config.profiles.remove(&ProfileName::from("default"));
-            config.profiles.remove(&ProfileName::from("cloud_default"));
-
-            // Rename profiles that are conflicting with the new ones
-            if let Some(profile) = config.profiles.remove(&ProfileName::from("local")) {
-                config
-                    .profiles
-                    .insert(ProfileName::from("local-migrated"), profile);
-            };
-            if let Some(profile) = config.profiles.remove(&ProfileName::from("cloud")) {
-                config
-                    .profiles
-                    .insert(ProfileName::from("cloud-migrated"), profile);
+        let mut raw_config: serde_json::Value = serde_json::from_reader(reader)
+            .map_err(|err| InvalidConfigError::new(config_path, err))?;
+
+        let migration_result = Self::apply_migrations(config_path, &mut raw_config)?;
+
+        let config: Config = serde_json::from_value(raw_config)
+            .map_err(|err| InvalidConfigError::new(config_path, err))?;
+
+        Ok((config, migration_result))
+    }
+
+    /// Handles an invalid config file: the last good copy is used (and restored in persist mode)
+    /// when available, otherwise the user is pointed to the repair command. In repair mode
+    /// the salvageable parts of the config are used without changing the config file.
+    fn recover(
+        config_dir: &Path,
+        migration_mode: ConfigMigrationMode,
+        error: anyhow::Error,
+    ) -> anyhow::Result<(Config, Option<ConfigMigrationResult>)> {
+        let config_path = Self::config_path(config_dir);
+
+        if migration_mode == ConfigMigrationMode::Repair {
+            let (config, _) = Self::salvage(&config_path)?;
+            return Ok((config.with_local_and_cloud_profiles(), None));
+        }
+
+        let last_good_path = Self::last_good_config_path(config_dir);
+        let last_good = if last_good_path.exists() {
+            Self::read_file(&last_good_path).ok()
+        } else {
+            None
+        };
+
+        let Some((config, migration_result)) = last_good else {
+            bail!(ContextInitHintError::InvalidConfig {
+                config_path,
+                error: format!("{:#}", error),
+            });
+        };
+
+        if migration_mode == ConfigMigrationMode::Persist {
+            let backup_path = Self::corrupted_config_backup_path(config_dir);
+            crate::fs::copy(&config_path, &backup_path)?;
+            config.store_file(config_dir).with_context(|| {
+                anyhow!(
+                    "Failed to restore config from last good copy: {}",
+                    last_good_path.display()
+                )
+            })?;
+            log_warn_action(
+                "Restored",
+                format!(
+                    "invalid config file {} from its last good copy, the invalid config was saved to {}",
+                    config_path.log_color_highlight(),
+                    backup_path.log_color_highlight()
+                ),
+            );
+        } else {
+            log_warn_action(
+                "Using",
+                format!(
+                    "the last good copy of the invalid config file {}",
+                    config_path.log_color_highlight()
+                ),
+            );
+        }
+
+        Ok((config.with_local_and_cloud_profiles(), migration_result))
+    }
+
+    /// Checks the config file, and if it is invalid, replaces it with the salvageable parts of
+    /// it: every profile which can still be parsed is kept, the others are reported as dropped.
+    pub fn repair(config_dir: &Path, dry_run: bool) -> anyhow::Result<ConfigRepairResult> {
+        let config_path = Self::config_path(config_dir);
+
+        let valid_result = |profiles: Vec<ProfileName>| ConfigRepairResult {
+            config_path: config_path.clone(),
+            valid: true,
+            salvaged_profiles: profiles,
+            dropped_profiles: vec![],
+            backup_path: None,
+        };
+
+        if !config_path.exists() {
+            return Ok(valid_result(vec![]));
+        }
+
+        match Self::read_file(&config_path) {
+            Ok((config, _)) => {
+                return Ok(valid_result(config.profiles.into_keys().sorted().collect()))
             }
+            Err(error) if error.downcast_ref::<InvalidConfigError>().is_some() => {}
+            Err(error) => return Err(error),
+        }
 
-            // Set default to local
-            config.default_profile = Some(ProfileName::from("local"));
+        let (config, dropped_profiles) = Self::salvage(&config_path)?;
 
-            // Save migrated config
+        let backup_path = if dry_run {
+            None
+        } else {
+            let backup_path = Self::corrupted_config_backup_path(config_dir);
+            crate::fs::copy(&config_path, &backup_path)?;
             config.store_file(config_dir).with_context(|| {
+                anyhow!("Failed to save repaired config: {}", config_path.display())
+            })?;
+            Some(backup_path)
+        };
+
+        Ok(ConfigRepairResult {
+            config_path,
+            valid: false,
+            salvaged_profiles: config.profiles.into_keys().sorted().collect(),
+            dropped_profiles,
+            backup_path,
+        })
+    }
+
+    fn salvage(config_path: &Path) -> anyhow::Result<(Config, Vec<DroppedProfile>)> {
+        let content = crate::fs::read_to_string(config_path)?;
+        let mut raw_config =
+            serde_json::Value::Object(salvage_json_object(&resolve_merge_conflicts(&content)));
+        Self::apply_migrations(config_path, &mut raw_config)?;
+
+        // Profiles are parsed one by one, so a single invalid profile does not invalidate the others
+        let raw_profiles = match raw_config.get_mut("profiles").map(serde_json::Value::take) {
+            Some(serde_json::Value::Object(raw_profiles)) => raw_profiles,
+            _ => serde_json::Map::new(),
+        };
+
+        let mut profiles = HashMap::new();
+        let mut dropped_profiles = Vec::new();
+        for (name, raw_profile) in raw_profiles {
+            match serde_json::from_value::<Profile>(raw_profile) {
+                Ok(profile) => {
+                    profiles.insert(ProfileName(name), profile);
+                }
+                Err(err) => dropped_profiles.push(DroppedProfile {
+                    name: ProfileName(name),
+                    reason: err.to_string(),
+                }),
+            }
+        }
+        dropped_profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let field = |name: &str| raw_config.get(name).cloned().unwrap_or_default();
+        let default_profile =
+            serde_json::from_value::<Option<ProfileName>>(field("default_profile"))
+                .ok()
+                .flatten()
+                .filter(|name| profiles.contains_key(name));
+
+        let config = Config {
+            config_version: CONFIG_VERSION,
+            profiles,
+            default_profile,
+            history_enabled: serde_json::from_value(field("history_enabled")).unwrap_or_default(),
+            aliases: serde_json::from_value(field("aliases")).unwrap_or_default(),
+            active_profile: None,
+            active_cloud_profile: None,
+        };
+
+        Ok((config, dropped_profiles))
+    }
+
+    /// Loads the config and applies the pending migrations, returns the result of
+    /// the migration even if the config is already up-to-date.
+    pub fn migrate(
+        config_dir: &Path,
+        migration_mode: ConfigMigrationMode,
+    ) -> anyhow::Result<ConfigMigrationResult> {
+        let (config, migration_result) = Self::load(config_dir, migration_mode)?;
+        Ok(migration_result.unwrap_or_else(|| ConfigMigrationResult {
+            config_path: Self::config_path(config_dir),
+            from_version: config.config_version,
+            to_version: config.config_version,
+            applied_migrations: vec![],
+            backup_path: None,
+        }))
+    }
+
+    fn apply_migrations(
+        config_path: &Path,
+        raw_config: &mut serde_json::Value,
+    ) -> anyhow::Result<Option<ConfigMigrationResult>> {
+        let from_version = raw_config
+            .get("config_version")
+            .and_then(|version| version.as_u64())
+            .unwrap_or(0) as u32;
+
+        if from_version > CONFIG_VERSION {
+            bail!(
+                "The config file {} has version {}, but the latest supported version is {}, please update golem-cli",
+                config_path.display(),
+                from_version,
+                CONFIG_VERSION
+            );
+        }
+
+        if from_version == CONFIG_VERSION {
+            return Ok(None);
+        }
+
+        let mut applied_migrations = Vec::new();
+        for (version, migration) in CONFIG_MIGRATIONS
+            .iter()
+            .enumerate()
+            .skip(from_version as usize)
+        {
+            (migration.migrate)(raw_config).with_context(|| {
                 anyhow!(
-                    "Failed to save config after migration: {}",
-                    config_path.display()
+                    "Failed to migrate config file {} from version {} to {}",
+                    config_path.display(),
+                    version,
+                    version + 1
                 )
             })?;
+            applied_migrations.push(migration.description.to_string());
         }
 
-        Ok(config.with_local_and_cloud_profiles())
+        raw_config
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("Expected JSON object in config file"))?
+            .insert("config_version".to_string(), CONFIG_VERSION.into());
+
+        Ok(Some(ConfigMigrationResult {
+            config_path: config_path.to_path_buf(),
+            from_version,
+            to_version: CONFIG_VERSION,
+            applied_migrations,
+            backup_path: None,
+        }))
     }
 
     fn with_local_and_cloud_profiles(mut self) -> Self {
@@ -326,17 +865,34 @@ impl Config {
         create_dir_all(config_dir)
             .map_err(|err| anyhow!("Can't create config directory: {err}"))?;
 
+        // The config is written to a temporary file first and then renamed, so an interrupted
+        // write cannot leave a partial config behind
+        let config_path = Self::config_path(config_dir);
+        let temp_path = config_path.with_extension("json.tmp");
+
         let file = OpenOptions::new()
             .create(true)
             .read(true)
             .write(true)
             .truncate(true)
-            .open(Self::config_path(config_dir))
+            .open(&temp_path)
             .map_err(|err| anyhow!("Can't open config file: {err}"))?;
-        let writer = BufWriter::new(file);
+        let mut writer = BufWriter::new(file);
 
-        serde_json::to_writer_pretty(writer, self)
-            .map_err(|err| anyhow!("Can't save config to file: {err}"))
+        serde_json::to_writer_pretty(&mut writer, self)
+            .map_err(|err| anyhow!("Can't save config to file: {err}"))?;
+        writer
+            .flush()
+            .map_err(|err| anyhow!("Can't save config to file: {err}"))?;
+        drop(writer);
+
+        std::fs::rename(&temp_path, &config_path)
+            .map_err(|err| anyhow!("Can't save config to file: {err}"))?;
+
+        crate::fs::copy(&config_path, Self::last_good_config_path(config_dir))
+            .map_err(|err| anyhow!("Can't save last good copy of config: {err}"))?;
+
+        Ok(())
     }
 
     pub fn set_active_profile_name(
@@ -362,8 +918,9 @@ impl Config {
     pub fn get_active_profile(
         config_dir: &Path,
         selected_profile: Option<ProfileName>,
+        migration_mode: ConfigMigrationMode,
     ) -> anyhow::Result<NamedProfile> {
-        let mut config = Self::from_dir(config_dir)?;
+        let (mut config, _) = Self::load(config_dir, migration_mode)?;
 
         let name = selected_profile
             .unwrap_or_else(|| config.default_profile.unwrap_or_else(ProfileName::local));
@@ -394,6 +951,28 @@ impl Config {
         config.store_file(config_dir)
     }
 
+    pub fn set_history_enabled(enabled: bool, config_dir: &Path) -> anyhow::Result<()> {
+        let mut config = Self::from_dir(config_dir)?;
+        config.history_enabled = enabled;
+        config.store_file(config_dir)
+    }
+
+    pub fn set_alias(name: String, command: String, config_dir: &Path) -> anyhow::Result<()> {
+        let mut config = Self::from_dir(config_dir)?;
+        config.aliases.insert(name, command);
+        config.store_file(config_dir)
+    }
+
+    /// Returns false if the alias did not exist
+    pub fn remove_alias(name: &str, config_dir: &Path) -> anyhow::Result<bool> {
+        let mut config = Self::from_dir(config_dir)?;
+        if config.aliases.remove(name).is_none() {
+            return Ok(false);
+        }
+        config.store_file(config_dir)?;
+        Ok(true)
+    }
+
     pub fn delete_profile(name: &ProfileName, config_dir: &Path) -> anyhow::Result<()> {
         let mut config = Self::from_dir(config_dir)?;
         config.profiles.remove(name);
@@ -401,6 +980,141 @@ impl Config {
     }
 }
 
+// Config version 0 -> 1
+#[derive(Debug)]
+struct InvalidConfigError {
+    config_path: PathBuf,
+    error: serde_json::Error,
+}
+
+impl InvalidConfigError {
+    fn new(config_path: &Path, error: serde_json::Error) -> Self {
+        Self {
+            config_path: config_path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+impl Display for InvalidConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to deserialize config file {}: {}",
+            self.config_path.display(),
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for InvalidConfigError {}
+
+/// Keeps the local side of unresolved merge conflicts, e.g. when the config dir is versioned
+fn resolve_merge_conflicts(content: &str) -> String {
+    let mut in_theirs = false;
+    content
+        .lines()
+        .filter(|line| {
+            if line.starts_with("<<<<<<<") {
+                false
+            } else if line.starts_with("=======") {
+                in_theirs = true;
+                false
+            } else if line.starts_with(">>>>>>>") {
+                in_theirs = false;
+                false
+            } else {
+                !in_theirs
+            }
+        })
+        .join("\n")
+}
+
+/// Best-effort parsing of a possibly truncated or otherwise invalid JSON object: entries are
+/// parsed one by one until the first invalid one, nested objects are salvaged recursively
+fn salvage_json_object(content: &str) -> serde_json::Map<String, serde_json::Value> {
+    let mut entries = serde_json::Map::new();
+
+    let Some(mut rest) = content.trim_start().strip_prefix('{') else {
+        return entries;
+    };
+
+    loop {
+        rest = rest.trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next;
+            continue;
+        }
+        if rest.is_empty() || rest.starts_with('}') {
+            break;
+        }
+
+        let mut keys = serde_json::Deserializer::from_str(rest).into_iter::<String>();
+        let Some(Ok(key)) = keys.next() else {
+            break;
+        };
+        rest = rest[keys.byte_offset()..].trim_start();
+        let Some(next) = rest.strip_prefix(':') else {
+            break;
+        };
+        rest = next.trim_start();
+
+        let mut values = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+        match values.next() {
+            Some(Ok(value)) => {
+                entries.insert(key, value);
+                rest = &rest[values.byte_offset()..];
+            }
+            _ => {
+                if rest.starts_with('{') {
+                    entries.insert(key, serde_json::Value::Object(salvage_json_object(rest)));
+                }
+                break;
+            }
+        }
+    }
+
+    entries
+}
+
+fn migrate_legacy_default_profiles(config: &mut serde_json::Value) -> anyhow::Result<()> {
+    let config = config
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Expected JSON object"))?;
+
+    // Configs which already have a default profile were created after the legacy defaults were dropped
+    if config
+        .get("default_profile")
+        .is_some_and(|default_profile| !default_profile.is_null())
+    {
+        return Ok(());
+    }
+
+    if let Some(profiles) = config
+        .get_mut("profiles")
+        .and_then(|profiles| profiles.as_object_mut())
+    {
+        // Drop old default profiles
+        profiles.remove("default");
+        profiles.remove("cloud_default");
+
+        // Rename profiles that are conflicting with the new ones
+        for name in [PROFILE_NAME_LOCAL, PROFILE_NAME_CLOUD] {
+            if let Some(profile) = profiles.remove(name) {
+                profiles.insert(format!("{}-migrated", name), profile);
+            }
+        }
+    }
+
+    // Set default to local
+    config.insert(
+        "default_profile".to_string(),
+        PROFILE_NAME_LOCAL.to_string().into(),
+    );
+
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
     pub component_url: Url,
@@ -417,6 +1131,7 @@ impl From<&Profile> for ClientConfig {
         match profile {
             Profile::Golem(profile) => {
                 let allow_insecure = profile.allow_insecure;
+                let timeout = profile.config.service_timeout();
 
                 ClientConfig {
                     component_url: profile.url.clone(),
@@ -427,6 +1142,7 @@ impl From<&Profile> for ClientConfig {
                     cloud_url: None,
                     service_http_client_config: HttpClientConfig::new_for_service_calls(
                         allow_insecure,
+                        timeout,
                     ),
                     invoke_http_client_config: HttpClientConfig::new_for_invoke(allow_insecure),
                     health_check_http_client_config: HttpClientConfig::new_for_health_check(
@@ -451,6 +1167,7 @@ impl From<&Profile> for ClientConfig {
                     .clone()
                     .unwrap_or_else(|| component_url.clone());
                 let allow_insecure = profile.allow_insecure;
+                let timeout = profile.config.service_timeout();
 
                 ClientConfig {
                     component_url,
@@ -458,6 +1175,7 @@ impl From<&Profile> for ClientConfig {
                     cloud_url,
                     service_http_client_config: HttpClientConfig::new_for_service_calls(
                         allow_insecure,
+                        timeout,
                     ),
                     invoke_http_client_config: HttpClientConfig::new_for_invoke(allow_insecure),
                     health_check_http_client_config: HttpClientConfig::new_for_health_check(
@@ -478,15 +1196,40 @@ pub struct HttpClientConfig {
     pub timeout: Option<Duration>,
     pub connect_timeout: Option<Duration>,
     pub read_timeout: Option<Duration>,
+    pub pool: HttpPoolConfig,
+}
+
+/// Connection reuse settings, the clients are created once per command, so bulk operations
+/// (e.g. listing all pages or updating many workers) reuse the pooled connections
+#[derive(Debug, Clone)]
+pub struct HttpPoolConfig {
+    pub max_idle_per_host: Option<usize>,
+    pub idle_timeout: Option<Duration>,
+    pub tcp_keepalive: Option<Duration>,
+    /// Use HTTP/2 without ALPN negotiation, for plain HTTP (h2c) endpoints
+    pub http2_prior_knowledge: bool,
+}
+
+impl Default for HttpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: None,
+            idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            http2_prior_knowledge: false,
+        }
+    }
 }
 
 impl HttpClientConfig {
-    pub fn new_for_service_calls(allow_insecure: bool) -> Self {
+    pub fn new_for_service_calls(allow_insecure: bool, timeout: Option<Duration>) -> Self {
+        let timeout = timeout.unwrap_or(Duration::from_secs(10));
         Self {
             allow_insecure,
-            timeout: Some(Duration::from_secs(10)),
-            connect_timeout: Some(Duration::from_secs(10)),
-            read_timeout: Some(Duration::from_secs(10)),
+            timeout: Some(timeout),
+            connect_timeout: Some(Duration::from_secs(10).min(timeout)),
+            read_timeout: Some(timeout),
+            pool: HttpPoolConfig::default(),
         }
         .with_env_overrides("GOLEM_HTTP")
     }
@@ -497,6 +1240,7 @@ impl HttpClientConfig {
             timeout: None,
             connect_timeout: None,
             read_timeout: None,
+            pool: HttpPoolConfig::default(),
         }
         .with_env_overrides("GOLEM_HTTP_INVOKE")
     }
@@ -507,6 +1251,7 @@ impl HttpClientConfig {
             timeout: Some(Duration::from_secs(2)),
             connect_timeout: Some(Duration::from_secs(1)),
             read_timeout: Some(Duration::from_secs(1)),
+            pool: HttpPoolConfig::default(),
         }
         .with_env_overrides("GOLEM_HTTP_HEALTHCHECK")
     }
@@ -517,6 +1262,7 @@ impl HttpClientConfig {
             timeout: Some(Duration::from_secs(60)),
             connect_timeout: Some(Duration::from_secs(10)),
             read_timeout: Some(Duration::from_secs(60)),
+            pool: HttpPoolConfig::default(),
         }
         .with_env_overrides("GOLEM_HTTP_FILE_DOWNLOAD")
     }
@@ -539,6 +1285,209 @@ impl HttpClientConfig {
             }
         }
 
+        let pool_duration_fields: Vec<(&str, &mut Option<Duration>)> = vec![
+            ("POOL_IDLE_TIMEOUT", &mut self.pool.idle_timeout),
+            ("TCP_KEEPALIVE", &mut self.pool.tcp_keepalive),
+        ];
+
+        for (env_var_name, field) in pool_duration_fields {
+            if let Some(duration) = env_duration(&format!("{}_{}", prefix, env_var_name)) {
+                *field = Some(duration);
+            }
+        }
+
+        if let Some(max_idle_per_host) = std::env::var(format!("{}_POOL_MAX_IDLE_PER_HOST", prefix))
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+        {
+            self.pool.max_idle_per_host = Some(max_idle_per_host);
+        }
+
+        if let Some(http2_prior_knowledge) =
+            std::env::var(format!("{}_HTTP2_PRIOR_KNOWLEDGE", prefix))
+                .ok()
+                .and_then(|value| value.parse::<bool>().ok())
+        {
+            self.pool.http2_prior_knowledge = http2_prior_knowledge;
+        }
+
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::config::{
+        resolve_merge_conflicts, salvage_json_object, Config, OssProfile, Profile, ProfileConfig,
+        ProfileConfigKey, ProfileName, CONFIG_VERSION,
+    };
+    use crate::model::Format;
+    use assert2::check;
+    use serde_json::json;
+    use std::path::Path;
+    use test_r::test;
+
+    #[test]
+    fn migrate_legacy_config() {
+        let mut raw_config = json!({
+            "profiles": {
+                "default": {"Golem": {"url": "http://localhost:9881"}},
+                "cloud_default": {"GolemCloud": {}},
+                "local": {"Golem": {"url": "http://localhost:9000"}},
+                "custom": {"Golem": {"url": "http://localhost:9001"}}
+            },
+            "active_profile": "local"
+        });
+
+        let result = Config::apply_migrations(Path::new("config.json"), &mut raw_config)
+            .unwrap()
+            .unwrap();
+
+        check!(result.from_version == 0);
+        check!(result.to_version == CONFIG_VERSION);
+        check!(result.applied_migrations.len() == CONFIG_VERSION as usize);
+
+        let config: Config = serde_json::from_value(raw_config).unwrap();
+        let mut profile_names = config
+            .profiles
+            .keys()
+            .map(|name| name.0.as_str())
+            .collect::<Vec<_>>();
+        profile_names.sort();
+
+        check!(config.config_version == CONFIG_VERSION);
+        check!(config.default_profile == Some("local".into()));
+        check!(profile_names == vec!["custom", "local-migrated"]);
+    }
+
+    #[test]
+    fn migrate_up_to_date_config() {
+        let mut raw_config = json!({
+            "config_version": CONFIG_VERSION,
+            "profiles": {},
+            "default_profile": "local"
+        });
+
+        let result = Config::apply_migrations(Path::new("config.json"), &mut raw_config).unwrap();
+
+        check!(result.is_none());
+    }
+
+    #[test]
+    fn migrate_newer_config_fails() {
+        let mut raw_config = json!({
+            "config_version": CONFIG_VERSION + 1,
+            "profiles": {}
+        });
+
+        check!(Config::apply_migrations(Path::new("config.json"), &mut raw_config).is_err());
+    }
+
+    #[test]
+    fn profile_config_values() {
+        let mut profile = Profile::Golem(OssProfile {
+            url: "http://localhost:9881".parse().unwrap(),
+            worker_url: None,
+            allow_insecure: false,
+            config: ProfileConfig::default(),
+        });
+
+        check!("worker-url".parse::<ProfileConfigKey>() == Ok(ProfileConfigKey::WorkerUrl));
+        check!("unknown".parse::<ProfileConfigKey>().is_err());
+
+        check!(profile
+            .set_config_value(ProfileConfigKey::Format, "json")
+            .is_ok());
+        check!(profile.get_config().default_format == Format::Json);
+        check!(profile
+            .set_config_value(ProfileConfigKey::Timeout, "30")
+            .is_ok());
+        check!(profile.config_value(ProfileConfigKey::Timeout) == Some("30".to_string()));
+        check!(profile
+            .set_config_value(ProfileConfigKey::MaxUploadSize, "1KiB")
+            .is_ok());
+        check!(profile.get_config().max_upload_size == Some(1024));
+
+        check!(profile
+            .set_config_value(ProfileConfigKey::Timeout, "0")
+            .is_err());
+        check!(profile
+            .set_config_value(ProfileConfigKey::WorkerUrl, "not a url")
+            .is_err());
+        check!(profile
+            .set_config_value(ProfileConfigKey::DefaultProject, "project")
+            .is_err());
+        check!(profile.unset_config_value(ProfileConfigKey::Url).is_err());
+
+        check!(profile
+            .unset_config_value(ProfileConfigKey::Timeout)
+            .is_ok());
+        check!(profile.config_value(ProfileConfigKey::Timeout).is_none());
+        check!(profile.unset_config_value(ProfileConfigKey::Format).is_ok());
+        check!(profile.get_config().default_format == Format::Text);
+    }
+
+    #[test]
+    fn repair_truncated_config() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let content = r#"{
+  "config_version": 1,
+  "profiles": {
+    "custom": {"Golem": {"url": "http://localhost:9001", "allow_insecure": false, "config": {}}},
+    "broken": {"Golem": {"url": "not a url"}},
+    "partial": {"Golem": {"url": "http://loc"#;
+        std::fs::write(config_dir.path().join("config.json"), content).unwrap();
+
+        check!(Config::from_dir(config_dir.path()).is_err());
+
+        let result = Config::repair(config_dir.path(), false).unwrap();
+        let dropped_profiles = result
+            .dropped_profiles
+            .iter()
+            .map(|profile| profile.name.0.as_str())
+            .collect::<Vec<_>>();
+
+        check!(!result.valid);
+        check!(result.salvaged_profiles == vec!["custom".into()]);
+        check!(dropped_profiles == vec!["broken", "partial"]);
+        check!(result.backup_path.is_some());
+
+        let config = Config::from_dir(config_dir.path()).unwrap();
+        check!(config.profiles.contains_key(&"custom".into()));
+        check!(config.profiles.contains_key(&ProfileName::local()));
+
+        let result = Config::repair(config_dir.path(), false).unwrap();
+        check!(result.valid);
+    }
+
+    #[test]
+    fn restore_config_from_last_good_copy() {
+        let config_dir = tempfile::tempdir().unwrap();
+
+        Config::set_active_profile_name(ProfileName::cloud(), config_dir.path()).unwrap();
+        std::fs::write(
+            config_dir.path().join("config.json"),
+            "<<<<<<< HEAD\n{\n=======\n",
+        )
+        .unwrap();
+
+        let config = Config::from_dir(config_dir.path()).unwrap();
+        check!(config.default_profile == Some(ProfileName::cloud()));
+
+        let restored = std::fs::read_to_string(config_dir.path().join("config.json")).unwrap();
+        check!(serde_json::from_str::<Config>(&restored).is_ok());
+        check!(config_dir
+            .path()
+            .join("config.invalid.backup.json")
+            .exists());
+    }
+
+    #[test]
+    fn salvage_merge_conflicts() {
+        let content = "{\n<<<<<<< HEAD\n  \"default_profile\": \"local\",\n=======\n  \"default_profile\": \"cloud\",\n>>>>>>> other\n  \"profiles\": {}\n}";
+        let entries = salvage_json_object(&resolve_merge_conflicts(content));
+
+        check!(entries.get("default_profile") == Some(&json!("local")));
+        check!(entries.get("profiles") == Some(&json!({})));
+    }
+}
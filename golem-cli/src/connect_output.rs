@@ -12,18 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::model::{Format, WorkerConnectOptions};
+use crate::log::{output_sink, CapturedOutput, OutputSink};
+use crate::model::{Format, WorkerConnectOptions, WorkerLogFilter, WorkerLogStream};
+use chrono::{DateTime, Utc};
 use colored::Colorize;
 use golem_common::model::{LogLevel, Timestamp};
 use std::fmt::Write;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 
 #[derive(Clone)]
 pub struct ConnectOutput {
     state: Arc<Mutex<ConnectOutputState>>,
     options: WorkerConnectOptions,
     format: Format,
+    sink: Option<OutputSink>,
+    source: Option<String>,
+    filter: WorkerLogFilter,
+    awaited_invocation: Option<(String, Arc<Notify>)>,
 }
 
 struct ConnectOutputState {
@@ -44,10 +50,60 @@ impl ConnectOutput {
             })),
             options,
             format,
+            // Captured at creation, as the output is usually emitted from spawned tasks
+            sink: output_sink(),
+            source: None,
+            filter: WorkerLogFilter::default(),
+            awaited_invocation: None,
         }
     }
 
+    /// Notifies when the invocation with the given idempotency key finished, used for draining
+    /// the output of an invocation before showing its result
+    pub fn with_awaited_invocation(
+        mut self,
+        idempotency_key: String,
+        finished: Arc<Notify>,
+    ) -> Self {
+        self.awaited_invocation = Some((idempotency_key, finished));
+        self
+    }
+
+    pub fn invocation_finished(&self, idempotency_key: &str) {
+        if let Some((awaited_idempotency_key, finished)) = &self.awaited_invocation {
+            if awaited_idempotency_key == idempotency_key {
+                finished.notify_one();
+            }
+        }
+    }
+
+    /// Tags every emitted line with the given source, used when the output of multiple
+    /// workers is combined
+    pub fn with_source(mut self, source: Option<String>) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: WorkerLogFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Narrows the since filter, used for skipping the events replayed on (re)connection
+    /// which were already shown
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.filter.since = self.filter.since.max(since);
+        self
+    }
+
     pub async fn emit_stdout(&self, timestamp: Timestamp, message: String) {
+        if !self
+            .filter
+            .matches(WorkerLogStream::Stdout, None, timestamp.to_millis())
+        {
+            return;
+        }
+
         let mut state = self.state.lock().await;
         state.last_stdout_timestamp = timestamp;
 
@@ -73,6 +129,13 @@ impl ConnectOutput {
     }
 
     pub async fn emit_stderr(&self, timestamp: Timestamp, message: String) {
+        if !self
+            .filter
+            .matches(WorkerLogStream::Stderr, None, timestamp.to_millis())
+        {
+            return;
+        }
+
         let mut state = self.state.lock().await;
         state.last_stderr_timestamp = timestamp;
 
@@ -104,6 +167,14 @@ impl ConnectOutput {
         context: String,
         message: String,
     ) {
+        if !self.filter.matches(
+            WorkerLogStream::Log,
+            Some((&level).into()),
+            timestamp.to_millis(),
+        ) {
+            return;
+        }
+
         let level_str = match level {
             LogLevel::Trace => "TRACE",
             LogLevel::Debug => "DEBUG",
@@ -113,9 +184,22 @@ impl ConnectOutput {
             LogLevel::Critical => "CRITICAL",
         };
 
+        let line = Line {
+            timestamp,
+            stream: WorkerLogStream::Log,
+            level_or_source: level_str,
+            context: &context,
+            message: &message,
+        };
+
+        if self.capture(&line) {
+            return;
+        }
+
         match self.format {
-            Format::Json => self.json(level_str, &context, &message),
-            Format::Yaml => self.yaml(level_str, &context, &message),
+            // Streamed lines are not tabular, so CSV falls back to JSON lines
+            Format::Json | Format::Csv => self.json(&line),
+            Format::Yaml => self.yaml(&line),
             Format::Text => {
                 let prefix = self.prefix(timestamp, level_str);
                 self.colored(level, &format!("{prefix}[{context}] {message}"));
@@ -130,15 +214,27 @@ impl ConnectOutput {
             state.stdout = String::new();
         }
         if !state.stderr.is_empty() {
-            self.print_stderr(state.last_stdout_timestamp, &state.stderr);
+            self.print_stderr(state.last_stderr_timestamp, &state.stderr);
             state.stderr = String::new();
         }
     }
 
     fn print_stdout(&self, timestamp: Timestamp, message: &str) {
+        let line = Line {
+            timestamp,
+            stream: WorkerLogStream::Stdout,
+            level_or_source: "STDOUT",
+            context: "",
+            message,
+        };
+
+        if self.capture(&line) {
+            return;
+        }
+
         match self.format {
-            Format::Json => self.json("STDOUT", "", message),
-            Format::Yaml => self.yaml("STDOUT", "", message),
+            Format::Json | Format::Csv => self.json(&line),
+            Format::Yaml => self.yaml(&line),
             Format::Text => {
                 let prefix = self.prefix(timestamp, "STDOUT");
                 self.colored(LogLevel::Info, &format!("{prefix}{message}"));
@@ -147,9 +243,21 @@ impl ConnectOutput {
     }
 
     fn print_stderr(&self, timestamp: Timestamp, message: &str) {
+        let line = Line {
+            timestamp,
+            stream: WorkerLogStream::Stderr,
+            level_or_source: "STDERR",
+            context: "",
+            message,
+        };
+
+        if self.capture(&line) {
+            return;
+        }
+
         match self.format {
-            Format::Json => self.json("STDERR", "", message),
-            Format::Yaml => self.yaml("STDERR", "", message),
+            Format::Json | Format::Csv => self.json(&line),
+            Format::Yaml => self.yaml(&line),
             Format::Text => {
                 let prefix = self.prefix(timestamp, "STDERR");
                 self.colored(LogLevel::Error, &format!("{prefix}{message}"));
@@ -157,23 +265,38 @@ impl ConnectOutput {
         }
     }
 
-    fn json(&self, level_or_source: &str, context: &str, message: &str) {
-        let json = self.json_value(level_or_source, context, message);
+    fn capture(&self, line: &Line) -> bool {
+        match &self.sink {
+            Some(sink) => {
+                let _ = sink.send(CapturedOutput::StreamEvent(self.json_value(line)));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn json(&self, line: &Line) {
+        let json = self.json_value(line);
         println!("{}", json);
     }
 
-    fn yaml(&self, level_or_source: &str, context: &str, message: &str) {
-        let json = self.json_value(level_or_source, context, message);
+    fn yaml(&self, line: &Line) {
+        let json = self.json_value(line);
         println!("{}", serde_yaml::to_string(&json).unwrap());
     }
 
-    fn json_value(&self, level_or_source: &str, context: &str, message: &str) -> serde_json::Value {
-        serde_json::json!({
-            "timestamp": Timestamp::now_utc(),
-            "level": level_or_source,
-            "context": context,
-            "message": message,
-        })
+    fn json_value(&self, line: &Line) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "timestamp": line.timestamp,
+            "stream": line.stream.to_string(),
+            "level": line.level_or_source,
+            "context": line.context,
+            "message": line.message,
+        });
+        if let Some(source) = &self.source {
+            value["source"] = source.as_str().into();
+        }
+        value
     }
 
     fn colored(&self, level: LogLevel, s: &str) {
@@ -194,6 +317,9 @@ impl ConnectOutput {
 
     fn prefix(&self, timestamp: Timestamp, level_or_source: &str) -> String {
         let mut result = String::new();
+        if let Some(source) = &self.source {
+            let _ = write!(&mut result, "[{source}] ");
+        }
         if self.options.show_timestamp {
             let _ = write!(&mut result, "[{timestamp}] ");
         }
@@ -209,3 +335,12 @@ impl ConnectOutput {
         result
     }
 }
+
+/// A single emitted line, with the timestamp of the originating worker event
+struct Line<'a> {
+    timestamp: Timestamp,
+    stream: WorkerLogStream,
+    level_or_source: &'a str,
+    context: &'a str,
+    message: &'a str,
+}
@@ -14,18 +14,22 @@
 
 use crate::app::context::ApplicationContext;
 use crate::auth::{Auth, CloudAuthentication};
+use crate::cancellation::CancellationToken;
 use crate::cloud::{AccountId, CloudAuthenticationConfig};
 use crate::command::GolemCliGlobalFlags;
 use crate::config::{
-    ClientConfig, HttpClientConfig, NamedProfile, Profile, ProfileKind, ProfileName,
+    ClientConfig, HttpClientConfig, NamedProfile, Profile, ProfileConfig, ProfileKind, ProfileName,
 };
 use crate::error::HintError;
 use crate::log::{set_log_output, LogOutput, Output};
 use crate::model::app::{AppBuildStep, ApplicationSourceMode};
 use crate::model::app::{ApplicationConfig, BuildProfileName as AppBuildProfileName};
-use crate::model::{Format, HasFormatConfig};
+use crate::model::Format;
+use crate::name_cache;
+use crate::name_cache::DEFAULT_NAME_CACHE_TTL;
+use crate::throttle::Throttle;
 use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as AnyhowContext};
 use golem_client::api::ApiDefinitionClientLive as ApiDefinitionClientOss;
 use golem_client::api::ApiDeploymentClientLive as ApiDeploymentClientOss;
 use golem_client::api::ApiSecurityClientLive as ApiSecurityClientOss;
@@ -54,8 +58,10 @@ use golem_cloud_client::{Context as ContextCloud, Security};
 use golem_templates::model::{ComposableAppGroupName, GuestLanguage};
 use golem_templates::ComposableAppTemplate;
 use std::collections::{BTreeMap, HashSet};
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
 use url::Url;
 use uuid::Uuid;
@@ -71,9 +77,13 @@ pub struct Context {
     profile: Profile,
     app_context_config: ApplicationContextConfig,
     http_batch_size: u64,
+    name_cache_ttl: Duration,
     auth_token_override: Option<Uuid>,
     client_config: ClientConfig,
     yes: bool,
+    read_only: bool,
+    cancellation_token: CancellationToken,
+    throttle: Throttle,
 
     // Lazy initialized
     clients: tokio::sync::OnceCell<Clients>,
@@ -87,21 +97,32 @@ pub struct Context {
 
 impl Context {
     pub fn new(global_flags: &GolemCliGlobalFlags, profile: NamedProfile) -> Self {
-        let format = global_flags
-            .format
-            .unwrap_or(profile.profile.format().unwrap_or(Format::Text));
+        let format = global_flags.format.unwrap_or_else(|| {
+            match profile
+                .profile
+                .get_config()
+                .command_format(&global_flags.command_path)
+            {
+                // Text is the default of profiles, scripts get JSON when not explicitly set
+                Format::Text if !std::io::stdout().is_terminal() => Format::Json,
+                format => format,
+            }
+        });
         let log_output = match format {
             Format::Json => Output::Stderr,
             Format::Yaml => Output::Stderr,
+            Format::Csv => Output::Stderr,
             Format::Text => Output::Stdout,
         };
         set_log_output(log_output);
 
         let client_config = ClientConfig::from(&profile.profile);
+        let cancellation_token = CancellationToken::default();
+        let read_only = global_flags.read_only || profile.profile.get_config().read_only;
 
         Self {
             config_dir: global_flags.config_dir(),
-            format: global_flags.format.unwrap_or(Format::Text),
+            format,
             profile_name: profile.name,
             profile_kind: profile.profile.kind(),
             profile: profile.profile,
@@ -115,12 +136,29 @@ impl Context {
                 golem_rust_override: RustDependencyOverride {
                     path_override: global_flags.golem_rust_path.clone(),
                     version_override: global_flags.golem_rust_version.clone(),
+                    git_override: global_flags.golem_rust_git.clone(),
+                    git_branch_override: global_flags.golem_rust_git_branch.clone(),
                 },
                 wasm_rpc_client_build_offline: global_flags.wasm_rpc_offline,
+                build_dir: global_flags.build_dir.clone(),
+                manifest_variables: global_flags.manifest_variables.clone(),
+                cancellation_token: cancellation_token.clone(),
             },
             http_batch_size: global_flags.http_batch_size.unwrap_or(50),
+            // Resolutions done with an overridden token may belong to another account
+            name_cache_ttl: if global_flags.auth_token.is_some() {
+                Duration::ZERO
+            } else {
+                global_flags
+                    .name_cache_ttl
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_NAME_CACHE_TTL)
+            },
             auth_token_override: global_flags.auth_token,
             yes: global_flags.yes,
+            read_only,
+            throttle: Throttle::new(cancellation_token.clone()),
+            cancellation_token,
             client_config,
             clients: tokio::sync::OnceCell::new(),
             templates: std::sync::OnceLock::new(),
@@ -132,14 +170,37 @@ impl Context {
         &self.config_dir
     }
 
+    pub fn profile_name(&self) -> &ProfileName {
+        &self.profile_name
+    }
+
     pub fn format(&self) -> Format {
         self.format
     }
 
+    pub fn profile_config(&self) -> &ProfileConfig {
+        self.profile.get_config()
+    }
+
     pub fn yes(&self) -> bool {
         self.yes
     }
 
+    /// Mutating commands are rejected in read-only mode, which can be enabled by the
+    /// --read-only flag, or by the selected profile
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    /// Shared client-side throttling for bulk operations, see [Throttle]
+    pub fn throttle(&self) -> &Throttle {
+        &self.throttle
+    }
+
     pub async fn silence_app_context_init(&self) {
         let mut state = self.app_context_state.write().await;
         state.silent_init = true;
@@ -157,6 +218,29 @@ impl Context {
         self.http_batch_size
     }
 
+    pub fn name_cache_ttl(&self) -> Duration {
+        self.name_cache_ttl
+    }
+
+    /// Scope of the name cache entries, see [name_cache::scope], None if the cloud profile is not
+    /// authenticated yet, as the resolutions cannot be tied to a token then
+    pub fn name_cache_scope(&self) -> Option<String> {
+        let auth_token_id = match &self.profile {
+            Profile::Golem(_) => None,
+            Profile::GolemCloud(profile) => Some(profile.auth.as_ref()?.data.id),
+        };
+        let url = self
+            .client_config
+            .cloud_url
+            .as_ref()
+            .unwrap_or(&self.client_config.component_url);
+        Some(name_cache::scope(
+            &self.profile_name,
+            url,
+            auth_token_id.as_ref(),
+        ))
+    }
+
     pub async fn clients(&self) -> anyhow::Result<&Clients> {
         self.clients
             .get_or_try_init(|| async {
@@ -183,6 +267,12 @@ impl Context {
         Ok(self.clients().await?.file_download.clone())
     }
 
+    /// Client for downloads not served by the Golem services (e.g. CLI releases), using the HTTP
+    /// and TLS settings of the profile, without authenticating
+    pub fn external_download_client(&self) -> anyhow::Result<reqwest::Client> {
+        new_reqwest_client(&self.client_config.file_download_http_client_config)
+    }
+
     pub async fn golem_clients_cloud(&self) -> anyhow::Result<&GolemClientsCloud> {
         match &self.clients().await?.golem {
             GolemClients::Oss(_) => Err(anyhow!(HintError::ExpectedCloudProfile)),
@@ -194,6 +284,32 @@ impl Context {
         &self.client_config.worker_url
     }
 
+    /// Queries the version of the server used by the selected profile
+    pub async fn server_version(&self) -> anyhow::Result<String> {
+        #[derive(serde::Deserialize)]
+        struct VersionInfo {
+            version: String,
+        }
+
+        let url = self.client_config.component_url.join("version")?;
+        let version_info = self
+            .clients()
+            .await?
+            .health_check
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to query server version from {url}"))?
+            .text()
+            .await
+            .with_context(|| format!("Failed to query server version from {url}"))?;
+        let version_info = serde_json::from_str::<VersionInfo>(&version_info)
+            .with_context(|| format!("Failed to parse server version from {url}"))?;
+
+        Ok(version_info.version)
+    }
+
     pub fn allow_insecure(&self) -> bool {
         self.client_config.service_http_client_config.allow_insecure
     }
@@ -263,6 +379,46 @@ impl Context {
         .await
     }
 
+    pub async fn set_reproducible_build(&self, reproducible: bool) {
+        self.set_app_ctx_init_config(
+            "reproducible_build",
+            |ctx| &mut ctx.reproducible_build,
+            |ctx| &mut ctx.reproducible_build_was_set,
+            reproducible,
+        )
+        .await
+    }
+
+    pub async fn set_keep_temp_build_dirs(&self, keep: bool) {
+        self.set_app_ctx_init_config(
+            "keep_temp_build_dirs",
+            |ctx| &mut ctx.keep_temp_build_dirs,
+            |ctx| &mut ctx.keep_temp_build_dirs_was_set,
+            keep,
+        )
+        .await
+    }
+
+    pub async fn set_build_jobs(&self, jobs: usize) {
+        self.set_app_ctx_init_config(
+            "build_jobs",
+            |ctx| &mut ctx.build_jobs,
+            |ctx| &mut ctx.build_jobs_was_set,
+            jobs,
+        )
+        .await
+    }
+
+    pub async fn set_install_tools(&self, install: bool) {
+        self.set_app_ctx_init_config(
+            "install_tools",
+            |ctx| &mut ctx.install_tools,
+            |ctx| &mut ctx.install_tools_was_set,
+            install,
+        )
+        .await
+    }
+
     pub async fn set_steps_filter(&self, steps_filter: HashSet<AppBuildStep>) {
         self.set_app_ctx_init_config(
             "steps_filter",
@@ -285,6 +441,7 @@ impl Context {
 pub struct Clients {
     pub golem: GolemClients,
     pub file_download: reqwest::Client,
+    pub health_check: reqwest::Client,
 }
 
 impl Clients {
@@ -299,6 +456,7 @@ impl Clients {
         let invoke_http_client = new_reqwest_client(&config.invoke_http_client_config)?;
         let file_download_http_client =
             new_reqwest_client(&config.file_download_http_client_config)?;
+        let health_check_http_client = new_reqwest_client(&config.health_check_http_client_config)?;
 
         match &config.cloud_url {
             Some(cloud_url) => {
@@ -404,6 +562,7 @@ impl Clients {
                         },
                     }),
                     file_download: file_download_http_client,
+                    health_check: health_check_http_client,
                 })
             }
             None => {
@@ -447,6 +606,7 @@ impl Clients {
                         },
                     }),
                     file_download: file_download_http_client,
+                    health_check: health_check_http_client,
                 })
             }
         }
@@ -507,6 +667,9 @@ struct ApplicationContextConfig {
     disable_app_manifest_discovery: bool,
     golem_rust_override: RustDependencyOverride,
     wasm_rpc_client_build_offline: bool,
+    build_dir: Option<PathBuf>,
+    manifest_variables: Vec<(String, String)>,
+    cancellation_token: CancellationToken,
 }
 
 #[derive(Default)]
@@ -516,6 +679,14 @@ pub struct ApplicationContextState {
     skip_up_to_date_checks_was_set: bool,
     pub build_steps_filter: HashSet<AppBuildStep>,
     build_steps_filter_was_set: bool,
+    pub reproducible_build: bool,
+    reproducible_build_was_set: bool,
+    pub keep_temp_build_dirs: bool,
+    keep_temp_build_dirs_was_set: bool,
+    pub build_jobs: usize,
+    build_jobs_was_set: bool,
+    pub install_tools: bool,
+    install_tools_was_set: bool,
 
     app_context: Option<Result<Option<ApplicationContext>, Arc<anyhow::Error>>>,
 }
@@ -547,7 +718,14 @@ impl ApplicationContextState {
             profile: config.build_profile.as_ref().map(|p| p.to_string().into()),
             offline: config.wasm_rpc_client_build_offline,
             steps_filter: self.build_steps_filter.clone(),
+            reproducible_build: self.reproducible_build,
+            keep_temp_build_dirs: self.keep_temp_build_dirs,
+            build_jobs: self.build_jobs,
+            install_tools: self.install_tools,
             golem_rust_override: config.golem_rust_override.clone(),
+            build_dir: config.build_dir.clone(),
+            manifest_variables: config.manifest_variables.clone(),
+            cancellation_token: config.cancellation_token.clone(),
         };
 
         debug!(config = ?config, "Initializing application context");
@@ -609,6 +787,19 @@ fn new_reqwest_client(config: &HttpClientConfig) -> anyhow::Result<reqwest::Clie
         builder = builder.read_timeout(read_timeout);
     }
 
+    // HTTP/2 is negotiated through ALPN for TLS endpoints, so multiple requests of bulk
+    // operations can be multiplexed on the same connection
+    builder = builder
+        .pool_idle_timeout(config.pool.idle_timeout)
+        .tcp_keepalive(config.pool.tcp_keepalive)
+        .http2_adaptive_window(true);
+    if let Some(max_idle_per_host) = config.pool.max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle_per_host);
+    }
+    if config.pool.http2_prior_knowledge {
+        builder = builder.http2_prior_knowledge();
+    }
+
     Ok(builder.connection_verbose(true).build()?)
 }
 
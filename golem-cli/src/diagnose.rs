@@ -798,6 +798,19 @@ pub fn diagnose(dir: &Path, language: Option<GuestLanguage>) {
     }
 }
 
+/// Short names of the tools required for building components of the given language,
+/// including their dependencies
+pub fn required_tools(language: GuestLanguage) -> Vec<&'static str> {
+    SelectedLanguage::from_flag(Path::new("."), language)
+        .map(|selected_language| {
+            Tool::with_all_dependencies(selected_language.language.tools())
+                .iter()
+                .map(|tool| tool.metadata().short_name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn report_tools(all_tools: Vec<DetectedTool>) {
     let (name_padding, version_padding) = {
         let mut name_padding = 0;
@@ -900,7 +913,7 @@ fn rust_package_version(dir: &Path, package_name: &str) -> Result<String, String
     })
 }
 
-fn npm_package_version(dir: &Path, package_name: &str) -> Result<String, String> {
+pub fn npm_package_version(dir: &Path, package_name: &str) -> Result<String, String> {
     fn trim_scope_symbol(s: &str) -> &str {
         if let Some(stripped) = s.strip_prefix("@") {
             stripped
@@ -1,6 +1,7 @@
 use crate::config::ProfileName;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
 use strum_macros::Display;
 
 // NonSuccessfulExit is used to signal that an error got resolved with hints or error messages
@@ -18,6 +19,34 @@ impl Display for NonSuccessfulExit {
 
 impl Error for NonSuccessfulExit {}
 
+// CancelledError is used to stop an operation at a safe point after the user requested
+// cancellation (Ctrl-C or SIGTERM), results up to that point are expected to be already logged.
+#[derive(Debug)]
+pub struct CancelledError;
+
+impl Display for CancelledError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cancelled")
+    }
+}
+
+impl Error for CancelledError {}
+
+pub const TIMEOUT_EXIT_CODE: u8 = 124;
+
+// TimeoutError is used when an operation did not finish within the user provided timeout,
+// the timeout itself and the taken actions are expected to be already logged.
+#[derive(Debug)]
+pub struct TimeoutError;
+
+impl Display for TimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Timed out")
+    }
+}
+
+impl Error for TimeoutError {}
+
 /// Errors that should be handled by the command handler with showing hints or error messages
 #[derive(Debug, Display)]
 pub enum HintError {
@@ -30,6 +59,7 @@ impl Error for HintError {}
 #[derive(Debug, Display)]
 pub enum ContextInitHintError {
     ProfileNotFound(ProfileName),
+    InvalidConfig { config_path: PathBuf, error: String },
 }
 
 impl Error for ContextInitHintError {}
@@ -145,6 +175,22 @@ pub mod service {
 
     impl Error for ServiceError {}
 
+    impl ServiceError {
+        pub fn status_code(&self) -> Option<u16> {
+            match &self.kind {
+                ServiceErrorKind::ErrorResponse(response) => Some(response.status_code),
+                ServiceErrorKind::ReqwestError(error) => error.status().map(|code| code.as_u16()),
+                ServiceErrorKind::ReqwestHeaderError(_) => None,
+                ServiceErrorKind::SerdeError(_) => None,
+                ServiceErrorKind::UnexpectedResponse { status_code, .. } => Some(*status_code),
+            }
+        }
+
+        pub fn is_rate_limited(&self) -> bool {
+            self.status_code() == Some(StatusCode::TOO_MANY_REQUESTS.as_u16())
+        }
+    }
+
     impl<T> From<golem_client::Error<T>> for ServiceError
     where
         T: Into<ServiceErrorResponse> + HasServiceName,
@@ -335,6 +335,25 @@ impl OverwriteSafeAction {
             OverwriteSafeAction::WriteFile { target, .. } => target,
         }
     }
+
+    pub fn new_content(&self) -> anyhow::Result<String> {
+        match self {
+            OverwriteSafeAction::CopyFile { source, .. } => read_to_string(source),
+            OverwriteSafeAction::CopyFileTransformed {
+                source_content_transformed,
+                ..
+            } => Ok(source_content_transformed.clone()),
+            OverwriteSafeAction::WriteFile { content, .. } => Ok(content.clone()),
+        }
+    }
+}
+
+/// Content change of a file, old content is None for new files
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub target: PathBuf,
+    pub old_content: Option<String>,
+    pub new_content: String,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -366,6 +385,28 @@ impl OverwriteSafeActions {
         self.0.iter().map(|a| a.target()).collect()
     }
 
+    /// Returns the file changes the actions would make, without running them
+    pub fn changes(&self) -> anyhow::Result<Vec<FileChange>> {
+        let mut changes = Vec::new();
+        for action in &self.0 {
+            let target = action.target();
+            let old_content = if target.exists() {
+                Some(read_to_string(target)?)
+            } else {
+                None
+            };
+            let new_content = action.new_content()?;
+            if old_content.as_ref() != Some(&new_content) {
+                changes.push(FileChange {
+                    target: target.to_path_buf(),
+                    old_content,
+                    new_content,
+                });
+            }
+        }
+        Ok(changes)
+    }
+
     pub fn run<F>(
         self,
         allow_overwrite: bool,
@@ -0,0 +1,562 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cloud::AccountId;
+use crate::config::{Config, ProfileName};
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use clap::ArgMatches;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const REDACTED: &str = "<REDACTED>";
+
+// Flags which always have a secret value
+const SECRET_FLAGS: &[&str] = &["auth-token"];
+
+// Keys of KEY=VALUE arguments (e.g. worker env vars) containing these are treated as secrets
+const SECRET_KEY_PARTS: &[&str] = &["token", "secret", "password", "credential"];
+
+// Commands which do not change any server side state, matched on their subcommand path prefix
+const READ_ONLY_COMMANDS: &[&[&str]] = &[
+    &["api", "cloud", "certificate", "get"],
+    &["api", "cloud", "domain", "get"],
+    &["api", "definition", "diff"],
+    &["api", "definition", "get"],
+    &["api", "definition", "lint"],
+    &["api", "definition", "list"],
+    &["api", "definition", "try"],
+    &["api", "deployment", "get"],
+    &["api", "deployment", "list"],
+    &["api", "security-scheme", "get"],
+    &["app", "build"],
+    &["app", "clean"],
+    &["app", "deps"],
+    &["app", "diagnose"],
+    &["app", "trace"],
+    &["cloud", "account", "get"],
+    &["cloud", "account", "grant", "get"],
+    &["cloud", "account", "grant", "roles"],
+    &["cloud", "project", "get-default"],
+    &["cloud", "project", "list"],
+    &["cloud", "project", "plugin", "get"],
+    &["cloud", "project", "policy", "get"],
+    &["cloud", "token", "list"],
+    &["completion"],
+    &["component", "build"],
+    &["component", "clean"],
+    &["component", "client-wit"],
+    &["component", "diagnose"],
+    &["component", "diff"],
+    &["component", "function-schema"],
+    &["component", "get"],
+    &["component", "inspect"],
+    &["component", "list"],
+    &["component", "plugin", "get"],
+    &["component", "templates"],
+    &["plugin", "get"],
+    &["plugin", "list"],
+    &["worker", "backlog"],
+    &["worker", "curl"],
+    &["worker", "get"],
+    &["worker", "import-invocations"],
+    &["worker", "list"],
+    &["worker", "logs"],
+    &["worker", "monitor"],
+    &["worker", "oplog"],
+    &["worker", "stream"],
+];
+
+// Flags which make otherwise mutating commands read-only, matched on the subcommand path prefix
+// and the flag's argument ID
+const READ_ONLY_FLAGS: &[(&[&str], &str)] = &[
+    (&["component", "deploy"], "plan"),
+    (&["worker", "delete-many"], "dry_run"),
+];
+
+// Commands which are not recorded, matched on their subcommand path prefix
+const UNRECORDED_COMMANDS: &[&[&str]] = &[
+    &["app", "new"],
+    // Recorded explicitly by the handler, as an audit entry
+    &["cloud", "admin", "impersonate"],
+    &["component", "new"],
+    &["alias"],
+    &["cache"],
+    &["history"],
+    &["plugin", "new"],
+    &["self-update"],
+    &["serve"],
+    &["server"],
+];
+
+// Commands which only change local state, so they are allowed in read-only mode, matched on
+// their subcommand path prefix
+const LOCAL_ONLY_COMMANDS: &[&[&str]] = &[
+    &["alias"],
+    &["app", "new"],
+    &["cache"],
+    &["component", "new"],
+    &["history"],
+    &["profile"],
+    &["self-update"],
+    &["serve"],
+    &["server"],
+];
+
+// Flags which make local-only or read-only commands change server side state too, matched on the
+// subcommand path prefix and the flag's argument ID
+const SERVER_SIDE_FLAGS: &[(&[&str], &str)] = &[
+    (&["profile", "delete"], "purge_tokens"),
+    (&["worker", "curl"], "execute"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub command: String,
+    pub args: Vec<String>,
+    pub profile: ProfileName,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub working_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub impersonated_account_id: Option<AccountId>,
+    /// Set for entries recorded before the command is executed, the outcome of the command is
+    /// recorded in a separate entry
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub started: bool,
+    pub success: bool,
+}
+
+pub fn history_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("history.jsonl")
+}
+
+/// Records the command into the history journal, if the journal is enabled and the command
+/// is a mutating one. Secrets in the arguments are redacted.
+pub fn record(
+    config_dir: &Path,
+    profile_name: &ProfileName,
+    args: &[OsString],
+    matches: &ArgMatches,
+    success: bool,
+) -> anyhow::Result<()> {
+    let Some(command_path) = recorded_command_path(matches) else {
+        return Ok(());
+    };
+
+    if !Config::from_dir(config_dir)?.history_enabled {
+        return Ok(());
+    }
+
+    append(
+        config_dir,
+        &HistoryEntry {
+            timestamp: Utc::now(),
+            command: command_path.join(" "),
+            args: redact_args(args),
+            profile: profile_name.clone(),
+            user: current_user(),
+            working_dir: std::env::current_dir().ok(),
+            impersonated_account_id: None,
+            started: false,
+            success,
+        },
+    )
+}
+
+/// Records the start of an admin impersonation into the history journal. Unlike other commands,
+/// these are always recorded, even if the journal is disabled, to serve as an audit entry, so
+/// the impersonation must not be started if recording fails.
+pub fn record_impersonation_started(
+    config_dir: &Path,
+    profile_name: &ProfileName,
+    args: &[OsString],
+    impersonated_account_id: &AccountId,
+) -> anyhow::Result<()> {
+    append(
+        config_dir,
+        &impersonation_entry(profile_name, args, impersonated_account_id, true, true),
+    )
+}
+
+/// Records the outcome of an admin impersonation, started with [record_impersonation_started]
+pub fn record_impersonation_finished(
+    config_dir: &Path,
+    profile_name: &ProfileName,
+    args: &[OsString],
+    impersonated_account_id: &AccountId,
+    success: bool,
+) -> anyhow::Result<()> {
+    append(
+        config_dir,
+        &impersonation_entry(profile_name, args, impersonated_account_id, false, success),
+    )
+}
+
+fn impersonation_entry(
+    profile_name: &ProfileName,
+    args: &[OsString],
+    impersonated_account_id: &AccountId,
+    started: bool,
+    success: bool,
+) -> HistoryEntry {
+    HistoryEntry {
+        timestamp: Utc::now(),
+        command: "cloud admin impersonate".to_string(),
+        args: redact_args(args),
+        profile: profile_name.clone(),
+        user: current_user(),
+        working_dir: std::env::current_dir().ok(),
+        impersonated_account_id: Some(impersonated_account_id.clone()),
+        started,
+        success,
+    }
+}
+
+fn current_user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+}
+
+pub fn append(config_dir: &Path, entry: &HistoryEntry) -> anyhow::Result<()> {
+    create_dir_all(config_dir).map_err(|err| anyhow!("Can't create config directory: {err}"))?;
+
+    let history_path = history_path(config_dir);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .with_context(|| anyhow!("Failed to open history file: {}", history_path.display()))?;
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .with_context(|| anyhow!("Failed to write history file: {}", history_path.display()))
+}
+
+pub fn read_all(config_dir: &Path) -> anyhow::Result<Vec<HistoryEntry>> {
+    let history_path = history_path(config_dir);
+
+    if !history_path
+        .try_exists()
+        .with_context(|| anyhow!("Failed to check history file: {}", history_path.display()))?
+    {
+        return Ok(vec![]);
+    }
+
+    let file = File::open(&history_path)
+        .with_context(|| anyhow!("Failed to open history file: {}", history_path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|(idx, line)| {
+            let line = line.with_context(|| {
+                anyhow!("Failed to read history file: {}", history_path.display())
+            })?;
+            serde_json::from_str(&line).with_context(|| {
+                anyhow!(
+                    "Failed to parse history file {}, line {}",
+                    history_path.display(),
+                    idx + 1
+                )
+            })
+        })
+        .collect()
+}
+
+/// Returns the subcommand path of the parsed command line
+pub fn command_path(matches: &ArgMatches) -> Vec<String> {
+    let mut path = Vec::new();
+    let mut matches = matches;
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        path.push(name.to_string());
+        matches = sub_matches;
+    }
+    path
+}
+
+/// Returns whether the command could change server side state, these are rejected in read-only
+/// mode
+pub fn is_mutating_command(command_path: &[String]) -> bool {
+    !command_path.is_empty()
+        && !has_any_prefix(READ_ONLY_COMMANDS, command_path)
+        && !has_any_prefix(LOCAL_ONLY_COMMANDS, command_path)
+}
+
+/// Returns the subcommand path of the parsed command line if the command could change server
+/// side state, also considering the flags which change whether the command is mutating
+pub fn mutating_command_path(matches: &ArgMatches) -> Option<Vec<String>> {
+    let command_path = command_path(matches);
+    let mutating =
+        is_mutating_command(&command_path) && !has_flag(matches, &command_path, READ_ONLY_FLAGS);
+    (mutating || has_flag(matches, &command_path, SERVER_SIDE_FLAGS)).then_some(command_path)
+}
+
+/// Returns the subcommand path of the parsed command line if the command is recorded, read-only
+/// commands with server side flags (e.g. worker curl --execute) are recorded too
+fn recorded_command_path(matches: &ArgMatches) -> Option<Vec<String>> {
+    let command_path = command_path(matches);
+    let recorded =
+        is_recorded_command(&command_path) && !has_flag(matches, &command_path, READ_ONLY_FLAGS);
+    (recorded || has_flag(matches, &command_path, SERVER_SIDE_FLAGS)).then_some(command_path)
+}
+
+fn has_flag(matches: &ArgMatches, command_path: &[String], flags: &[(&[&str], &str)]) -> bool {
+    let mut matches = matches;
+    while let Some((_, sub_matches)) = matches.subcommand() {
+        matches = sub_matches;
+    }
+
+    flags.iter().any(|(prefix, flag)| {
+        has_any_prefix(&[*prefix], command_path)
+            && matches
+                .try_get_one::<bool>(flag)
+                .ok()
+                .flatten()
+                .is_some_and(|value| *value)
+    })
+}
+
+fn is_recorded_command(command_path: &[String]) -> bool {
+    !command_path.is_empty()
+        && !has_any_prefix(READ_ONLY_COMMANDS, command_path)
+        && !has_any_prefix(UNRECORDED_COMMANDS, command_path)
+}
+
+fn has_any_prefix(prefixes: &[&[&str]], command_path: &[String]) -> bool {
+    prefixes.iter().any(|prefix| {
+        prefix.len() <= command_path.len()
+            && prefix
+                .iter()
+                .zip(command_path)
+                .all(|(prefix, command)| prefix == command)
+    })
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SECRET_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+fn redact_args(args: &[OsString]) -> Vec<String> {
+    let mut redact_next = false;
+
+    args.iter()
+        .skip(1)
+        .map(|arg| {
+            let arg = arg.to_string_lossy().to_string();
+
+            if redact_next {
+                redact_next = false;
+                return REDACTED.to_string();
+            }
+
+            if let Some(flag) = arg.strip_prefix("--") {
+                return match flag.split_once('=') {
+                    Some((name, _)) if SECRET_FLAGS.contains(&name) => {
+                        format!("--{}={}", name, REDACTED)
+                    }
+                    Some(_) => arg,
+                    None => {
+                        redact_next = SECRET_FLAGS.contains(&flag);
+                        arg
+                    }
+                };
+            }
+
+            match arg.split_once('=') {
+                Some((key, _)) if is_secret_key(key) => format!("{}={}", key, REDACTED),
+                _ => arg,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::GolemCliCommand;
+    use crate::history::{
+        is_mutating_command, is_recorded_command, mutating_command_path, recorded_command_path,
+        redact_args,
+    };
+    use assert2::check;
+    use clap::{ArgMatches, CommandFactory};
+    use std::ffi::OsString;
+    use test_r::test;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn matches(args: &[&str]) -> ArgMatches {
+        GolemCliCommand::command()
+            .try_get_matches_from(args)
+            .unwrap()
+    }
+
+    fn path(path: &[&str]) -> Vec<String> {
+        path.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn redact_secrets() {
+        check!(
+            redact_args(&args(&[
+                "golem-cli",
+                "--auth-token",
+                "11111111-2222-3333-4444-555555555555",
+                "worker",
+                "new",
+                "comp/worker",
+                "-e",
+                "API_TOKEN=abc",
+                "-e",
+                "MODE=debug",
+            ])) == path(&[
+                "--auth-token",
+                "<REDACTED>",
+                "worker",
+                "new",
+                "comp/worker",
+                "-e",
+                "API_TOKEN=<REDACTED>",
+                "-e",
+                "MODE=debug",
+            ])
+        );
+        check!(
+            redact_args(&args(&[
+                "golem-cli",
+                "--auth-token=secret",
+                "app",
+                "deploy"
+            ])) == path(&["--auth-token=<REDACTED>", "app", "deploy"])
+        );
+    }
+
+    #[test]
+    fn recorded_commands() {
+        check!(is_recorded_command(&path(&["app", "deploy"])));
+        check!(is_recorded_command(&path(&["worker", "new"])));
+        check!(is_recorded_command(&path(&["cloud", "project", "new"])));
+        check!(!is_recorded_command(&path(&["worker", "list"])));
+        check!(!is_recorded_command(&path(&["component", "new"])));
+        check!(!is_recorded_command(&path(&["history", "list"])));
+        check!(!is_recorded_command(&path(&[
+            "cloud",
+            "admin",
+            "impersonate"
+        ])));
+        check!(!is_recorded_command(&path(&[])));
+
+        check!(
+            recorded_command_path(&matches(&["golem-cli", "worker", "curl", "w1", "run"]))
+                .is_none()
+        );
+        check!(
+            recorded_command_path(&matches(&[
+                "golem-cli",
+                "worker",
+                "curl",
+                "w1",
+                "run",
+                "--execute"
+            ])) == Some(path(&["worker", "curl"]))
+        );
+    }
+
+    #[test]
+    fn mutating_commands() {
+        check!(is_mutating_command(&path(&["app", "deploy"])));
+        check!(is_mutating_command(&path(&["worker", "invoke"])));
+        check!(is_mutating_command(&path(&[
+            "cloud",
+            "admin",
+            "impersonate"
+        ])));
+        check!(!is_mutating_command(&path(&["worker", "list"])));
+        check!(!is_mutating_command(&path(&["app", "build"])));
+        check!(!is_mutating_command(&path(&["component", "new"])));
+        check!(!is_mutating_command(&path(&["profile", "switch"])));
+        check!(!is_mutating_command(&path(&["history", "disable"])));
+        check!(!is_mutating_command(&path(&[])));
+        // Read-only commands are matched on their full path
+        check!(!is_mutating_command(&path(&["component", "plugin", "get"])));
+        check!(is_mutating_command(&path(&[
+            "component",
+            "plugin",
+            "install"
+        ])));
+        check!(is_mutating_command(&path(&["app", "bench"])));
+    }
+
+    #[test]
+    fn read_only_flags() {
+        check!(
+            mutating_command_path(&matches(&["golem-cli", "component", "deploy"]))
+                == Some(path(&["component", "deploy"]))
+        );
+        check!(
+            mutating_command_path(&matches(&["golem-cli", "component", "deploy", "--plan"]))
+                .is_none()
+        );
+        check!(
+            recorded_command_path(&matches(&["golem-cli", "component", "deploy", "--plan"]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn mutating_flags() {
+        check!(
+            mutating_command_path(&matches(&["golem-cli", "profile", "delete", "local"])).is_none()
+        );
+        check!(
+            mutating_command_path(&matches(&[
+                "golem-cli",
+                "profile",
+                "delete",
+                "local",
+                "--purge-tokens"
+            ])) == Some(path(&["profile", "delete"]))
+        );
+        check!(
+            mutating_command_path(&matches(&["golem-cli", "app", "deploy"]))
+                == Some(path(&["app", "deploy"]))
+        );
+        check!(
+            mutating_command_path(&matches(&["golem-cli", "worker", "curl", "w1", "run"]))
+                .is_none()
+        );
+        check!(
+            mutating_command_path(&matches(&[
+                "golem-cli",
+                "worker",
+                "curl",
+                "w1",
+                "run",
+                "--execute"
+            ])) == Some(path(&["worker", "curl"]))
+        );
+    }
+}
@@ -16,8 +16,10 @@ use clap_verbosity_flag::Verbosity;
 use shadow_rs::shadow;
 use tracing_subscriber::FmtSubscriber;
 
+pub mod alias;
 pub mod app;
 pub mod auth;
+pub mod cancellation;
 pub mod cloud;
 pub mod command;
 pub mod command_handler;
@@ -28,9 +30,14 @@ pub mod diagnose;
 pub mod error;
 pub mod fs;
 pub mod fuzzy;
+pub mod history;
 pub mod log;
 pub mod model;
+pub mod name_cache;
+pub mod self_update;
+pub mod throttle;
 pub mod validation;
+pub mod version_notes;
 pub mod wasm_rpc_stubgen;
 
 #[cfg(test)]
@@ -61,6 +68,7 @@ pub fn init_tracing(verbosity: Verbosity) {
         let subscriber = FmtSubscriber::builder()
             .with_max_level(level)
             .with_writer(std::io::stderr)
+            .with_ansi(crate::log::colors_enabled())
             .finish();
 
         tracing::subscriber::set_global_default(subscriber)
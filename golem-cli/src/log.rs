@@ -12,23 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::fs::{OverwriteSafeAction, OverwriteSafeActionPlan, PathExtra};
+use crate::fs::{FileChange, OverwriteSafeAction, OverwriteSafeActionPlan, PathExtra};
+use crate::model::ColorMode;
 use colored::{ColoredString, Colorize};
+use similar::{ChangeTag, TextDiff};
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{LazyLock, OnceLock, RwLock};
 use terminal_size::terminal_size;
 use textwrap::WordSplitter;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::debug;
 
 static LOG_STATE: LazyLock<RwLock<LogState>> = LazyLock::new(RwLock::default);
 static TERMINAL_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
 static WRAP_PADDING: usize = 2;
+static ASCII_ONLY: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static THREAD_LOG_PREFIX: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
 fn terminal_width() -> Option<usize> {
     *TERMINAL_WIDTH.get_or_init(|| terminal_size().map(|(width, _)| width.0 as usize))
 }
 
+/// Sets up colors and the character set used by logs and text views. NO_COLOR is only
+/// respected in auto mode, so colors can still be forced with --color always.
+pub fn init_output_style(color: ColorMode, ascii: bool) {
+    if let Some(colorize) = color_override(color, std::env::var("NO_COLOR").ok().as_deref()) {
+        colored::control::set_override(colorize);
+    }
+    ASCII_ONLY.store(ascii, Ordering::Relaxed);
+}
+
+fn color_override(color: ColorMode, no_color: Option<&str>) -> Option<bool> {
+    match color {
+        ColorMode::Always => Some(true),
+        ColorMode::Never => Some(false),
+        ColorMode::Auto => no_color.filter(|value| !value.is_empty()).map(|_| false),
+    }
+}
+
+pub fn colors_enabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Whether logs and text views should avoid Unicode box-drawing and other non-ASCII characters
+pub fn ascii_only() -> bool {
+    ASCII_ONLY.load(Ordering::Relaxed)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Output {
     Stdout,
@@ -111,6 +148,30 @@ impl Drop for LogIndent {
     }
 }
 
+/// Prefixes every line logged by the current thread, used for telling apart the interleaved
+/// output of concurrently running tasks
+pub struct LogThreadPrefix {
+    prev_prefix: Option<String>,
+}
+
+impl LogThreadPrefix {
+    pub fn new<S: AsRef<str>>(prefix: S) -> Self {
+        let prev_prefix =
+            THREAD_LOG_PREFIX.with_borrow_mut(|p| p.replace(prefix.as_ref().to_string()));
+        Self { prev_prefix }
+    }
+}
+
+impl Drop for LogThreadPrefix {
+    fn drop(&mut self) {
+        THREAD_LOG_PREFIX.with_borrow_mut(|p| *p = self.prev_prefix.take());
+    }
+}
+
+pub fn log_thread_prefix() -> Option<String> {
+    THREAD_LOG_PREFIX.with_borrow(|p| p.clone())
+}
+
 pub struct LogOutput {
     prev_output: Output,
 }
@@ -134,6 +195,40 @@ pub fn set_log_output(output: Output) {
     LOG_STATE.write().unwrap().set_output(output);
 }
 
+/// Command output which is sent to an output sink instead of being printed, used when
+/// serving CLI operations for other processes
+#[derive(Debug, Clone)]
+pub enum CapturedOutput {
+    View(serde_json::Value),
+    StreamEvent(serde_json::Value),
+    Error(String),
+}
+
+pub type OutputSink = UnboundedSender<CapturedOutput>;
+
+tokio::task_local! {
+    static OUTPUT_SINK: OutputSink;
+}
+
+/// Runs the future with its views, stream events and errors sent to the sink
+pub async fn with_output_sink<F: Future>(sink: OutputSink, future: F) -> F::Output {
+    OUTPUT_SINK.scope(sink, future).await
+}
+
+/// Returns the output sink of the current task, it has to be cloned for spawned tasks
+pub fn output_sink() -> Option<OutputSink> {
+    OUTPUT_SINK.try_with(|sink| sink.clone()).ok()
+}
+
+/// Sends the output to the sink of the current task, returns false if there is no sink
+pub fn capture_output<F: FnOnce() -> CapturedOutput>(output: F) -> bool {
+    OUTPUT_SINK
+        .try_with(|sink| {
+            let _ = sink.send(output());
+        })
+        .is_ok()
+}
+
 pub fn log_action<T: AsRef<str>>(action: &str, subject: T) {
     logln_internal(&format!(
         "{} {}",
@@ -160,6 +255,7 @@ pub fn logln<T: AsRef<str>>(message: T) {
 
 pub fn logln_internal(message: &str) {
     let state = LOG_STATE.read().unwrap();
+    let prefix = log_thread_prefix().unwrap_or_default();
 
     let lines = match state.max_width {
         Some(width) if width <= message.len() && !message.contains("\n") => {
@@ -179,14 +275,14 @@ pub fn logln_internal(message: &str) {
     for line in lines {
         match state.output {
             Output::Stdout => {
-                println!("{}{}", state.calculated_indent, line)
+                println!("{}{}{}", prefix, state.calculated_indent, line)
             }
             Output::Stderr => {
-                eprintln!("{}{}", state.calculated_indent, line)
+                eprintln!("{}{}{}", prefix, state.calculated_indent, line)
             }
             Output::None => {}
             Output::TracingDebug => {
-                debug!("{}{}", state.calculated_indent, line);
+                debug!("{}{}{}", prefix, state.calculated_indent, line);
             }
         }
     }
@@ -289,6 +385,35 @@ pub fn log_action_plan(action: &OverwriteSafeAction, plan: OverwriteSafeActionPl
     }
 }
 
+/// Logs a file change as a unified diff, colored when the output supports it
+pub fn log_file_change_diff(change: &FileChange) {
+    let old_content = change.old_content.as_deref().unwrap_or_default();
+    let diff = TextDiff::from_lines(old_content, &change.new_content);
+
+    let target = change.target.display().to_string();
+    logln(
+        match &change.old_content {
+            Some(_) => format!("--- a/{}", target),
+            None => "--- /dev/null".to_string(),
+        }
+        .bold()
+        .to_string(),
+    );
+    logln(format!("+++ b/{}", target).bold().to_string());
+
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        logln(hunk.header().to_string().cyan().to_string());
+        for line in hunk.iter_changes() {
+            let value = line.value().trim_end_matches(['\n', '\r']);
+            match line.tag() {
+                ChangeTag::Delete => logln(format!("-{}", value).red().to_string()),
+                ChangeTag::Insert => logln(format!("+{}", value).green().to_string()),
+                ChangeTag::Equal => logln(format!(" {}", value)),
+            }
+        }
+    }
+}
+
 pub trait LogColorize {
     fn as_str(&self) -> impl Colorize;
 
@@ -350,3 +475,20 @@ impl<P: AsRef<Path>> LogColorize for PathExtra<P> {
         ColoredString::from(self.display().to_string())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::log::color_override;
+    use crate::model::ColorMode;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn color_override_respects_no_color_in_auto_mode() {
+        check!(color_override(ColorMode::Auto, None).is_none());
+        check!(color_override(ColorMode::Auto, Some("")).is_none());
+        check!(color_override(ColorMode::Auto, Some("1")) == Some(false));
+        check!(color_override(ColorMode::Always, Some("1")) == Some(true));
+        check!(color_override(ColorMode::Never, None) == Some(false));
+    }
+}
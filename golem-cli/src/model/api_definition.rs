@@ -0,0 +1,999 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use url::Url;
+
+// Lints work on the JSON representation of API definitions, so the same rules can be applied
+// to local definition files (OSS and Cloud requests) and to stored definitions (responses).
+
+const MUTATING_METHODS: &[&str] = &["POST", "PUT", "PATCH", "DELETE"];
+
+const RIB_BINDING_FIELDS: &[&str] = &[
+    "workerName",
+    "idempotencyKey",
+    "response",
+    "invocationContext",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiDefinitionLintSeverity {
+    Error,
+    Warning,
+}
+
+impl Display for ApiDefinitionLintSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiDefinitionLintSeverity::Error => write!(f, "error"),
+            ApiDefinitionLintSeverity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDefinitionLintFinding {
+    pub severity: ApiDefinitionLintSeverity,
+    pub rule: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub route: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDefinitionLintResult {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<String>,
+    pub findings: Vec<ApiDefinitionLintFinding>,
+}
+
+impl ApiDefinitionLintResult {
+    pub fn error_count(&self) -> usize {
+        self.count(ApiDefinitionLintSeverity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(ApiDefinitionLintSeverity::Warning)
+    }
+
+    fn count(&self, severity: ApiDefinitionLintSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+}
+
+pub fn lint_api_definition(definition: &Value) -> ApiDefinitionLintResult {
+    let mut linter = Linter::default();
+
+    let Some(routes) = definition
+        .get("routes")
+        .and_then(|routes| routes.as_array())
+    else {
+        linter.error(
+            "invalid-definition",
+            None,
+            "Missing or invalid routes, expected a list of routes",
+        );
+        return linter.into_result(definition);
+    };
+
+    let mut routes_by_key = BTreeMap::<(String, String), usize>::new();
+
+    for (idx, route) in routes.iter().enumerate() {
+        let method = route
+            .get("method")
+            .and_then(|method| method.as_str())
+            .map(|method| method.to_uppercase());
+        let path = route.get("path").and_then(|path| path.as_str());
+
+        let (method, path) = match (method, path) {
+            (Some(method), Some(path)) => (method, path),
+            _ => {
+                linter.error(
+                    "invalid-definition",
+                    Some(format!("#{}", idx + 1)),
+                    "Route is missing its method or path",
+                );
+                continue;
+            }
+        };
+        let route_label = format!("{} {}", method, path);
+
+        *routes_by_key
+            .entry((method.clone(), normalize_path(path)))
+            .or_default() += 1;
+
+        let binding = route.get("binding").filter(|binding| !binding.is_null());
+        let binding_type = binding
+            .and_then(|binding| binding.get("bindingType").or_else(|| binding.get("type")))
+            .and_then(|binding_type| binding_type.as_str())
+            .map(normalize_binding_type)
+            .unwrap_or_else(|| "default".to_string());
+
+        match binding {
+            None => {
+                linter.error(
+                    "unbound-route",
+                    Some(route_label.clone()),
+                    "Route has no binding",
+                );
+            }
+            Some(binding) => {
+                lint_binding(&mut linter, &route_label, binding, &binding_type);
+            }
+        }
+
+        let has_security = route
+            .get("security")
+            .is_some_and(|security| !security.is_null());
+        if MUTATING_METHODS.contains(&method.as_str())
+            && !has_security
+            && binding_type != "corspreflight"
+        {
+            linter.warning(
+                "missing-security",
+                Some(route_label),
+                "Mutating route has no security scheme",
+            );
+        }
+    }
+
+    for ((method, path), count) in routes_by_key {
+        if count > 1 {
+            linter.error(
+                "duplicate-route",
+                Some(format!("{} {}", method, path)),
+                format!("Route is defined {} times", count),
+            );
+        }
+    }
+
+    linter.into_result(definition)
+}
+
+fn lint_binding(linter: &mut Linter, route_label: &str, binding: &Value, binding_type: &str) {
+    let requires_component = matches!(binding_type, "default" | "fileserver" | "httphandler");
+    let requires_response = matches!(binding_type, "default" | "fileserver");
+
+    if requires_component {
+        let has_component = binding
+            .get("component")
+            .and_then(|component| component.get("name"))
+            .or_else(|| binding.get("componentName"))
+            .is_some_and(|name| name.as_str().is_some_and(|name| !name.is_empty()));
+        if !has_component {
+            linter.error(
+                "unbound-route",
+                Some(route_label.to_string()),
+                "Binding has no component",
+            );
+        }
+    }
+
+    if requires_response {
+        let has_response = binding
+            .get("response")
+            .and_then(|response| response.as_str())
+            .is_some_and(|response| !response.trim().is_empty());
+        if !has_response {
+            linter.error(
+                "unbound-route",
+                Some(route_label.to_string()),
+                "Binding has no response mapping",
+            );
+        }
+    }
+
+    for field in RIB_BINDING_FIELDS {
+        let Some(script) = binding.get(*field).and_then(|script| script.as_str()) else {
+            continue;
+        };
+        if script.trim().is_empty() {
+            continue;
+        }
+        if let Err(error) = rib::from_string(script) {
+            linter.error(
+                "rib-syntax",
+                Some(route_label.to_string()),
+                format!("Invalid Rib script in {}: {}", field, error.trim()),
+            );
+        }
+    }
+}
+
+/// Request used for trying API definition routes, in `METHOD /path?query` form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiRouteRequest {
+    pub method: String,
+    pub path: String,
+    pub query: BTreeMap<String, String>,
+}
+
+impl FromStr for ApiRouteRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((method, target)) = s.trim().split_once(char::is_whitespace) else {
+            return Err(format!(
+                "Invalid route: {s}. Expected METHOD /path, e.g. 'GET /users/1'"
+            ));
+        };
+        let target = target.trim();
+        if !target.starts_with('/') {
+            return Err(format!("Invalid route: {s}. The path must start with '/'"));
+        }
+
+        let url = Url::parse(&format!("http://localhost{}", target))
+            .map_err(|err| format!("Invalid route: {s}. {err}"))?;
+
+        Ok(Self {
+            method: method.to_uppercase(),
+            path: url.path().to_string(),
+            query: url.query_pairs().into_owned().collect(),
+        })
+    }
+}
+
+/// Result of resolving a request against the routes of an API definition locally
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRouteTryResult {
+    pub method: String,
+    pub path: String,
+    pub binding_type: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component_version: Option<u64>,
+    pub path_params: BTreeMap<String, String>,
+    pub query_params: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub worker_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub response: Option<String>,
+    /// Exported component functions called by the Rib scripts of the binding
+    pub functions: Vec<String>,
+    /// Request fields used by the Rib scripts, which are not provided by the request
+    pub unresolved: Vec<String>,
+}
+
+impl ApiRouteTryResult {
+    pub fn rib_scripts(&self) -> impl Iterator<Item = &str> {
+        [&self.worker_name, &self.idempotency_key, &self.response]
+            .into_iter()
+            .flatten()
+            .map(|script| script.as_str())
+    }
+}
+
+/// Selects the route matching the request, preferring literal path segments over
+/// path variables, and maps the request parameters used by the binding
+pub fn try_api_route(
+    definition: &Value,
+    request: &ApiRouteRequest,
+    headers: BTreeMap<String, String>,
+    body: Option<Value>,
+) -> Option<ApiRouteTryResult> {
+    let routes = definition.get("routes")?.as_array()?;
+
+    let (route, path_pattern, path_params, _) = routes
+        .iter()
+        .filter_map(|route| {
+            let method = route.get("method")?.as_str()?;
+            if !method.eq_ignore_ascii_case(&request.method) {
+                return None;
+            }
+            let path = route.get("path")?.as_str()?;
+            let (path_params, score) = match_path(path, &request.path)?;
+            Some((route, path, path_params, score))
+        })
+        .max_by(|(_, _, _, a), (_, _, _, b)| a.cmp(b))?;
+
+    let query_params = path_pattern
+        .split_once('?')
+        .map(|(_, query)| {
+            query
+                .split('&')
+                .filter_map(|var| var.strip_prefix('{')?.strip_suffix('}'))
+                .filter_map(|var| {
+                    request
+                        .query
+                        .get(var)
+                        .map(|value| (var.to_string(), value.clone()))
+                })
+                .collect::<BTreeMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let binding = route.get("binding").filter(|binding| !binding.is_null());
+    let binding_field = |name: &str| {
+        binding
+            .and_then(|binding| binding.get(name))
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| value.to_string())
+    };
+    let component = binding.and_then(|binding| binding.get("component"));
+
+    let mut result = ApiRouteTryResult {
+        method: request.method.clone(),
+        path: path_pattern.to_string(),
+        binding_type: binding_field("bindingType")
+            .or_else(|| binding_field("type"))
+            .unwrap_or_else(|| "default".to_string()),
+        component_name: component
+            .and_then(|component| component.get("name"))
+            .and_then(|name| name.as_str())
+            .map(|name| name.to_string())
+            .or_else(|| binding_field("componentName")),
+        component_version: component
+            .and_then(|component| component.get("version"))
+            .and_then(|version| version.as_u64())
+            .or_else(|| {
+                binding
+                    .and_then(|binding| binding.get("componentVersion"))
+                    .and_then(|version| version.as_u64())
+            }),
+        path_params,
+        query_params,
+        headers,
+        body,
+        worker_name: binding_field("workerName"),
+        idempotency_key: binding_field("idempotencyKey"),
+        response: binding_field("response"),
+        functions: vec![],
+        unresolved: vec![],
+    };
+    result.unresolved = unresolved_request_fields(&result);
+
+    Some(result)
+}
+
+// Returns the path variables and a score for choosing between matching routes
+fn match_path(pattern: &str, path: &str) -> Option<(BTreeMap<String, String>, Vec<u8>)> {
+    let pattern = pattern.split_once('?').map_or(pattern, |(path, _)| path);
+    let pattern_segments = path_segments(pattern);
+    let segments = path_segments(path);
+
+    let mut params = BTreeMap::new();
+    let mut score = vec![];
+    for (idx, pattern_segment) in pattern_segments.iter().enumerate() {
+        match pattern_segment
+            .strip_prefix('{')
+            .and_then(|var| var.strip_suffix('}'))
+        {
+            Some(var) if var.starts_with('+') => {
+                params.insert(
+                    var.trim_start_matches('+').to_string(),
+                    segments.get(idx..)?.join("/"),
+                );
+                score.push(0);
+                return Some((params, score));
+            }
+            Some(var) => {
+                params.insert(var.to_string(), segments.get(idx)?.to_string());
+                score.push(1);
+            }
+            None => {
+                if segments.get(idx)? != pattern_segment {
+                    return None;
+                }
+                score.push(2);
+            }
+        }
+    }
+
+    (pattern_segments.len() == segments.len()).then_some((params, score))
+}
+
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn unresolved_request_fields(result: &ApiRouteTryResult) -> Vec<String> {
+    let mut unresolved = vec![];
+    for script in result.rib_scripts() {
+        for (idx, _) in script.match_indices("request.") {
+            if idx > 0 && is_rib_identifier_char(script[..idx].chars().last().unwrap()) {
+                continue;
+            }
+            let reference = script[idx..]
+                .chars()
+                .take_while(|c| is_rib_identifier_char(*c) || *c == '.')
+                .collect::<String>();
+            let mut parts = reference.split('.').skip(1);
+            let resolved = match (parts.next(), parts.next()) {
+                (Some("path"), Some(name)) => {
+                    result.path_params.contains_key(name) || result.query_params.contains_key(name)
+                }
+                (Some("headers" | "header"), Some(name)) => result
+                    .headers
+                    .keys()
+                    .any(|header| header.eq_ignore_ascii_case(name)),
+                (Some("body"), Some(field)) => result
+                    .body
+                    .as_ref()
+                    .is_some_and(|body| body.get(field).is_some()),
+                (Some("body"), None) => result.body.is_some(),
+                _ => true,
+            };
+            if !resolved && !unresolved.contains(&reference) {
+                unresolved.push(reference);
+            }
+        }
+    }
+    unresolved
+}
+
+/// Checks if the Rib script calls the function, e.g. `worker.get-cart-contents()` or
+/// `worker.{golem:it/api.{get-cart-contents}}()`
+pub fn rib_script_calls_function(script: &str, function_name: &str) -> bool {
+    script.match_indices(function_name).any(|(idx, _)| {
+        let preceded_by_identifier = script[..idx]
+            .chars()
+            .last()
+            .is_some_and(is_rib_identifier_char);
+        let rest = script[idx + function_name.len()..].trim_start_matches('}');
+        !preceded_by_identifier && rest.trim_start().starts_with('(')
+    })
+}
+
+fn is_rib_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Binding fields accepted in API definition requests, response only fields are dropped
+/// when a stored definition is cloned
+const REQUEST_BINDING_FIELDS: &[&str] = &[
+    "bindingType",
+    "type",
+    "component",
+    "componentName",
+    "workerName",
+    "idempotencyKey",
+    "response",
+    "invocationContext",
+];
+
+/// Turns a stored API definition into a request for creating it again as a new version
+pub fn bump_api_definition(definition: &Value, to_version: &str, draft: bool) -> Value {
+    let mut request = serde_json::Map::new();
+    request.insert(
+        "id".to_string(),
+        definition.get("id").cloned().unwrap_or(Value::Null),
+    );
+    request.insert("version".to_string(), Value::String(to_version.to_string()));
+    request.insert("draft".to_string(), Value::Bool(draft));
+    if let Some(security) = definition.get("security").filter(|value| !value.is_null()) {
+        request.insert("security".to_string(), security.clone());
+    }
+
+    let routes = definition
+        .get("routes")
+        .and_then(|routes| routes.as_array())
+        .map(|routes| routes.iter().map(bump_route).collect())
+        .unwrap_or_default();
+    request.insert("routes".to_string(), Value::Array(routes));
+
+    Value::Object(request)
+}
+
+fn bump_route(route: &Value) -> Value {
+    let Value::Object(route) = route else {
+        return route.clone();
+    };
+
+    route
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| {
+            let value = match (key.as_str(), value) {
+                ("binding", Value::Object(binding)) => Value::Object(
+                    binding
+                        .iter()
+                        .filter(|(key, value)| {
+                            REQUEST_BINDING_FIELDS.contains(&key.as_str()) && !value.is_null()
+                        })
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                ),
+                _ => value.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiRouteChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Display for ApiRouteChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiRouteChange::Added => write!(f, "added"),
+            ApiRouteChange::Removed => write!(f, "removed"),
+            ApiRouteChange::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRouteDiff {
+    pub change: ApiRouteChange,
+    pub method: String,
+    pub path: String,
+    /// Changed route and binding fields, only for changed routes
+    pub fields: Vec<String>,
+}
+
+/// Route level differences between two versions of an API definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDefinitionDiff {
+    pub id: String,
+    pub from_version: String,
+    pub to_version: String,
+    pub routes: Vec<ApiRouteDiff>,
+}
+
+/// Compares the routes of two API definitions, routes are identified by method and path
+pub fn diff_api_definitions(from: &Value, to: &Value) -> Vec<ApiRouteDiff> {
+    let from_routes = routes_by_method_and_path(from);
+    let to_routes = routes_by_method_and_path(to);
+
+    let mut diffs = Vec::new();
+    for ((method, path), from_route) in &from_routes {
+        match to_routes.get(&(method.clone(), path.clone())) {
+            None => diffs.push(ApiRouteDiff {
+                change: ApiRouteChange::Removed,
+                method: method.clone(),
+                path: path.clone(),
+                fields: vec![],
+            }),
+            Some(to_route) => {
+                let fields = changed_route_fields(from_route, to_route);
+                if !fields.is_empty() {
+                    diffs.push(ApiRouteDiff {
+                        change: ApiRouteChange::Changed,
+                        method: method.clone(),
+                        path: path.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    for (method, path) in to_routes.keys() {
+        if !from_routes.contains_key(&(method.clone(), path.clone())) {
+            diffs.push(ApiRouteDiff {
+                change: ApiRouteChange::Added,
+                method: method.clone(),
+                path: path.clone(),
+                fields: vec![],
+            });
+        }
+    }
+
+    diffs.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+    diffs
+}
+
+fn routes_by_method_and_path(definition: &Value) -> BTreeMap<(String, String), Value> {
+    definition
+        .get("routes")
+        .and_then(|routes| routes.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|route| {
+            let method = route.get("method")?.as_str()?.to_uppercase();
+            let path = route.get("path")?.as_str()?.to_string();
+            Some(((method, path), bump_route(route)))
+        })
+        .collect()
+}
+
+fn changed_route_fields(from: &Value, to: &Value) -> Vec<String> {
+    let mut fields = Vec::new();
+
+    if from.get("security") != to.get("security") {
+        fields.push("security".to_string());
+    }
+
+    let empty = serde_json::Map::new();
+    let binding_fields = |route: &Value| {
+        route
+            .get("binding")
+            .and_then(|binding| binding.as_object())
+            .unwrap_or(&empty)
+            .clone()
+    };
+    let from_binding = binding_fields(from);
+    let to_binding = binding_fields(to);
+    for key in from_binding
+        .keys()
+        .chain(to_binding.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        if from_binding.get(key) != to_binding.get(key) {
+            fields.push(key.clone());
+        }
+    }
+
+    fields
+}
+
+// Path variable names do not matter for matching, so /users/{id} and /users/{user-id} collide
+fn normalize_path(path: &str) -> String {
+    path.trim_end_matches('/')
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') && segment.ends_with('}') {
+                if segment.starts_with("{+") {
+                    "{+}"
+                } else {
+                    "{}"
+                }
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn normalize_binding_type(binding_type: &str) -> String {
+    binding_type
+        .chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+#[derive(Default)]
+struct Linter {
+    findings: Vec<ApiDefinitionLintFinding>,
+}
+
+impl Linter {
+    fn error(&mut self, rule: &str, route: Option<String>, message: impl Into<String>) {
+        self.add(ApiDefinitionLintSeverity::Error, rule, route, message);
+    }
+
+    fn warning(&mut self, rule: &str, route: Option<String>, message: impl Into<String>) {
+        self.add(ApiDefinitionLintSeverity::Warning, rule, route, message);
+    }
+
+    fn add(
+        &mut self,
+        severity: ApiDefinitionLintSeverity,
+        rule: &str,
+        route: Option<String>,
+        message: impl Into<String>,
+    ) {
+        self.findings.push(ApiDefinitionLintFinding {
+            severity,
+            rule: rule.to_string(),
+            route,
+            message: message.into(),
+        })
+    }
+
+    fn into_result(mut self, definition: &Value) -> ApiDefinitionLintResult {
+        self.findings
+            .sort_by(|a, b| (a.severity, &a.route, &a.rule).cmp(&(b.severity, &b.route, &b.rule)));
+
+        let field = |name: &str| {
+            definition.get(name).and_then(|value| match value {
+                Value::String(value) => Some(value.clone()),
+                Value::Null => None,
+                value => Some(value.to_string()),
+            })
+        };
+
+        ApiDefinitionLintResult {
+            id: field("id"),
+            version: field("version"),
+            findings: self.findings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::api_definition::{
+        bump_api_definition, diff_api_definitions, lint_api_definition, rib_script_calls_function,
+        try_api_route, ApiDefinitionLintSeverity, ApiRouteChange, ApiRouteRequest,
+    };
+    use assert2::{check, let_assert};
+    use std::collections::BTreeMap;
+    use test_r::test;
+
+    fn lint(yaml: &str) -> Vec<(ApiDefinitionLintSeverity, String, Option<String>)> {
+        let definition: serde_json::Value = serde_yaml::from_str(yaml).unwrap();
+        lint_api_definition(&definition)
+            .findings
+            .into_iter()
+            .map(|finding| (finding.severity, finding.rule, finding.route))
+            .collect()
+    }
+
+    #[test]
+    fn valid_definition() {
+        check!(lint(
+            r#"
+id: shopping-cart
+version: 0.0.1
+routes:
+  - method: Get
+    path: /{user}/contents
+    binding:
+      component:
+        name: shopping-cart
+      workerName: 'let user: u64 = request.path.user; "worker-${user}"'
+      response: '{status: 200u64, body: "ok"}'
+  - method: Post
+    path: /{user}/contents
+    security: my-scheme
+    binding:
+      type: default
+      componentName: shopping-cart
+      response: '{status: 200u64, body: "ok"}'
+"#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn invalid_definition() {
+        let findings = lint(
+            r#"
+id: shopping-cart
+version: 0.0.1
+routes:
+  - method: Get
+    path: /{user}/contents
+    binding:
+      workerName: 'let user = ;'
+      response: '{status: 200u64}'
+  - method: Get
+    path: /{id}/contents/
+  - method: Delete
+    path: /{user}
+    binding:
+      type: cors-preflight
+  - method: Put
+    path: /{user}
+    binding:
+      componentName: shopping-cart
+"#,
+        );
+
+        check!(
+            findings
+                == vec![
+                    (
+                        ApiDefinitionLintSeverity::Error,
+                        "unbound-route".to_string(),
+                        Some("GET /{id}/contents/".to_string())
+                    ),
+                    (
+                        ApiDefinitionLintSeverity::Error,
+                        "rib-syntax".to_string(),
+                        Some("GET /{user}/contents".to_string())
+                    ),
+                    (
+                        ApiDefinitionLintSeverity::Error,
+                        "unbound-route".to_string(),
+                        Some("GET /{user}/contents".to_string())
+                    ),
+                    (
+                        ApiDefinitionLintSeverity::Error,
+                        "duplicate-route".to_string(),
+                        Some("GET /{}/contents".to_string())
+                    ),
+                    (
+                        ApiDefinitionLintSeverity::Error,
+                        "unbound-route".to_string(),
+                        Some("PUT /{user}".to_string())
+                    ),
+                    (
+                        ApiDefinitionLintSeverity::Warning,
+                        "missing-security".to_string(),
+                        Some("PUT /{user}".to_string())
+                    ),
+                ]
+        );
+    }
+
+    #[test]
+    fn try_route() {
+        let definition: serde_json::Value = serde_yaml::from_str(
+            r#"
+id: shopping-cart
+version: 0.0.1
+routes:
+  - method: Get
+    path: /{user}/contents
+    binding:
+      component:
+        name: shopping-cart
+        version: 2
+      workerName: 'let user: u64 = request.path.user; "worker-${user}"'
+      response: 'let result = worker.get-cart-contents(); {status: 200u64, body: result}'
+  - method: Get
+    path: /admin/contents?{limit}
+    binding:
+      componentName: admin
+      workerName: '"admin"'
+      response: '{status: 200u64, body: request.path.limit}'
+  - method: Get
+    path: /files/{+path}
+    binding:
+      type: file-server
+      componentName: files
+      response: 'request.path.path'
+"#,
+        )
+        .unwrap();
+
+        let try_route = |route: &str| {
+            try_api_route(
+                &definition,
+                &route.parse::<ApiRouteRequest>().unwrap(),
+                BTreeMap::new(),
+                None,
+            )
+        };
+
+        let_assert!(Some(result) = try_route("GET /1/contents"));
+        check!(result.path == "/{user}/contents");
+        check!(result.component_name == Some("shopping-cart".to_string()));
+        check!(result.component_version == Some(2));
+        check!(result.path_params == BTreeMap::from([("user".to_string(), "1".to_string())]));
+        check!(result.unresolved.is_empty());
+
+        let_assert!(Some(result) = try_route("get /admin/contents"));
+        check!(result.path == "/admin/contents?{limit}");
+        check!(result.unresolved == vec!["request.path.limit".to_string()]);
+
+        let_assert!(Some(result) = try_route("GET /admin/contents?limit=10"));
+        check!(result.query_params == BTreeMap::from([("limit".to_string(), "10".to_string())]));
+        check!(result.unresolved.is_empty());
+
+        let_assert!(Some(result) = try_route("GET /files/a/b.txt"));
+        check!(result.binding_type == "file-server");
+        check!(result.path_params == BTreeMap::from([("path".to_string(), "a/b.txt".to_string())]));
+
+        check!(try_route("POST /1/contents").is_none());
+        check!(try_route("GET /1/contents/2").is_none());
+        check!("/1/contents".parse::<ApiRouteRequest>().is_err());
+    }
+
+    #[test]
+    fn rib_function_calls() {
+        check!(rib_script_calls_function(
+            "let result = worker.get-cart-contents(); result",
+            "get-cart-contents"
+        ));
+        check!(rib_script_calls_function(
+            "worker.{golem:it/api.{get-cart-contents}}()",
+            "get-cart-contents"
+        ));
+        check!(!rib_script_calls_function(
+            "worker.get-cart-contents-v2()",
+            "get-cart-contents"
+        ));
+        check!(!rib_script_calls_function(
+            "let get-cart-contents = 1; get-cart-contents",
+            "get-cart-contents"
+        ));
+    }
+
+    #[test]
+    fn bump_and_diff() {
+        let from: serde_json::Value = serde_json::from_str(
+            r#"{
+              "id": "shopping-cart",
+              "version": "1",
+              "draft": false,
+              "createdAt": "2025-01-01T00:00:00Z",
+              "routes": [
+                {
+                  "method": "Get",
+                  "path": "/{user}/contents",
+                  "security": null,
+                  "binding": {
+                    "component": { "name": "shopping-cart", "version": 1 },
+                    "workerName": "\"worker\"",
+                    "response": "{status: 200u64}",
+                    "workerNameInput": {}
+                  }
+                },
+                {
+                  "method": "Delete",
+                  "path": "/{user}",
+                  "binding": { "componentName": "shopping-cart", "response": "{status: 200u64}" }
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+
+        let bumped = bump_api_definition(&from, "2", true);
+        check!(bumped["version"] == "2");
+        check!(bumped["draft"] == true);
+        check!(bumped.get("createdAt").is_none());
+        check!(bumped["routes"][0].get("security").is_none());
+        check!(bumped["routes"][0]["binding"]
+            .get("workerNameInput")
+            .is_none());
+        check!(diff_api_definitions(&from, &bumped).is_empty());
+
+        let mut to = bumped.clone();
+        to["routes"][0]["binding"]["component"]["version"] = serde_json::json!(2);
+        to["routes"][1]["path"] = serde_json::json!("/users/{user}");
+
+        let diff = diff_api_definitions(&from, &to)
+            .into_iter()
+            .map(|route| (route.change, route.method, route.path, route.fields))
+            .collect::<Vec<_>>();
+        check!(
+            diff == vec![
+                (
+                    ApiRouteChange::Added,
+                    "DELETE".to_string(),
+                    "/users/{user}".to_string(),
+                    vec![]
+                ),
+                (
+                    ApiRouteChange::Removed,
+                    "DELETE".to_string(),
+                    "/{user}".to_string(),
+                    vec![]
+                ),
+                (
+                    ApiRouteChange::Changed,
+                    "GET".to_string(),
+                    "/{user}/contents".to_string(),
+                    vec!["component".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -1,13 +1,15 @@
+use crate::cancellation::CancellationToken;
 use crate::fs;
 use crate::log::LogColorize;
 use crate::model::app::app_builder::build_application;
 use crate::model::app_raw;
 use crate::model::component::AppComponentType;
+use crate::model::deploy::DEPLOY_LOCK_FILE_NAME;
 use crate::model::template::Template;
 use crate::validation::{ValidatedResult, ValidationBuilder};
 use crate::wasm_rpc_stubgen::naming;
 use crate::wasm_rpc_stubgen::naming::wit::package_dep_dir_name_from_parser;
-use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
+use crate::wasm_rpc_stubgen::stub::{ClientBorrowMode, RustDependencyOverride};
 use golem_common::model::{ComponentFilePathWithPermissions, ComponentFilePermissions};
 use serde::Serialize;
 use std::cmp::Ordering;
@@ -29,7 +31,14 @@ pub struct ApplicationConfig {
     pub profile: Option<BuildProfileName>,
     pub offline: bool,
     pub steps_filter: HashSet<AppBuildStep>,
+    pub reproducible_build: bool,
+    pub keep_temp_build_dirs: bool,
+    pub build_jobs: usize,
+    pub install_tools: bool,
     pub golem_rust_override: RustDependencyOverride,
+    pub build_dir: Option<PathBuf>,
+    pub manifest_variables: Vec<(String, String)>,
+    pub cancellation_token: CancellationToken,
 }
 
 impl ApplicationConfig {
@@ -288,6 +297,53 @@ impl FromStr for DependencyType {
     }
 }
 
+/// Build tool whose version can be pinned in the application manifest
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PinnedTool {
+    CargoComponent,
+    Jco,
+    TinyGo,
+    WasmTools,
+    WitBindgen,
+}
+
+impl PinnedTool {
+    pub const ALL: [PinnedTool; 5] = [
+        PinnedTool::CargoComponent,
+        PinnedTool::Jco,
+        PinnedTool::TinyGo,
+        PinnedTool::WasmTools,
+        PinnedTool::WitBindgen,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PinnedTool::CargoComponent => "cargo-component",
+            PinnedTool::Jco => "jco",
+            PinnedTool::TinyGo => "tinygo",
+            PinnedTool::WasmTools => "wasm-tools",
+            PinnedTool::WitBindgen => "wit-bindgen",
+        }
+    }
+}
+
+impl Display for PinnedTool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for PinnedTool {
+    type Err = ();
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|tool| tool.as_str() == str)
+            .ok_or(())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct DependentComponent {
     pub name: AppComponentName,
@@ -306,14 +362,25 @@ impl Ord for DependentComponent {
     }
 }
 
+/// Component which is not built by the application, but already deployed,
+/// and can be used as WASM RPC dependency target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalComponent {
+    pub version: Option<u64>,
+    pub project: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Application {
     temp_dir: Option<WithSource<String>>,
+    temp_dir_override: Option<PathBuf>,
     wit_deps: WithSource<Vec<String>>,
+    tools: BTreeMap<PinnedTool, WithSource<String>>,
     components: BTreeMap<AppComponentName, Component>,
+    external_components: BTreeMap<AppComponentName, ExternalComponent>,
     dependencies: BTreeMap<AppComponentName, BTreeSet<DependentComponent>>,
     no_dependencies: BTreeSet<DependentComponent>,
-    custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
+    custom_commands: HashMap<String, WithSource<app_raw::CustomCommand>>,
     clean: Vec<WithSource<String>>,
 }
 
@@ -334,9 +401,17 @@ impl Application {
         self.components.contains_key(component_name)
     }
 
-    pub fn common_custom_commands(
+    pub fn external_components(
         &self,
-    ) -> &HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>> {
+    ) -> impl Iterator<Item = (&AppComponentName, &ExternalComponent)> {
+        self.external_components.iter()
+    }
+
+    pub fn is_external_component(&self, component_name: &AppComponentName) -> bool {
+        self.external_components.contains_key(component_name)
+    }
+
+    pub fn common_custom_commands(&self) -> &HashMap<String, WithSource<app_raw::CustomCommand>> {
         &self.custom_commands
     }
 
@@ -348,6 +423,11 @@ impl Application {
         &self.wit_deps
     }
 
+    /// Tool versions pinned in the manifest, with the source dir of the pinning manifest
+    pub fn tools(&self) -> &BTreeMap<PinnedTool, WithSource<String>> {
+        &self.tools
+    }
+
     pub fn all_dependencies(&self) -> BTreeSet<DependentComponent> {
         self.dependencies.values().flatten().cloned().collect()
     }
@@ -410,7 +490,16 @@ impl Application {
         custom_commands
     }
 
+    /// Overrides the manifest defined temp dir, all generated artifacts are placed under it
+    pub fn override_temp_dir(&mut self, temp_dir: PathBuf) {
+        self.temp_dir_override = Some(temp_dir);
+    }
+
     pub fn temp_dir(&self) -> PathBuf {
+        if let Some(temp_dir) = &self.temp_dir_override {
+            return temp_dir.clone();
+        }
+
         match self.temp_dir.as_ref() {
             Some(temp_dir) => temp_dir.source.as_path().join(&temp_dir.value),
             None => Path::new("golem-temp").to_path_buf(),
@@ -421,6 +510,10 @@ impl Application {
         self.temp_dir().join("task-results")
     }
 
+    pub fn deploy_lock_file(&self) -> PathBuf {
+        Path::new(DEPLOY_LOCK_FILE_NAME).to_path_buf()
+    }
+
     fn component(&self, component_name: &AppComponentName) -> &Component {
         self.components
             .get(component_name)
@@ -641,6 +734,24 @@ impl Application {
             .join(self.component_name_as_safe_path_elem(component_name))
             .join(naming::wit::WIT_DIR)
     }
+
+    fn external_component_dir(&self, component_name: &AppComponentName) -> PathBuf {
+        self.temp_dir()
+            .join("external-components")
+            .join(self.component_name_as_safe_path_elem(component_name))
+    }
+
+    /// WIT directory extracted from the deployed external component
+    pub fn external_component_wit(&self, component_name: &AppComponentName) -> PathBuf {
+        self.external_component_dir(component_name)
+            .join(naming::wit::WIT_DIR)
+    }
+
+    /// Describes the fetched version of the external component
+    pub fn external_component_metadata(&self, component_name: &AppComponentName) -> PathBuf {
+        self.external_component_dir(component_name)
+            .join("component.json")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -672,10 +783,14 @@ pub struct ComponentProperties {
     pub component_wasm: String,
     pub linked_wasm: Option<String>,
     pub build: Vec<app_raw::ExternalCommand>,
-    pub custom_commands: HashMap<String, Vec<app_raw::ExternalCommand>>,
+    pub custom_commands: HashMap<String, app_raw::CustomCommand>,
     pub clean: Vec<String>,
     pub component_type: AppComponentType,
     pub files: Vec<InitialComponentFile>,
+    pub client_borrow_mode: ClientBorrowMode,
+    pub client_derives: Vec<String>,
+    /// Worker env vars, values can be secret references, see [crate::model::app_secrets]
+    pub env: HashMap<String, String>,
 }
 
 impl ComponentProperties {
@@ -696,6 +811,9 @@ impl ComponentProperties {
             clean: raw.clean,
             component_type: raw.component_type.unwrap_or_default(),
             files,
+            client_borrow_mode: raw.client_borrow_mode.unwrap_or_default(),
+            client_derives: raw.client_derives,
+            env: raw.env,
         })
     }
 
@@ -757,6 +875,21 @@ impl ComponentProperties {
             any_overrides = true;
         }
 
+        if let Some(client_borrow_mode) = overrides.client_borrow_mode {
+            self.client_borrow_mode = client_borrow_mode;
+            any_overrides = true;
+        }
+
+        if !overrides.client_derives.is_empty() {
+            self.client_derives = overrides.client_derives;
+            any_overrides = true;
+        }
+
+        if !overrides.env.is_empty() {
+            any_overrides = true;
+            self.env.extend(overrides.env);
+        }
+
         if !overrides.files.is_empty() {
             any_overrides = true;
             match InitialComponentFile::from_raw_vec(validation, source, overrides.files) {
@@ -837,19 +970,22 @@ pub struct InitialComponentFileSource(Url);
 
 impl InitialComponentFileSource {
     pub fn new(url_string: &str, relative_to: &Path) -> Result<Self, String> {
+        Self::new_relative_to_dir(
+            url_string,
+            relative_to.parent().expect("Failed to get parent"),
+        )
+    }
+
+    pub fn new_relative_to_dir(url_string: &str, relative_to_dir: &Path) -> Result<Self, String> {
         // Try to parse the URL as an absolute URL
         let url = Url::parse(url_string).or_else(|_| {
             // If that fails, try to parse it as a relative path
-            let canonical_relative_to = relative_to
-                .parent()
-                .expect("Failed to get parent")
-                .canonicalize()
-                .map_err(|_| {
-                    format!(
-                        "Failed to canonicalize relative path: {}",
-                        relative_to.log_color_highlight()
-                    )
-                })?;
+            let canonical_relative_to = relative_to_dir.canonicalize().map_err(|_| {
+                format!(
+                    "Failed to canonicalize relative path: {}",
+                    relative_to_dir.log_color_highlight()
+                )
+            })?;
 
             let source = canonical_relative_to.join(PathBuf::from(url_string));
             Url::from_file_path(&source).map_err(|_| {
@@ -886,10 +1022,12 @@ mod app_builder {
     use crate::log::LogColorize;
     use crate::model::app::{
         AppComponentName, Application, BuildProfileName, Component, ComponentProperties,
-        DependencyType, DependentComponent, ResolvedComponentProperties, TemplateName, WithSource,
+        DependencyType, DependentComponent, ExternalComponent, PinnedTool,
+        ResolvedComponentProperties, TemplateName, WithSource,
     };
     use crate::model::app_raw;
     use crate::validation::{ValidatedResult, ValidationBuilder};
+    use crate::wasm_rpc_stubgen::naming;
     use heck::{
         ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutyKebabCase, ToShoutySnakeCase,
         ToSnakeCase, ToTitleCase, ToTrainCase, ToUpperCamelCase,
@@ -911,10 +1049,12 @@ mod app_builder {
         Include,
         TempDir,
         WitDeps,
+        Tool(PinnedTool),
         CustomCommand(String),
         Template(TemplateName),
         WasmRpcDependency((AppComponentName, DependentComponent)),
         Component(AppComponentName),
+        ExternalComponent(AppComponentName),
     }
 
     impl UniqueSourceCheckedEntityKey {
@@ -924,10 +1064,12 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::Include => property,
                 UniqueSourceCheckedEntityKey::TempDir => property,
                 UniqueSourceCheckedEntityKey::WitDeps => property,
+                UniqueSourceCheckedEntityKey::Tool(_) => "Tool version",
                 UniqueSourceCheckedEntityKey::CustomCommand(_) => "Custom command",
                 UniqueSourceCheckedEntityKey::Template(_) => "Template",
                 UniqueSourceCheckedEntityKey::WasmRpcDependency(_) => "WASM RPC dependency",
                 UniqueSourceCheckedEntityKey::Component(_) => "Component",
+                UniqueSourceCheckedEntityKey::ExternalComponent(_) => "External component",
             }
         }
 
@@ -942,6 +1084,9 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::WitDeps => {
                     "witDeps".log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::Tool(tool) => {
+                    tool.as_str().log_color_highlight().to_string()
+                }
                 UniqueSourceCheckedEntityKey::CustomCommand(command_name) => {
                     command_name.log_color_highlight().to_string()
                 }
@@ -962,6 +1107,9 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::Component(component_name) => {
                     component_name.as_str().log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::ExternalComponent(component_name) => {
+                    component_name.as_str().log_color_highlight().to_string()
+                }
             }
         }
     }
@@ -971,12 +1119,14 @@ mod app_builder {
         include: Vec<String>,
         temp_dir: Option<WithSource<String>>,
         wit_deps: WithSource<Vec<String>>,
+        tools: BTreeMap<PinnedTool, WithSource<String>>,
         templates: HashMap<TemplateName, app_raw::ComponentTemplate>,
         dependencies: BTreeMap<AppComponentName, BTreeSet<DependentComponent>>,
-        custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
+        custom_commands: HashMap<String, WithSource<app_raw::CustomCommand>>,
         clean: Vec<WithSource<String>>,
         raw_components: HashMap<AppComponentName, (PathBuf, app_raw::Component)>,
         resolved_components: BTreeMap<AppComponentName, Component>,
+        external_components: BTreeMap<AppComponentName, ExternalComponent>,
 
         entity_sources: HashMap<UniqueSourceCheckedEntityKey, Vec<PathBuf>>,
     }
@@ -987,14 +1137,18 @@ mod app_builder {
             let mut validation = ValidationBuilder::default();
 
             builder.add_raw_apps(&mut validation, apps);
+            builder.validate_external_components(&mut validation);
             builder.validate_dependency_targets(&mut validation);
             builder.validate_unique_sources(&mut validation);
             builder.resolve_components(&mut validation);
 
             validation.build(Application {
                 temp_dir: builder.temp_dir,
+                temp_dir_override: None,
                 wit_deps: builder.wit_deps,
+                tools: builder.tools,
                 components: builder.resolved_components,
+                external_components: builder.external_components,
                 dependencies: builder.dependencies,
                 no_dependencies: BTreeSet::new(),
                 custom_commands: builder.custom_commands,
@@ -1054,6 +1208,31 @@ mod app_builder {
                             WithSource::new(app_source_dir.to_path_buf(), app.application.wit_deps);
                     }
 
+                    for (tool_name, version) in app.application.tools {
+                        match PinnedTool::from_str(&tool_name) {
+                            Ok(tool) => {
+                                if self.add_entity_source(
+                                    UniqueSourceCheckedEntityKey::Tool(tool),
+                                    &app.source,
+                                ) {
+                                    self.tools.insert(
+                                        tool,
+                                        WithSource::new(app_source_dir.to_path_buf(), version),
+                                    );
+                                }
+                            }
+                            Err(()) => validation.add_error(format!(
+                                "Unknown tool in {}: {}, supported tools: {}",
+                                "tools".log_color_highlight(),
+                                tool_name.log_color_error_highlight(),
+                                PinnedTool::ALL
+                                    .iter()
+                                    .map(|tool| tool.as_str().log_color_highlight())
+                                    .join(", ")
+                            )),
+                        }
+                    }
+
                     for (template_name, template) in app.application.templates {
                         self.add_raw_template(validation, &app.source, template_name, template);
                     }
@@ -1068,6 +1247,22 @@ mod app_builder {
                         }
                     }
 
+                    for (component_name, external_component) in app.application.external_components
+                    {
+                        let component_name = AppComponentName::from(component_name);
+                        let unique_key =
+                            UniqueSourceCheckedEntityKey::ExternalComponent(component_name.clone());
+                        if self.add_entity_source(unique_key, &app.source) {
+                            self.external_components.insert(
+                                component_name,
+                                ExternalComponent {
+                                    version: external_component.version,
+                                    project: external_component.project,
+                                },
+                            );
+                        }
+                    }
+
                     for (component_name, component_dependencies) in app.application.dependencies {
                         self.add_component_dependencies(
                             validation,
@@ -1211,13 +1406,52 @@ mod app_builder {
                 })
         }
 
+        fn validate_external_components(&mut self, validation: &mut ValidationBuilder) {
+            for component_name in self.external_components.keys() {
+                let source = self
+                    .entity_sources
+                    .get(&UniqueSourceCheckedEntityKey::ExternalComponent(
+                        component_name.clone(),
+                    ))
+                    .expect("Missing sources for external component")
+                    .first()
+                    .expect("Missing source for external component");
+
+                validation.with_context(
+                    vec![("source", source.to_string_lossy().to_string())],
+                    |validation| {
+                        if self.raw_components.contains_key(component_name) {
+                            validation.add_error(format!(
+                                "External component {} is also defined as an application component",
+                                component_name.as_str().log_color_error_highlight()
+                            ));
+                        }
+                        if naming::wit::external_component_parser_package_name(
+                            component_name.as_str(),
+                        )
+                        .is_none()
+                        {
+                            validation.add_error(format!(
+                                "External component name {} is not a valid WIT package name, expected format: {}",
+                                component_name.as_str().log_color_error_highlight(),
+                                "namespace:name".log_color_highlight()
+                            ));
+                        }
+                    },
+                );
+            }
+        }
+
         fn validate_dependency_targets(&mut self, validation: &mut ValidationBuilder) {
             for (component, deps) in &self.dependencies {
                 for target in deps {
                     let invalid_source = !self.raw_components.contains_key(component);
-                    let invalid_target = !self.raw_components.contains_key(&target.name);
+                    let external_target = self.external_components.contains_key(&target.name);
+                    let invalid_target =
+                        !self.raw_components.contains_key(&target.name) && !external_target;
+                    let invalid_external_target = external_target && !target.dep_type.is_wasm_rpc();
 
-                    if invalid_source || invalid_target {
+                    if invalid_source || invalid_target || invalid_external_target {
                         let source = self
                             .entity_sources
                             .get(&UniqueSourceCheckedEntityKey::WasmRpcDependency((
@@ -1247,6 +1481,14 @@ mod app_builder {
                                         target.name.as_str().log_color_error_highlight()
                                     ))
                                 }
+                                if invalid_external_target {
+                                    validation.add_error(format!(
+                                        "{} {} - {} references external component, only WASM RPC dependencies can target external components",
+                                        target.dep_type.describe(),
+                                        component.as_str().log_color_highlight(),
+                                        target.name.as_str().log_color_error_highlight()
+                                    ))
+                                }
                             },
                         );
                     }
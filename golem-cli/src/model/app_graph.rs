@@ -0,0 +1,307 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::app::{AppComponentName, Application, DependencyType};
+use crate::model::trace::{dot_escape, mermaid_escape};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum AppGraphFormat {
+    #[default]
+    Text,
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppGraphComponent {
+    pub name: String,
+    /// Already deployed component, which is not built by the application
+    pub external: bool,
+    /// A client (stub) is generated for the component, as it is a WASM RPC target
+    pub client_generated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppGraphDependency {
+    pub component: String,
+    pub target: String,
+    pub dependency_type: String,
+    /// The generated client or the target component is composed into the dependant component
+    pub composed: bool,
+}
+
+/// Component and client (stub) dependency graph of an application
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDependencyGraph {
+    pub components: Vec<AppGraphComponent>,
+    pub dependencies: Vec<AppGraphDependency>,
+}
+
+impl AppDependencyGraph {
+    /// Graph of the selected components, including the components they depend on
+    pub fn new(application: &Application, selected: &BTreeSet<AppComponentName>) -> Self {
+        let mut dependencies = Vec::new();
+        let mut involved = selected.clone();
+        for component_name in selected {
+            for dependency in application.component_dependencies(component_name) {
+                involved.insert(dependency.name.clone());
+                dependencies.push(AppGraphDependency {
+                    component: component_name.to_string(),
+                    target: dependency.name.to_string(),
+                    dependency_type: dependency.dep_type.as_str().to_string(),
+                    composed: matches!(
+                        dependency.dep_type,
+                        DependencyType::StaticWasmRpc | DependencyType::Wasm
+                    ),
+                });
+            }
+        }
+
+        let components = involved
+            .iter()
+            .map(|component_name| AppGraphComponent {
+                name: component_name.to_string(),
+                external: application.is_external_component(component_name),
+                client_generated: dependencies.iter().any(|dependency| {
+                    dependency.target == component_name.as_str() && dependency.is_wasm_rpc()
+                }),
+            })
+            .collect();
+
+        Self {
+            components,
+            dependencies,
+        }
+    }
+
+    pub fn render(&self, format: AppGraphFormat) -> Option<String> {
+        match format {
+            AppGraphFormat::Text => None,
+            AppGraphFormat::Dot => Some(self.to_dot()),
+            AppGraphFormat::Mermaid => Some(self.to_mermaid()),
+        }
+    }
+
+    pub fn dependencies_of<'a>(
+        &'a self,
+        component_name: &'a str,
+    ) -> impl Iterator<Item = &'a AppGraphDependency> + 'a {
+        self.dependencies
+            .iter()
+            .filter(move |dependency| dependency.component == component_name)
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let nodes = self.node_ids();
+
+        let mut diagram = String::from("flowchart LR\n");
+        for (component, id) in self.components.iter().zip(0..) {
+            if component.external {
+                let _ = writeln!(
+                    diagram,
+                    "    c{}([\"{} (external)\"])",
+                    id,
+                    mermaid_escape(&component.name)
+                );
+            } else {
+                let _ = writeln!(
+                    diagram,
+                    "    c{}[\"{}\"]",
+                    id,
+                    mermaid_escape(&component.name)
+                );
+            }
+            if component.client_generated {
+                let _ = writeln!(
+                    diagram,
+                    "    s{}[[\"{} client\"]]",
+                    id,
+                    mermaid_escape(&component.name)
+                );
+                let _ = writeln!(diagram, "    s{} -.->|calls| c{}", id, id);
+            }
+        }
+        for dependency in &self.dependencies {
+            let _ = writeln!(
+                diagram,
+                "    c{} {}|\"{}\"| {}{}",
+                nodes[dependency.component.as_str()],
+                if dependency.composed { "==>" } else { "-->" },
+                dependency.label(),
+                target_node_prefix(dependency),
+                nodes[dependency.target.as_str()]
+            );
+        }
+        diagram
+    }
+
+    pub fn to_dot(&self) -> String {
+        let nodes = self.node_ids();
+
+        let mut diagram = String::from("digraph dependencies {\n    rankdir=LR;\n");
+        for (component, id) in self.components.iter().zip(0..) {
+            if component.external {
+                let _ = writeln!(
+                    diagram,
+                    "    c{} [shape=box, style=dashed, label=\"{} (external)\"];",
+                    id,
+                    dot_escape(&component.name)
+                );
+            } else {
+                let _ = writeln!(
+                    diagram,
+                    "    c{} [shape=box, label=\"{}\"];",
+                    id,
+                    dot_escape(&component.name)
+                );
+            }
+            if component.client_generated {
+                let _ = writeln!(
+                    diagram,
+                    "    s{} [shape=component, label=\"{} client\"];",
+                    id,
+                    dot_escape(&component.name)
+                );
+                let _ = writeln!(
+                    diagram,
+                    "    s{} -> c{} [label=\"calls\", style=dashed];",
+                    id, id
+                );
+            }
+        }
+        for dependency in &self.dependencies {
+            let _ = writeln!(
+                diagram,
+                "    c{} -> {}{} [label=\"{}\"{}];",
+                nodes[dependency.component.as_str()],
+                target_node_prefix(dependency),
+                nodes[dependency.target.as_str()],
+                dependency.label(),
+                if dependency.composed {
+                    ", style=bold"
+                } else {
+                    ""
+                }
+            );
+        }
+        diagram.push_str("}\n");
+        diagram
+    }
+
+    fn node_ids(&self) -> BTreeMap<&str, usize> {
+        self.components
+            .iter()
+            .zip(0..)
+            .map(|(component, id)| (component.name.as_str(), id))
+            .collect()
+    }
+}
+
+impl AppGraphDependency {
+    pub fn is_wasm_rpc(&self) -> bool {
+        self.dependency_type != DependencyType::WASM
+    }
+
+    pub fn label(&self) -> String {
+        if self.composed {
+            format!("{}, composed", self.dependency_type)
+        } else {
+            self.dependency_type.clone()
+        }
+    }
+}
+
+/// WASM RPC dependencies point to the generated client of the target, WASM dependencies
+/// to the target component itself
+fn target_node_prefix(dependency: &AppGraphDependency) -> &'static str {
+    if dependency.is_wasm_rpc() {
+        "s"
+    } else {
+        "c"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::app_graph::{AppDependencyGraph, AppGraphComponent, AppGraphDependency};
+    use assert2::check;
+    use test_r::test;
+
+    fn graph() -> AppDependencyGraph {
+        AppDependencyGraph {
+            components: vec![
+                AppGraphComponent {
+                    name: "api".to_string(),
+                    external: false,
+                    client_generated: false,
+                },
+                AppGraphComponent {
+                    name: "cart".to_string(),
+                    external: false,
+                    client_generated: true,
+                },
+                AppGraphComponent {
+                    name: "lib".to_string(),
+                    external: true,
+                    client_generated: false,
+                },
+            ],
+            dependencies: vec![
+                AppGraphDependency {
+                    component: "api".to_string(),
+                    target: "cart".to_string(),
+                    dependency_type: "static-wasm-rpc".to_string(),
+                    composed: true,
+                },
+                AppGraphDependency {
+                    component: "cart".to_string(),
+                    target: "lib".to_string(),
+                    dependency_type: "wasm".to_string(),
+                    composed: true,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn mermaid_graph() {
+        check!(
+            graph().to_mermaid()
+                == "flowchart LR\n    \
+                    c0[\"api\"]\n    \
+                    c1[\"cart\"]\n    \
+                    s1[[\"cart client\"]]\n    \
+                    s1 -.->|calls| c1\n    \
+                    c2([\"lib (external)\"])\n    \
+                    c0 ==>|\"static-wasm-rpc, composed\"| s1\n    \
+                    c1 ==>|\"wasm, composed\"| c2\n"
+        );
+    }
+
+    #[test]
+    fn dot_graph() {
+        let dot = graph().to_dot();
+        check!(dot.starts_with("digraph dependencies {\n"));
+        check!(dot.contains("    s1 [shape=component, label=\"cart client\"];\n"));
+        check!(dot.contains("    c0 -> s1 [label=\"static-wasm-rpc, composed\", style=bold];\n"));
+        check!(dot.contains("    c1 -> c2 [label=\"wasm, composed\", style=bold];\n"));
+    }
+}
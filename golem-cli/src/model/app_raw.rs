@@ -1,10 +1,12 @@
 use crate::fs;
 use crate::log::LogColorize;
+use crate::model::app_variables::substitute_variables;
 use crate::model::component::AppComponentType;
+use crate::wasm_rpc_stubgen::stub::ClientBorrowMode;
 use anyhow::{anyhow, Context};
 use golem_common::model::{ComponentFilePath, ComponentFilePermissions};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
@@ -14,15 +16,25 @@ pub struct ApplicationWithSource {
 }
 
 impl ApplicationWithSource {
-    pub fn from_yaml_file(file: PathBuf) -> anyhow::Result<Self> {
-        Self::from_yaml_string(file.clone(), fs::read_to_string(file.clone())?)
+    pub fn from_yaml_file(
+        file: PathBuf,
+        variables: &HashMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        Self::from_yaml_string(file.clone(), fs::read_to_string(file.clone())?, variables)
             .with_context(|| anyhow!("Failed to load source {}", file.log_color_highlight()))
     }
 
-    pub fn from_yaml_string(source: PathBuf, string: String) -> serde_yaml::Result<Self> {
+    pub fn from_yaml_string(
+        source: PathBuf,
+        string: String,
+        variables: &HashMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(string.as_str())?;
+        substitute_variables(&mut document, variables)
+            .map_err(|errors| anyhow!(errors.join("\n")))?;
         Ok(Self {
             source,
-            application: Application::from_yaml_str(string.as_str())?,
+            application: serde_yaml::from_value(document)?,
         })
     }
 
@@ -41,15 +53,23 @@ pub struct Application {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub wit_deps: Vec<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tools: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub templates: HashMap<String, ComponentTemplate>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub components: HashMap<String, Component>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub external_components: HashMap<String, ExternalComponent>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub dependencies: HashMap<String, Vec<Dependency>>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub custom_commands: HashMap<String, Vec<ExternalCommand>>,
+    pub custom_commands: HashMap<String, CustomCommand>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub clean: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profile_variables: HashMap<String, HashMap<String, String>>,
 }
 
 impl Application {
@@ -108,13 +128,19 @@ pub struct ComponentProperties {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub build: Vec<ExternalCommand>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub custom_commands: HashMap<String, Vec<ExternalCommand>>,
+    pub custom_commands: HashMap<String, CustomCommand>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub clean: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub component_type: Option<AppComponentType>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub files: Vec<InitialComponentFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_borrow_mode: Option<ClientBorrowMode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub client_derives: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
 }
 
 impl ComponentProperties {
@@ -153,6 +179,18 @@ impl ComponentProperties {
             vec.push("files");
         }
 
+        if self.client_borrow_mode.is_some() {
+            vec.push("clientBorrowMode");
+        }
+
+        if !self.client_derives.is_empty() {
+            vec.push("clientDerives");
+        }
+
+        if !self.env.is_empty() {
+            vec.push("env");
+        }
+
         vec
     }
 }
@@ -173,6 +211,76 @@ pub struct ExternalCommand {
     pub targets: Vec<String>,
 }
 
+/// Custom command, defined either as a list of steps, or as steps with parameters and env vars
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CustomCommand {
+    Steps(Vec<ExternalCommand>),
+    WithParams(CustomCommandWithParams),
+}
+
+impl CustomCommand {
+    pub fn steps(&self) -> &[ExternalCommand] {
+        match self {
+            CustomCommand::Steps(steps) => steps,
+            CustomCommand::WithParams(command) => &command.steps,
+        }
+    }
+
+    pub fn params(&self) -> Option<&BTreeMap<String, CustomCommandParam>> {
+        match self {
+            CustomCommand::Steps(_) => None,
+            CustomCommand::WithParams(command) => Some(&command.params),
+        }
+    }
+
+    pub fn env(&self) -> Option<&HashMap<String, String>> {
+        match self {
+            CustomCommand::Steps(_) => None,
+            CustomCommand::WithParams(command) => Some(&command.env),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CustomCommandWithParams {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, CustomCommandParam>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    pub steps: Vec<ExternalCommand>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CustomCommandParam {
+    #[serde(rename = "type", default)]
+    pub type_: CustomCommandParamType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CustomCommandParamType {
+    #[default]
+    String,
+    Int,
+    Bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExternalComponent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Dependency {
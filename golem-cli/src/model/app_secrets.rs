@@ -0,0 +1,302 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secret references in application manifests, e.g. `${secret:prod/db-password}`.
+//!
+//! References are not valid manifest variable names, so they are kept as is when loading the
+//! manifest, and are only resolved right before the values are sent to the server (when creating
+//! or redeploying workers). Resolved values are never logged, errors only refer to the reference.
+
+use crate::fs;
+use anyhow::{anyhow, bail, Context};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+const SECRET_PREFIX: &str = "${secret:";
+
+// Resolver used for references without an explicit resolver, defaults to env
+const DEFAULT_RESOLVER_ENV_VAR: &str = "GOLEM_SECRET_RESOLVER";
+// Command line used by the command resolver, the secret key is appended as the last argument
+const RESOLVER_COMMAND_ENV_VAR: &str = "GOLEM_SECRET_COMMAND";
+// Service name used for OS keychain entries
+const KEYCHAIN_SERVICE: &str = "golem";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretResolver {
+    /// Reads the env var derived from the key, e.g. prod/db-password -> PROD_DB_PASSWORD
+    Env,
+    /// Reads the file at the key path, relative to the component directory
+    File,
+    /// Runs the command set in GOLEM_SECRET_COMMAND, with the key as the last argument
+    Command,
+    /// Reads the generic password from the OS keychain with the golem service and the key as account
+    Keychain,
+}
+
+impl SecretResolver {
+    fn default_resolver() -> anyhow::Result<Self> {
+        match std::env::var(DEFAULT_RESOLVER_ENV_VAR) {
+            Ok(resolver) => resolver
+                .parse()
+                .with_context(|| anyhow!("Invalid {DEFAULT_RESOLVER_ENV_VAR}")),
+            Err(_) => Ok(Self::Env),
+        }
+    }
+}
+
+impl Display for SecretResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let resolver = match self {
+            SecretResolver::Env => "env",
+            SecretResolver::File => "file",
+            SecretResolver::Command => "command",
+            SecretResolver::Keychain => "keychain",
+        };
+        write!(f, "{resolver}")
+    }
+}
+
+impl FromStr for SecretResolver {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "env" => Ok(Self::Env),
+            "file" => Ok(Self::File),
+            "command" => Ok(Self::Command),
+            "keychain" => Ok(Self::Keychain),
+            other => bail!(
+                "Unknown secret resolver: {other}, expected one of: env, file, command, keychain"
+            ),
+        }
+    }
+}
+
+/// A `${secret:[resolver:]key}` reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretReference {
+    pub resolver: Option<SecretResolver>,
+    pub key: String,
+}
+
+impl SecretReference {
+    /// Parses a value which is a secret reference, returns None for plain values
+    pub fn parse(value: &str) -> anyhow::Result<Option<Self>> {
+        let Some(reference) = value
+            .strip_prefix(SECRET_PREFIX)
+            .and_then(|reference| reference.strip_suffix('}'))
+        else {
+            return Ok(None);
+        };
+
+        let (resolver, key) = match reference.split_once(':') {
+            Some((resolver, key)) => (Some(resolver.parse()?), key),
+            None => (None, reference),
+        };
+
+        if key.is_empty() {
+            bail!("Empty secret key in {value}");
+        }
+
+        Ok(Some(Self {
+            resolver,
+            key: key.to_string(),
+        }))
+    }
+
+    pub fn resolve(&self, component_dir: &Path) -> anyhow::Result<String> {
+        let resolver = match self.resolver {
+            Some(resolver) => resolver,
+            None => SecretResolver::default_resolver()?,
+        };
+
+        match resolver {
+            SecretResolver::Env => {
+                let env_var = env_var_name(&self.key);
+                std::env::var(&env_var).map_err(|_| anyhow!("Env var {env_var} is not set"))
+            }
+            SecretResolver::File => {
+                let path = component_dir.join(&self.key);
+                Ok(trim_line_ending(fs::read_to_string(&path)?))
+            }
+            SecretResolver::Command => {
+                let command_line = std::env::var(RESOLVER_COMMAND_ENV_VAR)
+                    .map_err(|_| anyhow!("{RESOLVER_COMMAND_ENV_VAR} is not set"))?;
+                let mut command_tokens = shlex::split(&command_line)
+                    .filter(|tokens| !tokens.is_empty())
+                    .ok_or_else(|| anyhow!("Invalid {RESOLVER_COMMAND_ENV_VAR}"))?;
+                command_tokens.push(self.key.clone());
+                run_resolver_command(&command_tokens)
+            }
+            SecretResolver::Keychain => run_resolver_command(&keychain_command(&self.key)?),
+        }
+    }
+}
+
+impl Display for SecretReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.resolver {
+            Some(resolver) => write!(f, "{SECRET_PREFIX}{resolver}:{}}}", self.key),
+            None => write!(f, "{SECRET_PREFIX}{}}}", self.key),
+        }
+    }
+}
+
+/// Resolves all secret references in the env, plain values are returned as is
+pub fn resolve_env_secrets(
+    env: &HashMap<String, String>,
+    component_dir: &Path,
+) -> anyhow::Result<HashMap<String, String>> {
+    env.iter()
+        .map(|(name, value)| {
+            let value = match SecretReference::parse(value)
+                .with_context(|| anyhow!("Invalid secret reference for env var {name}"))?
+            {
+                Some(reference) => reference.resolve(component_dir).with_context(|| {
+                    anyhow!("Failed to resolve secret {reference} for env var {name}")
+                })?,
+                None => value.clone(),
+            };
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+fn env_var_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn trim_line_ending(mut value: String) -> String {
+    let len = value.trim_end_matches(['\r', '\n']).len();
+    value.truncate(len);
+    value
+}
+
+fn keychain_command(key: &str) -> anyhow::Result<Vec<String>> {
+    let command: &[&str] = if cfg!(target_os = "macos") {
+        &[
+            "security",
+            "find-generic-password",
+            "-s",
+            KEYCHAIN_SERVICE,
+            "-a",
+            key,
+            "-w",
+        ]
+    } else if cfg!(target_os = "linux") {
+        &[
+            "secret-tool",
+            "lookup",
+            "service",
+            KEYCHAIN_SERVICE,
+            "key",
+            key,
+        ]
+    } else {
+        bail!(
+            "The keychain secret resolver is not supported on this platform, use the command resolver instead"
+        )
+    };
+    Ok(command.iter().map(|token| token.to_string()).collect())
+}
+
+// Only the exit status is reported on failure, as the output could contain the secret
+fn run_resolver_command(command_tokens: &[String]) -> anyhow::Result<String> {
+    let output = Command::new(&command_tokens[0])
+        .args(&command_tokens[1..])
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .with_context(|| anyhow!("Failed to execute {}", command_tokens[0]))?;
+
+    if !output.status.success() {
+        bail!("{} failed, {}", command_tokens[0], output.status);
+    }
+
+    let value = String::from_utf8(output.stdout)
+        .map_err(|_| anyhow!("{} returned a non UTF-8 secret", command_tokens[0]))?;
+    Ok(trim_line_ending(value))
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::model::app_secrets::{
+        env_var_name, resolve_env_secrets, SecretReference, SecretResolver,
+    };
+    use assert2::{check, let_assert};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_references() {
+        check!(SecretReference::parse("plain").unwrap().is_none());
+        check!(SecretReference::parse("${DB_PASSWORD}").unwrap().is_none());
+        check!(
+            SecretReference::parse("${secret:prod/db-password}").unwrap()
+                == Some(SecretReference {
+                    resolver: None,
+                    key: "prod/db-password".to_string()
+                })
+        );
+        check!(
+            SecretReference::parse("${secret:file:secrets/db}").unwrap()
+                == Some(SecretReference {
+                    resolver: Some(SecretResolver::File),
+                    key: "secrets/db".to_string()
+                })
+        );
+        check!(SecretReference::parse("${secret:vault:db}").is_err());
+        check!(SecretReference::parse("${secret:}").is_err());
+        check!(env_var_name("prod/db-password") == "PROD_DB_PASSWORD");
+    }
+
+    #[test]
+    fn resolve_file_secrets_without_leaking_values() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("db-password"), "s3cr3t\n").unwrap();
+
+        let env = HashMap::from([
+            ("PLAIN".to_string(), "value".to_string()),
+            (
+                "DB_PASSWORD".to_string(),
+                "${secret:file:db-password}".to_string(),
+            ),
+        ]);
+        let resolved = resolve_env_secrets(&env, temp_dir.path()).unwrap();
+        check!(resolved["PLAIN"] == "value");
+        check!(resolved["DB_PASSWORD"] == "s3cr3t");
+
+        let missing = HashMap::from([(
+            "DB_PASSWORD".to_string(),
+            "${secret:env:golem-test-missing-secret}".to_string(),
+        )]);
+        let_assert!(Err(error) = resolve_env_secrets(&missing, temp_dir.path()));
+        let error = format!("{error:#}");
+        check!(error.contains("${secret:env:golem-test-missing-secret}"));
+        check!(error.contains("GOLEM_TEST_MISSING_SECRET"));
+    }
+}
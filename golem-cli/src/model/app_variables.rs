@@ -0,0 +1,416 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::log::LogColorize;
+use crate::model::app::BuildProfileName;
+use itertools::Itertools;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+// Manifest sections which define variables, these are not substituted
+const VARIABLE_DEFINITION_KEYS: &[&str] = &["variables", "profileVariables"];
+
+// Command lines are substituted again when executed, see SubstitutionMode::Deferred
+const COMMAND_KEY: &str = "command";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubstitutionMode {
+    /// All references must be defined, `$${` escapes are resolved
+    Strict,
+    /// Undefined references are kept as is, `$${` escapes are resolved
+    KeepUndefined,
+    /// Undefined references and `$${` escapes are kept as is, and `${` in substituted values
+    /// is escaped, as the result is substituted again with KeepUndefined when executed
+    Deferred,
+}
+
+/// Variables defined in application manifests, merged from all manifest sources
+#[derive(Debug, Clone, Default)]
+pub struct ManifestVariableDefinitions {
+    variables: BTreeMap<String, (String, PathBuf)>,
+    profile_variables: BTreeMap<String, BTreeMap<String, (String, PathBuf)>>,
+}
+
+impl ManifestVariableDefinitions {
+    /// Collects variable definitions from a manifest source, returns errors for conflicting
+    /// definitions
+    pub fn add_from_yaml_file(&mut self, source: &Path) -> Vec<String> {
+        let Some(definitions) = fs::read_to_string(source)
+            .ok()
+            .and_then(|source| serde_yaml::from_str::<VariableDefinitions>(&source).ok())
+        else {
+            // Parsing errors are reported when loading the manifest
+            return vec![];
+        };
+
+        let mut errors = vec![];
+        add_variables(
+            &mut self.variables,
+            source,
+            definitions.variables,
+            &mut errors,
+        );
+        for (profile, variables) in definitions.profile_variables {
+            add_variables(
+                self.profile_variables.entry(profile).or_default(),
+                source,
+                variables,
+                &mut errors,
+            );
+        }
+        errors
+    }
+
+    /// Resolves the variables which are available for substitution, in increasing precedence:
+    /// manifest variables, manifest profile variables, environment variables, command line values
+    pub fn resolve(
+        &self,
+        profile: Option<&BuildProfileName>,
+        overrides: &[(String, String)],
+    ) -> HashMap<String, String> {
+        let mut resolved = HashMap::new();
+        resolved.extend(
+            self.variables
+                .iter()
+                .map(|(name, (value, _))| (name.clone(), value.clone())),
+        );
+        if let Some(profile_variables) =
+            profile.and_then(|profile| self.profile_variables.get(profile.as_str()))
+        {
+            resolved.extend(
+                profile_variables
+                    .iter()
+                    .map(|(name, (value, _))| (name.clone(), value.clone())),
+            );
+        }
+        resolved.extend(std::env::vars());
+        resolved.extend(overrides.iter().cloned());
+        resolved
+    }
+}
+
+// Only the variable definitions are parsed, as other sections can contain variable references
+// which do not deserialize to their expected types before substitution
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VariableDefinitions {
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    #[serde(default)]
+    profile_variables: HashMap<String, HashMap<String, String>>,
+}
+
+fn add_variables(
+    target: &mut BTreeMap<String, (String, PathBuf)>,
+    source: &Path,
+    variables: HashMap<String, String>,
+    errors: &mut Vec<String>,
+) {
+    for (name, value) in variables.into_iter().sorted() {
+        match target.get(&name) {
+            Some((existing_value, existing_source)) if *existing_value != value => {
+                errors.push(format!(
+                    "Manifest variable {} is defined with different values in {} and {}",
+                    name.log_color_highlight(),
+                    existing_source.log_color_highlight(),
+                    source.log_color_highlight()
+                ));
+            }
+            Some(_) => {}
+            None => {
+                target.insert(name, (value, source.to_path_buf()));
+            }
+        }
+    }
+}
+
+/// Substitutes `${NAME}` references in all string values of a manifest document,
+/// `$${` can be used to write a literal `${`
+pub fn substitute_variables(
+    document: &mut Value,
+    variables: &HashMap<String, String>,
+) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    if let Value::Mapping(mapping) = document {
+        for (key, value) in mapping.iter_mut() {
+            let key = key.as_str().unwrap_or_default();
+            if VARIABLE_DEFINITION_KEYS.contains(&key) {
+                continue;
+            }
+            substitute_value(value, variables, key, mode_for_key(key), &mut errors);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Substitutes `${NAME}` references in an external command line before executing it, undefined
+/// references are kept as is, `$${` can be used to write a literal `${`
+pub fn substitute_command(command: &str, variables: &HashMap<String, String>) -> String {
+    substitute_string(command, variables, SubstitutionMode::KeepUndefined)
+        .unwrap_or_else(|_| unreachable!("undefined references are kept as is"))
+}
+
+fn mode_for_key(key: &str) -> SubstitutionMode {
+    if key == COMMAND_KEY {
+        SubstitutionMode::Deferred
+    } else {
+        SubstitutionMode::Strict
+    }
+}
+
+fn substitute_value(
+    value: &mut Value,
+    variables: &HashMap<String, String>,
+    path: &str,
+    mode: SubstitutionMode,
+    errors: &mut Vec<String>,
+) {
+    match value {
+        Value::String(string) => match substitute_string(string, variables, mode) {
+            Ok(substituted) => *string = substituted,
+            Err(undefined) => errors.extend(undefined.into_iter().map(|name| {
+                format!(
+                    "Undefined manifest variable {} at {}",
+                    name.log_color_error_highlight(),
+                    path.log_color_highlight()
+                )
+            })),
+        },
+        Value::Sequence(sequence) => {
+            for (idx, value) in sequence.iter_mut().enumerate() {
+                substitute_value(
+                    value,
+                    variables,
+                    &format!("{}[{}]", path, idx),
+                    mode,
+                    errors,
+                );
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                let key = key.as_str().unwrap_or_default();
+                substitute_value(
+                    value,
+                    variables,
+                    &format!("{}.{}", path, key),
+                    match mode {
+                        SubstitutionMode::Strict => mode_for_key(key),
+                        mode => mode,
+                    },
+                    errors,
+                );
+            }
+        }
+        Value::Tagged(tagged) => substitute_value(&mut tagged.value, variables, path, mode, errors),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+fn substitute_string(
+    input: &str,
+    variables: &HashMap<String, String>,
+    mode: SubstitutionMode,
+) -> Result<String, Vec<String>> {
+    let mut result = String::with_capacity(input.len());
+    let mut undefined = vec![];
+    let mut rest = input;
+
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("$${") {
+            result.push_str(if mode == SubstitutionMode::Deferred {
+                "$${"
+            } else {
+                "${"
+            });
+            rest = &rest[3..];
+            continue;
+        }
+
+        if rest.starts_with("${") {
+            if let Some(end) = rest.find('}') {
+                let name = &rest[2..end];
+                if is_valid_variable_name(name) {
+                    match variables.get(name) {
+                        Some(value) if mode == SubstitutionMode::Deferred => {
+                            result.push_str(&value.replace("${", "$${"))
+                        }
+                        Some(value) => result.push_str(value),
+                        None => {
+                            if mode == SubstitutionMode::Strict {
+                                undefined.push(name.to_string());
+                            }
+                            result.push_str(&rest[..=end]);
+                        }
+                    }
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        result.push('$');
+        rest = &rest[1..];
+    }
+    result.push_str(rest);
+
+    if undefined.is_empty() {
+        Ok(result)
+    } else {
+        Err(undefined)
+    }
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::app_variables::{substitute_command, substitute_variables};
+    use assert2::{check, let_assert};
+    use std::collections::HashMap;
+    use test_r::test;
+
+    fn variables() -> HashMap<String, String> {
+        HashMap::from([
+            ("ACCOUNT".to_string(), "my-account".to_string()),
+            ("REGION".to_string(), "eu".to_string()),
+        ])
+    }
+
+    fn substitute(yaml: &str) -> Result<serde_yaml::Value, Vec<String>> {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        substitute_variables(&mut document, &variables()).map(|()| document)
+    }
+
+    #[test]
+    fn substitutes_defined_variables() {
+        let_assert!(
+            Ok(document) = substitute(
+                r#"
+variables:
+  ACCOUNT: ${NOT_SUBSTITUTED}
+components:
+  app:component:
+    sourceWit: wit-${ACCOUNT}-${REGION}
+    build:
+      - command: cargo build --target-dir ${GOLEM_BUILD_DIR}/${REGION}
+    clean:
+      - $${ACCOUNT}
+      - $HOME/${
+"#
+            )
+        );
+
+        let expected: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+variables:
+  ACCOUNT: ${NOT_SUBSTITUTED}
+components:
+  app:component:
+    sourceWit: wit-my-account-eu
+    build:
+      - command: cargo build --target-dir ${GOLEM_BUILD_DIR}/eu
+    clean:
+      - ${ACCOUNT}
+      - $HOME/${
+"#,
+        )
+        .unwrap();
+
+        check!(document == expected);
+    }
+
+    #[test]
+    fn command_escapes_are_resolved_once() {
+        let_assert!(
+            Ok(document) = substitute(
+                r#"
+components:
+  app:component:
+    build:
+      - command: echo $${HOME} ${ACCOUNT} ${GOLEM_BUILD_DIR}
+"#
+            )
+        );
+        let_assert!(
+            Some(command) = document["components"]["app:component"]["build"][0]["command"].as_str()
+        );
+
+        let executed = substitute_command(
+            command,
+            &HashMap::from([
+                ("HOME".to_string(), "/home/user".to_string()),
+                ("GOLEM_BUILD_DIR".to_string(), "golem-temp".to_string()),
+            ]),
+        );
+        check!(executed == "echo ${HOME} my-account golem-temp");
+    }
+
+    #[test]
+    fn command_substituted_values_are_not_expanded_again() {
+        let mut document: serde_yaml::Value =
+            serde_yaml::from_str("command: echo ${TEMPLATE}").unwrap();
+        let_assert!(
+            Ok(()) = substitute_variables(
+                &mut document,
+                &HashMap::from([("TEMPLATE".to_string(), "${HOME}".to_string())]),
+            )
+        );
+        let_assert!(Some(command) = document["command"].as_str());
+
+        let executed = substitute_command(
+            command,
+            &HashMap::from([("HOME".to_string(), "/home/user".to_string())]),
+        );
+        check!(executed == "echo ${HOME}");
+    }
+
+    #[test]
+    fn reports_undefined_variables() {
+        let_assert!(
+            Err(errors) = substitute(
+                r#"
+components:
+  app:component:
+    sourceWit: wit-${UNDEFINED}
+    files:
+      - sourcePath: ${ACCOUNT}/${OTHER}
+"#
+            )
+        );
+
+        check!(errors.len() == 2);
+        check!(errors[0].contains("UNDEFINED"));
+        check!(errors[0].contains("components.app:component.sourceWit"));
+        check!(errors[1].contains("OTHER"));
+        check!(errors[1].contains("components.app:component.files[0].sourcePath"));
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Throughput and latency statistics of repeated invoke-and-await calls of a worker function,
+/// latencies are in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationBenchmark {
+    pub function_name: String,
+    pub iterations: usize,
+    pub total_ms: f64,
+    pub invocations_per_second: f64,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl InvocationBenchmark {
+    pub fn new(function_name: String, mut latencies: Vec<Duration>, total: Duration) -> Self {
+        latencies.sort();
+
+        let iterations = latencies.len();
+        let sum: Duration = latencies.iter().sum();
+        let mean = if iterations == 0 {
+            Duration::ZERO
+        } else {
+            sum / iterations as u32
+        };
+        let invocations_per_second = if total.is_zero() {
+            0.0
+        } else {
+            iterations as f64 / total.as_secs_f64()
+        };
+
+        Self {
+            function_name,
+            iterations,
+            total_ms: as_ms(total),
+            invocations_per_second,
+            min_ms: as_ms(latencies.first().copied().unwrap_or_default()),
+            mean_ms: as_ms(mean),
+            p50_ms: as_ms(percentile(&latencies, 50.0)),
+            p90_ms: as_ms(percentile(&latencies, 90.0)),
+            p99_ms: as_ms(percentile(&latencies, 99.0)),
+            max_ms: as_ms(latencies.last().copied().unwrap_or_default()),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted latencies
+fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::bench::InvocationBenchmark;
+    use assert2::check;
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    fn benchmark_statistics() {
+        let latencies = (1..=100).rev().map(Duration::from_millis).collect();
+        let benchmark =
+            InvocationBenchmark::new("f".to_string(), latencies, Duration::from_secs(2));
+
+        check!(benchmark.iterations == 100);
+        check!(benchmark.invocations_per_second == 50.0);
+        check!(benchmark.min_ms == 1.0);
+        check!(benchmark.mean_ms == 50.5);
+        check!(benchmark.p50_ms == 50.0);
+        check!(benchmark.p90_ms == 90.0);
+        check!(benchmark.p99_ms == 99.0);
+        check!(benchmark.max_ms == 100.0);
+    }
+
+    #[test]
+    fn benchmark_statistics_single_invocation() {
+        let benchmark = InvocationBenchmark::new(
+            "f".to_string(),
+            vec![Duration::from_millis(7)],
+            Duration::from_millis(7),
+        );
+
+        check!(benchmark.p50_ms == 7.0);
+        check!(benchmark.p99_ms == 7.0);
+        check!(benchmark.max_ms == 7.0);
+    }
+}
@@ -16,6 +16,7 @@ use crate::cloud::ProjectId;
 use crate::model::to_oss::ToOss;
 use crate::model::wave::function_wave_compatible;
 use crate::model::ComponentName;
+use crate::version_notes::VersionNote;
 use anyhow::{anyhow, bail};
 use chrono::{DateTime, Utc};
 use golem_client::model::{
@@ -25,9 +26,12 @@ use golem_common::model::component_metadata::DynamicLinkedInstance;
 use golem_common::model::trim_date::TrimDateTime;
 use golem_wasm_ast::analysis::wave::DisplayNamedFunc;
 use golem_wasm_ast::analysis::{
-    AnalysedExport, AnalysedFunction, AnalysedInstance, AnalysedResourceMode, NameOptionTypePair,
-    NameTypePair, TypeEnum, TypeFlags, TypeRecord, TypeTuple, TypeVariant,
+    AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedInstance,
+    AnalysedResourceMode, NameOptionTypePair, NameTypePair, TypeEnum, TypeFlags, TypeRecord,
+    TypeTuple, TypeVariant,
 };
+use golem_wasm_rpc::parse_type_annotated_value;
+use itertools::Itertools;
 use rib::{ParsedFunctionName, ParsedFunctionSite};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -155,6 +159,26 @@ pub struct ComponentView {
     pub exports: Vec<String>,
     pub dynamic_linking: BTreeMap<String, BTreeMap<String, String>>,
     pub files: Vec<InitialComponentFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ComponentView {
+    /// Attaches the locally stored version note and metadata
+    pub fn with_version_note(self, note: Option<&VersionNote>) -> Self {
+        match note {
+            Some(note) => Self {
+                note: note.note.clone(),
+                metadata: note.metadata.clone(),
+                ..self
+            },
+            None => self,
+        }
+    }
 }
 
 impl TrimDateTime for ComponentView {
@@ -203,6 +227,8 @@ impl From<&Component> for ComponentView {
                 })
                 .collect(),
             files: value.files.clone(),
+            note: None,
+            metadata: BTreeMap::new(),
         }
     }
 }
@@ -290,6 +316,20 @@ pub fn show_exported_functions(exports: &[AnalysedExport]) -> Vec<String> {
         .collect()
 }
 
+/// Exported functions, with the name of their exporting instance
+pub fn exported_functions(exports: &[AnalysedExport]) -> Vec<(Option<&str>, &AnalysedFunction)> {
+    exports
+        .iter()
+        .flat_map(|exp| match exp {
+            AnalysedExport::Instance(AnalysedInstance { name, functions }) => functions
+                .iter()
+                .map(|f| (Some(name.as_str()), f))
+                .collect::<Vec<_>>(),
+            AnalysedExport::Function(f) => vec![(None, f)],
+        })
+        .collect()
+}
+
 pub fn render_exported_function(prefix: Option<&str>, f: &AnalysedFunction) -> String {
     // TODO: now that the formatter is implemented, and wave still not supports handles
     //       is there a point in using the customized wave formatter?
@@ -341,6 +381,198 @@ pub fn format_function_name(prefix: Option<&str>, name: &str) -> String {
     }
 }
 
+/// Resource method call with inline resource construction, in the form of
+/// `[<interface>.]{<resource>(<constructor args>).<method>}`, e.g. `api.{cart(user-1).add-item}`.
+///
+/// The constructor arguments are WAVE values separated by commas, string arguments can also
+/// be written without quotes. The interface can be a full interface name or just its last
+/// segment, and can be omitted if the resource name is unique.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineResourceCall {
+    pub interface: Option<String>,
+    pub resource: String,
+    pub constructor_args: Vec<String>,
+    pub method: String,
+}
+
+impl InlineResourceCall {
+    /// Returns None if the function name does not use the inline resource construction syntax
+    pub fn parse(function_name: &str) -> Option<Self> {
+        let function_name = function_name.trim();
+        let body = function_name.strip_suffix('}')?;
+        let (interface, body) = match body.strip_prefix('{') {
+            Some(body) => (None, body),
+            None => {
+                let (interface, body) = body.split_once(".{")?;
+                (Some(interface.to_string()), body)
+            }
+        };
+
+        let (resource, rest) = body.split_once('(')?;
+        let (args, method) = rest.rsplit_once(").")?;
+        if !is_wit_identifier(resource) || !is_wit_identifier(method) {
+            return None;
+        }
+
+        Some(Self {
+            interface,
+            resource: resource.to_string(),
+            constructor_args: split_wave_args(args),
+            method: method.to_string(),
+        })
+    }
+
+    /// Resolves the call against the exports of a component, and renders it as a function name
+    /// with an indexed resource, e.g. `ns:pkg/api.{cart("user-1").add-item}`
+    pub fn resolve(&self, exports: &[AnalysedExport]) -> Result<String, String> {
+        let constructor_name = format!("[constructor]{}", self.resource);
+        let method_name = format!("[method]{}.{}", self.resource, self.method);
+
+        let instances = exports
+            .iter()
+            .filter_map(|export| match export {
+                AnalysedExport::Instance(instance) => Some(instance),
+                AnalysedExport::Function(_) => None,
+            })
+            .filter(|instance| match &self.interface {
+                Some(interface) => {
+                    instance.name == *interface
+                        || instance.name.ends_with(&format!("/{}", interface))
+                }
+                None => true,
+            })
+            .filter(|instance| {
+                instance
+                    .functions
+                    .iter()
+                    .any(|function| function.name == method_name)
+            })
+            .collect::<Vec<_>>();
+
+        let instance = match instances.as_slice() {
+            [instance] => instance,
+            [] => {
+                return Err(format!(
+                    "Method {} of resource {} not found",
+                    self.method, self.resource
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "Resource {} is ambiguous, found in interfaces: {}",
+                    self.resource,
+                    instances.iter().map(|instance| &instance.name).join(", ")
+                ))
+            }
+        };
+
+        let Some(constructor) = instance
+            .functions
+            .iter()
+            .find(|function| function.name == constructor_name)
+        else {
+            return Err(format!(
+                "Resource {} has no constructor in interface {}",
+                self.resource, instance.name
+            ));
+        };
+
+        if constructor.parameters.len() != self.constructor_args.len() {
+            return Err(format!(
+                "Wrong number of constructor arguments for resource {}: expected {}, got {}",
+                self.resource,
+                constructor.parameters.len(),
+                self.constructor_args.len()
+            ));
+        }
+
+        let args = self
+            .constructor_args
+            .iter()
+            .zip(&constructor.parameters)
+            .map(|(arg, param)| {
+                if parse_type_annotated_value(&param.typ, arg).is_ok() {
+                    Ok(arg.clone())
+                } else if matches!(param.typ, AnalysedType::Str(_)) {
+                    Ok(format!(
+                        "\"{}\"",
+                        arg.replace('\\', "\\\\").replace('"', "\\\"")
+                    ))
+                } else {
+                    Err(format!(
+                        "Invalid constructor argument {} for parameter {}",
+                        arg, param.name
+                    ))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!(
+            "{}.{{{}({}).{}}}",
+            instance.name,
+            self.resource,
+            args.join(", "),
+            self.method
+        ))
+    }
+}
+
+fn is_wit_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '%')
+}
+
+/// Splits comma separated WAVE values, ignoring commas in quotes and nested values
+fn split_wave_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in args.chars() {
+        if let Some(quote_char) = quote {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote_char {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            ',' if depth == 0 => {
+                result.push(current.trim().to_string());
+                current.clear();
+            }
+            c => current.push(c),
+        }
+    }
+
+    if !current.trim().is_empty() || !result.is_empty() {
+        result.push(current.trim().to_string());
+    }
+
+    result
+}
+
 fn resolve_function<'t>(
     component: &'t Component,
     function: &str,
@@ -390,32 +622,43 @@ pub fn function_result_types<'t>(
     Ok(func.results.iter().map(|r| &r.typ).collect())
 }
 
-pub fn function_params_types<'t>(
+pub fn function_params<'t>(
     component: &'t Component,
     function: &str,
-) -> anyhow::Result<Vec<&'t AnalysedType>> {
+) -> anyhow::Result<Vec<&'t AnalysedFunctionParameter>> {
     let (func, parsed) = resolve_function(component, function)?;
 
     if parsed.function().is_indexed_resource() {
-        Ok(func.parameters.iter().skip(1).map(|r| &r.typ).collect())
+        Ok(func.parameters.iter().skip(1).collect())
     } else {
-        Ok(func.parameters.iter().map(|r| &r.typ).collect())
+        Ok(func.parameters.iter().collect())
     }
 }
 
+pub fn function_params_types<'t>(
+    component: &'t Component,
+    function: &str,
+) -> anyhow::Result<Vec<&'t AnalysedType>> {
+    Ok(function_params(component, function)?
+        .into_iter()
+        .map(|r| &r.typ)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use test_r::test;
 
-    use crate::model::component::render_exported_function;
+    use crate::model::component::{render_exported_function, InlineResourceCall};
+    use assert2::{check, let_assert};
     use golem_wasm_ast::analysis::analysed_type::{
         bool, case, chr, f32, f64, field, flags, handle, list, option, r#enum, record, result,
         result_err, result_ok, s16, s32, s64, s8, str, tuple, u16, u32, u64, u8, unit_case,
         variant,
     };
     use golem_wasm_ast::analysis::{
-        AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult, AnalysedResourceId,
-        AnalysedResourceMode, AnalysedType,
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance, AnalysedResourceId, AnalysedResourceMode, AnalysedType,
     };
 
     #[test]
@@ -708,4 +951,79 @@ mod tests {
     fn same_export_for_bool() {
         ensure_same_export(bool(), "bool")
     }
+
+    fn cart_exports() -> Vec<AnalysedExport> {
+        vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "shop:cart/api".to_string(),
+            functions: vec![
+                AnalysedFunction {
+                    name: "[constructor]cart".to_string(),
+                    parameters: vec![
+                        AnalysedFunctionParameter {
+                            name: "user".to_string(),
+                            typ: str(),
+                        },
+                        AnalysedFunctionParameter {
+                            name: "limit".to_string(),
+                            typ: u32(),
+                        },
+                    ],
+                    results: vec![AnalysedFunctionResult {
+                        name: None,
+                        typ: handle(AnalysedResourceId(0), AnalysedResourceMode::Owned),
+                    }],
+                },
+                AnalysedFunction {
+                    name: "[method]cart.add-item".to_string(),
+                    parameters: vec![AnalysedFunctionParameter {
+                        name: "self".to_string(),
+                        typ: handle(AnalysedResourceId(0), AnalysedResourceMode::Borrowed),
+                    }],
+                    results: vec![],
+                },
+            ],
+        })]
+    }
+
+    #[test]
+    fn parse_inline_resource_call() {
+        let_assert!(Some(call) = InlineResourceCall::parse("api.{cart(user-1, 10).add-item}"));
+        check!(call.interface == Some("api".to_string()));
+        check!(call.resource == "cart");
+        check!(call.constructor_args == vec!["user-1".to_string(), "10".to_string()]);
+        check!(call.method == "add-item");
+
+        let_assert!(Some(call) = InlineResourceCall::parse(r#"{cart("a, b", 1).add-item}"#));
+        check!(call.interface == None);
+        check!(call.constructor_args == vec![r#""a, b""#.to_string(), "1".to_string()]);
+
+        check!(InlineResourceCall::parse("api.{[method]cart.add-item}").is_none());
+        check!(InlineResourceCall::parse("api.{add-item}").is_none());
+    }
+
+    #[test]
+    fn resolve_inline_resource_call() {
+        let exports = cart_exports();
+
+        let_assert!(Some(call) = InlineResourceCall::parse("api.{cart(user-1, 10).add-item}"));
+        check!(
+            call.resolve(&exports)
+                == Ok(r#"shop:cart/api.{cart("user-1", 10).add-item}"#.to_string())
+        );
+
+        let_assert!(Some(call) = InlineResourceCall::parse(r#"{cart("user-1", 10).add-item}"#));
+        check!(
+            call.resolve(&exports)
+                == Ok(r#"shop:cart/api.{cart("user-1", 10).add-item}"#.to_string())
+        );
+
+        let_assert!(Some(call) = InlineResourceCall::parse("api.{cart(user-1).add-item}"));
+        check!(call.resolve(&exports).is_err());
+
+        let_assert!(Some(call) = InlineResourceCall::parse("api.{cart(user-1, ten).add-item}"));
+        check!(call.resolve(&exports).is_err());
+
+        let_assert!(Some(call) = InlineResourceCall::parse("other.{cart(user-1, 10).add-item}"));
+        check!(call.resolve(&exports).is_err());
+    }
 }
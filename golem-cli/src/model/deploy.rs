@@ -12,8 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::model::component::{AppComponentType, ComponentView};
 use crate::model::{ComponentName, WorkerName};
+use anyhow::{anyhow, Context};
+use golem_common::model::ComponentFilePermissions;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Name of the deploy lock file, placed next to the root application manifest
+pub const DEPLOY_LOCK_FILE_NAME: &str = "golem.lock";
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -38,3 +49,369 @@ pub struct WorkerUpdateAttempt {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerUpdateRolloutResult {
+    pub updated: Vec<WorkerUpdateAttempt>,
+    /// Updates which were triggered, but not awaited
+    pub triggered: Vec<WorkerUpdateAttempt>,
+    pub failed: Vec<WorkerUpdateAttempt>,
+    pub skipped: Vec<WorkerUpdateAttempt>,
+    pub rolled_back: Vec<WorkerUpdateAttempt>,
+    pub stopped: bool,
+}
+
+/// Deployment related component properties, as declared by the application manifest
+#[derive(Clone, Debug)]
+pub struct DeclaredComponentProperties {
+    pub component_type: AppComponentType,
+    /// Size of the linked WASM, None if the component is not built yet
+    pub linked_wasm_size: Option<u64>,
+    pub files: BTreeMap<String, ComponentFilePermissions>,
+    pub dynamic_linking: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Differences between the latest deployed version of a component and the application manifest
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDrift {
+    pub component_name: ComponentName,
+    /// None if the component is not deployed yet
+    pub deployed_version: Option<u64>,
+    pub changes: Vec<PropertyDrift>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyDrift {
+    pub property: String,
+    pub deployed: Option<String>,
+    pub manifest: Option<String>,
+}
+
+impl ComponentDrift {
+    pub fn new(
+        component_name: ComponentName,
+        deployed: Option<&ComponentView>,
+        declared: &DeclaredComponentProperties,
+    ) -> Self {
+        let Some(deployed) = deployed else {
+            return Self {
+                component_name,
+                deployed_version: None,
+                changes: vec![],
+            };
+        };
+
+        let mut changes = Vec::new();
+        let mut check = |property: String, deployed: Option<String>, manifest: Option<String>| {
+            if deployed != manifest {
+                changes.push(PropertyDrift {
+                    property,
+                    deployed,
+                    manifest,
+                });
+            }
+        };
+
+        check(
+            "component type".to_string(),
+            Some(deployed.component_type.to_string()),
+            Some(declared.component_type.to_string()),
+        );
+
+        // The size is only comparable if the component is already built
+        if let Some(linked_wasm_size) = declared.linked_wasm_size {
+            check(
+                "linked WASM size".to_string(),
+                Some(deployed.component_size.to_string()),
+                Some(linked_wasm_size.to_string()),
+            );
+        }
+
+        let deployed_files = deployed
+            .files
+            .iter()
+            .map(|file| (file.path.to_string(), file.permissions))
+            .collect::<BTreeMap<_, _>>();
+        for path in deployed_files
+            .keys()
+            .chain(declared.files.keys())
+            .collect::<BTreeSet<_>>()
+        {
+            check(
+                format!("file {}", path),
+                deployed_files
+                    .get(path)
+                    .map(|permissions| permissions.as_compact_str().to_string()),
+                declared
+                    .files
+                    .get(path)
+                    .map(|permissions| permissions.as_compact_str().to_string()),
+            );
+        }
+
+        for interface in deployed
+            .dynamic_linking
+            .keys()
+            .chain(declared.dynamic_linking.keys())
+            .collect::<BTreeSet<_>>()
+        {
+            check(
+                format!("dynamic link {}", interface),
+                deployed.dynamic_linking.get(interface).map(format_links),
+                declared.dynamic_linking.get(interface).map(format_links),
+            );
+        }
+
+        Self {
+            component_name,
+            deployed_version: Some(deployed.component_version),
+            changes,
+        }
+    }
+
+    pub fn is_new(&self) -> bool {
+        self.deployed_version.is_none()
+    }
+
+    pub fn has_drift(&self) -> bool {
+        self.is_new() || !self.changes.is_empty()
+    }
+}
+
+fn format_links(links: &BTreeMap<String, String>) -> String {
+    links
+        .iter()
+        .map(|(resource, interface)| format!("{}={}", resource, interface))
+        .join(", ")
+}
+
+/// Exact component versions produced by the last deployment, stored in golem.lock
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployLock {
+    #[serde(default)]
+    pub components: BTreeMap<String, LockedComponent>,
+}
+
+impl DeployLock {
+    /// Loads the lock file, returns None if it does not exist
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| anyhow!("Failed to read lock file {}", path.display()))?;
+        let lock = serde_yaml::from_str(&content)
+            .with_context(|| anyhow!("Failed to parse lock file {}", path.display()))?;
+        Ok(Some(lock))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(path, content)
+            .with_context(|| anyhow!("Failed to write lock file {}", path.display()))
+    }
+
+    pub fn component(&self, component_name: &ComponentName) -> Option<&LockedComponent> {
+        self.components.get(&component_name.0)
+    }
+
+    pub fn insert(&mut self, component_name: &ComponentName, component: LockedComponent) {
+        self.components.insert(component_name.0.clone(), component);
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedComponent {
+    pub component_id: Uuid,
+    pub version: u64,
+    /// SHA-256 of the uploaded linked WASM
+    pub linked_wasm_hash: String,
+}
+
+/// What a locked deployment has to do with a component
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LockedDeployAction {
+    /// The locked version is already deployed
+    UpToDate,
+    /// The component has to be uploaded, and the result must be the locked version
+    Deploy,
+}
+
+/// Decides how to reproduce a locked component version, returns the drift as error
+/// if the locked version cannot be reproduced
+pub fn locked_deploy_action(
+    locked: &LockedComponent,
+    linked_wasm_hash: &str,
+    deployed: Option<(Uuid, u64)>,
+) -> Result<LockedDeployAction, String> {
+    if locked.linked_wasm_hash != linked_wasm_hash {
+        return Err(format!(
+            "linked WASM hash {} differs from the locked hash {}",
+            linked_wasm_hash, locked.linked_wasm_hash
+        ));
+    }
+
+    match deployed {
+        None if locked.version == 0 => Ok(LockedDeployAction::Deploy),
+        None => Err(format!(
+            "component is not deployed, but version {} is locked",
+            locked.version
+        )),
+        Some((component_id, _)) if component_id != locked.component_id => Err(format!(
+            "deployed component ID {} differs from the locked ID {}",
+            component_id, locked.component_id
+        )),
+        Some((_, version)) if version == locked.version => Ok(LockedDeployAction::UpToDate),
+        Some((_, version)) if version + 1 == locked.version => Ok(LockedDeployAction::Deploy),
+        Some((_, version)) => Err(format!(
+            "deployed version {} cannot be advanced to the locked version {}",
+            version, locked.version
+        )),
+    }
+}
+
+pub fn linked_wasm_hash(linked_wasm: &[u8]) -> String {
+    hex::encode(Sha256::digest(linked_wasm))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::component::{AppComponentType, ComponentView};
+    use crate::model::deploy::{
+        locked_deploy_action, ComponentDrift, DeclaredComponentProperties, DeployLock,
+        LockedComponent, LockedDeployAction, PropertyDrift,
+    };
+    use assert2::check;
+    use golem_common::model::ComponentFilePermissions;
+    use std::collections::BTreeMap;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn deployed() -> ComponentView {
+        ComponentView {
+            component_name: "app:comp".into(),
+            component_id: Uuid::new_v4(),
+            component_type: AppComponentType::Durable,
+            component_version: 3,
+            component_size: 1024,
+            created_at: None,
+            project_id: None,
+            exports: vec![],
+            dynamic_linking: BTreeMap::new(),
+            files: vec![],
+            note: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    fn declared() -> DeclaredComponentProperties {
+        DeclaredComponentProperties {
+            component_type: AppComponentType::Durable,
+            linked_wasm_size: Some(1024),
+            files: BTreeMap::new(),
+            dynamic_linking: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn no_drift() {
+        let drift = ComponentDrift::new("app:comp".into(), Some(&deployed()), &declared());
+        check!(drift.deployed_version == Some(3));
+        check!(!drift.has_drift());
+    }
+
+    #[test]
+    fn not_deployed() {
+        let drift = ComponentDrift::new("app:comp".into(), None, &declared());
+        check!(drift.is_new());
+        check!(drift.has_drift());
+    }
+
+    #[test]
+    fn changed_properties() {
+        let mut declared = declared();
+        declared.component_type = AppComponentType::Ephemeral;
+        declared.linked_wasm_size = None;
+        declared
+            .files
+            .insert("/assets".to_string(), ComponentFilePermissions::ReadWrite);
+        declared.dynamic_linking.insert(
+            "app:other-client/other-client".to_string(),
+            BTreeMap::from([("counter".to_string(), "app:other/api".to_string())]),
+        );
+
+        let drift = ComponentDrift::new("app:comp".into(), Some(&deployed()), &declared);
+
+        check!(
+            drift.changes
+                == vec![
+                    PropertyDrift {
+                        property: "component type".to_string(),
+                        deployed: Some("Durable".to_string()),
+                        manifest: Some("Ephemeral".to_string()),
+                    },
+                    PropertyDrift {
+                        property: "file /assets".to_string(),
+                        deployed: None,
+                        manifest: Some("rw".to_string()),
+                    },
+                    PropertyDrift {
+                        property: "dynamic link app:other-client/other-client".to_string(),
+                        deployed: None,
+                        manifest: Some("counter=app:other/api".to_string()),
+                    },
+                ]
+        );
+    }
+
+    #[test]
+    fn locked_deploy_actions() {
+        let component_id = Uuid::new_v4();
+        let locked = LockedComponent {
+            component_id,
+            version: 2,
+            linked_wasm_hash: "abcd".to_string(),
+        };
+
+        check!(
+            locked_deploy_action(&locked, "abcd", Some((component_id, 2)))
+                == Ok(LockedDeployAction::UpToDate)
+        );
+        check!(
+            locked_deploy_action(&locked, "abcd", Some((component_id, 1)))
+                == Ok(LockedDeployAction::Deploy)
+        );
+        check!(locked_deploy_action(&locked, "abcd", Some((component_id, 3))).is_err());
+        check!(locked_deploy_action(&locked, "abcd", Some((Uuid::new_v4(), 2))).is_err());
+        check!(locked_deploy_action(&locked, "ef01", Some((component_id, 2))).is_err());
+        check!(locked_deploy_action(&locked, "abcd", None).is_err());
+
+        let new_locked = LockedComponent {
+            version: 0,
+            ..locked
+        };
+        check!(locked_deploy_action(&new_locked, "abcd", None) == Ok(LockedDeployAction::Deploy));
+    }
+
+    #[test]
+    fn deploy_lock_roundtrip() {
+        let mut lock = DeployLock::default();
+        lock.insert(
+            &"app:comp".into(),
+            LockedComponent {
+                component_id: Uuid::new_v4(),
+                version: 1,
+                linked_wasm_hash: "abcd".to_string(),
+            },
+        );
+
+        let yaml = serde_yaml::to_string(&lock).unwrap();
+        check!(yaml.contains("linkedWasmHash: abcd"));
+        check!(serde_yaml::from_str::<DeployLock>(&yaml).unwrap() == lock);
+    }
+}
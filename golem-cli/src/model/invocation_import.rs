@@ -0,0 +1,526 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::api_definition::{ApiRouteRequest, ApiRouteTryResult};
+use anyhow::{anyhow, bail};
+use golem_wasm_ast::analysis::AnalysedType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Saved HTTP requests are converted to worker invocations by resolving them against the routes
+// of the API definition, then evaluating the simple Rib forms used for worker names and function
+// arguments: string literals with interpolation, request field references and let bindings of them.
+
+const RIB_NUMBER_SUFFIXES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "s8", "s16", "s32", "s64", "f32", "f64",
+];
+
+/// HTTP request saved by an HTTP testing tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedHttpRequest {
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<String>,
+}
+
+impl RecordedHttpRequest {
+    /// The request without the scheme and host, which are not part of the API definition routes
+    pub fn route_request(&self) -> Result<ApiRouteRequest, String> {
+        let url = self.url.trim();
+        let path = if let Some((_, rest)) = url.split_once("://") {
+            rest.find('/').map_or("/", |idx| &rest[idx..])
+        } else if let Some(rest) = url.strip_prefix("{{") {
+            // Postman variables, e.g. {{baseUrl}}/users/1
+            rest.split_once("}}").map_or("/", |(_, rest)| rest)
+        } else {
+            url.find('/').map_or("/", |idx| &url[idx..])
+        };
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        };
+
+        format!("{} {}", self.method, path).parse()
+    }
+
+    /// JSON bodies are passed as JSON, others as strings, same as by the API gateway
+    pub fn body_json(&self) -> Option<Value> {
+        self.body
+            .as_ref()
+            .filter(|body| !body.trim().is_empty())
+            .map(|body| serde_json::from_str(body).unwrap_or(Value::String(body.clone())))
+    }
+}
+
+/// Worker invocation converted from a saved HTTP request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedInvocation {
+    pub name: String,
+    pub component_name: String,
+    pub worker_name: String,
+    pub function_name: String,
+    /// Function arguments in WAVE format
+    pub arguments: Vec<String>,
+}
+
+/// Requests of a Postman (v2.0 or v2.1) collection, including the ones in folders
+pub fn parse_postman_collection(collection: &Value) -> anyhow::Result<Vec<RecordedHttpRequest>> {
+    fn collect(items: &[Value], requests: &mut Vec<RecordedHttpRequest>) -> anyhow::Result<()> {
+        for item in items {
+            if let Some(items) = item.get("item").and_then(|items| items.as_array()) {
+                collect(items, requests)?;
+                continue;
+            }
+            let Some(request) = item.get("request") else {
+                continue;
+            };
+            let name = str_field(item, "name").unwrap_or_default();
+
+            // Requests can be saved as a plain URL string
+            if let Some(url) = request.as_str() {
+                requests.push(RecordedHttpRequest {
+                    name,
+                    method: "GET".to_string(),
+                    url: url.to_string(),
+                    headers: BTreeMap::new(),
+                    body: None,
+                });
+                continue;
+            }
+
+            let url = match request.get("url") {
+                Some(Value::String(url)) => url.clone(),
+                Some(url) => str_field(url, "raw")
+                    .ok_or_else(|| anyhow!("Missing raw URL for request {}", name))?,
+                None => bail!("Missing URL for request {}", name),
+            };
+            let headers = request
+                .get("header")
+                .and_then(|headers| headers.as_array())
+                .into_iter()
+                .flatten()
+                .filter(|header| header.get("disabled") != Some(&Value::Bool(true)))
+                .filter_map(|header| Some((str_field(header, "key")?, str_field(header, "value")?)))
+                .collect();
+            let body = request
+                .get("body")
+                .filter(|body| !str_field(body, "mode").is_some_and(|mode| mode != "raw"))
+                .and_then(|body| str_field(body, "raw"));
+
+            requests.push(RecordedHttpRequest {
+                name,
+                method: str_field(request, "method").unwrap_or_else(|| "GET".to_string()),
+                url,
+                headers,
+                body,
+            });
+        }
+        Ok(())
+    }
+
+    let Some(items) = collection.get("item").and_then(|items| items.as_array()) else {
+        bail!("Invalid Postman collection, missing items");
+    };
+    let mut requests = vec![];
+    collect(items, &mut requests)?;
+    Ok(requests)
+}
+
+/// Requests of a HAR (HTTP Archive) file
+pub fn parse_har(har: &Value) -> anyhow::Result<Vec<RecordedHttpRequest>> {
+    let Some(entries) = har
+        .get("log")
+        .and_then(|log| log.get("entries"))
+        .and_then(|entries| entries.as_array())
+    else {
+        bail!("Invalid HAR file, missing log entries");
+    };
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            let request = entry
+                .get("request")
+                .ok_or_else(|| anyhow!("Missing request in HAR entry #{}", idx + 1))?;
+            let method = str_field(request, "method")
+                .ok_or_else(|| anyhow!("Missing method in HAR entry #{}", idx + 1))?;
+            let url = str_field(request, "url")
+                .ok_or_else(|| anyhow!("Missing URL in HAR entry #{}", idx + 1))?;
+            let headers = request
+                .get("headers")
+                .and_then(|headers| headers.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|header| {
+                    Some((str_field(header, "name")?, str_field(header, "value")?))
+                })
+                .collect();
+            let body = request
+                .get("postData")
+                .and_then(|post_data| str_field(post_data, "text"));
+
+            Ok(RecordedHttpRequest {
+                name: format!("{} {}", method, url),
+                method,
+                url,
+                headers,
+                body,
+            })
+        })
+        .collect()
+}
+
+fn str_field(value: &Value, name: &str) -> Option<String> {
+    value
+        .get(name)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+/// Evaluates a worker name script of the form `let x = request.path.x; "worker-${x}"`
+pub fn rib_worker_name(script: &str, route: &ApiRouteTryResult) -> Option<String> {
+    let (bindings, expr) = rib_bindings(script);
+    let literal = expr.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut worker_name = String::new();
+    let mut rest = literal;
+    while let Some(start) = rest.find("${") {
+        worker_name.push_str(&rest[..start]);
+        let end = rest[start..].find('}')? + start;
+        let value = rib_expr_value(rest[start + 2..end].trim(), &bindings, route)?;
+        match value {
+            Value::String(value) => worker_name.push_str(&value),
+            value => worker_name.push_str(&value.to_string()),
+        }
+        rest = &rest[end + 1..];
+    }
+    worker_name.push_str(rest);
+
+    Some(worker_name)
+}
+
+/// Evaluates the arguments of the function call in the response script
+pub fn rib_function_arguments(
+    script: &str,
+    function_name: &str,
+    route: &ApiRouteTryResult,
+) -> Option<Vec<Value>> {
+    let (bindings, _) = rib_bindings(script);
+
+    let (idx, _) = script.match_indices(function_name).find(|(idx, _)| {
+        script[idx + function_name.len()..]
+            .trim_start_matches('}')
+            .trim_start()
+            .starts_with('(')
+    })?;
+    let args_start = idx + script[idx..].find('(')? + 1;
+    let args = split_top_level(&script[args_start..], ')')?;
+
+    args.into_iter()
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| rib_expr_value(arg, &bindings, route))
+        .collect()
+}
+
+// Let bindings of request fields, and the last expression of the script
+fn rib_bindings(script: &str) -> (BTreeMap<String, String>, &str) {
+    let mut bindings = BTreeMap::new();
+    let mut expr = script.trim();
+    for statement in script.split(';') {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+        expr = statement;
+        let Some((name, value)) = statement
+            .strip_prefix("let ")
+            .and_then(|binding| binding.split_once('='))
+        else {
+            continue;
+        };
+        let name = name.split(':').next().unwrap_or_default().trim();
+        bindings.insert(name.to_string(), value.trim().to_string());
+    }
+    (bindings, expr)
+}
+
+fn rib_expr_value(
+    expr: &str,
+    bindings: &BTreeMap<String, String>,
+    route: &ApiRouteTryResult,
+) -> Option<Value> {
+    let expr = expr.trim();
+    if let Some(bound) = bindings.get(expr) {
+        return rib_expr_value(bound, &BTreeMap::new(), route);
+    }
+    if let Some(literal) = expr
+        .strip_prefix('"')
+        .and_then(|expr| expr.strip_suffix('"'))
+    {
+        return Some(Value::String(literal.to_string()));
+    }
+    if let Some(reference) = expr.strip_prefix("request.") {
+        let mut parts = reference.split('.');
+        return match (parts.next(), parts.next(), parts.next()) {
+            (Some("path"), Some(name), None) => route
+                .path_params
+                .get(name)
+                .or_else(|| route.query_params.get(name))
+                .map(|value| Value::String(value.clone())),
+            (Some("headers" | "header"), Some(name), None) => route
+                .headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| Value::String(value.clone())),
+            (Some("body"), None, None) => route.body.clone(),
+            (Some("body"), Some(field), None) => route
+                .body
+                .as_ref()
+                .and_then(|body| body.get(field))
+                .cloned(),
+            _ => None,
+        };
+    }
+    // Numbers and booleans, numbers can have Rib type suffixes, e.g. 200u64
+    let literal = RIB_NUMBER_SUFFIXES
+        .iter()
+        .find_map(|suffix| expr.strip_suffix(suffix))
+        .unwrap_or(expr);
+    serde_json::from_str::<Value>(literal)
+        .ok()
+        .filter(|value| value.is_number() || value.is_boolean())
+}
+
+// Splits the arguments until the closing delimiter on top level commas,
+// ignoring the ones in strings and nested expressions
+fn split_top_level(input: &str, closing: char) -> Option<Vec<&str>> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (idx, c) in input.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ',' if depth == 0 => {
+                parts.push(input[start..idx].trim());
+                start = idx + 1;
+            }
+            c if c == closing && depth == 0 => {
+                parts.push(input[start..idx].trim());
+                return Some(parts);
+            }
+            ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Converts the JSON value to WAVE based on the parameter type, request path, query and
+/// header values are strings, so they are also accepted for numbers and booleans
+pub fn json_to_wave(value: &Value, typ: &AnalysedType) -> Option<String> {
+    match (typ, value) {
+        (AnalysedType::Str(_), Value::String(value)) => serde_json::to_string(value).ok(),
+        (AnalysedType::Str(_), value @ (Value::Number(_) | Value::Bool(_))) => {
+            serde_json::to_string(&value.to_string()).ok()
+        }
+        (AnalysedType::Chr(_), Value::String(value)) if value.chars().count() == 1 => {
+            Some(format!("'{}'", value))
+        }
+        (AnalysedType::Bool(_), Value::Bool(value)) => Some(value.to_string()),
+        (AnalysedType::Bool(_), Value::String(value)) => {
+            value.parse::<bool>().ok().map(|value| value.to_string())
+        }
+        (
+            AnalysedType::U8(_)
+            | AnalysedType::U16(_)
+            | AnalysedType::U32(_)
+            | AnalysedType::U64(_)
+            | AnalysedType::S8(_)
+            | AnalysedType::S16(_)
+            | AnalysedType::S32(_)
+            | AnalysedType::S64(_)
+            | AnalysedType::F32(_)
+            | AnalysedType::F64(_),
+            value,
+        ) => {
+            let number = match value {
+                Value::Number(number) => number.to_string(),
+                Value::String(number) => number.trim().to_string(),
+                _ => return None,
+            };
+            serde_json::from_str::<serde_json::Number>(&number)
+                .ok()
+                .map(|number| number.to_string())
+        }
+        (AnalysedType::Enum(typ), Value::String(case)) => {
+            typ.cases.contains(case).then(|| case.clone())
+        }
+        (AnalysedType::Option(_), Value::Null) => Some("none".to_string()),
+        (AnalysedType::Option(typ), value) => {
+            json_to_wave(value, &typ.inner).map(|value| format!("some({})", value))
+        }
+        (AnalysedType::List(typ), Value::Array(items)) => items
+            .iter()
+            .map(|item| json_to_wave(item, &typ.inner))
+            .collect::<Option<Vec<_>>>()
+            .map(|items| format!("[{}]", items.join(", "))),
+        (AnalysedType::Record(typ), Value::Object(fields)) => typ
+            .fields
+            .iter()
+            .map(|field| {
+                let value = fields.get(&field.name).unwrap_or(&Value::Null);
+                json_to_wave(value, &field.typ).map(|value| format!("{}: {}", field.name, value))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(|fields| format!("{{{}}}", fields.join(", "))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::api_definition::{try_api_route, ApiRouteRequest};
+    use crate::model::invocation_import::{
+        json_to_wave, parse_har, parse_postman_collection, rib_function_arguments, rib_worker_name,
+    };
+    use assert2::{check, let_assert};
+    use golem_wasm_ast::analysis::analysed_type::{field, list, option, record, str, u64};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use test_r::test;
+
+    #[test]
+    fn postman_collection() {
+        let collection = json!({
+            "info": { "name": "shopping-cart" },
+            "item": [
+                {
+                    "name": "carts",
+                    "item": [
+                        {
+                            "name": "Add item",
+                            "request": {
+                                "method": "POST",
+                                "url": { "raw": "{{baseUrl}}/1/contents" },
+                                "header": [
+                                    { "key": "Content-Type", "value": "application/json" },
+                                    { "key": "X-Disabled", "value": "1", "disabled": true }
+                                ],
+                                "body": { "mode": "raw", "raw": "{\"product-id\": \"p1\"}" }
+                            }
+                        }
+                    ]
+                },
+                { "name": "Get cart", "request": "http://localhost:9006/1/contents" }
+            ]
+        });
+
+        let_assert!(Ok(requests) = parse_postman_collection(&collection));
+        check!(requests.len() == 2);
+        check!(requests[0].name == "Add item");
+        check!(requests[0].method == "POST");
+        check!(
+            requests[0].headers
+                == BTreeMap::from([("Content-Type".to_string(), "application/json".to_string())])
+        );
+        check!(requests[0].route_request().unwrap().path == "/1/contents");
+        check!(requests[0].body_json() == Some(json!({"product-id": "p1"})));
+        check!(requests[1].method == "GET");
+        check!(requests[1].route_request().unwrap().path == "/1/contents");
+    }
+
+    #[test]
+    fn har() {
+        let har = json!({
+            "log": {
+                "entries": [
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/1/contents?limit=10",
+                            "headers": [{ "name": "Accept", "value": "*/*" }]
+                        }
+                    }
+                ]
+            }
+        });
+
+        let_assert!(Ok(requests) = parse_har(&har));
+        check!(requests.len() == 1);
+        let_assert!(Ok(request) = requests[0].route_request());
+        check!(request.path == "/1/contents");
+        check!(request.query == BTreeMap::from([("limit".to_string(), "10".to_string())]));
+    }
+
+    #[test]
+    fn rib_evaluation() {
+        let definition = json!({
+            "routes": [{
+                "method": "Post",
+                "path": "/{user}/contents",
+                "binding": {
+                    "componentName": "shopping-cart",
+                    "workerName": "let user: u64 = request.path.user; \"worker-${user}\"",
+                    "response": "let user = request.path.user; let result = worker.add-item(request.body, user, 1u32); {status: 200u64}"
+                }
+            }]
+        });
+        let route = try_api_route(
+            &definition,
+            &"POST /1/contents".parse::<ApiRouteRequest>().unwrap(),
+            BTreeMap::new(),
+            Some(json!({"product-id": "p1", "tags": ["a"]})),
+        )
+        .unwrap();
+
+        check!(
+            rib_worker_name(route.worker_name.as_ref().unwrap(), &route)
+                == Some("worker-1".to_string())
+        );
+        check!(
+            rib_function_arguments(route.response.as_ref().unwrap(), "add-item", &route)
+                == Some(vec![
+                    json!({"product-id": "p1", "tags": ["a"]}),
+                    json!("1"),
+                    json!(1)
+                ])
+        );
+        check!(rib_worker_name("let x = request.path.missing; \"${x}\"", &route).is_none());
+    }
+
+    #[test]
+    fn wave_conversion() {
+        let item = record(vec![
+            field("product-id", str()),
+            field("tags", list(str())),
+            field("price", option(u64())),
+        ]);
+        check!(
+            json_to_wave(&json!({"product-id": "p1", "tags": ["a"]}), &item)
+                == Some(r#"{product-id: "p1", tags: ["a"], price: none}"#.to_string())
+        );
+        check!(json_to_wave(&json!("12"), &u64()) == Some("12".to_string()));
+        check!(json_to_wave(&json!("x"), &u64()).is_none());
+        check!(json_to_wave(&json!(12), &str()) == Some(r#""12""#.to_string()));
+    }
+}
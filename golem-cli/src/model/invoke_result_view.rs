@@ -28,6 +28,8 @@ pub struct InvokeResultView {
     pub result_json: Option<TypeAnnotatedValue>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub result_wave: Option<Vec<String>>,
+    #[serde(skip)]
+    pub raw: bool,
 }
 
 impl InvokeResultView {
@@ -49,6 +51,17 @@ impl InvokeResultView {
             idempotency_key: idempotency_key.0,
             result_json: Some(result.result),
             result_wave: wave,
+            raw: false,
+        }
+    }
+
+    /// Keeps the result as the protocol JSON, without decoding it into WAVE
+    pub fn new_invoke_raw(idempotency_key: IdempotencyKey, result: InvokeResult) -> Self {
+        Self {
+            idempotency_key: idempotency_key.0,
+            result_json: Some(result.result),
+            result_wave: None,
+            raw: true,
         }
     }
 
@@ -57,6 +70,7 @@ impl InvokeResultView {
             idempotency_key: idempotency_key.0,
             result_json: None,
             result_wave: None,
+            raw: false,
         }
     }
 
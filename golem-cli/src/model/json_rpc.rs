@@ -0,0 +1,150 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// JSON-RPC 2.0 messages used by `golem serve`, exchanged as one JSON document per line
+
+pub const JSON_RPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const COMMAND_FAILED: i64 = -32000;
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: String,
+    /// Missing for notifications, which do not get a response
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl JsonRpcRequest {
+    pub fn parse(message: &str) -> Result<Self, Value> {
+        let value: Value = serde_json::from_str(message).map_err(|err| {
+            JsonRpcResponse::error(Value::Null, PARSE_ERROR, format!("Parse error: {}", err))
+        })?;
+
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request: JsonRpcRequest = serde_json::from_value(value).map_err(|err| {
+            JsonRpcResponse::error(
+                id.clone(),
+                INVALID_REQUEST,
+                format!("Invalid request: {}", err),
+            )
+        })?;
+
+        if request.jsonrpc != JSON_RPC_VERSION {
+            return Err(JsonRpcResponse::error(
+                id,
+                INVALID_REQUEST,
+                format!(
+                    "Invalid request: unsupported JSON-RPC version: {}",
+                    request.jsonrpc
+                ),
+            ));
+        }
+
+        Ok(request)
+    }
+
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T, String> {
+        let params = if self.params.is_null() {
+            json!({})
+        } else {
+            self.params.clone()
+        };
+        serde_json::from_value(params).map_err(|err| format!("Invalid params: {}", err))
+    }
+}
+
+pub struct JsonRpcResponse;
+
+impl JsonRpcResponse {
+    pub fn result(id: Value, result: Value) -> Value {
+        json!({
+            "jsonrpc": JSON_RPC_VERSION,
+            "id": id,
+            "result": result,
+        })
+    }
+
+    pub fn error(id: Value, code: i64, message: String) -> Value {
+        json!({
+            "jsonrpc": JSON_RPC_VERSION,
+            "id": id,
+            "error": {
+                "code": code,
+                "message": message,
+            },
+        })
+    }
+}
+
+pub fn json_rpc_notification(method: &str, params: Value) -> Value {
+    json!({
+        "jsonrpc": JSON_RPC_VERSION,
+        "method": method,
+        "params": params,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::json_rpc::{JsonRpcRequest, INVALID_REQUEST, PARSE_ERROR};
+    use assert2::{check, let_assert};
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    fn parse_request() {
+        let_assert!(
+            Ok(request) = JsonRpcRequest::parse(
+                r#"{"jsonrpc":"2.0","id":1,"method":"component/list","params":{"componentName":"a"}}"#
+            )
+        );
+        check!(request.id == Some(json!(1)));
+        check!(request.method == "component/list");
+        check!(request.params == json!({"componentName": "a"}));
+
+        let_assert!(Ok(request) = JsonRpcRequest::parse(r#"{"jsonrpc":"2.0","method":"exit"}"#));
+        check!(request.id == None);
+        check!(request.params.is_null());
+    }
+
+    #[test]
+    fn parse_invalid_request() {
+        let_assert!(Err(response) = JsonRpcRequest::parse("{"));
+        check!(response["id"].is_null());
+        check!(response["error"]["code"] == json!(PARSE_ERROR));
+
+        let_assert!(
+            Err(response) = JsonRpcRequest::parse(r#"{"jsonrpc":"1.0","id":"x","method":"a"}"#)
+        );
+        check!(response["id"] == json!("x"));
+        check!(response["error"]["code"] == json!(INVALID_REQUEST));
+
+        let_assert!(Err(response) = JsonRpcRequest::parse(r#"{"jsonrpc":"2.0","id":2}"#));
+        check!(response["id"] == json!(2));
+        check!(response["error"]["code"] == json!(INVALID_REQUEST));
+    }
+}
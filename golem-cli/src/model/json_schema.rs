@@ -0,0 +1,247 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_wasm_ast::analysis::{
+    AnalysedFunctionParameter, AnalysedResourceMode, AnalysedType, NameOptionTypePair,
+    NameTypePair, TypeEnum, TypeFlags, TypeRecord, TypeTuple, TypeVariant,
+};
+use serde_json::{json, Map, Value};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema of a function's parameters and result, describing the JSON values accepted and
+/// returned by typed invocations. Parameters are described as a positional array, the result is
+/// null for functions without results, and an array for functions with multiple results.
+pub fn function_json_schema(
+    function_name: &str,
+    parameters: &[&AnalysedFunctionParameter],
+    results: &[&AnalysedType],
+) -> Value {
+    let parameter_schemas = parameters
+        .iter()
+        .map(|parameter| {
+            let mut schema = type_json_schema(&parameter.typ);
+            if let Value::Object(schema) = &mut schema {
+                schema.insert("title".to_string(), json!(parameter.name));
+            }
+            schema
+        })
+        .collect::<Vec<_>>();
+
+    let result_schema = match results {
+        [] => json!({ "type": "null" }),
+        [result] => type_json_schema(result),
+        results => tuple_json_schema(results.iter().copied()),
+    };
+
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": function_name,
+        "type": "object",
+        "properties": {
+            "parameters": {
+                "type": "array",
+                "prefixItems": parameter_schemas,
+                "items": false,
+                "minItems": parameters.len(),
+                "maxItems": parameters.len(),
+            },
+            "result": result_schema,
+        },
+        "required": ["parameters", "result"],
+        "additionalProperties": false,
+    })
+}
+
+pub fn type_json_schema(typ: &AnalysedType) -> Value {
+    match typ {
+        AnalysedType::Bool { .. } => json!({ "type": "boolean" }),
+        AnalysedType::S8 { .. } => integer_json_schema(i8::MIN as i64, i8::MAX as u64),
+        AnalysedType::U8 { .. } => integer_json_schema(0, u8::MAX as u64),
+        AnalysedType::S16 { .. } => integer_json_schema(i16::MIN as i64, i16::MAX as u64),
+        AnalysedType::U16 { .. } => integer_json_schema(0, u16::MAX as u64),
+        AnalysedType::S32 { .. } => integer_json_schema(i32::MIN as i64, i32::MAX as u64),
+        AnalysedType::U32 { .. } => integer_json_schema(0, u32::MAX as u64),
+        AnalysedType::S64 { .. } => integer_json_schema(i64::MIN, i64::MAX as u64),
+        AnalysedType::U64 { .. } => integer_json_schema(0, u64::MAX),
+        AnalysedType::F32 { .. } | AnalysedType::F64 { .. } => json!({ "type": "number" }),
+        AnalysedType::Chr { .. } => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        AnalysedType::Str { .. } => json!({ "type": "string" }),
+        AnalysedType::List(list) => json!({
+            "type": "array",
+            "items": type_json_schema(&list.inner),
+        }),
+        AnalysedType::Tuple(TypeTuple { items }) => tuple_json_schema(items.iter()),
+        AnalysedType::Record(TypeRecord { fields }) => {
+            let properties = fields
+                .iter()
+                .map(|NameTypePair { name, typ }| (name.clone(), type_json_schema(typ)))
+                .collect::<Map<_, _>>();
+            let required = fields
+                .iter()
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            })
+        }
+        AnalysedType::Option(option) => json!({
+            "anyOf": [
+                { "type": "null" },
+                type_json_schema(&option.inner),
+            ]
+        }),
+        AnalysedType::Enum(TypeEnum { cases }) => json!({
+            "type": "string",
+            "enum": cases,
+        }),
+        AnalysedType::Flags(TypeFlags { names }) => json!({
+            "type": "array",
+            "items": {
+                "type": "string",
+                "enum": names,
+            },
+            "uniqueItems": true,
+        }),
+        AnalysedType::Variant(TypeVariant { cases }) => json!({
+            "oneOf": cases
+                .iter()
+                .map(|NameOptionTypePair { name, typ }| {
+                    single_property_json_schema(name, typ.as_ref())
+                })
+                .collect::<Vec<_>>()
+        }),
+        AnalysedType::Result(result) => json!({
+            "oneOf": [
+                single_property_json_schema("ok", result.ok.as_deref()),
+                single_property_json_schema("err", result.err.as_deref()),
+            ]
+        }),
+        AnalysedType::Handle(handle) => json!({
+            "type": "string",
+            "description": match handle.mode {
+                AnalysedResourceMode::Owned => "Owned resource handle",
+                AnalysedResourceMode::Borrowed => "Borrowed resource handle",
+            },
+        }),
+    }
+}
+
+fn integer_json_schema(minimum: i64, maximum: u64) -> Value {
+    json!({
+        "type": "integer",
+        "minimum": minimum,
+        "maximum": maximum,
+    })
+}
+
+fn tuple_json_schema<'a, I: ExactSizeIterator<Item = &'a AnalysedType>>(items: I) -> Value {
+    let len = items.len();
+    json!({
+        "type": "array",
+        "prefixItems": items.map(type_json_schema).collect::<Vec<_>>(),
+        "items": false,
+        "minItems": len,
+        "maxItems": len,
+    })
+}
+
+/// Schema for variant cases and results, which are represented as an object with one property
+/// named after the case, where cases without a type have a null value
+fn single_property_json_schema(name: &str, typ: Option<&AnalysedType>) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            name: typ.map(type_json_schema).unwrap_or_else(|| json!({ "type": "null" })),
+        },
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::json_schema::{function_json_schema, type_json_schema};
+    use assert2::check;
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, field, list, option, record, result_err, str, u32, u8, unit_case, variant,
+    };
+    use golem_wasm_ast::analysis::AnalysedFunctionParameter;
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    fn record_schema() {
+        let schema = type_json_schema(&record(vec![
+            field("name", str()),
+            field("tags", list(str())),
+            field("age", option(u8())),
+        ]));
+
+        check!(
+            schema
+                == json!({
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "age": {
+                            "anyOf": [
+                                { "type": "null" },
+                                { "type": "integer", "minimum": 0, "maximum": 255 },
+                            ]
+                        },
+                    },
+                    "required": ["name", "tags", "age"],
+                    "additionalProperties": false,
+                })
+        );
+    }
+
+    #[test]
+    fn variant_and_result_schema() {
+        let schema = type_json_schema(&variant(vec![unit_case("none"), case("some", str())]));
+        check!(schema["oneOf"][0]["properties"]["none"] == json!({ "type": "null" }));
+        check!(schema["oneOf"][1]["properties"]["some"] == json!({ "type": "string" }));
+
+        let schema = type_json_schema(&result_err(str()));
+        check!(schema["oneOf"][0]["properties"]["ok"] == json!({ "type": "null" }));
+        check!(schema["oneOf"][1]["properties"]["err"] == json!({ "type": "string" }));
+    }
+
+    #[test]
+    fn function_schema() {
+        let parameter = AnalysedFunctionParameter {
+            name: "count".to_string(),
+            typ: u32(),
+        };
+        let schema = function_json_schema("api.{inc}", &[&parameter], &[]);
+
+        check!(schema["title"] == json!("api.{inc}"));
+        check!(
+            schema["properties"]["parameters"]["prefixItems"]
+                == json!([{
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 4294967295u64,
+                    "title": "count",
+                }])
+        );
+        check!(schema["properties"]["parameters"]["maxItems"] == json!(1));
+        check!(schema["properties"]["result"] == json!({ "type": "null" }));
+    }
+}
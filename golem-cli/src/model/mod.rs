@@ -12,24 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod api_definition;
 pub mod app;
+pub mod app_graph;
 pub mod app_raw;
+pub mod app_secrets;
+pub mod app_variables;
+pub mod bench;
 pub mod component;
 pub mod deploy;
+pub mod invocation_import;
 pub mod invoke_result_view;
+pub mod json_rpc;
+pub mod json_schema;
+pub mod payload;
 pub mod plugin_manifest;
 pub mod project;
 pub mod template;
 pub mod text;
 pub mod to_cloud;
 pub mod to_oss;
+pub mod trace;
 pub mod wave;
 
 use crate::cloud::{AccountId, ProjectId};
 use crate::command::shared_args::StreamArgs;
 use crate::config::{
-    CloudProfile, NamedProfile, OssProfile, Profile, ProfileConfig, ProfileKind, ProfileName,
+    CloudProfile, NamedProfile, OssProfile, Profile, ProfileConfig, ProfileConfigKey, ProfileKind,
+    ProfileName,
 };
+use crate::diagnose::required_tools;
 use crate::model::to_oss::ToOss;
 use anyhow::{anyhow, Context};
 use chrono::{DateTime, Utc};
@@ -53,7 +65,6 @@ use std::fmt::{Debug, Display, Formatter};
 use std::io::Read;
 use std::path::PathBuf;
 use std::str::FromStr;
-use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use url::Url;
 use uuid::Uuid;
@@ -61,12 +72,18 @@ use uuid::Uuid;
 // TODO: move arg thing into command
 // TODO: move non generic entities into mods
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize, Default)]
+#[derive(
+    clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize, Default,
+)]
+#[clap(rename_all = "kebab_case")]
 pub enum Format {
     Json,
+    #[value(alias = "yml")]
     Yaml,
     #[default]
     Text,
+    /// Comma separated values, for list commands, other commands fall back to JSON
+    Csv,
 }
 
 impl Display for Format {
@@ -75,6 +92,7 @@ impl Display for Format {
             Self::Json => "json",
             Self::Yaml => "yaml",
             Self::Text => "text",
+            Self::Csv => "csv",
         };
         Display::fmt(&s, f)
     }
@@ -84,21 +102,121 @@ impl FromStr for Format {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "json" => Ok(Format::Json),
-            "yaml" => Ok(Format::Yaml),
-            "text" => Ok(Format::Text),
-            _ => {
-                let all = Format::iter()
-                    .map(|x| format!("\"{x}\""))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                Err(format!("Unknown format: {s}. Expected one of {all}"))
-            }
-        }
+        parse_value_enum("format", s)
+    }
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum ColorMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Display for ColorMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        };
+        Display::fmt(&s, f)
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_value_enum("color mode", s)
     }
 }
 
+/// Release channel used by self-update
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum UpdateChannel {
+    /// Latest published release
+    #[default]
+    Stable,
+    /// Latest pre-release, built from the main branch
+    Nightly,
+}
+
+impl Display for UpdateChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Stable => "stable",
+            Self::Nightly => "nightly",
+        };
+        Display::fmt(&s, f)
+    }
+}
+
+/// Side of a component's interfaces a client WIT is generated for
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum ClientDirection {
+    /// WASM RPC client for calling the exported interfaces of the component
+    #[default]
+    Export,
+    /// Mock exporting the imported interfaces of the component, which can be composed into the
+    /// component in place of the real implementations
+    Import,
+}
+
+/// Category of sensitive component imports, used by the security audit of `component inspect`
+#[derive(
+    clap::ValueEnum, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize,
+)]
+#[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportCategory {
+    /// wasi:sockets interfaces
+    Sockets,
+    /// wasi:filesystem interfaces
+    Filesystem,
+    /// wasi:cli/environment, giving access to env vars and arguments
+    Environment,
+    /// wasi:clocks interfaces
+    Clocks,
+    /// wasi:http/outgoing-handler, for outgoing HTTP requests
+    Http,
+    /// Imports which are neither WASI nor Golem host interfaces, these have to be provided by
+    /// composition before the component can be instantiated
+    NonWasi,
+}
+
+impl Display for ImportCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Sockets => "sockets",
+            Self::Filesystem => "filesystem",
+            Self::Environment => "environment",
+            Self::Clocks => "clocks",
+            Self::Http => "http",
+            Self::NonWasi => "non-wasi",
+        };
+        Display::fmt(&s, f)
+    }
+}
+
+/// FromStr helper for ValueEnums, so values parsed outside of clap (e.g. from config files or
+/// environment variables) accept the same names and aliases as the command line
+pub fn parse_value_enum<T: clap::ValueEnum>(kind: &str, s: &str) -> Result<T, String> {
+    T::from_str(s, false).map_err(|_| {
+        let all = T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| format!("\"{}\"", value.get_name()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("Unknown {kind}: {s}. Expected one of {all}")
+    })
+}
+
 pub trait HasFormatConfig {
     fn format(&self) -> Option<Format>;
 }
@@ -160,6 +278,77 @@ impl Display for WorkerName {
     }
 }
 
+/// Worker address in the `urn:worker:<COMPONENT_ID>/<WORKER>` format
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WorkerUrn {
+    pub component_id: Uuid,
+    pub worker_name: WorkerName,
+}
+
+impl WorkerUrn {
+    const PREFIX: &'static str = "urn:worker:";
+
+    pub fn is_urn(value: &str) -> bool {
+        value.starts_with(Self::PREFIX)
+    }
+}
+
+impl Display for WorkerUrn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}/{}",
+            Self::PREFIX,
+            self.component_id,
+            self.worker_name
+        )
+    }
+}
+
+impl FromStr for WorkerUrn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(id) = s.strip_prefix(Self::PREFIX) else {
+            return Err(format!(
+                "Invalid worker URN: {s}. Expected format: urn:worker:<COMPONENT_ID>/<WORKER>"
+            ));
+        };
+        let Some((component_id, worker_name)) = id.split_once('/') else {
+            return Err(format!(
+                "Missing worker name in worker URN: {s}. Expected format: urn:worker:<COMPONENT_ID>/<WORKER>"
+            ));
+        };
+        let component_id = Uuid::parse_str(component_id)
+            .map_err(|err| format!("Invalid component ID in worker URN: {s}: {err}"))?;
+        if worker_name.is_empty() {
+            return Err(format!(
+                "Missing worker name in worker URN: {s}. Expected format: urn:worker:<COMPONENT_ID>/<WORKER>"
+            ));
+        }
+
+        Ok(WorkerUrn {
+            component_id,
+            worker_name: worker_name.into(),
+        })
+    }
+}
+
+impl TryFrom<String> for WorkerUrn {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WorkerUrn> for String {
+    fn from(value: WorkerUrn) -> Self {
+        value.to_string()
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct IdempotencyKey(pub String);
 
@@ -336,12 +525,13 @@ impl TypedValueParser for JsonValueParser {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct TemplateDescription {
     pub name: TemplateName,
     pub language: GuestLanguage,
     pub tier: GuestLanguageTier,
     pub description: String,
+    pub required_tools: Vec<String>,
 }
 
 impl TemplateDescription {
@@ -351,10 +541,50 @@ impl TemplateDescription {
             language: template.language,
             description: template.description.clone(),
             tier: template.language.tier(),
+            required_tools: required_tools(template.language)
+                .into_iter()
+                .map(|tool| tool.to_string())
+                .collect(),
         }
     }
 }
 
+/// Filters for listing component templates, all set filters have to match
+#[derive(Clone, Debug, Default)]
+pub struct TemplateFilter {
+    pub language: Option<GuestLanguage>,
+    pub tier: Option<GuestLanguageTier>,
+    /// Case-insensitive search in the language, name and description of the template
+    pub search: Option<String>,
+}
+
+impl TemplateFilter {
+    pub fn matches(&self, language: GuestLanguage, name: &TemplateName, description: &str) -> bool {
+        let matches_search = |search: &String| {
+            let search = search.to_lowercase();
+            language.id().contains(&search)
+                || language.name().to_lowercase().contains(&search)
+                || name.as_str().to_lowercase().contains(&search)
+                || description.to_lowercase().contains(&search)
+        };
+
+        self.language.is_none_or(|filter| filter == language)
+            && self
+                .tier
+                .as_ref()
+                .is_none_or(|tier| *tier == language.tier())
+            && self.search.as_ref().is_none_or(matches_search)
+    }
+}
+
+/// Files of a component template, as created by instantiating it
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateFiles {
+    pub template: String,
+    pub files: Vec<PathBuf>,
+}
+
 #[derive(Clone, Debug)]
 pub enum PathBufOrStdin {
     Path(PathBuf),
@@ -396,8 +626,118 @@ impl FromStr for PathBufOrStdin {
     }
 }
 
+/// Action to take on the worker when an invocation times out on the client side
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvokeTimeoutAction {
+    Cancel,
+    Interrupt,
+}
+
+impl Display for InvokeTimeoutAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeTimeoutAction::Cancel => write!(f, "cancel"),
+            InvokeTimeoutAction::Interrupt => write!(f, "interrupt"),
+        }
+    }
+}
+
+impl FromStr for InvokeTimeoutAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cancel" => Ok(InvokeTimeoutAction::Cancel),
+            "interrupt" => Ok(InvokeTimeoutAction::Interrupt),
+            _ => Err(format!(
+                "Unknown invoke timeout action: {s}. Expected one of \"cancel\", \"interrupt\""
+            )),
+        }
+    }
+}
+
+/// Function argument read from a file, for list<u8> parameters, in POSITION=PATH form
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WorkerFunctionFileArgument {
+    /// 1-based position of the function parameter
+    pub position: usize,
+    pub path: PathBuf,
+}
+
+impl FromStr for WorkerFunctionFileArgument {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_format = || format!("Invalid file argument: {s}. Expected POSITION=PATH");
+
+        let (position, path) = s.split_once('=').ok_or_else(invalid_format)?;
+        let position = position
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|position| *position > 0)
+            .ok_or_else(invalid_format)?;
+        if path.is_empty() {
+            return Err(invalid_format());
+        }
+
+        Ok(Self {
+            position,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+/// How to react when the WASM RPC version used for clients is incompatible with the server
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VersionCheckMode {
+    Warn,
+    Fail,
+}
+
+impl Display for VersionCheckMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionCheckMode::Warn => write!(f, "warn"),
+            VersionCheckMode::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+impl FromStr for VersionCheckMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warn" => Ok(VersionCheckMode::Warn),
+            "fail" => Ok(VersionCheckMode::Fail),
+            _ => Err(format!(
+                "Unknown version check mode: {s}. Expected one of \"warn\", \"fail\""
+            )),
+        }
+    }
+}
+
+/// WASM RPC clients use the same protocol as the runtime only if the major and minor
+/// versions match, pre-release and patch differences are compatible
+pub fn is_wasm_rpc_runtime_compatible(
+    wasm_rpc_version: &str,
+    server_version: &str,
+) -> anyhow::Result<bool> {
+    let parse = |version: &str| {
+        semver::Version::parse(version.trim().trim_start_matches('v'))
+            .with_context(|| format!("Failed to parse version: {version}"))
+    };
+    let wasm_rpc_version = parse(wasm_rpc_version)?;
+    let server_version = parse(server_version)?;
+    Ok(wasm_rpc_version.major == server_version.major
+        && wasm_rpc_version.minor == server_version.minor)
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+#[clap(rename_all = "kebab_case")]
 pub enum WorkerUpdateMode {
+    #[value(name = "auto", alias = "automatic")]
     Automatic,
     Manual,
 }
@@ -419,13 +759,7 @@ impl FromStr for WorkerUpdateMode {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "auto" => Ok(WorkerUpdateMode::Automatic),
-            "manual" => Ok(WorkerUpdateMode::Manual),
-            _ => Err(format!(
-                "Unknown worker update mode: {s}. Expected one of \"auto\", \"manual\""
-            )),
-        }
+        parse_value_enum("worker update mode", s)
     }
 }
 
@@ -434,6 +768,7 @@ impl FromStr for WorkerUpdateMode {
 pub struct WorkerMetadataView {
     pub component_name: ComponentName,
     pub worker_name: WorkerName,
+    pub worker_urn: WorkerUrn,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub account_id: Option<AccountId>,
@@ -465,7 +800,11 @@ impl From<WorkerMetadata> for WorkerMetadataView {
     fn from(value: WorkerMetadata) -> Self {
         WorkerMetadataView {
             component_name: value.component_name,
-            worker_name: value.worker_id.worker_name.into(),
+            worker_name: value.worker_id.worker_name.clone().into(),
+            worker_urn: WorkerUrn {
+                component_id: value.worker_id.component_id.0,
+                worker_name: value.worker_id.worker_name.into(),
+            },
             account_id: value.account_id,
             args: value.args,
             env: value.env,
@@ -554,6 +893,212 @@ impl WorkerMetadata {
 pub struct WorkersMetadataResponseView {
     pub workers: Vec<WorkerMetadataView>,
     pub cursors: BTreeMap<String, String>,
+    /// Columns of the text and CSV output, JSON and YAML always contain the whole metadata
+    #[serde(skip)]
+    pub columns: Vec<WorkerListColumn>,
+}
+
+/// Column of the worker list, projected from the worker metadata on the client side
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum WorkerListColumn {
+    Env(String),
+    ComponentName,
+    WorkerName,
+    WorkerUrn,
+    AccountId,
+    Args,
+    Status,
+    ComponentVersion,
+    RetryCount,
+    PendingInvocationCount,
+    UpdateCount,
+    CreatedAt,
+    LastError,
+    ComponentSize,
+    TotalLinearMemorySize,
+    OwnedResourceCount,
+}
+
+impl WorkerListColumn {
+    const FIELDS: &'static [(&'static str, WorkerListColumn)] = &[
+        ("component_name", WorkerListColumn::ComponentName),
+        ("worker_name", WorkerListColumn::WorkerName),
+        ("worker_urn", WorkerListColumn::WorkerUrn),
+        ("account_id", WorkerListColumn::AccountId),
+        ("args", WorkerListColumn::Args),
+        ("status", WorkerListColumn::Status),
+        ("component_version", WorkerListColumn::ComponentVersion),
+        ("retry_count", WorkerListColumn::RetryCount),
+        (
+            "pending_invocation_count",
+            WorkerListColumn::PendingInvocationCount,
+        ),
+        ("update_count", WorkerListColumn::UpdateCount),
+        ("created_at", WorkerListColumn::CreatedAt),
+        ("last_error", WorkerListColumn::LastError),
+        ("component_size", WorkerListColumn::ComponentSize),
+        (
+            "total_linear_memory_size",
+            WorkerListColumn::TotalLinearMemorySize,
+        ),
+        ("owned_resource_count", WorkerListColumn::OwnedResourceCount),
+    ];
+
+    pub fn value(&self, worker: &WorkerMetadataView) -> String {
+        match self {
+            WorkerListColumn::Env(name) => worker.env.get(name).cloned().unwrap_or_default(),
+            WorkerListColumn::ComponentName => worker.component_name.to_string(),
+            WorkerListColumn::WorkerName => worker.worker_name.to_string(),
+            WorkerListColumn::WorkerUrn => worker.worker_urn.to_string(),
+            WorkerListColumn::AccountId => worker
+                .account_id
+                .as_ref()
+                .map(|account_id| account_id.0.clone())
+                .unwrap_or_default(),
+            WorkerListColumn::Args => worker.args.join(" "),
+            WorkerListColumn::Status => worker.status.to_string(),
+            WorkerListColumn::ComponentVersion => worker.component_version.to_string(),
+            WorkerListColumn::RetryCount => worker.retry_count.to_string(),
+            WorkerListColumn::PendingInvocationCount => worker.pending_invocation_count.to_string(),
+            WorkerListColumn::UpdateCount => worker.updates.len().to_string(),
+            WorkerListColumn::CreatedAt => worker.created_at.to_string(),
+            WorkerListColumn::LastError => worker.last_error.clone().unwrap_or_default(),
+            WorkerListColumn::ComponentSize => worker.component_size.to_string(),
+            WorkerListColumn::TotalLinearMemorySize => worker.total_linear_memory_size.to_string(),
+            WorkerListColumn::OwnedResourceCount => worker.owned_resources.len().to_string(),
+        }
+    }
+}
+
+impl Display for WorkerListColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerListColumn::Env(name) => write!(f, "env.{}", name),
+            column => {
+                let (name, _) = Self::FIELDS
+                    .iter()
+                    .find(|(_, field)| field == column)
+                    .expect("Missing worker list column name");
+                Display::fmt(name, f)
+            }
+        }
+    }
+}
+
+impl FromStr for WorkerListColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(name) = s.strip_prefix("env.") {
+            if name.is_empty() {
+                return Err("Missing environment variable name after \"env.\"".to_string());
+            }
+            return Ok(WorkerListColumn::Env(name.to_string()));
+        }
+
+        let normalized = s.replace('-', "_").to_lowercase();
+        Self::FIELDS
+            .iter()
+            .find(|(name, _)| *name == normalized)
+            .map(|(_, column)| column.clone())
+            .ok_or_else(|| {
+                format!(
+                    "Unknown worker column: {s}. Expected \"env.<NAME>\" or one of {}",
+                    Self::FIELDS
+                        .iter()
+                        .map(|(name, _)| format!("\"{name}\""))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+impl TryFrom<String> for WorkerListColumn {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WorkerListColumn> for String {
+    fn from(value: WorkerListColumn) -> Self {
+        value.to_string()
+    }
+}
+
+/// Prefix of the environment variables storing worker labels, as labels are not supported
+/// by the server, they are emulated using the worker environment
+pub const WORKER_LABEL_ENV_VAR_PREFIX: &str = "GOLEM_LABEL_";
+
+/// Worker label in KEY=VALUE form, used both for labeling new workers and as a selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerLabel {
+    pub key: String,
+    pub value: String,
+}
+
+impl WorkerLabel {
+    pub fn env_var(&self) -> (String, String) {
+        (
+            format!("{}{}", WORKER_LABEL_ENV_VAR_PREFIX, self.key),
+            self.value.clone(),
+        )
+    }
+
+    /// Worker metadata filter selecting the workers with this label
+    pub fn filter(&self) -> String {
+        format!(
+            "env.{}{} = {}",
+            WORKER_LABEL_ENV_VAR_PREFIX, self.key, self.value
+        )
+    }
+
+    /// Labels of a worker, extracted from its environment
+    pub fn from_env(env: &HashMap<String, String>) -> BTreeMap<String, String> {
+        env.iter()
+            .filter_map(|(name, value)| {
+                name.strip_prefix(WORKER_LABEL_ENV_VAR_PREFIX)
+                    .map(|key| (key.to_string(), value.clone()))
+            })
+            .collect()
+    }
+}
+
+impl Display for WorkerLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+impl FromStr for WorkerLabel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((key, value)) = s.split_once('=') else {
+            return Err(format!("Invalid label: {s}, expected KEY=VALUE"));
+        };
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "Invalid label key: {key}, expected a non-empty key of alphanumeric characters, '_' or '-'"
+            ));
+        }
+        if value.is_empty() || value.chars().any(char::is_whitespace) {
+            return Err(format!(
+                "Invalid label value: {value}, expected a non-empty value without whitespace"
+            ));
+        }
+        Ok(WorkerLabel {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
 }
 
 impl TrimDateTime for WorkersMetadataResponseView {
@@ -658,6 +1203,105 @@ impl From<StreamArgs> for WorkerConnectOptions {
     }
 }
 
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[clap(rename_all = "kebab_case")]
+pub enum WorkerLogStream {
+    Stdout,
+    Stderr,
+    Log,
+}
+
+impl Display for WorkerLogStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+            Self::Log => "log",
+        };
+        Display::fmt(&s, f)
+    }
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[clap(rename_all = "kebab_case")]
+pub enum WorkerLogLevel {
+    Trace,
+    Debug,
+    Info,
+    #[value(alias = "warning")]
+    Warn,
+    Error,
+    Critical,
+}
+
+impl From<&golem_common::model::LogLevel> for WorkerLogLevel {
+    fn from(value: &golem_common::model::LogLevel) -> Self {
+        match value {
+            golem_common::model::LogLevel::Trace => WorkerLogLevel::Trace,
+            golem_common::model::LogLevel::Debug => WorkerLogLevel::Debug,
+            golem_common::model::LogLevel::Info => WorkerLogLevel::Info,
+            golem_common::model::LogLevel::Warn => WorkerLogLevel::Warn,
+            golem_common::model::LogLevel::Error => WorkerLogLevel::Error,
+            golem_common::model::LogLevel::Critical => WorkerLogLevel::Critical,
+        }
+    }
+}
+
+/// Filter for worker output, both for the live stream and for output reconstructed from the oplog
+#[derive(Debug, Clone, Default)]
+pub struct WorkerLogFilter {
+    pub since: Option<DateTime<Utc>>,
+    /// Minimum level of log entries, stdout and stderr are not filtered by level
+    pub min_level: Option<WorkerLogLevel>,
+    /// Shown streams, all streams are shown if empty
+    pub streams: Vec<WorkerLogStream>,
+}
+
+impl WorkerLogFilter {
+    pub fn matches(
+        &self,
+        stream: WorkerLogStream,
+        level: Option<WorkerLogLevel>,
+        timestamp_millis: u64,
+    ) -> bool {
+        (self.streams.is_empty() || self.streams.contains(&stream))
+            && self
+                .since
+                .is_none_or(|since| timestamp_millis as i64 >= since.timestamp_millis())
+            && match (self.min_level, level) {
+                (Some(min_level), Some(level)) => level >= min_level,
+                _ => true,
+            }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileConfigValueView {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<String>,
+    pub description: String,
+}
+
+impl ProfileConfigValueView {
+    pub fn new(profile: &Profile, key: ProfileConfigKey) -> Self {
+        Self {
+            key: key.to_string(),
+            value: profile.config_value(key),
+            description: key.description().to_string(),
+        }
+    }
+
+    pub fn command_format(command: &str, format: Format) -> Self {
+        Self {
+            key: format!("{} --for {}", ProfileConfigKey::Format, command),
+            value: Some(format.to_string()),
+            description: format!("Output format of the {} command", command.replace('-', " ")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct ProfileView {
     pub is_active: bool,
@@ -745,6 +1389,46 @@ pub struct SelectedComponents {
     pub component_names: Vec<ComponentName>,
 }
 
+/// Permission scopes requested for a token, mapped to the project actions which grant them
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
+pub enum TokenScope {
+    #[value(name = "component:read")]
+    ComponentRead,
+    #[value(name = "component:write")]
+    ComponentWrite,
+    #[value(name = "worker:read")]
+    WorkerRead,
+    #[value(name = "worker:invoke")]
+    WorkerInvoke,
+    #[value(name = "worker:write")]
+    WorkerWrite,
+}
+
+impl TokenScope {
+    pub fn project_actions(&self) -> &'static [ProjectAction] {
+        match self {
+            TokenScope::ComponentRead => &[ProjectAction::ViewComponent],
+            TokenScope::ComponentWrite => &[
+                ProjectAction::ViewComponent,
+                ProjectAction::CreateComponent,
+                ProjectAction::UpdateComponent,
+            ],
+            TokenScope::WorkerRead => &[ProjectAction::ViewWorker],
+            TokenScope::WorkerInvoke => &[
+                ProjectAction::ViewWorker,
+                ProjectAction::CreateWorker,
+                ProjectAction::UpdateWorker,
+            ],
+            TokenScope::WorkerWrite => &[
+                ProjectAction::ViewWorker,
+                ProjectAction::CreateWorker,
+                ProjectAction::UpdateWorker,
+                ProjectAction::DeleteWorker,
+            ],
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct TokenId(pub Uuid);
 
@@ -767,17 +1451,58 @@ impl FromStr for ProjectPolicyId {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize)]
+/// Project grant to be created for a new project, in the form of `<ACCOUNT_ID>:<POLICY_ID>`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProjectPolicyGrant {
+    pub account_id: AccountId,
+    pub policy_id: ProjectPolicyId,
+}
+
+impl FromStr for ProjectPolicyGrant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((account_id, policy_id)) = s.rsplit_once(':') else {
+            return Err(format!(
+                "Invalid project grant: {s}. Expected format: <ACCOUNT_ID>:<POLICY_ID>"
+            ));
+        };
+        if account_id.is_empty() {
+            return Err(format!("Missing account id in project grant: {s}"));
+        }
+        let policy_id = policy_id
+            .parse::<ProjectPolicyId>()
+            .map_err(|err| format!("Invalid policy id in project grant: {s}, {err}"))?;
+
+        Ok(ProjectPolicyGrant {
+            account_id: AccountId(account_id.to_string()),
+            policy_id,
+        })
+    }
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize)]
+#[clap(rename_all = "verbatim")]
 pub enum Role {
+    #[value(alias = "admin")]
     Admin,
+    #[value(alias = "marketing-admin")]
     MarketingAdmin,
+    #[value(alias = "view-project")]
     ViewProject,
+    #[value(alias = "delete-project")]
     DeleteProject,
+    #[value(alias = "create-project")]
     CreateProject,
+    #[value(alias = "instance-server")]
     InstanceServer,
+    #[value(alias = "update-project")]
     UpdateProject,
+    #[value(alias = "view-plugin")]
     ViewPlugin,
+    #[value(alias = "create-plugin")]
     CreatePlugin,
+    #[value(alias = "delete-plugin")]
     DeletePlugin,
 }
 
@@ -800,28 +1525,62 @@ impl Display for Role {
     }
 }
 
+impl Role {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Role::Admin => "Unrestricted administrator access",
+            Role::MarketingAdmin => "Access to account and usage information of all users",
+            Role::ViewProject => "Read-only access to projects",
+            Role::DeleteProject => "Deletion of projects",
+            Role::CreateProject => "Creation of new projects",
+            Role::InstanceServer => "Access used by Golem service instances",
+            Role::UpdateProject => "Modification and sharing of projects",
+            Role::ViewPlugin => "Read-only access to plugins",
+            Role::CreatePlugin => "Registration of new plugins",
+            Role::DeletePlugin => "Deletion of plugins",
+        }
+    }
+
+    /// Informational summary of what the role enables. The permission model is only defined on
+    /// the server side, so this is maintained by hand and the server remains authoritative.
+    pub fn capabilities(&self) -> &'static [&'static str] {
+        match self {
+            Role::Admin => &[
+                "Create, view, update and delete any account",
+                "Grant and revoke roles of any account",
+                "Full access to all projects, components, workers and plugins",
+                "Manage account limits and plans",
+            ],
+            Role::MarketingAdmin => &[
+                "List and view all accounts",
+                "View resource usage and limits of all accounts",
+            ],
+            Role::ViewProject => &[
+                "List and view projects, including shared ones",
+                "View components, workers and API definitions in the projects",
+            ],
+            Role::DeleteProject => &["Delete projects owned by the account"],
+            Role::CreateProject => &["Create new projects for the account"],
+            Role::InstanceServer => &[
+                "Read components and their files for execution",
+                "Report resource usage on behalf of any account",
+            ],
+            Role::UpdateProject => &[
+                "Update projects owned by the account",
+                "Manage project policies and share projects with other accounts",
+            ],
+            Role::ViewPlugin => &["List and view plugins and their installations"],
+            Role::CreatePlugin => &["Register new plugins for the account"],
+            Role::DeletePlugin => &["Delete plugins owned by the account"],
+        }
+    }
+}
+
 impl FromStr for Role {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Admin" => Ok(Role::Admin),
-            "MarketingAdmin" => Ok(Role::MarketingAdmin),
-            "ViewProject" => Ok(Role::ViewProject),
-            "DeleteProject" => Ok(Role::DeleteProject),
-            "CreateProject" => Ok(Role::CreateProject),
-            "InstanceServer" => Ok(Role::InstanceServer),
-            "UpdateProject" => Ok(Role::UpdateProject),
-            "ViewPlugin" => Ok(Role::ViewPlugin),
-            "CreatePlugin" => Ok(Role::CreatePlugin),
-            _ => {
-                let all = Role::iter()
-                    .map(|x| format!("\"{x}\""))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                Err(format!("Unknown role: {s}. Expected one of {all}"))
-            }
-        }
+        parse_value_enum("role", s)
     }
 }
 
@@ -859,18 +1618,30 @@ impl From<golem_cloud_client::model::Role> for Role {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, EnumIter)]
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Hash, Debug, EnumIter)]
+#[clap(rename_all = "verbatim")]
 pub enum ProjectAction {
+    #[value(alias = "view-component")]
     ViewComponent,
+    #[value(alias = "create-component")]
     CreateComponent,
+    #[value(alias = "update-component")]
     UpdateComponent,
+    #[value(alias = "delete-component")]
     DeleteComponent,
+    #[value(alias = "view-worker")]
     ViewWorker,
+    #[value(alias = "create-worker")]
     CreateWorker,
+    #[value(alias = "update-worker")]
     UpdateWorker,
+    #[value(alias = "delete-worker")]
     DeleteWorker,
+    #[value(alias = "view-project-grants")]
     ViewProjectGrants,
+    #[value(alias = "create-project-grants")]
     CreateProjectGrants,
+    #[value(alias = "delete-project-grants")]
     DeleteProjectGrants,
 }
 
@@ -898,26 +1669,7 @@ impl FromStr for ProjectAction {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "ViewComponent" => Ok(ProjectAction::ViewComponent),
-            "CreateComponent" => Ok(ProjectAction::CreateComponent),
-            "UpdateComponent" => Ok(ProjectAction::UpdateComponent),
-            "DeleteComponent" => Ok(ProjectAction::DeleteComponent),
-            "ViewWorker" => Ok(ProjectAction::ViewWorker),
-            "CreateWorker" => Ok(ProjectAction::CreateWorker),
-            "UpdateWorker" => Ok(ProjectAction::UpdateWorker),
-            "DeleteWorker" => Ok(ProjectAction::DeleteWorker),
-            "ViewProjectGrants" => Ok(ProjectAction::ViewProjectGrants),
-            "CreateProjectGrants" => Ok(ProjectAction::CreateProjectGrants),
-            "DeleteProjectGrants" => Ok(ProjectAction::DeleteProjectGrants),
-            _ => {
-                let all = ProjectAction::iter()
-                    .map(|x| format!("\"{x}\""))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                Err(format!("Unknown action: {s}. Expected one of {all}"))
-            }
-        }
+        parse_value_enum("action", s)
     }
 }
 
@@ -1044,3 +1796,211 @@ impl From<PluginDefinitionCloudPluginOwnerCloudPluginScope> for PluginDefinition
         plugin_definition
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::text::fmt::TextView;
+    use crate::model::{
+        is_wasm_rpc_runtime_compatible, Format, ProjectAction, Role, TemplateFilter,
+        WorkerFunctionFileArgument, WorkerLabel, WorkerListColumn, WorkerLogFilter, WorkerLogLevel,
+        WorkerLogStream, WorkerMetadataView, WorkerName, WorkerUpdateMode, WorkerUrn,
+        WorkersMetadataResponseView,
+    };
+    use assert2::{check, let_assert};
+    use golem_templates::model::{GuestLanguage, GuestLanguageTier, TemplateName};
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::PathBuf;
+    use strum::IntoEnumIterator;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn role_descriptions() {
+        for role in Role::iter() {
+            check!(!role.description().is_empty(), "{role}");
+            check!(!role.capabilities().is_empty(), "{role}");
+            check!(Role::from(golem_cloud_client::model::Role::from(role)) == role);
+        }
+    }
+
+    #[test]
+    fn worker_list_columns() {
+        let worker = WorkerMetadataView {
+            component_name: "shop".into(),
+            worker_name: "worker-1".into(),
+            worker_urn: "urn:worker:c1a2b3c4-0000-4000-8000-000000000001/worker-1"
+                .parse()
+                .unwrap(),
+            account_id: None,
+            args: vec![],
+            env: HashMap::from([("SHARD".to_string(), "eu-1".to_string())]),
+            status: golem_client::model::WorkerStatus::Idle,
+            component_version: 3,
+            retry_count: 0,
+            pending_invocation_count: 0,
+            updates: vec![],
+            created_at: chrono::DateTime::from_timestamp(0, 0).unwrap(),
+            last_error: None,
+            component_size: 0,
+            total_linear_memory_size: 0,
+            owned_resources: HashMap::new(),
+        };
+        let view = WorkersMetadataResponseView {
+            workers: vec![worker],
+            cursors: BTreeMap::new(),
+            columns: vec![
+                "worker_name".parse().unwrap(),
+                "env.SHARD".parse::<WorkerListColumn>().unwrap(),
+            ],
+        };
+
+        let_assert!(Some(csv) = view.csv());
+        check!(csv.header == vec!["worker_name".to_string(), "env.SHARD".to_string()]);
+        check!(csv.rows == vec![vec!["worker-1".to_string(), "eu-1".to_string()]]);
+
+        // Structured outputs are not projected
+        let json = serde_json::to_value(&view).unwrap();
+        check!(json.get("columns").is_none());
+        check!(json["workers"][0]["env"]["SHARD"] == "eu-1");
+        check!(json["workers"][0]["componentVersion"] == 3);
+    }
+
+    #[test]
+    fn worker_urn_roundtrip() {
+        let urn = "urn:worker:c1a2b3c4-0000-4000-8000-000000000001/worker-1";
+        let_assert!(Ok(worker_urn) = urn.parse::<WorkerUrn>());
+        check!(
+            worker_urn.component_id
+                == Uuid::parse_str("c1a2b3c4-0000-4000-8000-000000000001").unwrap()
+        );
+        check!(worker_urn.worker_name == WorkerName::from("worker-1"));
+        check!(worker_urn.to_string() == urn);
+    }
+
+    #[test]
+    fn value_enum_names_and_aliases() {
+        check!("auto".parse::<WorkerUpdateMode>() == Ok(WorkerUpdateMode::Automatic));
+        check!("automatic".parse::<WorkerUpdateMode>() == Ok(WorkerUpdateMode::Automatic));
+        check!(WorkerUpdateMode::Automatic.to_string() == "auto");
+        check!("yml".parse::<Format>() == Ok(Format::Yaml));
+        check!("ViewProject".parse::<Role>() == Ok(Role::ViewProject));
+        check!("view-project".parse::<Role>() == Ok(Role::ViewProject));
+        check!(
+            "create-project-grants".parse::<ProjectAction>()
+                == Ok(ProjectAction::CreateProjectGrants)
+        );
+
+        let_assert!(Err(error) = "json5".parse::<Format>());
+        check!(
+            error == "Unknown format: json5. Expected one of \"json\", \"yaml\", \"text\", \"csv\""
+        );
+    }
+
+    #[test]
+    fn worker_log_filter() {
+        let since = "2025-01-01T00:00:00Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap();
+        let before = since.timestamp_millis() as u64 - 1;
+        let after = since.timestamp_millis() as u64 + 1;
+
+        let filter = WorkerLogFilter::default();
+        check!(filter.matches(WorkerLogStream::Stdout, None, before));
+        check!(filter.matches(WorkerLogStream::Log, Some(WorkerLogLevel::Trace), before));
+
+        let filter = WorkerLogFilter {
+            since: Some(since),
+            min_level: Some(WorkerLogLevel::Warn),
+            streams: vec![WorkerLogStream::Stderr, WorkerLogStream::Log],
+        };
+        check!(!filter.matches(WorkerLogStream::Log, Some(WorkerLogLevel::Error), before));
+        check!(filter.matches(WorkerLogStream::Log, Some(WorkerLogLevel::Error), after));
+        check!(!filter.matches(WorkerLogStream::Log, Some(WorkerLogLevel::Info), after));
+        check!(filter.matches(WorkerLogStream::Stderr, None, after));
+        check!(!filter.matches(WorkerLogStream::Stdout, None, after));
+    }
+
+    #[test]
+    fn template_filter() {
+        let name = TemplateName::from("http-server");
+        let description = "A simple HTTP server";
+
+        check!(TemplateFilter::default().matches(GuestLanguage::Rust, &name, description));
+        check!(TemplateFilter {
+            language: Some(GuestLanguage::Rust),
+            tier: Some(GuestLanguageTier::Tier1),
+            search: Some("HTTP".to_string()),
+        }
+        .matches(GuestLanguage::Rust, &name, description));
+        check!(!TemplateFilter {
+            language: Some(GuestLanguage::Go),
+            ..TemplateFilter::default()
+        }
+        .matches(GuestLanguage::Rust, &name, description));
+        check!(!TemplateFilter {
+            tier: Some(GuestLanguageTier::Tier2),
+            ..TemplateFilter::default()
+        }
+        .matches(GuestLanguage::Rust, &name, description));
+        check!(!TemplateFilter {
+            search: Some("grpc".to_string()),
+            ..TemplateFilter::default()
+        }
+        .matches(GuestLanguage::Rust, &name, description));
+    }
+
+    #[test]
+    fn worker_labels() {
+        let_assert!(Ok(label) = "tier=gold".parse::<WorkerLabel>());
+        check!(label.env_var() == ("GOLEM_LABEL_tier".to_string(), "gold".to_string()));
+        check!(label.filter() == "env.GOLEM_LABEL_tier = gold");
+
+        check!("tier".parse::<WorkerLabel>().is_err());
+        check!("=gold".parse::<WorkerLabel>().is_err());
+        check!("ti.er=gold".parse::<WorkerLabel>().is_err());
+        check!("tier=gold plus".parse::<WorkerLabel>().is_err());
+
+        let env = HashMap::from([
+            ("GOLEM_LABEL_tier".to_string(), "gold".to_string()),
+            ("SHARD".to_string(), "1".to_string()),
+        ]);
+        check!(
+            WorkerLabel::from_env(&env)
+                == BTreeMap::from([("tier".to_string(), "gold".to_string())])
+        );
+    }
+
+    #[test]
+    fn worker_urn_invalid() {
+        check!(!WorkerUrn::is_urn("component/worker"));
+        check!("urn:worker:not-a-uuid/worker".parse::<WorkerUrn>().is_err());
+        check!("urn:worker:c1a2b3c4-0000-4000-8000-000000000001"
+            .parse::<WorkerUrn>()
+            .is_err());
+        check!("urn:worker:c1a2b3c4-0000-4000-8000-000000000001/"
+            .parse::<WorkerUrn>()
+            .is_err());
+    }
+
+    #[test]
+    fn worker_function_file_argument() {
+        let_assert!(Ok(arg) = "2=./image.png".parse::<WorkerFunctionFileArgument>());
+        check!(arg.position == 2);
+        check!(arg.path == PathBuf::from("./image.png"));
+
+        check!("0=./image.png"
+            .parse::<WorkerFunctionFileArgument>()
+            .is_err());
+        check!("./image.png".parse::<WorkerFunctionFileArgument>().is_err());
+        check!("1=".parse::<WorkerFunctionFileArgument>().is_err());
+    }
+
+    #[test]
+    fn wasm_rpc_runtime_compatibility() {
+        check!(is_wasm_rpc_runtime_compatible("1.2.2-dev.4", "1.2.3").unwrap());
+        check!(is_wasm_rpc_runtime_compatible("1.2.2", "v1.2.0").unwrap());
+        check!(!is_wasm_rpc_runtime_compatible("1.2.2", "1.1.5").unwrap());
+        check!(!is_wasm_rpc_runtime_compatible("1.2.2", "2.2.2").unwrap());
+        check!(is_wasm_rpc_runtime_compatible("1.2.2", "unknown").is_err());
+    }
+}
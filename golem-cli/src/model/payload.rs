@@ -0,0 +1,107 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::{ProfileConfig, ProfileConfigKey};
+use std::fmt::{Display, Formatter};
+
+/// Request payloads which are checked against the size limits configured for the profile,
+/// before sending them to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// Component WASM and initial file system archive
+    ComponentUpload,
+    /// JSON encoded invocation parameters
+    InvokeParameters,
+}
+
+impl PayloadKind {
+    pub fn max_size(&self, config: &ProfileConfig) -> Option<u64> {
+        match self {
+            PayloadKind::ComponentUpload => config.max_upload_size,
+            PayloadKind::InvokeParameters => config.max_invoke_payload_size,
+        }
+    }
+
+    pub fn config_key(&self) -> ProfileConfigKey {
+        match self {
+            PayloadKind::ComponentUpload => ProfileConfigKey::MaxUploadSize,
+            PayloadKind::InvokeParameters => ProfileConfigKey::MaxInvokePayloadSize,
+        }
+    }
+}
+
+impl Display for PayloadKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadKind::ComponentUpload => write!(f, "component upload"),
+            PayloadKind::InvokeParameters => write!(f, "invocation parameters"),
+        }
+    }
+}
+
+/// Parses byte sizes with optional decimal (KB, MB, GB) or binary (KiB, MiB, GiB) units,
+/// e.g. `1048576`, `512KB` or `50MiB`
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let unit_start = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(unit_start);
+
+    let number = number
+        .parse::<u64>()
+        .map_err(|err| format!("Invalid size: {s}, {err}"))?;
+
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "kib" => 1024,
+        "mib" => 1024 * 1024,
+        "gib" => 1024 * 1024 * 1024,
+        _ => {
+            return Err(format!(
+                "Invalid size unit: {unit}, expected one of: B, KB, MB, GB, KiB, MiB, GiB"
+            ))
+        }
+    };
+
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Size is too large: {s}"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::payload::parse_byte_size;
+    use assert2::{check, let_assert};
+    use test_r::test;
+
+    #[test]
+    fn parse_valid_byte_sizes() {
+        check!(parse_byte_size("1048576") == Ok(1048576));
+        check!(parse_byte_size("10B") == Ok(10));
+        check!(parse_byte_size("512KB") == Ok(512_000));
+        check!(parse_byte_size("50MiB") == Ok(50 * 1024 * 1024));
+        check!(parse_byte_size("2 gib") == Ok(2 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn parse_invalid_byte_sizes() {
+        let_assert!(Err(_) = parse_byte_size(""));
+        let_assert!(Err(_) = parse_byte_size("MiB"));
+        let_assert!(Err(_) = parse_byte_size("10TB"));
+        let_assert!(Err(_) = parse_byte_size("1.5MiB"));
+        let_assert!(Err(_) = parse_byte_size("99999999999999GiB"));
+    }
+}
@@ -13,9 +13,16 @@
 // limitations under the License.
 
 use crate::cloud::{AccountId, ProjectId};
-use crate::model::ProjectName;
+use crate::model::{ProjectAction, ProjectName, ProjectPolicyId};
+use anyhow::{anyhow, bail, Context};
+use clap::ValueEnum;
 use golem_cloud_client::model::{Project, ProjectType};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use strum::IntoEnumIterator;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -40,3 +47,207 @@ impl From<Project> for ProjectView {
         }
     }
 }
+
+/// Team file of `project grant-many`, listing the accounts and the policies to grant to them
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectTeam {
+    /// Custom policies, as policy name to project actions
+    #[serde(default)]
+    pub policies: BTreeMap<String, Vec<String>>,
+    pub members: Vec<ProjectTeamMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectTeamMember {
+    /// Account email or account ID
+    pub account: String,
+    /// Policy ID, custom policy name or preset name
+    pub policy: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectTeamAccount {
+    Email(String),
+    Id(AccountId),
+}
+
+impl ProjectTeamMember {
+    pub fn account(&self) -> ProjectTeamAccount {
+        if self.account.contains('@') {
+            ProjectTeamAccount::Email(self.account.clone())
+        } else {
+            ProjectTeamAccount::Id(self.account.clone().into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectTeamPolicy {
+    Existing(ProjectPolicyId),
+    /// Policy to be created, custom policies take precedence over presets with the same name
+    New {
+        name: String,
+        actions: Vec<ProjectAction>,
+    },
+}
+
+impl ProjectTeam {
+    /// Resolves the distinct policies used by the members, in order of first use
+    pub fn policies_to_grant(&self) -> anyhow::Result<Vec<(String, ProjectTeamPolicy)>> {
+        let mut policies = Vec::<(String, ProjectTeamPolicy)>::new();
+        for member in &self.members {
+            if policies.iter().any(|(name, _)| name == &member.policy) {
+                continue;
+            }
+            let policy = self
+                .policy(&member.policy)
+                .with_context(|| anyhow!("Invalid policy for account {}", member.account))?;
+            policies.push((member.policy.clone(), policy));
+        }
+        Ok(policies)
+    }
+
+    fn policy(&self, policy: &str) -> anyhow::Result<ProjectTeamPolicy> {
+        if let Some(actions) = self.policies.get(policy) {
+            let actions = actions
+                .iter()
+                .map(|action| {
+                    ProjectAction::from_str(action, true)
+                        .map_err(|_| anyhow!("Unknown project action in policy {policy}: {action}"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            return Ok(ProjectTeamPolicy::New {
+                name: policy.to_string(),
+                actions,
+            });
+        }
+
+        if let Ok(preset) = ProjectPolicyPreset::from_str(policy, true) {
+            return Ok(ProjectTeamPolicy::New {
+                name: preset.to_string(),
+                actions: preset.project_actions(),
+            });
+        }
+
+        match Uuid::parse_str(policy) {
+            Ok(policy_id) => Ok(ProjectTeamPolicy::Existing(ProjectPolicyId(policy_id))),
+            Err(_) => bail!(
+                "Unknown policy: {policy}, expected a policy ID, a policy defined in the file, or one of the presets: {}",
+                ProjectPolicyPreset::value_variants().iter().join(", ")
+            ),
+        }
+    }
+}
+
+/// Predefined sets of project actions, used for creating policies for team files
+#[derive(clap::ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[clap(rename_all = "kebab_case")]
+pub enum ProjectPolicyPreset {
+    Viewer,
+    Operator,
+    Developer,
+    Admin,
+}
+
+impl ProjectPolicyPreset {
+    pub fn project_actions(&self) -> Vec<ProjectAction> {
+        match self {
+            ProjectPolicyPreset::Viewer => {
+                vec![ProjectAction::ViewComponent, ProjectAction::ViewWorker]
+            }
+            ProjectPolicyPreset::Operator => vec![
+                ProjectAction::ViewComponent,
+                ProjectAction::ViewWorker,
+                ProjectAction::CreateWorker,
+                ProjectAction::UpdateWorker,
+                ProjectAction::DeleteWorker,
+            ],
+            ProjectPolicyPreset::Developer => vec![
+                ProjectAction::ViewComponent,
+                ProjectAction::CreateComponent,
+                ProjectAction::UpdateComponent,
+                ProjectAction::ViewWorker,
+                ProjectAction::CreateWorker,
+                ProjectAction::UpdateWorker,
+                ProjectAction::DeleteWorker,
+            ],
+            ProjectPolicyPreset::Admin => ProjectAction::iter().collect(),
+        }
+    }
+}
+
+impl Display for ProjectPolicyPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let preset = match self {
+            ProjectPolicyPreset::Viewer => "viewer",
+            ProjectPolicyPreset::Operator => "operator",
+            ProjectPolicyPreset::Developer => "developer",
+            ProjectPolicyPreset::Admin => "admin",
+        };
+        write!(f, "{preset}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::project::{ProjectTeam, ProjectTeamAccount, ProjectTeamPolicy};
+    use crate::model::{ProjectAction, ProjectPolicyId};
+    use assert2::{check, let_assert};
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn team_file_policies() {
+        let policy_id = Uuid::new_v4();
+        let team: ProjectTeam = serde_yaml::from_str(&format!(
+            r#"
+policies:
+  support: [ViewWorker, update-worker]
+members:
+  - account: alice@example.com
+    policy: developer
+  - account: 4f2c0f5e-account
+    policy: support
+  - account: bob@example.com
+    policy: developer
+  - account: carol@example.com
+    policy: {policy_id}
+"#
+        ))
+        .unwrap();
+
+        check!(
+            team.members[0].account() == ProjectTeamAccount::Email("alice@example.com".to_string())
+        );
+        check!(
+            team.members[1].account()
+                == ProjectTeamAccount::Id("4f2c0f5e-account".to_string().into())
+        );
+
+        let policies = team.policies_to_grant().unwrap();
+        check!(policies.len() == 3);
+        let_assert!(ProjectTeamPolicy::New { name, actions } = &policies[0].1);
+        check!(name == "developer");
+        check!(actions.contains(&ProjectAction::CreateComponent));
+        check!(
+            policies[1].1
+                == ProjectTeamPolicy::New {
+                    name: "support".to_string(),
+                    actions: vec![ProjectAction::ViewWorker, ProjectAction::UpdateWorker],
+                }
+        );
+        check!(policies[2].1 == ProjectTeamPolicy::Existing(ProjectPolicyId(policy_id)));
+
+        let team: ProjectTeam = serde_yaml::from_str(
+            r#"
+members:
+  - account: alice@example.com
+    policy: unknown
+"#,
+        )
+        .unwrap();
+        check!(team.policies_to_grant().is_err());
+    }
+}
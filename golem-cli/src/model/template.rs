@@ -86,6 +86,29 @@ impl<C: Serialize> Template<C> for app_raw::ExternalCommand {
     }
 }
 
+impl<C: Serialize> Template<C> for app_raw::CustomCommand {
+    type Rendered = app_raw::CustomCommand;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        Ok(match self {
+            app_raw::CustomCommand::Steps(steps) => {
+                app_raw::CustomCommand::Steps(steps.render(env, ctx)?)
+            }
+            app_raw::CustomCommand::WithParams(command) => {
+                app_raw::CustomCommand::WithParams(app_raw::CustomCommandWithParams {
+                    params: command.params.clone(),
+                    env: command.env.render(env, ctx)?,
+                    steps: command.steps.render(env, ctx)?,
+                })
+            }
+        })
+    }
+}
+
 impl<C: Serialize> Template<C> for serde_json::Value {
     type Rendered = serde_json::Value;
 
@@ -153,6 +176,9 @@ impl<C: Serialize> Template<C> for app_raw::ComponentProperties {
             clean: self.clean.render(env, ctx)?,
             component_type: self.component_type,
             files: self.files.clone(),
+            client_borrow_mode: self.client_borrow_mode,
+            client_derives: self.client_derives.render(env, ctx)?,
+            env: self.env.render(env, ctx)?,
         })
     }
 }
@@ -14,7 +14,9 @@
 
 pub mod fmt {
     use crate::fuzzy::Match;
-    use crate::log::{log_warn_action, logln, LogColorize, LogIndent};
+    use crate::log::{
+        ascii_only, capture_output, log_warn_action, logln, CapturedOutput, LogColorize, LogIndent,
+    };
     use crate::model::{Format, WorkerNameMatch};
     use cli_table::{Row, Title, WithTitle};
     use colored::control::SHOULD_COLORIZE;
@@ -26,6 +28,51 @@ pub mod fmt {
 
     pub trait TextView {
         fn log(&self);
+
+        /// Rows for CSV output, only tabular views have one
+        fn csv(&self) -> Option<CsvTable> {
+            None
+        }
+    }
+
+    /// Header and rows of a tabular view, cells are plain (uncolored) values
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CsvTable {
+        pub header: Vec<String>,
+        pub rows: Vec<Vec<String>>,
+    }
+
+    /// Column mapping of a table element for CSV output
+    pub trait CsvRow {
+        fn csv_header() -> Vec<&'static str>;
+        fn csv_row(&self) -> Vec<String>;
+    }
+
+    pub fn csv_table<E: CsvRow>(elements: &[E]) -> CsvTable {
+        CsvTable {
+            header: E::csv_header()
+                .into_iter()
+                .map(|column| column.to_string())
+                .collect(),
+            rows: elements.iter().map(|element| element.csv_row()).collect(),
+        }
+    }
+
+    /// Formats the table as RFC 4180 CSV, with a header line
+    pub fn format_csv(table: &CsvTable) -> String {
+        std::iter::once(&table.header)
+            .chain(table.rows.iter())
+            .map(|row| row.iter().map(|cell| escape_csv_cell(cell)).join(","))
+            .map(|line| format!("{line}\r\n"))
+            .collect()
+    }
+
+    fn escape_csv_cell(cell: &str) -> String {
+        if cell.contains([',', '"', '\r', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
     }
 
     pub trait MessageWithFields {
@@ -318,6 +365,7 @@ pub mod fmt {
     }
 
     pub fn log_error<S: AsRef<str>>(message: S) {
+        capture_output(|| CapturedOutput::Error(message.as_ref().to_string()));
         logln(format!(
             "{} {}",
             "error:".log_color_error(),
@@ -356,15 +404,19 @@ pub mod fmt {
     impl NestedTextViewIndent {
         pub fn new(format: Format) -> Self {
             match format {
-                Format::Json | Format::Yaml => Self {
+                Format::Json | Format::Yaml | Format::Csv => Self {
                     format,
                     log_indent: Some(LogIndent::new()),
                 },
                 Format::Text => {
-                    logln("╔═");
+                    logln(if ascii_only() { "+=" } else { "╔═" });
                     Self {
                         format,
-                        log_indent: Some(LogIndent::prefix("║ ")),
+                        log_indent: Some(LogIndent::prefix(if ascii_only() {
+                            "| "
+                        } else {
+                            "║ "
+                        })),
                     }
                 }
             }
@@ -376,10 +428,10 @@ pub mod fmt {
             if let Some(ident) = self.log_indent.take() {
                 drop(ident);
                 match self.format {
-                    Format::Json | Format::Yaml => {
+                    Format::Json | Format::Yaml | Format::Csv => {
                         // NOP
                     }
-                    Format::Text => logln("╚═"),
+                    Format::Text => logln(if ascii_only() { "+=" } else { "╚═" }),
                 }
             }
         }
@@ -462,10 +514,16 @@ pub mod api_security {
 }
 
 pub mod api_definition {
+    use crate::log::{logln, LogColorize};
+    use crate::model::api_definition::{
+        ApiDefinitionDiff, ApiDefinitionLintFinding, ApiDefinitionLintResult,
+        ApiDefinitionLintSeverity, ApiRouteChange, ApiRouteDiff, ApiRouteTryResult,
+    };
     use crate::model::text::fmt::*;
     use crate::model::ComponentName;
     use cli_table::{format::Justify, Table};
     use golem_client::model::{HttpApiDefinitionResponseData, RouteResponseData};
+    use std::collections::BTreeMap;
 
     use serde::{Deserialize, Serialize};
 
@@ -601,10 +659,214 @@ pub mod api_definition {
         }
     }
 
+    impl CsvRow for HttpApiDefinitionResponseData {
+        fn csv_header() -> Vec<&'static str> {
+            vec!["id", "version", "route_count", "draft"]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            vec![
+                self.id.to_string(),
+                self.version.to_string(),
+                self.routes.len().to_string(),
+                self.draft.to_string(),
+            ]
+        }
+    }
+
     impl TextView for Vec<HttpApiDefinitionResponseData> {
         fn log(&self) {
             log_table::<_, HttpApiDefinitionTableView>(self);
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(self))
+        }
+    }
+
+    #[derive(Table)]
+    struct ApiDefinitionLintFindingTableView {
+        #[table(title = "Severity")]
+        pub severity: String,
+        #[table(title = "Rule")]
+        pub rule: String,
+        #[table(title = "Route")]
+        pub route: String,
+        #[table(title = "Message")]
+        pub message: String,
+    }
+
+    impl From<&ApiDefinitionLintFinding> for ApiDefinitionLintFindingTableView {
+        fn from(value: &ApiDefinitionLintFinding) -> Self {
+            Self {
+                severity: match value.severity {
+                    ApiDefinitionLintSeverity::Error => {
+                        value.severity.to_string().log_color_error().to_string()
+                    }
+                    ApiDefinitionLintSeverity::Warning => {
+                        value.severity.to_string().log_color_warn().to_string()
+                    }
+                },
+                rule: value.rule.clone(),
+                route: value.route.clone().unwrap_or_default(),
+                message: value.message.clone(),
+            }
+        }
+    }
+
+    impl TextView for ApiDefinitionLintResult {
+        fn log(&self) {
+            let definition = match (&self.id, &self.version) {
+                (Some(id), Some(version)) => format!(
+                    "API definition {} version {}",
+                    format_message_highlight(id),
+                    format_message_highlight(version)
+                ),
+                _ => "API definition".to_string(),
+            };
+
+            if self.findings.is_empty() {
+                logln(format!("No problems found in {}", definition));
+                return;
+            }
+
+            logln(format!(
+                "Found {} error(s) and {} warning(s) in {}",
+                self.error_count(),
+                self.warning_count(),
+                definition
+            ));
+            log_table::<_, ApiDefinitionLintFindingTableView>(&self.findings);
+        }
+    }
+
+    #[derive(Table)]
+    struct ApiRouteDiffTableView {
+        #[table(title = "Change")]
+        pub change: String,
+        #[table(title = "Method")]
+        pub method: String,
+        #[table(title = "Path")]
+        pub path: String,
+        #[table(title = "Changed fields")]
+        pub fields: String,
+    }
+
+    impl From<&ApiRouteDiff> for ApiRouteDiffTableView {
+        fn from(value: &ApiRouteDiff) -> Self {
+            Self {
+                change: match value.change {
+                    ApiRouteChange::Added => value
+                        .change
+                        .to_string()
+                        .log_color_ok_highlight()
+                        .to_string(),
+                    ApiRouteChange::Removed => {
+                        value.change.to_string().log_color_error().to_string()
+                    }
+                    ApiRouteChange::Changed => {
+                        value.change.to_string().log_color_warn().to_string()
+                    }
+                },
+                method: value.method.clone(),
+                path: value.path.clone(),
+                fields: value.fields.join("\n"),
+            }
+        }
+    }
+
+    impl TextView for ApiDefinitionDiff {
+        fn log(&self) {
+            let versions = format!(
+                "API definition {} versions {} and {}",
+                format_message_highlight(&self.id),
+                format_message_highlight(&self.from_version),
+                format_message_highlight(&self.to_version)
+            );
+
+            if self.routes.is_empty() {
+                logln(format!("No route changes between {}", versions));
+                return;
+            }
+
+            logln(format!(
+                "Found {} route change(s) between {}",
+                self.routes.len(),
+                versions
+            ));
+            log_table::<_, ApiRouteDiffTableView>(&self.routes);
+        }
+    }
+
+    fn format_params(params: &BTreeMap<String, String>) -> String {
+        params
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    impl MessageWithFields for ApiRouteTryResult {
+        fn message(&self) -> String {
+            format!(
+                "Matched route {} {}",
+                format_message_highlight(&self.method),
+                format_message_highlight(&self.path),
+            )
+        }
+
+        fn fields(&self) -> Vec<(String, String)> {
+            let mut fields = FieldsBuilder::new();
+
+            fields
+                .field("Binding type", &self.binding_type)
+                .fmt_field_option("Component", &self.component_name, |name| {
+                    match self.component_version {
+                        Some(version) => format!("{}@{}", format_main_id(name), version),
+                        None => format_main_id(name),
+                    }
+                })
+                .fmt_field_option("Worker name", &self.worker_name, |script| {
+                    script.to_string()
+                })
+                .fmt_field_option("Idempotency key", &self.idempotency_key, |script| {
+                    script.to_string()
+                })
+                .fmt_field_optional(
+                    "Functions",
+                    self.functions.as_slice(),
+                    !self.functions.is_empty(),
+                    |functions| functions.join("\n"),
+                )
+                .fmt_field_optional(
+                    "Path parameters",
+                    &self.path_params,
+                    !self.path_params.is_empty(),
+                    format_params,
+                )
+                .fmt_field_optional(
+                    "Query parameters",
+                    &self.query_params,
+                    !self.query_params.is_empty(),
+                    format_params,
+                )
+                .fmt_field_optional(
+                    "Headers",
+                    &self.headers,
+                    !self.headers.is_empty(),
+                    format_params,
+                )
+                .fmt_field_option("Body", &self.body, |body| body.to_string())
+                .fmt_field_option("Response", &self.response, |script| script.to_string())
+                .fmt_field_optional(
+                    "Unresolved",
+                    self.unresolved.as_slice(),
+                    !self.unresolved.is_empty(),
+                    |unresolved| unresolved.join("\n").log_color_warn().to_string(),
+                );
+
+            fields.build()
+        }
     }
 }
 
@@ -659,30 +921,57 @@ pub mod api_deployment {
         }
     }
 
+    impl CsvRow for (&ApiDeployment, &ApiDefinitionInfo) {
+        fn csv_header() -> Vec<&'static str> {
+            vec!["site", "definition_id", "version"]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            let (deployment, def) = self;
+            vec![
+                format_site(deployment),
+                def.id.to_string(),
+                def.version.to_string(),
+            ]
+        }
+    }
+
+    fn deployed_definitions(
+        deployments: &[ApiDeployment],
+    ) -> Vec<(&ApiDeployment, &ApiDefinitionInfo)> {
+        deployments
+            .iter()
+            .flat_map(|deployment| {
+                deployment
+                    .api_definitions
+                    .iter()
+                    .map(move |def| (deployment, def))
+            })
+            .collect()
+    }
+
     impl TextView for Vec<ApiDeployment> {
         fn log(&self) {
-            log_table::<_, ApiDeploymentTableView>(
-                self.iter()
-                    .flat_map(|deployment| {
-                        deployment
-                            .api_definitions
-                            .iter()
-                            .map(move |def| (deployment, def))
-                    })
-                    .collect::<Vec<_>>()
-                    .as_slice(),
-            );
+            log_table::<_, ApiDeploymentTableView>(deployed_definitions(self).as_slice());
+        }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(deployed_definitions(self).as_slice()))
         }
     }
 }
 
 pub mod component {
+    use crate::log::logln;
     use crate::model::component::ComponentView;
+    use crate::model::deploy::{ComponentDrift, PropertyDrift};
     use crate::model::text::fmt::*;
-    use crate::model::ComponentName;
+    use crate::model::{ComponentName, ImportCategory};
     use cli_table::{format::Justify, Table};
-
+    use colored::Colorize;
+    use itertools::Itertools;
     use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
 
     // TODO: review columns and formats
     #[derive(Table)]
@@ -695,6 +984,8 @@ pub mod component {
         pub component_size: u64,
         #[table(title = "Exports count", justify = "Justify::Right")]
         pub n_exports: usize,
+        #[table(title = "Note")]
+        pub note: String,
     }
 
     impl From<&ComponentView> for ComponentTableView {
@@ -704,14 +995,61 @@ pub mod component {
                 component_version: value.component_version,
                 component_size: value.component_size,
                 n_exports: value.exports.len(),
+                note: format_version_note(value.note.as_deref(), &value.metadata),
             }
         }
     }
 
+    fn format_version_note(note: Option<&str>, metadata: &BTreeMap<String, String>) -> String {
+        let metadata = metadata
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .join(", ");
+        match (note, metadata.is_empty()) {
+            (Some(note), true) => note.to_string(),
+            (Some(note), false) => format!("{} ({})", note, metadata),
+            (None, _) => metadata,
+        }
+    }
+
+    impl CsvRow for ComponentView {
+        fn csv_header() -> Vec<&'static str> {
+            vec![
+                "component_name",
+                "component_id",
+                "component_version",
+                "component_type",
+                "component_size",
+                "exports_count",
+                "created_at",
+                "note",
+            ]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            vec![
+                self.component_name.to_string(),
+                self.component_id.to_string(),
+                self.component_version.to_string(),
+                self.component_type.to_string(),
+                self.component_size.to_string(),
+                self.exports.len().to_string(),
+                self.created_at
+                    .map(|created_at| created_at.to_string())
+                    .unwrap_or_default(),
+                format_version_note(self.note.as_deref(), &self.metadata),
+            ]
+        }
+    }
+
     impl TextView for Vec<ComponentView> {
         fn log(&self) {
             log_table::<_, ComponentTableView>(self.as_slice())
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(self.as_slice()))
+        }
     }
 
     fn component_view_fields(view: &ComponentView) -> Vec<(String, String)> {
@@ -737,7 +1075,13 @@ pub mod component {
                 view.files.as_slice(),
                 !view.files.is_empty(),
                 format_ifs_entry,
-            );
+            )
+            .fmt_field_option("Note", &view.note, |n| n.to_string())
+            .fmt_field_optional("Metadata", &view.metadata, !view.metadata.is_empty(), |m| {
+                m.iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .join("\n")
+            });
 
         fields.build()
     }
@@ -798,13 +1142,146 @@ pub mod component {
             true
         }
     }
+
+    /// JSON Schema of a component function, printed as pretty JSON in text mode too
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct FunctionSchemaView(pub serde_json::Value);
+
+    impl TextView for FunctionSchemaView {
+        fn log(&self) {
+            logln(serde_json::to_string_pretty(&self.0).unwrap());
+        }
+    }
+
+    #[derive(Table)]
+    struct PropertyDriftTableView {
+        #[table(title = "Property")]
+        pub property: String,
+        #[table(title = "Deployed")]
+        pub deployed: String,
+        #[table(title = "Manifest")]
+        pub manifest: String,
+    }
+
+    impl From<&PropertyDrift> for PropertyDriftTableView {
+        fn from(value: &PropertyDrift) -> Self {
+            Self {
+                property: value.property.clone(),
+                deployed: value.deployed.clone().unwrap_or_else(|| "-".to_string()),
+                manifest: value.manifest.clone().unwrap_or_else(|| "-".to_string()),
+            }
+        }
+    }
+
+    /// Drift report of the selected components, compared to the application manifest
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ComponentDriftView(pub Vec<ComponentDrift>);
+
+    impl TextView for ComponentDriftView {
+        fn log(&self) {
+            for drift in &self.0 {
+                match drift.deployed_version {
+                    None => logln(format!(
+                        "Component {} is not deployed yet",
+                        format_message_highlight(&drift.component_name)
+                    )),
+                    Some(version) if drift.changes.is_empty() => logln(format!(
+                        "Component {} version {} is up to date with the manifest",
+                        format_message_highlight(&drift.component_name),
+                        format_id(&version)
+                    )),
+                    Some(version) => {
+                        logln(format!(
+                            "Component {} version {} differs from the manifest:",
+                            format_message_highlight(&drift.component_name),
+                            format_id(&version)
+                        ));
+                        log_table::<_, PropertyDriftTableView>(&drift.changes);
+                    }
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct InspectedImport {
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub category: Option<ImportCategory>,
+    }
+
+    /// Imports and exports of a local component WASM, with the result of the import audit
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ComponentInspectView {
+        pub component_wasm: String,
+        pub imports: Vec<InspectedImport>,
+        pub exports: Vec<String>,
+        pub denied: Vec<ImportCategory>,
+    }
+
+    impl TextView for ComponentInspectView {
+        fn log(&self) {
+            logln(format!(
+                "Inspected component {}",
+                format_message_highlight(&self.component_wasm)
+            ));
+
+            logln("");
+            logln("Imports:".bold().to_string());
+            for import in &self.imports {
+                match import.category {
+                    Some(category) => logln(format!(
+                        "  - {} {}",
+                        import.name,
+                        format!("[{category}]").yellow()
+                    )),
+                    None => logln(format!("  - {}", import.name)),
+                }
+            }
+
+            logln("");
+            logln("Exports:".bold().to_string());
+            for export in &self.exports {
+                logln(format!("  - {}", export));
+            }
+
+            let sensitive = self
+                .imports
+                .iter()
+                .filter_map(|import| import.category.map(|category| (category, &import.name)))
+                .into_group_map();
+
+            logln("");
+            logln("Security audit:".bold().to_string());
+            if sensitive.is_empty() {
+                logln(format!("  {}", "No sensitive imports".green()));
+            }
+            for (category, imports) in sensitive.iter().sorted_by_key(|(category, _)| **category) {
+                let category_label = if self.denied.contains(category) {
+                    format!("{category} (denied)").red().bold()
+                } else {
+                    category.to_string().yellow()
+                };
+                logln(format!(
+                    "  {}: {}",
+                    category_label,
+                    imports.iter().join(", ")
+                ));
+            }
+        }
+    }
 }
 
 pub mod template {
+    use crate::log::logln;
     use crate::model::text::fmt::*;
-    use crate::model::TemplateDescription;
+    use crate::model::{TemplateDescription, TemplateFiles};
     use cli_table::Table;
+    use colored::Colorize;
     use golem_templates::model::{GuestLanguage, GuestLanguageTier, TemplateName};
+    use std::path::PathBuf;
 
     #[derive(Table)]
     pub struct TemplateDescriptionTableView {
@@ -816,6 +1293,8 @@ pub mod template {
         pub tier: GuestLanguageTier,
         #[table(title = "Description")]
         pub description: String,
+        #[table(title = "Required tools")]
+        pub required_tools: String,
     }
 
     impl From<&TemplateDescription> for TemplateDescriptionTableView {
@@ -825,6 +1304,7 @@ pub mod template {
                 language: value.language,
                 tier: value.tier.clone(),
                 description: textwrap::wrap(&value.description, 30).join("\n"),
+                required_tools: value.required_tools.join("\n"),
             }
         }
     }
@@ -834,14 +1314,254 @@ pub mod template {
             log_table::<_, TemplateDescriptionTableView>(self);
         }
     }
+
+    impl TextView for TemplateFiles {
+        fn log(&self) {
+            logln(format!(
+                "Files of template {}:",
+                format_main_id(&self.template)
+            ));
+            for line in file_tree_lines(&self.files) {
+                logln(line);
+            }
+        }
+    }
+
+    /// Renders sorted relative file paths as an indented tree, directories are suffixed with '/'
+    fn file_tree_lines(files: &[PathBuf]) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut current_dirs = Vec::<String>::new();
+
+        for file in files {
+            let mut components = file
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>();
+            let Some(file_name) = components.pop() else {
+                continue;
+            };
+
+            let common = current_dirs
+                .iter()
+                .zip(&components)
+                .take_while(|(a, b)| a == b)
+                .count();
+            current_dirs.truncate(common);
+            for dir in &components[common..] {
+                lines.push(format!(
+                    "{}{}/",
+                    "  ".repeat(current_dirs.len()),
+                    dir.bold()
+                ));
+                current_dirs.push(dir.clone());
+            }
+
+            lines.push(format!("{}{}", "  ".repeat(current_dirs.len()), file_name));
+        }
+
+        lines
+    }
+}
+
+pub mod cache {
+    use crate::model::text::fmt::*;
+    use serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CacheStatsView {
+        pub name_cache_path: PathBuf,
+        pub name_cache_entries: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub stub_cache_dir: Option<PathBuf>,
+        pub stub_cache_entries: usize,
+        pub stub_cache_size: u64,
+    }
+
+    impl MessageWithFields for CacheStatsView {
+        fn message(&self) -> String {
+            "Local caches".to_string()
+        }
+
+        fn fields(&self) -> Vec<(String, String)> {
+            let mut fields = FieldsBuilder::new();
+
+            fields
+                .fmt_field("Name cache", &self.name_cache_path, |p| {
+                    p.display().to_string()
+                })
+                .field("Name cache entries", &self.name_cache_entries)
+                .fmt_field_option("Stub cache", &self.stub_cache_dir, |p| {
+                    p.display().to_string()
+                })
+                .field("Stub cache entries", &self.stub_cache_entries)
+                .fmt_field("Stub cache size", &self.stub_cache_size, format_binary_size);
+
+            fields.build()
+        }
+    }
+}
+
+pub mod history {
+    use crate::command_name;
+    use crate::history::HistoryEntry;
+    use crate::model::text::fmt::*;
+    use chrono::{DateTime, Utc};
+    use cli_table::Table;
+    use colored::Colorize;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HistoryEntryView {
+        pub index: usize,
+        #[serde(flatten)]
+        pub entry: HistoryEntry,
+    }
+
+    impl HistoryEntryView {
+        fn command_line(&self) -> String {
+            let args = std::iter::once(command_name())
+                .chain(self.entry.args.iter().cloned())
+                .collect::<Vec<_>>();
+            shlex::try_join(args.iter().map(|arg| arg.as_str())).unwrap_or_else(|_| args.join(" "))
+        }
+
+        fn result(&self) -> String {
+            if self.entry.started {
+                "started".yellow().to_string()
+            } else if self.entry.success {
+                "success".green().to_string()
+            } else {
+                "failure".red().to_string()
+            }
+        }
+    }
+
+    impl MessageWithFields for HistoryEntryView {
+        fn message(&self) -> String {
+            format!(
+                "History entry {}: {}",
+                format_main_id(&self.index),
+                format_message_highlight(&self.entry.command)
+            )
+        }
+
+        fn fields(&self) -> Vec<(String, String)> {
+            let mut fields = FieldsBuilder::new();
+
+            fields
+                .field("Timestamp", &self.entry.timestamp)
+                .fmt_field("Profile", &self.entry.profile, format_id)
+                .fmt_field_option("User", &self.entry.user, format_id)
+                .fmt_field_option("Working directory", &self.entry.working_dir, |dir| {
+                    dir.display().to_string()
+                })
+                .fmt_field_option(
+                    "Impersonated account",
+                    &self.entry.impersonated_account_id,
+                    |account_id| format_id(&account_id.0),
+                )
+                .field("Result", &self.result())
+                .fmt_field("Command", &self.command_line(), |command| {
+                    command.cyan().to_string()
+                });
+
+            fields.build()
+        }
+    }
+
+    #[derive(Table)]
+    struct HistoryEntryTableView {
+        #[table(title = "Index")]
+        pub index: usize,
+        #[table(title = "Timestamp")]
+        pub timestamp: DateTime<Utc>,
+        #[table(title = "Profile")]
+        pub profile: String,
+        #[table(title = "Result")]
+        pub result: String,
+        #[table(title = "Command")]
+        pub command: String,
+    }
+
+    impl From<&HistoryEntryView> for HistoryEntryTableView {
+        fn from(value: &HistoryEntryView) -> Self {
+            HistoryEntryTableView {
+                index: value.index,
+                timestamp: value.entry.timestamp,
+                profile: value.entry.profile.to_string(),
+                result: value.result(),
+                command: value.entry.command.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct HistoryListView(pub Vec<HistoryEntryView>);
+
+    impl TextView for HistoryListView {
+        fn log(&self) {
+            log_table::<_, HistoryEntryTableView>(&self.0);
+        }
+    }
+}
+
+pub mod alias {
+    use crate::log::logln;
+    use crate::model::text::fmt::*;
+    use cli_table::Table;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct AliasView {
+        pub name: String,
+        pub command: String,
+    }
+
+    #[derive(Table)]
+    struct AliasTableView {
+        #[table(title = "Alias")]
+        pub name: String,
+        #[table(title = "Command")]
+        pub command: String,
+    }
+
+    impl From<&AliasView> for AliasTableView {
+        fn from(value: &AliasView) -> Self {
+            AliasTableView {
+                name: value.name.clone(),
+                command: value.command.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct AliasListView(pub Vec<AliasView>);
+
+    impl TextView for AliasListView {
+        fn log(&self) {
+            if self.0.is_empty() {
+                logln("No aliases are defined.");
+            } else {
+                log_table::<_, AliasTableView>(&self.0);
+            }
+        }
+    }
 }
 
 pub mod profile {
-    use crate::config::{ProfileConfig, ProfileKind};
+    use crate::config::{
+        ConfigMigrationResult, ConfigRepairResult, ProfileConfig, ProfileKind, ProfileName,
+    };
     use crate::log::{logln, LogColorize};
     use crate::model::text::fmt::*;
-    use crate::model::ProfileView;
+    use crate::model::{ProfileConfigValueView, ProfileView};
     use colored::Colorize;
+    use serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
 
     impl TextView for Vec<ProfileView> {
         fn log(&self) {
@@ -901,33 +1621,227 @@ pub mod profile {
                 fields.field("Using default URLs", &true);
             }
 
-            fields.build()
+            fields.build()
+        }
+    }
+
+    impl TextView for Vec<ProfileConfigValueView> {
+        fn log(&self) {
+            for entry in self {
+                logln(format!(
+                    "{}: {}",
+                    entry.key.log_color_highlight(),
+                    match &entry.value {
+                        Some(value) => format_message_highlight(value),
+                        None => "-".to_string(),
+                    }
+                ));
+                logln(format!("  {}", entry.description));
+            }
+        }
+    }
+
+    impl TextView for ProfileConfig {
+        fn log(&self) {
+            logln(format!(
+                "Default output format: {}",
+                format_message_highlight(&self.default_format),
+            ));
+            if let Some(max_upload_size) = &self.max_upload_size {
+                logln(format!(
+                    "Max upload size: {}",
+                    format_message_highlight(&format_binary_size(max_upload_size)),
+                ));
+            }
+            if let Some(max_invoke_payload_size) = &self.max_invoke_payload_size {
+                logln(format!(
+                    "Max invoke payload size: {}",
+                    format_message_highlight(&format_binary_size(max_invoke_payload_size)),
+                ));
+            }
+            if self.read_only {
+                logln(format!(
+                    "Read-only: {}",
+                    format_message_highlight(&"enabled")
+                ));
+            }
+            if let Some(default_project) = &self.default_project {
+                logln(format!(
+                    "Default project: {}",
+                    format_message_highlight(&default_project.0),
+                ));
+            }
+            if let Some(timeout_seconds) = &self.timeout_seconds {
+                logln(format!(
+                    "Timeout: {}",
+                    format_message_highlight(&format!("{}s", timeout_seconds)),
+                ));
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ProfileMigrateView {
+        pub config_path: PathBuf,
+        pub from_version: u32,
+        pub to_version: u32,
+        pub applied_migrations: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub backup_path: Option<PathBuf>,
+        pub dry_run: bool,
+    }
+
+    impl ProfileMigrateView {
+        pub fn new(result: ConfigMigrationResult, dry_run: bool) -> Self {
+            Self {
+                config_path: result.config_path,
+                from_version: result.from_version,
+                to_version: result.to_version,
+                applied_migrations: result.applied_migrations,
+                backup_path: result.backup_path,
+                dry_run,
+            }
+        }
+    }
+
+    impl TextView for ProfileMigrateView {
+        fn log(&self) {
+            if self.applied_migrations.is_empty() {
+                logln(format!(
+                    "Config {} is up-to-date, config version: {}",
+                    format_message_highlight(&self.config_path.display()),
+                    format_id(&self.to_version),
+                ));
+                return;
+            }
+
+            logln(format!(
+                "{} config {} from version {} to {}",
+                if self.dry_run {
+                    "Would migrate"
+                } else {
+                    "Migrated"
+                },
+                format_message_highlight(&self.config_path.display()),
+                format_id(&self.from_version),
+                format_id(&self.to_version),
+            ));
+            logln("Applied migrations:".log_color_help_group().to_string());
+            for migration in &self.applied_migrations {
+                logln(format!("  - {}", migration));
+            }
+            if let Some(backup_path) = &self.backup_path {
+                logln(format!(
+                    "Backup of the original config: {}",
+                    format_message_highlight(&backup_path.display()),
+                ));
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DroppedProfileView {
+        pub name: ProfileName,
+        pub reason: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ProfileRepairView {
+        pub config_path: PathBuf,
+        pub valid: bool,
+        pub salvaged_profiles: Vec<ProfileName>,
+        pub dropped_profiles: Vec<DroppedProfileView>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub backup_path: Option<PathBuf>,
+        pub dry_run: bool,
+    }
+
+    impl ProfileRepairView {
+        pub fn new(result: ConfigRepairResult, dry_run: bool) -> Self {
+            Self {
+                config_path: result.config_path,
+                valid: result.valid,
+                salvaged_profiles: result.salvaged_profiles,
+                dropped_profiles: result
+                    .dropped_profiles
+                    .into_iter()
+                    .map(|profile| DroppedProfileView {
+                        name: profile.name,
+                        reason: profile.reason,
+                    })
+                    .collect(),
+                backup_path: result.backup_path,
+                dry_run,
+            }
         }
     }
 
-    impl TextView for ProfileConfig {
+    impl TextView for ProfileRepairView {
         fn log(&self) {
+            if self.valid {
+                logln(format!(
+                    "Config {} is valid, no repair needed",
+                    format_message_highlight(&self.config_path.display()),
+                ));
+                return;
+            }
+
             logln(format!(
-                "Default output format: {}",
-                format_message_highlight(&self.default_format),
-            ))
+                "{} config {}",
+                if self.dry_run {
+                    "Would repair"
+                } else {
+                    "Repaired"
+                },
+                format_message_highlight(&self.config_path.display()),
+            ));
+            logln("Kept profiles:".log_color_help_group().to_string());
+            for profile_name in &self.salvaged_profiles {
+                logln(format!("  - {}", format_id(profile_name)));
+            }
+            if !self.dropped_profiles.is_empty() {
+                logln("Dropped profiles:".log_color_help_group().to_string());
+                for profile in &self.dropped_profiles {
+                    logln(format!(
+                        "  - {}: {}",
+                        format_id(&profile.name),
+                        profile.reason.log_color_warn()
+                    ));
+                }
+            }
+            if let Some(backup_path) = &self.backup_path {
+                logln(format!(
+                    "Backup of the invalid config: {}",
+                    format_message_highlight(&backup_path.display()),
+                ));
+            }
         }
     }
 }
 
 pub mod worker {
+    use crate::command_name;
     use crate::log::{logln, LogColorize};
-    use crate::model::deploy::TryUpdateAllWorkersResult;
+    use crate::model::deploy::{
+        TryUpdateAllWorkersResult, WorkerUpdateAttempt, WorkerUpdateRolloutResult,
+    };
+    use crate::model::invocation_import::ImportedInvocation;
     use crate::model::invoke_result_view::InvokeResultView;
     use crate::model::text::fmt::*;
+    use crate::model::trace::{rpc_call, RpcCallKind};
     use crate::model::{
-        ComponentName, IdempotencyKey, WorkerMetadata, WorkerMetadataView, WorkerName,
-        WorkersMetadataResponseView,
+        ComponentName, IdempotencyKey, WorkerLabel, WorkerListColumn, WorkerMetadata,
+        WorkerMetadataView, WorkerName, WorkersMetadataResponseView,
     };
     use base64::prelude::BASE64_STANDARD;
     use base64::Engine;
     use chrono::{DateTime, Utc};
-    use cli_table::{format::Justify, Table};
+    use cli_table::{format::Justify, Cell, Style, Table};
     use colored::Colorize;
     use golem_client::model::PublicOplogEntry;
     use golem_common::model::public_oplog::{
@@ -1002,12 +1916,14 @@ pub mod worker {
         }
 
         fn fields(&self) -> Vec<(String, String)> {
+            let labels = WorkerLabel::from_env(&self.0.env);
             let mut fields = FieldsBuilder::new();
 
             fields
                 .fmt_field("Component name", &self.0.component_name, format_id)
                 .fmt_field("Component version", &self.0.component_version, format_id)
                 .fmt_field("Worker name", &self.0.worker_name, format_main_id)
+                .fmt_field("Worker URN", &self.0.worker_urn, format_id)
                 .field("Created at", &self.0.created_at)
                 .fmt_field("Component size", &self.0.component_size, format_binary_size)
                 .fmt_field(
@@ -1028,6 +1944,12 @@ pub mod worker {
                             .join(";")
                     },
                 )
+                .fmt_field_optional("Labels", &labels, !labels.is_empty(), |labels| {
+                    labels
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v.bold()))
+                        .join(";")
+                })
                 .fmt_field("Status", &self.0.status, format_status)
                 .fmt_field("Retry count", &self.0.retry_count, format_retry_count)
                 .fmt_field_optional(
@@ -1076,7 +1998,33 @@ pub mod worker {
 
     impl TextView for WorkersMetadataResponseView {
         fn log(&self) {
-            log_table::<_, WorkerMetadataTableView>(&self.workers);
+            if self.columns.is_empty() {
+                log_table::<_, WorkerMetadataTableView>(&self.workers);
+            } else {
+                let rows = self
+                    .workers
+                    .iter()
+                    .map(|worker| {
+                        self.columns
+                            .iter()
+                            .map(|column| column.value(worker).cell())
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                let title = self
+                    .columns
+                    .iter()
+                    .map(|column| column.to_string().cell().bold(true))
+                    .collect::<Vec<_>>();
+
+                logln(format!(
+                    "{}",
+                    rows.table()
+                        .title(title)
+                        .display()
+                        .expect("Failed to display table")
+                ));
+            }
 
             if !self.cursors.is_empty() {
                 logln("");
@@ -1089,6 +2037,45 @@ pub mod worker {
                 ));
             }
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            let columns = if self.columns.is_empty() {
+                vec![
+                    WorkerListColumn::ComponentName,
+                    WorkerListColumn::WorkerName,
+                    WorkerListColumn::ComponentVersion,
+                    WorkerListColumn::Status,
+                    WorkerListColumn::CreatedAt,
+                ]
+            } else {
+                self.columns.clone()
+            };
+
+            Some(CsvTable {
+                header: columns.iter().map(|column| column.to_string()).collect(),
+                rows: self
+                    .workers
+                    .iter()
+                    .map(|worker| columns.iter().map(|column| column.value(worker)).collect())
+                    .collect(),
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WorkerInvokeCurlView {
+        pub method: String,
+        pub url: String,
+        pub idempotency_key: String,
+        pub body: serde_json::Value,
+        pub command: String,
+    }
+
+    impl TextView for WorkerInvokeCurlView {
+        fn log(&self) {
+            logln(&self.command);
+        }
     }
 
     impl TextView for IdempotencyKey {
@@ -1107,9 +2094,53 @@ pub mod worker {
         }
     }
 
+    fn log_worker_update_attempts(title: &str, attempts: &[WorkerUpdateAttempt]) {
+        if attempts.is_empty() {
+            return;
+        }
+
+        logln(format!("{} ({}):", title.bold(), attempts.len()));
+        for attempt in attempts {
+            let worker = format!(
+                "{}/{} to version {}",
+                attempt.component_name.0.blue().bold(),
+                attempt.worker_name.0.green().bold(),
+                attempt.target_version.to_string().log_color_highlight()
+            );
+            match &attempt.error {
+                Some(error) => logln(format!("  - {}: {}", worker, format_error(error))),
+                None => logln(format!("  - {}", worker)),
+            }
+        }
+    }
+
     impl TextView for TryUpdateAllWorkersResult {
         fn log(&self) {
-            // NOP
+            if self.triggered.is_empty() && self.failed.is_empty() {
+                return;
+            }
+
+            logln("");
+            log_worker_update_attempts("Triggered updates", &self.triggered);
+            log_worker_update_attempts("Failed to trigger updates", &self.failed);
+        }
+    }
+
+    impl TextView for WorkerUpdateRolloutResult {
+        fn log(&self) {
+            logln("");
+            if self.stopped {
+                logln(format_warn("Worker update rollout stopped"));
+            } else {
+                logln(format_message_highlight("Worker update rollout finished"));
+            }
+            logln("");
+
+            log_worker_update_attempts("Updated", &self.updated);
+            log_worker_update_attempts("Triggered update", &self.triggered);
+            log_worker_update_attempts("Failed", &self.failed);
+            log_worker_update_attempts("Skipped", &self.skipped);
+            log_worker_update_attempts("Rolled back", &self.rolled_back);
         }
     }
 
@@ -1136,6 +2167,12 @@ pub mod worker {
                     }
                 }
             } else if let Some(json) = &self.result_json {
+                if self.raw {
+                    log_results_format("JSON");
+                    logln(serde_json::to_string_pretty(json).unwrap());
+                    return;
+                }
+
                 logln(format_warn(indoc!(
                     "
                     Failed to convert invocation result to WAVE format.
@@ -1194,6 +2231,39 @@ pub mod worker {
                     }
                 }
                 PublicOplogEntry::ImportedFunctionInvoked(params) => {
+                    if let Some(call) = rpc_call(&params.function_name, &params.request) {
+                        logln(format!(
+                            "{} {}",
+                            format_message_highlight(match call.kind {
+                                RpcCallKind::InvokeAndAwait => "RPC CALL",
+                                RpcCallKind::Invoke => "RPC ENQUEUE",
+                            }),
+                            format_id(&call.function_name),
+                        ));
+                        logln(format!(
+                            "{pad}at:                {}",
+                            format_id(&params.timestamp)
+                        ));
+                        logln(format!(
+                            "{pad}target worker:     {}",
+                            format_id(&format!(
+                                "{}/{}",
+                                call.callee_component_id, call.callee_worker_name
+                            )),
+                        ));
+                        if let Some(idempotency_key) = &call.idempotency_key {
+                            logln(format!(
+                                "{pad}idempotency key:   {}",
+                                format_id(idempotency_key),
+                            ));
+                        }
+                        logln(format!(
+                            "{pad}result:            {}",
+                            value_to_string(&params.response)
+                        ));
+                        return;
+                    }
+
                     logln(format!(
                         "{} {}",
                         format_message_highlight("CALL"),
@@ -1661,12 +2731,44 @@ pub mod worker {
         let tav: TypeAnnotatedValue = value.try_into().expect("Failed to convert value to string");
         print_type_annotated_value(&tav).expect("Failed to convert value to string")
     }
+
+    impl TextView for Vec<ImportedInvocation> {
+        fn log(&self) {
+            if self.is_empty() {
+                logln("No invocations were imported");
+                return;
+            }
+
+            for invocation in self {
+                let args = [
+                    command_name(),
+                    "worker".to_string(),
+                    "invoke".to_string(),
+                    format!("{}/{}", invocation.component_name, invocation.worker_name),
+                    invocation.function_name.clone(),
+                ]
+                .into_iter()
+                .chain(invocation.arguments.iter().cloned())
+                .collect::<Vec<_>>();
+
+                logln(
+                    format!("# {}", invocation.name)
+                        .log_color_help_group()
+                        .to_string(),
+                );
+                logln(
+                    shlex::try_join(args.iter().map(|arg| arg.as_str()))
+                        .unwrap_or_else(|_| args.join(" ")),
+                );
+            }
+        }
+    }
 }
 
 pub mod plugin {
     use crate::model::text::fmt::{
-        format_id, format_main_id, format_message_highlight, log_table, FieldsBuilder,
-        MessageWithFields, TextView,
+        csv_table, format_id, format_main_id, format_message_highlight, log_table, CsvRow,
+        CsvTable, FieldsBuilder, MessageWithFields, TextView,
     };
     use cli_table::Table;
     use golem_client::model::PluginInstallation;
@@ -1703,10 +2805,38 @@ pub mod plugin {
         }
     }
 
+    impl CsvRow for PluginDefinition {
+        fn csv_header() -> Vec<&'static str> {
+            vec![
+                "name",
+                "version",
+                "description",
+                "homepage",
+                "type",
+                "scope",
+            ]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            vec![
+                self.name.clone(),
+                self.version.clone(),
+                self.description.clone(),
+                self.homepage.clone(),
+                self.typ.clone(),
+                self.scope.clone(),
+            ]
+        }
+    }
+
     impl TextView for Vec<PluginDefinition> {
         fn log(&self) {
             log_table::<_, PluginDefinitionTableView>(self.as_slice())
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(self.as_slice()))
+        }
     }
 
     impl MessageWithFields for PluginDefinition {
@@ -1892,6 +3022,18 @@ pub mod help {
 
                     Behaves the same as <COMPONENT>/<WORKER>, except it can refer to components in a
                     specific project owned by another account
+
+                    "
+                ),
+            );
+            fields.field(
+                "urn:worker:<COMPONENT_ID>/<WORKER>",
+                &indoc!(
+                    "
+                    Worker URN, as shown by other commands, e.g. \"worker get\".
+
+                    Refers to the component by its ID, so it can be used without an application
+                    or project context
                     "
                 ),
             );
@@ -2075,6 +3217,7 @@ pub mod help {
 }
 
 pub mod account {
+    use crate::log::logln;
     use crate::model::text::fmt::*;
     use golem_cloud_client::model::{Account, Role};
     use serde::{Deserialize, Serialize};
@@ -2150,6 +3293,60 @@ pub mod account {
             }
         }
     }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RoleCapabilities {
+        pub role: crate::model::Role,
+        pub description: String,
+        pub capabilities: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    pub struct RoleCapabilitiesView(pub Vec<RoleCapabilities>);
+
+    impl RoleCapabilitiesView {
+        pub fn new(roles: Vec<crate::model::Role>) -> Self {
+            Self(
+                roles
+                    .into_iter()
+                    .map(|role| RoleCapabilities {
+                        role,
+                        description: role.description().to_string(),
+                        capabilities: role
+                            .capabilities()
+                            .iter()
+                            .map(|capability| capability.to_string())
+                            .collect(),
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    impl TextView for RoleCapabilitiesView {
+        fn log(&self) {
+            for (idx, role) in self.0.iter().enumerate() {
+                if idx > 0 {
+                    logln("");
+                }
+                logln(format!(
+                    "{}: {}",
+                    format_message_highlight(&role.role),
+                    role.description
+                ));
+                for capability in &role.capabilities {
+                    logln(format!("  - {}", capability));
+                }
+            }
+            logln("");
+            logln(
+                "The capabilities are an informational summary, the permissions enforced by the server are authoritative."
+                    .log_color_help_group()
+                    .to_string(),
+            );
+        }
+    }
 }
 
 pub mod api_domain {
@@ -2287,6 +3484,7 @@ pub mod project {
     use crate::model::project::ProjectView;
     use crate::model::text::fmt::*;
     use cli_table::Table;
+    use colored::Colorize;
     use golem_cloud_client::model::{Project, ProjectGrant, ProjectPolicy, ProjectType};
     use itertools::Itertools;
     use serde::{Deserialize, Serialize};
@@ -2379,7 +3577,35 @@ pub mod project {
 
     impl From<Vec<Project>> for ProjectListView {
         fn from(value: Vec<Project>) -> Self {
-            ProjectListView(value.into_iter().map(|v| v.into()).collect())
+            ProjectListView(
+                value
+                    .into_iter()
+                    .map(ProjectView::from)
+                    .sorted_by(|a, b| a.name.0.cmp(&b.name.0))
+                    .collect(),
+            )
+        }
+    }
+
+    impl CsvRow for ProjectView {
+        fn csv_header() -> Vec<&'static str> {
+            vec![
+                "project_id",
+                "name",
+                "owner_account_id",
+                "default_project",
+                "description",
+            ]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            vec![
+                self.project_id.0.to_string(),
+                self.name.0.clone(),
+                self.owner_account_id.0.clone(),
+                (self.project_type == ProjectType::Default).to_string(),
+                self.description.clone(),
+            ]
         }
     }
 
@@ -2387,6 +3613,10 @@ pub mod project {
         fn log(&self) {
             log_table::<_, ProjectTableView>(&self.0);
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(&self.0))
+        }
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -2410,6 +3640,54 @@ pub mod project {
         }
     }
 
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ProjectGrantOutcome {
+        pub account: String,
+        pub account_id: Option<String>,
+        pub policy: String,
+        pub policy_id: Option<Uuid>,
+        pub grant_id: Option<Uuid>,
+        pub error: Option<String>,
+    }
+
+    #[derive(Table)]
+    struct ProjectGrantOutcomeTableView {
+        #[table(title = "Account")]
+        pub account: String,
+        #[table(title = "Account ID")]
+        pub account_id: String,
+        #[table(title = "Policy")]
+        pub policy: String,
+        #[table(title = "Outcome")]
+        pub outcome: String,
+    }
+
+    impl From<&ProjectGrantOutcome> for ProjectGrantOutcomeTableView {
+        fn from(value: &ProjectGrantOutcome) -> Self {
+            ProjectGrantOutcomeTableView {
+                account: value.account.clone(),
+                account_id: value.account_id.clone().unwrap_or_default(),
+                policy: value.policy.clone(),
+                outcome: match &value.error {
+                    Some(error) => format!("Failed: {}", textwrap::wrap(error, 50).join("\n"))
+                        .red()
+                        .to_string(),
+                    None => "Granted".green().to_string(),
+                },
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct ProjectGrantManyView(pub Vec<ProjectGrantOutcome>);
+
+    impl TextView for ProjectGrantManyView {
+        fn log(&self) {
+            log_table::<_, ProjectGrantOutcomeTableView>(&self.0);
+        }
+    }
+
     fn project_policy_fields(policy: &ProjectPolicy) -> Vec<(String, String)> {
         let mut fields = FieldsBuilder::new();
 
@@ -2527,9 +3805,231 @@ pub mod token {
     #[derive(Debug, Serialize, Deserialize)]
     pub struct TokenListView(pub Vec<Token>);
 
+    impl CsvRow for Token {
+        fn csv_header() -> Vec<&'static str> {
+            vec!["id", "account_id", "created_at", "expires_at"]
+        }
+
+        fn csv_row(&self) -> Vec<String> {
+            vec![
+                self.id.to_string(),
+                self.account_id.to_string(),
+                self.created_at.to_string(),
+                self.expires_at.to_string(),
+            ]
+        }
+    }
+
     impl TextView for TokenListView {
         fn log(&self) {
             log_table::<_, TokenTableView>(&self.0);
         }
+
+        fn csv(&self) -> Option<CsvTable> {
+            Some(csv_table(&self.0))
+        }
+    }
+}
+
+pub mod bench {
+    use crate::log::{logln, LogColorize};
+    use crate::model::bench::InvocationBenchmark;
+    use crate::model::text::fmt::*;
+    use crate::model::{ComponentName, WorkerName};
+    use cli_table::{format::Justify, Table};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct InvocationBenchmarkView {
+        pub component_name: ComponentName,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub worker_name: Option<WorkerName>,
+        pub benchmark: InvocationBenchmark,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub baseline: Option<InvocationBenchmark>,
+    }
+
+    #[derive(Table)]
+    struct InvocationBenchmarkTableView {
+        #[table(title = "Function")]
+        pub function_name: String,
+        #[table(title = "Iterations", justify = "Justify::Right")]
+        pub iterations: usize,
+        #[table(title = "Invocations/s", justify = "Justify::Right")]
+        pub invocations_per_second: String,
+        #[table(title = "Min", justify = "Justify::Right")]
+        pub min: String,
+        #[table(title = "Mean", justify = "Justify::Right")]
+        pub mean: String,
+        #[table(title = "p50", justify = "Justify::Right")]
+        pub p50: String,
+        #[table(title = "p90", justify = "Justify::Right")]
+        pub p90: String,
+        #[table(title = "p99", justify = "Justify::Right")]
+        pub p99: String,
+        #[table(title = "Max", justify = "Justify::Right")]
+        pub max: String,
+    }
+
+    fn format_ms(ms: f64) -> String {
+        format!("{:.2} ms", ms)
+    }
+
+    impl From<&InvocationBenchmark> for InvocationBenchmarkTableView {
+        fn from(value: &InvocationBenchmark) -> Self {
+            InvocationBenchmarkTableView {
+                function_name: value.function_name.clone(),
+                iterations: value.iterations,
+                invocations_per_second: format!("{:.2}", value.invocations_per_second),
+                min: format_ms(value.min_ms),
+                mean: format_ms(value.mean_ms),
+                p50: format_ms(value.p50_ms),
+                p90: format_ms(value.p90_ms),
+                p99: format_ms(value.p99_ms),
+                max: format_ms(value.max_ms),
+            }
+        }
+    }
+
+    impl TextView for InvocationBenchmarkView {
+        fn log(&self) {
+            match &self.worker_name {
+                Some(worker_name) => logln(format!(
+                    "Benchmark results for worker {}/{}",
+                    self.component_name.0.log_color_highlight(),
+                    worker_name.0.log_color_highlight()
+                )),
+                None => logln(format!(
+                    "Benchmark results for ephemeral workers of {}",
+                    self.component_name.0.log_color_highlight(),
+                )),
+            }
+            logln("");
+
+            let benchmarks = std::iter::once(&self.benchmark)
+                .chain(self.baseline.iter())
+                .cloned()
+                .collect::<Vec<_>>();
+            log_table::<_, InvocationBenchmarkTableView>(&benchmarks);
+
+            if let Some(baseline) = &self.baseline {
+                logln("");
+                logln(format!(
+                    "Overhead compared to {}: {} at p50, {} at p99",
+                    baseline.function_name.log_color_highlight(),
+                    format_ms(self.benchmark.p50_ms - baseline.p50_ms).log_color_highlight(),
+                    format_ms(self.benchmark.p99_ms - baseline.p99_ms).log_color_highlight(),
+                ));
+            }
+        }
+    }
+}
+
+pub mod app_graph {
+    use crate::log::{logln, LogColorize};
+    use crate::model::app_graph::AppDependencyGraph;
+    use crate::model::text::fmt::TextView;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AppDependencyGraphView {
+        #[serde(flatten)]
+        pub graph: AppDependencyGraph,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        pub diagram: Option<String>,
+    }
+
+    impl TextView for AppDependencyGraphView {
+        fn log(&self) {
+            if let Some(diagram) = &self.diagram {
+                // Printed as is, so it can be redirected into a diagram file
+                print!("{}", diagram);
+                return;
+            }
+
+            for component in &self.graph.components {
+                if component.external {
+                    logln(format!(
+                        "{} (external)",
+                        component.name.log_color_highlight()
+                    ));
+                } else {
+                    logln(component.name.log_color_highlight().to_string());
+                }
+                for dependency in self.graph.dependencies_of(&component.name) {
+                    let target = if dependency.is_wasm_rpc() {
+                        format!("{} client", dependency.target)
+                    } else {
+                        dependency.target.clone()
+                    };
+                    logln(format!(
+                        "  -> {} ({})",
+                        target.log_color_highlight(),
+                        dependency.label()
+                    ));
+                }
+            }
+
+            let clients = self
+                .graph
+                .components
+                .iter()
+                .filter(|component| component.client_generated)
+                .map(|component| component.name.log_color_highlight().to_string())
+                .collect::<Vec<_>>();
+            if !clients.is_empty() {
+                logln("");
+                logln(format!("Generated clients: {}", clients.join(", ")));
+            }
+        }
+    }
+}
+
+pub mod trace {
+    use crate::model::text::fmt::TextView;
+    use crate::model::trace::CallTrace;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CallTraceView {
+        #[serde(flatten)]
+        pub trace: CallTrace,
+        pub diagram: String,
+    }
+
+    impl TextView for CallTraceView {
+        fn log(&self) {
+            // Printed as is, so it can be redirected into a diagram file
+            print!("{}", self.diagram);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::model::text::fmt::{format_csv, CsvTable};
+
+    #[test]
+    fn csv_cells_are_escaped() {
+        let table = CsvTable {
+            header: vec!["name".to_string(), "note".to_string()],
+            rows: vec![
+                vec!["a".to_string(), "plain".to_string()],
+                vec!["b".to_string(), "with, comma".to_string()],
+                vec!["c".to_string(), "say \"hi\"\nbye".to_string()],
+            ],
+        };
+
+        assert_eq!(
+            format_csv(&table),
+            "name,note\r\na,plain\r\nb,\"with, comma\"\r\nc,\"say \"\"hi\"\"\nbye\"\r\n"
+        );
     }
 }
@@ -0,0 +1,510 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_client::model::PublicOplogEntry;
+use golem_wasm_ast::analysis::{AnalysedType, TypeRecord, TypeTuple, TypeVariant};
+use golem_wasm_rpc::{Value, ValueAndType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write};
+use uuid::Uuid;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum TraceGraphFormat {
+    #[default]
+    Mermaid,
+    Dot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RpcCallKind {
+    /// The caller awaited the result of the remote invocation
+    InvokeAndAwait,
+    /// The remote invocation was only enqueued by the caller
+    Invoke,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceWorker {
+    pub component_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component_name: Option<String>,
+    pub worker_name: String,
+}
+
+impl TraceWorker {
+    fn key(&self) -> (Uuid, &str) {
+        (self.component_id, self.worker_name.as_str())
+    }
+}
+
+impl Display for TraceWorker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.component_name {
+            Some(component_name) => write!(f, "{}/{}", component_name, self.worker_name),
+            None => write!(f, "{}/{}", self.component_id, self.worker_name),
+        }
+    }
+}
+
+/// Worker-to-worker call, made through a WASM RPC stub
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCall {
+    pub caller: TraceWorker,
+    pub caller_function: String,
+    pub callee: TraceWorker,
+    pub function_name: String,
+    pub kind: RpcCallKind,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub idempotency_key: Option<String>,
+}
+
+/// RPC call graph of a single request, reconstructed from the oplogs of the involved workers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTrace {
+    pub entry: TraceWorker,
+    pub entry_function: String,
+    pub entry_idempotency_key: String,
+    pub calls: Vec<TraceCall>,
+}
+
+impl CallTrace {
+    pub fn render(&self, format: TraceGraphFormat) -> String {
+        match format {
+            TraceGraphFormat::Mermaid => self.to_mermaid(),
+            TraceGraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let nodes = self.node_ids();
+
+        let mut diagram = String::from("flowchart LR\n");
+        for (worker, id) in self.workers().iter().zip(0..) {
+            let _ = writeln!(
+                diagram,
+                "    w{}[\"{}\"]",
+                id,
+                mermaid_escape(&worker.to_string())
+            );
+        }
+        let _ = writeln!(
+            diagram,
+            "    request([request]) -->|\"{}\"| w0",
+            mermaid_escape(&self.entry_function)
+        );
+        for (call, idx) in self.calls.iter().zip(1..) {
+            let _ = writeln!(
+                diagram,
+                "    w{} {}|\"{}. {}\"| w{}",
+                nodes[&call.caller.key()],
+                match call.kind {
+                    RpcCallKind::InvokeAndAwait => "-->",
+                    RpcCallKind::Invoke => "-.->",
+                },
+                idx,
+                mermaid_escape(&call.function_name),
+                nodes[&call.callee.key()]
+            );
+        }
+        diagram
+    }
+
+    pub fn to_dot(&self) -> String {
+        let nodes = self.node_ids();
+
+        let mut diagram = String::from("digraph trace {\n    rankdir=LR;\n");
+        let _ = writeln!(diagram, "    request [shape=plaintext, label=\"request\"];");
+        for (worker, id) in self.workers().iter().zip(0..) {
+            let _ = writeln!(
+                diagram,
+                "    w{} [shape=box, label=\"{}\"];",
+                id,
+                dot_escape(&worker.to_string())
+            );
+        }
+        let _ = writeln!(
+            diagram,
+            "    request -> w0 [label=\"{}\"];",
+            dot_escape(&self.entry_function)
+        );
+        for (call, idx) in self.calls.iter().zip(1..) {
+            let _ = writeln!(
+                diagram,
+                "    w{} -> w{} [label=\"{}. {}\"{}];",
+                nodes[&call.caller.key()],
+                nodes[&call.callee.key()],
+                idx,
+                dot_escape(&call.function_name),
+                match call.kind {
+                    RpcCallKind::InvokeAndAwait => "",
+                    RpcCallKind::Invoke => ", style=dashed",
+                }
+            );
+        }
+        diagram.push_str("}\n");
+        diagram
+    }
+
+    /// Involved workers in the order of their first appearance, starting with the entry worker
+    fn workers(&self) -> Vec<&TraceWorker> {
+        let mut workers = vec![&self.entry];
+        for call in &self.calls {
+            for worker in [&call.caller, &call.callee] {
+                if !workers.iter().any(|w| w.key() == worker.key()) {
+                    workers.push(worker);
+                }
+            }
+        }
+        workers
+    }
+
+    fn node_ids(&self) -> HashMap<(Uuid, &str), usize> {
+        self.workers()
+            .into_iter()
+            .zip(0..)
+            .map(|(worker, id)| (worker.key(), id))
+            .collect()
+    }
+}
+
+pub fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+pub fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Outgoing RPC call, as recorded in the caller's oplog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcCall {
+    pub callee_component_id: Uuid,
+    pub callee_worker_name: String,
+    pub function_name: String,
+    pub idempotency_key: Option<String>,
+    pub kind: RpcCallKind,
+}
+
+/// Recognizes RPC calls between imported function calls, based on the recorded request,
+/// which contains the remote worker's ID and the invoked function
+pub fn rpc_call(imported_function_name: &str, request: &ValueAndType) -> Option<RpcCall> {
+    let (worker_id, worker_id_type) = find_field(&request.value, &request.typ, "remote_worker_id")?;
+
+    let callee_component_id = find_field(worker_id, worker_id_type, "component_id")
+        .and_then(|(value, typ)| find_uuid(value, typ))?;
+    let callee_worker_name = find_field(worker_id, worker_id_type, "worker_name")
+        .and_then(|(value, typ)| find_string(value, typ))?;
+    let function_name = find_field(&request.value, &request.typ, "function_name")
+        .and_then(|(value, typ)| find_string(value, typ))?;
+    let idempotency_key = find_field(&request.value, &request.typ, "idempotency_key")
+        .and_then(|(value, typ)| find_string(value, typ));
+
+    Some(RpcCall {
+        callee_component_id,
+        callee_worker_name,
+        function_name,
+        idempotency_key,
+        kind: if imported_function_name.contains("invoke-and-await") {
+            RpcCallKind::InvokeAndAwait
+        } else {
+            RpcCallKind::Invoke
+        },
+    })
+}
+
+/// Oplog entries of a single invocation
+pub struct InvocationEntries<'a> {
+    pub function_name: String,
+    pub idempotency_key: String,
+    pub entries: &'a [(u64, PublicOplogEntry)],
+}
+
+impl InvocationEntries<'_> {
+    pub fn rpc_calls(&self) -> Vec<RpcCall> {
+        self.entries
+            .iter()
+            .filter_map(|(_, entry)| match entry {
+                PublicOplogEntry::ImportedFunctionInvoked(params) => {
+                    rpc_call(&params.function_name, &params.request)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Finds the invocation with the given idempotency key, or the last invocation if no key is
+/// provided, and returns its entries until the invocation is completed
+pub fn find_invocation<'a>(
+    entries: &'a [(u64, PublicOplogEntry)],
+    idempotency_key: Option<&str>,
+) -> Option<InvocationEntries<'a>> {
+    let (start, function_name, key) = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (_, entry))| match entry {
+            PublicOplogEntry::ExportedFunctionInvoked(params) => Some((
+                idx,
+                params.function_name.clone(),
+                params.idempotency_key.to_string(),
+            )),
+            _ => None,
+        })
+        .filter(|(_, _, key)| idempotency_key.is_none_or(|expected| expected == key.as_str()))
+        .last()?;
+
+    let end = entries[start + 1..]
+        .iter()
+        .position(|(_, entry)| {
+            matches!(
+                entry,
+                PublicOplogEntry::ExportedFunctionInvoked(_)
+                    | PublicOplogEntry::ExportedFunctionCompleted(_)
+            )
+        })
+        .map(|offset| start + 1 + offset)
+        .unwrap_or(entries.len());
+
+    Some(InvocationEntries {
+        function_name,
+        idempotency_key: key,
+        entries: &entries[start..end],
+    })
+}
+
+fn normalize_field_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Depth-first search for a record field, matching both kebab and snake case field names
+fn find_field<'a>(
+    value: &'a Value,
+    typ: &'a AnalysedType,
+    name: &str,
+) -> Option<(&'a Value, &'a AnalysedType)> {
+    match (value, typ) {
+        (Value::Record(values), AnalysedType::Record(TypeRecord { fields })) => values
+            .iter()
+            .zip(fields)
+            .find(|(_, field)| normalize_field_name(&field.name) == name)
+            .map(|(value, field)| (value, &field.typ))
+            .or_else(|| {
+                values
+                    .iter()
+                    .zip(fields)
+                    .find_map(|(value, field)| find_field(value, &field.typ, name))
+            }),
+        _ => children(value, typ).find_map(|(value, typ)| find_field(value, typ, name)),
+    }
+}
+
+fn find_string(value: &Value, typ: &AnalysedType) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        _ => children(value, typ).find_map(|(value, typ)| find_string(value, typ)),
+    }
+}
+
+/// UUIDs are either encoded as strings, or as records of their high and low bits
+fn find_uuid(value: &Value, typ: &AnalysedType) -> Option<Uuid> {
+    if let Value::String(s) = value {
+        return s.parse().ok();
+    }
+
+    let high_bits = find_field(value, typ, "high_bits");
+    let low_bits = find_field(value, typ, "low_bits");
+    if let (Some((Value::U64(high_bits), _)), Some((Value::U64(low_bits), _))) =
+        (high_bits, low_bits)
+    {
+        return Some(Uuid::from_u64_pair(*high_bits, *low_bits));
+    }
+
+    children(value, typ).find_map(|(value, typ)| find_uuid(value, typ))
+}
+
+fn children<'a>(
+    value: &'a Value,
+    typ: &'a AnalysedType,
+) -> Box<dyn Iterator<Item = (&'a Value, &'a AnalysedType)> + 'a> {
+    match (value, typ) {
+        (Value::Record(values), AnalysedType::Record(TypeRecord { fields })) => Box::new(
+            values
+                .iter()
+                .zip(fields)
+                .map(|(value, field)| (value, &field.typ)),
+        ),
+        (Value::Tuple(values), AnalysedType::Tuple(TypeTuple { items })) => {
+            Box::new(values.iter().zip(items))
+        }
+        (Value::Option(Some(value)), AnalysedType::Option(option)) => {
+            Box::new(std::iter::once((value.as_ref(), option.inner.as_ref())))
+        }
+        (
+            Value::Variant {
+                case_idx,
+                case_value: Some(value),
+            },
+            AnalysedType::Variant(TypeVariant { cases }),
+        ) => Box::new(
+            cases
+                .get(*case_idx as usize)
+                .and_then(|case| case.typ.as_ref())
+                .map(|typ| (value.as_ref(), typ))
+                .into_iter(),
+        ),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::trace::{
+        rpc_call, CallTrace, RpcCallKind, TraceCall, TraceGraphFormat, TraceWorker,
+    };
+    use assert2::{check, let_assert};
+    use golem_wasm_ast::analysis::analysed_type::{field, list, record, str, u64};
+    use golem_wasm_rpc::{Value, ValueAndType};
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn worker(component_name: &str, worker_name: &str) -> TraceWorker {
+        TraceWorker {
+            component_id: Uuid::new_v5(&Uuid::NAMESPACE_OID, component_name.as_bytes()),
+            component_name: Some(component_name.to_string()),
+            worker_name: worker_name.to_string(),
+        }
+    }
+
+    fn trace() -> CallTrace {
+        let cart = worker("cart", "cart-1");
+        let inventory = worker("inventory", "inv-1");
+        let notifier = worker("notifier", "notify-1");
+        CallTrace {
+            entry: cart.clone(),
+            entry_function: "cart:api/api.{checkout}".to_string(),
+            entry_idempotency_key: "key".to_string(),
+            calls: vec![
+                TraceCall {
+                    caller: cart.clone(),
+                    caller_function: "cart:api/api.{checkout}".to_string(),
+                    callee: inventory,
+                    function_name: "inventory:api/api.{reserve}".to_string(),
+                    kind: RpcCallKind::InvokeAndAwait,
+                    idempotency_key: None,
+                },
+                TraceCall {
+                    caller: cart,
+                    caller_function: "cart:api/api.{checkout}".to_string(),
+                    callee: notifier,
+                    function_name: "notifier:api/api.{send}".to_string(),
+                    kind: RpcCallKind::Invoke,
+                    idempotency_key: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn render_mermaid() {
+        check!(
+            trace().render(TraceGraphFormat::Mermaid)
+                == [
+                    "flowchart LR",
+                    "    w0[\"cart/cart-1\"]",
+                    "    w1[\"inventory/inv-1\"]",
+                    "    w2[\"notifier/notify-1\"]",
+                    "    request([request]) -->|\"cart:api/api.{checkout}\"| w0",
+                    "    w0 -->|\"1. inventory:api/api.{reserve}\"| w1",
+                    "    w0 -.->|\"2. notifier:api/api.{send}\"| w2",
+                    "",
+                ]
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn render_dot() {
+        let dot = trace().render(TraceGraphFormat::Dot);
+        check!(dot.starts_with("digraph trace {\n"));
+        check!(dot.contains("    w1 [shape=box, label=\"inventory/inv-1\"];\n"));
+        check!(dot.contains("    w0 -> w2 [label=\"2. notifier:api/api.{send}\", style=dashed];\n"));
+        check!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn recognize_rpc_call() {
+        let component_id = Uuid::new_v4();
+        let (high_bits, low_bits) = component_id.as_u64_pair();
+
+        let request = ValueAndType {
+            value: Value::Record(vec![
+                Value::Record(vec![
+                    Value::Record(vec![Value::Record(vec![
+                        Value::U64(high_bits),
+                        Value::U64(low_bits),
+                    ])]),
+                    Value::String("inv-1".to_string()),
+                ]),
+                Value::String("key-1".to_string()),
+                Value::String("inventory:api/api.{reserve}".to_string()),
+                Value::List(vec![]),
+            ]),
+            typ: record(vec![
+                field(
+                    "remote-worker-id",
+                    record(vec![
+                        field(
+                            "component-id",
+                            record(vec![field(
+                                "uuid",
+                                record(vec![field("high-bits", u64()), field("low-bits", u64())]),
+                            )]),
+                        ),
+                        field("worker-name", str()),
+                    ]),
+                ),
+                field("idempotency-key", str()),
+                field("function-name", str()),
+                field("function-params", list(str())),
+            ]),
+        };
+
+        let_assert!(
+            Some(call) = rpc_call("golem::rpc::wasm-rpc::invoke-and-await result", &request)
+        );
+        check!(call.callee_component_id == component_id);
+        check!(call.callee_worker_name == "inv-1");
+        check!(call.function_name == "inventory:api/api.{reserve}");
+        check!(call.idempotency_key == Some("key-1".to_string()));
+        check!(call.kind == RpcCallKind::InvokeAndAwait);
+
+        let_assert!(Some(call) = rpc_call("golem::rpc::wasm-rpc::invoke", &request));
+        check!(call.kind == RpcCallKind::Invoke);
+    }
+
+    #[test]
+    fn ignore_non_rpc_call() {
+        let request = ValueAndType {
+            value: Value::Record(vec![Value::String("a".to_string())]),
+            typ: record(vec![field("path", str())]),
+        };
+        check!(rpc_call("wasi:filesystem/types::read", &request) == None);
+    }
+}
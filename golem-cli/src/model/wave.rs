@@ -66,3 +66,65 @@ pub fn function_wave_compatible(func: &AnalysedFunction) -> bool {
     func.parameters.iter().all(|p| type_wave_compatible(&p.typ))
         && func.results.iter().all(|r| type_wave_compatible(&r.typ))
 }
+
+/// Quotes and escapes the string as a WAVE string literal
+pub fn wave_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        push_escaped_char(&mut result, c);
+    }
+    result.push('"');
+    result
+}
+
+/// Quotes and escapes the char as a WAVE char literal
+pub fn wave_char(value: char) -> String {
+    let mut result = String::with_capacity(3);
+    result.push('\'');
+    push_escaped_char(&mut result, value);
+    result.push('\'');
+    result
+}
+
+fn push_escaped_char(result: &mut String, c: char) {
+    match c {
+        '"' => result.push_str("\\\""),
+        '\'' => result.push_str("\\'"),
+        '\\' => result.push_str("\\\\"),
+        '\n' => result.push_str("\\n"),
+        '\r' => result.push_str("\\r"),
+        '\t' => result.push_str("\\t"),
+        c if c.is_control() => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+        c => result.push(c),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::model::wave::{wave_char, wave_string};
+    use assert2::check;
+    use golem_wasm_ast::analysis::analysed_type::{chr, str};
+    use golem_wasm_rpc::parse_type_annotated_value;
+
+    #[test]
+    fn escaped_literals_parse_back() {
+        for value in [
+            "plain",
+            "with \"quotes\" and 'apostrophes'",
+            "a\\b\nc\td\u{1}",
+        ] {
+            let wave = wave_string(value);
+            check!(parse_type_annotated_value(&str(), &wave).is_ok(), "{wave}");
+        }
+        check!(wave_string("a\"b\n") == r#""a\"b\n""#);
+
+        for value in ['x', '\'', '\\', '\n'] {
+            let wave = wave_char(value);
+            check!(parse_type_annotated_value(&chr(), &wave).is_ok(), "{wave}");
+        }
+        check!(wave_char('\'') == r"'\''");
+    }
+}
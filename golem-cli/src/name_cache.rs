@@ -0,0 +1,248 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cloud::AccountId;
+use crate::config::ProfileName;
+use crate::model::ProjectName;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+use url::Url;
+use uuid::Uuid;
+
+/// Default time to live of the cached entries, can be overridden with the
+/// GOLEM_NAME_CACHE_TTL env var (in seconds, 0 disables the cache)
+pub const DEFAULT_NAME_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Key of the default project entry, project names cannot be empty, so it cannot collide
+const DEFAULT_PROJECT: &str = "";
+
+pub fn name_cache_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("name-cache.json")
+}
+
+/// On-disk cache of name to ID resolutions, for avoiding a server lookup on every command
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCache {
+    #[serde(default)]
+    pub entries: BTreeMap<String, NameCacheEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCacheEntry {
+    pub name: String,
+    pub id: Uuid,
+    pub cached_at: DateTime<Utc>,
+}
+
+impl NameCache {
+    /// Loads the cache, a missing or unreadable cache is treated as empty
+    pub fn load(config_dir: &Path) -> Self {
+        let path = name_cache_path(config_dir);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| serde_json::from_str(&content).map_err(anyhow::Error::from))
+        {
+            Ok(cache) => cache,
+            Err(error) => {
+                debug!(
+                    "Ignoring invalid name cache {}: {:#}",
+                    path.display(),
+                    error
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Stores the cache through a temporary file, so concurrently running commands
+    /// never see a partially written cache
+    pub fn store(&self, config_dir: &Path) -> anyhow::Result<()> {
+        create_dir_all(config_dir)
+            .map_err(|err| anyhow!("Can't create config directory: {err}"))?;
+
+        let path = name_cache_path(config_dir);
+        let temp_path = config_dir.join(format!("name-cache.json.{}", Uuid::new_v4()));
+        std::fs::write(&temp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| anyhow!("Failed to write name cache: {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &path)
+            .with_context(|| anyhow!("Failed to write name cache: {}", path.display()))
+    }
+
+    pub fn get(&self, key: &str, ttl: Duration, now: DateTime<Utc>) -> Option<&NameCacheEntry> {
+        self.entries.get(key).filter(|entry| {
+            chrono::Duration::from_std(ttl)
+                .is_ok_and(|ttl| entry.cached_at <= now && now - entry.cached_at < ttl)
+        })
+    }
+
+    pub fn insert(&mut self, key: String, name: String, id: Uuid, now: DateTime<Utc>) {
+        self.entries.insert(
+            key,
+            NameCacheEntry {
+                name,
+                id,
+                cached_at: now,
+            },
+        );
+    }
+
+    /// Drops the expired entries, so the cache does not grow without bounds
+    pub fn retain_valid(&mut self, ttl: Duration, now: DateTime<Utc>) {
+        let keys = self
+            .entries
+            .keys()
+            .filter(|key| self.get(key, ttl, now).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Scope of the cached entries, identifying the profile, the server and the authentication
+/// token used for the resolutions, so entries are not reused after the profile is recreated,
+/// re-authenticated or pointed to another server
+pub fn scope(profile_name: &ProfileName, url: &Url, auth_token_id: Option<&Uuid>) -> String {
+    format!(
+        "{}@{}#{}",
+        profile_name.0,
+        url,
+        auth_token_id.map(|id| id.to_string()).unwrap_or_default()
+    )
+}
+
+pub fn project_key(
+    scope: &str,
+    account_id: Option<&AccountId>,
+    project_name: &ProjectName,
+) -> String {
+    format!(
+        "{}/project/{}/{}",
+        scope,
+        account_id.map(|id| id.0.as_str()).unwrap_or_default(),
+        project_name.0
+    )
+}
+
+pub fn default_project_key(scope: &str) -> String {
+    project_key(scope, None, &ProjectName(DEFAULT_PROJECT.to_string()))
+}
+
+/// Returns the cached name and ID, or None if the entry is missing, expired or caching is disabled
+pub fn cached(config_dir: &Path, key: &str, ttl: Duration) -> Option<(String, Uuid)> {
+    if ttl.is_zero() {
+        return None;
+    }
+
+    NameCache::load(config_dir)
+        .get(key, ttl, Utc::now())
+        .map(|entry| (entry.name.clone(), entry.id))
+}
+
+/// Caches a resolved name, failures are only logged, as the cache is an optimization
+pub fn cache(config_dir: &Path, key: String, name: String, id: Uuid, ttl: Duration) {
+    if ttl.is_zero() {
+        return;
+    }
+
+    let now = Utc::now();
+    let mut cache = NameCache::load(config_dir);
+    cache.retain_valid(ttl, now);
+    cache.insert(key, name, id, now);
+    if let Err(error) = cache.store(config_dir) {
+        debug!("Failed to store name cache: {:#}", error);
+    }
+}
+
+/// Removes the cache file, returns false if there was nothing to remove
+pub fn clear(config_dir: &Path) -> anyhow::Result<bool> {
+    let path = name_cache_path(config_dir);
+    if !path.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_file(&path)
+        .with_context(|| anyhow!("Failed to remove name cache: {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::ProfileName;
+    use crate::model::ProjectName;
+    use crate::name_cache::{default_project_key, project_key, scope, NameCache};
+    use assert2::{check, let_assert};
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+    use test_r::test;
+    use url::Url;
+    use uuid::Uuid;
+
+    #[test]
+    fn entries_expire() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let ttl = Duration::from_secs(60);
+        let id = Uuid::new_v4();
+
+        let mut cache = NameCache::default();
+        cache.insert("key".to_string(), "name".to_string(), id, now);
+
+        let_assert!(Some(entry) = cache.get("key", ttl, now + chrono::Duration::seconds(59)));
+        check!(entry.id == id);
+        check!(cache
+            .get("key", ttl, now + chrono::Duration::seconds(60))
+            .is_none());
+        check!(cache.get("other", ttl, now).is_none());
+
+        cache.retain_valid(ttl, now + chrono::Duration::seconds(120));
+        check!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn keys() {
+        let url = Url::parse("https://release.api.golem.cloud").unwrap();
+        let token_id = Uuid::nil();
+        let cache_scope = scope(&ProfileName("cloud".to_string()), &url, Some(&token_id));
+        check!(
+            cache_scope
+                == "cloud@https://release.api.golem.cloud/#00000000-0000-0000-0000-000000000000"
+        );
+        check!(
+            project_key(&cache_scope, None, &ProjectName("proj".to_string()))
+                == format!("{}/project//proj", cache_scope)
+        );
+        check!(default_project_key(&cache_scope) == format!("{}/project//", cache_scope));
+
+        let other_token_id = Uuid::new_v4();
+        check!(
+            scope(
+                &ProfileName("cloud".to_string()),
+                &url,
+                Some(&other_token_id)
+            ) != cache_scope
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::UpdateChannel;
+use anyhow::{anyhow, bail, Context};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/golemcloud/golem-cli/releases";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    pub fn version(&self) -> anyhow::Result<Version> {
+        parse_version(&self.tag_name)
+            .with_context(|| anyhow!("Failed to parse release version: {}", self.tag_name))
+    }
+
+    pub fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Name of the released binary matching the running one, the golem binary is the CLI
+/// built with the server commands
+pub fn binary_name() -> &'static str {
+    if cfg!(feature = "server-commands") {
+        "golem"
+    } else {
+        "golem-cli"
+    }
+}
+
+/// Name of the release asset for the binary and target triple, following the naming used by
+/// the release workflow
+pub fn asset_name(binary_name: &str, target: &str) -> String {
+    if target.contains("windows") {
+        format!("{binary_name}-{target}.exe")
+    } else {
+        format!("{binary_name}-{target}")
+    }
+}
+
+pub fn checksum_asset_name(asset_name: &str) -> String {
+    format!("{asset_name}.sha256")
+}
+
+pub fn parse_version(version: &str) -> anyhow::Result<Version> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    Ok(Version::parse(version)?)
+}
+
+/// Returns true if the release is newer than the current version, unparseable current versions
+/// (e.g. local builds) are always considered outdated
+pub fn is_newer(current: &str, release: &Version) -> bool {
+    match parse_version(current) {
+        Ok(current) => release > &current,
+        Err(_) => true,
+    }
+}
+
+pub async fn find_release(
+    client: &reqwest::Client,
+    channel: UpdateChannel,
+    version: Option<&Version>,
+) -> anyhow::Result<Release> {
+    match version {
+        Some(version) => get_json(client, &format!("{RELEASES_URL}/tags/v{version}"))
+            .await
+            .with_context(|| anyhow!("Release not found: v{version}")),
+        None => match channel {
+            UpdateChannel::Stable => get_json(client, &format!("{RELEASES_URL}/latest")).await,
+            UpdateChannel::Nightly => {
+                let releases: Vec<Release> =
+                    get_json(client, &format!("{RELEASES_URL}?per_page=30")).await?;
+                releases
+                    .into_iter()
+                    .find(|release| release.prerelease)
+                    .ok_or_else(|| anyhow!("No {channel} release found"))
+            }
+        },
+    }
+}
+
+/// Downloads the asset and verifies it against the checksum published next to it in the release
+pub async fn download_verified(
+    client: &reqwest::Client,
+    release: &Release,
+    asset_name: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let asset = release.asset(asset_name).ok_or_else(|| {
+        anyhow!(
+            "Release {} has no binary for this platform: {}",
+            release.tag_name,
+            asset_name
+        )
+    })?;
+    let checksum_asset_name = checksum_asset_name(asset_name);
+    let checksum_asset = release.asset(&checksum_asset_name).ok_or_else(|| {
+        anyhow!(
+            "Release {} has no checksum for {}, refusing to install an unverified binary",
+            release.tag_name,
+            asset_name
+        )
+    })?;
+
+    let checksum = download(client, &checksum_asset.browser_download_url).await?;
+    let content = download(client, &asset.browser_download_url).await?;
+    verify_sha256(&content, &String::from_utf8_lossy(&checksum))?;
+
+    Ok(content)
+}
+
+/// Verifies the content against a sha256sum style checksum file, only the first hash is used
+pub fn verify_sha256(content: &[u8], checksum_file: &str) -> anyhow::Result<()> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow!("Empty checksum file"))?
+        .to_lowercase();
+    let actual = hex::encode(Sha256::digest(content));
+
+    if expected != actual {
+        bail!("Checksum mismatch, expected {expected}, got {actual}");
+    }
+
+    Ok(())
+}
+
+/// Replaces the executable through a temporary file in the same directory, so the executable is
+/// never partially written. On Windows the running executable cannot be overwritten, so it is
+/// moved aside first.
+pub fn replace_executable(exe: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let dir = exe
+        .parent()
+        .ok_or_else(|| anyhow!("Invalid executable path: {}", exe.display()))?;
+    let file_name = exe
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid executable path: {}", exe.display()))?
+        .to_string_lossy();
+    let temp_path = dir.join(format!(".{}.{}", file_name, Uuid::new_v4()));
+
+    std::fs::write(&temp_path, content)
+        .with_context(|| anyhow!("Failed to write {}", temp_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o755))
+            .with_context(|| anyhow!("Failed to set permissions of {}", temp_path.display()))?;
+    }
+
+    let result = if cfg!(windows) {
+        let old_path = old_executable_path(exe);
+        let _ = std::fs::remove_file(&old_path);
+        std::fs::rename(exe, &old_path)
+            .and_then(|()| std::fs::rename(&temp_path, exe))
+            .with_context(|| anyhow!("Failed to replace {}", exe.display()))
+    } else {
+        std::fs::rename(&temp_path, exe)
+            .with_context(|| anyhow!("Failed to replace {}", exe.display()))
+    };
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+fn old_executable_path(exe: &Path) -> PathBuf {
+    exe.with_extension("old.exe")
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> anyhow::Result<T> {
+    let content = download(client, url).await?;
+    serde_json::from_slice(&content).with_context(|| anyhow!("Failed to parse response of {url}"))
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(client
+        .get(url)
+        // Required by the GitHub API
+        .header(
+            reqwest::header::USER_AGENT,
+            format!("{}/{}", binary_name(), crate::version()),
+        )
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| anyhow!("Failed to download {url}"))?
+        .bytes()
+        .await
+        .with_context(|| anyhow!("Failed to download {url}"))?
+        .to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::self_update::{asset_name, is_newer, parse_version, verify_sha256};
+    use assert2::check;
+
+    #[test]
+    fn asset_names_follow_release_workflow() {
+        check!(
+            asset_name("golem-cli", "x86_64-unknown-linux-gnu")
+                == "golem-cli-x86_64-unknown-linux-gnu"
+        );
+        check!(asset_name("golem", "x86_64-pc-windows-gnu") == "golem-x86_64-pc-windows-gnu.exe");
+    }
+
+    #[test]
+    fn newer_versions() {
+        let release = parse_version("v1.2.3").unwrap();
+        check!(is_newer("1.2.2", &release));
+        check!(!is_newer("1.2.3", &release));
+        check!(!is_newer("1.3.0", &release));
+        check!(is_newer("v1.2.2-dev.4-3-gabcdef", &release));
+        check!(is_newer("0.0.0-local", &parse_version("v0.0.1").unwrap()));
+    }
+
+    #[test]
+    fn sha256_verification() {
+        let checksum =
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  golem-cli\n";
+        check!(verify_sha256(b"hello", checksum).is_ok());
+        check!(verify_sha256(b"hello!", checksum).is_err());
+        check!(verify_sha256(b"hello", "").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cancellation::CancellationToken;
+use crate::error::service::ServiceError;
+use crate::log::{log_warn_action, LogColorize};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const MIN_DELAY: Duration = Duration::from_millis(250);
+const MAX_DELAY: Duration = Duration::from_secs(60);
+const MAX_RETRIES: usize = 8;
+
+/// Adaptive client-side throttling for bulk operations.
+///
+/// Calls made through the throttle are retried when the server rate-limits them (HTTP 429),
+/// using exponential backoff. After being throttled, subsequent calls are paced with the same
+/// delay, which is gradually decreased again by successful calls. The throttle is shared between
+/// the handlers, so all bulk calls of a command slow down together.
+///
+/// TODO: honor Retry-After, the errors of the generated clients only contain the status code and
+///       the body of the response, so this needs the response headers to be exposed by the client
+///       generator first
+#[derive(Clone)]
+pub struct Throttle {
+    delay: Arc<Mutex<Duration>>,
+    cancellation_token: CancellationToken,
+}
+
+impl Throttle {
+    pub fn new(cancellation_token: CancellationToken) -> Self {
+        Self {
+            delay: Arc::new(Mutex::new(Duration::ZERO)),
+            cancellation_token,
+        }
+    }
+
+    pub async fn call<R, F, Fut>(&self, mut f: F) -> anyhow::Result<R>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<R>>,
+    {
+        let mut retries = 0;
+        loop {
+            let pace = self.current_delay();
+            if !pace.is_zero() {
+                self.sleep(pace).await?;
+            }
+
+            match f().await {
+                Ok(result) => {
+                    self.on_success();
+                    return Ok(result);
+                }
+                Err(error) => {
+                    if !is_rate_limited(&error) || retries == MAX_RETRIES {
+                        return Err(error);
+                    }
+                    retries += 1;
+
+                    let wait = self.on_throttled();
+                    log_warn_action(
+                        "Throttled",
+                        format!(
+                            "by the server, retrying in {}",
+                            format_wait(wait).log_color_highlight()
+                        ),
+                    );
+                    // The increased pacing delay is applied before the next attempt
+                }
+            }
+        }
+    }
+
+    fn current_delay(&self) -> Duration {
+        *self.delay.lock().unwrap()
+    }
+
+    fn on_success(&self) {
+        let mut delay = self.delay.lock().unwrap();
+        *delay = decreased_delay(*delay);
+    }
+
+    fn on_throttled(&self) -> Duration {
+        let mut delay = self.delay.lock().unwrap();
+        *delay = increased_delay(*delay);
+        *delay
+    }
+
+    async fn sleep(&self, duration: Duration) -> anyhow::Result<()> {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => Ok(()),
+            _ = self.cancellation_token.cancelled() => self.cancellation_token.check(),
+        }
+    }
+}
+
+fn is_rate_limited(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<ServiceError>()
+        .is_some_and(|error| error.is_rate_limited())
+}
+
+fn increased_delay(delay: Duration) -> Duration {
+    (delay * 2).clamp(MIN_DELAY, MAX_DELAY)
+}
+
+fn decreased_delay(delay: Duration) -> Duration {
+    let delay = delay * 3 / 4;
+    if delay < MIN_DELAY {
+        Duration::ZERO
+    } else {
+        delay
+    }
+}
+
+fn format_wait(wait: Duration) -> String {
+    if wait < Duration::from_secs(1) {
+        format!("{}ms", wait.as_millis())
+    } else {
+        format!("{}s", wait.as_secs_f64().ceil() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::throttle::{decreased_delay, increased_delay, MAX_DELAY};
+    use assert2::check;
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    fn delay_adapts() {
+        let delay = increased_delay(Duration::ZERO);
+        check!(delay == Duration::from_millis(250));
+        check!(increased_delay(delay) == Duration::from_millis(500));
+        check!(increased_delay(MAX_DELAY) == MAX_DELAY);
+        check!(decreased_delay(Duration::from_secs(4)) == Duration::from_secs(3));
+        check!(decreased_delay(delay) == Duration::ZERO);
+    }
+}
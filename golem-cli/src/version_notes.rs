@@ -0,0 +1,165 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub fn version_notes_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("component-version-notes.json")
+}
+
+/// Notes and metadata attached to component versions on deploy, the server does not store
+/// these, so they are kept next to the CLI config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionNotes {
+    #[serde(default)]
+    pub components: BTreeMap<Uuid, BTreeMap<u64, VersionNote>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionNote {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl VersionNote {
+    pub fn new(note: Option<String>, metadata: Vec<(String, String)>) -> Self {
+        Self {
+            note,
+            metadata: metadata.into_iter().collect(),
+            created_at: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.note.is_none() && self.metadata.is_empty()
+    }
+}
+
+impl VersionNotes {
+    /// Loads the notes, a missing file is treated as empty
+    pub fn load(config_dir: &Path) -> anyhow::Result<Self> {
+        let path = version_notes_path(config_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| anyhow!("Failed to read version notes: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| anyhow!("Failed to parse version notes: {}", path.display()))
+    }
+
+    /// Stores the notes through a temporary file, so concurrently running commands
+    /// never see a partially written file
+    pub fn store(&self, config_dir: &Path) -> anyhow::Result<()> {
+        create_dir_all(config_dir)
+            .map_err(|err| anyhow!("Can't create config directory: {err}"))?;
+
+        let path = version_notes_path(config_dir);
+        let temp_path = config_dir.join(format!("component-version-notes.json.{}", Uuid::new_v4()));
+        std::fs::write(&temp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| anyhow!("Failed to write version notes: {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &path)
+            .with_context(|| anyhow!("Failed to write version notes: {}", path.display()))
+    }
+
+    pub fn get(&self, component_id: &Uuid, version: u64) -> Option<&VersionNote> {
+        self.components
+            .get(component_id)
+            .and_then(|versions| versions.get(&version))
+    }
+
+    /// Attaches the note to the version, metadata keys are merged with the existing ones,
+    /// and an existing note is only replaced if a new one is provided
+    pub fn insert(
+        &mut self,
+        component_id: Uuid,
+        version: u64,
+        note: VersionNote,
+        now: DateTime<Utc>,
+    ) {
+        let entry = self
+            .components
+            .entry(component_id)
+            .or_default()
+            .entry(version)
+            .or_insert_with(|| VersionNote {
+                created_at: Some(now),
+                ..VersionNote::default()
+            });
+        if note.note.is_some() {
+            entry.note = note.note;
+        }
+        entry.metadata.extend(note.metadata);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::version_notes::{VersionNote, VersionNotes};
+    use assert2::{check, let_assert};
+    use chrono::{TimeZone, Utc};
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn notes_are_merged_per_version() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let component_id = Uuid::new_v4();
+
+        let mut notes = VersionNotes::default();
+        notes.insert(
+            component_id,
+            2,
+            VersionNote::new(
+                Some("fix rounding bug".to_string()),
+                vec![("ticket".to_string(), "GC-12".to_string())],
+            ),
+            now,
+        );
+        notes.insert(
+            component_id,
+            2,
+            VersionNote::new(None, vec![("reviewer".to_string(), "ann".to_string())]),
+            now + chrono::Duration::minutes(1),
+        );
+
+        let_assert!(Some(note) = notes.get(&component_id, 2));
+        check!(note.note.as_deref() == Some("fix rounding bug"));
+        check!(note.metadata.len() == 2);
+        check!(note.metadata.get("ticket").map(String::as_str) == Some("GC-12"));
+        check!(note.created_at == Some(now));
+        check!(notes.get(&component_id, 1).is_none());
+        check!(notes.get(&Uuid::new_v4(), 2).is_none());
+
+        let json = serde_json::to_string(&notes).unwrap();
+        let parsed: VersionNotes = serde_json::from_str(&json).unwrap();
+        check!(parsed == notes);
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use std::path::Path;
+use wit_bindgen_core::Files;
+
+/// Generates C bindings for calling the client from C/C++ components, e.g. built with wasi-sdk.
+///
+/// The client component exports the client interface, so the bindings are generated with
+/// wit-bindgen-c for a caller world, in which the exports of the client world are turned
+/// into imports. Next to the header, implementation and component type object, a Makefile
+/// fragment and a small example are written to the target directory.
+pub fn generate_c_bindings(stub_def: &StubDefinition, target_dir: &Path) -> anyhow::Result<()> {
+    log_action(
+        "Generating",
+        format!("C client bindings to {}", target_dir.log_color_highlight()),
+    );
+    let _indent = LogIndent::new();
+
+    let mut resolved = stub_def
+        .resolve_client_wit()
+        .context("Failed to resolve the client WIT root")?;
+
+    let client_world_name = stub_def.client_world_name();
+    let caller_world_name = naming::c::caller_world_name(&client_world_name);
+    let world_id = resolved
+        .resolve
+        .select_world(resolved.package_id, Some(client_world_name.as_str()))?;
+    resolved
+        .resolve
+        .importize(world_id, Some(caller_world_name.clone()))
+        .context("Failed to create the C caller world")?;
+
+    let mut files = Files::default();
+    wit_bindgen_c::Opts::default()
+        .build()
+        .generate(&resolved.resolve, world_id, &mut files)
+        .context("Failed to generate C bindings")?;
+
+    fs::create_dir_all(target_dir)?;
+
+    let mut header = Option::<(String, String)>::None;
+    let mut sources = Vec::<String>::new();
+    let mut objects = Vec::<String>::new();
+    for (name, contents) in files.iter() {
+        log_action("Writing", name.log_color_highlight());
+        fs::write(target_dir.join(name), contents)?;
+
+        if name.ends_with(".h") {
+            header = Some((
+                name.to_string(),
+                String::from_utf8_lossy(contents).to_string(),
+            ));
+        } else if name.ends_with(".c") {
+            sources.push(name.to_string());
+        } else if name.ends_with(".o") {
+            objects.push(name.to_string());
+        }
+    }
+
+    let (header_name, header) =
+        header.ok_or_else(|| anyhow!("wit-bindgen-c did not generate a header"))?;
+
+    log_action(
+        "Writing",
+        naming::c::MAKEFILE_FRAGMENT.log_color_highlight(),
+    );
+    fs::write_str(
+        target_dir.join(naming::c::MAKEFILE_FRAGMENT),
+        makefile_fragment(
+            &naming::c::make_variable_prefix(&caller_world_name),
+            &sources,
+            &objects,
+        ),
+    )?;
+
+    log_action("Writing", naming::c::EXAMPLE.log_color_highlight());
+    fs::write_str(
+        target_dir.join(naming::c::EXAMPLE),
+        example(
+            &stub_def.client_interface_name(),
+            &header_name,
+            &imported_functions(&header),
+        ),
+    )?;
+
+    Ok(())
+}
+
+fn makefile_fragment(prefix: &str, sources: &[String], objects: &[String]) -> String {
+    let compiled_objects = sources.iter().map(|source| {
+        format!(
+            "$({prefix}_DIR){}.o",
+            source.strip_suffix(".c").unwrap_or(source)
+        )
+    });
+    let objects = compiled_objects
+        .chain(
+            objects
+                .iter()
+                .map(|object| format!("$({prefix}_DIR){object}")),
+        )
+        .join(" ");
+
+    format!(
+        r#"# Generated by golem-cli, do not edit.
+#
+# Include this fragment from the Makefile of the calling component, and link $({prefix}_OBJS)
+# into it. The compiled component still has to be composed with the client component.
+
+{prefix}_DIR := $(dir $(lastword $(MAKEFILE_LIST)))
+WASI_SDK_PATH ?= /opt/wasi-sdk
+{prefix}_CC ?= $(WASI_SDK_PATH)/bin/clang --target=wasm32-wasip2 -mexec-model=reactor
+{prefix}_OBJS := {objects}
+
+$({prefix}_DIR)%.o: $({prefix}_DIR)%.c
+	$({prefix}_CC) -c -I$({prefix}_DIR) $< -o $@
+"#
+    )
+}
+
+fn example(client_interface_name: &str, header_name: &str, functions: &[String]) -> String {
+    let functions = functions
+        .iter()
+        .map(|function| format!("//   {}", function))
+        .join("\n");
+
+    format!(
+        r#"// Generated by golem-cli, example of calling the {client_interface_name} client from C.
+//
+// See {header_name} for the exact signatures, and client.mk for building.
+#include "{header_name}"
+
+// Functions of the client:
+{functions}
+"#
+    )
+}
+
+/// Returns the names of the functions imported by the caller world, as declared in the header
+fn imported_functions(header: &str) -> Vec<String> {
+    header
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("extern "))
+        .filter_map(|declaration| declaration.split_once('(').map(|(signature, _)| signature))
+        .filter_map(|signature| signature.split_whitespace().last())
+        .map(|name| name.trim_start_matches('*').to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_rpc_stubgen::c::imported_functions;
+    use assert2::check;
+    use test_r::test;
+
+    #[test]
+    fn imported_functions_from_header() {
+        let header = r#"
+#include <stdint.h>
+
+// Imported Functions from `test:main-client/api-client`
+extern void test_main_client_api_client_method_iface1_blocking_no_op(test_main_client_api_client_borrow_iface1_t self);
+extern bool test_main_client_api_client_method_iface1_blocking_get_bool(test_main_client_api_client_borrow_iface1_t self);
+extern uint8_t *cabi_realloc_example(void *ptr);
+"#;
+        check!(
+            imported_functions(header)
+                == vec![
+                    "test_main_client_api_client_method_iface1_blocking_no_op".to_string(),
+                    "test_main_client_api_client_method_iface1_blocking_get_bool".to_string(),
+                    "cabi_realloc_example".to_string(),
+                ]
+        );
+    }
+}
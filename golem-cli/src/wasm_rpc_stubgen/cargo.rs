@@ -16,9 +16,11 @@ use crate::fs;
 use crate::fs::PathExtra;
 use crate::log::{log_action, log_warn_action, LogColorize};
 use crate::wasm_rpc_stubgen::naming;
-use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use crate::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubDefinition};
 use crate::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
-use crate::wasm_rpc_stubgen::{GOLEM_RPC_WIT_VERSION, WASI_WIT_VERSION, WIT_BINDGEN_VERSION};
+use crate::wasm_rpc_stubgen::{
+    GOLEM_RPC_WIT_VERSION, SERDE_VERSION, WASI_WIT_VERSION, WIT_BINDGEN_VERSION,
+};
 use anyhow::{anyhow, Context};
 use cargo_toml::{
     Dependency, DependencyDetail, DepsSet, Edition, Inheritable, LtoSetting, Manifest, Profile,
@@ -75,6 +77,8 @@ struct WitDependency {
 #[derive(Serialize, Deserialize)]
 struct Bindings {
     with: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    derives: Vec<String>,
 }
 
 pub fn generate_client_cargo_toml(def: &StubDefinition) -> anyhow::Result<()> {
@@ -141,7 +145,10 @@ pub fn generate_client_cargo_toml(def: &StubDefinition) -> anyhow::Result<()> {
 
         def.client_binding_mapping
             .add_to_cargo_bindings_table(&mut with);
-        Bindings { with }
+        Bindings {
+            with,
+            derives: def.config.client_derives.clone(),
+        }
     };
 
     let metadata = MetadataRoot {
@@ -197,34 +204,27 @@ pub fn generate_client_cargo_toml(def: &StubDefinition) -> anyhow::Result<()> {
         ..Default::default()
     }));
 
-    let dep_golem_rust = Dependency::Detailed(Box::new(DependencyDetail {
-        version: if def.config.golem_rust_override.path_override.is_none() {
-            if let Some(version) = def.config.golem_rust_override.version_override.as_ref() {
-                Some(version.to_string())
-            } else {
-                Some("1.3.0".to_string()) // TODO: constant
-            }
-        } else {
-            None
-        },
-        path: def
-            .config
-            .golem_rust_override
-            .path_override
-            .as_ref()
-            .map(|path| {
-                path.to_str()
-                    .expect("Failed to convert golem-rust override path to string")
-                    .to_string()
-            }),
-        default_features: false,
-        features: vec![],
-        ..Default::default()
-    }));
+    let dep_golem_rust = golem_rust_dependency(&def.config.golem_rust_override);
 
     let mut deps = DepsSet::new();
     deps.insert("wit-bindgen-rt".to_string(), dep_wit_bindgen);
     deps.insert("golem-rust".to_string(), dep_golem_rust);
+    // Serde derives are common enough for DTOs to add the dependency for them
+    if def
+        .config
+        .client_derives
+        .iter()
+        .any(|derive| derive.starts_with("serde::"))
+    {
+        deps.insert(
+            "serde".to_string(),
+            Dependency::Detailed(Box::new(DependencyDetail {
+                version: Some(SERDE_VERSION.to_string()),
+                features: vec!["derive".to_string()],
+                ..Default::default()
+            })),
+        );
+    }
     manifest.dependencies = deps;
 
     let cargo_toml = toml::to_string(&manifest)?;
@@ -240,6 +240,42 @@ pub fn generate_client_cargo_toml(def: &StubDefinition) -> anyhow::Result<()> {
     Ok(())
 }
 
+// Precedence of the overrides: path, git, version
+fn golem_rust_dependency(golem_rust_override: &RustDependencyOverride) -> Dependency {
+    let path = golem_rust_override.path_override.as_ref().map(|path| {
+        path.to_str()
+            .expect("Failed to convert golem-rust override path to string")
+            .to_string()
+    });
+
+    let git = path
+        .is_none()
+        .then(|| golem_rust_override.git_override.clone())
+        .flatten();
+
+    let branch = git
+        .is_some()
+        .then(|| golem_rust_override.git_branch_override.clone())
+        .flatten();
+
+    let version = (path.is_none() && git.is_none()).then(|| {
+        golem_rust_override
+            .version_override
+            .clone()
+            .unwrap_or_else(|| "1.3.0".to_string()) // TODO: constant
+    });
+
+    Dependency::Detailed(Box::new(DependencyDetail {
+        version,
+        path,
+        git,
+        branch,
+        default_features: false,
+        features: vec![],
+        ..Default::default()
+    }))
+}
+
 pub fn is_cargo_component_toml(path: &Path) -> anyhow::Result<bool> {
     let manifest: Manifest<MetadataRoot> = Manifest::from_path_with_metadata(path)?;
 
@@ -509,3 +545,66 @@ pub fn regenerate_cargo_package_component(
 fn format_package_name_without_version(package_name: &PackageName) -> String {
     format!("{}:{}", package_name.namespace, package_name.name)
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::wasm_rpc_stubgen::cargo::golem_rust_dependency;
+    use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
+    use assert2::{check, let_assert};
+    use cargo_toml::Dependency;
+    use std::path::PathBuf;
+
+    fn dependency_fields(
+        golem_rust_override: RustDependencyOverride,
+    ) -> (
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ) {
+        let_assert!(Dependency::Detailed(detail) = golem_rust_dependency(&golem_rust_override));
+        (detail.version, detail.path, detail.git, detail.branch)
+    }
+
+    #[test]
+    fn golem_rust_dependency_override_precedence() {
+        let all_overrides = RustDependencyOverride {
+            path_override: Some(PathBuf::from("/golem-rust")),
+            version_override: Some("1.2.3".to_string()),
+            git_override: Some("https://github.com/golemcloud/golem-rust".to_string()),
+            git_branch_override: Some("main".to_string()),
+        };
+
+        check!(
+            dependency_fields(all_overrides.clone())
+                == (None, Some("/golem-rust".to_string()), None, None)
+        );
+
+        check!(
+            dependency_fields(RustDependencyOverride {
+                path_override: None,
+                ..all_overrides.clone()
+            }) == (
+                None,
+                None,
+                Some("https://github.com/golemcloud/golem-rust".to_string()),
+                Some("main".to_string())
+            )
+        );
+
+        check!(
+            dependency_fields(RustDependencyOverride {
+                path_override: None,
+                git_override: None,
+                ..all_overrides
+            }) == (Some("1.2.3".to_string()), None, None, None)
+        );
+
+        check!(
+            dependency_fields(RustDependencyOverride::default())
+                == (Some("1.3.0".to_string()), None, None, None)
+        );
+    }
+}
@@ -7,11 +7,7 @@ use std::path::{Path, PathBuf};
 use wac_graph::types::{Package, SubtypeChecker};
 use wac_graph::{CompositionGraph, EncodeOptions, PackageId, PlugError};
 
-pub async fn compose(
-    source_wasm: &Path,
-    stub_wasms: &[PathBuf],
-    dest_wasm: &Path,
-) -> anyhow::Result<()> {
+pub fn compose(source_wasm: &Path, stub_wasms: &[PathBuf], dest_wasm: &Path) -> anyhow::Result<()> {
     // Based on https://github.com/bytecodealliance/wac/blob/release-0.6.0/src/commands/plug.rs
     // with allowing missing plugs (through the also customized plug function below)
     // and using local packages only (for now)
@@ -12,19 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_file_change_diff, logln, LogColorize};
+use crate::model::text::fmt::log_error;
 use crate::wasm_rpc_stubgen::wit_generate::{
-    add_client_as_dependency_to_wit_dir, AddClientAsDepConfig, UpdateCargoToml,
+    add_client_as_dependency_changes, add_client_as_dependency_to_wit_dir, AddClientAsDepConfig,
+    ClientPackageCollision, UpdateCargoToml,
 };
+use anyhow::bail;
 use std::path::Path;
 
+/// Adds the stub as a dependency to the destination WIT root, showing the diff of every changed
+/// WIT file before applying them. In check mode no changes are applied, and an error is returned
+/// if there would be any.
+///
+/// If the stub package's dependency directory is already used by another package in the
+/// destination, the given collision strategy is applied.
 pub fn add_stub_dependency(
     stub_wit_root: &Path,
     dest_wit_root: &Path,
     update_cargo_toml: UpdateCargoToml,
+    package_collision: ClientPackageCollision,
+    check: bool,
 ) -> anyhow::Result<()> {
-    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+    let config = AddClientAsDepConfig {
         client_wit_root: stub_wit_root.to_path_buf(),
         dest_wit_root: dest_wit_root.to_path_buf(),
         update_cargo_toml,
-    })
+        package_collision,
+    };
+
+    let changes = add_client_as_dependency_changes(&config)?;
+    for change in &changes {
+        log_file_change_diff(change);
+        logln("");
+    }
+
+    if check {
+        if !changes.is_empty() {
+            log_error(format!(
+                "Adding stub dependency to {} would change {} WIT file(s)",
+                dest_wit_root.log_color_highlight(),
+                changes.len().to_string().log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+        log_action(
+            "Checked",
+            format!(
+                "stub dependency in {}, no changes needed",
+                dest_wit_root.log_color_highlight()
+            ),
+        );
+        return Ok(());
+    }
+
+    add_client_as_dependency_to_wit_dir(config)
 }
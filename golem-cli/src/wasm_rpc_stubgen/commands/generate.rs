@@ -13,17 +13,21 @@
 // limitations under the License.
 
 use crate::fs;
-use crate::log::{log_action, LogColorize, LogIndent};
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::wasm_rpc_stubgen::c::generate_c_bindings;
 use crate::wasm_rpc_stubgen::cargo::generate_client_cargo_toml;
-use crate::wasm_rpc_stubgen::compilation::compile;
+use crate::wasm_rpc_stubgen::compilation::{compile, compile_reproducible};
 use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::reproducible::normalize_wasm;
 use crate::wasm_rpc_stubgen::rust::generate_stub_source;
 use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use crate::wasm_rpc_stubgen::stub_cache;
+use crate::wasm_rpc_stubgen::stub_cache::StubCacheKey;
 use crate::wasm_rpc_stubgen::wit_generate::{
     add_dependencies_to_stub_wit_dir, generate_client_wit_to_target,
 };
 use crate::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use fs_extra::dir::CopyOptions;
 use heck::ToSnakeCase;
 use std::path::{Path, PathBuf};
@@ -35,13 +39,20 @@ pub fn generate_client(stub_def: &StubDefinition) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn generate_c_client(stub_def: &StubDefinition, target_dir: &Path) -> anyhow::Result<()> {
+    let _ = generate_client_wit_dir(stub_def)?;
+    generate_c_bindings(stub_def, target_dir).context("Failed to generate the C bindings")?;
+    Ok(())
+}
+
 pub async fn build(
     stub_def: &StubDefinition,
     dest_wasm: &Path,
     dest_wit_root: &Path,
     offline: bool,
+    reproducible: bool,
 ) -> anyhow::Result<()> {
-    let wasm_path = generate_and_build_client(stub_def, offline).await?;
+    let wasm_path = generate_and_build_client(stub_def, offline, reproducible).await?;
 
     fs::copy(wasm_path, dest_wasm).context("Failed to copy the WASM file to the destination")?;
     fs::create_dir_all(dest_wit_root).context("Failed to create the target WIT root directory")?;
@@ -74,38 +85,115 @@ pub fn generate_and_copy_client_wit(
 pub async fn generate_and_build_client(
     stub_def: &StubDefinition,
     offline: bool,
+    reproducible: bool,
 ) -> anyhow::Result<PathBuf> {
     let _ = generate_client_wit_dir(stub_def)?;
     generate_client_cargo_toml(stub_def).context("Failed to generate the Cargo.toml file")?;
     generate_stub_source(stub_def).context("Failed to generate the client Rust source")?;
 
-    compile(
-        &stub_def
-            .config
-            .client_root
-            .canonicalize()
-            .with_context(|| {
-                anyhow!(
-                    "Failed to canonicalize client target root {}",
-                    stub_def.config.client_root.log_color_error_highlight()
-                )
-            })?,
-        offline,
-    )
-    .await
-    .context("Failed to compile the generated client")?;
-
-    let wasm_path = stub_def
+    let client_root = stub_def
         .config
         .client_root
-        .join("target")
+        .canonicalize()
+        .with_context(|| {
+            anyhow!(
+                "Failed to canonicalize client target root {}",
+                stub_def.config.client_root.log_color_error_highlight()
+            )
+        })?;
+
+    let cache = match stub_cache::cache_dir() {
+        Some(cache_dir) => {
+            StubCacheKey::new(stub_def, &client_root, reproducible)?.map(|key| (cache_dir, key))
+        }
+        None => None,
+    };
+
+    if let Some((cache_dir, key)) = &cache {
+        if let Some(wasm_path) = stub_cache::lookup(cache_dir, key) {
+            log_action(
+                "Reusing",
+                format!("cached client WASM {}", wasm_path.log_color_highlight()),
+            );
+            return Ok(wasm_path);
+        }
+    }
+
+    let wasm_path = if reproducible {
+        build_reproducible_client(stub_def, &client_root, offline).await?
+    } else {
+        let target_dir = client_root.join("target");
+        compile(&client_root, &target_dir, offline)
+            .await
+            .context("Failed to compile the generated client")?;
+        client_wasm_path(stub_def, &target_dir)
+    };
+
+    if let Some((cache_dir, key)) = &cache {
+        // A failing cache must not fail the build
+        if let Err(error) = stub_cache::store(cache_dir, key, &wasm_path) {
+            log_warn_action(
+                "Skipping",
+                format!("storing client WASM in the stub cache: {:#}", error),
+            );
+        }
+    }
+
+    Ok(wasm_path)
+}
+
+/// Builds the client twice into separate target dirs, and checks that the normalized
+/// WASM binaries are identical
+async fn build_reproducible_client(
+    stub_def: &StubDefinition,
+    client_root: &Path,
+    offline: bool,
+) -> anyhow::Result<PathBuf> {
+    let build = |target_dir: PathBuf| async move {
+        compile_reproducible(client_root, &target_dir, offline)
+            .await
+            .context("Failed to compile the generated client")?;
+
+        let wasm_path = client_wasm_path(stub_def, &target_dir);
+        let wasm = normalize_wasm(&fs::read(&wasm_path)?).with_context(|| {
+            anyhow!(
+                "Failed to normalize client WASM {}",
+                wasm_path.log_color_error_highlight()
+            )
+        })?;
+        fs::write(&wasm_path, &wasm)?;
+
+        Ok::<_, anyhow::Error>((wasm_path, wasm))
+    };
+
+    log_action("Building", "reproducible client");
+    let (wasm_path, wasm) = build(client_root.join("target")).await?;
+
+    log_action("Verifying", "reproducible client with a second build");
+    let verify_target_dir = client_root.join("target-verify");
+    let (verify_wasm_path, verify_wasm) = build(verify_target_dir.clone()).await?;
+
+    if wasm != verify_wasm {
+        bail!(
+            "Reproducible client build verification failed, {} and {} differ",
+            wasm_path.log_color_error_highlight(),
+            verify_wasm_path.log_color_error_highlight()
+        );
+    }
+
+    fs::remove(&verify_target_dir)?;
+
+    Ok(wasm_path)
+}
+
+fn client_wasm_path(stub_def: &StubDefinition, target_dir: &Path) -> PathBuf {
+    target_dir
         .join("wasm32-wasip1")
         .join("release")
         .join(format!(
             "{}.wasm",
             stub_def.client_crate_name().to_snake_case()
-        ));
-    Ok(wasm_path)
+        ))
 }
 
 pub fn generate_client_wit_dir(stub_def: &StubDefinition) -> anyhow::Result<ResolvedWitDir> {
@@ -12,12 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::wasm_rpc_stubgen::reproducible::{reproducible_build_env, EnvOverrides};
 use cargo_component::config::{CargoArguments, Config};
 use cargo_component::{load_component_metadata, load_metadata, run_cargo_command};
 use cargo_component_core::terminal::{Color, Terminal, Verbosity};
 use std::path::Path;
 
-pub async fn compile(root: &Path, offline: bool) -> anyhow::Result<()> {
+/// Compiles the crate into the given target dir.
+///
+/// The target dir is always set explicitly, so an inherited CARGO_TARGET_DIR or a target-dir
+/// configured in a parent .cargo/config.toml (e.g. the one of the application) cannot make
+/// the build share, and wait for the lock of, another target dir. Downloaded dependencies
+/// are still shared through the cargo home.
+pub async fn compile(root: &Path, target_dir: &Path, offline: bool) -> anyhow::Result<()> {
+    let _env = EnvOverrides::set(vec![("CARGO_TARGET_DIR".to_string(), target_dir.into())]);
+    compile_with_current_env(root, offline).await
+}
+
+async fn compile_with_current_env(root: &Path, offline: bool) -> anyhow::Result<()> {
     let current_dir = std::env::current_dir()?;
     std::env::set_current_dir(root)?;
 
@@ -54,3 +66,13 @@ pub async fn compile(root: &Path, offline: bool) -> anyhow::Result<()> {
     std::env::set_current_dir(current_dir)?;
     Ok(())
 }
+
+/// Compiles with pinned build settings and remapped paths into the given target dir
+pub async fn compile_reproducible(
+    root: &Path,
+    target_dir: &Path,
+    offline: bool,
+) -> anyhow::Result<()> {
+    let _env = EnvOverrides::set(reproducible_build_env(root, target_dir));
+    compile_with_current_env(root, offline).await
+}
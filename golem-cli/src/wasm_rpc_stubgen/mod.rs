@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod c;
 pub mod cargo;
 pub mod commands;
 pub mod compilation;
 pub mod naming;
+pub mod reproducible;
 pub mod rust;
 pub mod stub;
+pub mod stub_cache;
 pub mod wit_encode;
 pub mod wit_generate;
 pub mod wit_resolve;
@@ -25,3 +28,4 @@ pub mod wit_resolve;
 pub const WIT_BINDGEN_VERSION: &str = "0.40.0";
 pub const WASI_WIT_VERSION: &str = "0.2.0";
 pub const GOLEM_RPC_WIT_VERSION: &str = "0.2.0";
+pub const SERDE_VERSION: &str = "1.0";
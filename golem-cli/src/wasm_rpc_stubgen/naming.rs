@@ -9,6 +9,7 @@ pub mod wit {
 
     pub static CLIENT_WIT_FILE_NAME: &str = "client.wit";
     pub static EXPORTS_WIT_FILE_NAME: &str = "exports.wit";
+    pub static MOCK_WIT_FILE_NAME: &str = "mock.wit";
 
     pub fn client_parser_package_name(
         package_name: &wit_parser::PackageName,
@@ -30,10 +31,43 @@ pub mod wit {
         )
     }
 
+    pub fn mock_encoder_package_name(
+        package_name: &wit_parser::PackageName,
+    ) -> wit_encoder::PackageName {
+        wit_encoder::PackageName::new(
+            package_name.namespace.clone(),
+            format!("{}-mock", package_name.name),
+            package_name.version.clone(),
+        )
+    }
+
+    pub fn mock_world_name(source_world: &wit_parser::World) -> String {
+        format!("{}-mock", source_world.name)
+    }
+
     pub fn client_interface_name(source_world: &wit_parser::World) -> String {
         format!("{}-client", source_world.name)
     }
 
+    /// Main package name used for the WIT of an external component, which is derived from the
+    /// component name, as the decoded component WIT does not contain the original one
+    pub fn external_component_parser_package_name(
+        component_name: &str,
+    ) -> Option<wit_parser::PackageName> {
+        let (namespace, name) = component_name.split_once(':')?;
+        let is_valid_part = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        };
+        (is_valid_part(namespace) && is_valid_part(name)).then(|| wit_parser::PackageName {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: None,
+        })
+    }
+
     pub fn exports_parser_package_name(
         package_name: &wit_parser::PackageName,
     ) -> wit_parser::PackageName {
@@ -131,6 +165,10 @@ pub mod wit {
         format!("{}_{}", package_name.namespace(), package_name.name())
     }
 
+    pub fn package_dep_dir_name_with_suffix(package_dir_name: &str, index: usize) -> String {
+        format!("{package_dir_name}-{index}")
+    }
+
     pub fn package_merged_wit_name(package_name: &wit_parser::PackageName) -> String {
         format!("{}_{}.wit", package_name.namespace, package_name.name)
     }
@@ -156,6 +194,21 @@ pub mod wit {
     }
 }
 
+pub mod c {
+    use heck::ToShoutySnakeCase;
+
+    pub static MAKEFILE_FRAGMENT: &str = "client.mk";
+    pub static EXAMPLE: &str = "example.c";
+
+    pub fn caller_world_name(client_world_name: &str) -> String {
+        format!("{}-caller", client_world_name)
+    }
+
+    pub fn make_variable_prefix(caller_world_name: &str) -> String {
+        caller_world_name.to_shouty_snake_case()
+    }
+}
+
 pub mod rust {
     use crate::wasm_rpc_stubgen::stub::{FunctionStub, InterfaceStub};
     use heck::{ToSnakeCase, ToUpperCamelCase};
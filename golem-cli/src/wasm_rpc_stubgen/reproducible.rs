@@ -0,0 +1,250 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail};
+use std::ffi::OsString;
+use std::path::Path;
+
+/// Path prefix used instead of the client crate root in reproducible builds
+const REMAPPED_CLIENT_ROOT: &str = "/golem-rpc-client";
+/// Path prefix used instead of the cargo home in reproducible builds
+const REMAPPED_CARGO_HOME: &str = "/cargo";
+
+/// Custom sections which are kept when normalizing, all other custom sections (producers,
+/// debug info, build ids, etc.) describe the build environment and are not needed at runtime
+const KEPT_CUSTOM_SECTIONS: &[&str] = &["name", "component-name"];
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+const CORE_MODULE_VERSION: &[u8] = &[0x01, 0x00, 0x00, 0x00];
+
+const CUSTOM_SECTION_ID: u8 = 0;
+const COMPONENT_CORE_MODULE_SECTION_ID: u8 = 1;
+const COMPONENT_COMPONENT_SECTION_ID: u8 = 4;
+
+/// Environment variables passed to cargo for reproducible client builds:
+/// - pins the build timestamp and the release profile settings which affect code generation
+/// - remaps the absolute client and cargo home paths, so they are not embedded into the WASM
+pub fn reproducible_build_env(client_root: &Path, target_dir: &Path) -> Vec<(String, OsString)> {
+    let mut rustflags = existing_rustflags();
+    rustflags.push(remap_path_prefix(client_root, REMAPPED_CLIENT_ROOT));
+    if let Some(cargo_home) = cargo_home() {
+        rustflags.push(remap_path_prefix(&cargo_home, REMAPPED_CARGO_HOME));
+    }
+
+    vec![
+        ("SOURCE_DATE_EPOCH".to_string(), "0".into()),
+        ("CARGO_TARGET_DIR".to_string(), target_dir.into()),
+        ("CARGO_INCREMENTAL".to_string(), "0".into()),
+        (
+            "CARGO_PROFILE_RELEASE_CODEGEN_UNITS".to_string(),
+            "1".into(),
+        ),
+        (
+            "CARGO_PROFILE_RELEASE_INCREMENTAL".to_string(),
+            "false".into(),
+        ),
+        ("CARGO_PROFILE_RELEASE_DEBUG".to_string(), "false".into()),
+        (
+            "CARGO_ENCODED_RUSTFLAGS".to_string(),
+            rustflags.join("\x1f").into(),
+        ),
+    ]
+}
+
+fn existing_rustflags() -> Vec<String> {
+    if let Ok(flags) = std::env::var("CARGO_ENCODED_RUSTFLAGS") {
+        flags
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(|flag| flag.to_string())
+            .collect()
+    } else if let Ok(flags) = std::env::var("RUSTFLAGS") {
+        flags
+            .split_whitespace()
+            .map(|flag| flag.to_string())
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+fn remap_path_prefix(from: &Path, to: &str) -> String {
+    format!("--remap-path-prefix={}={}", from.display(), to)
+}
+
+fn cargo_home() -> Option<std::path::PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(|cargo_home| cargo_home.into())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+}
+
+/// Sets environment variables until dropped, then restores their previous values
+pub struct EnvOverrides {
+    previous: Vec<(String, Option<OsString>)>,
+}
+
+impl EnvOverrides {
+    pub fn set(vars: Vec<(String, OsString)>) -> Self {
+        let previous = vars
+            .into_iter()
+            .map(|(name, value)| {
+                let previous = std::env::var_os(&name);
+                std::env::set_var(&name, value);
+                (name, previous)
+            })
+            .collect();
+        Self { previous }
+    }
+}
+
+impl Drop for EnvOverrides {
+    fn drop(&mut self) {
+        for (name, previous) in self.previous.drain(..).rev() {
+            match previous {
+                Some(value) => std::env::set_var(&name, value),
+                None => std::env::remove_var(&name),
+            }
+        }
+    }
+}
+
+/// Removes build environment dependent custom sections from a core module or a component,
+/// including its nested modules and components
+pub fn normalize_wasm(wasm: &[u8]) -> anyhow::Result<Vec<u8>> {
+    if wasm.len() < 8 || &wasm[0..4] != WASM_MAGIC {
+        bail!("Not a WASM binary");
+    }
+    let is_component = &wasm[4..8] != CORE_MODULE_VERSION;
+
+    let mut result = Vec::with_capacity(wasm.len());
+    result.extend_from_slice(&wasm[0..8]);
+
+    let mut offset = 8;
+    while offset < wasm.len() {
+        let section_id = wasm[offset];
+        offset += 1;
+        let section_size = read_leb128_u32(wasm, &mut offset)? as usize;
+        let section = wasm
+            .get(offset..offset + section_size)
+            .ok_or_else(|| anyhow!("Unexpected end of WASM section {}", section_id))?;
+        offset += section_size;
+
+        match section_id {
+            CUSTOM_SECTION_ID => {
+                let mut name_offset = 0;
+                let name_size = read_leb128_u32(section, &mut name_offset)? as usize;
+                let name = section
+                    .get(name_offset..name_offset + name_size)
+                    .ok_or_else(|| anyhow!("Unexpected end of WASM custom section name"))?;
+                if KEPT_CUSTOM_SECTIONS
+                    .iter()
+                    .any(|kept| kept.as_bytes() == name)
+                {
+                    write_section(&mut result, section_id, section);
+                }
+            }
+            COMPONENT_CORE_MODULE_SECTION_ID | COMPONENT_COMPONENT_SECTION_ID if is_component => {
+                write_section(&mut result, section_id, &normalize_wasm(section)?);
+            }
+            _ => write_section(&mut result, section_id, section),
+        }
+    }
+
+    Ok(result)
+}
+
+fn write_section(result: &mut Vec<u8>, section_id: u8, section: &[u8]) {
+    result.push(section_id);
+    write_leb128_u32(result, section.len() as u32);
+    result.extend_from_slice(section);
+}
+
+fn read_leb128_u32(bytes: &[u8], offset: &mut usize) -> anyhow::Result<u32> {
+    let mut result: u32 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes
+            .get(*offset)
+            .ok_or_else(|| anyhow!("Unexpected end of WASM binary"))?;
+        *offset += 1;
+        if shift >= 32 {
+            bail!("Invalid LEB128 encoded integer in WASM binary");
+        }
+        result |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+    }
+}
+
+fn write_leb128_u32(result: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            result.push(byte);
+            return;
+        }
+        result.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_rpc_stubgen::reproducible::{normalize_wasm, write_section};
+    use assert2::check;
+    use test_r::test;
+
+    fn custom_section(result: &mut Vec<u8>, name: &str, data: &[u8]) {
+        let mut section = vec![name.len() as u8];
+        section.extend_from_slice(name.as_bytes());
+        section.extend_from_slice(data);
+        write_section(result, 0, &section);
+    }
+
+    fn core_module(custom_sections: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut module = b"\0asm\x01\x00\x00\x00".to_vec();
+        // type section with a single () -> () function type
+        write_section(&mut module, 1, &[0x01, 0x60, 0x00, 0x00]);
+        for (name, data) in custom_sections {
+            custom_section(&mut module, name, data);
+        }
+        module
+    }
+
+    #[test]
+    fn normalize_core_module() {
+        let module = core_module(&[("producers", b"rustc 1.0"), ("name", b"names")]);
+        let expected = core_module(&[("name", b"names")]);
+
+        check!(normalize_wasm(&module).unwrap() == expected);
+    }
+
+    #[test]
+    fn normalize_nested_core_module() {
+        let mut component = b"\0asm\x0d\x00\x01\x00".to_vec();
+        write_section(
+            &mut component,
+            1,
+            &core_module(&[("producers", &[0xff; 200])]),
+        );
+        custom_section(&mut component, "producers", b"wit-component");
+
+        let mut expected = b"\0asm\x0d\x00\x01\x00".to_vec();
+        write_section(&mut expected, 1, &core_module(&[]));
+
+        check!(normalize_wasm(&component).unwrap() == expected);
+    }
+}
@@ -798,7 +798,9 @@ fn type_to_rust_ident(typ: &Type, def: &StubDefinition) -> anyhow::Result<TokenS
                 TypeDefKind::Handle(handle) => {
                     let (type_id, is_ref) = match handle {
                         Handle::Own(type_id) => (type_id, false),
-                        Handle::Borrow(type_id) => (type_id, true),
+                        Handle::Borrow(type_id) => {
+                            (type_id, def.config.borrow_mode.keeps_borrows())
+                        }
                     };
 
                     let root_ns = def.rust_root_namespace();
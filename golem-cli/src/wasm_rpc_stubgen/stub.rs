@@ -22,12 +22,13 @@ use anyhow::{anyhow, Context};
 use indexmap::IndexMap;
 use itertools::Itertools;
 use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use wit_parser::{
-    Function, FunctionKind, Interface, InterfaceId, Package, PackageId, PackageName, Resolve, Type,
-    TypeDef, TypeDefKind, TypeId, TypeOwner, World, WorldId, WorldItem, WorldKey,
+    Function, FunctionKind, Handle, Interface, InterfaceId, Package, PackageId, PackageName,
+    Resolve, Type, TypeDef, TypeDefKind, TypeId, TypeOwner, World, WorldId, WorldItem, WorldKey,
 };
 
 #[derive(Clone, Debug)]
@@ -41,12 +42,35 @@ pub struct StubConfig {
     pub seal_cargo_workspace: bool,
     pub component_name: AppComponentName,
     pub is_ephemeral: bool,
+    pub borrow_mode: ClientBorrowMode,
+    /// Additional derives for the data types of the generated client, e.g. serde::Serialize
+    pub client_derives: Vec<String>,
+}
+
+/// Controls how borrowed resource handles in function parameters are exposed by the generated client
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClientBorrowMode {
+    /// Borrowed handles stay borrowed in the client functions
+    #[default]
+    Borrow,
+    /// Borrowed handles are exposed as owned handles, the client resource is consumed by the call
+    /// (and the remote resource is dropped with it), for guest languages with limited borrow support
+    OwnOnly,
+}
+
+impl ClientBorrowMode {
+    pub fn keeps_borrows(&self) -> bool {
+        *self == ClientBorrowMode::Borrow
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct RustDependencyOverride {
     pub path_override: Option<PathBuf>,
     pub version_override: Option<String>,
+    pub git_override: Option<String>,
+    pub git_branch_override: Option<String>,
 }
 
 pub struct StubDefinition {
@@ -92,7 +116,7 @@ impl StubDefinition {
             .name
             .clone();
 
-        Ok(Self {
+        let def = Self {
             config,
             resolve: resolved_source.resolve,
             source_world_id,
@@ -103,7 +127,11 @@ impl StubDefinition {
             source_package_id: resolved_source.package_id,
             source_package_name,
             client_binding_mapping: BindingMapping::default(),
-        })
+        };
+
+        def.validate_resource_handles()?;
+
+        Ok(def)
     }
 
     // NOTE: In the following "getters" below we trust that getting entities from the resolver
@@ -346,6 +374,171 @@ impl StubDefinition {
         })
     }
 
+    /// Only handles of resources exported by the component can be passed over RPC (as remote
+    /// resource references), so we fail early with guidance instead of generating a client crate
+    /// which fails to compile.
+    fn validate_resource_handles(&self) -> anyhow::Result<()> {
+        let exported_resource_ids = self
+            .partition_world_items(&self.source_world().exports)
+            .interfaces
+            .into_iter()
+            .flat_map(|(_, interface)| interface.types.values().copied())
+            .filter(|type_id| {
+                self.resolve
+                    .types
+                    .get(*type_id)
+                    .is_some_and(|type_def| type_def.kind == TypeDefKind::Resource)
+            })
+            .collect::<HashSet<_>>();
+
+        let mut problems = Vec::<String>::new();
+        for interface in self.stub_imported_interfaces() {
+            let constructor_params = interface
+                .constructor_params
+                .iter()
+                .flatten()
+                .map(|param| ("constructor".to_string(), Some(&param.name), param.typ));
+            let function_params = interface.all_functions().flat_map(|(function, _)| {
+                function
+                    .params
+                    .iter()
+                    .map(|param| (function.name.clone(), Some(&param.name), param.typ))
+            });
+            let function_results =
+                interface
+                    .all_functions()
+                    .filter_map(|(function, _)| match &function.results {
+                        FunctionResultStub::Anon(typ) => Some((function.name.clone(), None, *typ)),
+                        FunctionResultStub::Unit | FunctionResultStub::SelfType => None,
+                    });
+
+            for (function_name, param_name, typ) in constructor_params
+                .chain(function_params)
+                .chain(function_results)
+            {
+                let mut resource_ids = Vec::new();
+                self.collect_handle_resource_ids(&typ, &mut HashSet::new(), &mut resource_ids);
+
+                for resource_id in resource_ids {
+                    if exported_resource_ids.contains(&resource_id) {
+                        continue;
+                    }
+
+                    problems.push(format!(
+                        "{}.{}, {}: handle of resource {}",
+                        interface.name,
+                        function_name,
+                        match param_name {
+                            Some(param_name) => format!("parameter {}", param_name),
+                            None => "result".to_string(),
+                        },
+                        self.resource_display_name(resource_id),
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Cannot generate WASM RPC client for {}, the following functions use resources which cannot be passed over RPC:\n{}\n\
+             Only handles of resources exported by the component can be represented over RPC \
+             (as remote resource references). Export the resource from one of the component's interfaces, \
+             or pass an identifier or plain data instead of the resource handle.",
+            self.config.component_name,
+            problems.iter().map(|problem| format!("  - {problem}")).join("\n")
+        ))
+    }
+
+    fn collect_handle_resource_ids(
+        &self,
+        typ: &Type,
+        visited: &mut HashSet<TypeId>,
+        resource_ids: &mut Vec<TypeId>,
+    ) {
+        let Type::Id(type_id) = typ else {
+            return;
+        };
+        if !visited.insert(*type_id) {
+            return;
+        }
+        let Some(type_def) = self.resolve.types.get(*type_id) else {
+            return;
+        };
+
+        match &type_def.kind {
+            TypeDefKind::Handle(Handle::Own(resource_id))
+            | TypeDefKind::Handle(Handle::Borrow(resource_id)) => {
+                resource_ids.push(self.resolve_type_alias(*resource_id))
+            }
+            TypeDefKind::Record(record) => {
+                for field in &record.fields {
+                    self.collect_handle_resource_ids(&field.ty, visited, resource_ids);
+                }
+            }
+            TypeDefKind::Tuple(tuple) => {
+                for typ in &tuple.types {
+                    self.collect_handle_resource_ids(typ, visited, resource_ids);
+                }
+            }
+            TypeDefKind::Variant(variant) => {
+                for case in &variant.cases {
+                    if let Some(typ) = &case.ty {
+                        self.collect_handle_resource_ids(typ, visited, resource_ids);
+                    }
+                }
+            }
+            TypeDefKind::Option(typ) | TypeDefKind::List(typ) | TypeDefKind::Type(typ) => {
+                self.collect_handle_resource_ids(typ, visited, resource_ids);
+            }
+            TypeDefKind::Result(result) => {
+                for typ in result.ok.iter().chain(result.err.iter()) {
+                    self.collect_handle_resource_ids(typ, visited, resource_ids);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve_type_alias(&self, type_id: TypeId) -> TypeId {
+        match self
+            .resolve
+            .types
+            .get(type_id)
+            .map(|type_def| &type_def.kind)
+        {
+            Some(TypeDefKind::Type(Type::Id(target_id))) => self.resolve_type_alias(*target_id),
+            _ => type_id,
+        }
+    }
+
+    fn resource_display_name(&self, resource_id: TypeId) -> String {
+        let Some(type_def) = self.resolve.types.get(resource_id) else {
+            return format!("{:?}", resource_id);
+        };
+        let name = type_def.name.clone().unwrap_or_default();
+
+        match type_def.owner {
+            TypeOwner::Interface(interface_id) => {
+                let interface = self.resolve.interfaces.get(interface_id);
+                let interface_name = interface.and_then(|interface| interface.name.clone());
+                let package = interface
+                    .and_then(|interface| interface.package)
+                    .and_then(|package_id| self.resolve.packages.get(package_id));
+                match (package, interface_name) {
+                    (Some(package), Some(interface_name)) => {
+                        format!("{}.{}", package.name.interface_id(&interface_name), name)
+                    }
+                    (None, Some(interface_name)) => format!("{}.{}", interface_name, name),
+                    (_, None) => name,
+                }
+            }
+            TypeOwner::World(_) | TypeOwner::None => name,
+        }
+    }
+
     fn type_def_owner_package_ids(&self, type_def: &TypeDef, package_ids: &mut Vec<PackageId>) {
         let package_id = match type_def.owner {
             TypeOwner::World(_) => None,
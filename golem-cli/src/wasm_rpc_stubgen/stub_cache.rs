@@ -0,0 +1,196 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content-addressed cache of compiled client (stub) WASM binaries, shared between workspaces.
+//!
+//! Entries are keyed by the hash of the generated client crate (which is derived from the
+//! source WIT, and also contains the golem-rust dependency version), the stubgen version and
+//! the build mode.
+
+use crate::fs;
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use anyhow::{anyhow, Context};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+const CACHE_DIR_ENV_VAR: &str = "GOLEM_STUBGEN_CACHE_DIR";
+const ENTRY_EXTENSION: &str = "wasm";
+
+/// Cache directory, can be overridden with the GOLEM_STUBGEN_CACHE_DIR env var
+pub fn cache_dir() -> Option<PathBuf> {
+    match std::env::var_os(CACHE_DIR_ENV_VAR) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => dirs::cache_dir().map(|dir| dir.join("golem-stubgen")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StubCacheKey(String);
+
+impl StubCacheKey {
+    /// Computes the key from the generated client crate, returns None if the client depends on
+    /// a golem-rust path or git override, as the content of those is not covered by the key
+    pub fn new(
+        stub_def: &StubDefinition,
+        client_root: &Path,
+        reproducible: bool,
+    ) -> anyhow::Result<Option<Self>> {
+        let golem_rust_override = &stub_def.config.golem_rust_override;
+        if golem_rust_override.path_override.is_some() || golem_rust_override.git_override.is_some()
+        {
+            return Ok(None);
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(crate::version().as_bytes());
+        hasher.update(
+            if reproducible {
+                "#reproducible"
+            } else {
+                "#default"
+            }
+            .as_bytes(),
+        );
+
+        let mut files = Vec::new();
+        for entry in [
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("src"),
+            PathBuf::from(naming::wit::WIT_DIR),
+        ] {
+            for file in WalkDir::new(client_root.join(entry)) {
+                let file = file.with_context(|| {
+                    anyhow!(
+                        "Failed to list client crate files in {}",
+                        client_root.display()
+                    )
+                })?;
+                if file.file_type().is_file() {
+                    files.push(file.into_path());
+                }
+            }
+        }
+        files.sort();
+
+        for file in files {
+            let relative_path = file.strip_prefix(client_root)?;
+            hasher.update(relative_path.to_string_lossy().as_bytes());
+            hasher.update(b"\0");
+            hasher.update(&fs::read(&file)?);
+            hasher.update(b"\0");
+        }
+
+        Ok(Some(Self(hasher.finalize().to_hex().to_string())))
+    }
+
+    fn entry_path(&self, cache_dir: &Path) -> PathBuf {
+        cache_dir.join(format!("{}.{}", self.0, ENTRY_EXTENSION))
+    }
+}
+
+/// Returns the path of the cached WASM, if there is one for the key
+pub fn lookup(cache_dir: &Path, key: &StubCacheKey) -> Option<PathBuf> {
+    let path = key.entry_path(cache_dir);
+    path.is_file().then_some(path)
+}
+
+/// Stores the WASM through a temporary file, so concurrent builds never see partial entries
+pub fn store(cache_dir: &Path, key: &StubCacheKey, wasm: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(cache_dir)?;
+    let temp_path = cache_dir.join(format!(".{}.{}", key.0, Uuid::new_v4()));
+    fs::copy(wasm, &temp_path)?;
+    let entry_path = key.entry_path(cache_dir);
+    std::fs::rename(&temp_path, &entry_path)
+        .with_context(|| anyhow!("Failed to store stub cache entry {}", entry_path.display()))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StubCacheStats {
+    pub entries: usize,
+    pub size: u64,
+}
+
+pub fn stats(cache_dir: &Path) -> anyhow::Result<StubCacheStats> {
+    let mut stats = StubCacheStats::default();
+    for path in entries(cache_dir)? {
+        stats.entries += 1;
+        stats.size += fs::metadata(&path)?.len();
+    }
+    Ok(stats)
+}
+
+/// Removes all entries, returns the stats of the removed entries. Only the entries are removed,
+/// as the cache dir can be overridden to point to a shared directory.
+pub fn clean(cache_dir: &Path) -> anyhow::Result<StubCacheStats> {
+    let stats = stats(cache_dir)?;
+    for path in entries(cache_dir)? {
+        fs::remove(&path)?;
+    }
+    Ok(stats)
+}
+
+fn entries(cache_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if !cache_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(cache_dir)
+        .with_context(|| anyhow!("Failed to read stub cache dir {}", cache_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some(ENTRY_EXTENSION)
+        {
+            entries.push(path);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::wasm_rpc_stubgen::stub_cache::{clean, lookup, stats, store, StubCacheKey};
+    use assert2::check;
+    use tempfile::TempDir;
+
+    #[test]
+    fn store_lookup_and_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        let wasm = temp_dir.path().join("client.wasm");
+        std::fs::write(&wasm, b"\0asm").unwrap();
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::fs::write(cache_dir.join("unrelated.txt"), b"keep").unwrap();
+
+        let key = StubCacheKey("abcd".to_string());
+        check!(lookup(&cache_dir, &key).is_none());
+
+        store(&cache_dir, &key, &wasm).unwrap();
+        let cached = lookup(&cache_dir, &key).unwrap();
+        check!(std::fs::read(&cached).unwrap() == b"\0asm");
+
+        let stats = stats(&cache_dir).unwrap();
+        check!(stats.entries == 1);
+        check!(stats.size == 4);
+
+        let removed = clean(&cache_dir).unwrap();
+        check!(removed == stats);
+        check!(lookup(&cache_dir, &key).is_none());
+        check!(cache_dir.join("unrelated.txt").exists());
+    }
+}
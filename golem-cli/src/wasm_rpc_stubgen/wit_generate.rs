@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::fs;
-use crate::fs::{OverwriteSafeAction, OverwriteSafeActions, PathExtra};
+use crate::fs::{FileChange, OverwriteSafeAction, OverwriteSafeActions, PathExtra};
 use crate::log::{log_action, log_action_plan, log_warn_action, LogColorize, LogIndent};
 use crate::wasm_rpc_stubgen::naming::wit::package_dep_dir_name_from_encoder;
 use crate::wasm_rpc_stubgen::stub::{
@@ -390,10 +390,23 @@ pub enum UpdateCargoToml {
     NoUpdate,
 }
 
+/// Strategy for client packages whose default dependency directory in the destination WIT root
+/// is already used by another package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientPackageCollision {
+    /// Fail, listing all the conflicting packages
+    Fail,
+    /// Use the first free dependency directory with a numeric suffix, e.g. `ns_name-client-2`
+    AutoSuffix,
+    /// Use the given dependency directory name for the client package
+    Rename(String),
+}
+
 pub struct AddClientAsDepConfig {
     pub client_wit_root: PathBuf,
     pub dest_wit_root: PathBuf,
     pub update_cargo_toml: UpdateCargoToml,
+    pub package_collision: ClientPackageCollision,
 }
 
 pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyhow::Result<()> {
@@ -408,6 +421,59 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
 
     let _indent = LogIndent::new();
 
+    let (actions, package_names_to_package_path) = add_client_as_dependency_actions(&config)?;
+
+    // Check overwrites
+    let forbidden_overwrites = actions.run(true, false, log_action_plan)?;
+    if !forbidden_overwrites.is_empty() {
+        eprintln!("The following files would have been overwritten with new content:");
+        for action in forbidden_overwrites {
+            eprintln!("  {}", action.target().display());
+        }
+        eprintln!();
+        eprintln!("Use --overwrite to force overwrite.");
+    }
+
+    // Optionally update Cargo.toml
+    if let Some(target_parent) = config.dest_wit_root.parent() {
+        let target_cargo_toml = target_parent.join("Cargo.toml");
+        if target_cargo_toml.exists() && target_cargo_toml.is_file() {
+            if config.update_cargo_toml != UpdateCargoToml::NoUpdate {
+                cargo::is_cargo_component_toml(&target_cargo_toml).context(format!(
+                    "The file {target_cargo_toml:?} is not a valid cargo-component project"
+                ))?;
+                cargo::add_cargo_package_component_deps(
+                    &target_cargo_toml,
+                    package_names_to_package_path,
+                )?;
+            }
+        } else if config.update_cargo_toml == UpdateCargoToml::Update {
+            return Err(anyhow!(
+                "Cannot update {:?} file because it does not exist or is not a file",
+                target_cargo_toml.log_color_highlight()
+            ));
+        }
+    } else if config.update_cargo_toml == UpdateCargoToml::Update {
+        return Err(anyhow!("Cannot update the Cargo.toml file because parent directory of the destination WIT root does not exist."));
+    }
+
+    Ok(())
+}
+
+/// Returns the file changes that adding the client as a dependency would make, without
+/// applying them
+pub fn add_client_as_dependency_changes(
+    config: &AddClientAsDepConfig,
+) -> anyhow::Result<Vec<FileChange>> {
+    add_client_as_dependency_actions(config)?.0.changes()
+}
+
+fn add_client_as_dependency_actions(
+    config: &AddClientAsDepConfig,
+) -> anyhow::Result<(
+    OverwriteSafeActions,
+    BTreeMap<wit_parser::PackageName, PathBuf>,
+)> {
     let client_resolved_wit_root = ResolvedWitDir::new(&config.client_wit_root)?;
     let client_package = client_resolved_wit_root.main_package()?;
 
@@ -418,43 +484,31 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
     let mut actions = OverwriteSafeActions::new();
     let mut package_names_to_package_path = BTreeMap::<wit_parser::PackageName, PathBuf>::new();
 
+    let target_dirs =
+        client_package_target_dirs(config, &client_resolved_wit_root, &dest_resolved_wit_root)?;
+
     for (package_name, package_id) in &client_resolved_wit_root.resolve.package_names {
         let package_sources = client_resolved_wit_root
             .package_sources
             .get(package_id)
             .ok_or_else(|| anyhow!("Failed to get package sources for {}", package_name))?;
 
-        if *package_id == client_resolved_wit_root.package_id {
-            let package_path = naming::wit::package_wit_dep_dir_from_parser(package_name);
-
-            package_names_to_package_path.insert(package_name.clone(), package_path.clone());
+        let target_dir = target_dirs
+            .get(package_name)
+            .ok_or_else(|| anyhow!("Failed to get target directory for {}", package_name))?;
 
-            for source in &package_sources.files {
-                actions.add(OverwriteSafeAction::CopyFile {
-                    source: source.clone(),
-                    target: config
-                        .dest_wit_root
-                        .join(naming::wit::DEPS_DIR)
-                        .join(naming::wit::package_dep_dir_name_from_parser(package_name))
-                        .join(PathExtra::new(&source).file_name_to_string()?),
-                });
-            }
-        } else {
-            package_names_to_package_path.insert(
-                package_name.clone(),
-                naming::wit::package_wit_dep_dir_from_package_dir_name(
-                    &PathExtra::new(&package_sources.dir).file_name_to_string()?,
-                ),
-            );
+        package_names_to_package_path.insert(
+            package_name.clone(),
+            naming::wit::package_wit_dep_dir_from_package_dir_name(
+                &PathExtra::new(target_dir).file_name_to_string()?,
+            ),
+        );
 
-            for source in &package_sources.files {
-                actions.add(OverwriteSafeAction::CopyFile {
-                    source: source.clone(),
-                    target: config
-                        .dest_wit_root
-                        .join(PathExtra::new(&source).strip_prefix(&config.client_wit_root)?),
-                });
-            }
+        for source in &package_sources.files {
+            actions.add(OverwriteSafeAction::CopyFile {
+                source: source.clone(),
+                target: target_dir.join(PathExtra::new(&source).file_name_to_string()?),
+            });
         }
     }
 
@@ -493,41 +547,124 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
         target: dest_main_package_sources.files[0].clone(),
     });
 
-    // Check overwrites
-    let forbidden_overwrites = actions.run(true, false, log_action_plan)?;
-    if !forbidden_overwrites.is_empty() {
-        eprintln!("The following files would have been overwritten with new content:");
-        for action in forbidden_overwrites {
-            eprintln!("  {}", action.target().display());
-        }
-        eprintln!();
-        eprintln!("Use --overwrite to force overwrite.");
+    Ok((actions, package_names_to_package_path))
+}
+
+/// Selects the destination directories for the client packages.
+///
+/// Packages which are already present in the destination are updated in place. New packages use
+/// their default dependency directory, unless it is already used by another package, in which
+/// case the configured [ClientPackageCollision] strategy is applied. All conflicts which cannot be
+/// resolved are reported together.
+fn client_package_target_dirs(
+    config: &AddClientAsDepConfig,
+    client_resolved_wit_root: &ResolvedWitDir,
+    dest_resolved_wit_root: &ResolvedWitDir,
+) -> anyhow::Result<BTreeMap<wit_parser::PackageName, PathBuf>> {
+    let deps_dir = config.dest_wit_root.join(naming::wit::DEPS_DIR);
+
+    let mut used_dirs = HashMap::<PathBuf, String>::new();
+    for (package_name, package_id) in &dest_resolved_wit_root.resolve.package_names {
+        used_dirs.insert(
+            dest_resolved_wit_root
+                .package_sources(*package_id)?
+                .dir
+                .clone(),
+            package_name.to_string(),
+        );
     }
 
-    // Optionally update Cargo.toml
-    if let Some(target_parent) = config.dest_wit_root.parent() {
-        let target_cargo_toml = target_parent.join("Cargo.toml");
-        if target_cargo_toml.exists() && target_cargo_toml.is_file() {
-            if config.update_cargo_toml != UpdateCargoToml::NoUpdate {
-                cargo::is_cargo_component_toml(&target_cargo_toml).context(format!(
-                    "The file {target_cargo_toml:?} is not a valid cargo-component project"
-                ))?;
-                cargo::add_cargo_package_component_deps(
-                    &target_cargo_toml,
-                    package_names_to_package_path,
-                )?;
+    let mut target_dirs = BTreeMap::<wit_parser::PackageName, PathBuf>::new();
+    let mut conflicts = Vec::<String>::new();
+
+    for (package_name, package_id) in &client_resolved_wit_root.resolve.package_names {
+        if let Some(dest_package_id) = dest_resolved_wit_root
+            .resolve
+            .package_names
+            .get(package_name)
+        {
+            if *dest_package_id == dest_resolved_wit_root.package_id {
+                conflicts.push(format!(
+                    "{} is the main package of the destination",
+                    package_name.to_string().log_color_error_highlight()
+                ));
+            } else {
+                target_dirs.insert(
+                    package_name.clone(),
+                    dest_resolved_wit_root
+                        .package_sources(*dest_package_id)?
+                        .dir
+                        .clone(),
+                );
             }
-        } else if config.update_cargo_toml == UpdateCargoToml::Update {
-            return Err(anyhow!(
-                "Cannot update {:?} file because it does not exist or is not a file",
-                target_cargo_toml.log_color_highlight()
-            ));
+            continue;
+        }
+
+        let is_client_package = *package_id == client_resolved_wit_root.package_id;
+        let default_dir_name = if is_client_package {
+            naming::wit::package_dep_dir_name_from_parser(package_name)
+        } else {
+            PathExtra::new(&client_resolved_wit_root.package_sources(*package_id)?.dir)
+                .file_name_to_string()?
+        };
+        let default_dir = deps_dir.join(&default_dir_name);
+
+        let Some(used_by) = used_dirs.get(&default_dir) else {
+            used_dirs.insert(default_dir.clone(), package_name.to_string());
+            target_dirs.insert(package_name.clone(), default_dir);
+            continue;
+        };
+
+        let target_dir = match &config.package_collision {
+            ClientPackageCollision::AutoSuffix => (2..)
+                .map(|index| {
+                    deps_dir.join(naming::wit::package_dep_dir_name_with_suffix(
+                        &default_dir_name,
+                        index,
+                    ))
+                })
+                .find(|dir| !used_dirs.contains_key(dir) && !dir.exists()),
+            ClientPackageCollision::Rename(dir_name) if is_client_package => {
+                let dir = deps_dir.join(dir_name);
+                match used_dirs.get(&dir) {
+                    Some(renamed_used_by) => {
+                        conflicts.push(format!(
+                            "{} cannot be renamed to dependency directory {}, it is already used by {}",
+                            package_name.to_string().log_color_error_highlight(),
+                            dir_name.log_color_highlight(),
+                            renamed_used_by.log_color_highlight()
+                        ));
+                        None
+                    }
+                    None => Some(dir),
+                }
+            }
+            ClientPackageCollision::Fail | ClientPackageCollision::Rename(_) => {
+                conflicts.push(format!(
+                    "{} dependency directory {} is already used by {}",
+                    package_name.to_string().log_color_error_highlight(),
+                    default_dir_name.log_color_highlight(),
+                    used_by.log_color_highlight()
+                ));
+                None
+            }
+        };
+
+        if let Some(target_dir) = target_dir {
+            used_dirs.insert(target_dir.clone(), package_name.to_string());
+            target_dirs.insert(package_name.clone(), target_dir);
         }
-    } else if config.update_cargo_toml == UpdateCargoToml::Update {
-        return Err(anyhow!("Cannot update the Cargo.toml file because parent directory of the destination WIT root does not exist."));
     }
 
-    Ok(())
+    if !conflicts.is_empty() {
+        bail!(
+            "Cannot add client packages to {}, conflicting package names:\n  {}",
+            config.dest_wit_root.log_color_highlight(),
+            conflicts.join("\n  ")
+        );
+    }
+
+    Ok(target_dirs)
 }
 
 trait ToEncoder {
@@ -619,6 +756,11 @@ impl ToEncoder for wit_parser::Type {
                             wit_parser::Handle::Own(type_id) => {
                                 wit_parser::Type::Id(*type_id).to_encoder(def)?
                             }
+                            wit_parser::Handle::Borrow(type_id)
+                                if !def.config.borrow_mode.keeps_borrows() =>
+                            {
+                                wit_parser::Type::Id(*type_id).to_encoder(def)?
+                            }
                             wit_parser::Handle::Borrow(type_id) => Type::borrow(
                                 wit_parser::Type::Id(*type_id).to_encoder(def)?.to_string(),
                             ),
@@ -714,6 +856,86 @@ fn remove_world_named_interface_imports(package: &mut Package, import_prefix: &s
     }
 }
 
+// Interfaces of these namespaces are provided by the runtime, so they are not mocked
+const RUNTIME_PROVIDED_NAMESPACES: &[&str] = &["wasi", "golem"];
+
+/// Generates a package with a world exporting the interfaces imported by the selected world of
+/// the source WIT root, so a mock implementing it can be composed into the source component
+pub fn generate_import_mock_package(
+    source: &ResolvedWitDir,
+    world: Option<&str>,
+) -> anyhow::Result<Package> {
+    let world_id = source.resolve.select_world(source.package_id, world)?;
+    let source_world = &source.resolve.worlds[world_id];
+    let source_package_name = &source.resolve.packages[source.package_id].name;
+
+    let mut mock_world = World::new(naming::wit::mock_world_name(source_world));
+    let mut any_export = false;
+    for item in source_world.imports.values() {
+        let wit_parser::WorldItem::Interface { id, .. } = item else {
+            continue;
+        };
+        // Inline interfaces cannot be exported by another component
+        let Some(interface_name) = source.resolve.id_of(*id) else {
+            continue;
+        };
+        let is_runtime_provided = source.resolve.interfaces[*id]
+            .package
+            .map(|package_id| &source.resolve.packages[package_id].name.namespace)
+            .is_some_and(|namespace| RUNTIME_PROVIDED_NAMESPACES.contains(&namespace.as_str()));
+        if !is_runtime_provided {
+            mock_world.named_interface_export(interface_name);
+            any_export = true;
+        }
+    }
+
+    if !any_export {
+        bail!(
+            "World {} does not import any interfaces which could be mocked",
+            source_world.name.log_color_error_highlight()
+        );
+    }
+
+    let mut package = Package::new(naming::wit::mock_encoder_package_name(source_package_name));
+    package.world(mock_world);
+
+    Ok(package)
+}
+
+/// Writes the mock package into the destination WIT root, with all the source packages as
+/// dependencies
+pub fn generate_and_copy_import_mock_wit(
+    source: &ResolvedWitDir,
+    world: Option<&str>,
+    dest_wit_root: &Path,
+) -> anyhow::Result<()> {
+    let mock_package = generate_import_mock_package(source, world)?;
+
+    for (package_id, package_source) in &source.package_sources {
+        let dep_dir = dest_wit_root.join(naming::wit::DEPS_DIR).join(
+            naming::wit::package_dep_dir_name_from_parser(
+                &source.resolve.packages[*package_id].name,
+            ),
+        );
+        fs::create_dir_all(&dep_dir)?;
+        for file in &package_source.files {
+            let file_name = file
+                .file_name()
+                .ok_or_else(|| anyhow!("Missing file name: {}", file.log_color_highlight()))?;
+            fs::copy(file, dep_dir.join(file_name))?;
+        }
+    }
+
+    fs::write_str(
+        dest_wit_root.join(naming::wit::MOCK_WIT_FILE_NAME),
+        mock_package.to_string(),
+    )?;
+
+    ResolvedWitDir::new(dest_wit_root).context("Failed to resolve the generated mock WIT")?;
+
+    Ok(())
+}
+
 pub fn extract_exports_as_wit_dep(wit_dir: &Path) -> anyhow::Result<()> {
     log_action(
         "Extracting",
@@ -774,6 +996,95 @@ pub fn extract_exports_as_wit_dep(wit_dir: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Writes the WIT of an already built component as a WIT directory, so it can be used as
+/// a stub source. The decoded component world is placed into a main package named after
+/// the component, as the original package name is not part of the component.
+pub fn extract_component_wasm_wit(
+    component_wasm: &[u8],
+    main_package_name: &PackageName,
+    wit_dir: &Path,
+) -> anyhow::Result<()> {
+    let decoded = wit_parser::decoding::decode(component_wasm)
+        .context("Failed to decode WIT from component WASM")?;
+    let wit_parser::decoding::DecodedWasm::Component(resolve, world_id) = decoded else {
+        bail!("Expected a WASM component, got a WIT package");
+    };
+
+    let root_package_id = resolve
+        .worlds
+        .get(world_id)
+        .and_then(|world| world.package)
+        .ok_or_else(|| anyhow!("Failed to get package of the decoded component world"))?;
+    let existing_main_package_id = resolve.package_names.get(main_package_name).cloned();
+
+    let mut encoded_wit_dir = EncodedWitDir::new(&resolve)?;
+
+    let mut root_worlds = encoded_wit_dir
+        .package(root_package_id)?
+        .items()
+        .iter()
+        .filter_map(|item| match item {
+            PackageItem::World(world) => Some(world.clone()),
+            PackageItem::Interface(_) => None,
+        })
+        .collect::<Vec<_>>();
+    for world in &mut root_worlds {
+        world.set_name(main_package_name.name.clone());
+    }
+
+    // Components which export interfaces from a package with the same name are merged into it,
+    // otherwise the root package is renamed
+    let main_package_id = existing_main_package_id.unwrap_or(root_package_id);
+    let main_package = {
+        let package = encoded_wit_dir.package(main_package_id)?;
+        if existing_main_package_id.is_some() {
+            for world in root_worlds {
+                package.world(world);
+            }
+        } else {
+            package.set_name(wit_encoder::PackageName::new(
+                main_package_name.namespace.clone(),
+                main_package_name.name.clone(),
+                None,
+            ));
+        }
+        package.clone()
+    };
+
+    let main_package_path = wit_dir.join(format!("{}.wit", main_package_name.name));
+    log_action(
+        "Writing",
+        format!(
+            "main package to {}",
+            main_package_path.log_color_highlight()
+        ),
+    );
+    fs::write_str(&main_package_path, main_package.to_string())?;
+
+    for package_id in resolve.packages.iter().map(|(package_id, _)| package_id) {
+        if package_id == root_package_id || package_id == main_package_id {
+            continue;
+        }
+
+        let package = encoded_wit_dir.package(package_id)?;
+        let package_path = wit_dir
+            .join(naming::wit::DEPS_DIR)
+            .join(package_dep_dir_name_from_encoder(package.name()))
+            .join(format!("{}.wit", package.name().name()));
+        log_action(
+            "Writing",
+            format!(
+                "package {} to {}",
+                package.name().to_string().log_color_highlight(),
+                package_path.log_color_highlight()
+            ),
+        );
+        fs::write_str(&package_path, package.to_string())?;
+    }
+
+    Ok(())
+}
+
 // TODO: handle world include
 // TODO: handle world use
 // TODO: maybe transform inline interfaces and functions into included world?
@@ -304,6 +304,7 @@ impl ResolvedWitApplication {
         let mut validation = ValidationBuilder::new();
 
         resolved_app.add_components_from_app(&mut validation, app, profile);
+        resolved_app.add_external_components_from_app(app);
 
         resolved_app.validate_package_names(&mut validation);
         resolved_app.collect_component_deps(app, &mut validation);
@@ -363,6 +364,21 @@ impl ResolvedWitApplication {
         self.components.insert(component_name, resolved_component);
     }
 
+    // External components are not resolved, as their WIT is only available after fetching them
+    // from the server, but their client packages are tracked for the dependency graph
+    fn add_external_components_from_app(&mut self, app: &Application) {
+        for (component_name, _) in app.external_components() {
+            if let Some(package_name) =
+                naming::wit::external_component_parser_package_name(component_name.as_str())
+            {
+                self.stub_package_to_component.insert(
+                    naming::wit::client_parser_package_name(&package_name),
+                    component_name.clone(),
+                );
+            }
+        }
+    }
+
     fn add_components_from_app(
         &mut self,
         validation: &mut ValidationBuilder,
@@ -547,10 +563,13 @@ impl ResolvedWitApplication {
                 }
 
                 for dep_component_name in stub_deps {
-                    let dep_package_name = &self
-                        .component(&dep_component_name)
-                        .unwrap()
-                        .main_package_name;
+                    let dep_package_name = &match self.components.get(&dep_component_name) {
+                        Some(dep_component) => dep_component.main_package_name.clone(),
+                        None => naming::wit::external_component_parser_package_name(
+                            dep_component_name.as_str(),
+                        )
+                        .expect("Invalid external component name"),
+                    };
 
                     validation
                         .push_context("referenced package name", dep_package_name.to_string());
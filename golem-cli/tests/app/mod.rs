@@ -613,6 +613,8 @@ impl TestContext {
 
         let output: Output = Command::new(&self.golem_cli_path)
             .args(args)
+            // The output is not a terminal, which would default to JSON
+            .env("GOLEM_FORMAT", "text")
             .current_dir(working_dir)
             .output()
             .unwrap()
@@ -16,13 +16,16 @@
 
 use test_r::test;
 
-use assert2::assert;
+use assert2::{assert, let_assert};
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_wit_dir;
-use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
+use golem_cli::wasm_rpc_stubgen::stub::{
+    ClientBorrowMode, RustDependencyOverride, StubConfig, StubDefinition,
+};
 use golem_cli::wasm_rpc_stubgen::wit_generate::{
-    add_client_as_dependency_to_wit_dir, AddClientAsDepConfig, UpdateCargoToml,
+    add_client_as_dependency_changes, add_client_as_dependency_to_wit_dir, AddClientAsDepConfig,
+    ClientPackageCollision, UpdateCargoToml,
 };
 use golem_cli::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
 use golem_wit::{WASI_POLL_WIT, WASI_WALL_CLOCKS_WIT, WASM_RPC_WIT};
@@ -46,6 +49,7 @@ fn all_wit_types_no_collision() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -66,6 +70,30 @@ fn all_wit_types_no_collision() {
     );
 }
 
+#[test]
+fn all_wit_types_no_changes_after_add() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+
+    let config = || AddClientAsDepConfig {
+        client_wit_root: stub_dir.path().join("wit"),
+        dest_wit_root: dest_dir.path().join("wit"),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
+    };
+
+    let changes = add_client_as_dependency_changes(&config()).unwrap();
+    assert!(!changes.is_empty());
+    assert!(changes
+        .iter()
+        .any(|change| change.old_content.is_some() && change.target.ends_with("caller.wit")));
+
+    add_client_as_dependency_to_wit_dir(config()).unwrap();
+
+    let changes = add_client_as_dependency_changes(&config()).unwrap();
+    assert!(changes.is_empty());
+}
+
 #[test]
 fn all_wit_types_re_add_with_changes() {
     let (source_dir, stub_dir) = init_stub("all-wit-types");
@@ -80,6 +108,7 @@ fn all_wit_types_re_add_with_changes() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -105,6 +134,7 @@ fn all_wit_types_re_add_with_changes() {
         client_wit_root: alternative_stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -139,6 +169,7 @@ fn many_ways_to_export_no_collision() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -177,12 +208,14 @@ fn direct_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -230,12 +263,14 @@ fn direct_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -253,12 +288,14 @@ fn direct_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -310,12 +347,14 @@ fn direct_circular_same_world_name() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -365,18 +404,21 @@ fn indirect_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -439,18 +481,21 @@ fn indirect_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -470,18 +515,21 @@ fn indirect_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -541,6 +589,7 @@ fn self_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
     })
     .unwrap();
 
@@ -555,6 +604,82 @@ fn self_circular() {
     );
 }
 
+#[test]
+fn dep_dir_collision_fails_with_conflicts() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+    let dest_wit_root = dest_dir.path().join("wit");
+    add_colliding_dep_dir(&dest_wit_root, "test_main-client");
+
+    let result = add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+        client_wit_root: stub_dir.path().join("wit"),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Fail,
+    });
+
+    let_assert!(Err(error) = result);
+    let error = format!("{error:#}");
+    assert!(error.contains("test:main-client"));
+    assert!(error.contains("other:collision"));
+}
+
+#[test]
+fn dep_dir_collision_auto_suffix() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+    let stub_wit_root = stub_dir.path().join("wit");
+    let dest_wit_root = dest_dir.path().join("wit");
+    add_colliding_dep_dir(&dest_wit_root, "test_main-client");
+
+    let config = || AddClientAsDepConfig {
+        client_wit_root: stub_wit_root.clone(),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::AutoSuffix,
+    };
+
+    add_client_as_dependency_to_wit_dir(config()).unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+    assert!(dest_wit_root.join("deps/test_main-client-2").is_dir());
+    assert_has_same_wit_package(
+        &PackageName::new("test", "main-client", None),
+        &dest_wit_root,
+        &stub_wit_root,
+    );
+
+    // Re-adding updates the suffixed directory in place
+    let changes = add_client_as_dependency_changes(&config()).unwrap();
+    assert!(changes.is_empty());
+    assert!(!dest_wit_root.join("deps/test_main-client-3").exists());
+}
+
+#[test]
+fn dep_dir_collision_rename() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+    let stub_wit_root = stub_dir.path().join("wit");
+    let dest_wit_root = dest_dir.path().join("wit");
+    add_colliding_dep_dir(&dest_wit_root, "test_main-client");
+
+    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+        client_wit_root: stub_wit_root.clone(),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_collision: ClientPackageCollision::Rename("main-client".to_string()),
+    })
+    .unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+    assert!(dest_wit_root.join("deps/main-client").is_dir());
+    assert_has_same_wit_package(
+        &PackageName::new("test", "main-client", None),
+        &dest_wit_root,
+        &stub_wit_root,
+    );
+}
+
 fn init_stub(name: &str) -> (TempDir, TempDir) {
     let source = TempDir::new().unwrap();
     let canonical_source = source.path().canonicalize().unwrap();
@@ -579,6 +704,8 @@ fn init_stub(name: &str) -> (TempDir, TempDir) {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
     let _ = generate_client_wit_dir(&def).unwrap();
@@ -596,6 +723,8 @@ fn regenerate_stub(stub_dir: &Path, source_wit_root: &Path) {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
     let _ = generate_client_wit_dir(&def).unwrap();
@@ -615,6 +744,16 @@ fn init_caller(name: &str) -> TempDir {
     temp_dir
 }
 
+fn add_colliding_dep_dir(wit_root: &Path, dir_name: &str) {
+    let dir = wit_root.join("deps").join(dir_name);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("collision.wit"),
+        "package other:collision;\n\ninterface api {\n  f: func();\n}\n",
+    )
+    .unwrap();
+}
+
 fn assert_valid_wit_root(wit_root: &Path) {
     ResolvedWitDir::new(wit_root).unwrap();
 }
@@ -0,0 +1,78 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the generated C bindings of the client.
+
+use test_r::test;
+
+use assert2::assert;
+use fs_extra::dir::CopyOptions;
+use golem_cli::model::app::AppComponentName;
+use golem_cli::wasm_rpc_stubgen::commands::generate::generate_c_client;
+use golem_cli::wasm_rpc_stubgen::stub::{
+    ClientBorrowMode, RustDependencyOverride, StubConfig, StubDefinition,
+};
+use std::path::Path;
+use tempfile::{tempdir, TempDir};
+
+test_r::enable!();
+
+#[test]
+fn all_wit_types_c_bindings() {
+    let source_wit_root = init_source("all-wit-types");
+    let target_root = tempdir().unwrap();
+    let c_root = tempdir().unwrap();
+
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: target_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: true,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
+    })
+    .unwrap();
+
+    generate_c_client(&def, c_root.path()).unwrap();
+
+    let header =
+        std::fs::read_to_string(c_root.path().join("wasm_rpc_client_api_caller.h")).unwrap();
+    assert!(header.contains("api_client"));
+    assert!(c_root.path().join("wasm_rpc_client_api_caller.c").exists());
+
+    let makefile = std::fs::read_to_string(c_root.path().join("client.mk")).unwrap();
+    assert!(makefile.contains("WASM_RPC_CLIENT_API_CALLER_OBJS"));
+
+    let example = std::fs::read_to_string(c_root.path().join("example.c")).unwrap();
+    assert!(example.contains("#include \"wasm_rpc_client_api_caller.h\""));
+}
+
+fn init_source(name: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let source = Path::new("test-data/wit").join(name);
+
+    fs_extra::dir::copy(
+        source,
+        temp_dir.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .unwrap();
+
+    temp_dir
+}
@@ -1,8 +1,12 @@
-use crate::stubgen::{cargo_component_build, test_data_path};
+use crate::stubgen::{cargo_component_build, golem_rust_override, test_data_path};
 use assert2::check;
 use fs_extra::dir::CopyOptions;
 use golem_cli::fs;
-use golem_cli::wasm_rpc_stubgen::cargo::regenerate_cargo_package_component;
+use golem_cli::model::app::AppComponentName;
+use golem_cli::wasm_rpc_stubgen::cargo::{
+    generate_client_cargo_toml, regenerate_cargo_package_component,
+};
+use golem_cli::wasm_rpc_stubgen::stub::{ClientBorrowMode, StubConfig, StubDefinition};
 use tempfile::TempDir;
 use test_r::test;
 
@@ -128,3 +132,40 @@ generate_unused_types = true
     check!(cargo_toml.contains("\"test:sub\" = { path = \"wit/deps/sub\" }"));
     check!(cargo_toml.contains("[package.metadata.component.bindings]\nderives = [\"serde::Serialize\", \"serde::Deserialize\"]\ngenerate_unused_types = true"));
 }
+
+#[test]
+fn client_cargo_toml_with_derives() {
+    let source_wit_root = TempDir::new().unwrap();
+    fs_extra::dir::copy(
+        test_data_path().join("wit").join("all-wit-types"),
+        source_wit_root.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .unwrap();
+    let client_root = TempDir::new().unwrap();
+
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: client_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: golem_rust_override(),
+        extract_source_exports_package: true,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec!["serde::Serialize".to_string(), "Hash".to_string()],
+    })
+    .unwrap();
+
+    generate_client_cargo_toml(&def).unwrap();
+
+    let cargo_toml = fs::read_to_string(def.client_cargo_path()).unwrap();
+    println!(">\n{}", cargo_toml);
+    let manifest: toml::Value = toml::from_str(&cargo_toml).unwrap();
+    let derives = &manifest["package"]["metadata"]["component"]["bindings"]["derives"];
+    check!(derives.as_array().unwrap().len() == 2);
+    check!(derives[0].as_str() == Some("serde::Serialize"));
+    check!(manifest["dependencies"].get("serde").is_some());
+}
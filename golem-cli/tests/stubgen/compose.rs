@@ -22,7 +22,7 @@ use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::composition::compose;
 use golem_cli::wasm_rpc_stubgen::commands::dependencies::add_stub_dependency;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_and_build_client;
-use golem_cli::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
+use golem_cli::wasm_rpc_stubgen::stub::{ClientBorrowMode, StubConfig, StubDefinition};
 use golem_cli::wasm_rpc_stubgen::wit_generate::UpdateCargoToml;
 use golem_wasm_ast::component::Component;
 use golem_wasm_ast::DefaultAst;
@@ -55,9 +55,7 @@ async fn compose_with_single_stub() {
     assert_is_component(&component_wasm);
 
     let dest_wasm = caller_dir.path().join("target/result.wasm");
-    compose(&component_wasm, &[stub_wasm], &dest_wasm)
-        .await
-        .unwrap();
+    compose(&component_wasm, &[stub_wasm], &dest_wasm).unwrap();
 }
 
 async fn init_stub(name: &str) -> (TempDir, TempDir, PathBuf) {
@@ -84,9 +82,11 @@ async fn init_stub(name: &str) -> (TempDir, TempDir, PathBuf) {
         seal_cargo_workspace: true,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
-    let wasm_path = generate_and_build_client(&def, false).await.unwrap();
+    let wasm_path = generate_and_build_client(&def, false, false).await.unwrap();
     (source_dir, stub_dir, wasm_path)
 }
 
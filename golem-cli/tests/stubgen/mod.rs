@@ -17,6 +17,7 @@ use std::path::Path;
 use test_r::tag_suite;
 
 mod add_dep;
+mod c;
 mod cargo;
 mod compose;
 mod stub_wasm;
@@ -36,6 +37,8 @@ pub fn golem_rust_override() -> RustDependencyOverride {
     RustDependencyOverride {
         path_override: None,
         version_override: None,
+        git_override: None,
+        git_branch_override: None,
     }
 }
 
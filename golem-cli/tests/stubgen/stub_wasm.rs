@@ -19,7 +19,7 @@ use crate::stubgen::{golem_rust_override, test_data_path};
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_and_build_client;
-use golem_cli::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
+use golem_cli::wasm_rpc_stubgen::stub::{ClientBorrowMode, StubConfig, StubDefinition};
 use golem_wasm_ast::analysis::analysed_type::*;
 use golem_wasm_ast::analysis::{
     AnalysedExport, AnalysedFunctionParameter, AnalysedInstance, AnalysedResourceId,
@@ -55,10 +55,12 @@ async fn all_wit_types() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
 
-    let wasm_path = generate_and_build_client(&def, false).await.unwrap();
+    let wasm_path = generate_and_build_client(&def, false, false).await.unwrap();
 
     let stub_bytes = std::fs::read(wasm_path).unwrap();
     let stub_component = Component::<IgnoreAllButMetadata>::from_bytes(&stub_bytes).unwrap();
@@ -366,10 +368,12 @@ async fn resource() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
 
-    let wasm_path = generate_and_build_client(&def, false).await.unwrap();
+    let wasm_path = generate_and_build_client(&def, false, false).await.unwrap();
 
     let stub_bytes = std::fs::read(wasm_path).unwrap();
     let stub_component = Component::<IgnoreAllButMetadata>::from_bytes(&stub_bytes).unwrap();
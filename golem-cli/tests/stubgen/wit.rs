@@ -19,10 +19,14 @@ use test_r::test;
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_wit_dir;
-use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
+use golem_cli::wasm_rpc_stubgen::stub::{
+    ClientBorrowMode, RustDependencyOverride, StubConfig, StubDefinition,
+};
+use golem_cli::wasm_rpc_stubgen::wit_generate::generate_and_copy_import_mock_wit;
+use golem_cli::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
 use std::path::Path;
 use tempfile::{tempdir, TempDir};
-use wit_parser::{FunctionKind, Resolve, TypeDefKind, TypeOwner};
+use wit_parser::{FunctionKind, Handle, Resolve, Type, TypeDefKind, TypeOwner};
 
 test_r::enable!();
 
@@ -41,6 +45,8 @@ fn all_wit_types() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
     let resolve = generate_client_wit_dir(&def).unwrap().resolve;
@@ -146,6 +152,8 @@ fn many_ways_to_export() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
     })
     .unwrap();
     let resolve = generate_client_wit_dir(&def).unwrap().resolve;
@@ -171,6 +179,139 @@ fn many_ways_to_export() {
     // TODO: add asserts for non-unique types
 }
 
+#[test]
+fn borrowed_resources() {
+    for (borrow_mode, expect_borrow) in [
+        (ClientBorrowMode::Borrow, true),
+        (ClientBorrowMode::OwnOnly, false),
+    ] {
+        let source_wit_root = init_source("borrowed-resources");
+        let target_root = tempdir().unwrap();
+
+        let def = StubDefinition::new(StubConfig {
+            source_wit_root: source_wit_root.path().to_path_buf(),
+            client_root: target_root.path().to_path_buf(),
+            selected_world: None,
+            stub_crate_version: "1.0.0".to_string(),
+            golem_rust_override: RustDependencyOverride::default(),
+            extract_source_exports_package: true,
+            seal_cargo_workspace: false,
+            component_name: AppComponentName::from("test:component"),
+            is_ephemeral: false,
+            borrow_mode,
+        })
+        .unwrap();
+        let resolve = generate_client_wit_dir(&def).unwrap().resolve;
+
+        assert_has_stub_function(&resolve, "api-client", "iface1", "compare", true);
+        assert_has_stub_function(&resolve, "api-client", "resource1", "merge", false);
+
+        assert_stub_function_param_is_borrow(
+            &resolve,
+            "api-client",
+            "[method]iface1.blocking-compare",
+            "a",
+            expect_borrow,
+        );
+        assert_stub_function_param_is_borrow(
+            &resolve,
+            "api-client",
+            "[method]resource1.blocking-merge",
+            "other",
+            expect_borrow,
+        );
+    }
+}
+
+#[test]
+fn unexported_resources() {
+    let source_wit_root = init_source("unexported-resources");
+    let target_root = tempdir().unwrap();
+
+    let result = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: target_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: false,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        borrow_mode: ClientBorrowMode::default(),
+        client_derives: vec![],
+    });
+
+    let Err(error) = result else {
+        panic!("expected stub definition to fail on unexported resources");
+    };
+    let error = error.to_string();
+
+    assert!(error.contains("store.put, parameter value: handle of resource test:main/types.blob"));
+    assert!(error.contains("store.get, result: handle of resource test:main/types.blob"));
+}
+
+#[test]
+fn import_mock() {
+    let source_wit_root = init_source("mocked-imports");
+    let target_root = tempdir().unwrap();
+
+    let source = ResolvedWitDir::new(source_wit_root.path()).unwrap();
+    generate_and_copy_import_mock_wit(&source, None, target_root.path()).unwrap();
+    let resolved = ResolvedWitDir::new(target_root.path()).unwrap();
+    let resolve = resolved.resolve;
+
+    assert_has_package_name(&resolve, "test:main-mock");
+    assert_has_world(&resolve, "api-mock");
+
+    let mock_world_id = resolve
+        .select_world(resolved.package_id, Some("api-mock"))
+        .unwrap();
+    let exports = resolve.worlds[mock_world_id]
+        .exports
+        .values()
+        .filter_map(|item| match item {
+            wit_parser::WorldItem::Interface { id, .. } => resolve.id_of(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Runtime provided interfaces are not mocked
+    assert_eq!(exports, vec!["test:store/store".to_string()]);
+}
+
+fn assert_stub_function_param_is_borrow(
+    resolve: &Resolve,
+    interface_name: &str,
+    function_name: &str,
+    param_name: &str,
+    expect_borrow: bool,
+) {
+    let (_, iface) = resolve
+        .interfaces
+        .iter()
+        .find(|(_iface_id, iface)| iface.name == Some(interface_name.to_string()))
+        .unwrap();
+    let function = iface
+        .functions
+        .get(function_name)
+        .unwrap_or_else(|| panic!("Could not find {function_name} in interface {interface_name}"));
+    let (_, param_type) = function
+        .params
+        .iter()
+        .find(|(name, _)| name == param_name)
+        .unwrap_or_else(|| panic!("Could not find parameter {param_name} for {function_name}"));
+    let Type::Id(param_type_id) = param_type else {
+        panic!("Expected handle type for parameter {param_name} of {function_name}");
+    };
+    let param_type_def = resolve.types.get(*param_type_id).unwrap();
+    match &param_type_def.kind {
+        TypeDefKind::Handle(Handle::Borrow(_)) => assert!(expect_borrow),
+        TypeDefKind::Handle(Handle::Own(_)) => assert!(!expect_borrow),
+        other => panic!("Expected handle type for parameter {param_name}, got: {other:?}"),
+    }
+}
+
 fn assert_has_package_name(resolve: &Resolve, package_name: &str) {
     assert!(resolve
         .packages
@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use crate::model::{
-    ComposableAppGroupName, GuestLanguage, PackageName, TargetExistsResolveDecision,
-    TargetExistsResolveMode, Template, TemplateKind, TemplateMetadata, TemplateName,
-    TemplateParameters,
+    ComposableAppGroupName, GuestLanguage, GuestLanguageTier, PackageName, PluginType,
+    TargetExistsResolveDecision, TargetExistsResolveMode, Template, TemplateKind, TemplateMetadata,
+    TemplateName, TemplateParameters,
 };
 use anyhow::Context;
 use include_dir::{include_dir, Dir, DirEntry};
@@ -70,6 +70,13 @@ fn all_templates() -> Vec<Template> {
     result
 }
 
+/// WASI preview1 adapter of the given tier, for turning preview1 core modules into components
+pub fn wasi_preview1_adapter(tier: &GuestLanguageTier) -> Option<&'static [u8]> {
+    ADAPTERS
+        .get_file(Path::new(tier.name()).join("wasi_snapshot_preview1.wasm"))
+        .map(|file| file.contents())
+}
+
 pub fn all_standalone_templates() -> Vec<Template> {
     all_templates()
         .into_iter()
@@ -77,6 +84,13 @@ pub fn all_standalone_templates() -> Vec<Template> {
         .collect()
 }
 
+pub fn all_plugin_templates() -> Vec<Template> {
+    all_templates()
+        .into_iter()
+        .filter(|template| matches!(template.kind, TemplateKind::Plugin { .. }))
+        .collect()
+}
+
 #[derive(Debug, Default)]
 pub struct ComposableAppTemplate {
     pub common: Option<Template>,
@@ -105,7 +119,7 @@ pub fn all_composable_app_templates(
 
     for template in all_templates() {
         match &template.kind {
-            TemplateKind::Standalone => continue,
+            TemplateKind::Standalone | TemplateKind::Plugin { .. } => continue,
             TemplateKind::ComposableAppCommon { group, .. } => {
                 let common = &mut app_templates(&mut templates, template.language, group).common;
                 if let Some(common) = common {
@@ -180,6 +194,63 @@ pub fn instantiate_template(
     Ok(render_template_instructions(template, parameters))
 }
 
+/// Relative paths of the files created by instantiating the template, the component and
+/// package name placeholders are kept in the paths
+pub fn template_files(template: &Template) -> Vec<PathBuf> {
+    fn collect_dir(dir: &Dir<'_>, template: &Template, target: &Path, files: &mut Vec<PathBuf>) {
+        for entry in dir.entries() {
+            let name = entry.path().file_name().unwrap().to_str().unwrap();
+            if template.exclude.contains(name) || name == "metadata.json" {
+                continue;
+            }
+            let name = name.replace("Cargo.toml._", "Cargo.toml");
+            match entry {
+                DirEntry::Dir(dir) => collect_dir(dir, template, &target.join(name), files),
+                DirEntry::File(_) => files.push(target.join(name)),
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+
+    if let Some(dir) = TEMPLATES.get_dir(&template.template_path) {
+        collect_dir(dir, template, Path::new(""), &mut files);
+    }
+
+    if let Some(adapter_path) = &template.adapter_source {
+        files.push(
+            template
+                .adapter_target
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("adapters"))
+                .join(template.language.tier().name())
+                .join(adapter_path.file_name().unwrap()),
+        );
+    }
+
+    let wit_deps_targets = template
+        .wit_deps_targets
+        .clone()
+        .unwrap_or_else(|| vec![Path::new("wit").join("deps")]);
+    for wit_dep in &template.wit_deps {
+        if let Some(dir) = WIT.get_dir(wit_dep) {
+            for target in &wit_deps_targets {
+                for file in dir.files() {
+                    files.push(
+                        target
+                            .join(wit_dep.file_name().unwrap())
+                            .join(file.path().file_name().unwrap()),
+                    );
+                }
+            }
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    files
+}
+
 pub fn add_component_by_template(
     common_template: Option<&Template>,
     component_template: Option<&Template>,
@@ -493,23 +564,36 @@ fn parse_template(
     let metadata = serde_json::from_slice::<TemplateMetadata>(raw_metadata)
         .expect("Failed to parse metadata JSON");
 
-    let kind = match (metadata.app_common_group, metadata.app_component_group) {
-        (None, None) => TemplateKind::Standalone,
-        (Some(group), None) => TemplateKind::ComposableAppCommon {
+    let kind = match (
+        metadata.app_common_group,
+        metadata.app_component_group,
+        metadata.plugin_type,
+    ) {
+        (None, None, None) => TemplateKind::Standalone,
+        (Some(group), None, None) => TemplateKind::ComposableAppCommon {
             group: group.into(),
             skip_if_exists: metadata.app_common_skip_if_exists.map(PathBuf::from),
         },
-        (None, Some(group)) => TemplateKind::ComposableAppComponent {
+        (None, Some(group), None) => TemplateKind::ComposableAppComponent {
             group: group.into(),
         },
-        (Some(_), Some(_)) => panic!(
-            "Only one of appCommonGroup and appComponentGroup can be specified, template root: {}",
+        (None, None, Some(plugin_type)) => TemplateKind::Plugin {
+            plugin_type: PluginType::from_string(&plugin_type).unwrap_or_else(|| {
+                panic!(
+                    "Invalid plugin type: {}, template root: {}",
+                    plugin_type,
+                    template_root.display()
+                )
+            }),
+        },
+        _ => panic!(
+            "Only one of appCommonGroup, appComponentGroup and pluginType can be specified, template root: {}",
             template_root.display()
         ),
     };
 
     let instructions = match &kind {
-        TemplateKind::Standalone => {
+        TemplateKind::Standalone | TemplateKind::Plugin { .. } => {
             let instructions_path = match metadata.instructions {
                 Some(instructions_file_name) => lang_path.join(instructions_file_name),
                 None => lang_path.join(default_instructions_file_name),
@@ -609,3 +693,118 @@ fn parse_template(
         transform: metadata.transform.unwrap_or(true),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::model::{PluginType, TargetExistsResolveMode, TemplateKind, TemplateParameters};
+    use crate::{all_plugin_templates, instantiate_template, template_files};
+    use assert2::{check, let_assert};
+    use strum::IntoEnumIterator;
+    use tempfile::TempDir;
+
+    #[test]
+    fn plugin_types_from_string() {
+        check!(
+            PluginType::from_string("component-transformer")
+                == Some(PluginType::ComponentTransformer)
+        );
+        check!(PluginType::from_string("Transformer") == Some(PluginType::ComponentTransformer));
+        check!(PluginType::from_string("oplog-processor").is_none());
+
+        for plugin_type in PluginType::iter() {
+            check!(plugin_type.id().parse::<PluginType>() == Ok(plugin_type));
+        }
+    }
+
+    #[test]
+    fn plugin_templates_are_found() {
+        let templates = all_plugin_templates();
+
+        for template in &templates {
+            check!(matches!(template.kind, TemplateKind::Plugin { .. }));
+        }
+
+        for plugin_type in PluginType::iter() {
+            check!(
+                templates
+                    .iter()
+                    .any(|template| template.kind == TemplateKind::Plugin { plugin_type }),
+                "No template was found for plugin type {plugin_type}"
+            );
+        }
+
+        let rust_transformer = templates
+            .iter()
+            .find(|template| template.name.as_str() == "rust-plugin-component-transformer");
+        let_assert!(Some(rust_transformer) = rust_transformer);
+        check!(!rust_transformer.instructions.is_empty());
+    }
+
+    #[test]
+    fn instantiate_plugin_templates() {
+        for template in all_plugin_templates() {
+            let temp_dir = TempDir::new().unwrap();
+            let parameters = TemplateParameters {
+                component_name: "my-plugin".into(),
+                package_name: "my:plugin".parse().unwrap(),
+                target_path: temp_dir.path().join("my-plugin"),
+            };
+
+            let instructions =
+                instantiate_template(&template, &parameters, TargetExistsResolveMode::Fail);
+            let_assert!(Ok(instructions) = instructions);
+            check!(!instructions.is_empty());
+
+            for file in template_files(&template) {
+                check!(
+                    parameters.target_path.join(&file).is_file(),
+                    "Missing file {} for template {}",
+                    file.display(),
+                    template.name
+                );
+            }
+            check!(!parameters.target_path.join("metadata.json").exists());
+        }
+    }
+
+    #[test]
+    fn instantiate_rust_component_transformer_plugin_template() {
+        let template = all_plugin_templates()
+            .into_iter()
+            .find(|template| template.name.as_str() == "rust-plugin-component-transformer");
+        let_assert!(Some(template) = template);
+
+        let temp_dir = TempDir::new().unwrap();
+        let target_path = temp_dir.path().join("my-plugin");
+        let parameters = TemplateParameters {
+            component_name: "my-plugin".into(),
+            package_name: "my:plugin".parse().unwrap(),
+            target_path: target_path.clone(),
+        };
+
+        let_assert!(
+            Ok(_) = instantiate_template(&template, &parameters, TargetExistsResolveMode::Fail)
+        );
+
+        for file in [
+            ".gitignore",
+            "Cargo.toml",
+            "golem-plugin.yaml",
+            "icon.svg",
+            "src/main.rs",
+        ] {
+            check!(target_path.join(file).is_file(), "Missing file {file}");
+        }
+        check!(!target_path.join("Cargo.toml._").exists());
+
+        let cargo_toml = std::fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        check!(cargo_toml.contains("name = \"my-plugin\""));
+
+        let plugin_manifest =
+            std::fs::read_to_string(target_path.join("golem-plugin.yaml")).unwrap();
+        check!(plugin_manifest.contains("name: my-plugin"));
+        check!(plugin_manifest.contains("type: ComponentTransformer"));
+    }
+}
@@ -105,6 +105,9 @@ pub enum TemplateKind {
     ComposableAppComponent {
         group: ComposableAppGroupName,
     },
+    Plugin {
+        plugin_type: PluginType,
+    },
 }
 
 #[derive(
@@ -201,6 +204,48 @@ impl FromStr for GuestLanguage {
     }
 }
 
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize,
+)]
+pub enum PluginType {
+    ComponentTransformer,
+}
+
+impl PluginType {
+    pub fn from_string(s: impl AsRef<str>) -> Option<PluginType> {
+        match s.as_ref().to_lowercase().as_str() {
+            "component-transformer" | "transformer" => Some(PluginType::ComponentTransformer),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            PluginType::ComponentTransformer => "component-transformer",
+        }
+    }
+}
+
+impl fmt::Display for PluginType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for PluginType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PluginType::from_string(s).ok_or({
+            let all = PluginType::iter()
+                .map(|x| format!("\"{x}\""))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("Unknown plugin type: {s}. Expected one of {all}")
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize)]
 pub enum GuestLanguageTier {
     Tier1,
@@ -442,6 +487,8 @@ pub(crate) struct TemplateMetadata {
     pub app_common_skip_if_exists: Option<String>,
     #[serde(rename = "appComponentGroup")]
     pub app_component_group: Option<String>,
+    #[serde(rename = "pluginType")]
+    pub plugin_type: Option<String>,
     #[serde(rename = "requiresAdapter")]
     pub requires_adapter: Option<bool>,
     #[serde(rename = "adapterTarget")]
@@ -0,0 +1,90 @@
+use axum::extract::{DefaultBodyLimit, Multipart};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use serde_json::Value;
+use wasm_metadata::Producers;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let port = std::env::var("PORT").unwrap_or_else(|_| "9000".to_string());
+
+    let app = Router::new()
+        .route("/validate", post(validate))
+        .route("/transform", post(transform))
+        .layer(DefaultBodyLimit::disable());
+
+    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}")).await?;
+    println!("Listening on {}", listener.local_addr()?);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+struct TransformRequest {
+    component: Vec<u8>,
+    configuration: Value,
+}
+
+async fn validate(multipart: Multipart) -> Result<StatusCode, (StatusCode, String)> {
+    let request = read_request(multipart).await?;
+    validate_configuration(&request.configuration).map_err(bad_request)?;
+    Ok(StatusCode::OK)
+}
+
+async fn transform(multipart: Multipart) -> Result<Vec<u8>, (StatusCode, String)> {
+    let request = read_request(multipart).await?;
+    validate_configuration(&request.configuration).map_err(bad_request)?;
+    transform_component(&request.component, &request.configuration).map_err(internal_error)
+}
+
+async fn read_request(mut multipart: Multipart) -> Result<TransformRequest, (StatusCode, String)> {
+    let mut component = Vec::new();
+    let mut configuration = Value::Null;
+
+    while let Some(field) = multipart.next_field().await.map_err(bad_request)? {
+        match field.name() {
+            Some("component") => {
+                component = field.bytes().await.map_err(bad_request)?.to_vec();
+            }
+            Some("configuration") => {
+                let bytes = field.bytes().await.map_err(bad_request)?;
+                configuration = serde_json::from_slice(&bytes).map_err(bad_request)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(TransformRequest {
+        component,
+        configuration,
+    })
+}
+
+fn validate_configuration(configuration: &Value) -> anyhow::Result<()> {
+    match configuration.get("tag") {
+        None | Some(Value::String(_)) => Ok(()),
+        Some(_) => anyhow::bail!("Expected string value for the 'tag' parameter"),
+    }
+}
+
+// Example transformation: records the plugin in the producers section of the component.
+// Replace this with the real transformation logic.
+fn transform_component(component: &[u8], configuration: &Value) -> anyhow::Result<Vec<u8>> {
+    let tag = configuration
+        .get("tag")
+        .and_then(|tag| tag.as_str())
+        .unwrap_or(env!("CARGO_PKG_VERSION"));
+
+    let mut producers = Producers::empty();
+    producers.add("processed-by", env!("CARGO_PKG_NAME"), tag);
+    producers.add_to_wasm(component)
+}
+
+fn bad_request(err: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, err.to_string())
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}